@@ -3,36 +3,68 @@ use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent,
 use ratatui::prelude::*;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::mpsc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::config::{Action, Config};
-use crate::db::{Database, ScheduledTaskType, SimilarityGroup};
+use chrono::{Datelike, NaiveDate};
+
+use crate::config::{Action, Config, DatabaseConfig, ImageProtocol, PreviewMode, SortOrder};
+use crate::db::{
+    parse_search_query, AlbumSort, Database, DirectoryPhotoStats, GeoRadius, ScheduledTaskType, SearchAst, SearchFilter, SearchScope, SimilarityGroup, UserTag,
+};
 use crate::llm::LlmClient;
-use crate::scanner::{detect_changes, ChangeDetectionResult, Scanner};
+use crate::scanner::{detect_changes, diff_metadata, ChangeDetectionResult, Scanner, ThumbnailManager};
+use crate::history::{outcome_message, OperationHistory, ReversibleOperation, TrashedFile};
+use crate::scanner::adb::{AdbDevice, AdbScanner};
+use crate::scanner::quality::assess_quality;
 use crate::schedule::ScheduleManager;
-use crate::tasks::{BackgroundTaskManager, TaskType, TaskUpdate};
+use crate::session::{SessionState, SessionView};
+use crate::share::{self, SharedPhoto};
+use crate::tasks::{BackgroundTaskManager, TaskCompletionInfo, TaskProgress, TaskType, TaskUpdate};
+use crate::transfer::{self, BatchOutcome, TransferProgress};
 use crate::trash::TrashManager;
 use crate::ui;
-use crate::ui::changes_dialog::ChangesDialog;
-use crate::ui::duplicates::DuplicatesView;
+use crate::ui::changes_dialog::{ChangesDialog, MetadataDiff};
+use crate::ui::duplicates::{DuplicatesView, DuplicatesViewMode};
 use crate::ui::export_dialog::ExportDialog;
 use crate::ui::move_dialog::MoveDialog;
+use crate::ui::adb_dialog::{AdbImportDialog, AdbImportStep, AdbPreview};
+use crate::ui::batch_progress::BatchProgressDialog;
+use crate::ui::conflict_dialog::{find_conflicts, ConflictDialog, Resolution};
+use crate::ui::cross_dedup_dialog::CrossDirDedupDialog;
 use crate::ui::overdue_dialog::OverdueDialog;
 use crate::ui::preview::ImagePreviewState;
 use crate::ui::rename_dialog::RenameDialog;
 use crate::ui::schedule_dialog::ScheduleDialog;
-use crate::ui::search_dialog::SearchDialog;
-use crate::ui::people_dialog::PeopleDialog;
+use crate::ui::share_dialog::ShareDialog;
+use crate::ui::search_dialog::{SearchDialog, SearchDialogMode, FACE_LISTING_LIMIT, SEARCH_PAGE_SIZE};
+use crate::ui::people::{PeoplePrompt, PeopleView, PersonCard, CARD_FACES};
+use crate::ui::people_dialog::{FaceEntry, PeopleDialog, PeopleViewMode};
 use crate::ui::trash_dialog::TrashDialog;
 use crate::ui::edit_dialog::EditDescriptionDialog;
-use crate::ui::gallery::GalleryView;
+use crate::ui::gps_dialog::GpsDialog;
+use crate::ui::camera_dialog::CameraDialog;
+use crate::ui::expanded_field_dialog::ExpandedFieldDialog;
+use crate::ui::quick_filter::QuickFilter;
+use crate::ui::rescan_dialog::RescanDialog;
+use crate::ui::tag_browser::{TagBrowser, TagBrowserMode};
+use crate::ui::prompt_manager::{PromptManager, PromptManagerMode};
+use crate::ui::albums::{AlbumEntry, AlbumPrompt, AlbumsView};
+use crate::ui::calendar::CalendarView;
+use crate::ui::locations::{cluster_label, LocationsView};
+use crate::ui::date_filter_dialog::DateFilterDialog;
+use crate::ui::gallery::{GallerySort, GalleryView};
 use crate::ui::tag_dialog::{TagDialog, TagDialogMode};
+use crate::ui::batch_tag_dialog::{BatchTagChange, BatchTagDialog};
 use crate::ui::slideshow::SlideshowView;
 use crate::ui::centralise_dialog::{CentraliseDialog, CentraliseDialogMode};
 use crate::ui::confirm_dialog::ConfirmDialog;
+use crate::ui::theme::Theme;
+use crate::ui::theme_dialog::ThemeDialog;
+use crate::ui::warmup_dialog::WarmupDialog;
+use crate::watch::{merge_change, DirWatcher, WatchChange};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -48,8 +80,10 @@ pub enum AppMode {
     Help,
     Duplicates,
     DuplicatesHelp,
+    CrossDirDedup,
     Visual,
     Moving,
+    ConflictResolving,
     Renaming,
     Exporting,
     Searching,
@@ -60,14 +94,47 @@ pub enum AppMode {
     Scheduling,
     OverdueDialog,
     EditingDescription,
+    EditingGPS,
     Gallery,
     GalleryHelp,
     Tagging,
+    BatchTagging,
     Slideshow,
     SlideshowHelp,
     Centralising,
     Confirming,
     Settings,
+    ThemePicking,
+    Breadcrumb,
+    QuickFilter,
+    DateFilter,
+    CameraFilter,
+    ExpandedField,
+    RescanMissing,
+    TagBrowser,
+    PromptManager,
+    Calendar,
+    Albums,
+    People,
+    Locations,
+    ThumbnailWarmup,
+    BatchProgress,
+    AdbImport,
+    Watching,
+    Sharing,
+}
+
+/// Directory and LLM prompt for a queued Scan → LLM → Faces pipeline
+#[derive(Debug, Clone)]
+struct Pipeline {
+    directory: PathBuf,
+    prompt: Option<String>,
+}
+
+/// A running auto-cull and the channel its poor quality photos arrive on
+struct PendingAutoCull {
+    task_id: crate::tasks::TaskId,
+    rx: mpsc::Receiver<Vec<(i64, PathBuf)>>,
 }
 
 #[allow(dead_code)]
@@ -86,10 +153,18 @@ pub struct App {
     pub should_quit: bool,
     pub status_message: Option<String>,
     pub g_pressed: bool,
+    /// Breadcrumb segment under the mouse
+    pub breadcrumb_hover: Option<usize>,
+    /// Focused breadcrumb segment while in breadcrumb mode
+    pub breadcrumb_cursor: Option<usize>,
+    /// Files being dragged with the mouse
+    pub drag: Option<DragState>,
     // Duplicates view
     pub duplicates_view: Option<DuplicatesView>,
     // Receiver for background duplicate detection results
     pending_duplicates: Option<mpsc::Receiver<Vec<SimilarityGroup>>>,
+    /// Pipeline whose tasks are still queued
+    pipeline: Option<Pipeline>,
     // LLM state
     pub llm_client: LlmClient,
     pub llm_descriptions: HashMap<PathBuf, String>,
@@ -101,6 +176,10 @@ pub struct App {
     pub visual_anchor: Option<usize>,
     // Move dialog state
     pub move_dialog: Option<MoveDialog>,
+    pub conflict_dialog: Option<ConflictDialog>,
+    // Undo/redo for moves, renames and trashing
+    pub operation_history: OperationHistory,
+    pub cross_dedup_dialog: Option<CrossDirDedupDialog>,
     // Rename dialog state
     pub rename_dialog: Option<RenameDialog>,
     // Export dialog state
@@ -125,12 +204,21 @@ pub struct App {
     pub overdue_dialog: Option<OverdueDialog>,
     // Clipboard for cut/paste operations
     pub clipboard: Vec<PathBuf>,
+    /// Report file operations instead of performing them (`--dry-run`)
+    pub dry_run: bool,
+    /// Config file given with `--config`, reloaded by `reload_config`
+    pub config_file: Option<PathBuf>,
     // Edit description dialog
     pub edit_dialog: Option<EditDescriptionDialog>,
+    pub gps_dialog: Option<GpsDialog>,
     // Gallery view
     pub gallery_view: Option<GalleryView>,
     // Tag dialog
     pub tag_dialog: Option<TagDialog>,
+    pub batch_tag_dialog: Option<BatchTagDialog>,
+    // Watch mode
+    pub dir_watcher: Option<DirWatcher>,
+    pub watch_changes: HashMap<PathBuf, WatchChange>,
     // Slideshow view
     pub slideshow_view: Option<SlideshowView>,
     // Centralise dialog
@@ -139,34 +227,182 @@ pub struct App {
     pub confirm_dialog: Option<ConfirmDialog>,
     // Settings dialog
     pub settings_dialog: Option<crate::ui::settings_dialog::SettingsDialog>,
+    // Active color theme and theme picker
+    pub theme: Theme,
+    pub theme_dialog: Option<ThemeDialog>,
     // Action map for configurable keybindings
     pub action_map: HashMap<(KeyCode, KeyModifiers), Action>,
     // View filters
     pub show_hidden: bool,
     pub show_all_files: bool,
+    pub sort_order: SortOrder,
+    /// Only show files taken within this range (inclusive)
+    pub date_filter: Option<(NaiveDate, NaiveDate)>,
+    pub date_filter_dialog: Option<DateFilterDialog>,
+    /// "Make Model" label the browser is restricted to
+    pub camera_filter: Option<String>,
+    pub camera_dialog: Option<CameraDialog>,
+    pub expanded_field_dialog: Option<ExpandedFieldDialog>,
+    /// Fuzzy file name filter over the current directory, while typing
+    pub quick_filter: Option<QuickFilter>,
+    pub rescan_dialog: Option<RescanDialog>,
+    /// Tag the browser is restricted to
+    pub tag_filter: Option<UserTag>,
+    /// Label and photos of the location cluster the browser is restricted to
+    pub location_filter: Option<(String, HashSet<PathBuf>)>,
+    pub tag_browser: Option<TagBrowser>,
+    pub prompt_manager: Option<PromptManager>,
+    pub calendar: Option<CalendarView>,
+    pub albums_view: Option<AlbumsView>,
+    pub people_view: Option<PeopleView>,
+    pub locations_view: Option<LocationsView>,
+    pub thumbnail_warmup: Option<WarmupDialog>,
+    pub batch_progress: Option<BatchProgressDialog>,
+    pub adb_import: Option<AdbImportDialog>,
+    auto_cull: Option<PendingAutoCull>,
+    pub share_dialog: Option<ShareDialog>,
+    // Stored CLIP embeddings came from a different model than the current one,
+    // so embedding generation overwrites them instead of skipping
+    clip_model_changed: bool,
     // Flag to trigger full screen clear on next render
     // Used when transitioning from views with terminal graphics (gallery/slideshow)
     pub clear_on_next_render: bool,
+    // Last session snapshot written to disk and when it was written
+    saved_session: Option<SessionState>,
+    last_session_save: Option<Instant>,
+    // Pane ratios were adjusted at runtime and should be saved on exit
+    pane_ratios_changed: bool,
+    // Folder statistics for directory previews, cached per path
+    pub dir_stats: HashMap<PathBuf, DirStats>,
+    // Directory waiting out the hover debounce, and when it was selected
+    dir_stats_pending: Option<(PathBuf, Instant)>,
+    dir_stats_loading: HashSet<PathBuf>,
+    // Directories to count, sent to a worker that keeps one connection open
+    dir_stats_tx: mpsc::Sender<PathBuf>,
+    dir_stats_rx: mpsc::Receiver<(PathBuf, DirStats)>,
+    // Recursive sizes of directories shown in the browser panes
+    pub dir_size_cache: HashMap<PathBuf, u64>,
+    dir_size_loading: HashSet<PathBuf>,
+    dir_size_tx: mpsc::Sender<(PathBuf, u64)>,
+    dir_size_rx: mpsc::Receiver<(PathBuf, u64)>,
+    // Rows visible in the browser panes at the last render
+    pub browser_rows: usize,
+}
+
+/// Files being dragged from the current pane to a directory
+#[derive(Debug, Clone)]
+pub struct DragState {
+    pub files: Vec<PathBuf>,
+    /// Mouse position (column, row)
+    pub position: (u16, u16),
+    /// Set once the mouse moves with the button held, so a click is not a drop
+    pub active: bool,
+    /// Directory under the cursor that would receive the files
+    pub target: Option<PathBuf>,
+}
+
+/// Folder statistics shown in the preview pane for a directory
+#[derive(Debug, Clone, Default)]
+pub struct DirStats {
+    pub subdirectories: usize,
+    pub photos: DirectoryPhotoStats,
+}
+
+/// Sort directory entries: directories first by name, then files in
+/// `order`. Ties and files without a capture time fall back to name order,
+/// with missing capture times last.
+fn sort_entries(entries: &mut [DirEntry], order: SortOrder, taken_at: &HashMap<PathBuf, String>) {
+    use std::cmp::Ordering;
+
+    entries.sort_by(|a, b| {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        match (a.is_dir, b.is_dir) {
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (true, true) => return by_name(),
+            (false, false) => {}
+        }
+        let primary = match order {
+            SortOrder::Name => Ordering::Equal,
+            SortOrder::DateTaken => match (taken_at.get(&a.path), taken_at.get(&b.path)) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortOrder::Size => a.size.cmp(&b.size),
+            SortOrder::DateModified => a.modified.cmp(&b.modified),
+        };
+        primary.then_with(by_name)
+    });
+}
+
+/// Count subdirectories and photos for each requested directory on one
+/// background thread. The database is opened on the first request and kept
+/// for the rest of the session.
+fn spawn_dir_stats_worker(
+    db_config: DatabaseConfig,
+) -> (mpsc::Sender<PathBuf>, mpsc::Receiver<(PathBuf, DirStats)>) {
+    let (request_tx, request_rx) = mpsc::channel::<PathBuf>();
+    let (result_tx, result_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut db = None;
+        for path in request_rx {
+            let subdirectories = std::fs::read_dir(&path)
+                .map(|dir| {
+                    dir.filter_map(|e| e.ok())
+                        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                        .count()
+                })
+                .unwrap_or(0);
+            if db.is_none() {
+                db = Database::open_read_only(&db_config).ok();
+            }
+            let photos = db
+                .as_ref()
+                .and_then(|db| db.count_photos_in_dir_recursive(&path.to_string_lossy()).ok())
+                .unwrap_or_default();
+            if result_tx.send((path, DirStats { subdirectories, photos })).is_err() {
+                break;
+            }
+        }
+    });
+    (request_tx, result_rx)
 }
 
+/// How long a directory must stay selected before its statistics are loaded.
+const DIR_STATS_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Minimum interval between session file writes.
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `app_state` key holding the JSON list of directories whose thumbnails
+/// have all been generated, so opening their gallery skips the warm-up.
+const WARMED_THUMBNAIL_DIRS_KEY: &str = "warmed_thumbnail_dirs";
+
 #[derive(Debug, Clone)]
 pub struct DirEntry {
     pub name: String,
     pub path: PathBuf,
     pub is_dir: bool,
     pub size: u64,
+    pub modified: Option<std::time::SystemTime>,
 }
 
 impl App {
     pub fn new(config: Config, db: Database) -> Result<Self> {
         let current_dir = std::env::current_dir()?;
+        let (dir_stats_tx, dir_stats_rx) = spawn_dir_stats_worker(config.database.clone());
+        let (dir_size_tx, dir_size_rx) = mpsc::channel();
         let llm_client = LlmClient::from_config(&config.llm);
-        let image_preview = ImagePreviewState::new(config.preview.protocol, &config.thumbnails);
+        let image_preview = ImagePreviewState::new(&config.preview, &config.thumbnails);
         let trash_manager = TrashManager::new(config.trash.clone());
         let duplicate_trash_manager = TrashManager::new_from_duplicate_config(config.duplicate_trash.clone());
         let action_map = config.keybindings.build_action_map();
+        let theme = Theme::from_config(&config.theme);
         // Extract view settings before moving config
         let show_hidden = config.view.show_hidden;
+        let sort_order = config.view.sort_order;
         let show_all_files = config.view.show_all_files;
         let mut app = Self {
             config,
@@ -183,14 +419,21 @@ impl App {
             should_quit: false,
             status_message: None,
             g_pressed: false,
+            breadcrumb_hover: None,
+            breadcrumb_cursor: None,
+            drag: None,
             duplicates_view: None,
             pending_duplicates: None,
+            pipeline: None,
             llm_client,
             llm_descriptions: HashMap::new(),
             image_preview,
             selected_files: HashSet::new(),
             visual_anchor: None,
             move_dialog: None,
+            conflict_dialog: None,
+            operation_history: OperationHistory::default(),
+            cross_dedup_dialog: None,
             rename_dialog: None,
             export_dialog: None,
             search_dialog: None,
@@ -205,20 +448,71 @@ impl App {
             schedule_dialog: None,
             overdue_dialog: None,
             clipboard: Vec::new(),
+            dry_run: false,
+            config_file: None,
             edit_dialog: None,
+            gps_dialog: None,
             gallery_view: None,
             tag_dialog: None,
+            batch_tag_dialog: None,
+            dir_watcher: None,
+            watch_changes: HashMap::new(),
             slideshow_view: None,
             centralise_dialog: None,
             confirm_dialog: None,
             settings_dialog: None,
+            theme,
+            theme_dialog: None,
             action_map,
             show_hidden,
             show_all_files,
+            sort_order,
+            date_filter: None,
+            date_filter_dialog: None,
+            camera_filter: None,
+            camera_dialog: None,
+            expanded_field_dialog: None,
+            quick_filter: None,
+            rescan_dialog: None,
+            tag_filter: None,
+            location_filter: None,
+            tag_browser: None,
+            prompt_manager: None,
+            calendar: None,
+            albums_view: None,
+            people_view: None,
+            locations_view: None,
+            thumbnail_warmup: None,
+            batch_progress: None,
+            adb_import: None,
+            auto_cull: None,
+            share_dialog: None,
+            clip_model_changed: false,
             clear_on_next_render: false,
+            saved_session: None,
+            last_session_save: None,
+            pane_ratios_changed: false,
+            dir_stats: HashMap::new(),
+            dir_stats_pending: None,
+            dir_stats_loading: HashSet::new(),
+            dir_stats_tx,
+            dir_stats_rx,
+            dir_size_cache: HashMap::new(),
+            dir_size_loading: HashSet::new(),
+            dir_size_tx,
+            dir_size_rx,
+            browser_rows: 0,
         };
         app.load_directory(&current_dir)?;
 
+        if app.config.ui.restore_session {
+            if let Some(state) = SessionState::load() {
+                app.restore_session(state);
+            }
+        }
+
+        app.check_clip_model();
+
         // Check for overdue schedules on startup
         if app.config.schedule.check_overdue_on_startup {
             let overdue = app.schedule_manager.check_overdue(&app.db);
@@ -233,6 +527,7 @@ impl App {
 
     pub fn load_directory(&mut self, path: &PathBuf) -> Result<()> {
         self.current_dir = path.clone();
+        self.quick_filter = None;
         self.entries = self.read_directory(path)?;
         self.selected_index = 0;
         self.scroll_offset = 0;
@@ -267,7 +562,7 @@ impl App {
         let result = detect_changes(
             &self.current_dir,
             &self.db,
-            &self.config.scanner.image_extensions,
+            &self.config.scanner.extensions(),
         );
 
         match result {
@@ -280,12 +575,197 @@ impl App {
         }
     }
 
+    /// Compare the current CLIP model with the one that produced the most
+    /// recent stored embeddings, and drop the old model's embeddings since
+    /// they can't be compared with new queries.
+    fn check_clip_model(&mut self) {
+        use crate::clip::ClipModel;
+
+        if let Ok(Some(stored)) = self.db.get_latest_clip_model() {
+            if stored != ClipModel::model_version() {
+                match self.db.delete_embeddings_for_model(&stored) {
+                    Ok(removed) => {
+                        self.status_message = Some(format!(
+                            "CLIP model changed — removed {} old embeddings, they should be regenerated",
+                            removed
+                        ));
+                    }
+                    Err(e) => {
+                        // Keep the old embeddings but overwrite them as photos are re-embedded
+                        tracing::warn!(model = %stored, error = %e, "Failed to remove old CLIP embeddings");
+                        self.clip_model_changed = true;
+                        self.status_message = Some("CLIP model changed — embeddings should be regenerated".to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Restore directory, selection and view from a saved session.
+    /// Sessions pointing at a directory that is no longer accessible are ignored.
+    fn restore_session(&mut self, state: SessionState) {
+        if !state.current_dir.is_dir() || self.load_directory(&state.current_dir).is_err() {
+            return;
+        }
+
+        if !self.entries.is_empty() {
+            self.selected_index = state.selected_index.min(self.entries.len() - 1);
+        }
+        let known: HashSet<&PathBuf> = self.entries.iter().map(|e| &e.path).collect();
+        self.selected_files = state
+            .selected_files
+            .into_iter()
+            .filter(|p| known.contains(p))
+            .collect();
+
+        let _ = match state.view {
+            SessionView::Browser => Ok(()),
+            SessionView::Gallery => self.open_gallery_view(),
+            SessionView::Slideshow => self.open_slideshow(),
+        };
+    }
+
+    /// Snapshot of the current browser state for the session file.
+    fn session_state(&self) -> SessionState {
+        let mut selected_files: Vec<PathBuf> = self.selected_files.iter().cloned().collect();
+        selected_files.sort();
+        let view = match self.mode {
+            AppMode::Gallery | AppMode::GalleryHelp => SessionView::Gallery,
+            AppMode::Slideshow | AppMode::SlideshowHelp => SessionView::Slideshow,
+            _ => SessionView::Browser,
+        };
+
+        SessionState {
+            current_dir: self.current_dir.clone(),
+            selected_index: self.selected_index,
+            selected_files,
+            view,
+        }
+    }
+
+    /// Write the session file in the background if the state changed,
+    /// at most once per `SESSION_SAVE_INTERVAL`.
+    fn save_session_debounced(&mut self) {
+        if !self.config.ui.restore_session {
+            return;
+        }
+        if self
+            .last_session_save
+            .is_some_and(|t| t.elapsed() < SESSION_SAVE_INTERVAL)
+        {
+            return;
+        }
+
+        let state = self.session_state();
+        if self.saved_session.as_ref() == Some(&state) {
+            return;
+        }
+
+        self.saved_session = Some(state.clone());
+        self.last_session_save = Some(Instant::now());
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = state.save() {
+                tracing::warn!(error = %e, "Failed to save session");
+            }
+        });
+    }
+
+    /// Load statistics for the selected directory once it has stayed
+    /// selected for `DIR_STATS_DEBOUNCE`, and collect finished lookups.
+    fn poll_dir_stats(&mut self) {
+        while let Ok((path, stats)) = self.dir_stats_rx.try_recv() {
+            self.dir_stats_loading.remove(&path);
+            self.dir_stats.insert(path, stats);
+        }
+
+        let path = match self.selected_entry() {
+            Some(e) if e.is_dir => e.path.clone(),
+            _ => {
+                self.dir_stats_pending = None;
+                return;
+            }
+        };
+        if self.dir_stats.contains_key(&path) || self.dir_stats_loading.contains(&path) {
+            return;
+        }
+
+        match self.dir_stats_pending {
+            Some((ref pending, since)) if *pending == path => {
+                if since.elapsed() < DIR_STATS_DEBOUNCE {
+                    return;
+                }
+            }
+            _ => {
+                self.dir_stats_pending = Some((path, Instant::now()));
+                return;
+            }
+        }
+
+        self.dir_stats_pending = None;
+        self.dir_stats_loading.insert(path.clone());
+        let _ = self.dir_stats_tx.send(path);
+    }
+
+    /// Compute recursive sizes for directories visible in the parent and
+    /// current panes, one background walk at a time.
+    fn poll_dir_sizes(&mut self) {
+        while let Ok((path, size)) = self.dir_size_rx.try_recv() {
+            self.dir_size_loading.remove(&path);
+            self.dir_size_cache.insert(path, size);
+        }
+
+        if self.browser_rows == 0 || self.task_manager.is_running(TaskType::DirSize) {
+            return;
+        }
+
+        let visible = |entries: &[DirEntry], selected: usize| -> Vec<PathBuf> {
+            // Same window ratatui's List shows: from the top until the
+            // selection would scroll past the bottom row
+            let start = (selected + 1).saturating_sub(self.browser_rows);
+            entries
+                .iter()
+                .skip(start)
+                .take(self.browser_rows)
+                .filter(|e| e.is_dir)
+                .map(|e| e.path.clone())
+                .collect()
+        };
+        let mut dirs = visible(&self.parent_entries, self.parent_selected_index);
+        dirs.extend(visible(&self.entries, self.selected_index));
+        dirs.retain(|d| !self.dir_size_cache.contains_key(d) && !self.dir_size_loading.contains(d));
+        dirs.dedup();
+        if dirs.is_empty() {
+            return;
+        }
+
+        self.dir_size_loading.extend(dirs.iter().cloned());
+        let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::DirSize);
+        let size_tx = self.dir_size_tx.clone();
+        std::thread::spawn(move || {
+            let total = dirs.len();
+            let _ = tx.send(TaskUpdate::Started { total });
+            for (i, dir) in dirs.into_iter().enumerate() {
+                if cancel_flag.load(Ordering::SeqCst) {
+                    let _ = tx.send(TaskUpdate::Cancelled);
+                    return;
+                }
+                let size = walkdir::WalkDir::new(&dir)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_file())
+                    .filter_map(|e| e.metadata().ok())
+                    .map(|m| m.len())
+                    .sum();
+                let _ = size_tx.send((dir, size));
+                let _ = tx.send(TaskUpdate::Progress(TaskProgress::new(i + 1, total)));
+            }
+            let _ = tx.send(TaskUpdate::Completed { message: String::new() });
+        });
+    }
+
     fn read_directory(&self, path: &PathBuf) -> Result<Vec<DirEntry>> {
         let mut entries = Vec::new();
-        let supported_extensions: Vec<String> = self.config.scanner.image_extensions
-            .iter()
-            .map(|e| e.to_lowercase())
-            .collect();
+        let supported_extensions = self.config.scanner.extensions();
 
         if let Ok(read_dir) = std::fs::read_dir(path) {
             for entry in read_dir.flatten() {
@@ -293,6 +773,7 @@ impl App {
                 let metadata = entry.metadata().ok();
                 let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
                 let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+                let modified = metadata.as_ref().and_then(|m| m.modified().ok());
 
                 // Filter hidden files/directories (starting with .)
                 if !self.show_hidden && name.starts_with('.') {
@@ -315,136 +796,1092 @@ impl App {
                     path: entry.path(),
                     is_dir,
                     size,
+                    modified,
                 });
             }
         }
 
-        // Sort: directories first, then alphabetically
-        entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-        });
+        // Date filter: keep directories, and files taken within the range
+        if let Some((start, end)) = self.date_filter {
+            let matching: HashSet<PathBuf> = self
+                .db
+                .get_photos_in_dir_date_range(&path.to_string_lossy(), start, end)
+                .unwrap_or_default()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect();
+            entries.retain(|e| e.is_dir || matching.contains(&e.path));
+        }
 
-        Ok(entries)
-    }
+        // Camera filter: keep directories, and files shot with that camera
+        if let Some(ref camera) = self.camera_filter {
+            let matching: HashSet<PathBuf> = self
+                .db
+                .get_photos_in_dir_by_camera(&path.to_string_lossy(), camera)
+                .unwrap_or_default()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect();
+            entries.retain(|e| e.is_dir || matching.contains(&e.path));
+        }
 
-    pub async fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
-        while !self.should_quit {
-            // Poll for task updates and handle completions
-            let completions = self.task_manager.poll_updates();
-            for completion in completions {
-                let prefix = completion.task_type.display_name();
-                if completion.success {
-                    self.status_message = Some(format!("{}: {}", prefix, completion.message));
+        // Tag filter: keep directories, and files carrying the tag
+        if let Some(ref tag) = self.tag_filter {
+            let matching: HashSet<PathBuf> = self
+                .db
+                .get_photos_in_dir_with_tag(&path.to_string_lossy(), tag.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect();
+            entries.retain(|e| e.is_dir || matching.contains(&e.path));
+        }
 
-                    // Clear metadata cache after scan completes so preview shows fresh data
-                    if matches!(completion.task_type, TaskType::Scan | TaskType::LlmSingle | TaskType::LlmBatch | TaskType::FaceDetection | TaskType::FaceClustering) {
-                        self.image_preview.metadata_cache.clear();
-                    }
+        // Location filter: keep directories, and files in the cluster
+        if let Some((_, ref matching)) = self.location_filter {
+            entries.retain(|e| e.is_dir || matching.contains(&e.path));
+        }
 
-                    // Invalidate cached duplicates after scan (new files may create new groups)
-                    if completion.task_type == TaskType::Scan {
-                        self.duplicates_view = None;
-                    }
+        // Capture times come from the database, so only scanned photos have one
+        let taken_at: HashMap<PathBuf, String> = if self.sort_order == SortOrder::DateTaken {
+            self.db
+                .get_photos_mtime_in_dir(&path.to_string_lossy())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|(p, _, taken)| taken.map(|t| (PathBuf::from(p), t)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
 
-                    // Pick up completed duplicate detection results
-                    if completion.task_type == TaskType::FindDuplicates {
-                        if let Some(rx) = self.pending_duplicates.take() {
-                            if let Ok(groups) = rx.try_recv() {
-                                if groups.is_empty() {
-                                    self.status_message = Some("No duplicates found".to_string());
-                                } else {
-                                    self.duplicates_view = Some(DuplicatesView::new(groups));
-                                    self.mode = AppMode::Duplicates;
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    self.status_message = Some(format!("{} - {}", prefix, completion.message));
+        sort_entries(&mut entries, self.sort_order, &taken_at);
 
-                    // Clean up receiver on failure too
-                    if completion.task_type == TaskType::FindDuplicates {
-                        self.pending_duplicates = None;
-                    }
-                }
+        Ok(entries)
+    }
+
+    fn handle_date_filter_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.date_filter_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
             }
+        };
 
-            // Poll for scheduled tasks that are due
-            let _ = self.poll_schedules();
+        let new_filter = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.date_filter_dialog = None;
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+            KeyCode::Enter => Some(dialog.range()),
+            KeyCode::Char('c') | KeyCode::Delete => None,
+            KeyCode::Left | KeyCode::Char('h') => {
+                dialog.prev_field();
+                return Ok(());
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                dialog.next_field();
+                return Ok(());
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                dialog.toggle_date();
+                return Ok(());
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                dialog.adjust(1);
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                dialog.adjust(-1);
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
 
-            terminal.draw(|frame| ui::render(frame, self))?;
+        self.date_filter = new_filter;
+        self.date_filter_dialog = None;
+        self.mode = AppMode::Normal;
+        self.status_message = Some(match new_filter {
+            Some((start, end)) => format!(
+                "Showing photos taken {}",
+                ui::date_filter_dialog::describe_range(start, end)
+            ),
+            None => "Date filter cleared".to_string(),
+        });
 
-            // Use shorter poll timeout when tasks are running for responsive progress updates,
-            // longer timeout when idle to reduce CPU usage
-            let poll_timeout = if self.task_manager.has_running_tasks() {
-                Duration::from_millis(16) // ~60 FPS when tasks running
-            } else {
-                Duration::from_millis(50) // ~20 FPS when idle (still responsive)
-            };
+        let current_dir = self.current_dir.clone();
+        self.load_directory(&current_dir)
+    }
 
-            if event::poll(poll_timeout)? {
-                match event::read()? {
-                    Event::Key(key) => self.handle_key(key)?,
-                    Event::Mouse(mouse) => {
-                        let size = terminal.size()?;
-                        let area = Rect::new(0, 0, size.width, size.height);
-                        match self.mode {
-                            AppMode::PeopleManaging => self.handle_people_dialog_mouse(mouse, area)?,
-                            AppMode::Duplicates => self.handle_duplicates_mouse(mouse, area)?,
-                            AppMode::Normal => self.handle_mouse(mouse, area)?,
-                            _ => {} // Other modes don't have mouse support yet
-                        }
-                    }
-                    Event::Resize(_, _) => {}
-                    _ => {}
-                }
-            }
+    fn open_camera_picker(&mut self) {
+        let cameras = self
+            .db
+            .get_camera_models_in_dir(&self.current_dir.to_string_lossy())
+            .unwrap_or_default();
+        if cameras.is_empty() && self.camera_filter.is_none() {
+            self.status_message = Some("No camera information for photos in this directory".to_string());
+            return;
         }
-
-        Ok(())
+        self.camera_dialog = Some(CameraDialog::new(cameras, self.camera_filter.as_deref()));
+        self.mode = AppMode::CameraFilter;
     }
 
-    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
-        // Handle help mode
-        if self.mode == AppMode::Help {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('?') => {
-                    self.mode = AppMode::Normal;
-                }
-                _ => {}
+    fn handle_camera_filter_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.camera_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
             }
-            return Ok(());
-        }
+        };
 
-        // Handle duplicates help mode
-        if self.mode == AppMode::DuplicatesHelp {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('?') => {
-                    self.mode = AppMode::Duplicates;
-                }
-                _ => {}
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.camera_dialog = None;
+                self.mode = AppMode::Normal;
+                return Ok(());
             }
-            return Ok(());
+            KeyCode::Down | KeyCode::Char('j') => {
+                dialog.move_down();
+                return Ok(());
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                dialog.move_up();
+                return Ok(());
+            }
+            KeyCode::Enter => {}
+            _ => return Ok(()),
         }
 
-        // Handle duplicates mode
-        if self.mode == AppMode::Duplicates {
-            return self.handle_duplicates_key(key);
-        }
+        self.camera_filter = dialog.selected_camera();
+        self.camera_dialog = None;
+        self.mode = AppMode::Normal;
+        self.status_message = Some(match self.camera_filter {
+            Some(ref camera) => format!("Showing photos from {}", camera),
+            None => "Camera filter cleared".to_string(),
+        });
 
-        // Handle Moving mode
-        if self.mode == AppMode::Moving {
-            return self.handle_move_dialog_key(key);
-        }
+        let current_dir = self.current_dir.clone();
+        self.load_directory(&current_dir)
+    }
 
-        // Handle Renaming mode
-        if self.mode == AppMode::Renaming {
-            return self.handle_rename_dialog_key(key);
-        }
+    fn handle_quick_filter_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(filter) = self.quick_filter.as_mut() else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
 
-        // Handle Exporting mode
+        match key.code {
+            KeyCode::Esc => self.close_quick_filter(),
+            KeyCode::Enter => {
+                self.close_quick_filter();
+                return self.enter_selected();
+            }
+            KeyCode::Down => self.move_down(),
+            KeyCode::Up => self.move_up(),
+            KeyCode::Backspace => {
+                filter.query.pop();
+                self.apply_quick_filter();
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                filter.query.push(c);
+                self.apply_quick_filter();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn apply_quick_filter(&mut self) {
+        if let Some(ref mut filter) = self.quick_filter {
+            self.entries = filter.apply();
+            self.selected_index = 0;
+            self.scroll_offset = 0;
+        }
+    }
+
+    /// Show the whole directory again, keeping the cursor on the entry it
+    /// was on in the filtered list
+    fn close_quick_filter(&mut self) {
+        self.mode = AppMode::Normal;
+        let Some(filter) = self.quick_filter.take() else {
+            return;
+        };
+        let selected = self.selected_entry().map(|e| e.path.clone());
+        self.entries = filter.all_entries;
+        self.selected_index = selected
+            .and_then(|p| self.entries.iter().position(|e| e.path == p))
+            .unwrap_or(0);
+    }
+
+    fn open_expanded_fields(&mut self) {
+        let Some(path) = self.selected_entry().filter(|e| !e.is_dir).map(|e| e.path.clone()) else {
+            return;
+        };
+        let Some(meta) = self.get_photo_metadata(&path) else {
+            self.status_message = Some("No metadata: photo not scanned yet".to_string());
+            return;
+        };
+        let all_exif = self.db.get_all_exif(&path).unwrap_or_default();
+        self.expanded_field_dialog = Some(ExpandedFieldDialog::from_metadata(&meta, all_exif.as_deref()));
+        self.mode = AppMode::ExpandedField;
+    }
+
+    fn handle_expanded_field_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.expanded_field_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        let expanded = dialog.expanded.is_some();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') if expanded => dialog.collapse(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.expanded_field_dialog = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') if expanded => dialog.scroll_down(1),
+            KeyCode::Up | KeyCode::Char('k') if expanded => dialog.scroll_up(1),
+            KeyCode::PageDown if expanded => dialog.scroll_down(10),
+            KeyCode::PageUp if expanded => dialog.scroll_up(10),
+            KeyCode::Down | KeyCode::Char('j') => dialog.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => dialog.move_up(),
+            KeyCode::Enter if !expanded => dialog.expand(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn open_tag_browser(&mut self) {
+        match self.db.get_tag_stats() {
+            Ok(stats) => {
+                let mut browser = TagBrowser::new(stats);
+                if let Some(ref tag) = self.tag_filter {
+                    browser.selected_index = browser
+                        .stats
+                        .iter()
+                        .position(|s| s.tag.id == tag.id)
+                        .unwrap_or(0);
+                }
+                self.tag_browser = Some(browser);
+                self.mode = AppMode::TagBrowser;
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load tags: {}", e)),
+        }
+    }
+
+    fn handle_tag_browser_key(&mut self, key: KeyEvent) -> Result<()> {
+        let browser = match self.tag_browser.as_mut() {
+            Some(b) => b,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match browser.mode {
+            TagBrowserMode::Rename => {
+                match key.code {
+                    KeyCode::Esc => browser.cancel(),
+                    KeyCode::Backspace => {
+                        browser.input.pop();
+                    }
+                    KeyCode::Char(c) => browser.input.push(c),
+                    KeyCode::Enter => {
+                        let new_name = browser.input.trim().to_string();
+                        let Some(tag) = browser.selected_tag() else {
+                            browser.cancel();
+                            return Ok(());
+                        };
+                        if new_name.is_empty() || new_name == tag.name {
+                            browser.cancel();
+                            return Ok(());
+                        }
+                        if browser.stats.iter().any(|s| s.tag.id != tag.id && s.tag.name.eq_ignore_ascii_case(&new_name)) {
+                            self.status_message = Some(format!("A tag named \"{}\" already exists", new_name));
+                            return Ok(());
+                        }
+                        self.db.rename_tag(tag.id, &new_name)?;
+                        self.status_message = Some(format!("Renamed \"{}\" to \"{}\"", tag.name, new_name));
+                        if let Some(ref mut filter) = self.tag_filter {
+                            if filter.id == tag.id {
+                                filter.name = new_name;
+                            }
+                        }
+                        self.refresh_tag_browser();
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            TagBrowserMode::ConfirmDelete => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        if let Some(stat) = browser.selected().cloned() {
+                            self.db.delete_tag(stat.tag.id)?;
+                            self.status_message = Some(format!(
+                                "Deleted tag \"{}\" from {} photos",
+                                stat.tag.name, stat.photo_count
+                            ));
+                            self.refresh_tag_browser();
+                            if self.tag_filter.as_ref().is_some_and(|t| t.id == stat.tag.id) {
+                                self.tag_filter = None;
+                                let current_dir = self.current_dir.clone();
+                                self.load_directory(&current_dir)?;
+                            }
+                        }
+                    }
+                    _ => browser.cancel(),
+                }
+                return Ok(());
+            }
+            TagBrowserMode::PickParent => {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => browser.cancel(),
+                    KeyCode::Down | KeyCode::Char('j') => browser.move_parent_down(),
+                    KeyCode::Up | KeyCode::Char('k') => browser.move_parent_up(),
+                    KeyCode::Enter => {
+                        let (Some(tag), Some(parent)) = (browser.selected_tag(), browser.selected_parent().cloned()) else {
+                            browser.cancel();
+                            return Ok(());
+                        };
+                        let parent_name = browser
+                            .stats
+                            .iter()
+                            .find(|s| Some(s.tag.id) == parent.id)
+                            .map(|s| s.tag.name.clone());
+                        match self.db.set_tag_parent(tag.id, parent.id) {
+                            Ok(()) => {
+                                self.status_message = Some(match parent_name {
+                                    Some(name) => format!("Moved \"{}\" under \"{}\"", tag.name, name),
+                                    None => format!("Moved \"{}\" to the top level", tag.name),
+                                });
+                                self.refresh_tag_browser();
+                                if let Some(ref mut browser) = self.tag_browser {
+                                    browser.select_tag(tag.id);
+                                }
+                            }
+                            Err(e) => {
+                                self.status_message = Some(format!("Failed to move tag: {}", e));
+                                browser.cancel();
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            TagBrowserMode::Browse => {}
+        }
+
+        let new_filter = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.tag_browser = None;
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                browser.move_down();
+                return Ok(());
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                browser.move_up();
+                return Ok(());
+            }
+            KeyCode::Char('r') => {
+                browser.start_rename();
+                return Ok(());
+            }
+            KeyCode::Char('m') => {
+                browser.start_pick_parent();
+                return Ok(());
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                browser.start_delete();
+                return Ok(());
+            }
+            KeyCode::Enter => match browser.selected_tag() {
+                Some(tag) => Some(tag),
+                None => return Ok(()),
+            },
+            KeyCode::Char('c') => None,
+            _ => return Ok(()),
+        };
+
+        self.status_message = Some(match new_filter {
+            Some(ref tag) => format!("Showing photos tagged \"{}\"", tag.name),
+            None => "Tag filter cleared".to_string(),
+        });
+        self.tag_filter = new_filter;
+        self.tag_browser = None;
+        self.mode = AppMode::Normal;
+
+        let current_dir = self.current_dir.clone();
+        self.load_directory(&current_dir)
+    }
+
+    /// Reload tag counts after a rename or delete.
+    fn refresh_tag_browser(&mut self) {
+        let stats = self.db.get_tag_stats().unwrap_or_default();
+        if let Some(ref mut browser) = self.tag_browser {
+            browser.set_stats(stats);
+        }
+    }
+
+    fn open_prompt_manager(&mut self) {
+        match self.db.get_directory_prompts() {
+            Ok(prompts) => {
+                let current_dir = self.current_dir.to_string_lossy().to_string();
+                self.prompt_manager = Some(PromptManager::new(prompts, current_dir));
+                self.mode = AppMode::PromptManager;
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load directory prompts: {}", e)),
+        }
+    }
+
+    fn handle_prompt_manager_key(&mut self, key: KeyEvent) -> Result<()> {
+        let manager = match self.prompt_manager.as_mut() {
+            Some(m) => m,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match manager.mode {
+            PromptManagerMode::Edit => {
+                match key.code {
+                    KeyCode::Esc => manager.cancel(),
+                    KeyCode::Backspace => {
+                        manager.input.pop();
+                    }
+                    KeyCode::Char(c) => manager.input.push(c),
+                    KeyCode::Enter => {
+                        let Some(directory) = manager.selected().map(|e| e.directory.clone()) else {
+                            manager.cancel();
+                            return Ok(());
+                        };
+                        let prompt = manager.input.trim().to_string();
+                        self.db.set_directory_prompt(&directory, &prompt)?;
+                        self.status_message = Some(if prompt.is_empty() {
+                            format!("{} now uses the global default prompt", directory)
+                        } else {
+                            format!("Saved prompt for {}", directory)
+                        });
+                        self.refresh_prompt_manager(&directory);
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            PromptManagerMode::ConfirmDelete => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        if let Some(directory) = manager.selected().map(|e| e.directory.clone()) {
+                            self.db.set_directory_prompt(&directory, "")?;
+                            self.status_message = Some(format!("Deleted prompt for {}", directory));
+                            self.refresh_prompt_manager(&directory);
+                        }
+                    }
+                    _ => manager.cancel(),
+                }
+                return Ok(());
+            }
+            PromptManagerMode::Browse => {}
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.prompt_manager = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') => manager.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => manager.move_up(),
+            KeyCode::Char('e') | KeyCode::Enter => manager.start_edit(),
+            KeyCode::Char('n') => manager.start_new(),
+            KeyCode::Char('d') | KeyCode::Delete => manager.start_delete(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reload directory prompts after an edit or delete, keeping `directory`
+    /// selected.
+    fn refresh_prompt_manager(&mut self, directory: &str) {
+        let prompts = self.db.get_directory_prompts().unwrap_or_default();
+        if let Some(ref mut manager) = self.prompt_manager {
+            manager.set_prompts(prompts);
+            manager.select_directory(directory);
+        }
+    }
+
+    fn open_calendar(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        match self.db.get_photo_counts_by_date(today.year()) {
+            Ok(counts) => {
+                self.calendar = Some(CalendarView::new(today, counts));
+                self.mode = AppMode::Calendar;
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load photo dates: {}", e)),
+        }
+    }
+
+    fn handle_calendar_key(&mut self, key: KeyEvent) -> Result<()> {
+        let view = match self.calendar.as_mut() {
+            Some(v) => v,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.calendar = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let year = view.year + if key.code == KeyCode::Left { -1 } else { 1 };
+                let counts = self.db.get_photo_counts_by_date(year).unwrap_or_default();
+                view.set_year(year, counts);
+            }
+            KeyCode::Char('h') => view.move_days(-7),
+            KeyCode::Char('l') => view.move_days(7),
+            KeyCode::Down | KeyCode::Char('j') => view.move_days(1),
+            KeyCode::Up | KeyCode::Char('k') => view.move_days(-1),
+            KeyCode::Enter => {
+                let date = view.selected;
+                let images: Vec<PathBuf> = self
+                    .db
+                    .get_photos_taken_on(date)?
+                    .into_iter()
+                    .map(PathBuf::from)
+                    .collect();
+                if images.is_empty() {
+                    self.status_message = Some(format!("No photos taken on {}", date.format("%-d %b %Y")));
+                    return Ok(());
+                }
+
+                // Paste still targets the browser's directory
+                let mut gallery = GalleryView::new(self.current_dir.clone(), images, self.image_protocol(&self.current_dir))
+                    .with_sort_metadata(&self.config.database);
+                gallery.title = Some(date.format("%a %-d %b %Y").to_string());
+                self.gallery_view = Some(gallery);
+                self.calendar = None;
+                self.mode = AppMode::Gallery;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // --- Album browser ---
+
+    fn load_album_entries(&self) -> Result<Vec<AlbumEntry>> {
+        let mut entries = Vec::new();
+        for album in self.db.get_all_albums()? {
+            let paths = self.db.get_album_paths(&album)?;
+            let cover = match album.cover_photo_id {
+                Some(id) => self.db.get_photo_path(id)?,
+                None => None,
+            };
+            entries.push(AlbumEntry {
+                cover: cover.or_else(|| paths.first().cloned()).map(PathBuf::from),
+                count: paths.len(),
+                album,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn open_albums(&mut self) {
+        match self.load_album_entries() {
+            Ok(entries) => {
+                self.albums_view = Some(AlbumsView::new(self.current_dir.clone(), entries, self.image_protocol(&self.current_dir)));
+                self.mode = AppMode::Albums;
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load albums: {}", e)),
+        }
+    }
+
+    fn reload_albums(&mut self) {
+        match self.load_album_entries() {
+            Ok(entries) => {
+                if let Some(view) = self.albums_view.as_mut() {
+                    view.set_entries(entries);
+                }
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load albums: {}", e)),
+        }
+    }
+
+    fn handle_albums_key(&mut self, key: KeyEvent) -> Result<()> {
+        let view = match self.albums_view.as_mut() {
+            Some(v) => v,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        if let Some(prompt) = view.prompt.take() {
+            return self.handle_album_prompt_key(prompt, key);
+        }
+
+        let columns = view.grid.cached_columns();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.albums_view = None;
+                self.mode = AppMode::Normal;
+                self.clear_on_next_render = true;
+            }
+            KeyCode::Left | KeyCode::Char('h') => view.grid.move_left(),
+            KeyCode::Right | KeyCode::Char('l') => view.grid.move_right(),
+            KeyCode::Up | KeyCode::Char('k') => view.grid.move_up(columns),
+            KeyCode::Down | KeyCode::Char('j') => view.grid.move_down(columns),
+            KeyCode::Home | KeyCode::Char('g') => view.grid.move_to_start(),
+            KeyCode::End | KeyCode::Char('G') => view.grid.move_to_end(),
+            KeyCode::Char('n') => view.prompt = Some(AlbumPrompt::NewAlbum(String::new())),
+            KeyCode::Char('d') if view.selected().is_some() => view.prompt = Some(AlbumPrompt::ConfirmDelete),
+            KeyCode::Char('e') => {
+                let Some(entry) = view.selected() else { return Ok(()) };
+                let names: Vec<String> = self
+                    .db
+                    .get_all_tags()?
+                    .into_iter()
+                    .filter(|t| entry.album.filter_tags.contains(&t.id))
+                    .map(|t| t.name)
+                    .collect();
+                view.prompt = Some(AlbumPrompt::SmartTags(names.join(", ")));
+            }
+            KeyCode::Char('r') => {
+                let Some(entry) = view.selected() else { return Ok(()) };
+                let current = entry.album.geo_radius.map(|geo| geo.label()).unwrap_or_default();
+                view.prompt = Some(AlbumPrompt::GeoRadius(current));
+            }
+            KeyCode::Char('x') if view.selected().is_some() => {
+                view.prompt = Some(AlbumPrompt::ExportDir(self.current_dir.display().to_string()));
+            }
+            KeyCode::Char('o') => {
+                let Some(entry) = view.selected() else { return Ok(()) };
+                let sort = entry.album.auto_sort.cycle();
+                let name = entry.album.name.clone();
+                self.db.set_album_auto_sort(entry.album.id, sort)?;
+                self.reload_albums();
+                self.status_message = Some(format!("\"{}\" opens in {}", name, sort.label()));
+            }
+            KeyCode::Enter => {
+                let Some(entry) = view.selected() else { return Ok(()) };
+                let name = entry.album.name.clone();
+                let album = entry.album.clone();
+                let images: Vec<PathBuf> = self
+                    .db
+                    .get_album_paths(&entry.album)?
+                    .into_iter()
+                    .map(PathBuf::from)
+                    .collect();
+                if images.is_empty() {
+                    self.status_message = Some(format!("Album \"{}\" is empty", name));
+                    return Ok(());
+                }
+
+                // Paste still targets the browser's directory
+                let mut gallery = GalleryView::new(self.current_dir.clone(), images, self.image_protocol(&self.current_dir))
+                    .with_sort_metadata(&self.config.database);
+                gallery.title = Some(format!("Album {}", name));
+                match album.auto_sort {
+                    AlbumSort::Manual if !album.is_smart => gallery.album_id = Some(album.id),
+                    AlbumSort::Manual => {}
+                    AlbumSort::Date => gallery.set_sort(GallerySort::Date),
+                    AlbumSort::Name => gallery.set_sort(GallerySort::Name),
+                    AlbumSort::Rating => gallery.set_sort(GallerySort::Rating),
+                }
+                self.gallery_view = Some(gallery);
+                self.mode = AppMode::Gallery;
+                self.clear_on_next_render = true;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_album_prompt_key(&mut self, mut prompt: AlbumPrompt, key: KeyEvent) -> Result<()> {
+        if prompt == AlbumPrompt::ConfirmDelete {
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.delete_selected_album()?;
+            }
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Enter => return self.submit_album_prompt(prompt),
+            KeyCode::Backspace => {
+                if let Some(input) = prompt.input_mut() {
+                    input.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(input) = prompt.input_mut() {
+                    input.push(c);
+                }
+            }
+            _ => {}
+        }
+        if let Some(view) = self.albums_view.as_mut() {
+            view.prompt = Some(prompt);
+        }
+        Ok(())
+    }
+
+    fn submit_album_prompt(&mut self, prompt: AlbumPrompt) -> Result<()> {
+        let Some(entry) = self.albums_view.as_ref().and_then(|v| v.selected()).cloned() else {
+            // Only a new album makes sense with nothing selected
+            if let AlbumPrompt::NewAlbum(name) = prompt {
+                self.create_album_named(&name)?;
+            }
+            return Ok(());
+        };
+
+        match prompt {
+            AlbumPrompt::NewAlbum(name) => self.create_album_named(&name)?,
+            AlbumPrompt::ConfirmDelete => {}
+            AlbumPrompt::SmartTags(input) => {
+                let tags = self.db.get_all_tags()?;
+                let mut tag_ids = Vec::new();
+                for name in input.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                    match tags.iter().find(|t| t.name.eq_ignore_ascii_case(name)) {
+                        Some(tag) => tag_ids.push(tag.id),
+                        None => {
+                            self.status_message = Some(format!("No tag named \"{}\"", name));
+                            return Ok(());
+                        }
+                    }
+                }
+                if tag_ids.is_empty() {
+                    self.status_message = Some("A smart album needs at least one tag".to_string());
+                    return Ok(());
+                }
+                self.db.set_album_filter_tags(entry.album.id, &tag_ids)?;
+                self.reload_albums();
+                self.status_message = Some(format!("\"{}\" now collects photos with all of: {}", entry.album.name, input.trim()));
+            }
+            AlbumPrompt::GeoRadius(input) => {
+                if input.trim().is_empty() {
+                    self.db.set_album_geo_radius(entry.album.id, None)?;
+                    self.reload_albums();
+                    self.status_message = Some(format!("\"{}\" no longer limited to an area", entry.album.name));
+                    return Ok(());
+                }
+                let Some(geo) = GeoRadius::parse(&input) else {
+                    self.status_message = Some("Expected latitude, longitude, radius in km, e.g. 51.5072, -0.1276, 5".to_string());
+                    return Ok(());
+                };
+                self.db.set_album_geo_radius(entry.album.id, Some(&geo))?;
+                self.reload_albums();
+                self.status_message = Some(format!("\"{}\" now collects photos taken within {}", entry.album.name, geo.label()));
+            }
+            AlbumPrompt::ExportDir(dir) => {
+                if dir.trim().is_empty() {
+                    return Ok(());
+                }
+                if let Some(view) = self.albums_view.as_mut() {
+                    view.prompt = Some(AlbumPrompt::ExportPattern { dir, input: String::new() });
+                }
+            }
+            AlbumPrompt::ExportPattern { dir, input } => {
+                let dest = PathBuf::from(crate::config::Config::expand_env_vars(dir.trim()));
+                let pattern = Some(input.trim()).filter(|p| !p.is_empty());
+                self.status_message = Some(match self.db.export_album_to_dir(entry.album.id, &dest, pattern) {
+                    Ok(copied) => format!("Exported {} photos from \"{}\" to {}", copied, entry.album.name, dest.display()),
+                    Err(e) => format!("Album export failed: {}", e),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn create_album_named(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Ok(());
+        }
+        let album_id = self.db.create_album(name, None, false)?;
+        self.reload_albums();
+        if let Some(view) = self.albums_view.as_mut() {
+            view.select_album(album_id);
+        }
+        self.status_message = Some(format!("Created album \"{}\"", name));
+        Ok(())
+    }
+
+    fn delete_selected_album(&mut self) -> Result<()> {
+        let Some(entry) = self.albums_view.as_ref().and_then(|v| v.selected()).cloned() else {
+            return Ok(());
+        };
+        self.db.delete_album(entry.album.id)?;
+        self.reload_albums();
+        self.status_message = Some(format!("Deleted album \"{}\"", entry.album.name));
+        Ok(())
+    }
+
+    /// Preview mode in effect, falling back to metadata only when images
+    /// cannot be displayed.
+    pub fn preview_mode(&self) -> PreviewMode {
+        self.config.preview.mode.resolve(self.images_available())
+    }
+
+    fn images_available(&self) -> bool {
+        self.config.preview.image_preview && self.image_preview.is_available()
+    }
+
+    fn cycle_preview_mode(&mut self) {
+        let mode = self.preview_mode().next(self.images_available());
+        self.config.preview.mode = mode;
+        let _ = self.config.save();
+        self.status_message = Some(format!("Preview: {}", mode.label()));
+    }
+
+    fn cycle_sort(&mut self) -> Result<()> {
+        self.sort_order = self.sort_order.next();
+        self.status_message = Some(format!("Sort by {}", self.sort_order.label()));
+        // Persist to config
+        self.config.view.sort_order = self.sort_order;
+        let _ = self.config.save();
+
+        // Reload, keeping the cursor on the same entry
+        let selected = self.selected_entry().map(|e| e.path.clone());
+        let current_dir = self.current_dir.clone();
+        self.load_directory(&current_dir)?;
+        if let Some(idx) = selected.and_then(|p| self.entries.iter().position(|e| e.path == p)) {
+            self.selected_index = idx;
+        }
+        Ok(())
+    }
+
+    /// Desktop notification for a task that succeeded, when enabled
+    fn notify_completion(&self, completion: &TaskCompletionInfo) {
+        if !self.config.notifications.enabled || !completion.success {
+            return;
+        }
+        let title = format!("Clepho: {}", completion.task_type.display_name());
+        if let Err(e) = crate::notifications::send(&title, &completion.message) {
+            tracing::warn!(error = %e, "Failed to send desktop notification");
+        }
+    }
+
+    pub async fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
+        while !self.should_quit {
+            // Poll for task updates and handle completions
+            let completions = self.task_manager.poll_updates();
+            for completion in completions {
+                // Directory size walks are housekeeping, not worth a status message
+                if completion.task_type == TaskType::DirSize {
+                    continue;
+                }
+                self.notify_completion(&completion);
+                if completion.task_type == TaskType::ThumbnailWarmup {
+                    self.finish_thumbnail_warmup(completion.id, completion.success, &completion.message)?;
+                    continue;
+                }
+                if matches!(completion.task_type, TaskType::MoveFiles | TaskType::Centralise) {
+                    self.finish_batch_operation(completion.id, &completion.message)?;
+                    continue;
+                }
+                if completion.task_type == TaskType::AutoCull {
+                    self.finish_auto_cull(completion.id, completion.success, &completion.message);
+                    continue;
+                }
+                // Sharing ends when its dialog closes; only a failure while
+                // it is still open needs showing
+                if completion.task_type == TaskType::Share {
+                    if let Some(dialog) = self.share_dialog.as_mut().filter(|d| d.task_id == completion.id) {
+                        if !completion.success {
+                            dialog.error.get_or_insert(completion.message);
+                        }
+                    }
+                    continue;
+                }
+
+                let prefix = completion.task_type.display_name();
+                if completion.success {
+                    self.status_message = Some(format!("{}: {}", prefix, completion.message));
+
+                    // Clear metadata cache after scan completes so preview shows fresh data
+                    if matches!(completion.task_type, TaskType::Scan | TaskType::LlmSingle | TaskType::LlmBatch | TaskType::FaceDetection | TaskType::FaceClustering) {
+                        self.image_preview.metadata_cache.clear();
+                    }
+
+                    // New descriptions may form new events
+                    if completion.task_type == TaskType::LlmBatch {
+                        self.start_event_detection();
+                    }
+
+                    // Invalidate cached duplicates after scan (new files may create new groups)
+                    if completion.task_type == TaskType::Scan {
+                        self.duplicates_view = None;
+                        self.dir_stats.clear();
+                        self.dir_size_cache.clear();
+                        self.dir_size_loading.clear();
+
+                        // New photos may match smart album filters
+                        if let Err(e) = self.db.refresh_smart_albums() {
+                            tracing::warn!(error = %e, "Failed to refresh smart albums");
+                        }
+                    }
+
+                    // Pick up completed duplicate detection results
+                    if completion.task_type == TaskType::FindDuplicates {
+                        if let Some(rx) = self.pending_duplicates.take() {
+                            if let Ok(groups) = rx.try_recv() {
+                                if groups.is_empty() {
+                                    self.status_message = Some("No duplicates found".to_string());
+                                } else {
+                                    self.duplicates_view = Some(DuplicatesView::new(groups));
+                                    self.mode = AppMode::Duplicates;
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    self.status_message = Some(format!("{} - {}", prefix, completion.message));
+
+                    // Clean up receiver on failure too
+                    if completion.task_type == TaskType::FindDuplicates {
+                        self.pending_duplicates = None;
+                    }
+                }
+            }
+
+            // Start queued tasks whose dependency has finished
+            for task_type in self.task_manager.take_ready() {
+                if let Err(e) = self.start_queued_task(task_type) {
+                    self.status_message = Some(format!("{}: {}", task_type.display_name(), e));
+                }
+            }
+            if self.task_manager.queued_tasks().is_empty() {
+                self.pipeline = None;
+            }
+
+            // Poll for scheduled tasks that are due
+            let _ = self.poll_schedules();
+
+            self.save_session_debounced();
+
+            self.poll_dir_stats();
+            self.poll_dir_sizes();
+            self.poll_watcher();
+
+            self.image_preview.tick_animation();
+
+            if let Some(ref mut dialog) = self.gps_dialog {
+                dialog.poll_geocode();
+            }
+            self.poll_location_geocode();
+            self.poll_adb_preview();
+            if let Some(ref mut dialog) = self.share_dialog {
+                dialog.poll();
+            }
+
+            terminal.draw(|frame| ui::render(frame, self))?;
+
+            // Use shorter poll timeout when tasks are running for responsive progress updates,
+            // longer timeout when idle to reduce CPU usage
+            let poll_timeout = if self.task_manager.has_running_tasks() {
+                Duration::from_millis(16) // ~60 FPS when tasks running
+            } else {
+                Duration::from_millis(50) // ~20 FPS when idle (still responsive)
+            };
+
+            if event::poll(poll_timeout)? {
+                match event::read()? {
+                    Event::Key(key) => self.handle_key(key)?,
+                    Event::Mouse(mouse) => {
+                        let size = terminal.size()?;
+                        let area = Rect::new(0, 0, size.width, size.height);
+                        match self.mode {
+                            AppMode::PeopleManaging => self.handle_people_dialog_mouse(mouse, area)?,
+                            AppMode::Duplicates => self.handle_duplicates_mouse(mouse, area)?,
+                            AppMode::Gallery => self.handle_gallery_mouse(mouse, area)?,
+                            AppMode::Slideshow => self.handle_slideshow_mouse(mouse),
+                            AppMode::Normal | AppMode::Breadcrumb => self.handle_mouse(mouse, area)?,
+                            _ => {} // Other modes don't have mouse support yet
+                        }
+                    }
+                    // The panes may appear or go at the new width, which
+                    // can leave a preview image behind
+                    Event::Resize(_, _) => self.clear_on_next_render = true,
+                    _ => {}
+                }
+            }
+        }
+
+        if self.pane_ratios_changed {
+            let _ = self.config.save();
+        }
+
+        // Final synchronous write so the last navigation is never lost
+        if self.config.ui.restore_session {
+            let state = self.session_state();
+            if self.saved_session.as_ref() != Some(&state) {
+                let _ = state.save();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Handle help mode
+        if self.mode == AppMode::Help {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('?') => {
+                    self.mode = AppMode::Normal;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        // Handle duplicates help mode
+        if self.mode == AppMode::DuplicatesHelp {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('?') => {
+                    self.mode = AppMode::Duplicates;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        // Handle duplicates mode
+        if self.mode == AppMode::Duplicates {
+            return self.handle_duplicates_key(key);
+        }
+
+        // Handle Moving mode
+        if self.mode == AppMode::Moving {
+            return self.handle_move_dialog_key(key);
+        }
+
+        if self.mode == AppMode::ConflictResolving {
+            return self.handle_conflict_dialog_key(key);
+        }
+
+        if self.mode == AppMode::CrossDirDedup {
+            return self.handle_cross_dedup_key(key);
+        }
+
+        // Handle Renaming mode
+        if self.mode == AppMode::Renaming {
+            return self.handle_rename_dialog_key(key);
+        }
+
+        // Handle Exporting mode
         if self.mode == AppMode::Exporting {
             return self.handle_export_dialog_key(key);
         }
@@ -489,9 +1926,110 @@ impl App {
             return self.handle_edit_description_key(key);
         }
 
-        // Handle Settings mode
-        if self.mode == AppMode::Settings {
-            return self.handle_settings_key(key);
+        // Handle EditingGPS mode
+        if self.mode == AppMode::EditingGPS {
+            return self.handle_gps_dialog_key(key);
+        }
+
+        // Handle Settings mode
+        if self.mode == AppMode::Settings {
+            return self.handle_settings_key(key);
+        }
+
+        // Handle DateFilter mode
+        if self.mode == AppMode::DateFilter {
+            return self.handle_date_filter_key(key);
+        }
+
+        // Handle RescanMissing mode
+        if self.mode == AppMode::RescanMissing {
+            return self.handle_rescan_dialog_key(key);
+        }
+
+        // Handle QuickFilter mode
+        if self.mode == AppMode::QuickFilter {
+            return self.handle_quick_filter_key(key);
+        }
+
+        // Handle ExpandedField mode
+        if self.mode == AppMode::ExpandedField {
+            return self.handle_expanded_field_key(key);
+        }
+
+        // Handle CameraFilter mode
+        if self.mode == AppMode::CameraFilter {
+            return self.handle_camera_filter_key(key);
+        }
+
+        // Handle TagBrowser mode
+        if self.mode == AppMode::TagBrowser {
+            return self.handle_tag_browser_key(key);
+        }
+
+        // Handle PromptManager mode
+        if self.mode == AppMode::PromptManager {
+            return self.handle_prompt_manager_key(key);
+        }
+
+        if self.mode == AppMode::Calendar {
+            return self.handle_calendar_key(key);
+        }
+
+        if self.mode == AppMode::Albums {
+            return self.handle_albums_key(key);
+        }
+
+        if self.mode == AppMode::People {
+            return self.handle_people_view_key(key);
+        }
+
+        if self.mode == AppMode::Locations {
+            return self.handle_locations_key(key);
+        }
+
+        if self.mode == AppMode::AdbImport {
+            return self.handle_adb_import_key(key);
+        }
+
+        if self.mode == AppMode::ThumbnailWarmup {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                if let Some(dialog) = self.thumbnail_warmup.take() {
+                    self.task_manager.cancel_task(dialog.task_id);
+                }
+                self.mode = AppMode::Normal;
+                self.status_message = Some("Thumbnail warm-up cancelled".to_string());
+            }
+            return Ok(());
+        }
+
+        if self.mode == AppMode::Sharing {
+            let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if ctrl_c || matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                self.stop_sharing();
+            }
+            return Ok(());
+        }
+
+        if self.mode == AppMode::BatchProgress {
+            // The dialog stays up until the task stops, so files already
+            // handled are still reported
+            if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                if let Some(ref dialog) = self.batch_progress {
+                    self.task_manager.cancel_task(dialog.task_id);
+                    self.status_message = Some("Cancelling...".to_string());
+                }
+            }
+            return Ok(());
+        }
+
+        // Handle Breadcrumb mode
+        if self.mode == AppMode::Breadcrumb {
+            return self.handle_breadcrumb_key(key);
+        }
+
+        // Handle ThemePicking mode
+        if self.mode == AppMode::ThemePicking {
+            return self.handle_theme_picker_key(key);
         }
 
         // Handle Gallery Help mode
@@ -515,6 +2053,10 @@ impl App {
             return self.handle_tag_dialog_key(key);
         }
 
+        if self.mode == AppMode::BatchTagging {
+            return self.handle_batch_tag_dialog_key(key);
+        }
+
         // Handle Slideshow Help mode
         if self.mode == AppMode::SlideshowHelp {
             match key.code {
@@ -603,7 +2145,9 @@ impl App {
         }
 
         // Special case: 'g' starts the gg sequence
-        if key.code == KeyCode::Char('g') && !key.modifiers.contains(KeyModifiers::SHIFT) {
+        if key.code == KeyCode::Char('g')
+            && !key.modifiers.intersects(KeyModifiers::SHIFT | KeyModifiers::CONTROL)
+        {
             self.g_pressed = true;
             return Ok(());
         }
@@ -623,6 +2167,9 @@ impl App {
             } else if !self.selected_files.is_empty() || self.mode == AppMode::Visual {
                 self.exit_visual_mode();
                 self.clear_selection();
+            } else if self.mode == AppMode::Watching {
+                self.stop_watching();
+                self.status_message = Some("Stopped watching".to_string());
             }
             return Ok(());
         }
@@ -666,23 +2213,43 @@ impl App {
             Action::EnterVisualMode => self.enter_visual_mode(),
 
             // Actions requiring confirmation
-            Action::Scan | Action::DescribeWithLlm | Action::BatchLlm |
-            Action::DetectFaces | Action::ClusterFaces | Action::ClipEmbedding => {
+            Action::Scan | Action::ScanAll | Action::DescribeWithLlm | Action::BatchLlm |
+            Action::DetectFaces | Action::ClusterFaces | Action::RegenerateFaceEmbeddings |
+            Action::ClipEmbedding | Action::WriteExifToFiles => {
                 self.show_confirmation(action);
             }
             Action::FindDuplicates => self.find_duplicates()?,
+            Action::AutoCull => self.start_auto_cull(),
+            Action::FindCrossDirDuplicates => {
+                self.cross_dedup_dialog = Some(CrossDirDedupDialog::new(self.current_dir.clone()));
+                self.mode = AppMode::CrossDirDedup;
+            }
             Action::ViewTasks => self.mode = AppMode::TaskList,
             Action::ViewTrash => self.open_trash_dialog()?,
             Action::MoveFiles => self.open_move_dialog()?,
             Action::RenameFiles => self.open_rename_dialog()?,
             Action::ExportDatabase => self.open_export_dialog()?,
             Action::SemanticSearch => self.open_search_dialog()?,
-            Action::ManagePeople => self.open_people_dialog()?,
+            Action::RescanMissingMetadata => {
+                self.rescan_dialog = Some(RescanDialog::new());
+                self.mode = AppMode::RescanMissing;
+            }
+            Action::QuickFilter => {
+                self.quick_filter = Some(QuickFilter::new(self.entries.clone()));
+                self.mode = AppMode::QuickFilter;
+            }
+            Action::SearchByImage => self.search_by_image()?,
+            Action::ManagePeople => self.open_people_view()?,
+            Action::ManagePrompts => self.open_prompt_manager(),
             Action::EditDescription => self.open_edit_description_dialog()?,
+            Action::EditGps => self.open_gps_dialog()?,
             Action::ViewChanges => self.open_changes_dialog()?,
             Action::OpenSchedule => self.open_schedule_dialog()?,
             Action::OpenGallery => self.open_gallery_view()?,
             Action::OpenTags => self.open_tag_dialog()?,
+            Action::BatchEditTags => self.open_batch_tag_dialog()?,
+            Action::ToggleWatch => self.toggle_watch()?,
+            Action::ReloadConfig => self.reload_config(),
             Action::OpenSlideshow => self.open_slideshow()?,
             Action::CentraliseFiles => self.open_centralise_dialog()?,
             Action::RotateCW => self.rotate_photo_cw()?,
@@ -690,29 +2257,130 @@ impl App {
             Action::YankFiles => self.yank_selected()?,
             Action::PasteFiles => self.paste_from_clipboard()?,
             Action::DeleteFiles => self.trash_selected()?,
+            Action::TrashRedLabeled => self.trash_red_labeled()?,
+            Action::Undo => self.undo()?,
+            Action::Redo => self.redo()?,
             Action::ShowHelp => self.mode = AppMode::Help,
             Action::Quit => self.should_quit = true,
             Action::ToggleHidden => self.toggle_hidden()?,
             Action::ToggleShowAllFiles => self.toggle_show_all_files()?,
             Action::OpenExternal => self.open_external()?,
+            Action::PickTheme => self.open_theme_picker(),
+            Action::CycleSort => self.cycle_sort()?,
+            Action::FilterByDate => {
+                self.date_filter_dialog = Some(DateFilterDialog::new(self.date_filter));
+                self.mode = AppMode::DateFilter;
+            }
+            Action::FilterByCamera => self.open_camera_picker(),
+            Action::OpenTagBrowser => self.open_tag_browser(),
+            Action::OpenCalendar => self.open_calendar(),
+            Action::OpenAlbums => self.open_albums(),
+            Action::OpenLocations => self.open_locations(),
+            Action::OpenAdbImport => self.open_adb_import(),
+            Action::ShareSelected => self.share_selected(),
+            Action::CyclePreviewMode => self.cycle_preview_mode(),
+            Action::ExpandMetadata => self.open_expanded_fields(),
+            Action::ToggleHistogram => {
+                self.image_preview.show_histogram = !self.image_preview.show_histogram;
+                self.status_message = Some(
+                    if self.image_preview.show_histogram { "Histogram shown" } else { "Histogram hidden" }.to_string(),
+                );
+            }
+            Action::FocusBreadcrumb => {
+                // Start on the parent, the most likely target
+                let len = ui::current_crumbs(self).len();
+                self.breadcrumb_cursor = Some(len.saturating_sub(2));
+                self.mode = AppMode::Breadcrumb;
+            }
+            // Layout
+            Action::ShrinkPreview => self.resize_preview(-5),
+            Action::GrowPreview => self.resize_preview(5),
+        }
+        Ok(())
+    }
+
+    fn handle_breadcrumb_key(&mut self, key: KeyEvent) -> Result<()> {
+        let crumbs = ui::current_crumbs(self);
+        let cursor = self.breadcrumb_cursor.unwrap_or(0);
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.breadcrumb_cursor = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.breadcrumb_cursor = Some(cursor.saturating_sub(1));
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.breadcrumb_cursor = Some((cursor + 1).min(crumbs.len().saturating_sub(1)));
+            }
+            KeyCode::Enter => {
+                self.breadcrumb_cursor = None;
+                self.mode = AppMode::Normal;
+                if let Some(crumb) = crumbs.get(cursor) {
+                    self.load_directory(&crumb.path)?;
+                }
+            }
+            _ => {}
         }
         Ok(())
     }
 
     fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) -> Result<()> {
         // Calculate pane layout for all mouse events
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(20),
-                Constraint::Percentage(40),
-                Constraint::Percentage(40),
-            ])
-            .split(area);
+        let main_chunks = ui::main_layout(area);
+        let chunks = ui::browser_layout(main_chunks[0], &self.config.ui);
+        let breadcrumb_area = main_chunks[1];
 
         let x = mouse.column;
         let y = mouse.row;
 
+        // Drag-and-drop: track the drop target while dragging, move on release
+        if self.drag.is_some() {
+            match mouse.kind {
+                MouseEventKind::Drag(crossterm::event::MouseButton::Left) => {
+                    let target = self.drop_target(x, y, &chunks);
+                    if let Some(ref mut drag) = self.drag {
+                        drag.position = (x, y);
+                        drag.active = true;
+                        drag.target = target;
+                    }
+                    return Ok(());
+                }
+                MouseEventKind::Up(crossterm::event::MouseButton::Left) => {
+                    let drag = self.drag.take().unwrap();
+                    if drag.active {
+                        match self.drop_target(x, y, &chunks) {
+                            Some(target) => self.execute_move(drag.files, target)?,
+                            None => self.status_message = Some("Move cancelled".to_string()),
+                        }
+                    }
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // Breadcrumb bar: hover highlights a segment, click navigates to it
+        let crumb_index = if y == breadcrumb_area.y {
+            ui::breadcrumb_hit_test(&ui::current_crumbs(self), breadcrumb_area.width, x)
+        } else {
+            None
+        };
+        self.breadcrumb_hover = crumb_index;
+        if let Some(index) = crumb_index {
+            if mouse.kind == MouseEventKind::Down(crossterm::event::MouseButton::Left) {
+                let path = ui::current_crumbs(self)[index].path.clone();
+                self.breadcrumb_cursor = None;
+                self.mode = AppMode::Normal;
+                self.load_directory(&path)?;
+            }
+            return Ok(());
+        }
+        if mouse.kind == MouseEventKind::Moved {
+            return Ok(());
+        }
+
         // Determine which pane the mouse is in
         let in_parent_pane = x < chunks[0].right() && y >= chunks[0].y && y < chunks[0].bottom();
         let in_current_pane = x >= chunks[1].x && x < chunks[1].right() && y >= chunks[1].y && y < chunks[1].bottom();
@@ -720,6 +2388,8 @@ impl App {
 
         match mouse.kind {
             MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+                // A release outside the terminal never reaches us
+                self.drag = None;
                 if in_parent_pane {
                     // Clicked in parent pane
                     let clicked_index = (y - chunks[0].y - 1) as usize + self.parent_scroll_offset;
@@ -736,10 +2406,24 @@ impl App {
                     let clicked_index = (y - chunks[1].y - 1) as usize + self.scroll_offset;
                     if clicked_index < self.entries.len() {
                         self.selected_index = clicked_index;
+                        let path = self.entries[clicked_index].path.clone();
                         // Navigate into directory if clicked on one (like yazi)
                         if self.entries[clicked_index].is_dir {
-                            let path = self.entries[clicked_index].path.clone();
                             self.load_directory(&path)?;
+                        } else {
+                            // A file may be dragged: the whole selection if it
+                            // is part of it, otherwise just this file
+                            let files = if self.selected_files.contains(&path) {
+                                self.selected_files.iter().cloned().collect()
+                            } else {
+                                vec![path]
+                            };
+                            self.drag = Some(DragState {
+                                files,
+                                position: (x, y),
+                                active: false,
+                                target: None,
+                            });
                         }
                         self.image_preview.reset_scroll();
                     }
@@ -754,6 +2438,24 @@ impl App {
                     }
                 }
             }
+            MouseEventKind::Down(crossterm::event::MouseButton::Middle) if in_current_pane => {
+                // Middle click opens the clicked file, like common file managers
+                let clicked_index = (y.saturating_sub(chunks[1].y + 1)) as usize + self.scroll_offset;
+                if let Some(entry) = self.entries.get(clicked_index) {
+                    if !entry.is_dir {
+                        let path = entry.path.clone();
+                        self.selected_index = clicked_index;
+                        self.open_with_system(&path)?;
+                    }
+                }
+            }
+            // Touchpad pinch arrives as Ctrl+scroll; zoom the preview image
+            MouseEventKind::ScrollUp if in_preview_pane && mouse.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.image_preview.zoom_in();
+            }
+            MouseEventKind::ScrollDown if in_preview_pane && mouse.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.image_preview.zoom_out();
+            }
             MouseEventKind::ScrollDown => {
                 if in_preview_pane {
                     // Scroll preview text down
@@ -778,8 +2480,69 @@ impl App {
         Ok(())
     }
 
+    fn handle_gallery_mouse(&mut self, mouse: MouseEvent, area: Rect) -> Result<()> {
+        let gallery = match self.gallery_view.as_mut() {
+            Some(g) => g,
+            None => return Ok(()),
+        };
+
+        if mouse.kind == MouseEventKind::Down(crossterm::event::MouseButton::Middle) {
+            if let Some(index) = gallery.hit_test(area, mouse.column, mouse.row) {
+                gallery.selected = index;
+                let path = gallery.images[index].clone();
+                self.open_with_system(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_slideshow_mouse(&mut self, mouse: MouseEvent) {
+        let slideshow = match self.slideshow_view.as_mut() {
+            Some(s) => s,
+            None => return,
+        };
+
+        // Touchpad pinch arrives as Ctrl+scroll
+        if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => slideshow.zoom.zoom_in(),
+                MouseEventKind::ScrollDown => slideshow.zoom.zoom_out(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Directory that would receive files dropped at (x, y): a directory
+    /// entry in the parent or current pane, or the parent directory itself
+    /// when dropped elsewhere in the parent pane.
+    fn drop_target(&self, x: u16, y: u16, chunks: &[Rect]) -> Option<PathBuf> {
+        let inside = |r: Rect| x >= r.x && x < r.right() && y >= r.y && y < r.bottom();
+        let row = |r: Rect| (y.checked_sub(r.y + 1)).map(|row| row as usize);
+
+        let target = if inside(chunks[0]) {
+            row(chunks[0])
+                .map(|row| row + self.parent_scroll_offset)
+                .and_then(|i| self.parent_entries.get(i))
+                .filter(|e| e.is_dir)
+                .map(|e| e.path.clone())
+                .or_else(|| self.current_dir.parent().map(|p| p.to_path_buf()))
+        } else if inside(chunks[1]) {
+            row(chunks[1])
+                .map(|row| row + self.scroll_offset)
+                .and_then(|i| self.entries.get(i))
+                .filter(|e| e.is_dir)
+                .map(|e| e.path.clone())
+        } else {
+            None
+        };
+
+        // Dropping onto the directory the files are already in is a no-op
+        target.filter(|t| *t != self.current_dir)
+    }
+
     fn handle_people_dialog_mouse(&mut self, mouse: MouseEvent, area: Rect) -> Result<()> {
-        use crate::ui::people_dialog::{InputMode, PeopleViewMode};
+        use crate::ui::people_dialog::InputMode;
 
         let dialog = match self.people_dialog.as_mut() {
             Some(d) => d,
@@ -850,8 +2613,12 @@ impl App {
                     // Account for list border (1 row for top border)
                     let list_local_y = local_y - list_start_y - 1;
 
-                    // Each item takes 2 rows (name + subtext)
-                    let clicked_index = (list_local_y / 2) as usize;
+                    // Each item takes 2 rows (name + subtext); the face
+                    // list may be scrolled
+                    let clicked_index = (list_local_y / 2) as usize + match dialog.view_mode {
+                        PeopleViewMode::People => 0,
+                        PeopleViewMode::Faces => dialog.scroll_offset,
+                    };
 
                     let max_index = match dialog.view_mode {
                         PeopleViewMode::People => dialog.people.len().saturating_sub(1),
@@ -1028,7 +2795,42 @@ impl App {
         metadata
     }
 
+    /// Queue a scan of the current directory, then LLM descriptions once it
+    /// completes, then face detection once those complete.
+    fn start_pipeline(&mut self) {
+        if self.pipeline.is_some() {
+            self.status_message = Some("A pipeline is already queued".to_string());
+            return;
+        }
+        let directory = self.current_dir.clone();
+        let prompt = self.db.get_directory_prompt(&directory.to_string_lossy()).ok().flatten();
+        self.pipeline = Some(Pipeline { directory, prompt });
+
+        let scan = self.task_manager.queue_task(TaskType::Scan, None);
+        let llm = self.task_manager.queue_task(TaskType::LlmBatch, Some(scan));
+        self.task_manager.queue_task(TaskType::FaceDetection, Some(llm));
+        self.status_message = Some("Pipeline queued: Scan → LLM → Faces".to_string());
+    }
+
+    /// Start a pipeline task released by the task manager.
+    fn start_queued_task(&mut self, task_type: TaskType) -> Result<()> {
+        let Some(pipeline) = self.pipeline.clone() else {
+            return Ok(());
+        };
+        match task_type {
+            TaskType::Scan => self.start_scan_in(pipeline.directory),
+            TaskType::LlmBatch => self.start_batch_llm_in(&pipeline.directory, pipeline.prompt),
+            TaskType::FaceDetection => self.start_face_scan_in(&pipeline.directory),
+            _ => Ok(()),
+        }
+    }
+
     fn start_scan(&mut self) -> Result<()> {
+        let dir = self.current_dir.clone();
+        self.start_scan_in(dir)
+    }
+
+    fn start_scan_in(&mut self, dir: PathBuf) -> Result<()> {
         // Don't start a new scan if one is already running
         if self.task_manager.is_running(TaskType::Scan) {
             self.status_message = Some("Scan already running".to_string());
@@ -1036,7 +2838,6 @@ impl App {
         }
 
         let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::Scan);
-        let dir = self.current_dir.clone();
         let config = self.config.clone();
         let db_config = self.config.database.clone();
 
@@ -1068,6 +2869,116 @@ impl App {
         Ok(())
     }
 
+    /// Scan every directory in `scanner.auto_scan_dirs` as one task.
+    fn start_scan_all(&mut self) {
+        let directories = self.config.scanner.auto_scan_dirs.clone();
+        if directories.is_empty() {
+            self.status_message = Some("No directories to scan: set scanner.auto_scan_dirs in the config".to_string());
+            return;
+        }
+        if self.task_manager.is_running(TaskType::Scan) {
+            self.status_message = Some("Scan already running".to_string());
+            return;
+        }
+
+        let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::Scan);
+        let config = self.config.clone();
+        let db_config = self.config.database.clone();
+        let count = directories.len();
+
+        std::thread::spawn(move || {
+            let db = match Database::open(&db_config).and_then(|db| db.initialize().map(|_| db)) {
+                Ok(db) => db,
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Failed to open database: {}", e),
+                    });
+                    return;
+                }
+            };
+
+            let scanner = Scanner::new(config);
+            scanner.scan_directories_cancellable(&directories, &db, tx, cancel_flag);
+        });
+
+        self.status_message = Some(format!("Scanning {} directories...", count));
+    }
+
+    fn handle_rescan_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.rescan_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.rescan_dialog = None;
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => dialog.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => dialog.move_up(),
+            KeyCode::Char(' ') => dialog.toggle(),
+            KeyCode::Enter => {
+                let fields = dialog.selected_fields();
+                if fields.is_empty() {
+                    self.status_message = Some("Tick at least one field with Space".to_string());
+                    return Ok(());
+                }
+                self.rescan_dialog = None;
+                self.mode = AppMode::Normal;
+                self.start_rescan_missing(&fields)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Rescan every photo lacking any of `fields`, to backfill metadata the
+    /// scanner did not extract when they were first scanned.
+    fn start_rescan_missing(&mut self, fields: &[&str]) -> Result<()> {
+        if self.task_manager.is_running(TaskType::Scan) {
+            self.status_message = Some("Scan already running".to_string());
+            return Ok(());
+        }
+        let paths: Vec<PathBuf> = self
+            .db
+            .get_photos_with_missing_fields(fields)?
+            .into_iter()
+            .map(|(_, path)| PathBuf::from(path))
+            .collect();
+        if paths.is_empty() {
+            self.status_message = Some("No photos are missing those fields".to_string());
+            return Ok(());
+        }
+
+        let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::Scan);
+        let config = self.config.clone();
+        let db_config = self.config.database.clone();
+        let count = paths.len();
+
+        std::thread::spawn(move || {
+            let db = match Database::open(&db_config) {
+                Ok(db) => db,
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Failed to open database: {}", e),
+                    });
+                    return;
+                }
+            };
+
+            let scanner = Scanner::new(config);
+            scanner.rescan_files_cancellable(&paths, &db, tx, cancel_flag);
+        });
+
+        self.status_message = Some(format!("Rescanning {} photos missing metadata...", count));
+        Ok(())
+    }
+
     fn find_duplicates(&mut self) -> Result<()> {
         // If we already have results, just re-enter the view
         if self.duplicates_view.is_some() {
@@ -1084,13 +2995,14 @@ impl App {
         let (_task_id, tx, _cancel_flag) = self.task_manager.register_task(TaskType::FindDuplicates);
         let db_config = self.config.database.clone();
         let threshold = self.config.scanner.similarity_threshold;
+        let embedding_threshold = self.config.scanner.embedding_similarity_threshold;
 
         // Channel to receive the computed groups
         let (groups_tx, groups_rx) = mpsc::channel();
         self.pending_duplicates = Some(groups_rx);
 
         std::thread::spawn(move || {
-            let db = match Database::open(&db_config) {
+            let db = match Database::open_read_only(&db_config) {
                 Ok(db) => db,
                 Err(e) => {
                     let _ = tx.send(TaskUpdate::Failed {
@@ -1100,36 +3012,141 @@ impl App {
                 }
             };
 
-            let _ = tx.send(TaskUpdate::Started { total: 0 });
+            let _ = tx.send(TaskUpdate::Started { total: 0 });
+
+            let mut all_groups = match db.find_exact_duplicates() {
+                Ok(g) => g,
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Exact duplicate search failed: {}", e),
+                    });
+                    return;
+                }
+            };
+
+            match db.find_perceptual_duplicates(threshold) {
+                Ok(perceptual) => all_groups.extend(perceptual),
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Perceptual duplicate search failed: {}", e),
+                    });
+                    return;
+                }
+            };
+
+            match db.find_embedding_clusters(embedding_threshold) {
+                Ok(similar) => all_groups.extend(similar),
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Embedding similarity search failed: {}", e),
+                    });
+                    return;
+                }
+            };
+
+            let count = all_groups.len();
+            let _ = groups_tx.send(all_groups);
+            let _ = tx.send(TaskUpdate::Completed {
+                message: format!("Found {} duplicate groups", count),
+            });
+        });
+
+        self.status_message = Some("Finding duplicates in background...".to_string());
+        Ok(())
+    }
+
+    fn handle_cross_dedup_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.cross_dedup_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+        let picker = &mut dialog.picker;
+
+        if picker.input_mode {
+            match key.code {
+                KeyCode::Esc => picker.input_mode = false,
+                KeyCode::Enter => picker.confirm_input(),
+                KeyCode::Backspace => picker.backspace(),
+                KeyCode::Char(c) => picker.handle_input(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Esc => {
+                self.cross_dedup_dialog = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => picker.move_down(),
+            KeyCode::Char('k') | KeyCode::Up => picker.move_up(),
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => picker.enter_selected(),
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => picker.go_parent(),
+            KeyCode::Char('/') => picker.toggle_input_mode(),
+            KeyCode::Char(' ') => {
+                if let Some((staging, library)) = dialog.choose() {
+                    if staging == library {
+                        dialog.staging = Some(staging);
+                        self.status_message = Some("Pick a library directory other than the staging one".to_string());
+                        return Ok(());
+                    }
+                    self.cross_dedup_dialog = None;
+                    self.mode = AppMode::Normal;
+                    self.find_cross_dir_duplicates(staging, library)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Look for duplicates between an import staging directory and the
+    /// library, then show them in the duplicates view with the staging
+    /// copies marked for deletion.
+    fn find_cross_dir_duplicates(&mut self, staging: PathBuf, library: PathBuf) -> Result<()> {
+        if self.task_manager.is_running(TaskType::FindDuplicates) {
+            self.status_message = Some("Duplicate detection already running...".to_string());
+            return Ok(());
+        }
+
+        let (_task_id, tx, _cancel_flag) = self.task_manager.register_task(TaskType::FindDuplicates);
+        let db_config = self.config.database.clone();
+        let threshold = self.config.scanner.similarity_threshold;
+
+        // Results replace any earlier duplicate search
+        self.duplicates_view = None;
+        let (groups_tx, groups_rx) = mpsc::channel();
+        self.pending_duplicates = Some(groups_rx);
 
-            let mut all_groups = match db.find_exact_duplicates() {
-                Ok(g) => g,
-                Err(e) => {
-                    let _ = tx.send(TaskUpdate::Failed {
-                        error: format!("Exact duplicate search failed: {}", e),
+        std::thread::spawn(move || {
+            let _ = tx.send(TaskUpdate::Started { total: 0 });
+            let result = Database::open_read_only(&db_config)
+                .and_then(|db| db.find_cross_dir_duplicates(&staging, &library, threshold));
+            match result {
+                Ok(groups) => {
+                    let count = groups.len();
+                    let _ = groups_tx.send(groups);
+                    let _ = tx.send(TaskUpdate::Completed {
+                        message: format!(
+                            "Found {} duplicate groups between {} and {}",
+                            count,
+                            staging.display(),
+                            library.display()
+                        ),
                     });
-                    return;
                 }
-            };
-
-            match db.find_perceptual_duplicates(threshold) {
-                Ok(perceptual) => all_groups.extend(perceptual),
                 Err(e) => {
                     let _ = tx.send(TaskUpdate::Failed {
-                        error: format!("Perceptual duplicate search failed: {}", e),
+                        error: format!("Cross-directory duplicate search failed: {}", e),
                     });
-                    return;
                 }
-            };
-
-            let count = all_groups.len();
-            let _ = groups_tx.send(all_groups);
-            let _ = tx.send(TaskUpdate::Completed {
-                message: format!("Found {} duplicate groups", count),
-            });
+            }
         });
 
-        self.status_message = Some("Finding duplicates in background...".to_string());
+        self.status_message = Some("Finding cross-directory duplicates in background...".to_string());
         Ok(())
     }
 
@@ -1177,6 +3194,20 @@ impl App {
                 }
             }
 
+            // Switch between exact, perceptual and similar groups
+            KeyCode::Tab => {
+                if let Some(ref mut view) = self.duplicates_view {
+                    view.next_tab();
+                    self.clear_on_next_render = true;
+                }
+            }
+            KeyCode::BackTab => {
+                if let Some(ref mut view) = self.duplicates_view {
+                    view.prev_tab();
+                    self.clear_on_next_render = true;
+                }
+            }
+
             // Toggle deletion mark
             KeyCode::Char(' ') => {
                 if let Some(ref mut view) = self.duplicates_view {
@@ -1233,6 +3264,25 @@ impl App {
                 }
             }
 
+            // Toggle the pixel diff panel
+            KeyCode::Char('d') => {
+                if !(self.config.preview.image_preview && self.image_preview.is_available()) {
+                    self.status_message = Some("Pixel diff needs image preview".to_string());
+                } else if let Some(ref mut view) = self.duplicates_view {
+                    view.toggle_pixel_diff();
+                    self.clear_on_next_render = true;
+                }
+            }
+
+            // Ask the LLM how the pair differs
+            KeyCode::Char('e') => {
+                if let Some(ref mut view) = self.duplicates_view {
+                    if !view.explain_pair(&self.llm_client) {
+                        self.status_message = Some("Need two photos to compare".to_string());
+                    }
+                }
+            }
+
             // Open current photo in external viewer
             KeyCode::Char('o') => {
                 if let Some(ref view) = self.duplicates_view {
@@ -1292,6 +3342,7 @@ impl App {
                     // Auto-empty duplicate trash if configured
                     if self.config.duplicate_trash.auto_empty {
                         if let Ok(cleanup) = self.duplicate_trash_manager.auto_empty() {
+                            crate::trash::TrashCleanupTask::forget_deleted(&self.db, &cleanup);
                             if cleanup.files_deleted > 0 {
                                 tracing::info!(
                                     "Auto-emptied duplicate trash: {} files, {} bytes freed",
@@ -1305,7 +3356,7 @@ impl App {
                     // Remove trashed photos from the in-memory view
                     if let Some(ref mut view) = self.duplicates_view {
                         view.remove_photos(&moved_ids);
-                        if view.groups.is_empty() {
+                        if view.is_empty() {
                             self.duplicates_view = None;
                             self.mode = AppMode::Normal;
                             self.status_message = Some("No more duplicates".to_string());
@@ -1357,7 +3408,7 @@ impl App {
                     // Remove deleted photos from the in-memory view
                     if let Some(ref mut view) = self.duplicates_view {
                         view.remove_photos(&deleted_ids);
-                        if view.groups.is_empty() {
+                        if view.is_empty() {
                             self.duplicates_view = None;
                             self.mode = AppMode::Normal;
                             self.status_message = Some("No more duplicates".to_string());
@@ -1381,8 +3432,18 @@ impl App {
         // Check if image preview is available to determine layout
         let has_preview = self.config.preview.image_preview && self.image_preview.is_available();
 
+        let pixel_diff = self
+            .duplicates_view
+            .as_ref()
+            .is_some_and(|v| v.mode == DuplicatesViewMode::PixelDiff);
+
         // Calculate layout to match render logic in duplicates.rs
-        let (groups_width, photos_start, photos_end) = if has_preview {
+        let (groups_width, photos_start, photos_end) = if has_preview && pixel_diff {
+            // Four-column layout: 20% groups | 30% photos | 25% preview | 25% diff
+            let groups_w = (area.width * 20) / 100;
+            let photos_w = (area.width * 30) / 100;
+            (groups_w, groups_w, groups_w + photos_w)
+        } else if has_preview {
             // Three-column layout: 25% groups | 40% photos | 35% preview
             let groups_w = (area.width * 25) / 100;
             let photos_w = (area.width * 40) / 100;
@@ -1608,14 +3669,19 @@ impl App {
     }
 
     fn start_batch_llm(&mut self, custom_prompt: Option<String>) -> Result<()> {
+        let dir = self.current_dir.clone();
+        self.start_batch_llm_in(&dir, custom_prompt)
+    }
+
+    fn start_batch_llm_in(&mut self, dir: &Path, custom_prompt: Option<String>) -> Result<()> {
         // Don't start if already processing
         if self.task_manager.is_running(TaskType::LlmBatch) {
             self.status_message = Some("Batch LLM already running".to_string());
             return Ok(());
         }
 
-        // Get photos without descriptions in current directory
-        let task_rows = self.db.get_photos_without_description_in_dir(&self.current_dir)?;
+        // Get photos without descriptions in the directory
+        let task_rows = self.db.get_photos_without_description_in_dir(dir)?;
         let tasks: Vec<crate::llm::LlmTask> = task_rows.into_iter().map(|(id, path)| {
             crate::llm::LlmTask { photo_id: id, photo_path: PathBuf::from(path) }
         }).collect();
@@ -1626,7 +3692,7 @@ impl App {
         }
 
         let total = tasks.len();
-        let concurrency = self.config.llm.batch_concurrency;
+        let concurrency = self.config.llm.concurrency();
         let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::LlmBatch);
         let mut llm_config = self.config.llm.clone();
         if let Some(prompt) = custom_prompt {
@@ -1647,6 +3713,20 @@ impl App {
         Ok(())
     }
 
+    /// Re-cluster described photos into events in the background
+    fn start_event_detection(&mut self) {
+        if self.task_manager.is_running(TaskType::EventDetection) {
+            return;
+        }
+
+        let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::EventDetection);
+        let llm_config = self.config.llm.clone();
+        let db_config = self.config.database.clone();
+        std::thread::spawn(move || {
+            crate::llm::events::detect_events(&db_config, &llm_config, tx, cancel_flag);
+        });
+    }
+
     // --- Multi-select and Visual mode methods ---
 
     fn toggle_selection(&mut self) {
@@ -1671,7 +3751,10 @@ impl App {
     }
 
     fn exit_visual_mode(&mut self) {
-        self.mode = AppMode::Normal;
+        // Clearing a selection does not stop watch mode
+        if self.mode != AppMode::Watching {
+            self.mode = AppMode::Normal;
+        }
         self.visual_anchor = None;
         let count = self.selected_files.len();
         if count > 0 {
@@ -1795,7 +3878,10 @@ impl App {
             }
             KeyCode::Char('m') => {
                 // Confirm move
-                self.execute_move()?;
+                if let Some(dialog) = self.move_dialog.take() {
+                    let target_dir = dialog.target_dir().clone();
+                    self.execute_move(dialog.files_to_move, target_dir)?;
+                }
             }
             _ => {}
         }
@@ -1803,80 +3889,119 @@ impl App {
         Ok(())
     }
 
-    fn execute_move(&mut self) -> Result<()> {
-        let dialog = match self.move_dialog.take() {
-            Some(d) => d,
-            None => return Ok(()),
-        };
+    fn execute_move(&mut self, files_to_move: Vec<PathBuf>, target_dir: PathBuf) -> Result<()> {
+        if self.dry_run {
+            let mut would_move = 0;
+            for source_path in &files_to_move {
+                if let Some(filename) = source_path.file_name() {
+                    let target_path = target_dir.join(filename);
+                    if target_path.exists() {
+                        tracing::info!("WOULD SKIP: {} (already exists)", target_path.display());
+                    } else {
+                        tracing::info!("WOULD MOVE: {} → {}", source_path.display(), target_path.display());
+                        would_move += 1;
+                    }
+                }
+            }
+            self.mode = AppMode::Normal;
+            self.status_message = Some(format!(
+                "Dry run: would move {} of {} files to {}",
+                would_move,
+                files_to_move.len(),
+                target_dir.display()
+            ));
+            return Ok(());
+        }
 
-        let target_dir = dialog.target_dir().clone();
-        let files_to_move = dialog.files_to_move;
+        // Ask about every taken name before moving anything
+        let conflicts = find_conflicts(&files_to_move, &target_dir);
+        if !conflicts.is_empty() {
+            self.conflict_dialog = Some(ConflictDialog::new(files_to_move, target_dir, conflicts));
+            self.mode = AppMode::ConflictResolving;
+            return Ok(());
+        }
 
-        let mut moved = 0;
-        let mut failed = 0;
+        self.perform_move(files_to_move, target_dir, HashMap::new())
+    }
 
-        for source_path in &files_to_move {
-            if let Some(filename) = source_path.file_name() {
-                let target_path = target_dir.join(filename);
+    /// Move files in a background task, resolving taken names as chosen in
+    /// the conflict dialog. A progress dialog stays up until it finishes.
+    fn perform_move(
+        &mut self,
+        files_to_move: Vec<PathBuf>,
+        target_dir: PathBuf,
+        resolutions: HashMap<PathBuf, Resolution>,
+    ) -> Result<()> {
+        let (task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::MoveFiles);
+        let (outcome_tx, outcome_rx) = mpsc::channel();
+        let db_config = self.config.database.clone();
+        let total = files_to_move.len();
 
-                // Check for conflicts
-                if target_path.exists() {
-                    // Skip existing files (could add overwrite option later)
-                    self.status_message = Some(format!(
-                        "Skipped {}: already exists",
-                        filename.to_string_lossy()
-                    ));
-                    failed += 1;
-                    continue;
+        std::thread::spawn(move || {
+            let db = match Database::open(&db_config) {
+                Ok(db) => db,
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Failed to open database: {}", e),
+                    });
+                    return;
                 }
+            };
+            let sizes = transfer::file_sizes(&files_to_move);
+            let mut progress = TransferProgress::start(tx.clone(), files_to_move.len(), sizes.values().sum());
+            let summary = transfer::move_files(
+                &db,
+                &files_to_move,
+                &target_dir,
+                &resolutions,
+                &sizes,
+                &mut progress,
+                &cancel_flag,
+            );
+            let message = summary.message(&target_dir);
+            let cancelled = summary.cancelled;
+            let _ = outcome_tx.send(BatchOutcome::Move { target_dir, summary });
+            let _ = tx.send(if cancelled { TaskUpdate::Cancelled } else { TaskUpdate::Completed { message } });
+        });
 
-                // Perform the move
-                match std::fs::rename(source_path, &target_path) {
-                    Ok(_) => {
-                        // Update database path
-                        if let Err(e) = self.db.update_photo_path(source_path, &target_path) {
-                            tracing::warn!(error = %e, "Failed to update DB path");
-                        }
-                        moved += 1;
-                    }
-                    Err(_) => {
-                        // Try copy + delete for cross-filesystem moves
-                        if let Err(copy_err) = std::fs::copy(source_path, &target_path) {
-                            self.status_message = Some(format!(
-                                "Failed to move {}: {}",
-                                filename.to_string_lossy(),
-                                copy_err
-                            ));
-                            failed += 1;
-                            continue;
-                        }
-                        if let Err(del_err) = std::fs::remove_file(source_path) {
-                            self.status_message = Some(format!(
-                                "Warning: Copied but failed to delete original: {}",
-                                del_err
-                            ));
-                        }
-                        // Update database path
-                        if let Err(e) = self.db.update_photo_path(source_path, &target_path) {
-                            tracing::warn!(error = %e, "Failed to update DB path");
-                        }
-                        moved += 1;
-                    }
-                }
-            }
-        }
+        self.batch_progress = Some(BatchProgressDialog {
+            task_id,
+            verb: "Moving",
+            total,
+            outcome: outcome_rx,
+        });
+        self.mode = AppMode::BatchProgress;
+        Ok(())
+    }
 
-        // Clear selection and refresh directory
-        self.selected_files.clear();
-        self.load_directory(&self.current_dir.clone())?;
+    fn handle_conflict_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(dialog) = self.conflict_dialog.as_mut() else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
 
-        self.mode = AppMode::Normal;
-        if failed > 0 {
-            self.status_message = Some(format!("Moved {} files, {} failed", moved, failed));
-        } else {
-            self.status_message = Some(format!("Moved {} files to {}", moved, target_dir.display()));
+        match key.code {
+            KeyCode::Esc => {
+                self.conflict_dialog = None;
+                self.mode = AppMode::Normal;
+                self.status_message = Some("Move cancelled".to_string());
+            }
+            KeyCode::Char('j') | KeyCode::Down => dialog.move_down(),
+            KeyCode::Char('k') | KeyCode::Up => dialog.move_up(),
+            KeyCode::Char('s') => dialog.resolve_selected(Resolution::Skip),
+            KeyCode::Char('o') => dialog.resolve_selected(Resolution::Overwrite),
+            KeyCode::Char('b') => dialog.resolve_selected(Resolution::KeepBoth),
+            KeyCode::Char('S') => dialog.resolve_all(Resolution::Skip),
+            KeyCode::Char('O') => dialog.resolve_all(Resolution::Overwrite),
+            KeyCode::Char('B') => dialog.resolve_all(Resolution::KeepBoth),
+            KeyCode::Enter => {
+                if let Some(dialog) = self.conflict_dialog.take() {
+                    let resolutions = dialog.resolutions_by_source();
+                    self.perform_move(dialog.files, dialog.target_dir, resolutions)?;
+                }
+            }
+            _ => {}
         }
-
         Ok(())
     }
 
@@ -1924,6 +4049,15 @@ impl App {
 
         let dialog = self.rename_dialog.as_mut().unwrap();
 
+        // A single file about to replace another waits for y/n
+        if dialog.confirming_overwrite {
+            dialog.confirming_overwrite = false;
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                return self.execute_rename();
+            }
+            return Ok(());
+        }
+
         match key.code {
             KeyCode::Esc => {
                 self.rename_dialog = None;
@@ -1931,28 +4065,15 @@ impl App {
                 self.status_message = Some("Rename cancelled".to_string());
             }
             KeyCode::Enter => {
-                // Execute rename
-                match dialog.execute() {
-                    Ok((success, failed)) => {
-                        self.rename_dialog = None;
-                        self.mode = AppMode::Normal;
-                        self.selected_files.clear();
-
-                        // Refresh directory
-                        self.load_directory(&self.current_dir.clone())?;
-
-                        if failed > 0 {
-                            self.status_message =
-                                Some(format!("Renamed {} files, {} failed", success, failed));
-                        } else {
-                            self.status_message = Some(format!("Renamed {} files", success));
-                        }
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Rename error: {}", e));
-                    }
+                if dialog.error.is_none() && dialog.needs_overwrite_confirmation() {
+                    dialog.confirming_overwrite = true;
+                } else {
+                    self.execute_rename()?;
                 }
             }
+            KeyCode::Tab => {
+                dialog.cycle_conflict_strategy();
+            }
             KeyCode::Left => {
                 dialog.move_cursor_left();
             }
@@ -1980,6 +4101,34 @@ impl App {
         Ok(())
     }
 
+    fn execute_rename(&mut self) -> Result<()> {
+        let Some(dialog) = self.rename_dialog.as_ref() else { return Ok(()) };
+        match dialog.execute() {
+            Ok(summary) => {
+                self.rename_dialog = None;
+                self.mode = AppMode::Normal;
+                self.selected_files.clear();
+                self.operation_history.record(ReversibleOperation::Rename(summary.moves));
+
+                // Refresh directory
+                self.load_directory(&self.current_dir.clone())?;
+
+                let mut message = format!("Renamed {} files", summary.renamed);
+                if summary.skipped > 0 {
+                    message.push_str(&format!(", {} skipped (name taken)", summary.skipped));
+                }
+                if summary.failed > 0 {
+                    message.push_str(&format!(", {} failed", summary.failed));
+                }
+                self.status_message = Some(message);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Rename error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
     // --- Export dialog methods ---
 
     fn open_export_dialog(&mut self) -> Result<()> {
@@ -2008,20 +4157,32 @@ impl App {
             KeyCode::Char('k') | KeyCode::Up => {
                 dialog.move_up();
             }
+            KeyCode::Char('s') => {
+                dialog.toggle_sidecar_location();
+            }
             KeyCode::Enter => {
                 // Execute export
                 let format = dialog.selected_format();
                 let output_path = dialog.output_path().clone();
+                let beside_photos = format == crate::export::ExportFormat::LightroomCatalog && dialog.sidecars_beside_photos;
 
-                match crate::export::export_photos(&self.db, &output_path, format) {
-                    Ok(count) => {
+                let result = if beside_photos {
+                    crate::export::export_xmp_sidecars(&self.db, None).map(|report| {
+                        let mut message = format!("Wrote {} XMP sidecars next to their photos", report.written);
+                        if report.skipped > 0 {
+                            message.push_str(&format!(", skipped {} already taken", report.skipped));
+                        }
+                        message
+                    })
+                } else {
+                    crate::export::export_photos(&self.db, &output_path, format)
+                        .map(|count| format!("Exported {} photos to {}", count, output_path.display()))
+                };
+                match result {
+                    Ok(message) => {
                         self.export_dialog = None;
                         self.mode = AppMode::Normal;
-                        self.status_message = Some(format!(
-                            "Exported {} photos to {}",
-                            count,
-                            output_path.display()
-                        ));
+                        self.status_message = Some(message);
                     }
                     Err(e) => {
                         self.status_message = Some(format!("Export error: {}", e));
@@ -2037,11 +4198,52 @@ impl App {
     // --- Search dialog methods ---
 
     fn open_search_dialog(&mut self) -> Result<()> {
-        self.search_dialog = Some(SearchDialog::new());
+        let current_dir = self.current_dir.to_string_lossy().to_string();
+        let dir_name = self
+            .current_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| current_dir.clone());
+
+        let mut scopes = vec![(SearchScope::Directory(current_dir), format!("Directory: {}", dir_name))];
+        for album in self.db.get_all_albums().unwrap_or_default() {
+            scopes.push((SearchScope::Album(album.id), format!("Album: {}", album.name)));
+        }
+        for tag in self.db.get_all_tags().unwrap_or_default() {
+            scopes.push((SearchScope::Tag(tag.id), format!("Tag: {}", tag.name)));
+        }
+        for person in self.db.get_all_people().unwrap_or_default() {
+            scopes.push((SearchScope::Person(person.id), format!("Person: {}", person.name)));
+        }
+
+        // Start from what the browser is showing
+        let current = match self.tag_filter {
+            Some(ref tag) => SearchScope::Tag(tag.id),
+            None => SearchScope::All,
+        };
+
+        self.search_dialog = Some(SearchDialog::new().with_scopes(scopes, &current));
         self.mode = AppMode::Searching;
         Ok(())
     }
 
+    /// Open the search dialog with the selected photo as the query.
+    fn search_by_image(&mut self) -> Result<()> {
+        let path = match self.selected_entry() {
+            Some(entry) if !entry.is_dir => entry.path.clone(),
+            _ => {
+                self.status_message = Some("Select a photo to search by".to_string());
+                return Ok(());
+            }
+        };
+
+        self.open_search_dialog()?;
+        if let Some(dialog) = self.search_dialog.as_mut() {
+            dialog.set_image_query(path);
+        }
+        self.execute_semantic_search()
+    }
+
     fn handle_search_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
         if self.search_dialog.is_none() {
             self.mode = AppMode::Normal;
@@ -2050,23 +4252,140 @@ impl App {
 
         let dialog = self.search_dialog.as_mut().unwrap();
 
+        match dialog.mode {
+            SearchDialogMode::NamingSave => {
+                match key.code {
+                    KeyCode::Esc => dialog.mode = SearchDialogMode::Query,
+                    KeyCode::Backspace => {
+                        dialog.save_name.pop();
+                    }
+                    KeyCode::Enter => {
+                        let name = dialog.save_name.trim().to_string();
+                        if !name.is_empty() {
+                            self.db.save_search(&name, &dialog.query, &dialog.scope())?;
+                            dialog.status = Some(format!("Saved search \"{}\"", name));
+                            dialog.mode = SearchDialogMode::Query;
+                        }
+                    }
+                    KeyCode::Char(c) => dialog.save_name.push(c),
+                    _ => {}
+                }
+                return Ok(());
+            }
+            SearchDialogMode::PickingSaved => {
+                match key.code {
+                    KeyCode::Esc => dialog.mode = SearchDialogMode::Query,
+                    KeyCode::Down | KeyCode::Char('j') => dialog.move_saved_down(),
+                    KeyCode::Up | KeyCode::Char('k') => dialog.move_saved_up(),
+                    KeyCode::Delete => {
+                        if let Some(search) = dialog.selected_saved().cloned() {
+                            self.db.delete_saved_search(search.id)?;
+                            let saved = self.db.get_saved_searches()?;
+                            let index = dialog.saved_index.min(saved.len().saturating_sub(1));
+                            dialog.open_saved(saved);
+                            dialog.saved_index = index;
+                            dialog.status = Some(format!("Removed saved search \"{}\"", search.name));
+                            if dialog.saved.is_empty() {
+                                dialog.mode = SearchDialogMode::Query;
+                            }
+                        }
+                    }
+                    KeyCode::Enter => {
+                        if let Some(search) = dialog.selected_saved().cloned() {
+                            dialog.load_saved(&search);
+                            self.execute_semantic_search()?;
+                        }
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            SearchDialogMode::EditingFaces => {
+                match key.code {
+                    KeyCode::Esc => dialog.mode = SearchDialogMode::Query,
+                    KeyCode::Backspace => {
+                        dialog.faces_input.pop();
+                    }
+                    KeyCode::Enter => {
+                        if !dialog.apply_faces_input() {
+                            dialog.status = Some("Face count must look like 5+, 3, 2-4 or -3".to_string());
+                        } else if dialog.has_query() {
+                            self.execute_semantic_search()?;
+                        }
+                    }
+                    KeyCode::Char(c) => dialog.faces_input.push(c),
+                    _ => {}
+                }
+                return Ok(());
+            }
+            SearchDialogMode::PickingColor => {
+                match key.code {
+                    KeyCode::Esc => dialog.mode = SearchDialogMode::Query,
+                    KeyCode::Left | KeyCode::Char('h') => dialog.move_color(false),
+                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => dialog.move_color(true),
+                    KeyCode::Enter => {
+                        dialog.pick_color();
+                        self.execute_semantic_search()?;
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            SearchDialogMode::Query => {}
+        }
+
         match key.code {
             KeyCode::Esc => {
                 self.search_dialog = None;
                 self.mode = AppMode::Normal;
             }
             KeyCode::Enter => {
-                // Execute search
-                if !dialog.query.is_empty() {
+                // Execute search; face limits alone list photos by face count
+                if dialog.is_load_more_selected() {
+                    self.load_more_search_results()?;
+                } else if dialog.has_query() {
                     self.execute_semantic_search()?;
                 }
             }
-            KeyCode::Tab | KeyCode::Down => {
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                dialog.start_faces_edit();
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                dialog.start_color_pick();
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if dialog.query.trim().is_empty() {
+                    dialog.status = Some("Type a query to save".to_string());
+                } else {
+                    dialog.start_save();
+                }
+            }
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let saved = self.db.get_saved_searches()?;
+                if saved.is_empty() {
+                    dialog.status = Some("No saved searches yet (Ctrl+S saves one)".to_string());
+                } else {
+                    dialog.open_saved(saved);
+                }
+            }
+            KeyCode::Tab => {
+                dialog.next_scope();
+            }
+            KeyCode::BackTab => {
+                dialog.prev_scope();
+            }
+            KeyCode::Down => {
                 dialog.move_selection_down();
             }
-            KeyCode::BackTab | KeyCode::Up => {
+            KeyCode::Up => {
                 dialog.move_selection_up();
             }
+            KeyCode::PageDown => {
+                dialog.page_down();
+            }
+            KeyCode::PageUp => {
+                dialog.page_up();
+            }
             KeyCode::Left => {
                 dialog.move_cursor_left();
             }
@@ -2107,11 +4426,64 @@ impl App {
     }
 
     fn execute_semantic_search(&mut self) -> Result<()> {
+        self.run_semantic_search(None)
+    }
+
+    /// Re-run the current search with a larger limit and append the new results
+    fn load_more_search_results(&mut self) -> Result<()> {
+        let limit = match self.search_dialog.as_ref() {
+            Some(d) => d.result_limit + SEARCH_PAGE_SIZE,
+            None => return Ok(()),
+        };
+        self.run_semantic_search(Some(limit))
+    }
+
+    /// Search with the dialog's query. `more` is the larger limit to fetch
+    /// when loading more results; `None` starts a new search.
+    fn run_semantic_search(&mut self, more: Option<usize>) -> Result<()> {
         // Extract query before borrowing dialog mutably
-        let query = match self.search_dialog.as_ref() {
-            Some(d) => d.query.clone(),
+        let (query, image_query, color, filter) = match self.search_dialog.as_ref() {
+            Some(d) => (d.query.clone(), d.image_query.clone(), d.query_color(), d.filter()),
             None => return Ok(()),
         };
+        let limit = more.unwrap_or(SEARCH_PAGE_SIZE);
+
+        if let Some((name, rgb)) = color {
+            let results = self.search_by_color(rgb, limit, &filter)?;
+            if let Some(dialog) = self.search_dialog.as_mut() {
+                if results.is_empty() && more.is_none() {
+                    dialog.results.clear();
+                    dialog.status = Some(format!("No {} photos found (photos scanned before colors were stored need a rescan)", name));
+                } else {
+                    dialog.show_results(results, limit, more.is_some());
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(path) = image_query {
+            let results = self.search_by_image_embedding(&path, limit, &filter)?;
+            if let Some(dialog) = self.search_dialog.as_mut() {
+                match results {
+                    Some(results) => dialog.show_results(results, limit, more.is_some()),
+                    None => {
+                        dialog.results.clear();
+                        dialog.status = Some("No embedding for this photo yet (press E to generate)".to_string());
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        // Face count limits without a query list matching photos directly
+        if query.trim().is_empty() {
+            let limit = more.unwrap_or(FACE_LISTING_LIMIT);
+            let results = self.db.get_photos_by_face_count(&filter, limit)?;
+            if let Some(dialog) = self.search_dialog.as_mut() {
+                dialog.show_results(results, limit, more.is_some());
+            }
+            return Ok(());
+        }
 
         // Update dialog status
         if let Some(dialog) = self.search_dialog.as_mut() {
@@ -2119,8 +4491,11 @@ impl App {
             dialog.status = Some("Searching...".to_string());
         }
 
+        // AND/OR/NOT queries embed each term separately and combine the scores
+        let ast = parse_search_query(&query);
+
         // Try CLIP embedding search first (local, no API needed)
-        let results = match self.try_clip_search(&query) {
+        let results = match self.try_clip_search(&ast, limit, &filter) {
             Ok(results) if !results.is_empty() => {
                 // CLIP search succeeded
                 results
@@ -2128,30 +4503,70 @@ impl App {
             _ => {
                 // Fall back to LLM-based search
                 if self.llm_client.supports_embeddings() {
-                    match self.llm_client.get_text_embedding(&query) {
-                        Ok(query_embedding) => {
-                            match self.db.semantic_search(&query_embedding, 20, 0.3) {
+                    match embed_terms(&ast, |term| self.llm_client.get_text_embedding(term)) {
+                        Ok(term_embeddings) => {
+                            match self.db.semantic_search_query(&ast, &term_embeddings, limit, 0.3, &filter) {
                                 Ok(results) if !results.is_empty() => results,
-                                _ => self.db.semantic_search_by_text(&query, 20)?
+                                _ => self.db.semantic_search_by_text_scoped(&query, limit, &filter)?
                             }
                         }
-                        Err(_) => self.db.semantic_search_by_text(&query, 20)?
+                        Err(_) => self.db.semantic_search_by_text_scoped(&query, limit, &filter)?
                     }
                 } else {
-                    self.db.semantic_search_by_text(&query, 20)?
+                    self.db.semantic_search_by_text_scoped(&query, limit, &filter)?
                 }
             }
         };
 
         // Set results
         if let Some(dialog) = self.search_dialog.as_mut() {
-            dialog.set_results(results);
+            dialog.show_results(results, limit, more.is_some());
         }
         Ok(())
     }
 
+    /// Photos with a palette color within `MATCH_DISTANCE` of `rgb`, closest
+    /// first. Similarity falls from 100% as the ΔE grows.
+    fn search_by_color(&self, rgb: [u8; 3], limit: usize, filter: &SearchFilter) -> Result<Vec<crate::db::SearchResult>> {
+        use crate::scanner::color::{palette_distance, MATCH_DISTANCE};
+
+        let mut results: Vec<_> = self
+            .db
+            .get_color_palettes(filter)?
+            .into_iter()
+            .filter_map(|(mut result, palette)| {
+                let distance = palette_distance(&palette, rgb);
+                (distance <= MATCH_DISTANCE).then(|| {
+                    result.similarity = (1.0 - distance / 100.0) as f32;
+                    result
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity).then_with(|| a.path.cmp(&b.path)));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    /// Photos whose embeddings are closest to the stored embedding of `path`,
+    /// leaving out the photo itself. None if it has no embedding.
+    fn search_by_image_embedding(&self, path: &std::path::Path, limit: usize, filter: &SearchFilter) -> Result<Option<Vec<crate::db::SearchResult>>> {
+        let photo_id = match self.db.get_photo_metadata(path)? {
+            Some(meta) => meta.id,
+            None => return Ok(None),
+        };
+        let record = match self.db.get_embedding(photo_id)? {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+
+        let mut results = self.db.semantic_search(&record.embedding, limit + 1, 0.5, filter)?;
+        results.retain(|r| r.photo_id != photo_id);
+        results.truncate(limit);
+        Ok(Some(results))
+    }
+
     /// Try to search using CLIP embeddings (local, no API needed)
-    fn try_clip_search(&self, query: &str) -> Result<Vec<crate::db::SearchResult>> {
+    fn try_clip_search(&self, query: &SearchAst, limit: usize, filter: &SearchFilter) -> Result<Vec<crate::db::SearchResult>> {
         use crate::clip::ClipModel;
 
         // Check if we have any CLIP embeddings
@@ -2160,25 +4575,30 @@ impl App {
             return Ok(Vec::new());
         }
 
-        // Generate text embedding using CLIP
+        // Generate text embeddings using CLIP
         let clip = ClipModel::new();
-        let query_embedding = clip.embed_text(query)?;
+        let term_embeddings = embed_terms(query, |term| clip.embed_text(term))?;
 
         // Search against stored CLIP embeddings
-        self.db.semantic_search(&query_embedding, 20, 0.2)
+        self.db.semantic_search_query(query, &term_embeddings, limit, 0.2, filter)
     }
 
     // --- Face scanning methods ---
 
     fn start_face_scan(&mut self) -> Result<()> {
+        let dir = self.current_dir.clone();
+        self.start_face_scan_in(&dir)
+    }
+
+    fn start_face_scan_in(&mut self, dir: &Path) -> Result<()> {
         // Don't start if already scanning
         if self.task_manager.is_running(TaskType::FaceDetection) {
             self.status_message = Some("Face scan already running".to_string());
             return Ok(());
         }
 
-        // Get photos without faces in current directory (and subdirectories)
-        let current_dir = self.current_dir.to_string_lossy().to_string();
+        // Get photos without faces in the directory (and subdirectories)
+        let current_dir = dir.to_string_lossy().to_string();
         let photos = self.db.get_photos_without_faces_in_dir(&current_dir, 100)?;
 
         if photos.is_empty() {
@@ -2222,8 +4642,7 @@ impl App {
             return Ok(());
         }
 
-        // Use a default threshold of 0.6 for face similarity
-        let threshold = 0.6;
+        let threshold = crate::faces::DEFAULT_SIMILARITY_THRESHOLD;
         let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::FaceClustering);
         let db_config = self.config.database.clone();
 
@@ -2246,6 +4665,37 @@ impl App {
         Ok(())
     }
 
+    /// Re-embed faces that are missing an embedding or were embedded by an
+    /// older model (background task)
+    fn regenerate_face_embeddings(&mut self) -> Result<()> {
+        use crate::tasks::TaskType;
+
+        if self.task_manager.is_running(TaskType::FaceEmbeddingRegen) {
+            self.status_message = Some("Face embedding regeneration already running".to_string());
+            return Ok(());
+        }
+
+        let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::FaceEmbeddingRegen);
+        let db_config = self.config.database.clone();
+
+        std::thread::spawn(move || {
+            let db = match crate::db::Database::open(&db_config) {
+                Ok(db) => db,
+                Err(e) => {
+                    let _ = tx.send(crate::tasks::TaskUpdate::Failed {
+                        error: format!("Failed to open database: {}", e),
+                    });
+                    return;
+                }
+            };
+
+            crate::faces::regenerate_face_embeddings_background(&db, tx, cancel_flag);
+        });
+
+        self.status_message = Some("Regenerating face embeddings in background...".to_string());
+        Ok(())
+    }
+
     // --- CLIP embedding methods ---
 
     /// Start CLIP embedding generation for photos in current directory
@@ -2258,9 +4708,15 @@ impl App {
             return Ok(());
         }
 
-        // Get photos without embeddings in current directory
+        // Get photos without embeddings in current directory, including
+        // those embedded by an older model once the model has changed
         let current_dir = self.current_dir.to_string_lossy().to_string();
-        let photos = self.db.get_photos_without_embeddings_in_dir(&current_dir, 100)?;
+        let model_version = crate::clip::ClipModel::model_version();
+        let photos = if self.clip_model_changed {
+            self.db.get_photos_with_stale_clip_embeddings_in_dir(&current_dir, model_version, 100)?
+        } else {
+            self.db.get_photos_without_embeddings_in_dir(&current_dir, 100)?
+        };
 
         if photos.is_empty() {
             self.status_message = Some("No photos need embedding in this directory".to_string());
@@ -2322,7 +4778,7 @@ impl App {
                 // Generate embedding
                 match clip.embed_image_file(std::path::Path::new(path)) {
                     Ok(embedding) => {
-                        if let Err(e) = db.store_embedding(*photo_id, &embedding, "clip-vit-base-patch32") {
+                        if let Err(e) = db.store_embedding(*photo_id, &embedding, model_version) {
                             tracing::error!(path = %path, error = %e, "Failed to store CLIP embedding");
                         } else {
                             processed += 1;
@@ -2485,10 +4941,6 @@ impl App {
 
     /// Move selected files to trash
     fn trash_selected(&mut self) -> Result<()> {
-        // Save current position to restore after deletion
-        let saved_index = self.selected_index;
-        let original_count = self.entries.len();
-
         let files_to_trash: Vec<PathBuf> = if self.selected_files.is_empty() {
             // Use current selection
             if let Some(entry) = self.selected_entry() {
@@ -2515,7 +4967,48 @@ impl App {
             return Ok(());
         }
 
-        let mut trashed = 0;
+        self.trash_files(files_to_trash)
+    }
+
+    /// Trash every photo in the current directory labeled red, e.g. by the
+    /// auto-cull.
+    fn trash_red_labeled(&mut self) -> Result<()> {
+        let red: Vec<PathBuf> = self
+            .entries
+            .iter()
+            .filter(|e| !e.is_dir)
+            .filter(|e| {
+                self.db
+                    .get_photo_metadata(&e.path)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|photo| photo.color_label.as_deref() == Some("red"))
+            })
+            .map(|e| e.path.clone())
+            .collect();
+
+        if red.is_empty() {
+            self.status_message = Some("No red-labeled photos in this directory".to_string());
+            return Ok(());
+        }
+
+        self.trash_files(red)
+    }
+
+    fn trash_files(&mut self, files_to_trash: Vec<PathBuf>) -> Result<()> {
+        // Save current position to restore after deletion
+        let saved_index = self.selected_index;
+        let original_count = self.entries.len();
+
+        if self.dry_run {
+            for path in &files_to_trash {
+                tracing::info!("WOULD TRASH: {}", path.display());
+            }
+            self.status_message = Some(format!("Dry run: would trash {} files", files_to_trash.len()));
+            return Ok(());
+        }
+
+        let mut trashed = Vec::new();
         let mut failed = 0;
 
         for path in &files_to_trash {
@@ -2529,7 +5022,7 @@ impl App {
                             tracing::error!(error = %e, path = ?path, "Failed to mark as trashed in DB");
                         }
                     }
-                    trashed += 1;
+                    trashed.push(TrashedFile { original_path: path.clone(), trash_path, photo_id });
                 }
                 Err(e) => {
                     tracing::error!(error = %e, path = ?path, "Failed to move to trash");
@@ -2538,6 +5031,12 @@ impl App {
             }
         }
 
+        let trashed_count = trashed.len();
+        if trashed_count > 0 {
+            self.operation_history.record(ReversibleOperation::Trash(trashed));
+            self.enforce_trash_size_limit();
+        }
+
         // Refresh directory listing
         self.load_directory(&self.current_dir.clone())?;
         self.clear_selection();
@@ -2559,9 +5058,9 @@ impl App {
         }
 
         if failed > 0 {
-            self.status_message = Some(format!("Trashed {} files, {} failed", trashed, failed));
+            self.status_message = Some(format!("Trashed {} files, {} failed", trashed_count, failed));
         } else {
-            self.status_message = Some(format!("Moved {} files to trash", trashed));
+            self.status_message = Some(format!("Moved {} files to trash", trashed_count));
         }
 
         Ok(())
@@ -2611,7 +5110,27 @@ impl App {
         }
 
         let target_dir = self.current_dir.clone();
-        let mut moved = 0;
+
+        // Keep the clipboard so the paste can still be done for real
+        if self.dry_run {
+            let mut would_move = 0;
+            for source_path in &self.clipboard {
+                let target_path = target_dir.join(source_path.file_name().unwrap_or_default());
+                if *source_path == target_path {
+                    continue;
+                }
+                if target_path.exists() {
+                    tracing::info!("WOULD SKIP: {} (already exists)", target_path.display());
+                } else {
+                    tracing::info!("WOULD MOVE: {} → {}", source_path.display(), target_path.display());
+                    would_move += 1;
+                }
+            }
+            self.status_message = Some(format!("Dry run: would paste {} files", would_move));
+            return Ok(());
+        }
+
+        let mut moved = Vec::new();
         let mut failed = 0;
 
         for source_path in self.clipboard.drain(..).collect::<Vec<_>>() {
@@ -2637,7 +5156,7 @@ impl App {
                     if let Err(e) = self.db.update_photo_path(&source_path, &target_path) {
                         tracing::warn!(error = %e, "Failed to update DB path");
                     }
-                    moved += 1;
+                    moved.push((source_path, target_path));
                 }
                 Err(_) => {
                     // Try copy + delete for cross-filesystem moves
@@ -2653,20 +5172,180 @@ impl App {
                     if let Err(e) = self.db.update_photo_path(&source_path, &target_path) {
                         tracing::warn!(error = %e, "Failed to update DB path");
                     }
-                    moved += 1;
+                    moved.push((source_path, target_path));
                 }
             }
         }
+        let moved_count = moved.len();
+        self.operation_history.record(ReversibleOperation::Move(moved));
 
         // Refresh directory listing
         self.load_directory(&self.current_dir.clone())?;
 
         if failed > 0 {
-            self.status_message = Some(format!("Moved {} files, {} failed", moved, failed));
-        } else if moved > 0 {
-            self.status_message = Some(format!("Pasted {} files", moved));
+            self.status_message = Some(format!("Moved {} files, {} failed", moved_count, failed));
+        } else if moved_count > 0 {
+            self.status_message = Some(format!("Pasted {} files", moved_count));
+        }
+
+        Ok(())
+    }
+
+    // --- Undo/redo ---
+
+    /// Reverse the most recent file operation.
+    fn undo(&mut self) -> Result<()> {
+        let Some(operation) = self.operation_history.take_undo() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return Ok(());
+        };
+        let description = operation.description();
+        let (undone, failed) = self.undo_operation(operation);
+        if !undone.is_empty() {
+            self.operation_history.push_redo(undone);
+        }
+        self.load_directory(&self.current_dir.clone())?;
+        self.status_message = Some(outcome_message("Undid", &description, failed));
+        Ok(())
+    }
+
+    /// Repeat the most recently undone file operation.
+    fn redo(&mut self) -> Result<()> {
+        let Some(operation) = self.operation_history.take_redo() else {
+            self.status_message = Some("Nothing to redo".to_string());
+            return Ok(());
+        };
+        let description = operation.description();
+        let (redone, failed) = self.redo_operation(operation);
+        if !redone.is_empty() {
+            self.operation_history.push_undo(redone);
+        }
+        self.load_directory(&self.current_dir.clone())?;
+        self.status_message = Some(outcome_message("Redid", &description, failed));
+        Ok(())
+    }
+
+    /// Put files back, last one first. Returns the part that was undone and
+    /// how many files could not be put back.
+    fn undo_operation(&mut self, operation: ReversibleOperation) -> (ReversibleOperation, usize) {
+        let mut failed = 0;
+        let undone = match operation {
+            ReversibleOperation::Trash(files) => {
+                let mut restored = Vec::new();
+                for file in files.into_iter().rev() {
+                    match self.trash_manager.restore(&file.trash_path, &file.original_path) {
+                        Ok(()) => {
+                            if let Some(id) = file.photo_id {
+                                if let Err(e) = self.db.restore_photo(id) {
+                                    tracing::warn!(error = %e, "Failed to restore photo in DB");
+                                }
+                            }
+                            restored.push(file);
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, path = ?file.original_path, "Failed to undo trash");
+                            failed += 1;
+                        }
+                    }
+                }
+                restored.reverse();
+                ReversibleOperation::Trash(restored)
+            }
+            ReversibleOperation::Move(moves) => {
+                ReversibleOperation::Move(self.relocate_all(moves, true, &mut failed))
+            }
+            ReversibleOperation::Rename(moves) => {
+                ReversibleOperation::Rename(self.relocate_all(moves, true, &mut failed))
+            }
+        };
+        (undone, failed)
+    }
+
+    /// Do an undone operation again, in its original order. Returns the part
+    /// that was redone and how many files failed.
+    fn redo_operation(&mut self, operation: ReversibleOperation) -> (ReversibleOperation, usize) {
+        let mut failed = 0;
+        let redone = match operation {
+            ReversibleOperation::Trash(files) => {
+                let mut trashed = Vec::new();
+                for file in files {
+                    match self.trash_manager.move_to_trash(&file.original_path) {
+                        Ok(trash_path) => {
+                            if let Some(id) = file.photo_id {
+                                if let Err(e) = self.db.mark_trashed(id, &trash_path) {
+                                    tracing::error!(error = %e, "Failed to mark as trashed in DB");
+                                }
+                            }
+                            trashed.push(TrashedFile { trash_path, ..file });
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, path = ?file.original_path, "Failed to redo trash");
+                            failed += 1;
+                        }
+                    }
+                }
+                if !trashed.is_empty() {
+                    self.enforce_trash_size_limit();
+                }
+                ReversibleOperation::Trash(trashed)
+            }
+            ReversibleOperation::Move(moves) => {
+                ReversibleOperation::Move(self.relocate_all(moves, false, &mut failed))
+            }
+            ReversibleOperation::Rename(moves) => {
+                ReversibleOperation::Rename(self.relocate_all(moves, false, &mut failed))
+            }
+        };
+        (redone, failed)
+    }
+
+    /// Move each `(from, to)` pair back (`backwards`, last pair first) or
+    /// forwards again. Returns the pairs that moved, in their original order.
+    fn relocate_all(
+        &mut self,
+        moves: Vec<(PathBuf, PathBuf)>,
+        backwards: bool,
+        failed: &mut usize,
+    ) -> Vec<(PathBuf, PathBuf)> {
+        let mut done = Vec::new();
+        let ordered: Vec<(PathBuf, PathBuf)> = if backwards {
+            moves.into_iter().rev().collect()
+        } else {
+            moves
+        };
+        for (from, to) in ordered {
+            let result = if backwards {
+                self.relocate_file(&to, &from)
+            } else {
+                self.relocate_file(&from, &to)
+            };
+            match result {
+                Ok(()) => done.push((from, to)),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to relocate file");
+                    *failed += 1;
+                }
+            }
+        }
+        if backwards {
+            done.reverse();
         }
+        done
+    }
 
+    /// Move one file without replacing anything, keeping its DB record.
+    fn relocate_file(&mut self, from: &Path, to: &Path) -> Result<()> {
+        if to.exists() {
+            anyhow::bail!("{} already exists", to.display());
+        }
+        if std::fs::rename(from, to).is_err() {
+            // Cross-filesystem move
+            std::fs::copy(from, to)?;
+            std::fs::remove_file(from)?;
+        }
+        if let Err(e) = self.db.update_photo_path(from, to) {
+            tracing::warn!(error = %e, "Failed to update DB path");
+        }
         Ok(())
     }
 
@@ -2690,6 +5369,76 @@ impl App {
         Ok(())
     }
 
+    fn open_gps_dialog(&mut self) -> Result<()> {
+        let entry = match self.selected_entry() {
+            Some(e) if !e.is_dir => e.clone(),
+            _ => {
+                self.status_message = Some("Select a photo first".to_string());
+                return Ok(());
+            }
+        };
+
+        // Coordinates are stored per photo, so it has to be scanned first
+        let meta = match self.db.get_photo_metadata(&entry.path)? {
+            Some(m) => m,
+            None => {
+                self.status_message = Some("Photo not scanned yet - press s to scan".to_string());
+                return Ok(());
+            }
+        };
+
+        let existing = meta.gps_latitude.zip(meta.gps_longitude);
+        self.gps_dialog = Some(GpsDialog::new(entry.path, meta.id, existing, meta.gps_source));
+        self.mode = AppMode::EditingGPS;
+        Ok(())
+    }
+
+    fn handle_gps_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.gps_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.gps_dialog = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Enter => match dialog.coordinates() {
+                Ok((lat, lon)) => {
+                    let path = dialog.photo_path.clone();
+                    match self.db.set_manual_gps(dialog.photo_id, lat, lon) {
+                        Ok(_) => {
+                            self.status_message =
+                                Some(format!("GPS set to {:.6}, {:.6}", lat, lon));
+                            self.image_preview.metadata_cache.remove(&path);
+                            self.gps_dialog = None;
+                            self.mode = AppMode::Normal;
+                        }
+                        Err(e) => {
+                            self.status_message = Some(format!("Error saving: {}", e));
+                        }
+                    }
+                }
+                Err(e) => dialog.error = Some(e),
+            },
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                dialog.start_geocode();
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => dialog.next_field(),
+            KeyCode::Backspace => dialog.backspace(),
+            KeyCode::Left => dialog.move_cursor_left(),
+            KeyCode::Right => dialog.move_cursor_right(),
+            KeyCode::Char(c) => dialog.handle_char(c),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     fn handle_edit_description_key(&mut self, key: KeyEvent) -> Result<()> {
         let dialog = match self.edit_dialog.as_mut() {
             Some(d) => d,
@@ -2784,19 +5533,207 @@ impl App {
 
             _ => {}
         }
-
+
+        Ok(())
+    }
+
+    // --- People dialog methods ---
+
+    fn open_people_dialog(&mut self) -> Result<()> {
+        let people = self.db.get_all_people()?;
+        let faces = self.db.get_unassigned_faces()?;
+
+        // Always open the dialog, even if empty (shows instructions)
+        self.people_dialog = Some(PeopleDialog::new(people, faces));
+        self.mode = AppMode::PeopleManaging;
+        Ok(())
+    }
+
+    /// Close the people dialog, back to the people browser if it was opened
+    /// from there
+    fn close_people_dialog(&mut self) {
+        self.people_dialog = None;
+        if self.people_view.is_some() {
+            // Faces may have been named meanwhile
+            self.reload_people();
+            self.mode = AppMode::People;
+            self.clear_on_next_render = true;
+        } else {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    // --- People browser ---
+
+    fn load_person_cards(&self) -> Result<Vec<PersonCard>> {
+        let unassigned = self.db.get_unassigned_faces()?;
+        let mut cards = vec![PersonCard {
+            person: None,
+            count: unassigned.len(),
+            faces: unassigned.into_iter().take(CARD_FACES).map(FaceEntry::from).collect(),
+        }];
+        for person in self.db.get_all_people()? {
+            let faces = self.db.get_faces_for_person(person.id)?;
+            cards.push(PersonCard {
+                count: self.db.search_photos_by_person(person.id)?.len(),
+                faces: faces.into_iter().take(CARD_FACES).map(FaceEntry::from).collect(),
+                person: Some(person),
+            });
+        }
+        Ok(cards)
+    }
+
+    fn open_people_view(&mut self) -> Result<()> {
+        self.people_view = Some(PeopleView::new(self.load_person_cards()?));
+        self.mode = AppMode::People;
+        self.clear_on_next_render = true;
+        Ok(())
+    }
+
+    fn reload_people(&mut self) {
+        match self.load_person_cards() {
+            Ok(cards) => {
+                if let Some(view) = self.people_view.as_mut() {
+                    view.set_cards(cards);
+                }
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load people: {}", e)),
+        }
+    }
+
+    fn handle_people_view_key(&mut self, key: KeyEvent) -> Result<()> {
+        let view = match self.people_view.as_mut() {
+            Some(v) => v,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        // Arrow keys move the cursor both normally and while picking a merge target
+        let picking = matches!(view.prompt, None | Some(PeoplePrompt::MergeInto { .. }));
+        if picking {
+            let mut moved = true;
+            match key.code {
+                KeyCode::Left | KeyCode::Char('h') => view.move_left(),
+                KeyCode::Right | KeyCode::Char('l') => view.move_right(),
+                KeyCode::Up | KeyCode::Char('k') => view.move_up(),
+                KeyCode::Down | KeyCode::Char('j') => view.move_down(),
+                _ => moved = false,
+            }
+            if moved {
+                return Ok(());
+            }
+        }
+
+        if let Some(prompt) = view.prompt.take() {
+            return self.handle_people_prompt_key(prompt, key);
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.people_view = None;
+                self.mode = AppMode::Normal;
+                self.clear_on_next_render = true;
+            }
+            KeyCode::Char('n') => {
+                if let Some(name) = view.selected_person().map(|p| p.name.clone()) {
+                    view.prompt = Some(PeoplePrompt::Rename(name));
+                }
+            }
+            KeyCode::Char('m') if view.selected_person().is_some() => {
+                view.prompt = Some(PeoplePrompt::MergeInto { source: view.selected });
+            }
+            KeyCode::Char('d') if view.selected_person().is_some() => {
+                view.prompt = Some(PeoplePrompt::ConfirmDelete);
+            }
+            KeyCode::Enter => {
+                let Some(card) = view.selected_card() else { return Ok(()) };
+                let Some(person) = card.person.clone() else {
+                    // Unassigned faces are named in the people dialog
+                    self.open_people_dialog()?;
+                    if let Some(dialog) = self.people_dialog.as_mut() {
+                        dialog.view_mode = PeopleViewMode::Faces;
+                    }
+                    return Ok(());
+                };
+
+                let images: Vec<PathBuf> = self
+                    .db
+                    .search_photos_by_person(person.id)?
+                    .into_iter()
+                    .map(|(_, path, _)| PathBuf::from(path))
+                    .collect();
+                if images.is_empty() {
+                    self.status_message = Some(format!("No photos of {}", person.name));
+                    return Ok(());
+                }
+
+                // Paste still targets the browser's directory
+                let mut gallery = GalleryView::new(self.current_dir.clone(), images, self.image_protocol(&self.current_dir))
+                    .with_sort_metadata(&self.config.database);
+                gallery.title = Some(person.name);
+                self.gallery_view = Some(gallery);
+                self.mode = AppMode::Gallery;
+                self.clear_on_next_render = true;
+            }
+            _ => {}
+        }
         Ok(())
     }
 
-    // --- People dialog methods ---
-
-    fn open_people_dialog(&mut self) -> Result<()> {
-        let people = self.db.get_all_people()?;
-        let faces = self.db.get_unassigned_faces()?;
+    fn handle_people_prompt_key(&mut self, mut prompt: PeoplePrompt, key: KeyEvent) -> Result<()> {
+        let Some(view) = self.people_view.as_mut() else { return Ok(()) };
+        let selected = view.selected_person().cloned();
 
-        // Always open the dialog, even if empty (shows instructions)
-        self.people_dialog = Some(PeopleDialog::new(people, faces));
-        self.mode = AppMode::PeopleManaging;
+        match (&mut prompt, key.code) {
+            (_, KeyCode::Esc) => return Ok(()),
+            (PeoplePrompt::ConfirmDelete, code) => {
+                if let (Some(person), KeyCode::Char('y') | KeyCode::Char('Y')) = (selected, code) {
+                    self.db.delete_person(person.id)?;
+                    self.reload_people();
+                    self.status_message = Some(format!("Deleted {}", person.name));
+                }
+                return Ok(());
+            }
+            (PeoplePrompt::Rename(name), KeyCode::Enter) => {
+                let name = name.trim().to_string();
+                if let (Some(person), false) = (selected, name.is_empty()) {
+                    self.db.update_person_name(person.id, &name)?;
+                    self.reload_people();
+                    self.status_message = Some(format!("Renamed {} to {}", person.name, name));
+                }
+                return Ok(());
+            }
+            (PeoplePrompt::Rename(name), KeyCode::Backspace) => {
+                name.pop();
+            }
+            (PeoplePrompt::Rename(name), KeyCode::Char(c)) => name.push(c),
+            (PeoplePrompt::MergeInto { source }, KeyCode::Enter) => {
+                let source = view.cards.get(*source).and_then(|c| c.person.clone());
+                match (source, selected) {
+                    (Some(source), Some(target)) if source.id != target.id => {
+                        let moved = self.db.merge_people(source.id, target.id)?;
+                        self.reload_people();
+                        if let Some(view) = self.people_view.as_mut() {
+                            view.selected = view
+                                .cards
+                                .iter()
+                                .position(|c| c.person.as_ref().is_some_and(|p| p.id == target.id))
+                                .unwrap_or(0);
+                        }
+                        self.status_message =
+                            Some(format!("Merged {} into {} ({} faces moved)", source.name, target.name, moved));
+                        return Ok(());
+                    }
+                    _ => self.status_message = Some("Pick another person to merge into".to_string()),
+                }
+            }
+            _ => {}
+        }
+        if let Some(view) = self.people_view.as_mut() {
+            view.prompt = Some(prompt);
+        }
         Ok(())
     }
 
@@ -2872,10 +5809,7 @@ impl App {
 
         // Normal navigation mode
         match key.code {
-            KeyCode::Esc => {
-                self.people_dialog = None;
-                self.mode = AppMode::Normal;
-            }
+            KeyCode::Esc => self.close_people_dialog(),
             KeyCode::Char('j') | KeyCode::Down => {
                 dialog.move_down();
             }
@@ -2885,8 +5819,7 @@ impl App {
             KeyCode::Char('h') | KeyCode::Left => {
                 // Move left or close dialog if already at leftmost pane
                 if !dialog.move_left() {
-                    self.people_dialog = None;
-                    self.mode = AppMode::Normal;
+                    self.close_people_dialog();
                 }
             }
             KeyCode::Char('l') | KeyCode::Right => {
@@ -2922,6 +5855,7 @@ impl App {
                             }
                         }
                         self.people_dialog = None;
+                        self.people_view = None;
                         self.mode = AppMode::Normal;
                         self.status_message = Some(format!("Found {} photos", photos.len()));
                     } else {
@@ -2949,6 +5883,361 @@ impl App {
         Ok(())
     }
 
+    // --- Location browser ---
+
+    fn open_locations(&mut self) {
+        let precision = crate::db::locations::DEFAULT_PRECISION;
+        match self.db.get_gps_clusters(precision) {
+            Ok(clusters) => {
+                self.locations_view = Some(LocationsView::new(
+                    self.current_dir.clone(),
+                    clusters,
+                    precision,
+                    self.image_protocol(&self.current_dir),
+                ));
+                self.mode = AppMode::Locations;
+            }
+            Err(e) => self.status_message = Some(format!("Failed to load locations: {}", e)),
+        }
+    }
+
+    /// Regroup the location clusters with another cell size.
+    fn set_location_precision(&mut self, precision: f32) -> Result<()> {
+        let clusters = self.db.get_gps_clusters(precision)?;
+        if let Some(view) = self.locations_view.as_mut() {
+            view.set_clusters(clusters, precision);
+        }
+        self.status_message = Some(format!(
+            "Grouping by {}° cells ({})",
+            precision,
+            crate::db::locations::precision_label(precision)
+        ));
+        Ok(())
+    }
+
+    fn handle_locations_key(&mut self, key: KeyEvent) -> Result<()> {
+        let view = match self.locations_view.as_mut() {
+            Some(v) => v,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        let columns = view.grid.cached_columns();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.locations_view = None;
+                self.mode = AppMode::Normal;
+                self.clear_on_next_render = true;
+            }
+            KeyCode::Left | KeyCode::Char('h') => view.grid.move_left(),
+            KeyCode::Right | KeyCode::Char('l') => view.grid.move_right(),
+            KeyCode::Up | KeyCode::Char('k') => view.grid.move_up(columns),
+            KeyCode::Down | KeyCode::Char('j') => view.grid.move_down(columns),
+            KeyCode::Home | KeyCode::Char('g') => view.grid.move_to_start(),
+            KeyCode::End | KeyCode::Char('G') => view.grid.move_to_end(),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let precision = crate::db::locations::finer(view.precision);
+                self.set_location_precision(precision)?;
+            }
+            KeyCode::Char('-') => {
+                let precision = crate::db::locations::coarser(view.precision);
+                self.set_location_precision(precision)?;
+            }
+            KeyCode::Char('n') if view.selected().is_some() && !view.is_geocoding() => view.start_geocode(),
+            KeyCode::Char('c') if self.location_filter.is_some() => {
+                self.location_filter = None;
+                self.status_message = Some("Location filter cleared".to_string());
+                let current_dir = self.current_dir.clone();
+                self.load_directory(&current_dir)?;
+            }
+            KeyCode::Enter => {
+                let Some(cluster) = view.selected() else { return Ok(()) };
+                let label = cluster_label(cluster);
+                let paths: HashSet<PathBuf> = cluster.paths.iter().map(PathBuf::from).collect();
+                let first = PathBuf::from(&cluster.paths[0]);
+
+                self.status_message = Some(format!("Showing {} photos from {}", paths.len(), label));
+                self.location_filter = Some((label, paths));
+                self.locations_view = None;
+                self.mode = AppMode::Normal;
+                self.clear_on_next_render = true;
+
+                // Start in the directory of the cluster's first photo
+                let dir = first.parent().map(Path::to_path_buf).unwrap_or_else(|| self.current_dir.clone());
+                self.load_directory(&dir)?;
+                if let Some(idx) = self.entries.iter().position(|e| e.path == first) {
+                    self.selected_index = idx;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Cache a place name looked up from the location browser (called from main loop).
+    fn poll_location_geocode(&mut self) {
+        let Some(view) = self.locations_view.as_mut() else { return };
+        let Some((latitude, longitude, result)) = view.poll_geocode() else { return };
+        self.status_message = Some(match result {
+            Ok(name) => match self.db.save_place_name(latitude, longitude, &name) {
+                Ok(()) => format!("Named {}", name),
+                Err(e) => format!("Failed to save place name: {}", e),
+            },
+            Err(e) => format!("Place lookup failed: {}", e),
+        });
+    }
+
+    // --- Sharing ---
+
+    /// Serve the selected photos as a web gallery until the share dialog
+    /// is closed.
+    fn share_selected(&mut self) {
+        let mut files: Vec<PathBuf> = self.selected_files.iter().filter(|p| p.is_file()).cloned().collect();
+        if files.is_empty() {
+            self.status_message = Some("Select photos to share first".to_string());
+            return;
+        }
+        files.sort();
+        let photos: Vec<SharedPhoto> = files
+            .into_iter()
+            .map(|path| {
+                let rotation = self.get_photo_rotation(&path);
+                SharedPhoto { path, rotation }
+            })
+            .collect();
+
+        let (task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::Share);
+        let (ready_tx, ready_rx) = mpsc::channel();
+        self.share_dialog = Some(ShareDialog::new(task_id, photos.len(), ready_rx));
+        self.mode = AppMode::Sharing;
+
+        let thumbnails = ThumbnailManager::new(&self.config.thumbnails);
+        let runtime = tokio::runtime::Handle::current();
+        std::thread::spawn(move || share::serve_gallery(photos, thumbnails, runtime, ready_tx, tx, cancel_flag));
+    }
+
+    /// Write descriptions, tags and ratings into the selected photos, or all
+    /// photos in the current directory, in the background.
+    fn start_metadata_write(&mut self) {
+        use crate::scanner::exif_writer::{self, EmbeddedMetadata};
+
+        let mut paths: Vec<PathBuf> = if self.selected_files.is_empty() {
+            self.entries.iter().filter(|e| !e.is_dir && is_image(&e.name)).map(|e| e.path.clone()).collect()
+        } else {
+            self.selected_files.iter().filter(|p| p.is_file()).cloned().collect()
+        };
+        paths.sort();
+
+        let mut skipped = 0;
+        let mut photos: Vec<(PathBuf, EmbeddedMetadata)> = Vec::new();
+        for path in paths {
+            let Some(photo) = self.db.get_photo_metadata(&path).ok().flatten() else { continue };
+            if !exif_writer::is_supported(&path) {
+                tracing::warn!(path = %path.display(), "Skipping metadata write: unsupported format");
+                skipped += 1;
+                continue;
+            }
+            let metadata = EmbeddedMetadata {
+                description: photo.description.filter(|d| !d.trim().is_empty()),
+                tags: self.db.get_photo_tags(photo.id).unwrap_or_default().into_iter().map(|t| t.name).collect(),
+                rating: photo.rating.and_then(|r| u8::try_from(r).ok()),
+            };
+            if !metadata.is_empty() {
+                photos.push((path, metadata));
+            }
+        }
+        if photos.is_empty() {
+            self.status_message = Some(if skipped > 0 {
+                format!("No JPEG/TIFF photos with metadata to write ({} unsupported skipped)", skipped)
+            } else {
+                "No scanned photos with descriptions, tags or ratings to write".to_string()
+            });
+            return;
+        }
+
+        let (_, tx, cancel_flag) = self.task_manager.register_task(TaskType::WriteMetadata);
+        self.status_message = Some(format!("Writing metadata to {} photos...", photos.len()));
+        std::thread::spawn(move || {
+            let total = photos.len();
+            let _ = tx.send(TaskUpdate::Started { total });
+            let mut written = 0;
+            let mut failed = 0;
+            for (i, (path, metadata)) in photos.iter().enumerate() {
+                if cancel_flag.load(Ordering::SeqCst) {
+                    let _ = tx.send(TaskUpdate::Cancelled);
+                    return;
+                }
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let _ = tx.send(TaskUpdate::Progress(TaskProgress::new(i, total).with_item(name)));
+                match exif_writer::write_metadata(path, metadata) {
+                    Ok(true) => written += 1,
+                    Ok(false) => skipped += 1,
+                    Err(e) => {
+                        tracing::warn!(error = %e, path = ?path, "Failed to write metadata");
+                        failed += 1;
+                    }
+                }
+            }
+
+            let mut message = format!("Wrote metadata to {} photos", written);
+            if skipped > 0 {
+                message.push_str(&format!(", {} unsupported skipped", skipped));
+            }
+            if failed > 0 {
+                message.push_str(&format!(", {} failed", failed));
+            }
+            let _ = tx.send(TaskUpdate::Completed { message });
+        });
+    }
+
+    fn stop_sharing(&mut self) {
+        if let Some(dialog) = self.share_dialog.take() {
+            self.task_manager.cancel_task(dialog.task_id);
+        }
+        self.mode = AppMode::Normal;
+        self.status_message = Some("Stopped sharing".to_string());
+    }
+
+    // --- Android import ---
+
+    fn open_adb_import(&mut self) {
+        match AdbScanner::list_devices() {
+            Ok(devices) => {
+                let mut dialog = AdbImportDialog::new(devices);
+                // With a single usable phone there is nothing to choose
+                let ready: Vec<&AdbDevice> = dialog.devices.iter().filter(|d| d.is_ready()).collect();
+                if let [device] = ready[..] {
+                    let serial = device.serial.clone();
+                    self.browse_adb_device(&mut dialog, serial);
+                }
+                self.adb_import = Some(dialog);
+                self.mode = AppMode::AdbImport;
+            }
+            Err(e) => self.status_message = Some(e.to_string()),
+        }
+    }
+
+    /// List a device's DCIM files into the dialog.
+    fn browse_adb_device(&mut self, dialog: &mut AdbImportDialog, serial: String) {
+        match AdbScanner::list_dcim_files(Some(&serial)) {
+            Ok(files) => dialog.set_files(serial, files, &self.config.scanner.extensions()),
+            Err(e) => self.status_message = Some(e.to_string()),
+        }
+    }
+
+    fn handle_adb_import_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(mut dialog) = self.adb_import.take() else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
+
+        match (dialog.step, key.code) {
+            (_, KeyCode::Char('j') | KeyCode::Down) => dialog.move_down(),
+            (_, KeyCode::Char('k') | KeyCode::Up) => dialog.move_up(),
+            (AdbImportStep::Devices, KeyCode::Esc | KeyCode::Char('q')) => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+            (AdbImportStep::Devices, KeyCode::Char('r')) => match AdbScanner::list_devices() {
+                Ok(devices) => dialog = AdbImportDialog::new(devices),
+                Err(e) => self.status_message = Some(e.to_string()),
+            },
+            (AdbImportStep::Devices, KeyCode::Enter) => match dialog.selected_device() {
+                Some(device) if device.is_ready() => {
+                    let serial = device.serial.clone();
+                    self.browse_adb_device(&mut dialog, serial);
+                }
+                Some(device) => {
+                    self.status_message = Some(format!("{} is {}; allow USB debugging on the phone", device.serial, device.state));
+                }
+                None => {}
+            },
+            (AdbImportStep::Browse, KeyCode::Esc | KeyCode::Char('q')) => {
+                if dialog.preview.is_some() {
+                    dialog.preview = None;
+                } else if !dialog.back() {
+                    self.mode = AppMode::Normal;
+                    return Ok(());
+                }
+            }
+            (AdbImportStep::Browse, KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter) => dialog.open_selected(),
+            (AdbImportStep::Browse, KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace) => {
+                dialog.back();
+            }
+            (AdbImportStep::Browse, KeyCode::Char(' ')) => {
+                dialog.toggle_selected();
+                dialog.move_down();
+            }
+            (AdbImportStep::Browse, KeyCode::Char('p')) => dialog.start_preview(),
+            (AdbImportStep::Browse, KeyCode::Char('i')) => {
+                let started = self.start_adb_import(&dialog);
+                if started {
+                    self.mode = AppMode::Normal;
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+
+        self.adb_import = Some(dialog);
+        Ok(())
+    }
+
+    /// Import the picked files in the background. Returns whether the
+    /// dialog can close.
+    fn start_adb_import(&mut self, dialog: &AdbImportDialog) -> bool {
+        let files = dialog.picked_files();
+        if files.is_empty() {
+            self.status_message = Some("Pick photos with Space first".to_string());
+            return false;
+        }
+        let Some(library) = self.config.library.path.clone() else {
+            self.status_message = Some("Library path not configured. Set library.path in config.".to_string());
+            return false;
+        };
+        if self.dry_run {
+            for file in &files {
+                tracing::info!("WOULD IMPORT: {} → {}", file.path, library.display());
+            }
+            self.status_message = Some(format!("Dry run: would import {} photos", files.len()));
+            return true;
+        }
+
+        let (_task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::AdbImport);
+        let config = self.config.clone();
+        let serial = dialog.serial.clone();
+        let total = files.len();
+        std::thread::spawn(move || {
+            let db = match Database::open(&config.database) {
+                Ok(db) => db,
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Failed to open database: {}", e),
+                    });
+                    return;
+                }
+            };
+            crate::scanner::adb::import_to_library(&config, &db, &library, serial.as_deref(), &files, tx, cancel_flag);
+        });
+        self.status_message = Some(format!("Importing {} photos from the device...", total));
+        true
+    }
+
+    /// Show a photo read from the device (called from main loop).
+    fn poll_adb_preview(&mut self) {
+        let Some(dialog) = self.adb_import.as_mut() else { return };
+        let Some((path, result)) = dialog.poll_preview() else { return };
+        match result {
+            Ok(img) => {
+                let image = self.image_preview.new_protocol(img);
+                dialog.preview = Some(AdbPreview { path, image });
+            }
+            Err(e) => self.status_message = Some(format!("Preview failed: {}", e)),
+        }
+    }
+
     // --- Changes dialog methods ---
 
     fn open_changes_dialog(&mut self) -> Result<()> {
@@ -2972,7 +6261,39 @@ impl App {
 
         let dialog = self.changes_dialog.as_mut().unwrap();
 
+        if dialog.diff.is_some() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('q') => dialog.diff = None,
+                KeyCode::Char(' ') => dialog.toggle_selection(),
+                _ => {}
+            }
+            return Ok(());
+        }
+
         match key.code {
+            KeyCode::Char('d') => {
+                let Some(path) = dialog.selected_modified_file().cloned() else {
+                    return Ok(());
+                };
+                let diff = self
+                    .db
+                    .get_photo_metadata(&path)?
+                    .map(|stored| diff_metadata(&path, &stored))
+                    .transpose();
+                match diff {
+                    Ok(Some(changes)) => {
+                        let filename = path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        if let Some(ref mut dialog) = self.changes_dialog {
+                            dialog.diff = Some(MetadataDiff { filename, changes });
+                        }
+                    }
+                    Ok(None) => self.status_message = Some("Not in the database".to_string()),
+                    Err(e) => self.status_message = Some(format!("Failed to read {}: {}", path.display(), e)),
+                }
+            }
             KeyCode::Esc => {
                 // Put changes back so indicator stays visible
                 let changes = dialog.changes.clone();
@@ -3208,9 +6529,120 @@ impl App {
             let _ = crate::schedule::mark_task_completed(task.id, &self.db);
         }
 
+        if self.schedule_manager.trash_cleanup_due(&self.db, self.config.trash.cleanup_interval_hours) {
+            self.run_trash_cleanup();
+        }
+
         Ok(())
     }
 
+    /// Enforce the trash age and size limits and forget the photos it deletes.
+    fn run_trash_cleanup(&mut self) {
+        if let Err(e) = crate::trash::TrashCleanupTask::run(&self.trash_manager, &self.db) {
+            tracing::warn!(error = %e, "Trash cleanup failed");
+        }
+    }
+
+    /// Clean up straight away if a move to trash pushed it over its size limit.
+    fn enforce_trash_size_limit(&mut self) {
+        match self.trash_manager.total_size() {
+            Ok(size) if size > self.trash_manager.max_size_bytes() => self.run_trash_cleanup(),
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to measure trash size"),
+        }
+    }
+
+    // --- Auto-cull ---
+
+    /// Assess the unrated photos of the current directory in the background
+    /// and mark the blurry or badly exposed ones for review.
+    fn start_auto_cull(&mut self) {
+        if self.auto_cull.is_some() {
+            self.status_message = Some("Auto-cull is already running".to_string());
+            return;
+        }
+        let candidates: Vec<(i64, PathBuf)> = self
+            .entries
+            .iter()
+            .filter(|e| !e.is_dir && is_image(&e.name))
+            .filter_map(|e| self.db.get_photo_metadata(&e.path).ok().flatten())
+            .filter(|photo| photo.rating.is_none())
+            .map(|photo| (photo.id, PathBuf::from(photo.path)))
+            .collect();
+        if candidates.is_empty() {
+            self.status_message = Some("No unrated scanned photos in current directory".to_string());
+            return;
+        }
+
+        let (task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::AutoCull);
+        let (result_tx, result_rx) = mpsc::channel();
+        self.auto_cull = Some(PendingAutoCull { task_id, rx: result_rx });
+        let quality = self.config.quality.clone();
+        self.status_message = Some(format!("Assessing quality of {} photos...", candidates.len()));
+
+        std::thread::spawn(move || {
+            let total = candidates.len();
+            let _ = tx.send(TaskUpdate::Started { total });
+            let done = std::sync::atomic::AtomicUsize::new(0);
+            let poor: Vec<(i64, PathBuf)> = candidates
+                .into_par_iter()
+                .filter(|(_, path)| {
+                    if cancel_flag.load(Ordering::SeqCst) {
+                        return false;
+                    }
+                    let current = done.fetch_add(1, Ordering::SeqCst);
+                    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    let _ = tx.send(TaskUpdate::Progress(TaskProgress::new(current, total).with_item(name)));
+                    match assess_quality(path) {
+                        Ok(assessment) => assessment.is_poor(&quality),
+                        Err(e) => {
+                            tracing::warn!(error = %e, path = ?path, "Failed to assess photo quality");
+                            false
+                        }
+                    }
+                })
+                .collect();
+
+            if cancel_flag.load(Ordering::SeqCst) {
+                let _ = tx.send(TaskUpdate::Cancelled);
+                return;
+            }
+            let count = poor.len();
+            let _ = result_tx.send(poor);
+            let _ = tx.send(TaskUpdate::Completed {
+                message: format!("{} of {} photos below quality thresholds", count, total),
+            });
+        });
+    }
+
+    /// Label the poor photos red and select them, so they can be reviewed
+    /// and trashed together.
+    fn finish_auto_cull(&mut self, task_id: crate::tasks::TaskId, success: bool, message: &str) {
+        if self.auto_cull.as_ref().map(|p| p.task_id) != Some(task_id) {
+            return;
+        }
+        let Some(pending) = self.auto_cull.take() else { return };
+        let poor = match pending.rx.try_recv() {
+            Ok(poor) if success => poor,
+            _ => {
+                self.status_message = Some(format!("Auto-cull: {}", message));
+                return;
+            }
+        };
+
+        let mut marked = 0;
+        for (id, path) in poor {
+            if let Err(e) = self.db.set_photo_color_label(id, Some("red")) {
+                tracing::warn!(error = %e, path = ?path, "Failed to label poor quality photo");
+                continue;
+            }
+            self.image_preview.metadata_cache.remove(&path);
+            self.selected_files.insert(path);
+            marked += 1;
+        }
+        self.status_message = Some(format!("Marked {} photos as poor quality", marked));
+    }
+
     // --- Gallery view ---
 
     /// Open gallery view for current directory
@@ -3228,14 +6660,159 @@ impl App {
             return Ok(());
         }
 
-        let gallery = GalleryView::new(
-            self.current_dir.clone(),
-            images,
-            self.config.preview.protocol,
-        );
+        // Generate missing thumbnails first, unless this directory was
+        // warmed before
+        let thumbnails = ThumbnailManager::new(&self.config.thumbnails);
+        let mut warmed = self.warmed_thumbnail_dirs();
+        if !warmed.contains(&self.current_dir) {
+            let jobs: Vec<(PathBuf, i32)> = images
+                .iter()
+                .map(|p| (p.clone(), self.get_photo_rotation(p)))
+                .filter(|(p, rotation)| !thumbnails.has_cached(p, *rotation))
+                .collect();
+            if !jobs.is_empty() {
+                self.start_thumbnail_warmup(thumbnails, jobs);
+                return Ok(());
+            }
+            warmed.insert(self.current_dir.clone());
+            self.save_warmed_thumbnail_dirs(&warmed);
+        }
+
+        let gallery = GalleryView::new(
+            self.current_dir.clone(),
+            images,
+            self.image_protocol(&self.current_dir),
+        )
+        .with_thumbnails(thumbnails)
+        .with_sort_metadata(&self.config.database);
+
+        self.gallery_view = Some(gallery);
+        self.mode = AppMode::Gallery;
+        Ok(())
+    }
+
+    fn start_thumbnail_warmup(&mut self, thumbnails: ThumbnailManager, jobs: Vec<(PathBuf, i32)>) {
+        let (task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::ThumbnailWarmup);
+        self.thumbnail_warmup = Some(WarmupDialog {
+            task_id,
+            directory: self.current_dir.clone(),
+            total: jobs.len(),
+        });
+        self.mode = AppMode::ThumbnailWarmup;
+        std::thread::spawn(move || thumbnails.warm_up(jobs, tx, cancel_flag));
+    }
+
+    /// Open the gallery the finished warm-up was started for.
+    fn finish_thumbnail_warmup(&mut self, task_id: crate::tasks::TaskId, success: bool, message: &str) -> Result<()> {
+        // A cancelled warm-up has already closed its dialog
+        if self.thumbnail_warmup.as_ref().map(|d| d.task_id) != Some(task_id) {
+            return Ok(());
+        }
+        let Some(dialog) = self.thumbnail_warmup.take() else { return Ok(()) };
+        self.mode = AppMode::Normal;
+        if !success {
+            self.status_message = Some(format!("Thumbnail warm-up failed: {}", message));
+            return Ok(());
+        }
+
+        let mut warmed = self.warmed_thumbnail_dirs();
+        warmed.insert(dialog.directory.clone());
+        self.save_warmed_thumbnail_dirs(&warmed);
+        self.status_message = Some(message.to_string());
+        if self.current_dir == dialog.directory {
+            self.open_gallery_view()?;
+        }
+        Ok(())
+    }
+
+    /// Directories whose thumbnails were all generated before
+    fn warmed_thumbnail_dirs(&self) -> HashSet<PathBuf> {
+        self.db
+            .get_app_state(WARMED_THUMBNAIL_DIRS_KEY)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_warmed_thumbnail_dirs(&self, dirs: &HashSet<PathBuf>) {
+        if let Ok(json) = serde_json::to_string(dirs) {
+            if let Err(e) = self.db.set_app_state(WARMED_THUMBNAIL_DIRS_KEY, &json) {
+                tracing::warn!(error = %e, "Failed to remember warmed thumbnail directory");
+            }
+        }
+    }
+
+    /// Leave the gallery, back to the album browser if it was opened from there
+    fn close_gallery(&mut self) {
+        self.gallery_view = None;
+        self.clear_on_next_render = true;
+        if self.albums_view.is_some() {
+            // Photos may have been trashed from the album meanwhile
+            self.reload_albums();
+            self.mode = AppMode::Albums;
+        } else if self.people_view.is_some() {
+            self.reload_people();
+            self.mode = AppMode::People;
+        } else {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// Rotate the selected gallery images, or the one under the cursor, and
+    /// redraw just their cells.
+    fn rotate_gallery_images(&mut self, clockwise: bool) {
+        let Some(gallery) = self.gallery_view.as_mut() else { return };
+        let paths = if gallery.selection_count() > 0 {
+            gallery.get_selected_paths()
+        } else {
+            gallery.selected_image().cloned().into_iter().collect()
+        };
 
-        self.gallery_view = Some(gallery);
-        self.mode = AppMode::Gallery;
+        let mut rotated = 0;
+        for path in &paths {
+            let result = if clockwise { self.db.rotate_photo_cw(path) } else { self.db.rotate_photo_ccw(path) };
+            match result {
+                Ok(new_rotation) => {
+                    self.image_preview.invalidate_thumbnail(path);
+                    gallery.refresh_rotated(path, new_rotation);
+                    rotated += 1;
+                    tracing::debug!(path = %path.display(), rotation = new_rotation, clockwise, "Rotated");
+                }
+                Err(e) => tracing::warn!(path = %path.display(), error = %e, "Failed to rotate"),
+            }
+        }
+        if rotated > 0 {
+            let direction = if clockwise { "clockwise" } else { "counter-clockwise" };
+            self.status_message = Some(format!("Rotated {} image(s) {}", rotated, direction));
+        }
+    }
+
+    /// Move the photo under the gallery cursor one place later or earlier
+    /// in the album it was opened from
+    fn move_album_photo(&mut self, later: bool) -> Result<()> {
+        let Some(gallery) = self.gallery_view.as_mut() else { return Ok(()) };
+        let Some(album_id) = gallery.album_id else {
+            self.status_message = Some("Only a manual album opened in its own order can be rearranged".to_string());
+            return Ok(());
+        };
+        let Some(target) = (if later { gallery.selected.checked_add(1) } else { gallery.selected.checked_sub(1) })
+            .filter(|&t| t < gallery.images.len())
+        else {
+            return Ok(());
+        };
+        let photo_id = |path: Option<&PathBuf>| path.and_then(|p| self.db.get_photo_metadata(p).ok().flatten()).map(|m| m.id);
+        let (Some(photo), Some(neighbour)) = (photo_id(gallery.selected_image()), photo_id(gallery.images.get(target))) else {
+            return Ok(());
+        };
+        // The album may hold photos the gallery no longer shows, so move to
+        // the neighbour's place in the stored order rather than `target`
+        let Some(new_index) = self.db.get_album_photos(album_id)?.iter().position(|&id| id == neighbour) else {
+            return Ok(());
+        };
+        if self.db.reorder_album_photo(album_id, photo, new_index)? {
+            gallery.swap_selected_with(target);
+        }
         Ok(())
     }
 
@@ -3264,17 +6841,11 @@ impl App {
                 } else if gallery.selection_count() > 0 {
                     gallery.clear_selection();
                 } else {
-                    self.gallery_view = None;
-                    self.mode = AppMode::Normal;
-                    self.clear_on_next_render = true;
+                    self.close_gallery();
                 }
             }
 
-            KeyCode::Char('q') => {
-                self.gallery_view = None;
-                self.mode = AppMode::Normal;
-                self.clear_on_next_render = true;
-            }
+            KeyCode::Char('q') => self.close_gallery(),
 
             // Help
             KeyCode::Char('?') => {
@@ -3352,51 +6923,12 @@ impl App {
             KeyCode::Char('s') => gallery.cycle_sort(),
 
             // Rotate selected images
-            KeyCode::Char(']') => {
-                let paths = if gallery.selection_count() > 0 {
-                    gallery.get_selected_paths()
-                } else if let Some(path) = gallery.selected_image().cloned() {
-                    vec![path]
-                } else {
-                    vec![]
-                };
-
-                let mut rotated = 0;
-                for path in &paths {
-                    if let Ok(new_rotation) = self.db.rotate_photo_cw(path) {
-                        self.image_preview.invalidate_thumbnail(path);
-                        rotated += 1;
-                        tracing::debug!(path = %path.display(), rotation = new_rotation, "Rotated CW");
-                    }
-                }
-                if rotated > 0 {
-                    self.status_message = Some(format!("Rotated {} image(s) clockwise", rotated));
-                    // Clear thumbnail cache to force reload with new rotation
-                    gallery.clear_cache();
-                }
-            }
-            KeyCode::Char('[') => {
-                let paths = if gallery.selection_count() > 0 {
-                    gallery.get_selected_paths()
-                } else if let Some(path) = gallery.selected_image().cloned() {
-                    vec![path]
-                } else {
-                    vec![]
-                };
+            KeyCode::Char(']') | KeyCode::Char('r') => self.rotate_gallery_images(true),
+            KeyCode::Char('[') => self.rotate_gallery_images(false),
 
-                let mut rotated = 0;
-                for path in &paths {
-                    if let Ok(new_rotation) = self.db.rotate_photo_ccw(path) {
-                        self.image_preview.invalidate_thumbnail(path);
-                        rotated += 1;
-                        tracing::debug!(path = %path.display(), rotation = new_rotation, "Rotated CCW");
-                    }
-                }
-                if rotated > 0 {
-                    self.status_message = Some(format!("Rotated {} image(s) counter-clockwise", rotated));
-                    gallery.clear_cache();
-                }
-            }
+            // Rearrange a manual album
+            KeyCode::Char('J') => self.move_album_photo(true)?,
+            KeyCode::Char('K') => self.move_album_photo(false)?,
 
             // Delete selected images (move to trash) - yazi-aligned: d or Delete
             KeyCode::Char('d') | KeyCode::Delete => {
@@ -3426,11 +6958,10 @@ impl App {
                         }
                         // Exit gallery if no images left
                         if gallery.images.is_empty() {
-                            self.gallery_view = None;
-                            self.mode = AppMode::Normal;
-                            self.clear_on_next_render = true;
+                            self.close_gallery();
                             self.status_message = Some("Gallery empty - returning to browser".to_string());
                         }
+                        self.enforce_trash_size_limit();
                     }
                 }
             }
@@ -3504,12 +7035,11 @@ impl App {
                 let directory = gallery.directory.clone();
 
                 if !images.is_empty() {
-                    let mut slideshow = SlideshowView::new(
-                        directory,
-                        images,
-                        self.config.preview.protocol,
-                    );
+                    let protocol = self.image_protocol(&directory);
+                    let interval = self.slideshow_interval(&directory);
+                    let mut slideshow = SlideshowView::new(directory, images, protocol);
                     slideshow.current = selected;
+                    slideshow.set_interval(interval);
                     self.slideshow_view = Some(slideshow);
                     self.mode = AppMode::Slideshow;
                 }
@@ -3655,6 +7185,188 @@ impl App {
         Ok(())
     }
 
+    /// Open the batch tag dialog for all selected photos
+    fn open_batch_tag_dialog(&mut self) -> Result<()> {
+        if self.selected_files.is_empty() {
+            self.status_message = Some("Select photos to batch edit tags".to_string());
+            return Ok(());
+        }
+
+        let mut paths: Vec<&PathBuf> = self.selected_files.iter().collect();
+        paths.sort();
+
+        let mut photos = Vec::new();
+        let mut not_scanned = 0;
+        for path in paths {
+            match self.db.get_photo_metadata(path)? {
+                Some(meta) => photos.push((meta.id, self.db.get_photo_tags(meta.id)?)),
+                None => not_scanned += 1,
+            }
+        }
+
+        if photos.is_empty() {
+            self.status_message = Some("Selected photos are not in database. Scan first.".to_string());
+            return Ok(());
+        }
+        if not_scanned > 0 {
+            self.status_message = Some(format!("Skipping {} unscanned files", not_scanned));
+        }
+
+        self.batch_tag_dialog = Some(BatchTagDialog::new(photos));
+        self.mode = AppMode::BatchTagging;
+        Ok(())
+    }
+
+    /// Handle key events in the batch tag dialog
+    fn handle_batch_tag_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.batch_tag_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.batch_tag_dialog = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => dialog.move_down(),
+            KeyCode::Char('k') | KeyCode::Up => dialog.move_up(),
+            KeyCode::Char(' ') => dialog.toggle_selected(),
+            KeyCode::Enter => {
+                let photo_ids = dialog.photo_ids.clone();
+                let changes = dialog.pending_changes();
+                self.batch_tag_dialog = None;
+                self.mode = AppMode::Normal;
+
+                if changes.is_empty() {
+                    self.status_message = Some("No tag changes".to_string());
+                    return Ok(());
+                }
+
+                let (mut added, mut removed) = (0, 0);
+                for (tag, change) in &changes {
+                    for &photo_id in &photo_ids {
+                        match change {
+                            BatchTagChange::AddToAll => self.db.add_tag_to_photo(photo_id, tag.id)?,
+                            BatchTagChange::RemoveFromAll => self.db.remove_tag_from_photo(photo_id, tag.id)?,
+                            BatchTagChange::Keep => {}
+                        }
+                    }
+                    match change {
+                        BatchTagChange::AddToAll => added += 1,
+                        BatchTagChange::RemoveFromAll => removed += 1,
+                        BatchTagChange::Keep => {}
+                    }
+                }
+                self.status_message = Some(format!(
+                    "Tags on {} photos: {} added, {} removed",
+                    photo_ids.len(),
+                    added,
+                    removed
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    // --- Watch mode ---
+
+    /// Start or stop watching the current directory
+    fn toggle_watch(&mut self) -> Result<()> {
+        if self.mode == AppMode::Watching {
+            self.stop_watching();
+            self.status_message = Some("Stopped watching".to_string());
+            return Ok(());
+        }
+
+        match DirWatcher::new(&self.current_dir) {
+            Ok(watcher) => {
+                self.dir_watcher = Some(watcher);
+                self.watch_changes.clear();
+                self.mode = AppMode::Watching;
+                self.status_message = Some(format!("Watching {}", self.current_dir.display()));
+            }
+            Err(e) => self.status_message = Some(format!("Cannot watch directory: {}", e)),
+        }
+        Ok(())
+    }
+
+    fn stop_watching(&mut self) {
+        self.dir_watcher = None;
+        self.watch_changes.clear();
+        if self.mode == AppMode::Watching {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// Apply debounced file system changes to the browser and database.
+    /// The watcher is dropped once the app leaves watch mode or the directory.
+    fn poll_watcher(&mut self) {
+        let Some(ref mut watcher) = self.dir_watcher else {
+            return;
+        };
+        if self.mode != AppMode::Watching || watcher.dir != self.current_dir {
+            self.stop_watching();
+            return;
+        }
+        let Some(changes) = watcher.poll() else {
+            return;
+        };
+
+        let mut added = 0;
+        for (path, change) in changes {
+            if change == WatchChange::Created && path.is_file() {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if is_image(&name) {
+                    let size = std::fs::metadata(&path).map(|m| m.len() as i64).unwrap_or(0);
+                    let directory = self.current_dir.to_string_lossy();
+                    if self.db.insert_basic_photo(&path.to_string_lossy(), &name, &directory, size).is_ok() {
+                        added += 1;
+                    }
+                }
+            }
+
+            match merge_change(self.watch_changes.get(&path).copied(), change) {
+                Some(change) => self.watch_changes.insert(path, change),
+                None => self.watch_changes.remove(&path),
+            };
+        }
+
+        self.refresh_watched_directory();
+        if added > 0 {
+            let photos = if added == 1 { "photo" } else { "photos" };
+            self.status_message = Some(format!("Watching: {} new {}", added, photos));
+        }
+    }
+
+    /// Re-read the watched directory, keeping the cursor on the same file and
+    /// deleted files in place so they can be shown struck through.
+    fn refresh_watched_directory(&mut self) {
+        let dir = self.current_dir.clone();
+        let Ok(mut entries) = self.read_directory(&dir) else {
+            return;
+        };
+
+        for (index, old) in self.entries.iter().enumerate() {
+            let removed = self.watch_changes.get(&old.path) == Some(&WatchChange::Removed);
+            if removed && !entries.iter().any(|e| e.path == old.path) {
+                entries.insert(index.min(entries.len()), old.clone());
+            }
+        }
+
+        let selected_path = self.selected_entry().map(|e| e.path.clone());
+        self.entries = entries;
+        self.selected_index = selected_path
+            .and_then(|p| self.entries.iter().position(|e| e.path == p))
+            .unwrap_or(self.selected_index)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
     // --- Slideshow ---
 
     /// Open slideshow for images in current directory
@@ -3688,15 +7400,44 @@ impl App {
         let mut slideshow = SlideshowView::new(
             self.current_dir.clone(),
             images,
-            self.config.preview.protocol,
+            self.image_protocol(&self.current_dir),
         );
         slideshow.current = start_index;
+        slideshow.set_interval(self.slideshow_interval(&self.current_dir));
 
         self.slideshow_view = Some(slideshow);
         self.mode = AppMode::Slideshow;
         Ok(())
     }
 
+    /// Graphics protocol for images in `dir`, with `auto` resolved to the
+    /// one detected at startup
+    fn image_protocol(&self, dir: &Path) -> ImageProtocol {
+        self.image_preview.resolve_protocol(self.config.preview.protocol_for(dir))
+    }
+
+    /// The slideshow interval saved for `directory`, or the configured default
+    fn slideshow_interval(&self, directory: &Path) -> f64 {
+        match self.db.get_slideshow_interval(&directory.to_string_lossy()) {
+            Ok(Some(interval)) => interval,
+            Ok(None) => self.config.slideshow.default_interval_secs,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load slideshow interval");
+                self.config.slideshow.default_interval_secs
+            }
+        }
+    }
+
+    /// Remember the open slideshow's interval for its directory
+    fn save_slideshow_interval(&self) {
+        let Some(ref slideshow) = self.slideshow_view else {
+            return;
+        };
+        if let Err(e) = self.db.set_slideshow_interval(&slideshow.directory.to_string_lossy(), slideshow.interval) {
+            tracing::warn!(error = %e, "Failed to save slideshow interval");
+        }
+    }
+
     /// Handle key events in slideshow mode
     fn handle_slideshow_key(&mut self, key: KeyEvent) -> Result<()> {
         let slideshow = match self.slideshow_view.as_mut() {
@@ -3733,11 +7474,17 @@ impl App {
             KeyCode::Char(' ') => slideshow.toggle_play(),
 
             // Speed control
-            KeyCode::Char('+') | KeyCode::Char('=') => slideshow.increase_interval(),
-            KeyCode::Char('-') => slideshow.decrease_interval(),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                slideshow.increase_interval();
+                self.save_slideshow_interval();
+            }
+            KeyCode::Char('-') => {
+                slideshow.decrease_interval();
+                self.save_slideshow_interval();
+            }
 
             // Toggle display mode (fullscreen/presenter)
-            KeyCode::Char('v') => slideshow.toggle_display_mode(),
+            KeyCode::Char('P') | KeyCode::Char('v') => slideshow.toggle_display_mode(),
 
             _ => {}
         }
@@ -3803,6 +7550,17 @@ impl App {
         Ok(())
     }
 
+    /// Grow or shrink the preview pane by `delta` percentage points.
+    fn resize_preview(&mut self, delta: i16) {
+        if self.config.ui.resize_preview(delta) {
+            self.pane_ratios_changed = true;
+            let [parent, current, preview] = self.config.ui.pane_ratios;
+            self.status_message = Some(format!("Panes: {}/{}/{}", parent, current, preview));
+        } else {
+            self.status_message = Some("Pane size limit reached".to_string());
+        }
+    }
+
     /// Toggle between showing only supported image files vs all files
     fn toggle_show_all_files(&mut self) -> Result<()> {
         self.show_all_files = !self.show_all_files;
@@ -3876,11 +7634,12 @@ impl App {
             return Ok(());
         }
 
-        let dialog = CentraliseDialog::new(
+        let mut dialog = CentraliseDialog::new(
             library_path,
             self.config.library.operation,
             source_files,
         );
+        dialog.dry_run = self.dry_run;
         self.centralise_dialog = Some(dialog);
         self.mode = AppMode::Centralising;
         Ok(())
@@ -3888,7 +7647,7 @@ impl App {
 
     /// Handle key events in centralise dialog
     fn handle_centralise_key(&mut self, key: KeyEvent) -> Result<()> {
-        use crate::centralise::{preview_centralise, execute_centralise};
+        use crate::centralise::preview_centralise;
 
         let dialog = match self.centralise_dialog.as_mut() {
             Some(d) => d,
@@ -3914,7 +7673,7 @@ impl App {
                             &self.db,
                             &dialog.library_path,
                             &dialog.source_files,
-                            self.config.library.max_filename_length,
+                            &self.config.library,
                         ) {
                             Ok(preview) => {
                                 dialog.preview = Some(preview);
@@ -3939,24 +7698,11 @@ impl App {
                     KeyCode::PageDown => dialog.page_down(15),
                     KeyCode::PageUp => dialog.page_up(15),
                     KeyCode::Enter => {
-                        // Execute the operation
-                        if let Some(ref preview) = dialog.preview {
+                        // Execute the operation in the background
+                        if let Some(preview) = dialog.preview.clone() {
                             dialog.mode = CentraliseDialogMode::Executing;
-                            match execute_centralise(&self.db, preview, dialog.operation) {
-                                Ok(result) => {
-                                    let success_count = result.succeeded.len();
-                                    dialog.result = Some(result);
-                                    dialog.mode = CentraliseDialogMode::Results;
-                                    self.status_message = Some(format!(
-                                        "Centralised {} files",
-                                        success_count
-                                    ));
-                                }
-                                Err(e) => {
-                                    dialog.mode = CentraliseDialogMode::Preview;
-                                    dialog.error = Some(e.to_string());
-                                }
-                            }
+                            let operation = dialog.operation;
+                            self.start_centralise(preview, operation);
                         }
                     }
                     _ => {}
@@ -3984,6 +7730,119 @@ impl App {
         Ok(())
     }
 
+    /// Run a previewed centralise in a background task.
+    fn start_centralise(&mut self, preview: crate::centralise::CentralisePreview, operation: crate::config::CentraliseOperation) {
+        let (task_id, tx, cancel_flag) = self.task_manager.register_task(TaskType::Centralise);
+        let (outcome_tx, outcome_rx) = mpsc::channel();
+        let db_config = self.config.database.clone();
+        let dry_run = self.dry_run;
+        let total = preview.operations.len();
+
+        std::thread::spawn(move || {
+            let db = match Database::open(&db_config) {
+                Ok(db) => db,
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed {
+                        error: format!("Failed to open database: {}", e),
+                    });
+                    return;
+                }
+            };
+            let mut progress = TransferProgress::start(tx.clone(), preview.operations.len(), preview.total_bytes);
+            let result = crate::centralise::execute_centralise_with_progress(&db, &preview, operation, dry_run, |planned| {
+                if cancel_flag.load(Ordering::SeqCst) {
+                    return false;
+                }
+                progress.file(&planned.source, planned.size_bytes);
+                true
+            });
+            let message = match result {
+                Ok(ref result) => match result.conflict_summary() {
+                    Some(conflicts) => format!("Centralised {} files ({})", result.succeeded.len(), conflicts),
+                    None => format!("Centralised {} files", result.succeeded.len()),
+                },
+                Err(ref e) => e.to_string(),
+            };
+            let failed = result.is_err();
+            let _ = outcome_tx.send(BatchOutcome::Centralise(result.map_err(|e| e.to_string())));
+            let _ = tx.send(if cancel_flag.load(Ordering::SeqCst) {
+                TaskUpdate::Cancelled
+            } else if failed {
+                TaskUpdate::Failed { error: message }
+            } else {
+                TaskUpdate::Completed { message }
+            });
+        });
+
+        self.batch_progress = Some(BatchProgressDialog {
+            task_id,
+            verb: if operation == crate::config::CentraliseOperation::Copy { "Copying" } else { "Moving" },
+            total,
+            outcome: outcome_rx,
+        });
+        self.mode = AppMode::BatchProgress;
+    }
+
+    /// Close the progress dialog of a finished move or centralise and show
+    /// what it did.
+    fn finish_batch_operation(&mut self, task_id: crate::tasks::TaskId, message: &str) -> Result<()> {
+        if self.batch_progress.as_ref().map(|d| d.task_id) != Some(task_id) {
+            return Ok(());
+        }
+        let Some(dialog) = self.batch_progress.take() else { return Ok(()) };
+
+        match dialog.outcome.try_recv() {
+            Ok(BatchOutcome::Move { target_dir, summary }) => {
+                self.status_message = Some(summary.message(&target_dir));
+                self.operation_history.record(ReversibleOperation::Move(summary.moved));
+                self.selected_files.clear();
+                self.mode = AppMode::Normal;
+            }
+            Ok(BatchOutcome::Centralise(result)) => {
+                self.mode = AppMode::Centralising;
+                if let Some(centralise) = self.centralise_dialog.as_mut() {
+                    match result {
+                        Ok(result) => {
+                            let success_count = result.succeeded.len();
+                            let conflicts = result.conflict_summary().map(|c| format!(" ({})", c)).unwrap_or_default();
+                            if self.dry_run {
+                                for op in &result.succeeded {
+                                    let verb = if op.was_copy { "COPY" } else { "MOVE" };
+                                    tracing::info!("WOULD {}: {} → {}", verb, op.source.display(), op.destination.display());
+                                }
+                            }
+                            centralise.result = Some(result);
+                            centralise.mode = CentraliseDialogMode::Results;
+                            self.status_message = Some(if self.dry_run {
+                                format!("Dry run: would centralise {} files{}", success_count, conflicts)
+                            } else {
+                                format!("Centralised {} files{}", success_count, conflicts)
+                            });
+                        }
+                        Err(e) => {
+                            centralise.mode = CentraliseDialogMode::Preview;
+                            centralise.error = Some(e);
+                        }
+                    }
+                }
+            }
+            // The worker could not start, e.g. the database failed to open
+            Err(_) => {
+                if let Some(centralise) = self.centralise_dialog.as_mut() {
+                    centralise.mode = CentraliseDialogMode::Preview;
+                    centralise.error = Some(message.to_string());
+                    self.mode = AppMode::Centralising;
+                } else {
+                    self.mode = AppMode::Normal;
+                }
+                self.status_message = Some(message.to_string());
+            }
+        }
+
+        self.load_directory(&self.current_dir.clone())?;
+        Ok(())
+    }
+
     fn handle_confirm_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
         use crate::ui::confirm_dialog::ConfirmFocus;
 
@@ -4063,6 +7922,11 @@ impl App {
                         dialog.toggle_focus();
                     }
                 }
+                KeyCode::Char('P') => {
+                    if let Some(dialog) = self.confirm_dialog.as_mut() {
+                        dialog.toggle_pipeline();
+                    }
+                }
                 KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
                     // User confirmed - execute the pending action
                     if let Some(dialog) = self.confirm_dialog.take() {
@@ -4073,6 +7937,10 @@ impl App {
                             let dir_str = self.current_dir.to_string_lossy().to_string();
                             let _ = self.db.set_directory_prompt(&dir_str, &dialog.prompt_text);
                         }
+                        if dialog.pipeline {
+                            self.start_pipeline();
+                            return Ok(());
+                        }
                         let custom_prompt = if dialog.has_prompt_field {
                             if dialog.prompt_text.is_empty() { None } else { Some(dialog.prompt_text.clone()) }
                         } else {
@@ -4097,11 +7965,14 @@ impl App {
     fn execute_confirmed_action_with_prompt(&mut self, action: Action, custom_prompt: Option<String>) -> Result<()> {
         match action {
             Action::Scan => self.start_scan()?,
+            Action::ScanAll => self.start_scan_all(),
             Action::DescribeWithLlm => self.describe_with_llm(custom_prompt)?,
             Action::BatchLlm => self.start_batch_llm(custom_prompt)?,
             Action::DetectFaces => self.start_face_scan()?,
             Action::ClusterFaces => self.cluster_faces()?,
+            Action::RegenerateFaceEmbeddings => self.regenerate_face_embeddings()?,
             Action::ClipEmbedding => self.start_clip_embedding()?,
+            Action::WriteExifToFiles => self.start_metadata_write(),
             _ => {} // Other actions don't need confirmation
         }
         Ok(())
@@ -4124,6 +7995,38 @@ impl App {
 
     // --- Settings dialog methods ---
 
+    /// Re-read the config file and apply it without restarting. Settings
+    /// only read at startup keep their running values and are named in
+    /// the status message.
+    fn reload_config(&mut self) {
+        let path = self.config_file.clone().unwrap_or_else(Config::config_path);
+        let mut new_config = match Config::load_from(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                self.status_message = Some(format!("Error reloading config: {}", e));
+                return;
+            }
+        };
+
+        let needs_restart = self.config.restart_required_changes(&new_config);
+        new_config.database = self.config.database.clone();
+        new_config.preview.protocol = self.config.preview.protocol;
+        new_config.thumbnails.path = self.config.thumbnails.path.clone();
+
+        self.config = new_config;
+        self.llm_client = LlmClient::from_config(&self.config.llm);
+        self.trash_manager = TrashManager::new(self.config.trash.clone());
+        self.duplicate_trash_manager = TrashManager::new_from_duplicate_config(self.config.duplicate_trash.clone());
+        self.action_map = self.config.keybindings.build_action_map();
+        self.theme = Theme::from_config(&self.config.theme);
+
+        self.status_message = Some(if needs_restart.is_empty() {
+            "Config reloaded".to_string()
+        } else {
+            format!("Config reloaded; restart to apply {}", needs_restart.join(", "))
+        });
+    }
+
     fn open_settings_dialog(&mut self) {
         self.settings_dialog = Some(crate::ui::settings_dialog::SettingsDialog::new(&self.config));
         self.mode = AppMode::Settings;
@@ -4208,18 +8111,72 @@ impl App {
             }
             // Reload config (Ctrl+R)
             KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                match Config::load() {
-                    Ok(new_config) => {
-                        self.config = new_config;
-                        self.llm_client = LlmClient::from_config(&self.config.llm);
-                        // Recreate settings dialog with fresh config
-                        self.settings_dialog = Some(crate::ui::settings_dialog::SettingsDialog::new(&self.config));
-                        self.status_message = Some("Config reloaded from file".to_string());
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Error reloading config: {}", e));
-                    }
-                }
+                self.reload_config();
+                // Recreate settings dialog with fresh config
+                self.settings_dialog = Some(crate::ui::settings_dialog::SettingsDialog::new(&self.config));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    // --- Theme methods ---
+
+    /// Switch to a built-in theme by name, keeping any color overrides from
+    /// config. Returns false if no such theme exists.
+    pub fn apply_theme(&mut self, name: &str) -> bool {
+        if Theme::builtin(name).is_none() {
+            return false;
+        }
+        let theme_config = crate::config::ThemeConfig {
+            name: name.to_string(),
+            ..self.config.theme.clone()
+        };
+        self.theme = Theme::from_config(&theme_config);
+        true
+    }
+
+    fn open_theme_picker(&mut self) {
+        self.theme_dialog = Some(ThemeDialog::new(&self.theme.name));
+        self.mode = AppMode::ThemePicking;
+    }
+
+    fn handle_theme_picker_key(&mut self, key: KeyEvent) -> Result<()> {
+        let dialog = match self.theme_dialog.as_mut() {
+            Some(d) => d,
+            None => {
+                self.mode = AppMode::Normal;
+                return Ok(());
+            }
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                // Restore the theme that was active before previewing
+                let original = dialog.original.clone();
+                self.apply_theme(&original);
+                self.theme_dialog = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                dialog.move_down();
+                let name = dialog.selected_name();
+                self.apply_theme(name);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                dialog.move_up();
+                let name = dialog.selected_name();
+                self.apply_theme(name);
+            }
+            KeyCode::Enter => {
+                let name = dialog.selected_name();
+                self.apply_theme(name);
+                self.config.theme.name = name.to_string();
+                let _ = self.config.save(); // Ignore save errors to not disrupt the UI
+                self.status_message = Some(format!("Theme: {}", name));
+                self.theme_dialog = None;
+                self.mode = AppMode::Normal;
             }
             _ => {}
         }
@@ -4238,3 +8195,12 @@ fn is_image(filename: &str) -> bool {
         || lower.ends_with(".heic")
         || lower.ends_with(".heif")
 }
+
+/// Embed every term of a search query.
+fn embed_terms(query: &SearchAst, embed: impl Fn(&str) -> Result<Vec<f32>>) -> Result<HashMap<String, Vec<f32>>> {
+    query
+        .terms()
+        .into_iter()
+        .map(|term| Ok((term.to_string(), embed(term)?)))
+        .collect()
+}
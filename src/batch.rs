@@ -0,0 +1,294 @@
+//! Scriptable batch mode: run one action without the TUI.
+//!
+//! Progress goes to stderr so stdout carries only the result, either a
+//! one-line summary or, with `--json`, a JSON document for pipelines.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc};
+
+use crate::config::Config;
+use crate::db::{Database, DirectorySummary, SimilarityGroup};
+use crate::export::{export_photos, ExportFormat, ExportResult};
+use crate::llm::{LlmClient, LlmQueue, LlmTask};
+use crate::scanner::{ScanErrorLog, ScanResult, Scanner};
+use crate::tasks::TaskUpdate;
+
+/// Action requested on the command line
+#[derive(Debug, Clone)]
+pub enum BatchAction {
+    Scan { directory: PathBuf },
+    /// Scan every directory in `scanner.auto_scan_dirs`
+    ScanAll,
+    Describe { directory: PathBuf },
+    FindDuplicates,
+    Export { output: PathBuf },
+    /// Every directory with scanned photos, with counts and sizes
+    ListDirectories,
+}
+
+impl BatchAction {
+    /// Parse an action name and its argument, e.g. `scan ~/photos`
+    pub fn parse(name: &str, arg: Option<&str>) -> Result<Self> {
+        let arg = |what: &str| match arg {
+            Some(value) => Ok(PathBuf::from(value)),
+            None => bail!("{} requires {}", name, what),
+        };
+        match name {
+            "scan" => Ok(BatchAction::Scan { directory: arg("a directory")? }),
+            "describe" => Ok(BatchAction::Describe { directory: arg("a directory")? }),
+            "find-duplicates" => Ok(BatchAction::FindDuplicates),
+            "export" => Ok(BatchAction::Export { output: arg("an output file")? }),
+            _ => bail!("Unknown action: {}", name),
+        }
+    }
+
+    /// Whether the action takes a path argument
+    pub fn takes_argument(name: &str) -> bool {
+        matches!(name, "scan" | "describe" | "export")
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            BatchAction::Scan { .. } => "scan",
+            BatchAction::ScanAll => "scan-all",
+            BatchAction::Describe { .. } => "describe",
+            BatchAction::FindDuplicates => "find-duplicates",
+            BatchAction::Export { .. } => "export",
+            BatchAction::ListDirectories => "list-directories",
+        }
+    }
+}
+
+/// Result of a batch action, tagged with the action name in JSON
+#[derive(Debug, Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+enum BatchOutput {
+    Scan {
+        directory: PathBuf,
+        #[serde(flatten)]
+        result: ScanResult,
+        errors: Vec<ScanErrorLog>,
+    },
+    ScanAll {
+        directories: Vec<PathBuf>,
+        #[serde(flatten)]
+        result: ScanResult,
+        errors: Vec<ScanErrorLog>,
+    },
+    Describe {
+        directory: PathBuf,
+        total: usize,
+        processed: usize,
+        failed: usize,
+    },
+    FindDuplicates {
+        groups: Vec<SimilarityGroup>,
+    },
+    Export(ExportResult),
+    ListDirectories {
+        directories: Vec<DirectorySummary>,
+    },
+}
+
+impl BatchOutput {
+    /// The `--json` document: the tagged output, except that directory
+    /// listings are a bare array scripts can iterate directly
+    fn to_json(&self) -> serde_json::Result<String> {
+        match self {
+            BatchOutput::ListDirectories { directories } => serde_json::to_string_pretty(directories),
+            output => serde_json::to_string_pretty(output),
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            BatchOutput::Scan { directory, result, errors } => format!(
+                "Scanned {}: {} found, {} scanned, {} new, {} updated, {} errors",
+                directory.display(),
+                result.total_found,
+                result.scanned,
+                result.new,
+                result.updated,
+                errors.len()
+            ),
+            BatchOutput::ScanAll { directories, result, errors } => format!(
+                "Scanned {} directories: {} found, {} scanned, {} new, {} updated, {} errors",
+                directories.len(),
+                result.total_found,
+                result.scanned,
+                result.new,
+                result.updated,
+                errors.len()
+            ),
+            BatchOutput::Describe { directory, total, processed, failed } => format!(
+                "Described {}: {} of {} photos, {} failed",
+                directory.display(),
+                processed,
+                total,
+                failed
+            ),
+            BatchOutput::FindDuplicates { groups } => {
+                let photos: usize = groups.iter().map(|g| g.photos.len()).sum();
+                format!("Found {} duplicate groups ({} photos)", groups.len(), photos)
+            }
+            BatchOutput::Export(result) => format!(
+                "Exported {} photos to {} ({})",
+                result.photos,
+                result.path.display(),
+                result.format
+            ),
+            // One line per directory for shell loops: path, photos, bytes
+            BatchOutput::ListDirectories { directories } => directories
+                .iter()
+                .map(|d| format!("{}\t{}\t{}", d.directory, d.photo_count, d.total_bytes))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+/// Run `action` and print its result to stdout
+pub fn run(config: Config, action: BatchAction, json: bool) -> Result<()> {
+    let db = Database::open(&config.database)?;
+    db.initialize()?;
+
+    let label = action.label();
+    let output = match action {
+        BatchAction::Scan { directory } => {
+            // Photos are stored under absolute paths
+            let directory = std::fs::canonicalize(&directory).unwrap_or(directory);
+            let scanner = Scanner::new(config);
+            let (result, errors) = with_progress(label, |tx| {
+                scanner.scan_directory_cancellable(&directory, &db, tx, Arc::new(AtomicBool::new(false)))
+            })?;
+            BatchOutput::Scan { directory, result, errors }
+        }
+        BatchAction::ScanAll => {
+            let directories: Vec<PathBuf> = config
+                .scanner
+                .auto_scan_dirs
+                .iter()
+                .map(|d| std::fs::canonicalize(d).unwrap_or_else(|_| d.clone()))
+                .collect();
+            if directories.is_empty() {
+                bail!("No directories to scan: set scanner.auto_scan_dirs in the config");
+            }
+            let scanner = Scanner::new(config);
+            let (result, errors) = with_progress(label, |tx| {
+                scanner.scan_directories_cancellable(&directories, &db, tx, Arc::new(AtomicBool::new(false)))
+            })?;
+            BatchOutput::ScanAll { directories, result, errors }
+        }
+        BatchAction::Describe { directory } => {
+            let directory = std::fs::canonicalize(&directory).unwrap_or(directory);
+            let tasks: Vec<LlmTask> = db
+                .get_photos_without_description_in_dir(&directory)?
+                .into_iter()
+                .map(|(id, path)| LlmTask { photo_id: id, photo_path: PathBuf::from(path) })
+                .collect();
+            let total = tasks.len();
+
+            let mut queue = LlmQueue::new(LlmClient::from_config(&config.llm));
+            queue.add_tasks(tasks);
+            let concurrency = config.llm.concurrency();
+            let (processed, failed) = with_progress(label, |tx| {
+                queue.process_all_parallel(&config.database, tx, Arc::new(AtomicBool::new(false)), concurrency)
+            })?;
+            BatchOutput::Describe { directory, total, processed, failed }
+        }
+        BatchAction::FindDuplicates => {
+            eprintln!("{}: searching...", label);
+            let mut groups = db.find_exact_duplicates()?;
+            groups.extend(db.find_perceptual_duplicates(config.scanner.similarity_threshold)?);
+            groups.extend(db.find_embedding_clusters(config.scanner.embedding_similarity_threshold)?);
+            BatchOutput::FindDuplicates { groups }
+        }
+        BatchAction::Export { output } => {
+            let Some(format) = ExportFormat::from_path(&output) else {
+                bail!("Cannot tell the export format of {}: use .json, .csv or .html", output.display());
+            };
+            eprintln!("{}: writing {}...", label, output.display());
+            let photos = export_photos(&db, &output, format)?;
+            BatchOutput::Export(ExportResult { path: output, format: format.name(), photos })
+        }
+        BatchAction::ListDirectories => BatchOutput::ListDirectories { directories: db.list_directories()? },
+    };
+
+    if json {
+        println!("{}", output.to_json()?);
+    } else {
+        let summary = output.summary();
+        if !summary.is_empty() {
+            println!("{}", summary);
+        }
+    }
+    Ok(())
+}
+
+/// Run `work` with a task update channel, echoing its progress to stderr.
+/// Fails if the work reports a failure.
+fn with_progress<T>(label: &str, work: impl FnOnce(mpsc::Sender<TaskUpdate>) -> T) -> Result<T> {
+    let (tx, rx) = mpsc::channel();
+    let label = label.to_string();
+
+    let printer = std::thread::spawn(move || {
+        let mut failure = None;
+        for update in rx {
+            match update {
+                TaskUpdate::Started { total } => eprintln!("{}: {} items", label, total),
+                TaskUpdate::Progress(progress) => match progress.current_item {
+                    Some(item) => eprintln!("{}: {}/{} {}", label, progress.current, progress.total, item),
+                    None => eprintln!("{}: {}/{}", label, progress.current, progress.total),
+                },
+                TaskUpdate::Completed { message } => eprintln!("{}: {}", label, message),
+                TaskUpdate::Cancelled => eprintln!("{}: cancelled", label),
+                TaskUpdate::Failed { error } => failure = Some(error),
+            }
+        }
+        failure
+    });
+
+    // The sender is dropped when `work` returns, which ends the printer
+    let result = work(tx);
+    match printer.join() {
+        Ok(Some(error)) => bail!(error),
+        _ => Ok(result),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_parses_actions() {
+        assert!(matches!(
+            BatchAction::parse("scan", Some("/photos")),
+            Ok(BatchAction::Scan { directory }) if directory == Path::new("/photos")
+        ));
+        assert!(matches!(BatchAction::parse("find-duplicates", None), Ok(BatchAction::FindDuplicates)));
+        assert!(BatchAction::parse("export", None).is_err());
+        assert!(BatchAction::parse("frobnicate", None).is_err());
+    }
+
+    #[test]
+    fn test_lists_directories_one_per_line() {
+        let (_dir, db) = crate::db::test_db();
+        for (path, size) in [("/photos/b/2.jpg", 300), ("/photos/a/1.jpg", 100), ("/photos/b/3.jpg", 200)] {
+            let path = Path::new(path);
+            let directory = path.parent().unwrap().to_string_lossy();
+            let filename = path.file_name().unwrap().to_string_lossy();
+            db.insert_basic_photo(&path.to_string_lossy(), &filename, &directory, size).unwrap();
+        }
+
+        let output = BatchOutput::ListDirectories { directories: db.list_directories().unwrap() };
+        assert_eq!(output.summary(), "/photos/a\t1\t100\n/photos/b\t2\t500");
+        let json: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        assert_eq!(json[1]["directory"], "/photos/b");
+        assert_eq!(json[1]["photo_count"], 2);
+    }
+}
@@ -5,7 +5,8 @@
 //! - Batch LLM description processing
 //! - Face detection on new photos
 //!
-//! The daemon communicates with the TUI via the shared SQLite database.
+//! The daemon communicates with the TUI via the shared SQLite database, and
+//! answers shell scripts on a unix socket (see `clepho::ipc`).
 //!
 //! ## Usage
 //!
@@ -25,6 +26,7 @@
 use anyhow::{Context, Result};
 use chrono::{Local, NaiveTime};
 use std::path::PathBuf;
+use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 use tracing::{error, info, warn};
@@ -32,6 +34,11 @@ use walkdir::WalkDir;
 
 use clepho::config::Config;
 use clepho::db::{Database, ScheduledTask, ScheduledTaskType};
+#[cfg(unix)]
+use clepho::{db::ScheduleStatus, ipc::{self, Request, Response}};
+
+/// `app_state` key holding when the daemon last finished a scan
+const LAST_SCAN_STATE: &str = "daemon_last_scan";
 
 /// Daemon configuration
 struct DaemonConfig {
@@ -77,7 +84,12 @@ fn main() -> Result<()> {
         process_pending_tasks(&db, &config)?;
     } else {
         info!("Running in daemon mode, polling every {} seconds", daemon_config.poll_interval);
-        run_daemon_loop(&db, &config, daemon_config.poll_interval)?;
+        let (wake_tx, wake_rx) = mpsc::channel();
+        #[cfg(unix)]
+        spawn_ipc_server(&config, wake_tx);
+        #[cfg(not(unix))]
+        drop(wake_tx);
+        run_daemon_loop(&db, &config, daemon_config.poll_interval, wake_rx)?;
     }
 
     info!("Clepho daemon stopped");
@@ -199,10 +211,13 @@ fn load_config(daemon_config: &DaemonConfig) -> Result<Config> {
     }
 }
 
+/// Poll for due tasks until killed. A message on `wake` starts the next
+/// cycle early, e.g. for a scan queued over the socket.
 fn run_daemon_loop(
     db: &Database,
     config: &Config,
     poll_interval: u64,
+    wake: mpsc::Receiver<()>,
 ) -> Result<()> {
     loop {
         // Check if we should process (based on hours of operation)
@@ -215,10 +230,79 @@ fn run_daemon_loop(
         }
 
         // Sleep until next poll
-        thread::sleep(Duration::from_secs(poll_interval));
+        let interval = Duration::from_secs(poll_interval);
+        if let Err(mpsc::RecvTimeoutError::Disconnected) = wake.recv_timeout(interval) {
+            thread::sleep(interval);
+        }
     }
 }
 
+/// Answer socket requests on a thread of their own, with a separate
+/// database connection so they are not held up by a running task.
+#[cfg(unix)]
+fn spawn_ipc_server(config: &Config, wake: mpsc::Sender<()>) {
+    let database = config.database.clone();
+    thread::spawn(move || {
+        let result = (|| -> Result<()> {
+            let db = Database::open(&database)?;
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+            runtime.block_on(async {
+                let listener = ipc::bind()?;
+                info!("Listening for commands on {}", ipc::socket_path().display());
+                ipc::serve(listener, |request| handle_request(&db, request, &wake)).await
+            })
+        })();
+        if let Err(e) = result {
+            error!("Command socket stopped: {:#}", e);
+        }
+    });
+}
+
+#[cfg(unix)]
+fn handle_request(db: &Database, request: Request, wake: &mpsc::Sender<()>) -> Response {
+    let result = match request {
+        Request::Status => db.get_all_schedules().and_then(|tasks| {
+            let tasks = tasks
+                .into_iter()
+                .filter(|t| matches!(t.status, ScheduleStatus::Pending | ScheduleStatus::Running))
+                .collect();
+            let last_scan = db.get_app_state(LAST_SCAN_STATE)?;
+            Ok(Response::Status { tasks, last_scan })
+        }),
+        Request::Scan { path } => queue_scan(db, &path).map(|id| {
+            let _ = wake.send(());
+            Response::Scheduled { scheduled: id }
+        }),
+        Request::Cancel { task_id } => cancel_task(db, task_id).map(|()| Response::Cancelled { cancelled: task_id }),
+        Request::GetPhoto { path } => db.get_photo_metadata(&path).map(|photo| Response::Photo { photo: photo.map(Box::new) }),
+    };
+    result.unwrap_or_else(Response::error)
+}
+
+#[cfg(unix)]
+fn queue_scan(db: &Database, path: &std::path::Path) -> Result<i64> {
+    let path = path.canonicalize().with_context(|| format!("Cannot scan {}", path.display()))?;
+    if !path.is_dir() {
+        anyhow::bail!("{} is not a directory", path.display());
+    }
+    // Due tasks are compared against the database's UTC clock
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+    db.create_scheduled_task(ScheduledTaskType::Scan, &path.to_string_lossy(), None, &now, None, None)
+}
+
+#[cfg(unix)]
+fn cancel_task(db: &Database, task_id: i64) -> Result<()> {
+    let task = db
+        .get_all_schedules()?
+        .into_iter()
+        .find(|t| t.id == task_id)
+        .with_context(|| format!("No task {}", task_id))?;
+    if task.status != ScheduleStatus::Pending {
+        anyhow::bail!("Task {} is {} and cannot be cancelled", task_id, task.status.as_str());
+    }
+    db.cancel_schedule(task_id)
+}
+
 fn should_process_now(config: &Config) -> bool {
     let (start, end) = match (config.schedule.default_hours_start, config.schedule.default_hours_end) {
         (Some(s), Some(e)) => (s, e),
@@ -298,7 +382,13 @@ fn task_within_hours(task: &ScheduledTask) -> bool {
 
 fn execute_task(task: &ScheduledTask, config: &Config, db: &Database) -> Result<()> {
     match task.task_type {
-        ScheduledTaskType::Scan => execute_scan_task(&task.target_path, db),
+        ScheduledTaskType::Scan => {
+            execute_scan_task(&task.target_path, db)?;
+            // New photos may match smart album filters
+            db.refresh_smart_albums()?;
+            let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            db.set_app_state(LAST_SCAN_STATE, &now)
+        }
         ScheduledTaskType::LlmBatch => execute_llm_batch_task(&task.target_path, config, db),
         ScheduledTaskType::FaceDetection => execute_face_detection_task(&task.target_path, db),
     }
@@ -1,6 +1,7 @@
 //! File centralization - organize photos into a managed library location.
 //!
-//! Organizes files into a Year/Month hierarchy with descriptive filenames:
+//! Organizes files into date folders (Year/Month by default, see
+//! `library.folder_structure`) with descriptive filenames:
 //! ```text
 //! /Library/
 //! ├── 2024/
@@ -10,13 +11,15 @@
 //!     └── {NO_CAT}_old-photo-scan_001.jpg
 //! ```
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::{Datelike, NaiveDateTime, Timelike};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::config::CentraliseOperation;
-use crate::db::{Database, PhotoMetadata};
+use crate::config::{CentraliseConflictPolicy, CentraliseOperation, FolderStructure, LibraryConfig};
+use crate::db::events::{parse_taken_at, EVENT_DATE_FORMAT};
+use crate::scanner::hashing::calculate_hashes;
+use crate::db::{Database, Event, PhotoMetadata};
 
 /// Marker for uncategorized content
 const NO_CAT: &str = "{NO_CAT}";
@@ -30,6 +33,23 @@ pub struct CentraliseResult {
     pub failed: Vec<(PathBuf, String)>,
     /// Files skipped (already in library, etc.)
     pub skipped: Vec<(PathBuf, String)>,
+    /// Skipped because an identical file already has their destination
+    pub identical: usize,
+    /// Given the next free numbered name because their destination was taken
+    pub renamed: usize,
+}
+
+impl CentraliseResult {
+    /// "N identical, M conflicts resolved by renaming", when there were any
+    pub fn conflict_summary(&self) -> Option<String> {
+        if self.identical == 0 && self.renamed == 0 {
+            return None;
+        }
+        Some(format!(
+            "{} identical, {} conflicts resolved by renaming",
+            self.identical, self.renamed
+        ))
+    }
 }
 
 /// A single file operation
@@ -53,6 +73,11 @@ pub struct CentralisePreview {
     pub skipped: Vec<(PathBuf, String)>,
     /// Total bytes to be processed
     pub total_bytes: u64,
+    /// Destinations that exist by the time the files are moved are resolved
+    /// with this policy
+    pub conflict_policy: CentraliseConflictPolicy,
+    /// Limit for numbered names given to resolve conflicts
+    pub max_filename_length: usize,
 }
 
 /// A planned file operation (for dry-run preview)
@@ -62,11 +87,9 @@ pub struct PlannedOperation {
     pub source: PathBuf,
     /// Proposed destination path
     pub destination: PathBuf,
-    /// File size in bytes (reserved for UI display)
-    #[allow(dead_code)]
+    /// File size in bytes
     pub size_bytes: u64,
-    /// Generated filename components for display (reserved for UI display)
-    #[allow(dead_code)]
+    /// Components the destination filename was generated from
     pub filename_parts: FilenameParts,
 }
 
@@ -84,7 +107,8 @@ pub struct FilenameParts {
 }
 
 impl FilenameParts {
-    /// Generate the full filename from parts
+    /// Generate the full filename from parts, at most `max_length`
+    /// characters including the extension
     pub fn to_filename(&self, max_length: usize) -> String {
         let mut parts = Vec::new();
 
@@ -123,20 +147,22 @@ impl FilenameParts {
             parts.push(sanitize_filename(&self.original_name));
         }
 
-        // Add count
-        parts.push(format!("{:03}", self.count));
-
         let mut filename = parts.join("_");
-
-        // Truncate if too long (leaving room for extension)
-        let ext_len = self.extension.len() + 1; // +1 for the dot
-        if filename.len() > max_length.saturating_sub(ext_len) {
-            filename = filename[..max_length.saturating_sub(ext_len)].to_string();
+        let count = format!("{:03}", self.count);
+
+        // Truncate if too long, leaving room for the count and extension so
+        // numbered names stay distinct. Characters are counted so multi-byte
+        // names are not cut mid-character.
+        let suffix_len = count.len() + 1 + self.extension.len() + 1; // "_NNN" and ".ext"
+        let max_chars = max_length.saturating_sub(suffix_len);
+        let max_bytes = MAX_NAME_BYTES.saturating_sub(suffix_len);
+        if filename.chars().count() > max_chars || filename.len() > max_bytes {
+            filename = truncate_name(&filename, max_chars, max_bytes).to_string();
             // Clean up any trailing underscore or hyphen
             filename = filename.trim_end_matches(|c| c == '_' || c == '-').to_string();
         }
 
-        format!("{}.{}", filename, self.extension)
+        format!("{}_{}.{}", filename, count, self.extension)
     }
 
     /// Check if this file has the NO_CAT marker
@@ -146,14 +172,36 @@ impl FilenameParts {
     }
 }
 
-/// Sanitize a string for use in filenames
+/// Longest filename most filesystems accept, in bytes
+const MAX_NAME_BYTES: usize = 255;
+
+/// Longest prefix of `s` with at most `max_chars` characters and
+/// `max_bytes` bytes
+fn truncate_name(s: &str, max_chars: usize, max_bytes: usize) -> &str {
+    let end = s
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .take(max_chars)
+        .take_while(|&end| end <= max_bytes)
+        .last()
+        .unwrap_or(0);
+    &s[..end]
+}
+
+/// Sanitize a string for use in filenames. Accented Latin letters become
+/// their ASCII equivalents (`é` → `e`, `ß` → `ss`); letters and digits of
+/// other scripts, such as Chinese, Japanese or Arabic names, are kept.
 fn sanitize_filename(s: &str) -> String {
     s.to_lowercase()
         .chars()
         .map(|c| match c {
-            'a'..='z' | '0'..='9' => c,
-            ' ' | '_' | '-' => '-',
-            _ => '-',
+            'a'..='z' | '0'..='9' => c.to_string(),
+            c if is_latin(c) => deunicode::deunicode_char(c)
+                .filter(|t| t.chars().all(|t| t.is_ascii_alphanumeric()))
+                .map(str::to_lowercase)
+                .unwrap_or_else(|| "-".to_string()),
+            c if c.is_alphanumeric() => c.to_string(),
+            _ => "-".to_string(),
         })
         .collect::<String>()
         // Collapse multiple hyphens
@@ -163,6 +211,11 @@ fn sanitize_filename(s: &str) -> String {
         .join("-")
 }
 
+/// Latin-1 Supplement, Latin Extended-A/B and Latin Extended Additional
+fn is_latin(c: char) -> bool {
+    matches!(c, '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}')
+}
+
 /// Extract event/category from description or tags
 fn extract_event(metadata: &PhotoMetadata) -> Option<String> {
     // Try to extract from tags first
@@ -262,23 +315,48 @@ pub fn generate_filename_parts(metadata: &PhotoMetadata, existing_count: u32) ->
     parts
 }
 
-/// Determine the destination folder path for a photo
-pub fn get_destination_folder(library_root: &Path, metadata: &PhotoMetadata) -> PathBuf {
-    // Parse date from taken_at
-    if let Some(ref taken_at) = metadata.taken_at {
-        if let Ok(dt) = NaiveDateTime::parse_from_str(taken_at, "%Y-%m-%d %H:%M:%S") {
-            return library_root
-                .join(format!("{:04}", dt.year()))
-                .join(format!("{:02}", dt.month()));
-        } else if let Ok(dt) = NaiveDateTime::parse_from_str(taken_at, "%Y:%m:%d %H:%M:%S") {
-            return library_root
-                .join(format!("{:04}", dt.year()))
-                .join(format!("{:02}", dt.month()));
-        }
+/// Determine the destination folder path for a photo. With
+/// `events_as_subfolders`, a photo taken during one of `events` goes in a
+/// folder named after it below the date folders.
+pub fn get_destination_folder(
+    library_root: &Path,
+    metadata: &PhotoMetadata,
+    structure: FolderStructure,
+    events_as_subfolders: Option<&[Event]>,
+) -> PathBuf {
+    // No date - use "unknown" folder
+    let Some(dt) = metadata.taken_at.as_deref().and_then(parse_taken_at) else {
+        return library_root.join("unknown");
+    };
+
+    let year = format!("{:04}", dt.year());
+    let folder = match structure {
+        FolderStructure::YearMonth => library_root.join(year).join(format!("{:02}", dt.month())),
+        FolderStructure::YearMonthDay => library_root.join(dt.format("%Y-%m-%d").to_string()),
+        FolderStructure::YearSlashMonthSlashDay => library_root
+            .join(year)
+            .join(format!("{:02}", dt.month()))
+            .join(format!("{:02}", dt.day())),
+        FolderStructure::Year => library_root.join(year),
+    };
+
+    match events_as_subfolders.and_then(|events| event_folder(events, dt)) {
+        Some(event) => folder.join(event),
+        None => folder,
     }
+}
 
-    // No date - use "unknown" folder
-    library_root.join("unknown")
+/// Folder name of the event `taken` falls in
+fn event_folder(events: &[Event], taken: NaiveDateTime) -> Option<String> {
+    events
+        .iter()
+        .find(|event| {
+            let start = NaiveDateTime::parse_from_str(&event.start_date, EVENT_DATE_FORMAT);
+            let end = NaiveDateTime::parse_from_str(&event.end_date, EVENT_DATE_FORMAT);
+            matches!((start, end), (Ok(start), Ok(end)) if start <= taken && taken <= end)
+        })
+        .map(|event| sanitize_filename(&event.name))
+        .filter(|name| !name.is_empty())
 }
 
 /// Preview what a centralise operation would do (dry-run)
@@ -286,8 +364,10 @@ pub fn preview_centralise(
     db: &Database,
     library_root: &Path,
     source_paths: &[PathBuf],
-    max_filename_length: usize,
+    library: &LibraryConfig,
 ) -> Result<CentralisePreview> {
+    let max_filename_length = library.max_filename_length;
+    let events = if library.use_event_as_subfolder { Some(db.get_events()?) } else { None };
     let mut operations = Vec::new();
     let mut skipped = Vec::new();
     let mut total_bytes = 0u64;
@@ -318,7 +398,7 @@ pub fn preview_centralise(
         };
 
         // Determine destination folder
-        let dest_folder = get_destination_folder(library_root, &metadata);
+        let dest_folder = get_destination_folder(library_root, &metadata, library.folder_structure, events.as_deref());
 
         // Generate filename
         let base_dest = dest_folder.clone();
@@ -328,15 +408,17 @@ pub fn preview_centralise(
 
         let mut destination = dest_folder.join(&filename);
 
-        // Handle conflicts by incrementing count
+        // Handle clashes within this batch by incrementing count; files
+        // already in the library are left to the conflict policy
         let mut conflict_count = count;
-        while dest_counts.values().any(|_| destination.exists()) ||
-              operations.iter().any(|op: &PlannedOperation| op.destination == destination) {
+        let mut filename_parts = filename_parts;
+        while operations.iter().any(|op: &PlannedOperation| op.destination == destination) {
+            if conflict_count - count >= MAX_NUMBERING_ATTEMPTS {
+                bail!("No free filename for {} in {}", source.display(), dest_folder.display());
+            }
             conflict_count += 1;
-            let mut new_parts = filename_parts.clone();
-            new_parts.count = conflict_count + 1;
-            let new_filename = new_parts.to_filename(max_filename_length);
-            destination = dest_folder.join(&new_filename);
+            filename_parts.count = conflict_count + 1;
+            destination = dest_folder.join(filename_parts.to_filename(max_filename_length));
         }
 
         dest_counts.insert(base_dest, conflict_count);
@@ -359,24 +441,117 @@ pub fn preview_centralise(
         operations,
         skipped,
         total_bytes,
+        conflict_policy: library.conflict_policy,
+        max_filename_length,
     })
 }
 
-/// Execute the centralise operation
+/// Whether two files have the same SHA-256. Unreadable files count as
+/// different.
+fn same_contents(a: &Path, b: &Path) -> bool {
+    match (calculate_hashes(&a.to_path_buf()), calculate_hashes(&b.to_path_buf())) {
+        (Ok(a), Ok(b)) => a.sha256 == b.sha256,
+        _ => false,
+    }
+}
+
+/// How many numbered names to try for one file before giving up
+const MAX_NUMBERING_ATTEMPTS: u32 = 10_000;
+
+/// Next numbered destination for `planned` that is neither on disk nor
+/// claimed by another file of the batch
+fn free_destination(planned: &PlannedOperation, max_filename_length: usize, claimed: &HashSet<PathBuf>) -> Result<PathBuf> {
+    let folder = planned.destination.parent().unwrap_or(Path::new(""));
+    let mut parts = planned.filename_parts.clone();
+    for _ in 0..MAX_NUMBERING_ATTEMPTS {
+        parts.count += 1;
+        let candidate = folder.join(parts.to_filename(max_filename_length));
+        if !candidate.exists() && !claimed.contains(&candidate) {
+            return Ok(candidate);
+        }
+    }
+    bail!("No free filename in {}", folder.display())
+}
+
+/// Execute the centralise operation. Destinations that already exist are
+/// handled by the preview's conflict policy. With `dry_run` no files or
+/// database rows are touched.
 pub fn execute_centralise(
     db: &Database,
     preview: &CentralisePreview,
     operation: CentraliseOperation,
+    dry_run: bool,
+) -> Result<CentraliseResult> {
+    execute_centralise_with_progress(db, preview, operation, dry_run, |_| true)
+}
+
+/// Execute the centralise operation, calling `on_file` before each planned
+/// operation. Returning false from it stops before that file.
+pub fn execute_centralise_with_progress(
+    db: &Database,
+    preview: &CentralisePreview,
+    operation: CentraliseOperation,
+    dry_run: bool,
+    mut on_file: impl FnMut(&PlannedOperation) -> bool,
 ) -> Result<CentraliseResult> {
     let mut result = CentraliseResult {
         succeeded: Vec::new(),
         failed: Vec::new(),
         skipped: preview.skipped.clone(),
+        identical: 0,
+        renamed: 0,
     };
+    let mut claimed: HashSet<PathBuf> = preview.operations.iter().map(|op| op.destination.clone()).collect();
 
     for planned in &preview.operations {
+        if !on_file(planned) {
+            break;
+        }
+
+        let mut destination = planned.destination.clone();
+        // Record of a library photo this move replaces
+        let mut replaced = None;
+        if destination.exists() {
+            match preview.conflict_policy {
+                CentraliseConflictPolicy::Skip => {
+                    result.skipped.push((planned.source.clone(), "Destination exists".to_string()));
+                    continue;
+                }
+                CentraliseConflictPolicy::SkipIfIdentical if same_contents(&planned.source, &destination) => {
+                    result.identical += 1;
+                    result.skipped.push((planned.source.clone(), "Identical file already in library".to_string()));
+                    continue;
+                }
+                CentraliseConflictPolicy::SkipIfIdentical | CentraliseConflictPolicy::AlwaysCopy => {
+                    destination = match free_destination(planned, preview.max_filename_length, &claimed) {
+                        Ok(destination) => destination,
+                        Err(e) => {
+                            result.failed.push((planned.source.clone(), e.to_string()));
+                            continue;
+                        }
+                    };
+                    claimed.insert(destination.clone());
+                    result.renamed += 1;
+                }
+                CentraliseConflictPolicy::Overwrite => {
+                    if operation == CentraliseOperation::Move {
+                        replaced = db.get_photo_metadata(&destination).ok().flatten().map(|existing| existing.id);
+                    }
+                }
+            }
+        }
+
+        if dry_run {
+            result.succeeded.push(FileOperation {
+                source: planned.source.clone(),
+                destination,
+                was_copy: operation == CentraliseOperation::Copy,
+            });
+            continue;
+        }
+
         // Ensure destination directory exists
-        if let Some(parent) = planned.destination.parent() {
+        if let Some(parent) = destination.parent() {
             if let Err(e) = std::fs::create_dir_all(parent) {
                 result.failed.push((
                     planned.source.clone(),
@@ -389,16 +564,16 @@ pub fn execute_centralise(
         // Perform the operation
         let op_result = match operation {
             CentraliseOperation::Copy => {
-                std::fs::copy(&planned.source, &planned.destination)
+                std::fs::copy(&planned.source, &destination)
                     .map(|_| ())
                     .context("Copy failed")
             }
             CentraliseOperation::Move => {
                 // Try rename first (same filesystem)
-                std::fs::rename(&planned.source, &planned.destination)
+                std::fs::rename(&planned.source, &destination)
                     .or_else(|_| {
                         // Fall back to copy + delete for cross-filesystem
-                        std::fs::copy(&planned.source, &planned.destination)?;
+                        std::fs::copy(&planned.source, &destination)?;
                         std::fs::remove_file(&planned.source)
                     })
                     .context("Move failed")
@@ -407,9 +582,16 @@ pub fn execute_centralise(
 
         match op_result {
             Ok(()) => {
-                // Update database path if moved
+                // Update database path if moved. The replaced photo's record
+                // would clash with the moved one's path, and goes only now
+                // that its file is really gone.
                 if operation == CentraliseOperation::Move {
-                    if let Err(e) = db.update_photo_path(&planned.source, &planned.destination) {
+                    if let Some(id) = replaced {
+                        if let Err(e) = db.delete_photos_by_ids(&[id]) {
+                            tracing::warn!(error = %e, "Failed to remove overwritten photo from DB");
+                        }
+                    }
+                    if let Err(e) = db.update_photo_path(&planned.source, &destination) {
                         // Log but don't fail - file was moved successfully
                         eprintln!("Warning: Failed to update database path: {}", e);
                     }
@@ -417,7 +599,7 @@ pub fn execute_centralise(
 
                 result.succeeded.push(FileOperation {
                     source: planned.source.clone(),
-                    destination: planned.destination.clone(),
+                    destination,
                     was_copy: operation == CentraliseOperation::Copy,
                 });
             }
@@ -441,6 +623,45 @@ mod tests {
         assert_eq!(sanitize_filename("Special@#$Characters"), "special-characters");
     }
 
+    #[test]
+    fn test_sanitize_filename_unicode() {
+        assert_eq!(sanitize_filename("Café Crème"), "cafe-creme");
+        assert_eq!(sanitize_filename("Straße in Łódź"), "strasse-in-lodz");
+        assert_eq!(sanitize_filename("北京 旅行"), "北京-旅行");
+        assert_eq!(sanitize_filename("東京タワー"), "東京タワー");
+        assert_eq!(sanitize_filename("رحلة إلى دبي"), "رحلة-إلى-دبي");
+    }
+
+    #[test]
+    fn test_to_filename_truncates_by_characters() {
+        let parts = FilenameParts {
+            date: Some("20241120".to_string()),
+            time: None,
+            event: None,
+            people: None,
+            description: Some("東京タワーの夜景".repeat(10)),
+            original_name: "IMG_1".to_string(),
+            count: 1,
+            extension: "jpg".to_string(),
+        };
+
+        let filename = parts.to_filename(40);
+        assert_eq!(filename.chars().count(), 40);
+        assert!(filename.ends_with(".jpg"));
+
+        // Still fits the 255-byte limit when the character budget would not
+        let filename = parts.to_filename(200);
+        assert!(filename.len() <= 255);
+        assert!(filename.ends_with(".jpg"));
+
+        // The count survives truncation, so numbering finds a free name
+        let mut second = parts.clone();
+        second.count = 2;
+        assert!(parts.to_filename(40).ends_with("_001.jpg"));
+        assert!(second.to_filename(40).ends_with("_002.jpg"));
+        assert_eq!(second.to_filename(40).chars().count(), 40);
+    }
+
     #[test]
     fn test_filename_parts_to_filename() {
         let parts = FilenameParts {
@@ -475,4 +696,124 @@ mod tests {
         assert!(filename.contains(NO_CAT));
         assert!(filename.contains("old-photo"));
     }
+
+    #[test]
+    fn test_destination_folder_structures() {
+        let root = Path::new("/lib");
+        let metadata = PhotoMetadata {
+            taken_at: Some("2024:03:15 09:30:00".to_string()),
+            ..Default::default()
+        };
+        let folder = |structure| get_destination_folder(root, &metadata, structure, None);
+        assert_eq!(folder(FolderStructure::YearMonth), root.join("2024/03"));
+        assert_eq!(folder(FolderStructure::YearMonthDay), root.join("2024-03-15"));
+        assert_eq!(folder(FolderStructure::YearSlashMonthSlashDay), root.join("2024/03/15"));
+        assert_eq!(folder(FolderStructure::Year), root.join("2024"));
+        assert_eq!(
+            get_destination_folder(root, &PhotoMetadata::default(), FolderStructure::Year, None),
+            root.join("unknown")
+        );
+
+        let event = |start: &str, end: &str| Event {
+            id: 1,
+            name: "Emma's Birthday Party".to_string(),
+            start_date: start.to_string(),
+            end_date: end.to_string(),
+            photo_count: 12,
+        };
+        let during = [event("2024-03-15 08:00:00", "2024-03-15 12:00:00")];
+        assert_eq!(
+            get_destination_folder(root, &metadata, FolderStructure::YearMonth, Some(&during)),
+            root.join("2024/03/emma-s-birthday-party")
+        );
+        let before = [event("2024-03-14 08:00:00", "2024-03-14 12:00:00")];
+        assert_eq!(
+            get_destination_folder(root, &metadata, FolderStructure::YearMonth, Some(&before)),
+            root.join("2024/03")
+        );
+    }
+
+    #[test]
+    fn test_dry_run_leaves_files_alone() {
+        let (dir, db) = crate::db::test_db();
+
+        let source = dir.path().join("a.jpg");
+        std::fs::write(&source, b"jpeg").unwrap();
+        let destination = dir.path().join("library").join("unknown").join("a_001.jpg");
+        let preview = CentralisePreview {
+            operations: vec![PlannedOperation {
+                source: source.clone(),
+                destination: destination.clone(),
+                size_bytes: 4,
+                filename_parts: FilenameParts::default(),
+            }],
+            skipped: Vec::new(),
+            total_bytes: 4,
+            conflict_policy: CentraliseConflictPolicy::default(),
+            max_filename_length: 100,
+        };
+
+        let result = execute_centralise(&db, &preview, CentraliseOperation::Move, true).unwrap();
+        assert_eq!(result.succeeded.len(), 1);
+        assert_eq!(result.succeeded[0].destination, destination);
+        assert!(source.exists());
+        assert!(!dir.path().join("library").exists());
+    }
+
+    #[test]
+    fn test_conflict_policies() {
+        let (dir, db) = crate::db::test_db();
+
+        let library = dir.path().join("library");
+        std::fs::create_dir_all(&library).unwrap();
+        let planned = |name: &str, contents: &[u8]| {
+            let source = dir.path().join(format!("{}.jpg", name));
+            std::fs::write(&source, contents).unwrap();
+            let filename_parts = FilenameParts {
+                original_name: name.to_string(),
+                count: 1,
+                extension: "jpg".to_string(),
+                ..Default::default()
+            };
+            let destination = library.join(filename_parts.to_filename(100));
+            std::fs::write(&destination, b"existing").unwrap();
+            PlannedOperation { source, destination, size_bytes: 8, filename_parts }
+        };
+        let preview = |conflict_policy| CentralisePreview {
+            operations: vec![planned("same", b"existing"), planned("other", b"different")],
+            skipped: Vec::new(),
+            total_bytes: 16,
+            conflict_policy,
+            max_filename_length: 100,
+        };
+
+        let result = execute_centralise(&db, &preview(CentraliseConflictPolicy::SkipIfIdentical), CentraliseOperation::Copy, false).unwrap();
+        assert_eq!((result.identical, result.renamed), (1, 1));
+        assert_eq!(result.succeeded.len(), 1);
+        assert!(result.succeeded[0].destination.ends_with("{NO_CAT}_other_002.jpg"));
+        assert_eq!(std::fs::read(&result.succeeded[0].destination).unwrap(), b"different");
+        assert_eq!(result.conflict_summary().unwrap(), "1 identical, 1 conflicts resolved by renaming");
+
+        let result = execute_centralise(&db, &preview(CentraliseConflictPolicy::Skip), CentraliseOperation::Copy, false).unwrap();
+        assert_eq!((result.succeeded.len(), result.skipped.len()), (0, 2));
+
+        let result = execute_centralise(&db, &preview(CentraliseConflictPolicy::AlwaysCopy), CentraliseOperation::Copy, false).unwrap();
+        assert_eq!(result.renamed, 2);
+        assert!(result.succeeded[1].destination.ends_with("{NO_CAT}_other_003.jpg"));
+
+        let result = execute_centralise(&db, &preview(CentraliseConflictPolicy::Overwrite), CentraliseOperation::Copy, false).unwrap();
+        assert_eq!(result.succeeded.len(), 2);
+        assert_eq!(std::fs::read(&result.succeeded[1].destination).unwrap(), b"different");
+        assert!(result.conflict_summary().is_none());
+
+        // A failed overwriting move keeps the replaced photo's record
+        let preview = preview(CentraliseConflictPolicy::Overwrite);
+        let target = &preview.operations[1];
+        let (target_path, target_dir) = (target.destination.to_string_lossy(), library.to_string_lossy());
+        db.insert_basic_photo(&target_path, "other.jpg", &target_dir, 8).unwrap();
+        std::fs::remove_file(&target.source).unwrap();
+        let result = execute_centralise(&db, &preview, CentraliseOperation::Move, false).unwrap();
+        assert_eq!(result.failed.len(), 1);
+        assert!(db.get_photo_metadata(&target.destination).unwrap().is_some());
+    }
 }
@@ -10,6 +10,9 @@ use std::sync::{Mutex, OnceLock};
 /// CLIP embedding (512-dimensional vector for ViT-B/32)
 pub type ClipEmbedding = Vec<f32>;
 
+/// Name stored with every embedding, so a model upgrade can be detected
+const MODEL_VERSION: &str = "clip-vit-base-patch32";
+
 /// CLIP visual encoder model
 static VISUAL_MODEL: OnceLock<Mutex<Session>> = OnceLock::new();
 
@@ -34,6 +37,11 @@ impl ClipModel {
         Ok(())
     }
 
+    /// Model name recorded alongside stored embeddings
+    pub fn model_version() -> &'static str {
+        MODEL_VERSION
+    }
+
     /// Check if models are ready
     pub fn is_ready(&self) -> bool {
         VISUAL_MODEL.get().is_some()
@@ -2,7 +2,7 @@ use anyhow::Result;
 use crossterm::event::{KeyCode, KeyModifiers};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -33,11 +33,35 @@ pub struct Config {
     #[serde(default)]
     pub library: LibraryConfig,
 
+    #[serde(default)]
+    pub quality: QualityConfig,
+
     #[serde(default)]
     pub keybindings: KeyBindings,
 
     #[serde(default)]
     pub view: ViewConfig,
+
+    #[serde(default)]
+    pub ui: UiConfig,
+
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    #[serde(default)]
+    pub gallery: GalleryConfig,
+
+    #[serde(default)]
+    pub slideshow: SlideshowConfig,
+
+    #[serde(default)]
+    pub theme: ThemeConfig,
+
+    /// Path fields as written in the file, before environment variable
+    /// expansion, keyed by field name. Restored on save so `${VAR}`
+    /// references survive a round trip.
+    #[serde(skip)]
+    raw_paths: HashMap<&'static str, (PathBuf, PathBuf)>,
 }
 
 /// View filter settings (persisted across sessions)
@@ -50,6 +74,238 @@ pub struct ViewConfig {
     /// Show all files, not just supported image formats
     #[serde(default)]
     pub show_all_files: bool,
+
+    /// Order of files in the browser
+    #[serde(default)]
+    pub sort_order: SortOrder,
+}
+
+/// Browser file ordering. Directories are always listed first, by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Name,
+    /// EXIF capture time; files without one go last
+    DateTaken,
+    Size,
+    DateModified,
+}
+
+impl SortOrder {
+    /// Next order in the cycle name → date taken → size → date modified.
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::Name => SortOrder::DateTaken,
+            SortOrder::DateTaken => SortOrder::Size,
+            SortOrder::Size => SortOrder::DateModified,
+            SortOrder::DateModified => SortOrder::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Name => "name",
+            SortOrder::DateTaken => "date taken",
+            SortOrder::Size => "size",
+            SortOrder::DateModified => "date modified",
+        }
+    }
+}
+
+/// User interface behaviour settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Restore the last directory, selection and view on startup
+    #[serde(default = "default_restore_session")]
+    pub restore_session: bool,
+
+    /// Width percentages of the parent, current and preview panes (must sum to 100)
+    #[serde(default = "default_pane_ratios")]
+    pub pane_ratios: [u16; 3],
+
+    /// Hide the preview pane rather than show it narrower than this many columns
+    #[serde(default = "default_min_preview_width")]
+    pub min_preview_width: u16,
+}
+
+fn default_restore_session() -> bool {
+    true
+}
+
+fn default_pane_ratios() -> [u16; 3] {
+    [20, 40, 40]
+}
+
+fn default_min_preview_width() -> u16 {
+    30
+}
+
+/// Smallest width a browser pane can be resized to, in percent.
+pub const MIN_PANE_PERCENT: u16 = 10;
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            restore_session: default_restore_session(),
+            pane_ratios: default_pane_ratios(),
+            min_preview_width: default_min_preview_width(),
+        }
+    }
+}
+
+impl UiConfig {
+    /// Grow (positive delta) or shrink (negative delta) the preview pane,
+    /// giving or taking the difference from the current directory pane.
+    /// Returns false if either pane would drop below `MIN_PANE_PERCENT`.
+    pub fn resize_preview(&mut self, delta: i16) -> bool {
+        let [parent, current, preview] = self.pane_ratios;
+        let new_preview = preview as i16 + delta;
+        let new_current = current as i16 - delta;
+        let min = MIN_PANE_PERCENT as i16;
+        if new_preview < min || new_current < min {
+            return false;
+        }
+        self.pane_ratios = [parent, new_current as u16, new_preview as u16];
+        true
+    }
+}
+
+/// Desktop notification settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Show a desktop notification when a background task succeeds
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Gallery view settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalleryConfig {
+    /// Show a rated photo's stars along the bottom of its thumbnail
+    #[serde(default = "default_show_overlay")]
+    pub show_rating_overlay: bool,
+
+    /// Show a photo's color label as a block in the thumbnail's top-right corner
+    #[serde(default = "default_show_overlay")]
+    pub show_label_overlay: bool,
+}
+
+fn default_show_overlay() -> bool {
+    true
+}
+
+impl Default for GalleryConfig {
+    fn default() -> Self {
+        Self {
+            show_rating_overlay: default_show_overlay(),
+            show_label_overlay: default_show_overlay(),
+        }
+    }
+}
+
+/// Slideshow settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlideshowConfig {
+    /// Seconds between images when playing, in directories where no speed
+    /// has been chosen yet
+    #[serde(default = "default_slideshow_interval_secs")]
+    pub default_interval_secs: f64,
+}
+
+fn default_slideshow_interval_secs() -> f64 {
+    3.0
+}
+
+impl Default for SlideshowConfig {
+    fn default() -> Self {
+        Self {
+            default_interval_secs: default_slideshow_interval_secs(),
+        }
+    }
+}
+
+/// Color theme settings.
+///
+/// `name` selects a built-in theme (dark, light, solarized); any color set
+/// here overrides the corresponding theme color. Colors are ratatui color
+/// names ("yellow", "darkgray"), indexed colors ("208") or hex ("#ff8800").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    #[serde(default = "default_theme_name")]
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emphasis: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_active: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marked_bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_bar_fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_bar_bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_color: Option<String>,
+
+    /// Palette cycled through when coloring tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_colors: Vec<String>,
+}
+
+fn default_theme_name() -> String {
+    "dark".to_string()
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: default_theme_name(),
+            fg: None,
+            bg: None,
+            muted: None,
+            secondary: None,
+            accent: None,
+            highlight: None,
+            emphasis: None,
+            success: None,
+            error: None,
+            border_active: None,
+            selected_fg: None,
+            selected_bg: None,
+            marked_bg: None,
+            status_bar_fg: None,
+            status_bar_bg: None,
+            header_fg: None,
+            directory_color: None,
+            image_color: None,
+            tag_colors: Vec::new(),
+        }
+    }
 }
 
 /// Database backend type
@@ -122,11 +378,16 @@ pub enum Action {
 
     // Actions
     Scan,
+    ScanAll,
+    RescanMissingMetadata,
     FindDuplicates,
+    FindCrossDirDuplicates,
+    AutoCull,
     DescribeWithLlm,
     BatchLlm,
     DetectFaces,
     ClusterFaces,
+    RegenerateFaceEmbeddings,
     ClipEmbedding,
     ViewTasks,
     ViewTrash,
@@ -134,12 +395,19 @@ pub enum Action {
     RenameFiles,
     ExportDatabase,
     SemanticSearch,
+    QuickFilter,
+    SearchByImage,
     ManagePeople,
+    ManagePrompts,
     EditDescription,
+    EditGps,
     ViewChanges,
     OpenSchedule,
     OpenGallery,
     OpenTags,
+    BatchEditTags,
+    ToggleWatch,
+    ReloadConfig,
     OpenSlideshow,
     CentraliseFiles,
     RotateCW,
@@ -147,12 +415,33 @@ pub enum Action {
     YankFiles,
     PasteFiles,
     DeleteFiles,
+    TrashRedLabeled,
+    Undo,
+    Redo,
     ShowHelp,
     Quit,
     // View filters
     ToggleHidden,
     ToggleShowAllFiles,
     OpenExternal,
+    PickTheme,
+    FocusBreadcrumb,
+    CycleSort,
+    FilterByDate,
+    FilterByCamera,
+    ToggleHistogram,
+    CyclePreviewMode,
+    ExpandMetadata,
+    OpenTagBrowser,
+    OpenCalendar,
+    OpenAlbums,
+    OpenLocations,
+    OpenAdbImport,
+    ShareSelected,
+    WriteExifToFiles,
+    // Layout
+    ShrinkPreview,
+    GrowPreview,
 }
 
 /// A keybinding specification in config
@@ -266,8 +555,16 @@ pub struct KeyBindings {
     // Actions
     #[serde(default = "default_scan")]
     pub scan: Vec<KeySpec>,
+    #[serde(default = "default_scan_all")]
+    pub scan_all: Vec<KeySpec>,
+    #[serde(default = "default_rescan_missing_metadata")]
+    pub rescan_missing_metadata: Vec<KeySpec>,
     #[serde(default = "default_find_duplicates")]
     pub find_duplicates: Vec<KeySpec>,
+    #[serde(default = "default_find_cross_dir_duplicates")]
+    pub find_cross_dir_duplicates: Vec<KeySpec>,
+    #[serde(default = "default_auto_cull")]
+    pub auto_cull: Vec<KeySpec>,
     #[serde(default = "default_describe_with_llm")]
     pub describe_with_llm: Vec<KeySpec>,
     #[serde(default = "default_batch_llm")]
@@ -276,6 +573,8 @@ pub struct KeyBindings {
     pub detect_faces: Vec<KeySpec>,
     #[serde(default = "default_cluster_faces")]
     pub cluster_faces: Vec<KeySpec>,
+    #[serde(default = "default_regenerate_face_embeddings")]
+    pub regenerate_face_embeddings: Vec<KeySpec>,
     #[serde(default = "default_clip_embedding")]
     pub clip_embedding: Vec<KeySpec>,
     #[serde(default = "default_view_tasks")]
@@ -290,10 +589,18 @@ pub struct KeyBindings {
     pub export_database: Vec<KeySpec>,
     #[serde(default = "default_semantic_search")]
     pub semantic_search: Vec<KeySpec>,
+    #[serde(default = "default_quick_filter")]
+    pub quick_filter: Vec<KeySpec>,
+    #[serde(default = "default_search_by_image")]
+    pub search_by_image: Vec<KeySpec>,
     #[serde(default = "default_manage_people")]
     pub manage_people: Vec<KeySpec>,
+    #[serde(default = "default_manage_prompts")]
+    pub manage_prompts: Vec<KeySpec>,
     #[serde(default = "default_edit_description")]
     pub edit_description: Vec<KeySpec>,
+    #[serde(default = "default_edit_gps")]
+    pub edit_gps: Vec<KeySpec>,
     #[serde(default = "default_view_changes")]
     pub view_changes: Vec<KeySpec>,
     #[serde(default = "default_open_schedule")]
@@ -302,6 +609,12 @@ pub struct KeyBindings {
     pub open_gallery: Vec<KeySpec>,
     #[serde(default = "default_open_tags")]
     pub open_tags: Vec<KeySpec>,
+    #[serde(default = "default_batch_edit_tags")]
+    pub batch_edit_tags: Vec<KeySpec>,
+    #[serde(default = "default_toggle_watch")]
+    pub toggle_watch: Vec<KeySpec>,
+    #[serde(default = "default_reload_config")]
+    pub reload_config: Vec<KeySpec>,
     #[serde(default = "default_open_slideshow")]
     pub open_slideshow: Vec<KeySpec>,
     #[serde(default = "default_centralise_files")]
@@ -316,6 +629,12 @@ pub struct KeyBindings {
     pub paste_files: Vec<KeySpec>,
     #[serde(default = "default_delete_files")]
     pub delete_files: Vec<KeySpec>,
+    #[serde(default = "default_trash_red_labeled")]
+    pub trash_red_labeled: Vec<KeySpec>,
+    #[serde(default = "default_undo")]
+    pub undo: Vec<KeySpec>,
+    #[serde(default = "default_redo")]
+    pub redo: Vec<KeySpec>,
     #[serde(default = "default_show_help")]
     pub show_help: Vec<KeySpec>,
     #[serde(default = "default_quit")]
@@ -326,6 +645,40 @@ pub struct KeyBindings {
     pub toggle_show_all_files: Vec<KeySpec>,
     #[serde(default = "default_open_external")]
     pub open_external: Vec<KeySpec>,
+    #[serde(default = "default_pick_theme")]
+    pub pick_theme: Vec<KeySpec>,
+    #[serde(default = "default_focus_breadcrumb")]
+    pub focus_breadcrumb: Vec<KeySpec>,
+    #[serde(default = "default_cycle_sort")]
+    pub cycle_sort: Vec<KeySpec>,
+    #[serde(default = "default_filter_by_date")]
+    pub filter_by_date: Vec<KeySpec>,
+    #[serde(default = "default_filter_by_camera")]
+    pub filter_by_camera: Vec<KeySpec>,
+    #[serde(default = "default_toggle_histogram")]
+    pub toggle_histogram: Vec<KeySpec>,
+    #[serde(default = "default_cycle_preview_mode")]
+    pub cycle_preview_mode: Vec<KeySpec>,
+    #[serde(default = "default_expand_metadata")]
+    pub expand_metadata: Vec<KeySpec>,
+    #[serde(default = "default_open_tag_browser")]
+    pub open_tag_browser: Vec<KeySpec>,
+    #[serde(default = "default_open_calendar")]
+    pub open_calendar: Vec<KeySpec>,
+    #[serde(default = "default_open_albums")]
+    pub open_albums: Vec<KeySpec>,
+    #[serde(default = "default_open_locations")]
+    pub open_locations: Vec<KeySpec>,
+    #[serde(default = "default_open_adb_import")]
+    pub open_adb_import: Vec<KeySpec>,
+    #[serde(default = "default_share_selected")]
+    pub share_selected: Vec<KeySpec>,
+    #[serde(default = "default_write_exif_to_files")]
+    pub write_exif_to_files: Vec<KeySpec>,
+    #[serde(default = "default_shrink_preview")]
+    pub shrink_preview: Vec<KeySpec>,
+    #[serde(default = "default_grow_preview")]
+    pub grow_preview: Vec<KeySpec>,
 }
 
 // Default keybinding functions
@@ -343,13 +696,18 @@ fn default_toggle_selection() -> Vec<KeySpec> { vec![KeySpec::Simple("Space".int
 // Yazi-aligned: v = visual mode (V also works)
 fn default_enter_visual_mode() -> Vec<KeySpec> { vec![KeySpec::Simple("v".into()), KeySpec::Simple("V".into())] }
 fn default_scan() -> Vec<KeySpec> { vec![KeySpec::Simple("s".into())] }
+fn default_scan_all() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+s".into())] }
+fn default_rescan_missing_metadata() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+r".into())] }
 // Clepho-specific: u = duplicates (d is trash in yazi)
 fn default_find_duplicates() -> Vec<KeySpec> { vec![KeySpec::Simple("u".into())] }
+fn default_find_cross_dir_duplicates() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+u".into())] }
+fn default_auto_cull() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+q".into())] }
 // Clepho-specific: i = describe with LLM (info)
 fn default_describe_with_llm() -> Vec<KeySpec> { vec![KeySpec::Simple("i".into())] }
 fn default_batch_llm() -> Vec<KeySpec> { vec![KeySpec::Simple("I".into())] }
 fn default_detect_faces() -> Vec<KeySpec> { vec![KeySpec::Simple("F".into())] }
 fn default_cluster_faces() -> Vec<KeySpec> { vec![KeySpec::Simple("C".into())] }
+fn default_regenerate_face_embeddings() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+c".into())] }
 fn default_clip_embedding() -> Vec<KeySpec> { vec![KeySpec::Simple("E".into())] }
 fn default_view_tasks() -> Vec<KeySpec> { vec![KeySpec::Simple("T".into())] }
 // Clepho-specific: X = view trash (t is tabs in yazi, we don't have tabs)
@@ -359,13 +717,24 @@ fn default_move_files() -> Vec<KeySpec> { vec![KeySpec::Simple("m".into())] }
 fn default_rename_files() -> Vec<KeySpec> { vec![KeySpec::Simple("r".into())] }
 fn default_export_database() -> Vec<KeySpec> { vec![KeySpec::Simple("O".into())] }
 fn default_semantic_search() -> Vec<KeySpec> { vec![KeySpec::Simple("/".into())] }
+// Yazi-aligned: f = filter the current directory
+fn default_quick_filter() -> Vec<KeySpec> { vec![KeySpec::Simple("f".into())] }
+// I is batch LLM processing, so searching by image lives on Alt+i
+fn default_search_by_image() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+i".into())] }
 // Clepho-specific: P = manage people (p is paste in yazi)
 fn default_manage_people() -> Vec<KeySpec> { vec![KeySpec::Simple("P".into())] }
+fn default_manage_prompts() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+p".into())] }
 fn default_edit_description() -> Vec<KeySpec> { vec![KeySpec::Simple("e".into())] }
+// G is go-to-bottom, so manual GPS entry lives on Ctrl+g
+fn default_edit_gps() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+g".into())] }
 fn default_view_changes() -> Vec<KeySpec> { vec![KeySpec::Simple("c".into())] }
 fn default_open_schedule() -> Vec<KeySpec> { vec![KeySpec::Simple("@".into())] }
 fn default_open_gallery() -> Vec<KeySpec> { vec![KeySpec::Simple("A".into())] }
 fn default_open_tags() -> Vec<KeySpec> { vec![KeySpec::Simple("b".into())] }
+// T is the task list, so batch tag editing lives on Alt+b next to b for tags
+fn default_batch_edit_tags() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+b".into())] }
+fn default_toggle_watch() -> Vec<KeySpec> { vec![KeySpec::Simple("W".into())] }
+fn default_reload_config() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+r".into())] }
 // Clepho-specific: S = slideshow (v is now visual mode)
 fn default_open_slideshow() -> Vec<KeySpec> { vec![KeySpec::Simple("S".into())] }
 fn default_centralise_files() -> Vec<KeySpec> { vec![KeySpec::Simple("L".into())] }
@@ -377,6 +746,11 @@ fn default_yank_files() -> Vec<KeySpec> { vec![KeySpec::Simple("y".into()), KeyS
 fn default_paste_files() -> Vec<KeySpec> { vec![KeySpec::Simple("p".into())] }
 // Yazi-aligned: d = trash, D = permanent delete
 fn default_delete_files() -> Vec<KeySpec> { vec![KeySpec::Simple("d".into()), KeySpec::Simple("Delete".into())] }
+// x is cut, so trashing the red-labeled culls lives on Alt+x
+fn default_trash_red_labeled() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+x".into())] }
+fn default_undo() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+z".into())] }
+// Ctrl+Shift+z arrives as Ctrl+z in most terminals
+fn default_redo() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+y".into())] }
 fn default_show_help() -> Vec<KeySpec> { vec![KeySpec::Simple("?".into())] }
 fn default_quit() -> Vec<KeySpec> { vec![KeySpec::Simple("q".into())] }
 // Yazi-aligned: . = toggle hidden files
@@ -384,6 +758,32 @@ fn default_toggle_hidden() -> Vec<KeySpec> { vec![KeySpec::Simple(".".into())] }
 // Clepho-specific: H = show all files (not just images)
 fn default_toggle_show_all_files() -> Vec<KeySpec> { vec![KeySpec::Simple("H".into())] }
 fn default_open_external() -> Vec<KeySpec> { vec![KeySpec::Simple("o".into())] }
+// T is taken by the task list, so the theme picker lives on Ctrl+t
+fn default_pick_theme() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+t".into())] }
+fn default_focus_breadcrumb() -> Vec<KeySpec> { vec![KeySpec::Simple("B".into())] }
+// o opens files externally, so sorting uses Yazi's , prefix key
+fn default_cycle_sort() -> Vec<KeySpec> { vec![KeySpec::Simple(",".into())] }
+fn default_filter_by_date() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+d".into())] }
+fn default_filter_by_camera() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+k".into())] }
+// h is go_parent and H toggles all files
+fn default_toggle_histogram() -> Vec<KeySpec> { vec![KeySpec::Simple("#".into())] }
+fn default_cycle_preview_mode() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Ctrl+p".into())] }
+fn default_expand_metadata() -> Vec<KeySpec> { vec![KeySpec::Simple("M".into())] }
+// Ctrl+Shift+t arrives as Ctrl+t (the theme picker) in most terminals
+fn default_open_tag_browser() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+t".into())] }
+fn default_open_calendar() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+d".into())] }
+// A is the gallery, so albums live on Alt+a
+fn default_open_albums() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+a".into())] }
+// L is centralising files
+fn default_open_locations() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+l".into())] }
+// m is the move dialog, so importing from a mobile device lives on Alt+m
+fn default_open_adb_import() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+m".into())] }
+// S is the slideshow, so sharing lives on Alt+s
+fn default_share_selected() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+s".into())] }
+// E is CLIP embeddings, so writing metadata into files lives on Alt+e
+fn default_write_exif_to_files() -> Vec<KeySpec> { vec![KeySpec::WithModifiers("Alt+e".into())] }
+fn default_shrink_preview() -> Vec<KeySpec> { vec![KeySpec::Simple("<".into())] }
+fn default_grow_preview() -> Vec<KeySpec> { vec![KeySpec::Simple(">".into())] }
 
 impl Default for KeyBindings {
     fn default() -> Self {
@@ -401,11 +801,16 @@ impl Default for KeyBindings {
             toggle_selection: default_toggle_selection(),
             enter_visual_mode: default_enter_visual_mode(),
             scan: default_scan(),
+            scan_all: default_scan_all(),
+            rescan_missing_metadata: default_rescan_missing_metadata(),
             find_duplicates: default_find_duplicates(),
+            find_cross_dir_duplicates: default_find_cross_dir_duplicates(),
+            auto_cull: default_auto_cull(),
             describe_with_llm: default_describe_with_llm(),
             batch_llm: default_batch_llm(),
             detect_faces: default_detect_faces(),
             cluster_faces: default_cluster_faces(),
+            regenerate_face_embeddings: default_regenerate_face_embeddings(),
             clip_embedding: default_clip_embedding(),
             view_tasks: default_view_tasks(),
             view_trash: default_view_trash(),
@@ -413,12 +818,19 @@ impl Default for KeyBindings {
             rename_files: default_rename_files(),
             export_database: default_export_database(),
             semantic_search: default_semantic_search(),
+            quick_filter: default_quick_filter(),
+            search_by_image: default_search_by_image(),
             manage_people: default_manage_people(),
+            manage_prompts: default_manage_prompts(),
             edit_description: default_edit_description(),
+            edit_gps: default_edit_gps(),
             view_changes: default_view_changes(),
             open_schedule: default_open_schedule(),
             open_gallery: default_open_gallery(),
             open_tags: default_open_tags(),
+            batch_edit_tags: default_batch_edit_tags(),
+            toggle_watch: default_toggle_watch(),
+            reload_config: default_reload_config(),
             open_slideshow: default_open_slideshow(),
             centralise_files: default_centralise_files(),
             rotate_cw: default_rotate_cw(),
@@ -426,11 +838,31 @@ impl Default for KeyBindings {
             yank_files: default_yank_files(),
             paste_files: default_paste_files(),
             delete_files: default_delete_files(),
+            trash_red_labeled: default_trash_red_labeled(),
+            undo: default_undo(),
+            redo: default_redo(),
             show_help: default_show_help(),
             quit: default_quit(),
             toggle_hidden: default_toggle_hidden(),
             toggle_show_all_files: default_toggle_show_all_files(),
             open_external: default_open_external(),
+            pick_theme: default_pick_theme(),
+            focus_breadcrumb: default_focus_breadcrumb(),
+            cycle_sort: default_cycle_sort(),
+            filter_by_date: default_filter_by_date(),
+            filter_by_camera: default_filter_by_camera(),
+            toggle_histogram: default_toggle_histogram(),
+            cycle_preview_mode: default_cycle_preview_mode(),
+            expand_metadata: default_expand_metadata(),
+            open_tag_browser: default_open_tag_browser(),
+            open_calendar: default_open_calendar(),
+            open_albums: default_open_albums(),
+            open_locations: default_open_locations(),
+            open_adb_import: default_open_adb_import(),
+            share_selected: default_share_selected(),
+            write_exif_to_files: default_write_exif_to_files(),
+            shrink_preview: default_shrink_preview(),
+            grow_preview: default_grow_preview(),
         }
     }
 }
@@ -454,11 +886,16 @@ impl KeyBindings {
             (&self.toggle_selection, Action::ToggleSelection),
             (&self.enter_visual_mode, Action::EnterVisualMode),
             (&self.scan, Action::Scan),
+            (&self.scan_all, Action::ScanAll),
+            (&self.rescan_missing_metadata, Action::RescanMissingMetadata),
             (&self.find_duplicates, Action::FindDuplicates),
+            (&self.find_cross_dir_duplicates, Action::FindCrossDirDuplicates),
+            (&self.auto_cull, Action::AutoCull),
             (&self.describe_with_llm, Action::DescribeWithLlm),
             (&self.batch_llm, Action::BatchLlm),
             (&self.detect_faces, Action::DetectFaces),
             (&self.cluster_faces, Action::ClusterFaces),
+            (&self.regenerate_face_embeddings, Action::RegenerateFaceEmbeddings),
             (&self.clip_embedding, Action::ClipEmbedding),
             (&self.view_tasks, Action::ViewTasks),
             (&self.view_trash, Action::ViewTrash),
@@ -466,12 +903,19 @@ impl KeyBindings {
             (&self.rename_files, Action::RenameFiles),
             (&self.export_database, Action::ExportDatabase),
             (&self.semantic_search, Action::SemanticSearch),
+            (&self.quick_filter, Action::QuickFilter),
+            (&self.search_by_image, Action::SearchByImage),
             (&self.manage_people, Action::ManagePeople),
+            (&self.manage_prompts, Action::ManagePrompts),
             (&self.edit_description, Action::EditDescription),
+            (&self.edit_gps, Action::EditGps),
             (&self.view_changes, Action::ViewChanges),
             (&self.open_schedule, Action::OpenSchedule),
             (&self.open_gallery, Action::OpenGallery),
             (&self.open_tags, Action::OpenTags),
+            (&self.batch_edit_tags, Action::BatchEditTags),
+            (&self.toggle_watch, Action::ToggleWatch),
+            (&self.reload_config, Action::ReloadConfig),
             (&self.open_slideshow, Action::OpenSlideshow),
             (&self.centralise_files, Action::CentraliseFiles),
             (&self.rotate_cw, Action::RotateCW),
@@ -479,11 +923,31 @@ impl KeyBindings {
             (&self.yank_files, Action::YankFiles),
             (&self.paste_files, Action::PasteFiles),
             (&self.delete_files, Action::DeleteFiles),
+            (&self.trash_red_labeled, Action::TrashRedLabeled),
+            (&self.undo, Action::Undo),
+            (&self.redo, Action::Redo),
             (&self.show_help, Action::ShowHelp),
             (&self.quit, Action::Quit),
             (&self.toggle_hidden, Action::ToggleHidden),
             (&self.toggle_show_all_files, Action::ToggleShowAllFiles),
             (&self.open_external, Action::OpenExternal),
+            (&self.pick_theme, Action::PickTheme),
+            (&self.focus_breadcrumb, Action::FocusBreadcrumb),
+            (&self.cycle_sort, Action::CycleSort),
+            (&self.filter_by_date, Action::FilterByDate),
+            (&self.filter_by_camera, Action::FilterByCamera),
+            (&self.toggle_histogram, Action::ToggleHistogram),
+            (&self.cycle_preview_mode, Action::CyclePreviewMode),
+            (&self.expand_metadata, Action::ExpandMetadata),
+            (&self.open_tag_browser, Action::OpenTagBrowser),
+            (&self.open_calendar, Action::OpenCalendar),
+            (&self.open_albums, Action::OpenAlbums),
+            (&self.open_locations, Action::OpenLocations),
+            (&self.open_adb_import, Action::OpenAdbImport),
+            (&self.share_selected, Action::ShareSelected),
+            (&self.write_exif_to_files, Action::WriteExifToFiles),
+            (&self.shrink_preview, Action::ShrinkPreview),
+            (&self.grow_preview, Action::GrowPreview),
         ];
 
         for (specs, action) in bindings {
@@ -508,6 +972,11 @@ pub struct TrashConfig {
 
     #[serde(default = "default_max_size_bytes")]
     pub max_size_bytes: u64,
+
+    /// Hours between automatic cleanups enforcing the age and size limits
+    /// (0 disables periodic cleanup)
+    #[serde(default = "default_cleanup_interval_hours")]
+    pub cleanup_interval_hours: u32,
 }
 
 fn default_trash_path() -> PathBuf {
@@ -524,12 +993,17 @@ fn default_max_size_bytes() -> u64 {
     1024 * 1024 * 1024 // 1GB
 }
 
+fn default_cleanup_interval_hours() -> u32 {
+    24
+}
+
 impl Default for TrashConfig {
     fn default() -> Self {
         Self {
             path: default_trash_path(),
             max_age_days: default_max_age_days(),
             max_size_bytes: default_max_size_bytes(),
+            cleanup_interval_hours: default_cleanup_interval_hours(),
         }
     }
 }
@@ -578,9 +1052,7 @@ pub struct ThumbnailConfig {
 }
 
 fn default_thumb_cache_path() -> PathBuf {
-    dirs::cache_dir()
-        .unwrap_or_else(|| PathBuf::from(".cache"))
-        .join("clepho/thumbnails")
+    Config::cache_dir().join("thumbs")
 }
 
 fn default_thumb_cache_size() -> u32 {
@@ -647,13 +1119,57 @@ pub struct LibraryConfig {
     #[serde(default)]
     pub operation: CentraliseOperation,
 
-    /// Maximum filename length (excluding extension)
+    /// Maximum filename length in characters, including the extension
     #[serde(default = "default_max_filename_length")]
     pub max_filename_length: usize,
+
+    /// Date folders photos are sorted into
+    #[serde(default)]
+    pub folder_structure: FolderStructure,
+
+    /// Put photos of a detected event in a folder named after it, below
+    /// the date folders
+    #[serde(default)]
+    pub use_event_as_subfolder: bool,
+
+    /// What to do when a centralised file's destination already exists
+    #[serde(default)]
+    pub conflict_policy: CentraliseConflictPolicy,
+}
+
+/// Handling of a centralise destination that is already taken
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CentraliseConflictPolicy {
+    /// Leave the file where it is
+    Skip,
+    /// Leave the file if the existing one has the same SHA-256, otherwise
+    /// take the next free numbered name
+    #[default]
+    SkipIfIdentical,
+    /// Always take the next free numbered name
+    AlwaysCopy,
+    /// Replace the existing file
+    Overwrite,
+}
+
+/// Date folder layout of the library
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderStructure {
+    /// `2024/03`
+    #[default]
+    YearMonth,
+    /// `2024-03-15`
+    YearMonthDay,
+    /// `2024/03/15`
+    YearSlashMonthSlashDay,
+    /// `2024`
+    Year,
 }
 
 fn default_max_filename_length() -> usize {
-    100
+    200
 }
 
 impl Default for LibraryConfig {
@@ -662,6 +1178,38 @@ impl Default for LibraryConfig {
             path: None,
             operation: CentraliseOperation::default(),
             max_filename_length: default_max_filename_length(),
+            folder_structure: FolderStructure::default(),
+            use_event_as_subfolder: false,
+            conflict_policy: CentraliseConflictPolicy::default(),
+        }
+    }
+}
+
+/// Thresholds for auto-culling poor quality photos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityConfig {
+    /// Minimum variance of the Laplacian; lower scores count as blurry
+    #[serde(default = "default_min_sharpness")]
+    pub min_sharpness: f64,
+
+    /// Acceptable mean luminance, from 0.0 (black) to 1.0 (white)
+    #[serde(default = "default_exposure_range")]
+    pub exposure_range: [f64; 2],
+}
+
+fn default_min_sharpness() -> f64 {
+    100.0
+}
+
+fn default_exposure_range() -> [f64; 2] {
+    [0.05, 0.95]
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            min_sharpness: default_min_sharpness(),
+            exposure_range: default_exposure_range(),
         }
     }
 }
@@ -702,7 +1250,8 @@ pub struct LlmConfig {
     #[serde(default)]
     pub base_prompt: Option<String>,
 
-    /// Number of concurrent LLM requests for batch processing (default: 4)
+    /// Number of concurrent LLM requests for batch processing (default: 4,
+    /// capped at [`MAX_LLM_CONCURRENCY`])
     #[serde(default = "default_batch_concurrency")]
     pub batch_concurrency: usize,
 
@@ -710,12 +1259,77 @@ pub struct LlmConfig {
     /// Disable for providers that don't support JSON mode (e.g. some LM Studio models).
     #[serde(default = "default_json_mode")]
     pub json_mode: bool,
+
+    /// Several providers to spread requests over. When set, the single
+    /// provider fields above are ignored; prompts, concurrency and JSON mode
+    /// still apply to every provider.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<LlmProviderConfig>,
+
+    /// How requests are spread over `providers`
+    #[serde(default)]
+    pub strategy: LlmStrategy,
+}
+
+/// One entry of `[[llm.providers]]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmProviderConfig {
+    #[serde(default)]
+    pub provider: LlmProviderType,
+
+    #[serde(default = "default_llm_endpoint")]
+    pub endpoint: String,
+
+    #[serde(default = "default_llm_model")]
+    pub model: String,
+
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+/// Order in which multiple LLM providers are tried. Whichever is picked
+/// first, a failing request falls through to the others.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmStrategy {
+    /// Always start with the first provider in the list
+    #[default]
+    Fallback,
+    /// Rotate the starting provider with each request
+    RoundRobin,
+    /// Start with whichever provider answered a ping first
+    FastestFirst,
 }
 
+/// Upper bound on concurrent LLM requests; beyond this local servers
+/// only queue requests and cloud providers start rate limiting.
+pub const MAX_LLM_CONCURRENCY: usize = 8;
+
 fn default_batch_concurrency() -> usize {
     4
 }
 
+impl LlmConfig {
+    /// Batch concurrency clamped to `1..=MAX_LLM_CONCURRENCY`
+    pub fn concurrency(&self) -> usize {
+        self.batch_concurrency.clamp(1, MAX_LLM_CONCURRENCY)
+    }
+
+    /// Configured providers; the top-level fields are shorthand for a
+    /// single-provider list.
+    pub fn provider_list(&self) -> Vec<LlmProviderConfig> {
+        if !self.providers.is_empty() {
+            return self.providers.clone();
+        }
+        vec![LlmProviderConfig {
+            provider: self.provider,
+            endpoint: self.endpoint.clone(),
+            model: self.model.clone(),
+            api_key: self.api_key.clone(),
+        }]
+    }
+}
+
 fn default_json_mode() -> bool {
     true
 }
@@ -727,6 +1341,52 @@ pub struct ScannerConfig {
 
     #[serde(default = "default_similarity_threshold")]
     pub similarity_threshold: u32,
+
+    /// Minimum embedding cosine similarity for near-duplicate shots
+    #[serde(default = "default_embedding_similarity_threshold")]
+    pub embedding_similarity_threshold: f32,
+
+    /// Also index PDFs, rendering their first page with Ghostscript
+    #[serde(default)]
+    pub scan_pdfs: bool,
+
+    /// Number of scan worker threads (all CPU cores when unset)
+    #[serde(default)]
+    pub max_threads: Option<usize>,
+
+    /// Scheduling priority of the scan worker threads
+    #[serde(default)]
+    pub io_priority: IoPriority,
+
+    /// Directories scanned one after another by `--scan-all` and Ctrl+s
+    #[serde(default)]
+    pub auto_scan_dirs: Vec<PathBuf>,
+}
+
+/// Scheduling priority for scan workers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IoPriority {
+    #[default]
+    Normal,
+    /// Lower the workers' niceness so the TUI and other disk users come first
+    Low,
+}
+
+impl ScannerConfig {
+    /// Lowercase extensions to scan and list: the image extensions, plus
+    /// `pdf` when PDF scanning is enabled.
+    pub fn extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = self
+            .image_extensions
+            .iter()
+            .map(|e| e.to_lowercase())
+            .collect();
+        if self.scan_pdfs && !extensions.iter().any(|e| e == "pdf") {
+            extensions.push("pdf".to_string());
+        }
+        extensions
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -756,6 +1416,91 @@ pub struct PreviewConfig {
     /// If not set, uses system default (xdg-open on Linux, open on macOS)
     #[serde(default)]
     pub external_viewer: Option<String>,
+
+    /// What the preview pane shows for images
+    #[serde(default)]
+    pub mode: PreviewMode,
+
+    /// Protocol to use instead of `protocol` under a directory, keyed by
+    /// directory prefix (e.g. a NAS mounted over SSH that has no Kitty
+    /// support)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub protocol_overrides: HashMap<String, ImageProtocol>,
+}
+
+impl PreviewConfig {
+    /// Protocol for images in `dir`: the override with the longest prefix
+    /// of `dir`, otherwise `protocol`.
+    pub fn protocol_for(&self, dir: &Path) -> ImageProtocol {
+        self.protocol_overrides
+            .iter()
+            .map(|(prefix, protocol)| (PathBuf::from(Config::expand_env_vars(prefix)), *protocol))
+            .filter(|(prefix, _)| dir.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.components().count())
+            .map_or(self.protocol, |(_, protocol)| protocol)
+    }
+
+    /// Whether any directory can show images, so the terminal is worth
+    /// querying for graphics support
+    pub fn any_images(&self) -> bool {
+        self.protocol != ImageProtocol::None
+            || self.protocol_overrides.values().any(|p| *p != ImageProtocol::None)
+    }
+}
+
+/// Content of the preview pane for image files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewMode {
+    #[default]
+    ImageAndMetadata,
+    /// EXIF and description only, no terminal graphics
+    MetadataOnly,
+    ImageOnly,
+    /// File contents as text or hex, for images too
+    TextPreview,
+}
+
+impl PreviewMode {
+    /// Next mode in the cycle, skipping modes that draw images when
+    /// `images_available` is false.
+    pub fn next(self, images_available: bool) -> Self {
+        let mut mode = self;
+        loop {
+            mode = match mode {
+                PreviewMode::ImageAndMetadata => PreviewMode::MetadataOnly,
+                PreviewMode::MetadataOnly => PreviewMode::ImageOnly,
+                PreviewMode::ImageOnly => PreviewMode::TextPreview,
+                PreviewMode::TextPreview => PreviewMode::ImageAndMetadata,
+            };
+            if images_available || !mode.shows_image() {
+                return mode;
+            }
+        }
+    }
+
+    /// The mode to use: image modes fall back to metadata only when images
+    /// cannot be shown (protocol `none` or no graphics support).
+    pub fn resolve(self, images_available: bool) -> Self {
+        if self.shows_image() && !images_available {
+            PreviewMode::MetadataOnly
+        } else {
+            self
+        }
+    }
+
+    pub fn shows_image(self) -> bool {
+        matches!(self, PreviewMode::ImageAndMetadata | PreviewMode::ImageOnly)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PreviewMode::ImageAndMetadata => "image and metadata",
+            PreviewMode::MetadataOnly => "metadata only",
+            PreviewMode::ImageOnly => "image only",
+            PreviewMode::TextPreview => "file contents",
+        }
+    }
 }
 
 fn default_preview_enabled() -> bool {
@@ -773,15 +1518,14 @@ impl Default for PreviewConfig {
             protocol: ImageProtocol::default(),
             thumbnail_size: default_thumbnail_size(),
             external_viewer: None,
+            mode: PreviewMode::default(),
+            protocol_overrides: HashMap::new(),
         }
     }
 }
 
 fn default_db_path() -> PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("clepho")
-        .join("clepho.db")
+    Config::data_dir().join("photos.db")
 }
 
 fn default_llm_endpoint() -> String {
@@ -813,11 +1557,20 @@ fn default_similarity_threshold() -> u32 {
        // Higher values catch more edited versions (borders, contrast) but may have false positives
 }
 
+fn default_embedding_similarity_threshold() -> f32 {
+    0.95
+}
+
 impl Default for ScannerConfig {
     fn default() -> Self {
         Self {
             image_extensions: default_image_extensions(),
             similarity_threshold: default_similarity_threshold(),
+            embedding_similarity_threshold: default_embedding_similarity_threshold(),
+            scan_pdfs: false,
+            max_threads: None,
+            io_priority: IoPriority::default(),
+            auto_scan_dirs: Vec::new(),
         }
     }
 }
@@ -834,8 +1587,15 @@ impl Default for Config {
             thumbnails: ThumbnailConfig::default(),
             schedule: ScheduleConfig::default(),
             library: LibraryConfig::default(),
+            quality: QualityConfig::default(),
             keybindings: KeyBindings::default(),
             view: ViewConfig::default(),
+            ui: UiConfig::default(),
+            notifications: NotificationsConfig::default(),
+            gallery: GalleryConfig::default(),
+            slideshow: SlideshowConfig::default(),
+            theme: ThemeConfig::default(),
+            raw_paths: HashMap::new(),
         }
     }
 }
@@ -846,11 +1606,17 @@ impl Config {
 
         if config_path.exists() {
             let content = std::fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&content)?;
+            let mut config: Config = toml::from_str(&content)?;
+            config.expand_path_vars();
+            config.validate()?;
+            if config.migrate_legacy_paths() {
+                config.save()?;
+            }
             Ok(config)
         } else {
             // Create default config
-            let config = Config::default();
+            let mut config = Config::default();
+            config.migrate_legacy_paths();
             config.save()?;
             Ok(config)
         }
@@ -858,10 +1624,149 @@ impl Config {
 
     pub fn load_from(path: &std::path::Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        let config: Config = toml::from_str(&content)?;
+        let mut config: Config = toml::from_str(&content)?;
+        config.expand_path_vars();
+        config.validate()?;
         Ok(config)
     }
 
+    /// Replace `${VAR}` and `$VAR` references with values from the
+    /// environment. Unset variables expand to an empty string.
+    pub fn expand_env_vars(value: &str) -> String {
+        let mut result = String::with_capacity(value.len());
+        let mut chars = value.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '$' {
+                result.push(c);
+                continue;
+            }
+
+            let name = if chars.peek() == Some(&'{') {
+                let rest: String = chars.clone().skip(1).collect();
+                match rest.find('}') {
+                    Some(end) => {
+                        let name = rest[..end].to_string();
+                        // Skip '{', the name and '}'
+                        for _ in 0..name.chars().count() + 2 {
+                            chars.next();
+                        }
+                        name
+                    }
+                    None => {
+                        // Unterminated ${ - keep literally
+                        result.push(c);
+                        continue;
+                    }
+                }
+            } else {
+                let mut name = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_ascii_alphanumeric() || next == '_' {
+                        name.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                name
+            };
+
+            if name.is_empty() {
+                result.push(c);
+            } else {
+                result.push_str(&std::env::var(&name).unwrap_or_default());
+            }
+        }
+
+        result
+    }
+
+    /// All path-valued settings, by their config key.
+    fn path_fields_mut(&mut self) -> Vec<(&'static str, &mut PathBuf)> {
+        let mut fields = vec![
+            ("database.sqlite_path", &mut self.database.sqlite_path),
+            ("thumbnails.path", &mut self.thumbnails.path),
+            ("trash.path", &mut self.trash.path),
+            ("duplicate_trash.path", &mut self.duplicate_trash.path),
+        ];
+        if let Some(path) = self.library.path.as_mut() {
+            fields.push(("library.path", path));
+        }
+        fields
+    }
+
+    /// Copy of the config with `${VAR}` references put back into any
+    /// path that still holds its expanded value, for writing to disk.
+    fn with_raw_paths(&self) -> Config {
+        let mut config = self.clone();
+        for (key, path) in config.path_fields_mut() {
+            if let Some((raw, expanded)) = self.raw_paths.get(key) {
+                if path == expanded {
+                    *path = raw.clone();
+                }
+            }
+        }
+        config
+    }
+
+    /// Expand environment variables in all path settings.
+    fn expand_path_vars(&mut self) {
+        let mut raw_paths = HashMap::new();
+        for (key, path) in self.path_fields_mut() {
+            let raw = path.to_string_lossy().to_string();
+            if !raw.contains('$') {
+                continue;
+            }
+            let expanded = PathBuf::from(Self::expand_env_vars(&raw));
+            raw_paths.insert(key, (path.clone(), expanded.clone()));
+            *path = expanded;
+        }
+        self.raw_paths = raw_paths;
+    }
+
+    /// Keys of settings that differ in `reloaded` but are only read at
+    /// startup, so changing them needs a restart.
+    pub fn restart_required_changes(&self, reloaded: &Config) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.database.backend != reloaded.database.backend {
+            changed.push("database.backend");
+        }
+        if self.database.sqlite_path != reloaded.database.sqlite_path {
+            changed.push("database.sqlite_path");
+        }
+        if self.database.postgresql_url != reloaded.database.postgresql_url {
+            changed.push("database.postgresql_url");
+        }
+        if self.database.pool_size != reloaded.database.pool_size {
+            changed.push("database.pool_size");
+        }
+        if self.preview.protocol != reloaded.preview.protocol {
+            changed.push("preview.protocol");
+        }
+        if self.thumbnails.path != reloaded.thumbnails.path {
+            changed.push("thumbnails.path");
+        }
+        changed
+    }
+
+    /// Check settings that parse fine but cannot be used as-is.
+    pub fn validate(&self) -> Result<()> {
+        let ratios = self.ui.pane_ratios;
+        let total: u16 = ratios.iter().sum();
+        if total != 100 {
+            anyhow::bail!(
+                "ui.pane_ratios must sum to 100, got {:?} (sum {})",
+                ratios,
+                total
+            );
+        }
+        if ratios.contains(&0) {
+            anyhow::bail!("ui.pane_ratios must all be greater than 0, got {:?}", ratios);
+        }
+        Ok(())
+    }
+
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path();
 
@@ -870,20 +1775,372 @@ impl Config {
             std::fs::create_dir_all(parent)?;
         }
 
-        let content = toml::to_string_pretty(self)?;
+        let content = toml::to_string_pretty(&self.with_raw_paths())?;
         std::fs::write(&config_path, content)?;
 
         Ok(())
     }
 
-    fn config_path() -> PathBuf {
+    /// Default config file location (`$XDG_CONFIG_HOME/clepho/config.toml`).
+    pub fn config_path() -> PathBuf {
         Self::config_dir().join("config.toml")
     }
 
-    /// Get the clepho configuration directory.
+    /// Get the clepho configuration directory (`$XDG_CONFIG_HOME/clepho`).
     pub fn config_dir() -> PathBuf {
         dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("clepho")
     }
+
+    /// Directory for the database (`$XDG_DATA_HOME/clepho`).
+    pub fn data_dir() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("clepho")
+    }
+
+    /// Directory for regenerable files such as thumbnails
+    /// (`$XDG_CACHE_HOME/clepho`).
+    pub fn cache_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from(".cache"))
+            .join("clepho")
+    }
+
+    /// Directory for logs and session state (`$XDG_STATE_HOME/clepho`).
+    /// Platforms without a state directory use the local data directory.
+    pub fn state_dir() -> PathBuf {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("clepho")
+    }
+
+    /// Move files left at the defaults of older releases to their current
+    /// locations. Only settings pointing at an old or current default are
+    /// touched; returns true if any setting changed and should be saved.
+    pub fn migrate_legacy_paths(&mut self) -> bool {
+        let legacy_db = Self::data_dir().join("clepho.db");
+        let legacy_thumbs = Self::cache_dir().join("thumbnails");
+        let mut changed = false;
+
+        let new_db = default_db_path();
+        if self.database.sqlite_path == legacy_db || self.database.sqlite_path == new_db {
+            // SQLite keeps uncommitted pages in the -wal/-shm side files,
+            // so the database only moves together with them
+            let files: Vec<(PathBuf, PathBuf)> = ["", "-wal", "-shm"]
+                .iter()
+                .map(|suffix| {
+                    (
+                        PathBuf::from(format!("{}{}", legacy_db.display(), suffix)),
+                        PathBuf::from(format!("{}{}", new_db.display(), suffix)),
+                    )
+                })
+                .collect();
+            let moved = migrate_paths_together(&files);
+            if moved && self.database.sqlite_path != new_db {
+                self.database.sqlite_path = new_db;
+                changed = true;
+            }
+        }
+
+        let new_thumbs = default_thumb_cache_path();
+        if self.thumbnails.path == legacy_thumbs {
+            if migrate_path(&legacy_thumbs, &new_thumbs) {
+                self.thumbnails.path = new_thumbs;
+                changed = true;
+            }
+        } else if self.thumbnails.path == new_thumbs {
+            migrate_path(&legacy_thumbs, &new_thumbs);
+        }
+
+        // Logs used to live next to the config file
+        let legacy_logs = Self::config_dir().join("logs");
+        if legacy_logs.is_dir() {
+            migrate_dir_contents(&legacy_logs, &Self::state_dir());
+        }
+
+        changed
+    }
+}
+
+/// Move `from` to `to` unless `to` already exists. Returns true when `to`
+/// is usable afterwards (moved, or nothing to move).
+fn migrate_path(from: &std::path::Path, to: &std::path::Path) -> bool {
+    if !from.exists() {
+        return true;
+    }
+    if to.exists() {
+        tracing::warn!(
+            from = %from.display(),
+            to = %to.display(),
+            "Not migrating: destination already exists"
+        );
+        return false;
+    }
+    if let Some(parent) = to.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            tracing::warn!(error = %e, path = %parent.display(), "Failed to create directory");
+            return false;
+        }
+    }
+    match std::fs::rename(from, to) {
+        Ok(()) => {
+            tracing::info!(from = %from.display(), to = %to.display(), "Migrated to XDG location");
+            true
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, from = %from.display(), "Failed to migrate");
+            false
+        }
+    }
+}
+
+/// Move each `(from, to)` pair like `migrate_path`, all or none: nothing
+/// moves if any destination already exists, and the files already moved
+/// go back if a later one fails. Returns true when every `to` is usable.
+fn migrate_paths_together(files: &[(PathBuf, PathBuf)]) -> bool {
+    if let Some((from, to)) = files.iter().find(|(from, to)| from.exists() && to.exists()) {
+        tracing::warn!(
+            from = %from.display(),
+            to = %to.display(),
+            "Not migrating: destination already exists"
+        );
+        return false;
+    }
+
+    let mut moved: Vec<&(PathBuf, PathBuf)> = Vec::new();
+    for pair in files {
+        let (from, to) = pair;
+        let existed = from.exists();
+        if !migrate_path(from, to) {
+            for (from, to) in moved.into_iter().rev() {
+                if let Err(e) = std::fs::rename(to, from) {
+                    tracing::error!(error = %e, from = %to.display(), to = %from.display(), "Failed to roll back migration");
+                }
+            }
+            return false;
+        }
+        if existed {
+            moved.push(pair);
+        }
+    }
+    true
+}
+
+/// Move every entry of `from` into `to`, then remove `from` if empty.
+fn migrate_dir_contents(from: &std::path::Path, to: &std::path::Path) {
+    let Ok(entries) = std::fs::read_dir(from) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        migrate_path(&entry.path(), &to.join(entry.file_name()));
+    }
+    let _ = std::fs::remove_dir(from);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_order_cycle() {
+        let mut order = SortOrder::default();
+        let mut seen = vec![order];
+        for _ in 0..3 {
+            order = order.next();
+            seen.push(order);
+        }
+        assert_eq!(
+            seen,
+            [SortOrder::Name, SortOrder::DateTaken, SortOrder::Size, SortOrder::DateModified]
+        );
+        assert_eq!(order.next(), SortOrder::Name);
+
+        let view: ViewConfig = toml::from_str("sort_order = \"date_taken\"").unwrap();
+        assert_eq!(view.sort_order, SortOrder::DateTaken);
+    }
+
+    #[test]
+    fn test_preview_mode_cycle_skips_images() {
+        assert_eq!(PreviewMode::ImageAndMetadata.next(true), PreviewMode::MetadataOnly);
+        assert_eq!(PreviewMode::MetadataOnly.next(true), PreviewMode::ImageOnly);
+        assert_eq!(PreviewMode::TextPreview.next(true), PreviewMode::ImageAndMetadata);
+
+        // Without image support only the text modes are offered
+        assert_eq!(PreviewMode::MetadataOnly.next(false), PreviewMode::TextPreview);
+        assert_eq!(PreviewMode::TextPreview.next(false), PreviewMode::MetadataOnly);
+        assert_eq!(PreviewMode::ImageOnly.resolve(false), PreviewMode::MetadataOnly);
+        assert_eq!(PreviewMode::TextPreview.resolve(false), PreviewMode::TextPreview);
+
+        let preview: PreviewConfig = toml::from_str("mode = \"image_only\"").unwrap();
+        assert_eq!(preview.mode, PreviewMode::ImageOnly);
+    }
+
+    #[test]
+    fn test_resize_preview_keeps_total() {
+        let mut ui = UiConfig::default();
+        assert!(ui.resize_preview(5));
+        assert_eq!(ui.pane_ratios, [20, 35, 45]);
+        assert!(ui.resize_preview(-10));
+        assert_eq!(ui.pane_ratios, [20, 45, 35]);
+        assert_eq!(ui.pane_ratios.iter().sum::<u16>(), 100);
+    }
+
+    #[test]
+    fn test_resize_preview_respects_minimum() {
+        let mut ui = UiConfig {
+            pane_ratios: [20, 15, 65],
+            ..Default::default()
+        };
+        assert!(ui.resize_preview(5));
+        assert!(!ui.resize_preview(5));
+        assert_eq!(ui.pane_ratios, [20, 10, 70]);
+    }
+
+    #[test]
+    fn test_validate_pane_ratios() {
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+
+        config.ui.pane_ratios = [30, 40, 40];
+        assert!(config.validate().is_err());
+
+        config.ui.pane_ratios = [0, 50, 50];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_migrate_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("clepho.db");
+        let new = dir.path().join("sub/photos.db");
+
+        // Nothing to move is fine
+        assert!(migrate_path(&old, &new));
+
+        std::fs::write(&old, b"db").unwrap();
+        assert!(migrate_path(&old, &new));
+        assert!(!old.exists());
+        assert_eq!(std::fs::read(&new).unwrap(), b"db");
+
+        // Never overwrite an existing destination
+        std::fs::write(&old, b"stale").unwrap();
+        assert!(!migrate_path(&old, &new));
+        assert_eq!(std::fs::read(&new).unwrap(), b"db");
+    }
+
+    #[test]
+    fn test_migrate_paths_together() {
+        let dir = tempfile::tempdir().unwrap();
+        let pair = |suffix: &str| {
+            (dir.path().join(format!("clepho.db{}", suffix)), dir.path().join(format!("new/photos.db{}", suffix)))
+        };
+        let files = vec![pair(""), pair("-wal"), pair("-shm")];
+        std::fs::write(&files[0].0, b"db").unwrap();
+        std::fs::write(&files[1].0, b"wal").unwrap();
+
+        // A clash on the WAL keeps the database where it was too
+        std::fs::create_dir_all(dir.path().join("new")).unwrap();
+        std::fs::write(&files[1].1, b"other wal").unwrap();
+        assert!(!migrate_paths_together(&files));
+        assert_eq!(std::fs::read(&files[0].0).unwrap(), b"db");
+        assert!(!files[0].1.exists());
+
+        // A failure part way moves the database back
+        std::fs::remove_file(&files[1].1).unwrap();
+        std::fs::create_dir(&files[1].1).unwrap();
+        std::fs::write(files[1].1.join("occupant"), b"").unwrap();
+        let clash = vec![files[0].clone(), (files[1].0.clone(), files[1].1.join("occupant").join("wal"))];
+        assert!(!migrate_paths_together(&clash));
+        assert_eq!(std::fs::read(&files[0].0).unwrap(), b"db");
+        assert!(!files[0].1.exists());
+
+        std::fs::remove_dir_all(&files[1].1).unwrap();
+        assert!(migrate_paths_together(&files));
+        assert_eq!(std::fs::read(&files[0].1).unwrap(), b"db");
+        assert_eq!(std::fs::read(&files[1].1).unwrap(), b"wal");
+        assert!(!files[0].0.exists());
+    }
+
+    #[test]
+    fn test_restart_required_changes() {
+        let running = Config::default();
+        let mut reloaded = Config::default();
+        reloaded.llm.endpoint = "http://elsewhere:1234/v1".to_string();
+        assert!(running.restart_required_changes(&reloaded).is_empty());
+
+        reloaded.database.sqlite_path = PathBuf::from("/tmp/other.db");
+        reloaded.preview.protocol = ImageProtocol::Sixel;
+        assert_eq!(
+            running.restart_required_changes(&reloaded),
+            vec!["database.sqlite_path", "preview.protocol"]
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        std::env::set_var("CLEPHO_TEST_LIB", "/mnt/photos");
+        std::env::remove_var("CLEPHO_TEST_UNSET");
+
+        assert_eq!(Config::expand_env_vars("${CLEPHO_TEST_LIB}/2024"), "/mnt/photos/2024");
+        assert_eq!(Config::expand_env_vars("$CLEPHO_TEST_LIB/2024"), "/mnt/photos/2024");
+        assert_eq!(Config::expand_env_vars("/a/${CLEPHO_TEST_UNSET}/b"), "/a//b");
+        assert_eq!(Config::expand_env_vars("/a/$CLEPHO_TEST_UNSET"), "/a/");
+        assert_eq!(Config::expand_env_vars("/no/vars"), "/no/vars");
+        assert_eq!(Config::expand_env_vars("cost$ ${unterminated"), "cost$ ${unterminated");
+    }
+
+    #[test]
+    fn test_expanded_paths_restored_for_save() {
+        std::env::set_var("CLEPHO_TEST_TRASH", "/srv/trash");
+        let mut config = Config::default();
+        config.trash.path = PathBuf::from("${CLEPHO_TEST_TRASH}/clepho");
+        config.expand_path_vars();
+        assert_eq!(config.trash.path, PathBuf::from("/srv/trash/clepho"));
+
+        let to_write = config.with_raw_paths();
+        assert_eq!(to_write.trash.path, PathBuf::from("${CLEPHO_TEST_TRASH}/clepho"));
+
+        // Paths changed at runtime are saved as-is
+        config.trash.path = PathBuf::from("/elsewhere");
+        assert_eq!(config.with_raw_paths().trash.path, PathBuf::from("/elsewhere"));
+    }
+
+    #[test]
+    fn test_llm_provider_list() {
+        let single: LlmConfig = toml::from_str("provider = \"ollama\"\nmodel = \"llava\"").unwrap();
+        let list = single.provider_list();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].provider, LlmProviderType::Ollama);
+        assert_eq!(list[0].model, "llava");
+
+        let multi: LlmConfig = toml::from_str(
+            "strategy = \"round_robin\"\n\
+             [[providers]]\nprovider = \"ollama\"\nendpoint = \"http://localhost:11434\"\nmodel = \"llava\"\n\
+             [[providers]]\nprovider = \"openai\"\nmodel = \"gpt-4o\"\napi_key = \"sk-test\"\n",
+        )
+        .unwrap();
+        assert_eq!(multi.strategy, LlmStrategy::RoundRobin);
+        let list = multi.provider_list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[1].provider, LlmProviderType::OpenAI);
+        assert_eq!(list[1].api_key.as_deref(), Some("sk-test"));
+    }
+
+    #[test]
+    fn test_preview_protocol_overrides() {
+        let preview: PreviewConfig = toml::from_str(
+            "protocol = \"kitty\"\n\
+             [protocol_overrides]\n\"/mnt/nas\" = \"sixel\"\n\"/mnt/nas/slow\" = \"halfblocks\"\n",
+        )
+        .unwrap();
+        assert_eq!(preview.protocol_for(Path::new("/home/me/photos")), ImageProtocol::Kitty);
+        assert_eq!(preview.protocol_for(Path::new("/mnt/nas/2024")), ImageProtocol::Sixel);
+        assert_eq!(preview.protocol_for(Path::new("/mnt/nas/slow/2024")), ImageProtocol::Halfblocks);
+        // Prefixes match whole directory names
+        assert_eq!(preview.protocol_for(Path::new("/mnt/nas2")), ImageProtocol::Kitty);
+        assert!(preview.any_images());
+    }
 }
@@ -0,0 +1,140 @@
+//! Copying an album's photos into one flat directory, for example to send
+//! to a print lab, optionally giving them sequential names.
+
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+/// How many sequence numbers to try for one photo before giving up
+pub const MAX_NUMBERING_ATTEMPTS: usize = 10_000;
+
+/// Part of a rename pattern
+#[derive(Debug, PartialEq)]
+enum Piece<'a> {
+    Text(&'a str),
+    /// `{seq}` or `{seq:N}`, with the zero padded width
+    Seq(usize),
+}
+
+/// Split a pattern into text and sequence placeholders. Longer names such
+/// as `{sequence}` are text; `{seq:` without a width and `}` is an error.
+fn parse_pattern(pattern: &str) -> Result<Vec<Piece<'_>>> {
+    let mut pieces = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find("{seq") {
+        let after = &rest[start + 4..];
+        let (width, len) = if after.starts_with('}') {
+            (0, 1)
+        } else if let Some(spec) = after.strip_prefix(':') {
+            match spec.find('}').map(|end| (spec[..end].parse::<usize>(), end)) {
+                Some((Ok(width), end)) => (width, end + 2),
+                _ => bail!("Malformed sequence placeholder in {:?}: use {{seq}} or {{seq:N}}", pattern),
+            }
+        } else {
+            pieces.push(Piece::Text(&rest[..start + 4]));
+            rest = after;
+            continue;
+        };
+        pieces.push(Piece::Text(&rest[..start]));
+        pieces.push(Piece::Seq(width));
+        rest = &after[len..];
+    }
+    pieces.push(Piece::Text(rest));
+    Ok(pieces)
+}
+
+/// Expand a rename pattern for one photo. Understands `{album}` (the album
+/// name made filename safe), `{name}` (the photo's file stem), `{ext}`, and
+/// `{seq}` or `{seq:N}` (the sequence number, zero padded to N digits).
+pub fn expand_pattern(pattern: &str, album: &str, source: &Path, seq: usize) -> Result<String> {
+    let name = source.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = source.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+
+    let mut result = String::with_capacity(pattern.len());
+    for piece in parse_pattern(pattern)? {
+        match piece {
+            Piece::Text(text) => result.push_str(text),
+            Piece::Seq(width) => result.push_str(&format!("{:0width$}", seq, width = width)),
+        }
+    }
+
+    Ok(result
+        .replace("{album}", &safe_album_name(album))
+        .replace("{name}", &name)
+        .replace("{ext}", &ext))
+}
+
+/// Whether a pattern numbers its files, so conflicts can take the next
+/// number. Fails for malformed sequence placeholders.
+pub fn pattern_has_seq(pattern: &str) -> Result<bool> {
+    Ok(parse_pattern(pattern)?.iter().any(|piece| matches!(piece, Piece::Seq(_))))
+}
+
+/// `path`, or the first of `stem_1.ext`, `stem_2.ext`, ... that does not exist
+pub fn unique_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{}_{}{}", stem, n, ext)))
+        .find(|candidate| !candidate.exists())
+        .expect("some numbered name is free")
+}
+
+fn safe_album_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_pattern() {
+        let source = Path::new("/photos/IMG_0042.JPG");
+        let expand = |pattern: &str, album: &str, seq: usize| expand_pattern(pattern, album, source, seq).unwrap();
+        assert_eq!(expand("{album}_{seq:04}.{ext}", "Smith Wedding", 7), "Smith_Wedding_0007.JPG");
+        assert_eq!(expand("{seq}-{name}.{ext}", "x", 12), "12-IMG_0042.JPG");
+        assert_eq!(expand("{sequence}{seq", "x", 1), "{sequence}{seq");
+        assert!(pattern_has_seq("a_{seq:3}").unwrap());
+        assert!(!pattern_has_seq("{name}.{ext}").unwrap());
+        for malformed in ["{seq:}", "{seq:x}", "{seq:3"] {
+            assert!(pattern_has_seq(malformed).is_err());
+            assert!(expand_pattern(malformed, "x", source, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn test_export_album_renames_and_skips_taken_names() {
+        let (dir, db) = crate::db::test_db();
+
+        let album = db.create_album("Lab", None, false).unwrap();
+        for (sub, name) in [("a", "one.jpg"), ("b", "one.jpg")] {
+            let photo_dir = dir.path().join(sub);
+            std::fs::create_dir_all(&photo_dir).unwrap();
+            let path = photo_dir.join(name);
+            std::fs::write(&path, sub).unwrap();
+            let path_str = path.to_string_lossy().to_string();
+            db.insert_basic_photo(&path_str, name, &photo_dir.to_string_lossy(), 1).unwrap();
+            let id = db.get_photo_metadata(&path).unwrap().unwrap().id;
+            db.add_photo_to_album(album, id).unwrap();
+        }
+
+        // Same file names from two directories are kept apart
+        let flat = dir.path().join("flat");
+        assert_eq!(db.export_album_to_dir(album, &flat, None).unwrap(), 2);
+        assert!(flat.join("one.jpg").exists() && flat.join("one_1.jpg").exists());
+
+        // A taken sequence number moves on to the next one
+        let lab = dir.path().join("lab");
+        std::fs::create_dir_all(&lab).unwrap();
+        std::fs::write(lab.join("Lab_01.jpg"), "existing").unwrap();
+        assert_eq!(db.export_album_to_dir(album, &lab, Some("{album}_{seq:02}.{ext}")).unwrap(), 2);
+        assert_eq!(std::fs::read_to_string(lab.join("Lab_02.jpg")).unwrap(), "a");
+        assert_eq!(std::fs::read_to_string(lab.join("Lab_03.jpg")).unwrap(), "b");
+    }
+}
@@ -2,6 +2,8 @@
 
 #![allow(dead_code)]
 
+use serde::{Deserialize, Serialize};
+
 /// A user-defined tag
 #[derive(Debug, Clone)]
 pub struct UserTag {
@@ -10,6 +12,15 @@ pub struct UserTag {
     pub color: String,
 }
 
+/// A tag with the number of photos carrying it
+#[derive(Debug, Clone)]
+pub struct TagStat {
+    pub tag: UserTag,
+    pub photo_count: i64,
+    /// The tag this one is nested under
+    pub parent_id: Option<i64>,
+}
+
 /// An album (collection of photos)
 #[derive(Debug, Clone)]
 pub struct Album {
@@ -19,5 +30,104 @@ pub struct Album {
     pub cover_photo_id: Option<i64>,
     pub is_smart: bool,
     pub filter_tags: Vec<i64>,
+    /// Area a smart album's photos must have been taken in
+    pub geo_radius: Option<GeoRadius>,
     pub photo_count: i64,
+    /// Order the album's photos are shown in when it is opened
+    pub auto_sort: AlbumSort,
+}
+
+/// Smart album criterion: photos with GPS coordinates within `radius_km`
+/// of the centre. Stored as JSON in `albums.geo_radius`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoRadius {
+    pub center_lat: f64,
+    pub center_lon: f64,
+    pub radius_km: f64,
+}
+
+impl GeoRadius {
+    /// Parse `lat, lon, radius_km`, e.g. `51.5072, -0.1276, 5`
+    pub fn parse(input: &str) -> Option<Self> {
+        let parts: Vec<f64> = input
+            .split(',')
+            .map(|p| p.trim().trim_end_matches("km").trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let [center_lat, center_lon, radius_km] = parts[..] else {
+            return None;
+        };
+        let valid = (-90.0..=90.0).contains(&center_lat) && (-180.0..=180.0).contains(&center_lon) && radius_km > 0.0;
+        valid.then_some(Self { center_lat, center_lon, radius_km })
+    }
+
+    pub fn label(&self) -> String {
+        format!("{:.4}, {:.4}, {} km", self.center_lat, self.center_lon, self.radius_km)
+    }
+}
+
+/// Members of a smart album from the photos matching each criterion it
+/// has; `None` for criteria it does not use. An album without any
+/// criteria is empty.
+pub fn smart_album_members(tagged: Option<Vec<i64>>, nearby: Option<Vec<i64>>) -> Vec<i64> {
+    match (tagged, nearby) {
+        (Some(tagged), Some(nearby)) => {
+            let nearby: std::collections::HashSet<i64> = nearby.into_iter().collect();
+            tagged.into_iter().filter(|id| nearby.contains(id)).collect()
+        }
+        (Some(ids), None) | (None, Some(ids)) => ids,
+        (None, None) => Vec::new(),
+    }
+}
+
+/// Order an album opens in. Anything but `Manual` sorts on open without
+/// changing the stored order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlbumSort {
+    /// The order photos were added in, as rearranged in the gallery
+    #[default]
+    Manual,
+    /// Newest first
+    Date,
+    Name,
+    /// Highest rating first
+    Rating,
+}
+
+impl AlbumSort {
+    pub fn cycle(&self) -> Self {
+        match self {
+            AlbumSort::Manual => AlbumSort::Date,
+            AlbumSort::Date => AlbumSort::Name,
+            AlbumSort::Name => AlbumSort::Rating,
+            AlbumSort::Rating => AlbumSort::Manual,
+        }
+    }
+
+    /// Value stored in `albums.auto_sort`, `None` for the manual order
+    pub fn as_db_str(&self) -> Option<&'static str> {
+        match self {
+            AlbumSort::Manual => None,
+            AlbumSort::Date => Some("date"),
+            AlbumSort::Name => Some("name"),
+            AlbumSort::Rating => Some("rating"),
+        }
+    }
+
+    pub fn from_db_str(s: Option<&str>) -> Self {
+        match s {
+            Some("date") => AlbumSort::Date,
+            Some("name") => AlbumSort::Name,
+            Some("rating") => AlbumSort::Rating,
+            _ => AlbumSort::Manual,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlbumSort::Manual => "manual order",
+            AlbumSort::Date => "by date",
+            AlbumSort::Name => "by name",
+            AlbumSort::Rating => "by rating",
+        }
+    }
 }
@@ -12,7 +12,6 @@ pub struct EmbeddingRecord {
 /// Search result with similarity score
 #[derive(Debug, Clone)]
 pub struct SearchResult {
-    #[allow(dead_code)]
     pub photo_id: i64,
     pub path: String,
     pub filename: String,
@@ -20,6 +19,95 @@ pub struct SearchResult {
     pub description: Option<String>,
 }
 
+/// Photos a semantic search is limited to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SearchScope {
+    /// The whole library
+    #[default]
+    All,
+    Album(i64),
+    Tag(i64),
+    /// Photos with a face assigned to this person
+    Person(i64),
+    /// A directory and everything below it
+    Directory(String),
+}
+
+impl SearchScope {
+    /// LIKE pattern matching every path inside a directory scope, for use
+    /// with `ESCAPE '\'`. Wildcards in the directory name match literally.
+    pub fn directory_pattern(directory: &str) -> String {
+        let escaped = directory
+            .trim_end_matches('/')
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("{}/%", escaped)
+    }
+
+    /// Stored form, e.g. "all", "tag:3" or "directory:/photos/2024".
+    pub fn to_db_string(&self) -> String {
+        match self {
+            SearchScope::All => "all".to_string(),
+            SearchScope::Album(id) => format!("album:{}", id),
+            SearchScope::Tag(id) => format!("tag:{}", id),
+            SearchScope::Person(id) => format!("person:{}", id),
+            SearchScope::Directory(dir) => format!("directory:{}", dir),
+        }
+    }
+
+    pub fn from_db_string(s: &str) -> Option<Self> {
+        if s == "all" {
+            return Some(SearchScope::All);
+        }
+        let (kind, value) = s.split_once(':')?;
+        match kind {
+            "album" => value.parse().ok().map(SearchScope::Album),
+            "tag" => value.parse().ok().map(SearchScope::Tag),
+            "person" => value.parse().ok().map(SearchScope::Person),
+            "directory" => Some(SearchScope::Directory(value.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// Photos a search considers: a scope, optionally narrowed by face count
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFilter {
+    pub scope: SearchScope,
+    pub min_faces: Option<usize>,
+    pub max_faces: Option<usize>,
+}
+
+impl SearchFilter {
+    pub fn has_face_limits(&self) -> bool {
+        self.min_faces.is_some() || self.max_faces.is_some()
+    }
+
+    /// Face count bounds as an inclusive range for SQL.
+    pub fn face_range(&self) -> (i64, i64) {
+        (
+            self.min_faces.map(|n| n as i64).unwrap_or(0),
+            self.max_faces.map(|n| n as i64).unwrap_or(i64::MAX),
+        )
+    }
+}
+
+impl From<SearchScope> for SearchFilter {
+    fn from(scope: SearchScope) -> Self {
+        Self { scope, ..Self::default() }
+    }
+}
+
+/// A named search query and the scope it runs in
+#[derive(Debug, Clone)]
+pub struct SavedSearch {
+    pub id: i64,
+    pub name: String,
+    pub query: String,
+    pub scope: SearchScope,
+}
+
 /// Convert f32 slice to bytes for storage
 pub fn embedding_to_bytes(embedding: &[f32]) -> Vec<u8> {
     let mut bytes = Vec::with_capacity(embedding.len() * 4);
@@ -61,6 +149,28 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_directory_pattern() {
+        assert_eq!(SearchScope::directory_pattern("/photos/2024"), "/photos/2024/%");
+        assert_eq!(SearchScope::directory_pattern("/photos/2024/"), "/photos/2024/%");
+        assert_eq!(SearchScope::directory_pattern("/photos/100%_b\\w"), "/photos/100\\%\\_b\\\\w/%");
+    }
+
+    #[test]
+    fn test_scope_db_string_round_trip() {
+        for scope in [
+            SearchScope::All,
+            SearchScope::Album(4),
+            SearchScope::Tag(12),
+            SearchScope::Person(7),
+            SearchScope::Directory("/photos/a:b".to_string()),
+        ] {
+            assert_eq!(SearchScope::from_db_string(&scope.to_db_string()), Some(scope));
+        }
+        assert_eq!(SearchScope::from_db_string("tag:x"), None);
+        assert_eq!(SearchScope::from_db_string("bogus"), None);
+    }
+
     #[test]
     fn test_cosine_similarity() {
         let a = vec![1.0, 0.0, 0.0];
@@ -0,0 +1,208 @@
+//! Events: runs of photos taken close together that look alike, named by
+//! the LLM from their descriptions.
+
+use chrono::NaiveDateTime;
+
+use super::embeddings::cosine_similarity;
+
+/// Longest gap between consecutive photos of one event
+pub const EVENT_GAP_HOURS: i64 = 6;
+
+/// Similarity a photo's CLIP embedding needs to the event so far to join it
+pub const EVENT_SIMILARITY_THRESHOLD: f32 = 0.6;
+
+/// Fewer photos than this are not worth naming as an event
+pub const MIN_EVENT_PHOTOS: usize = 3;
+
+/// Format `start_date` and `end_date` are stored in
+pub const EVENT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// A stored event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub id: i64,
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub photo_count: i64,
+}
+
+/// A described photo with a capture date, considered for event detection
+#[derive(Debug, Clone)]
+pub struct EventCandidate {
+    pub photo_id: i64,
+    /// `taken_at` as stored by the scanner
+    pub taken_at: String,
+    pub description: String,
+    /// CLIP embedding, when one has been generated
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// A cluster of photos that still needs a name
+#[derive(Debug, Clone)]
+pub struct DetectedEvent {
+    pub photo_ids: Vec<i64>,
+    pub descriptions: Vec<String>,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl DetectedEvent {
+    /// The stored event with the same photos, so its name can be reused
+    pub fn matches(&self, event: &Event) -> bool {
+        event.start_date == self.start.format(EVENT_DATE_FORMAT).to_string()
+            && event.end_date == self.end.format(EVENT_DATE_FORMAT).to_string()
+            && event.photo_count == self.photo_ids.len() as i64
+    }
+
+    /// The event to store under `name`
+    pub fn into_event(self, name: String) -> Event {
+        Event {
+            id: 0,
+            name,
+            start_date: self.start.format(EVENT_DATE_FORMAT).to_string(),
+            end_date: self.end.format(EVENT_DATE_FORMAT).to_string(),
+            photo_count: self.photo_ids.len() as i64,
+        }
+    }
+}
+
+/// Parse an EXIF-style `taken_at`, with dashes or colons in the date
+pub fn parse_taken_at(taken_at: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(taken_at, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(taken_at, "%Y:%m:%d %H:%M:%S"))
+        .ok()
+}
+
+/// Group photos into events. Photos are taken in date order; each one joins
+/// the current event when it follows the previous photo within
+/// `EVENT_GAP_HOURS` and its embedding is close enough to the event's mean
+/// embedding. Photos without an embedding are grouped by time alone.
+pub fn cluster_events(candidates: Vec<EventCandidate>) -> Vec<DetectedEvent> {
+    let mut dated: Vec<(NaiveDateTime, EventCandidate)> = candidates
+        .into_iter()
+        .filter_map(|c| parse_taken_at(&c.taken_at).map(|t| (t, c)))
+        .collect();
+    dated.sort_by_key(|(t, _)| *t);
+
+    let max_gap = chrono::Duration::hours(EVENT_GAP_HOURS);
+    let mut events = Vec::new();
+    let mut current: Option<(DetectedEvent, Option<Vec<f32>>)> = None;
+
+    for (taken, candidate) in dated {
+        let joins = current.as_ref().is_some_and(|(event, centroid)| {
+            let close_in_time = taken - event.end <= max_gap;
+            let looks_alike = match (centroid, &candidate.embedding) {
+                (Some(centroid), Some(embedding)) => {
+                    cosine_similarity(centroid, embedding) >= EVENT_SIMILARITY_THRESHOLD
+                }
+                _ => true,
+            };
+            close_in_time && looks_alike
+        });
+
+        if !joins {
+            if let Some((event, _)) = current.take() {
+                if event.photo_ids.len() >= MIN_EVENT_PHOTOS {
+                    events.push(event);
+                }
+            }
+            current = Some((
+                DetectedEvent { photo_ids: Vec::new(), descriptions: Vec::new(), start: taken, end: taken },
+                None,
+            ));
+        }
+
+        let (event, centroid) = current.as_mut().expect("an event was just started");
+        event.photo_ids.push(candidate.photo_id);
+        event.descriptions.push(candidate.description);
+        event.end = taken;
+        // The sum of the embeddings points the same way as their mean
+        if let Some(embedding) = candidate.embedding {
+            match centroid {
+                Some(sum) if sum.len() == embedding.len() => {
+                    sum.iter_mut().zip(&embedding).for_each(|(s, e)| *s += e);
+                }
+                Some(_) => {}
+                None => *centroid = Some(embedding),
+            }
+        }
+    }
+
+    if let Some((event, _)) = current {
+        if event.photo_ids.len() >= MIN_EVENT_PHOTOS {
+            events.push(event);
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn photo(id: i64, taken_at: &str, embedding: Option<Vec<f32>>) -> EventCandidate {
+        EventCandidate {
+            photo_id: id,
+            taken_at: taken_at.to_string(),
+            description: format!("photo {}", id),
+            embedding,
+        }
+    }
+
+    #[test]
+    fn test_splits_on_time_gaps_and_drops_small_clusters() {
+        let events = cluster_events(vec![
+            photo(3, "2024:05:01 12:00:00", None),
+            photo(1, "2024:05:01 10:00:00", None),
+            photo(2, "2024-05-01 11:00:00", None),
+            // A day later: a new run, too short to be an event
+            photo(4, "2024:05:02 12:00:00", None),
+            photo(5, "2024:05:02 13:00:00", None),
+            photo(6, "not a date", None),
+        ]);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].photo_ids, vec![1, 2, 3]);
+        let stored = events[0].clone().into_event("Picnic".to_string());
+        assert_eq!(stored.start_date, "2024-05-01 10:00:00");
+        assert_eq!(stored.end_date, "2024-05-01 12:00:00");
+        assert!(events[0].matches(&stored));
+    }
+
+    #[test]
+    fn test_splits_when_photos_stop_looking_alike() {
+        let beach = Some(vec![1.0, 0.0]);
+        let museum = Some(vec![0.0, 1.0]);
+        let events = cluster_events(vec![
+            photo(1, "2024:05:01 10:00:00", beach.clone()),
+            photo(2, "2024:05:01 10:10:00", beach.clone()),
+            photo(3, "2024:05:01 10:20:00", None),
+            photo(4, "2024:05:01 10:30:00", museum.clone()),
+            photo(5, "2024:05:01 10:40:00", museum.clone()),
+            photo(6, "2024:05:01 10:50:00", museum),
+        ]);
+
+        let ids: Vec<Vec<i64>> = events.into_iter().map(|e| e.photo_ids).collect();
+        assert_eq!(ids, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_replace_events() {
+        let (_dir, db) = crate::db::test_db();
+        assert!(db.get_event_candidates().unwrap().is_empty());
+
+        let event = |name: &str, start: &str| Event {
+            id: 0,
+            name: name.to_string(),
+            start_date: start.to_string(),
+            end_date: start.to_string(),
+            photo_count: 3,
+        };
+        db.replace_events(&[event("Old", "2023-01-01 10:00:00")]).unwrap();
+        db.replace_events(&[event("Zoo", "2024-06-01 10:00:00"), event("Beach", "2024-05-01 10:00:00")]).unwrap();
+
+        let names: Vec<String> = db.get_events().unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["Beach", "Zoo"]);
+    }
+}
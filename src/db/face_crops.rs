@@ -0,0 +1,101 @@
+//! Cropping detected faces out of their photos for use as training data.
+
+use anyhow::{Context, Result};
+use image::{imageops::FilterType, DynamicImage, GenericImageView};
+use std::path::Path;
+
+use super::faces::BoundingBox;
+
+/// Directory used for faces not assigned to a person
+pub const UNKNOWN_PERSON_DIR: &str = "unknown";
+
+/// Outcome of a face crop export
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaceCropReport {
+    /// Crops written to disk
+    pub exported: usize,
+    /// Faces whose photo could not be read or whose crop could not be saved
+    pub skipped: usize,
+}
+
+/// Crop a face with 20% padding on every side, clamped to the image bounds.
+/// Matches the region the detector feeds to the embedding model.
+pub fn crop_with_padding(img: &DynamicImage, bbox: &BoundingBox) -> DynamicImage {
+    let (img_width, img_height) = img.dimensions();
+    let padding_x = (bbox.width as f32 * 0.2) as i32;
+    let padding_y = (bbox.height as f32 * 0.2) as i32;
+
+    let x = ((bbox.x - padding_x).max(0) as u32).min(img_width.saturating_sub(1));
+    let y = ((bbox.y - padding_y).max(0) as u32).min(img_height.saturating_sub(1));
+    let w = ((bbox.width + padding_x * 2).max(1) as u32).min(img_width - x);
+    let h = ((bbox.height + padding_y * 2).max(1) as u32).min(img_height - y);
+
+    img.crop_imm(x, y, w.max(1), h.max(1))
+}
+
+/// Crop a face and write it as a JPEG, upscaling crops smaller than
+/// `min_size` on either side to `min_size × min_size`.
+pub fn save_face_crop(img: &DynamicImage, bbox: &BoundingBox, min_size: u32, path: &Path) -> Result<()> {
+    let mut crop = crop_with_padding(img, bbox);
+    if crop.width() < min_size || crop.height() < min_size {
+        crop = crop.resize_exact(min_size, min_size, FilterType::Lanczos3);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    DynamicImage::ImageRgb8(crop.to_rgb8())
+        .save(path)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Turn a person's name into a safe directory name
+pub fn person_dir_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    if cleaned.trim().is_empty() {
+        UNKNOWN_PERSON_DIR.to_string()
+    } else {
+        cleaned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padding_is_clamped_to_image_bounds() {
+        let img = DynamicImage::new_rgb8(100, 80);
+
+        let centred = crop_with_padding(&img, &BoundingBox { x: 40, y: 30, width: 20, height: 20 });
+        assert_eq!(centred.dimensions(), (28, 28));
+
+        let corner = crop_with_padding(&img, &BoundingBox { x: 0, y: 0, width: 20, height: 20 });
+        assert_eq!(corner.dimensions(), (28, 28));
+
+        let edge = crop_with_padding(&img, &BoundingBox { x: 90, y: 70, width: 20, height: 20 });
+        assert_eq!(edge.dimensions(), (14, 14));
+    }
+
+    #[test]
+    fn small_crops_are_upscaled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Alice").join("1.jpg");
+        let img = DynamicImage::new_rgb8(100, 100);
+
+        save_face_crop(&img, &BoundingBox { x: 40, y: 40, width: 10, height: 10 }, 64, &path).unwrap();
+        assert_eq!(image::open(&path).unwrap().dimensions(), (64, 64));
+    }
+
+    #[test]
+    fn person_names_become_safe_directories() {
+        assert_eq!(person_dir_name("Alice Smith"), "Alice Smith");
+        assert_eq!(person_dir_name("../etc"), "___etc");
+        assert_eq!(person_dir_name("  "), UNKNOWN_PERSON_DIR);
+    }
+}
@@ -2,6 +2,10 @@
 
 #![allow(dead_code)]
 
+/// Model that produces face embeddings. Faces whose `embedding_model`
+/// differs are regenerated by the face embedding task.
+pub const FACE_EMBEDDING_MODEL: &str = "arcface-resnet100-int8";
+
 /// Bounding box for a detected face
 #[derive(Debug, Clone)]
 pub struct BoundingBox {
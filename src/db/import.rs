@@ -0,0 +1,116 @@
+//! Reading photo metadata from CSV files produced by other cataloguing tools.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+
+/// Outcome of a metadata import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Rows applied to a photo
+    pub matched: usize,
+    /// Rows naming a file that is not in the database
+    pub unmatched: usize,
+    /// Rows that could not be read or applied
+    pub errors: usize,
+}
+
+/// One CSV row, with empty cells left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataRow {
+    pub filename: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub rating: Option<i32>,
+}
+
+/// Read every row of a metadata CSV. Column names are matched case
+/// insensitively and may come in any order; a missing `filename` column
+/// fails the whole import, while a bad row only fails that row.
+pub fn read_metadata_csv(csv_path: &Path) -> Result<Vec<Result<MetadataRow>>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(csv_path)
+        .with_context(|| format!("Failed to open {}", csv_path.display()))?;
+
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.to_lowercase()).collect();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let filename_col = column("filename").ok_or_else(|| anyhow!("CSV has no filename column"))?;
+    let description_col = column("description");
+    let tags_col = column("tags");
+    let rating_col = column("rating");
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            let record = record?;
+            let cell = |col: Option<usize>| {
+                col.and_then(|c| record.get(c)).filter(|v| !v.is_empty())
+            };
+            let filename = cell(Some(filename_col)).ok_or_else(|| anyhow!("Row has no filename"))?;
+            Ok(MetadataRow {
+                filename: filename.to_string(),
+                description: cell(description_col).map(str::to_string),
+                tags: cell(tags_col).map(parse_tags).unwrap_or_default(),
+                rating: cell(rating_col).map(parse_rating).transpose()?,
+            })
+        })
+        .collect();
+    Ok(rows)
+}
+
+/// Split a tag cell on commas or semicolons.
+fn parse_tags(cell: &str) -> Vec<String> {
+    cell.split([',', ';'])
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A 0-5 star rating.
+fn parse_rating(cell: &str) -> Result<i32> {
+    match cell.parse::<i32>() {
+        Ok(rating) if (0..=5).contains(&rating) => Ok(rating),
+        _ => Err(anyhow!("Rating must be a number from 0 to 5, got \"{}\"", cell)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_read_metadata_csv() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "Rating,Filename,Tags,Description").unwrap();
+        writeln!(file, "4,beach.jpg,\"holiday; sea, family\",Waves at dusk").unwrap();
+        writeln!(file, ",dog.jpg,,").unwrap();
+        writeln!(file, "9,bad.jpg,,").unwrap();
+        writeln!(file, "3,,,").unwrap();
+
+        let rows = read_metadata_csv(file.path()).unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[0].as_ref().unwrap(),
+            &MetadataRow {
+                filename: "beach.jpg".to_string(),
+                description: Some("Waves at dusk".to_string()),
+                tags: vec!["holiday".to_string(), "sea".to_string(), "family".to_string()],
+                rating: Some(4),
+            }
+        );
+        let dog = rows[1].as_ref().unwrap();
+        assert_eq!((dog.description.as_deref(), dog.tags.len(), dog.rating), (None, 0, None));
+        assert!(rows[2].is_err());
+        assert!(rows[3].is_err());
+    }
+
+    #[test]
+    fn test_missing_filename_column() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "name,description").unwrap();
+        assert!(read_metadata_csv(file.path()).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Location clusters: photos grouped by rounding their coordinates to a
+//! grid, from country-sized cells down to street-sized ones.
+
+use std::collections::HashMap;
+
+/// Grid cell sizes in degrees, coarsest first
+pub const PRECISIONS: [f32; 5] = [10.0, 1.0, 0.1, 0.01, 0.001];
+
+/// Cell size the locations view starts with, about a kilometre
+pub const DEFAULT_PRECISION: f32 = 0.01;
+
+/// Photos whose coordinates round to the same grid cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpsCluster {
+    /// Mean coordinates of the photos in the cell
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Photo paths, sorted
+    pub paths: Vec<String>,
+    /// Cached place name near the mean coordinates
+    pub place: Option<String>,
+}
+
+/// Group `(path, latitude, longitude)` triples into cells `precision`
+/// degrees wide, largest cluster first.
+pub fn cluster_by_grid(photos: Vec<(String, f64, f64)>, precision: f32) -> Vec<GpsCluster> {
+    let precision = f64::from(precision);
+    // Coordinates are summed while grouping and averaged afterwards
+    let mut cells: HashMap<(i64, i64), GpsCluster> = HashMap::new();
+    for (path, lat, lon) in photos {
+        let key = ((lat / precision).round() as i64, (lon / precision).round() as i64);
+        let cell = cells.entry(key).or_insert_with(|| GpsCluster {
+            latitude: 0.0,
+            longitude: 0.0,
+            paths: Vec::new(),
+            place: None,
+        });
+        cell.latitude += lat;
+        cell.longitude += lon;
+        cell.paths.push(path);
+    }
+
+    let mut clusters: Vec<GpsCluster> = cells
+        .into_values()
+        .map(|mut cluster| {
+            let n = cluster.paths.len() as f64;
+            cluster.latitude /= n;
+            cluster.longitude /= n;
+            cluster.paths.sort();
+            cluster
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.paths
+            .len()
+            .cmp(&a.paths.len())
+            .then(a.latitude.total_cmp(&b.latitude))
+            .then(a.longitude.total_cmp(&b.longitude))
+    });
+    clusters
+}
+
+/// Key place names are cached under: the coordinates on a 0.01° grid
+pub fn place_key(latitude: f64, longitude: f64) -> (i64, i64) {
+    ((latitude * 100.0).round() as i64, (longitude * 100.0).round() as i64)
+}
+
+/// Next finer cell size, or the finest one
+pub fn finer(precision: f32) -> f32 {
+    PRECISIONS.iter().copied().find(|p| *p < precision).unwrap_or(PRECISIONS[PRECISIONS.len() - 1])
+}
+
+/// Next coarser cell size, or the coarsest one
+pub fn coarser(precision: f32) -> f32 {
+    PRECISIONS.iter().rev().copied().find(|p| *p > precision).unwrap_or(PRECISIONS[0])
+}
+
+/// Rough size of a cell for display
+pub fn precision_label(precision: f32) -> &'static str {
+    match precision {
+        p if p >= 10.0 => "country",
+        p if p >= 1.0 => "region",
+        p if p >= 0.1 => "city",
+        p if p >= 0.01 => "neighbourhood",
+        _ => "street",
+    }
+}
+
+/// Mean radius of the Earth
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Great-circle distance between two coordinates (haversine formula)
+pub fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+/// `(min_lat, max_lat, min_lon, max_lon)` of a box holding every point
+/// within `radius_km`, to pre-filter in SQL before measuring distances.
+/// Spans all longitudes near the poles or across the antimeridian.
+pub fn bounding_box(lat: f64, lon: f64, radius_km: f64) -> (f64, f64, f64, f64) {
+    let lat_delta = (radius_km / EARTH_RADIUS_KM).to_degrees();
+    let (min_lat, max_lat) = (lat - lat_delta, lat + lat_delta);
+    if min_lat <= -90.0 || max_lat >= 90.0 {
+        return (min_lat.max(-90.0), max_lat.min(90.0), -180.0, 180.0);
+    }
+    // Widest in longitude where the circle touches its tangent meridians
+    let sin_delta = (radius_km / EARTH_RADIUS_KM).sin() / lat.to_radians().cos();
+    if sin_delta >= 1.0 {
+        return (min_lat, max_lat, -180.0, 180.0);
+    }
+    let lon_delta = sin_delta.asin().to_degrees();
+    let (min_lon, max_lon) = (lon - lon_delta, lon + lon_delta);
+    if min_lon < -180.0 || max_lon > 180.0 {
+        return (min_lat, max_lat, -180.0, 180.0);
+    }
+    (min_lat, max_lat, min_lon, max_lon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_by_grid() {
+        let photos = vec![
+            ("/b.jpg".to_string(), 51.5072, -0.1276),
+            ("/a.jpg".to_string(), 51.5081, -0.1281),
+            ("/paris.jpg".to_string(), 48.8566, 2.3522),
+        ];
+
+        let fine = cluster_by_grid(photos.clone(), 0.01);
+        assert_eq!(fine.len(), 2);
+        assert_eq!(fine[0].paths, vec!["/a.jpg", "/b.jpg"]);
+        assert!((fine[0].latitude - 51.50765).abs() < 1e-9);
+
+        // London and Paris share a 10° cell
+        assert_eq!(cluster_by_grid(photos, 10.0).len(), 1);
+
+        assert_eq!(place_key(51.5072, -0.1276), (5151, -13));
+        assert_eq!(finer(0.01), 0.001);
+        assert_eq!(finer(0.001), 0.001);
+        assert_eq!(coarser(0.01), 0.1);
+        assert_eq!(coarser(10.0), 10.0);
+    }
+
+    #[test]
+    fn test_haversine_km() {
+        // London to Paris is about 344 km
+        let distance = haversine_km(51.5072, -0.1276, 48.8566, 2.3522);
+        assert!((distance - 343.6).abs() < 1.0);
+        assert_eq!(haversine_km(10.0, 20.0, 10.0, 20.0), 0.0);
+
+        let (min_lat, max_lat, min_lon, max_lon) = bounding_box(51.5, -0.13, 10.0);
+        assert!(min_lat < 51.5 && max_lat > 51.5 && max_lat - min_lat < 0.2);
+        assert!((max_lon + 0.13 - 0.1444).abs() < 0.001);
+        assert!(min_lon > -0.5);
+        assert_eq!(bounding_box(0.0, 179.99, 50.0).2, -180.0);
+    }
+}
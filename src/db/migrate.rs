@@ -1,14 +1,19 @@
-//! SQLite-to-PostgreSQL migration tool.
+//! Migration between SQLite and PostgreSQL.
 //!
 //! Reads all tables from the SQLite database and bulk-inserts into PostgreSQL,
 //! respecting foreign key ordering. Preserves original IDs and resets
-//! PostgreSQL sequences afterward.
+//! PostgreSQL sequences afterward. The reverse direction copies every table
+//! out of PostgreSQL into a fresh SQLite database.
 
 use anyhow::{Context, Result};
 use postgres::NoTls;
+use rusqlite::types::Value;
 use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::Path;
 
 use super::postgres_schema::POSTGRES_SCHEMA;
+use super::sqlite::SqliteDb;
 
 /// Migrate all data from a SQLite database to a PostgreSQL database.
 ///
@@ -31,8 +36,13 @@ pub fn migrate_sqlite_to_postgres(sqlite_path: &std::path::Path, postgres_url: &
 
     // Migrate tables in foreign-key-safe order
     migrate_photos(&sqlite, &mut pg)?;
+    migrate_gps_sources(&sqlite, &mut pg)?;
+    migrate_ratings(&sqlite, &mut pg)?;
+    migrate_real_paths(&sqlite, &mut pg)?;
+    migrate_color_palettes(&sqlite, &mut pg)?;
     migrate_people(&sqlite, &mut pg)?;
     migrate_faces(&sqlite, &mut pg)?;
+    migrate_face_embedding_models(&sqlite, &mut pg)?;
     migrate_face_scans(&sqlite, &mut pg)?;
     migrate_embeddings(&sqlite, &mut pg)?;
     migrate_face_clusters(&sqlite, &mut pg)?;
@@ -42,10 +52,17 @@ pub fn migrate_sqlite_to_postgres(sqlite_path: &std::path::Path, postgres_url: &
     migrate_scans(&sqlite, &mut pg)?;
     migrate_llm_queue(&sqlite, &mut pg)?;
     migrate_user_tags(&sqlite, &mut pg)?;
+    migrate_tag_parents(&sqlite, &mut pg)?;
     migrate_photo_user_tags(&sqlite, &mut pg)?;
     migrate_albums(&sqlite, &mut pg)?;
+    migrate_album_settings(&sqlite, &mut pg)?;
     migrate_album_photos(&sqlite, &mut pg)?;
     migrate_scheduled_tasks(&sqlite, &mut pg)?;
+    migrate_saved_searches(&sqlite, &mut pg)?;
+    migrate_app_state(&sqlite, &mut pg)?;
+    migrate_events(&sqlite, &mut pg)?;
+    migrate_place_names(&sqlite, &mut pg)?;
+    migrate_slideshow_prefs(&sqlite, &mut pg)?;
 
     // Reset all sequences to max(id) + 1
     reset_sequences(&mut pg)?;
@@ -137,6 +154,86 @@ fn migrate_photos(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()>
     Ok(())
 }
 
+fn migrate_gps_sources(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before gps_source existed have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, gps_source FROM photos WHERE gps_source IS NOT NULL") else {
+        return Ok(());
+    };
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    for row in rows {
+        let (id, source) = row?;
+        pg.execute(
+            "UPDATE photos SET gps_source = $1 WHERE id = $2",
+            &[&source, &id],
+        )?;
+    }
+    Ok(())
+}
+
+fn migrate_ratings(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before ratings existed have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, rating FROM photos WHERE rating IS NOT NULL") else {
+        return Ok(());
+    };
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, i32>(1)?))
+    })?;
+
+    for row in rows {
+        let (id, rating) = row?;
+        pg.execute(
+            "UPDATE photos SET rating = $1 WHERE id = $2",
+            &[&rating, &id],
+        )?;
+    }
+    Ok(())
+}
+
+fn migrate_real_paths(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before real paths existed have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, real_path FROM photos WHERE real_path IS NOT NULL") else {
+        return Ok(());
+    };
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    for row in rows {
+        let (id, real_path) = row?;
+        pg.execute(
+            "UPDATE photos SET real_path = $1 WHERE id = $2",
+            &[&real_path, &id],
+        )?;
+    }
+    Ok(())
+}
+
+fn migrate_color_palettes(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before palettes existed have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, color_palette FROM photos WHERE color_palette IS NOT NULL") else {
+        return Ok(());
+    };
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    for row in rows {
+        let (id, palette) = row?;
+        pg.execute(
+            "UPDATE photos SET color_palette = $1 WHERE id = $2",
+            &[&palette, &id],
+        )?;
+    }
+    Ok(())
+}
+
 fn migrate_people(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
     let mut stmt = sqlite.prepare("SELECT id, name, created_at, updated_at FROM people")?;
     let rows = stmt.query_map([], |row| {
@@ -200,6 +297,26 @@ fn migrate_faces(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
     Ok(())
 }
 
+fn migrate_face_embedding_models(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before embedding models were tracked have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, embedding_model FROM faces WHERE embedding_model IS NOT NULL") else {
+        return Ok(());
+    };
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    for row in rows {
+        let (id, model) = row?;
+        pg.execute(
+            "UPDATE faces SET embedding_model = $1 WHERE id = $2",
+            &[&model, &id],
+        )?;
+    }
+    Ok(())
+}
+
 fn migrate_face_scans(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
     let mut stmt = sqlite.prepare("SELECT photo_id, scanned_at, faces_found FROM face_scans")?;
     let rows = stmt.query_map([], |row| {
@@ -453,6 +570,26 @@ fn migrate_user_tags(sqlite: &Connection, pg: &mut postgres::Client) -> Result<(
     Ok(())
 }
 
+fn migrate_tag_parents(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before tags could be nested have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, parent_id FROM user_tags WHERE parent_id IS NOT NULL") else {
+        return Ok(());
+    };
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+    })?;
+
+    for row in rows {
+        let (id, parent_id) = row?;
+        pg.execute(
+            "UPDATE user_tags SET parent_id = $1 WHERE id = $2",
+            &[&parent_id, &id],
+        )?;
+    }
+    Ok(())
+}
+
 fn migrate_photo_user_tags(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
     let mut stmt = sqlite.prepare(
         "SELECT photo_id, tag_id, created_at FROM photo_user_tags"
@@ -512,6 +649,26 @@ fn migrate_albums(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()>
     Ok(())
 }
 
+fn migrate_album_settings(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Each column is copied on its own: databases created before album
+    // sorting or geofences lack one or both, and have nothing to copy for it
+    for column in ["auto_sort", "geo_radius"] {
+        let Ok(mut stmt) = sqlite.prepare(&format!("SELECT id, {column} FROM albums WHERE {column} IS NOT NULL")) else {
+            continue;
+        };
+
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        for row in rows {
+            let (id, value) = row?;
+            pg.execute(&format!("UPDATE albums SET {column} = $1 WHERE id = $2"), &[&value, &id])?;
+        }
+    }
+    Ok(())
+}
+
 fn migrate_album_photos(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
     let mut stmt = sqlite.prepare(
         "SELECT album_id, photo_id, position, added_at FROM album_photos"
@@ -581,6 +738,144 @@ fn migrate_scheduled_tasks(sqlite: &Connection, pg: &mut postgres::Client) -> Re
     Ok(())
 }
 
+fn migrate_saved_searches(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    let mut stmt = sqlite.prepare("SELECT id, name, query, scope, created_at FROM saved_searches")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+        ))
+    })?;
+
+    let mut count = 0u64;
+    for row in rows {
+        let r = row?;
+        pg.execute(
+            "INSERT INTO saved_searches (id, name, query, scope, created_at) VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (id) DO NOTHING",
+            &[&r.0, &r.1, &r.2, &r.3, &r.4],
+        )?;
+        count += 1;
+    }
+    eprintln!("  saved_searches: {} rows migrated", count);
+    Ok(())
+}
+
+fn migrate_app_state(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before app_state existed have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT key, value, updated_at FROM app_state") else {
+        return Ok(());
+    };
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut count = 0u64;
+    for row in rows {
+        let r = row?;
+        pg.execute(
+            "INSERT INTO app_state (key, value, updated_at) VALUES ($1, $2, $3)
+             ON CONFLICT (key) DO NOTHING",
+            &[&r.0, &r.1, &r.2],
+        )?;
+        count += 1;
+    }
+    eprintln!("  app_state: {} rows migrated", count);
+    Ok(())
+}
+
+fn migrate_events(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before events existed have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT id, name, start_date, end_date, photo_count, created_at FROM events") else {
+        return Ok(());
+    };
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, i64>(4)?,
+            row.get::<_, String>(5)?,
+        ))
+    })?;
+
+    let mut count = 0u64;
+    for row in rows {
+        let r = row?;
+        pg.execute(
+            "INSERT INTO events (id, name, start_date, end_date, photo_count, created_at) VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (id) DO NOTHING",
+            &[&r.0, &r.1, &r.2, &r.3, &r.4, &r.5],
+        )?;
+        count += 1;
+    }
+    eprintln!("  events: {} rows migrated", count);
+    Ok(())
+}
+
+fn migrate_place_names(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before place names were cached have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT lat_key, lon_key, name, updated_at FROM place_names") else {
+        return Ok(());
+    };
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut count = 0u64;
+    for row in rows {
+        let r = row?;
+        pg.execute(
+            "INSERT INTO place_names (lat_key, lon_key, name, updated_at) VALUES ($1, $2, $3, $4)
+             ON CONFLICT (lat_key, lon_key) DO NOTHING",
+            &[&r.0, &r.1, &r.2, &r.3],
+        )?;
+        count += 1;
+    }
+    eprintln!("  place_names: {} rows migrated", count);
+    Ok(())
+}
+
+fn migrate_slideshow_prefs(sqlite: &Connection, pg: &mut postgres::Client) -> Result<()> {
+    // Databases created before slideshow speeds were saved have nothing to copy
+    let Ok(mut stmt) = sqlite.prepare("SELECT directory, interval_secs, updated_at FROM slideshow_prefs") else {
+        return Ok(());
+    };
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, f64>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut count = 0u64;
+    for row in rows {
+        let r = row?;
+        pg.execute(
+            "INSERT INTO slideshow_prefs (directory, interval_secs, updated_at) VALUES ($1, $2, $3)
+             ON CONFLICT (directory) DO NOTHING",
+            &[&r.0, &r.1, &r.2],
+        )?;
+        count += 1;
+    }
+    eprintln!("  slideshow_prefs: {} rows migrated", count);
+    Ok(())
+}
+
 /// Reset all BIGSERIAL sequences to max(id) + 1 so new inserts get correct IDs.
 fn reset_sequences(pg: &mut postgres::Client) -> Result<()> {
     let sequences = [
@@ -594,6 +889,8 @@ fn reset_sequences(pg: &mut postgres::Client) -> Result<()> {
         ("user_tags", "user_tags_id_seq"),
         ("albums", "albums_id_seq"),
         ("scheduled_tasks", "scheduled_tasks_id_seq"),
+        ("saved_searches", "saved_searches_id_seq"),
+        ("events", "events_id_seq"),
     ];
 
     for (table, seq) in &sequences {
@@ -613,3 +910,198 @@ fn reset_sequences(pg: &mut postgres::Client) -> Result<()> {
     eprintln!("  Sequences reset to current max IDs");
     Ok(())
 }
+
+/// Tables copied out of PostgreSQL, in foreign-key-safe order
+const TABLES: &[&str] = &[
+    "photos",
+    "people",
+    "faces",
+    "face_scans",
+    "embeddings",
+    "face_clusters",
+    "face_cluster_members",
+    "similarity_groups",
+    "photo_similarity",
+    "scans",
+    "llm_queue",
+    "user_tags",
+    "photo_user_tags",
+    "albums",
+    "album_photos",
+    "smart_album_photos",
+    "scheduled_tasks",
+    "directory_prompts",
+    "saved_searches",
+    "app_state",
+    "events",
+    "place_names",
+    "slideshow_prefs",
+];
+
+/// Rows fetched from PostgreSQL at a time
+const BATCH_ROWS: i32 = 1000;
+
+/// How a PostgreSQL column is read and stored in SQLite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    /// SMALLINT, INTEGER, BIGINT and SERIAL columns
+    Integer,
+    /// BOOLEAN, stored as 0 or 1
+    Boolean,
+    /// REAL, DOUBLE PRECISION and NUMERIC
+    Real,
+    /// BYTEA, stored as a BLOB
+    Blob,
+    /// Everything else (TEXT, TIMESTAMPTZ, JSONB, ...) as its text form
+    Text,
+}
+
+impl ColumnKind {
+    fn from_data_type(data_type: &str) -> Self {
+        match data_type {
+            "smallint" | "integer" | "bigint" => ColumnKind::Integer,
+            "boolean" => ColumnKind::Boolean,
+            "real" | "double precision" | "numeric" => ColumnKind::Real,
+            "bytea" => ColumnKind::Blob,
+            _ => ColumnKind::Text,
+        }
+    }
+
+    /// SELECT expression that yields a value of the Rust type read below
+    fn select_expr(self, column: &str) -> String {
+        match self {
+            ColumnKind::Integer => format!("\"{}\"::bigint", column),
+            ColumnKind::Real => format!("\"{}\"::float8", column),
+            ColumnKind::Boolean | ColumnKind::Blob => format!("\"{}\"", column),
+            ColumnKind::Text => format!("\"{}\"::text", column),
+        }
+    }
+
+    fn read(self, row: &postgres::Row, idx: usize) -> Result<Value> {
+        let value = match self {
+            ColumnKind::Integer => row.try_get::<_, Option<i64>>(idx)?.map(Value::Integer),
+            ColumnKind::Boolean => row.try_get::<_, Option<bool>>(idx)?.map(|b| Value::Integer(b as i64)),
+            ColumnKind::Real => row.try_get::<_, Option<f64>>(idx)?.map(Value::Real),
+            ColumnKind::Blob => row.try_get::<_, Option<Vec<u8>>>(idx)?.map(Value::Blob),
+            ColumnKind::Text => row.try_get::<_, Option<String>>(idx)?.map(Value::Text),
+        };
+        Ok(value.unwrap_or(Value::Null))
+    }
+}
+
+/// Copy all data from a PostgreSQL database into a new SQLite database.
+///
+/// The SQLite schema is created first, then every table is copied with its
+/// original IDs, columns matched by name. SQLite's AUTOINCREMENT counters
+/// follow the inserted IDs, so nothing needs resetting afterwards.
+pub fn migrate_postgres_to_sqlite(postgres_url: &str, sqlite_path: &Path) -> Result<()> {
+    if sqlite_path.exists() {
+        anyhow::bail!("{} already exists; choose a new output path", sqlite_path.display());
+    }
+
+    let mut pg = postgres::Client::connect(postgres_url, NoTls)
+        .with_context(|| "Failed to connect to PostgreSQL")?;
+
+    eprintln!("Creating SQLite schema...");
+    let db = SqliteDb::open(&sqlite_path.to_path_buf())
+        .with_context(|| format!("Failed to create SQLite database: {}", sqlite_path.display()))?;
+    db.initialize()?;
+
+    let tx = db.conn.unchecked_transaction()?;
+    for table in TABLES {
+        copy_table_to_sqlite(&mut pg, &tx, table)?;
+    }
+    tx.commit()?;
+
+    eprintln!("Migration complete!");
+    Ok(())
+}
+
+fn copy_table_to_sqlite(pg: &mut postgres::Client, sqlite: &Connection, table: &str) -> Result<()> {
+    let pg_columns: Vec<(String, ColumnKind)> = pg
+        .query(
+            "SELECT column_name::text, data_type::text FROM information_schema.columns
+             WHERE table_schema = current_schema() AND table_name = $1
+             ORDER BY ordinal_position",
+            &[&table],
+        )?
+        .iter()
+        .map(|row| (row.get(0), ColumnKind::from_data_type(row.get::<_, &str>(1))))
+        .collect();
+    if pg_columns.is_empty() {
+        eprintln!("  {}: not in PostgreSQL, skipped", table);
+        return Ok(());
+    }
+
+    // Only columns both schemas know about
+    let sqlite_columns: HashSet<String> = sqlite
+        .prepare(&format!("PRAGMA table_info({})", table))?
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<_>>()?;
+    let columns: Vec<(String, ColumnKind)> = pg_columns
+        .into_iter()
+        .filter(|(name, _)| sqlite_columns.contains(name))
+        .collect();
+
+    let select = format!(
+        "SELECT {} FROM \"{}\"",
+        columns.iter().map(|(name, kind)| kind.select_expr(name)).collect::<Vec<_>>().join(", "),
+        table
+    );
+    let insert = format!(
+        "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+        table,
+        columns.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", "),
+        vec!["?"; columns.len()].join(", ")
+    );
+    let mut stmt = sqlite.prepare(&insert)?;
+
+    // Read through a portal so large tables (embeddings) are not held in
+    // memory at once
+    let mut pg_tx = pg.transaction()?;
+    let portal = pg_tx.bind(select.as_str(), &[])?;
+    let mut count = 0u64;
+    loop {
+        let rows = pg_tx.query_portal(&portal, BATCH_ROWS)?;
+        if rows.is_empty() {
+            break;
+        }
+        for row in &rows {
+            let values = columns
+                .iter()
+                .enumerate()
+                .map(|(i, (_, kind))| kind.read(row, i))
+                .collect::<Result<Vec<Value>>>()?;
+            stmt.execute(rusqlite::params_from_iter(values))?;
+            count += 1;
+        }
+    }
+    pg_tx.commit()?;
+
+    eprintln!("  {}: {} rows migrated", table, count);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_kind_from_data_type() {
+        assert_eq!(ColumnKind::from_data_type("bigint"), ColumnKind::Integer);
+        assert_eq!(ColumnKind::from_data_type("boolean"), ColumnKind::Boolean);
+        assert_eq!(ColumnKind::from_data_type("double precision"), ColumnKind::Real);
+        assert_eq!(ColumnKind::from_data_type("bytea"), ColumnKind::Blob);
+        assert_eq!(ColumnKind::from_data_type("timestamp with time zone"), ColumnKind::Text);
+        assert_eq!(ColumnKind::Text.select_expr("taken_at"), "\"taken_at\"::text");
+    }
+
+    #[test]
+    fn test_migrate_refuses_existing_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("photos.db");
+        std::fs::write(&existing, b"").unwrap();
+        let err = migrate_postgres_to_sqlite("host=localhost", &existing).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,52 @@
+//! Metadata fields a rescan can backfill, for photos scanned before the
+//! scanner extracted them.
+
+use anyhow::{bail, Result};
+
+/// A field by the name `get_photos_with_missing_fields` takes, its label in
+/// the field picker, and the condition matching photos that lack it
+pub struct MissingField {
+    pub name: &'static str,
+    pub label: &'static str,
+    condition: &'static str,
+}
+
+pub const MISSING_FIELDS: &[MissingField] = &[
+    MissingField { name: "gps", label: "GPS location", condition: "gps_latitude IS NULL" },
+    MissingField { name: "camera", label: "Camera make and model", condition: "camera_make IS NULL AND camera_model IS NULL" },
+    MissingField { name: "lens", label: "Lens", condition: "lens IS NULL" },
+    MissingField { name: "dimensions", label: "Dimensions", condition: "width IS NULL OR height IS NULL" },
+    MissingField { name: "hashes", label: "Hashes", condition: "sha256_hash IS NULL OR perceptual_hash IS NULL" },
+];
+
+/// WHERE condition matching photos that lack any of `fields`. The SQL comes
+/// only from `MISSING_FIELDS`, so unknown names are an error rather than
+/// being spliced into the query.
+pub fn missing_fields_condition(fields: &[&str]) -> Result<String> {
+    if fields.is_empty() {
+        bail!("No fields to look for");
+    }
+    let mut conditions = Vec::new();
+    for name in fields {
+        match MISSING_FIELDS.iter().find(|f| f.name == *name) {
+            Some(field) => conditions.push(format!("({})", field.condition)),
+            None => bail!("Unknown metadata field: {}", name),
+        }
+    }
+    Ok(conditions.join(" OR "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_condition() {
+        assert_eq!(
+            missing_fields_condition(&["gps", "dimensions"]).unwrap(),
+            "(gps_latitude IS NULL) OR (width IS NULL OR height IS NULL)"
+        );
+        assert!(missing_fields_condition(&["gps; DROP TABLE photos"]).is_err());
+        assert!(missing_fields_condition(&[]).is_err());
+    }
+}
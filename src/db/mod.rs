@@ -1,11 +1,21 @@
 mod schema;
+pub mod album_export;
 pub mod albums;
 pub mod embeddings;
+pub mod events;
+pub mod face_crops;
 pub mod faces;
+pub mod import;
+pub mod locations;
+pub mod missing_fields;
+pub mod people_export;
+pub mod photoprism;
 pub mod schedule;
+pub mod search_query;
 pub mod similarity;
 pub mod sqlite;
 pub mod trash;
+pub mod write_queue;
 
 #[cfg(feature = "postgres")]
 pub mod postgres;
@@ -14,20 +24,39 @@ pub mod postgres_schema;
 #[cfg(feature = "postgres")]
 pub mod migrate;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 pub use schema::{SCHEMA, MIGRATIONS};
-pub use similarity::{PhotoRecord, SimilarityGroup, calculate_quality_score};
-pub use embeddings::SearchResult;
+pub use similarity::{PhotoRecord, SimilarityGroup, calculate_quality_score, restrict_to_directories};
+pub use embeddings::{SavedSearch, SearchFilter, SearchResult, SearchScope};
+pub use events::{Event, EventCandidate};
+pub use search_query::{SearchAst, parse_search_query};
 pub use faces::{BoundingBox, Face, FaceCluster, FaceWithPhoto, Person};
 pub use schedule::{ScheduledTask, ScheduledTaskType, ScheduleStatus};
-pub use albums::UserTag;
+pub use albums::{AlbumSort, GeoRadius, TagStat, UserTag};
+pub use face_crops::FaceCropReport;
+pub use import::ImportReport;
+pub use locations::GpsCluster;
+pub use people_export::PeopleImportReport;
 
 use crate::config::DatabaseConfig;
 #[cfg(feature = "postgres")]
 use crate::config::DatabaseType;
 
+/// Separator the export query joins tag and people names with (ASCII unit
+/// separator, so names may contain commas)
+const NAME_LIST_SEPARATOR: char = '\u{1f}';
+
+/// Split names joined with `NAME_LIST_SEPARATOR`; NULL means none
+fn split_name_list(joined: Option<String>) -> Vec<String> {
+    joined
+        .map(|s| s.split(NAME_LIST_SEPARATOR).map(String::from).collect())
+        .unwrap_or_default()
+}
+
 /// Convert EXIF orientation value (1-8) to rotation degrees (0, 90, 180, 270)
 fn exif_orientation_to_degrees(orientation: i32) -> i32 {
     match orientation {
@@ -65,8 +94,28 @@ fn read_exif_rotation_from_file(path: &Path) -> i32 {
     0
 }
 
-/// Full metadata for a photo from the database
+/// (path, modified_at, taken_at) of a photo
+pub type PhotoTimestamps = (String, Option<String>, Option<String>);
+
+/// Totals for the photos under a directory, including subdirectories
 #[derive(Debug, Clone, Default)]
+pub struct DirectoryPhotoStats {
+    pub photo_count: i64,
+    pub total_bytes: i64,
+    /// Most recent `taken_at` among the photos
+    pub latest_taken_at: Option<String>,
+}
+
+/// One directory holding scanned photos, for `--list-directories`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DirectorySummary {
+    pub directory: String,
+    pub photo_count: i64,
+    pub total_bytes: i64,
+}
+
+/// Full metadata for a photo from the database
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct PhotoMetadata {
     pub id: i64,
@@ -87,12 +136,20 @@ pub struct PhotoMetadata {
     pub taken_at: Option<String>,
     pub gps_latitude: Option<f64>,
     pub gps_longitude: Option<f64>,
+    /// "exif" or "manual"
+    pub gps_source: Option<String>,
+    /// EXIF orientation (1-8) read at scan time
+    pub exif_orientation: Option<i32>,
     pub modified_at: Option<String>,
     pub scanned_at: Option<String>,
     pub description: Option<String>,
     pub tags: Option<String>,
     pub sha256_hash: Option<String>,
     pub perceptual_hash: Option<String>,
+    /// 0-5 stars
+    pub rating: Option<i32>,
+    /// "red", "yellow", "green", "blue" or "purple"
+    pub color_label: Option<String>,
     pub face_count: i64,
     pub people_names: Vec<String>,
 }
@@ -100,6 +157,7 @@ pub struct PhotoMetadata {
 /// Photo data for export (database-layer struct to avoid circular dependency with export module)
 #[derive(Debug, Clone)]
 pub struct ExportedPhotoRow {
+    pub id: i64,
     pub path: String,
     pub filename: String,
     pub width: Option<u32>,
@@ -112,6 +170,13 @@ pub struct ExportedPhotoRow {
     pub date_taken: Option<String>,
     pub description: Option<String>,
     pub scanned_at: Option<String>,
+    pub rating: Option<i32>,
+    pub gps_latitude: Option<f64>,
+    pub gps_longitude: Option<f64>,
+    /// User tag names, sorted
+    pub tags: Vec<String>,
+    /// Names of the people whose faces are in the photo, sorted
+    pub people: Vec<String>,
 }
 
 /// Macro to dispatch a method call to the active backend variant.
@@ -162,6 +227,21 @@ impl Database {
         Ok(Self { inner: DatabaseInner::Sqlite(db) })
     }
 
+    /// Open a connection for queries only. With SQLite it never takes the
+    /// write lock, so it does not hold up the scanner's writes; PostgreSQL
+    /// is opened as usual.
+    pub fn open_read_only(config: &DatabaseConfig) -> Result<Self> {
+        #[cfg(feature = "postgres")]
+        {
+            if config.backend == DatabaseType::Postgresql {
+                return Self::open(config);
+            }
+        }
+
+        let db = sqlite::SqliteDb::open_read_only(&config.sqlite_path)?;
+        Ok(Self { inner: DatabaseInner::Sqlite(db) })
+    }
+
     pub fn initialize(&self) -> Result<()> {
         dispatch!(self, initialize())
     }
@@ -182,27 +262,121 @@ impl Database {
         dispatch!(self, update_photo_path(old_path, new_path))
     }
 
-    pub fn get_photos_mtime_in_dir(&self, directory: &str) -> Result<Vec<(String, Option<String>)>> {
+    pub fn get_photos_mtime_in_dir(&self, directory: &str) -> Result<Vec<PhotoTimestamps>> {
         dispatch!(self, get_photos_mtime_in_dir(directory))
     }
 
+    /// Number of photos taken on each day of `year`; days without photos are absent
+    pub fn get_photo_counts_by_date(&self, year: i32) -> Result<HashMap<chrono::NaiveDate, usize>> {
+        let year = format!("{:04}", year);
+        let rows: Vec<(String, i64)> = dispatch!(self, get_photo_counts_by_date(&year))?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(day, count)| {
+                let date = chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?;
+                Some((date, count as usize))
+            })
+            .collect())
+    }
+
+    /// Paths of photos taken on `date`, in capture order
+    pub fn get_photos_taken_on(&self, date: chrono::NaiveDate) -> Result<Vec<String>> {
+        let date = date.format("%Y-%m-%d").to_string();
+        dispatch!(self, get_photos_taken_on(&date))
+    }
+
+    /// Paths of photos in a directory taken between `start` and `end`, inclusive
+    pub fn get_photos_in_dir_date_range(
+        &self,
+        directory: &str,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+    ) -> Result<Vec<String>> {
+        let start = start.format("%Y-%m-%d").to_string();
+        let end = end.format("%Y-%m-%d").to_string();
+        dispatch!(self, get_photos_in_dir_date_range(directory, &start, &end))
+    }
+
+    /// Distinct "make model" camera labels in a directory with photo counts
+    pub fn get_camera_models_in_dir(&self, directory: &str) -> Result<Vec<(String, i64)>> {
+        dispatch!(self, get_camera_models_in_dir(directory))
+    }
+
+    /// Paths of photos in a directory shot with the given camera label
+    pub fn get_photos_in_dir_by_camera(&self, directory: &str, camera: &str) -> Result<Vec<String>> {
+        dispatch!(self, get_photos_in_dir_by_camera(directory, camera))
+    }
+
     pub fn get_photo_metadata(&self, path: &Path) -> Result<Option<PhotoMetadata>> {
         dispatch!(self, get_photo_metadata(path))
     }
 
     pub fn semantic_search_by_text(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        dispatch!(self, semantic_search_by_text(query, limit))
+        dispatch!(self, semantic_search_by_text(query, limit, &SearchFilter::default()))
+    }
+
+    /// Keyword search over descriptions, limited to photos matching a filter
+    pub fn semantic_search_by_text_scoped(&self, query: &str, limit: usize, filter: &SearchFilter) -> Result<Vec<SearchResult>> {
+        dispatch!(self, semantic_search_by_text(query, limit, filter))
+    }
+
+    /// Store a photo's dominant colors, most dominant first
+    pub fn set_color_palette(&self, path: &Path, palette: &[[u8; 3]]) -> Result<()> {
+        let json = serde_json::to_string(palette)?;
+        dispatch!(self, set_color_palette(&path.to_string_lossy(), &json))
+    }
+
+    /// Photos in a filter's scope with their color palettes, for ranking
+    /// by color. Photos scanned before palettes were stored are left out.
+    pub fn get_color_palettes(&self, filter: &SearchFilter) -> Result<Vec<(SearchResult, Vec<[u8; 3]>)>> {
+        let rows = dispatch!(self, get_color_palettes(filter))?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(result, json)| Some((result, serde_json::from_str(&json).ok()?)))
+            .collect())
+    }
+
+    /// Photos in a filter's scope within its face count limits, most faces
+    /// first. Only photos scanned for faces have a count.
+    pub fn get_photos_by_face_count(&self, filter: &SearchFilter, limit: usize) -> Result<Vec<SearchResult>> {
+        dispatch!(self, get_photos_by_face_count(filter, limit))
     }
 
     pub fn get_photo_rotation(&self, path: &Path) -> Result<i32> {
         dispatch!(self, get_photo_rotation(path))
     }
 
+    /// Every EXIF field read at scan time, as a JSON object
+    pub fn get_all_exif(&self, path: &Path) -> Result<Option<String>> {
+        dispatch!(self, get_all_exif(&path.to_string_lossy()))
+    }
+
     #[allow(dead_code)]
     pub fn set_user_rotation(&self, path: &Path, rotation: i32) -> Result<()> {
         dispatch!(self, set_user_rotation(path, rotation))
     }
 
+    /// Set a 0-5 star rating, or clear it with None
+    pub fn set_photo_rating(&self, photo_id: i64, rating: Option<i32>) -> Result<()> {
+        dispatch!(self, set_photo_rating(photo_id, rating))
+    }
+
+    /// Set a color label such as "red", or clear it with None
+    pub fn set_photo_color_label(&self, photo_id: i64, label: Option<&str>) -> Result<()> {
+        dispatch!(self, set_photo_color_label(photo_id, label))
+    }
+
+    /// Set coordinates entered by hand; these survive rescans.
+    pub fn set_manual_gps(&self, photo_id: i64, lat: f64, lon: f64) -> Result<()> {
+        dispatch!(self, set_manual_gps(photo_id, lat, lon))
+    }
+
+    /// Set the capture time ("YYYY-MM-DD HH:MM:SS") of a photo that has
+    /// none from EXIF
+    pub fn set_taken_at_if_missing(&self, photo_id: i64, taken_at: &str) -> Result<()> {
+        dispatch!(self, set_taken_at_if_missing(photo_id, taken_at))
+    }
+
     pub fn rotate_photo_cw(&self, path: &Path) -> Result<i32> {
         dispatch!(self, rotate_photo_cw(path))
     }
@@ -302,6 +476,19 @@ impl Database {
         dispatch!(self, get_all_face_embeddings())
     }
 
+    /// Up to `limit` faces with a current embedding that are in no cluster
+    /// and not assigned to a person, as (face id, embedding)
+    pub fn get_unclassified_faces(&self, limit: usize) -> Result<Vec<(i64, Vec<f32>)>> {
+        dispatch!(self, get_unclassified_faces(limit))
+    }
+
+    /// Embeddings of every clustered face, as (cluster id, embedding)
+    pub fn get_cluster_member_embeddings(&self) -> Result<Vec<(i64, Vec<f32>)>> {
+        dispatch!(self, get_cluster_member_embeddings())
+    }
+
+    /// Faces with no embedding, or one from a model other than
+    /// `FACE_EMBEDDING_MODEL`
     pub fn get_faces_without_embeddings(&self, limit: usize) -> Result<Vec<(i64, i64, BoundingBox)>> {
         dispatch!(self, get_faces_without_embeddings(limit))
     }
@@ -342,6 +529,36 @@ impl Database {
         dispatch!(self, search_photos_by_person(person_id))
     }
 
+    /// Move every face of `source_id` to `target_id` and delete `source_id`,
+    /// for when one person was named twice. Returns how many faces moved.
+    pub fn merge_people(&self, source_id: i64, target_id: i64) -> Result<usize> {
+        if source_id == target_id {
+            anyhow::bail!("Cannot merge a person into themselves");
+        }
+        let faces = self.get_faces_for_person(source_id)?;
+        for face in &faces {
+            self.assign_face_to_person(face.face.id, target_id)?;
+        }
+        self.delete_person(source_id)?;
+        Ok(faces.len())
+    }
+
+    // ========================================================================
+    // Event operations
+    // ========================================================================
+
+    pub fn get_event_candidates(&self) -> Result<Vec<EventCandidate>> {
+        dispatch!(self, get_event_candidates())
+    }
+
+    pub fn get_events(&self) -> Result<Vec<Event>> {
+        dispatch!(self, get_events())
+    }
+
+    pub fn replace_events(&self, events: &[Event]) -> Result<()> {
+        dispatch!(self, replace_events(events))
+    }
+
     // ========================================================================
     // Embedding operations
     // ========================================================================
@@ -350,7 +567,6 @@ impl Database {
         dispatch!(self, store_embedding(photo_id, embedding, model_name))
     }
 
-    #[allow(dead_code)]
     pub fn get_embedding(&self, photo_id: i64) -> Result<Option<embeddings::EmbeddingRecord>> {
         dispatch!(self, get_embedding(photo_id))
     }
@@ -359,8 +575,38 @@ impl Database {
         dispatch!(self, get_all_embeddings())
     }
 
-    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize, min_similarity: f32) -> Result<Vec<SearchResult>> {
-        dispatch!(self, semantic_search(query_embedding, limit, min_similarity))
+    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize, min_similarity: f32, filter: &SearchFilter) -> Result<Vec<SearchResult>> {
+        dispatch!(self, semantic_search(query_embedding, limit, min_similarity, filter))
+    }
+
+    /// Boolean query search; `term_embeddings` holds an embedding for every term
+    pub fn semantic_search_query(
+        &self,
+        query: &SearchAst,
+        term_embeddings: &HashMap<String, Vec<f32>>,
+        limit: usize,
+        min_similarity: f32,
+        filter: &SearchFilter,
+    ) -> Result<Vec<SearchResult>> {
+        dispatch!(self, semantic_search_query(query, term_embeddings, limit, min_similarity, filter))
+    }
+
+    /// Save a search under a name, replacing any saved search with that name
+    pub fn save_search(&self, name: &str, query: &str, scope: &SearchScope) -> Result<i64> {
+        dispatch!(self, save_search(name, query, scope))
+    }
+
+    pub fn get_saved_searches(&self) -> Result<Vec<SavedSearch>> {
+        dispatch!(self, get_saved_searches())
+    }
+
+    pub fn delete_saved_search(&self, id: i64) -> Result<()> {
+        dispatch!(self, delete_saved_search(id))
+    }
+
+    /// Embedding search limited to photos in an album, tag, person or directory
+    pub fn semantic_search_scoped(&self, query_embedding: &[f32], limit: usize, min_similarity: f32, scope: SearchScope) -> Result<Vec<SearchResult>> {
+        dispatch!(self, semantic_search(query_embedding, limit, min_similarity, &SearchFilter::from(scope)))
     }
 
     #[allow(dead_code)]
@@ -372,6 +618,21 @@ impl Database {
         dispatch!(self, get_photos_without_embeddings_in_dir(directory, limit))
     }
 
+    /// Photos in a directory with no embedding or a CLIP embedding from another model
+    pub fn get_photos_with_stale_clip_embeddings_in_dir(&self, directory: &str, model_name: &str, limit: usize) -> Result<Vec<(i64, String)>> {
+        dispatch!(self, get_photos_with_stale_clip_embeddings_in_dir(directory, model_name, limit))
+    }
+
+    /// Model name of the most recently stored CLIP embedding
+    pub fn get_latest_clip_model(&self) -> Result<Option<String>> {
+        dispatch!(self, get_latest_clip_model())
+    }
+
+    /// Remove every embedding produced by a model, returning how many were removed
+    pub fn delete_embeddings_for_model(&self, model_name: &str) -> Result<usize> {
+        dispatch!(self, delete_embeddings_for_model(model_name))
+    }
+
     pub fn count_embeddings(&self) -> Result<i64> {
         dispatch!(self, count_embeddings())
     }
@@ -388,6 +649,30 @@ impl Database {
         dispatch!(self, find_perceptual_duplicates(threshold))
     }
 
+    /// Exact and perceptual duplicates with a copy under each of `staging`
+    /// and `library`. The staging copies are marked for deletion so the
+    /// library keeps one of each; other marks on these photos are cleared.
+    pub fn find_cross_dir_duplicates(&self, staging: &Path, library: &Path, threshold: u32) -> Result<Vec<SimilarityGroup>> {
+        let mut groups = self.find_exact_duplicates()?;
+        groups.extend(self.find_perceptual_duplicates(threshold)?);
+        let groups = restrict_to_directories(groups, staging, library);
+
+        for photo in groups.iter().flat_map(|g| &g.photos) {
+            if photo.marked_for_deletion {
+                self.mark_for_deletion(photo.id)?;
+            } else {
+                self.unmark_for_deletion(photo.id)?;
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Near-duplicate shots: photos in the same directory whose embeddings
+    /// have at least `threshold` cosine similarity
+    pub fn find_embedding_clusters(&self, threshold: f32) -> Result<Vec<SimilarityGroup>> {
+        dispatch!(self, find_embedding_clusters(threshold))
+    }
+
     pub fn mark_for_deletion(&self, photo_id: i64) -> Result<()> {
         dispatch!(self, mark_for_deletion(photo_id))
     }
@@ -520,15 +805,45 @@ impl Database {
         dispatch!(self, get_or_create_tag(name))
     }
 
-    #[allow(dead_code)]
+    /// All tags with their photo counts, most used first
+    pub fn get_tag_stats(&self) -> Result<Vec<TagStat>> {
+        dispatch!(self, get_tag_stats())
+    }
+
+    /// Delete a tag. Tags nested in it move up a level, which can change
+    /// what smart albums hold, so they are refreshed.
     pub fn delete_tag(&self, tag_id: i64) -> Result<()> {
-        dispatch!(self, delete_tag(tag_id))
+        dispatch!(self, delete_tag(tag_id))?;
+        self.refresh_smart_albums().map(|_| ())
     }
 
     pub fn rename_tag(&self, tag_id: i64, new_name: &str) -> Result<()> {
         dispatch!(self, rename_tag(tag_id, new_name))
     }
 
+    /// Nest a tag under `parent_id`, or make it top level with None. A tag
+    /// cannot be moved under itself or one of its own descendants.
+    pub fn set_tag_parent(&self, tag_id: i64, parent_id: Option<i64>) -> Result<()> {
+        if let Some(parent_id) = parent_id {
+            if self.get_tag_descendants(tag_id)?.contains(&parent_id) {
+                anyhow::bail!("A tag cannot be moved under itself or a tag nested in it");
+            }
+        }
+        dispatch!(self, set_tag_parent(tag_id, parent_id))?;
+        // Smart albums match photos carrying tags nested in their filter tags
+        self.refresh_smart_albums().map(|_| ())
+    }
+
+    /// The tag and every tag nested under it, at any depth
+    pub fn get_tag_descendants(&self, tag_id: i64) -> Result<Vec<i64>> {
+        dispatch!(self, get_tag_descendants(tag_id))
+    }
+
+    /// Paths of photos in a directory carrying the given tag
+    pub fn get_photos_in_dir_with_tag(&self, directory: &str, tag_id: i64) -> Result<Vec<String>> {
+        dispatch!(self, get_photos_in_dir_with_tag(directory, tag_id))
+    }
+
     pub fn get_photo_tags(&self, photo_id: i64) -> Result<Vec<UserTag>> {
         dispatch!(self, get_photo_tags(photo_id))
     }
@@ -554,7 +869,11 @@ impl Database {
     }
 
     pub fn create_album(&self, name: &str, description: Option<&str>, is_smart: bool) -> Result<i64> {
-        dispatch!(self, create_album(name, description, is_smart))
+        let album_id = dispatch!(self, create_album(name, description, is_smart))?;
+        if is_smart {
+            self.refresh_smart_album(album_id)?;
+        }
+        Ok(album_id)
     }
 
     pub fn delete_album(&self, album_id: i64) -> Result<()> {
@@ -577,14 +896,78 @@ impl Database {
         dispatch!(self, get_album_photo_paths(album_id))
     }
 
+    /// Move a photo of a manual album to `new_index` in the album order,
+    /// shifting the photos in between. Returns false when the photo is
+    /// not in the album or `new_index` is past the end.
+    pub fn reorder_album_photo(&self, album_id: i64, photo_id: i64, new_index: usize) -> Result<bool> {
+        let mut photo_ids = self.get_album_photos(album_id)?;
+        let Some(index) = photo_ids.iter().position(|&id| id == photo_id) else {
+            return Ok(false);
+        };
+        if new_index >= photo_ids.len() {
+            return Ok(false);
+        }
+        let id = photo_ids.remove(index);
+        photo_ids.insert(new_index, id);
+        dispatch!(self, set_album_order(album_id, &photo_ids))?;
+        Ok(true)
+    }
+
+    pub fn set_album_auto_sort(&self, album_id: i64, sort: AlbumSort) -> Result<()> {
+        dispatch!(self, set_album_auto_sort(album_id, sort))
+    }
+
+    pub fn set_album_geo_radius(&self, album_id: i64, geo: Option<&GeoRadius>) -> Result<()> {
+        dispatch!(self, set_album_geo_radius(album_id, geo))?;
+        self.refresh_smart_album(album_id)
+    }
+
+    /// Photos with GPS coordinates within `radius_km` of `lat`, `lon`
+    pub fn get_photos_in_radius(&self, lat: f64, lon: f64, radius_km: f64) -> Result<Vec<i64>> {
+        dispatch!(self, get_photos_in_radius(lat, lon, radius_km))
+    }
+
     pub fn set_album_filter_tags(&self, album_id: i64, tag_ids: &[i64]) -> Result<()> {
-        dispatch!(self, set_album_filter_tags(album_id, tag_ids))
+        dispatch!(self, set_album_filter_tags(album_id, tag_ids))?;
+        self.refresh_smart_album(album_id)
     }
 
     pub fn get_smart_album_photos(&self, album_id: i64) -> Result<Vec<i64>> {
         dispatch!(self, get_smart_album_photos(album_id))
     }
 
+    /// Re-evaluate every smart album's tag filter and store its members in
+    /// `smart_album_photos`, which album photo counts are read from.
+    /// Returns the number of smart albums refreshed.
+    pub fn refresh_smart_albums(&self) -> Result<usize> {
+        let smart: Vec<_> = self.get_all_albums()?.into_iter().filter(|a| a.is_smart).collect();
+        for album in &smart {
+            self.refresh_smart_album(album.id)?;
+        }
+        Ok(smart.len())
+    }
+
+    /// Store one smart album's current members, after its criteria change
+    fn refresh_smart_album(&self, album_id: i64) -> Result<()> {
+        let photo_ids = self.get_smart_album_photos(album_id)?;
+        dispatch!(self, replace_smart_album_photos(album_id, &photo_ids))
+    }
+
+    /// Paths of the photos in an album: the photos carrying every tag of a
+    /// smart album, or a manual album's photos in album order.
+    pub fn get_album_paths(&self, album: &albums::Album) -> Result<Vec<String>> {
+        if !album.is_smart {
+            return self.get_album_photo_paths(album.id);
+        }
+        let mut paths = Vec::new();
+        for photo_id in self.get_smart_album_photos(album.id)? {
+            if let Some(path) = self.get_photo_path(photo_id)? {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
     // ========================================================================
     // LLM queue operations
     // ========================================================================
@@ -602,6 +985,13 @@ impl Database {
         dispatch!(self, get_photos_without_description_in_dir(directory))
     }
 
+    /// Photos outside the trash lacking any of `fields` (see
+    /// `missing_fields::MISSING_FIELDS`), for a rescan to backfill
+    pub fn get_photos_with_missing_fields(&self, fields: &[&str]) -> Result<Vec<(i64, String)>> {
+        let condition = missing_fields::missing_fields_condition(fields)?;
+        dispatch!(self, get_photos_matching(&condition))
+    }
+
     #[allow(dead_code)]
     pub fn get_photo_description(&self, photo_id: i64) -> Result<Option<String>> {
         dispatch!(self, get_photo_description(photo_id))
@@ -629,6 +1019,7 @@ impl Database {
     pub fn insert_scanned_photo(
         &self,
         path: &str,
+        real_path: Option<&str>,
         filename: &str,
         directory: &str,
         size_bytes: i64,
@@ -653,7 +1044,7 @@ impl Database {
         exif_orientation: i32,
     ) -> Result<()> {
         dispatch!(self, insert_scanned_photo(
-            path, filename, directory, size_bytes, modified_at,
+            path, real_path, filename, directory, size_bytes, modified_at,
             width, height, format,
             camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at,
             gps_lat, gps_lon, all_exif,
@@ -666,6 +1057,7 @@ impl Database {
     pub fn update_scanned_photo(
         &self,
         path: &str,
+        real_path: Option<&str>,
         filename: &str,
         directory: &str,
         size_bytes: i64,
@@ -690,7 +1082,7 @@ impl Database {
         exif_orientation: i32,
     ) -> Result<()> {
         dispatch!(self, update_scanned_photo(
-            path, filename, directory, size_bytes, modified_at,
+            path, real_path, filename, directory, size_bytes, modified_at,
             width, height, format,
             camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at,
             gps_lat, gps_lon, all_exif,
@@ -707,6 +1099,312 @@ impl Database {
         dispatch!(self, get_photos_for_export())
     }
 
+    // ========================================================================
+    // Metadata import
+    // ========================================================================
+
+    /// Apply descriptions, tags and ratings from a CSV with `filename`,
+    /// `description`, `tags` and `rating` columns to photos in `directory`.
+    /// Only `filename` is required; empty cells leave a photo unchanged.
+    pub fn import_metadata_from_csv(&self, csv_path: &Path, directory: &str) -> Result<ImportReport> {
+        let rows = import::read_metadata_csv(csv_path)?;
+        let mut report = ImportReport::default();
+
+        for row in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(e) => {
+                    tracing::warn!(csv = %csv_path.display(), error = %e, "Skipping CSV row");
+                    report.errors += 1;
+                    continue;
+                }
+            };
+
+            let path = Path::new(directory).join(&row.filename);
+            let photo_id = match self.get_photo_metadata(&path)? {
+                Some(meta) => meta.id,
+                None => {
+                    report.unmatched += 1;
+                    continue;
+                }
+            };
+
+            match self.apply_imported_row(photo_id, &path, &row) {
+                Ok(()) => report.matched += 1,
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to import metadata");
+                    report.errors += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Apply PhotoPrism `.yml` sidecars found under `sidecar_dir` to the
+    /// photos they describe, matched by file name (see
+    /// [`photoprism::select_photos`]). Descriptions, keywords, ratings and
+    /// coordinates replace what clepho has; the capture time is only filled
+    /// in for photos without one.
+    pub fn import_photoprism_sidecars(&self, sidecar_dir: &Path) -> Result<ImportReport> {
+        let mut by_stem: HashMap<String, Vec<(i64, String)>> = HashMap::new();
+        for photo in self.get_photos_for_export()? {
+            if let Some(stem) = Path::new(&photo.filename).file_stem() {
+                by_stem.entry(stem.to_string_lossy().to_string()).or_default().push((photo.id, photo.path));
+            }
+        }
+
+        let mut report = ImportReport::default();
+        for sidecar_path in photoprism::find_sidecars(sidecar_dir) {
+            let sidecar = match photoprism::read_sidecar(&sidecar_path) {
+                Ok(sidecar) => sidecar,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Skipping PhotoPrism sidecar");
+                    report.errors += 1;
+                    continue;
+                }
+            };
+
+            let relative = sidecar_path.strip_prefix(sidecar_dir).unwrap_or(&sidecar_path);
+            let stem = relative.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let candidates = by_stem.get(&stem).map(Vec::as_slice).unwrap_or_default();
+            let paths: Vec<&str> = candidates.iter().map(|(_, path)| path.as_str()).collect();
+            let Some(matches) = photoprism::select_photos(relative, &paths) else {
+                if !paths.is_empty() {
+                    tracing::warn!(sidecar = %sidecar_path.display(), "Sidecar matches photos in several directories");
+                }
+                report.unmatched += 1;
+                continue;
+            };
+
+            let mut result = Ok(());
+            for (photo_id, path) in candidates.iter().filter(|(_, path)| matches.contains(&path.as_str())) {
+                result = result.and_then(|()| self.apply_photoprism_sidecar(*photo_id, Path::new(path), &sidecar));
+            }
+            match result {
+                Ok(()) => report.matched += 1,
+                Err(e) => {
+                    tracing::warn!(sidecar = %sidecar_path.display(), error = %e, "Failed to import metadata");
+                    report.errors += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn apply_photoprism_sidecar(&self, photo_id: i64, path: &Path, sidecar: &photoprism::PhotoPrismSidecar) -> Result<()> {
+        if let Some(description) = sidecar.description_text() {
+            self.save_description(path, description)?;
+        }
+        for name in sidecar.keyword_list() {
+            let tag = self.get_or_create_tag(&name)?;
+            self.add_tag_to_photo(photo_id, tag.id)?;
+        }
+        if let Some(rating) = sidecar.star_rating() {
+            self.set_photo_rating(photo_id, Some(rating))?;
+        }
+        if let Some((lat, lng)) = sidecar.gps() {
+            self.set_manual_gps(photo_id, lat, lng)?;
+        }
+        if let Some(taken_at) = sidecar.taken_at_local() {
+            self.set_taken_at_if_missing(photo_id, &taken_at)?;
+        }
+        Ok(())
+    }
+
+    fn apply_imported_row(&self, photo_id: i64, path: &Path, row: &import::MetadataRow) -> Result<()> {
+        if let Some(ref description) = row.description {
+            self.save_description(path, description)?;
+        }
+        for name in &row.tags {
+            let tag = self.get_or_create_tag(name)?;
+            self.add_tag_to_photo(photo_id, tag.id)?;
+        }
+        if let Some(rating) = row.rating {
+            self.set_photo_rating(photo_id, Some(rating))?;
+        }
+        Ok(())
+    }
+
+    /// Copy an album's photos, in album order, into `dest_dir` without any
+    /// subdirectories. With a `rename_pattern` such as
+    /// `{album}_{seq:04}.{ext}` the copies are numbered; a name that is
+    /// already taken moves on to the next number. Without one, photos keep
+    /// their file names and clashes get a `_1`, `_2`, ... suffix. Photos
+    /// missing from disk are skipped. Returns how many files were copied.
+    pub fn export_album_to_dir(&self, album_id: i64, dest_dir: &Path, rename_pattern: Option<&str>) -> Result<usize> {
+        let album = self
+            .get_all_albums()?
+            .into_iter()
+            .find(|a| a.id == album_id)
+            .ok_or_else(|| anyhow::anyhow!("No album with id {}", album_id))?;
+        std::fs::create_dir_all(dest_dir)
+            .with_context(|| format!("Failed to create {}", dest_dir.display()))?;
+
+        let numbered = rename_pattern.map(album_export::pattern_has_seq).transpose()?.unwrap_or(false);
+
+        let mut copied = 0;
+        let mut seq = 1;
+        for path in self.get_album_paths(&album)? {
+            let source = Path::new(&path);
+            if !source.exists() {
+                tracing::warn!(path = %path, "Album photo is missing, not exported");
+                continue;
+            }
+
+            let dest = match rename_pattern {
+                Some(pattern) if numbered => {
+                    let mut free = None;
+                    for _ in 0..album_export::MAX_NUMBERING_ATTEMPTS {
+                        let candidate = dest_dir.join(album_export::expand_pattern(pattern, &album.name, source, seq)?);
+                        seq += 1;
+                        if !candidate.exists() {
+                            free = Some(candidate);
+                            break;
+                        }
+                    }
+                    free.ok_or_else(|| anyhow::anyhow!("No free name for {} in {}", path, dest_dir.display()))?
+                }
+                Some(pattern) => album_export::unique_path(
+                    dest_dir.join(album_export::expand_pattern(pattern, &album.name, source, seq)?),
+                ),
+                None => album_export::unique_path(dest_dir.join(source.file_name().unwrap_or_default())),
+            };
+            if dest.parent() != Some(dest_dir) {
+                anyhow::bail!("Rename pattern must produce a plain file name, got {}", dest.display());
+            }
+
+            std::fs::copy(source, &dest)
+                .with_context(|| format!("Failed to copy {} to {}", path, dest.display()))?;
+            copied += 1;
+        }
+
+        Ok(copied)
+    }
+
+    /// Write each detected face as `{output_dir}/{person}/{face_id}.jpg`,
+    /// cropped with 20% padding and upscaled to at least `min_size` pixels.
+    /// With no `person_id`, every face is exported and unassigned faces go
+    /// into an `unknown` directory.
+    pub fn export_face_crops(&self, person_id: Option<i64>, output_dir: &Path, min_size: u32) -> Result<FaceCropReport> {
+        let mut faces: Vec<(String, FaceWithPhoto)> = Vec::new();
+        match person_id {
+            Some(id) => {
+                let person = self
+                    .get_person(id)?
+                    .ok_or_else(|| anyhow::anyhow!("No person with id {}", id))?;
+                let dir = face_crops::person_dir_name(&person.name);
+                faces.extend(self.get_faces_for_person(id)?.into_iter().map(|f| (dir.clone(), f)));
+            }
+            None => {
+                for person in self.get_all_people()? {
+                    let dir = face_crops::person_dir_name(&person.name);
+                    faces.extend(self.get_faces_for_person(person.id)?.into_iter().map(|f| (dir.clone(), f)));
+                }
+                let unknown = face_crops::UNKNOWN_PERSON_DIR;
+                faces.extend(self.get_unassigned_faces()?.into_iter().map(|f| (unknown.to_string(), f)));
+            }
+        }
+
+        // Group by photo so each source image is decoded once
+        faces.sort_by(|a, b| a.1.photo_path.cmp(&b.1.photo_path));
+
+        let mut report = FaceCropReport::default();
+        let mut current: Option<(String, Option<image::DynamicImage>)> = None;
+        for (dir, face) in faces {
+            if current.as_ref().map(|(path, _)| path != &face.photo_path).unwrap_or(true) {
+                let img = match image::open(&face.photo_path) {
+                    Ok(img) => Some(img),
+                    Err(e) => {
+                        tracing::warn!(path = %face.photo_path, error = %e, "Failed to open photo for face crop");
+                        None
+                    }
+                };
+                current = Some((face.photo_path.clone(), img));
+            }
+
+            let Some((_, Some(img))) = current.as_ref() else {
+                report.skipped += 1;
+                continue;
+            };
+            let path = output_dir.join(&dir).join(format!("{}.jpg", face.face.id));
+            match face_crops::save_face_crop(img, &face.face.bbox, min_size, &path) {
+                Ok(()) => report.exported += 1,
+                Err(e) => {
+                    tracing::warn!(face_id = face.face.id, error = %e, "Failed to save face crop");
+                    report.skipped += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Serialise every person with their faces, plus the unnamed face
+    /// clusters, as JSON. Faces are identified by photo path and bounding
+    /// box; with `include_embeddings` their embeddings are added as base64.
+    pub fn export_people_json(&self, include_embeddings: bool) -> Result<String> {
+        let mut people = Vec::new();
+        for person in self.get_all_people()? {
+            let faces = self.get_faces_for_person(person.id)?;
+            people.push(people_export::ExportedPerson::new(&person, faces, include_embeddings));
+        }
+        let export = people_export::PeopleExport {
+            version: people_export::PEOPLE_EXPORT_VERSION,
+            people,
+            clusters: self.get_all_face_clusters()?.iter().map(Into::into).collect(),
+        };
+        Ok(serde_json::to_string_pretty(&export)?)
+    }
+
+    /// Recreate people from `export_people_json` output. Faces are matched
+    /// to already detected faces with the same bounding box, or stored as
+    /// new faces, and assigned to their person. Photos must already be
+    /// scanned; faces in unknown photos are counted and skipped.
+    pub fn import_people_json(&self, json: &str) -> Result<PeopleImportReport> {
+        let export: people_export::PeopleExport =
+            serde_json::from_str(json).context("Not a people export")?;
+        if export.version > people_export::PEOPLE_EXPORT_VERSION {
+            anyhow::bail!("People export version {} is newer than this clepho supports", export.version);
+        }
+
+        let mut report = PeopleImportReport::default();
+        for person in &export.people {
+            let person_id = self.find_or_create_person(&person.name)?;
+            report.people += 1;
+
+            for face in &person.faces {
+                let photo_id = match self.get_photo_metadata(Path::new(&face.photo_path))? {
+                    Some(meta) => meta.id,
+                    None => {
+                        report.missing_photos += 1;
+                        continue;
+                    }
+                };
+
+                let bbox = face.bbox();
+                let embedding = face.decode_embedding()?;
+                let existing = self.get_faces_for_photo(photo_id)?.into_iter().find(|f| {
+                    (f.bbox.x, f.bbox.y, f.bbox.width, f.bbox.height) == (bbox.x, bbox.y, bbox.width, bbox.height)
+                });
+                let face_id = match existing {
+                    Some(existing) => {
+                        if let (None, Some(embedding)) = (&existing.embedding, &embedding) {
+                            self.update_face_embedding(existing.id, embedding)?;
+                        }
+                        existing.id
+                    }
+                    None => self.store_face(photo_id, &bbox, embedding.as_deref(), face.confidence)?,
+                };
+                self.assign_face_to_person(face_id, person_id)?;
+                report.faces += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
     // ========================================================================
     // Daemon operations
     // ========================================================================
@@ -735,6 +1433,16 @@ impl Database {
         dispatch!(self, count_photos_without_faces_in_dir(directory))
     }
 
+    pub fn count_photos_in_dir_recursive(&self, directory: &str) -> Result<DirectoryPhotoStats> {
+        dispatch!(self, count_photos_in_dir_recursive(directory))
+    }
+
+    /// Every directory with photos in it, not counting trashed ones, in
+    /// path order
+    pub fn list_directories(&self) -> Result<Vec<DirectorySummary>> {
+        dispatch!(self, list_directories())
+    }
+
     // ========================================================================
     // Directory prompt operations
     // ========================================================================
@@ -743,7 +1451,77 @@ impl Database {
         dispatch!(self, get_directory_prompt(directory))
     }
 
+    /// Every directory with a custom prompt, as `(directory, prompt)` pairs
+    /// sorted by directory
+    pub fn get_directory_prompts(&self) -> Result<Vec<(String, String)>> {
+        dispatch!(self, get_directory_prompts())
+    }
+
     pub fn set_directory_prompt(&self, directory: &str, prompt: &str) -> Result<()> {
         dispatch!(self, set_directory_prompt(directory, prompt))
     }
+
+    // ========================================================================
+    // Slideshow preference operations
+    // ========================================================================
+
+    /// The slideshow interval last chosen in `directory`, in seconds
+    pub fn get_slideshow_interval(&self, directory: &str) -> Result<Option<f64>> {
+        dispatch!(self, get_slideshow_interval(directory))
+    }
+
+    pub fn set_slideshow_interval(&self, directory: &str, interval_secs: f64) -> Result<()> {
+        dispatch!(self, set_slideshow_interval(directory, interval_secs))
+    }
+
+    // ========================================================================
+    // App state operations
+    // ========================================================================
+
+    /// Read a value from the `app_state` key/value table
+    pub fn get_app_state(&self, key: &str) -> Result<Option<String>> {
+        dispatch!(self, get_app_state(key))
+    }
+
+    /// Insert or overwrite a value in the `app_state` key/value table
+    pub fn set_app_state(&self, key: &str, value: &str) -> Result<()> {
+        dispatch!(self, set_app_state(key, value))
+    }
+
+    // ========================================================================
+    // Location operations
+    // ========================================================================
+
+    /// Photos with coordinates grouped into cells `precision` degrees wide,
+    /// largest first, each named from the place name cache when possible
+    pub fn get_gps_clusters(&self, precision: f32) -> Result<Vec<GpsCluster>> {
+        let photos = dispatch!(self, get_gps_photos())?;
+        let mut clusters = locations::cluster_by_grid(photos, precision);
+        for cluster in &mut clusters {
+            let (lat_key, lon_key) = locations::place_key(cluster.latitude, cluster.longitude);
+            cluster.place = dispatch!(self, get_place_name(lat_key, lon_key))?;
+        }
+        Ok(clusters)
+    }
+
+    /// Cache a reverse geocoded name for the place around `latitude, longitude`
+    pub fn save_place_name(&self, latitude: f64, longitude: f64, name: &str) -> Result<()> {
+        let (lat_key, lon_key) = locations::place_key(latitude, longitude);
+        dispatch!(self, save_place_name(lat_key, lon_key, name))
+    }
+}
+
+/// A fresh, initialized SQLite database in a temporary directory, which
+/// must be kept alive for as long as the database is used. Built for the
+/// binary's tests too through the `test-support` feature.
+#[cfg(any(test, feature = "test-support"))]
+pub fn test_db() -> (tempfile::TempDir, Database) {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Database::open(&crate::config::DatabaseConfig {
+        sqlite_path: dir.path().join("photos.db"),
+        ..Default::default()
+    })
+    .unwrap();
+    db.initialize().unwrap();
+    (dir, db)
 }
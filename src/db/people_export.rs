@@ -0,0 +1,171 @@
+//! Portable JSON dump of people and their faces, for moving face
+//! recognition results to another installation.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::faces::{bytes_to_embedding, embedding_to_bytes, BoundingBox, FaceCluster, FaceWithPhoto, Person};
+
+/// Bumped when the layout changes incompatibly
+pub const PEOPLE_EXPORT_VERSION: u32 = 1;
+
+/// Everything `export_people_json` writes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeopleExport {
+    pub version: u32,
+    pub people: Vec<ExportedPerson>,
+    /// Unnamed clusters, for reference; they are rebuilt by clustering
+    /// rather than imported
+    #[serde(default)]
+    pub clusters: Vec<ExportedCluster>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedPerson {
+    pub name: String,
+    pub faces: Vec<ExportedFace>,
+}
+
+/// A face, identified by its photo's path and bounding box since database
+/// IDs differ between installations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedFace {
+    pub photo_path: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// Little-endian f32 values, base64 encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedCluster {
+    pub auto_name: String,
+    pub face_count: i64,
+}
+
+/// Outcome of a people import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeopleImportReport {
+    /// People found or created
+    pub people: usize,
+    /// Faces assigned to their person
+    pub faces: usize,
+    /// Faces whose photo is not in the database
+    pub missing_photos: usize,
+}
+
+impl ExportedPerson {
+    pub fn new(person: &Person, faces: Vec<FaceWithPhoto>, include_embeddings: bool) -> Self {
+        Self {
+            name: person.name.clone(),
+            faces: faces.into_iter().map(|f| ExportedFace::new(f, include_embeddings)).collect(),
+        }
+    }
+}
+
+impl ExportedFace {
+    fn new(face: FaceWithPhoto, include_embeddings: bool) -> Self {
+        let bbox = face.face.bbox;
+        Self {
+            photo_path: face.photo_path,
+            x: bbox.x,
+            y: bbox.y,
+            width: bbox.width,
+            height: bbox.height,
+            confidence: face.face.confidence,
+            embedding: face
+                .face
+                .embedding
+                .filter(|_| include_embeddings)
+                .map(|e| BASE64.encode(embedding_to_bytes(&e))),
+        }
+    }
+
+    pub fn bbox(&self) -> BoundingBox {
+        BoundingBox { x: self.x, y: self.y, width: self.width, height: self.height }
+    }
+
+    /// The decoded embedding, if one was exported
+    pub fn decode_embedding(&self) -> Result<Option<Vec<f32>>> {
+        self.embedding
+            .as_deref()
+            .map(|encoded| {
+                let bytes = BASE64.decode(encoded).context("Face embedding is not valid base64")?;
+                Ok(bytes_to_embedding(&bytes))
+            })
+            .transpose()
+    }
+}
+
+impl From<&FaceCluster> for ExportedCluster {
+    fn from(cluster: &FaceCluster) -> Self {
+        Self { auto_name: cluster.auto_name.clone(), face_count: cluster.face_count }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Face;
+
+    #[test]
+    fn test_embedding_round_trips_through_base64() {
+        let face = FaceWithPhoto {
+            face: Face {
+                id: 7,
+                photo_id: 3,
+                bbox: BoundingBox { x: 10, y: 20, width: 30, height: 40 },
+                embedding: Some(vec![0.5, -1.25, 3.0]),
+                person_id: Some(1),
+                confidence: Some(0.9),
+            },
+            photo_path: "/photos/a.jpg".to_string(),
+            photo_filename: "a.jpg".to_string(),
+        };
+
+        let without = ExportedFace::new(face.clone(), false);
+        assert_eq!(without.embedding, None);
+        assert_eq!(without.decode_embedding().unwrap(), None);
+
+        let with = ExportedFace::new(face, true);
+        assert_eq!(with.decode_embedding().unwrap(), Some(vec![0.5, -1.25, 3.0]));
+        assert_eq!(with.bbox().width, 30);
+    }
+
+    #[test]
+    fn test_people_survive_export_and_import() {
+        let open = || {
+            let (dir, db) = crate::db::test_db();
+            db.insert_basic_photo("/photos/a.jpg", "a.jpg", "/photos", 100).unwrap();
+            (dir, db)
+        };
+        let photo_id = |db: &crate::db::Database| {
+            db.get_photo_metadata(std::path::Path::new("/photos/a.jpg")).unwrap().unwrap().id
+        };
+
+        let (_source_dir, source) = open();
+        let bbox = BoundingBox { x: 1, y: 2, width: 3, height: 4 };
+        let face_id = source.store_face(photo_id(&source), &bbox, Some(&[1.0, 2.0]), Some(0.8)).unwrap();
+        let alice = source.create_person("Alice").unwrap();
+        source.assign_face_to_person(face_id, alice).unwrap();
+        let json = source.export_people_json(true).unwrap();
+
+        let (_target_dir, target) = open();
+        // Detected again on the new machine, without an embedding yet
+        target.store_face(photo_id(&target), &bbox, None, None).unwrap();
+        let report = target.import_people_json(&json).unwrap();
+        assert_eq!(report, PeopleImportReport { people: 1, faces: 1, missing_photos: 0 });
+
+        let person = target.find_person_by_name("Alice").unwrap().unwrap();
+        let faces = target.get_faces_for_person(person.id).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].face.embedding, Some(vec![1.0, 2.0]));
+        assert_eq!(target.count_faces().unwrap(), 1);
+    }
+}
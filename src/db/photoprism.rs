@@ -0,0 +1,182 @@
+//! PhotoPrism YAML sidecars, for moving a library between PhotoPrism and
+//! clepho.
+//!
+//! PhotoPrism keeps one `.yml` file per photo under `storage/sidecar`,
+//! mirroring the originals folder: `2019/08/IMG_1234.jpg` is described by
+//! `2019/08/IMG_1234.yml`. Only the fields clepho has a place for are read;
+//! everything else in the file is ignored.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// The fields of a PhotoPrism sidecar that clepho reads and writes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PhotoPrismSidecar {
+    /// RFC 3339 capture time, e.g. `2019-08-12T11:29:29Z`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taken_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Comma-separated keywords; PhotoPrism itself writes them under `Details`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lng: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<PhotoPrismDetails>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PhotoPrismDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+}
+
+impl PhotoPrismSidecar {
+    /// The description, or the title for photos that only have one
+    pub fn description_text(&self) -> Option<&str> {
+        [&self.description, &self.title]
+            .into_iter()
+            .filter_map(|v| v.as_deref().map(str::trim))
+            .find(|v| !v.is_empty())
+    }
+
+    /// Keywords from both the top level and `Details`, without repeats
+    pub fn keyword_list(&self) -> Vec<String> {
+        let details = self.details.as_ref().and_then(|d| d.keywords.as_deref());
+        let mut keywords: Vec<String> = Vec::new();
+        for keyword in [self.keywords.as_deref(), details]
+            .into_iter()
+            .flatten()
+            .flat_map(|k| k.split(','))
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+        {
+            if !keywords.iter().any(|k| k.eq_ignore_ascii_case(keyword)) {
+                keywords.push(keyword.to_string());
+            }
+        }
+        keywords
+    }
+
+    /// Coordinates, unless PhotoPrism left them at 0,0 for "unknown"
+    pub fn gps(&self) -> Option<(f64, f64)> {
+        match (self.lat, self.lng) {
+            (Some(lat), Some(lng)) if lat != 0.0 || lng != 0.0 => Some((lat, lng)),
+            _ => None,
+        }
+    }
+
+    /// The capture time as clepho stores it ("YYYY-MM-DD HH:MM:SS"), as a
+    /// clock time in whatever offset the sidecar gives
+    pub fn taken_at_local(&self) -> Option<String> {
+        let taken_at = self.taken_at.as_deref()?.trim();
+        let parsed = chrono::DateTime::parse_from_rfc3339(taken_at)
+            .map(|dt| dt.naive_local())
+            .or_else(|_| chrono::NaiveDateTime::parse_from_str(taken_at, "%Y-%m-%dT%H:%M:%S"))
+            .or_else(|_| chrono::NaiveDateTime::parse_from_str(taken_at, "%Y-%m-%d %H:%M:%S"))
+            .ok()?;
+        Some(parsed.format("%Y-%m-%d %H:%M:%S").to_string())
+    }
+
+    /// A 0-5 star rating; PhotoPrism's other values are ignored
+    pub fn star_rating(&self) -> Option<i32> {
+        self.rating.filter(|r| (0..=5).contains(r))
+    }
+}
+
+pub fn read_sidecar(path: &Path) -> Result<PhotoPrismSidecar> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_yaml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Every `.yml` / `.yaml` file under `dir`, sorted
+pub fn find_sidecars(dir: &Path) -> Vec<PathBuf> {
+    let mut sidecars: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|p| {
+            p.extension()
+                .map(|ext| matches!(ext.to_string_lossy().to_lowercase().as_str(), "yml" | "yaml"))
+                .unwrap_or(false)
+        })
+        .collect();
+    sidecars.sort();
+    sidecars
+}
+
+/// Which of the photos sharing a sidecar's file stem it describes.
+/// `relative` is the sidecar's path under the sidecar directory; photos
+/// whose directory ends with the same folders are preferred. All photos in
+/// the chosen directory match, so a RAW+JPEG pair both get the metadata.
+/// Returns None when the candidates are in several directories and the
+/// sidecar's folders do not pick one.
+pub fn select_photos<'a>(relative: &Path, candidates: &[&'a str]) -> Option<Vec<&'a str>> {
+    let folders = relative.parent().unwrap_or(Path::new(""));
+    let in_folder: Vec<&str> = candidates
+        .iter()
+        .copied()
+        .filter(|c| Path::new(c).parent().is_some_and(|p| p.ends_with(folders)))
+        .collect();
+    let matches = if in_folder.is_empty() { candidates.to_vec() } else { in_folder };
+
+    let mut directories = matches.iter().map(|m| Path::new(m).parent());
+    let first = directories.next()?;
+    if directories.all(|d| d == first) {
+        Some(matches)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_photoprism_sidecar() {
+        let yaml = "TakenAt: 2019-08-12T11:29:29+02:00\n\
+                    TakenSrc: meta\n\
+                    UID: pqxb2ak1x3n2v8ed\n\
+                    Title: Cat / Berlin / 2019\n\
+                    Description: ''\n\
+                    Lat: 52.52\n\
+                    Lng: 13\n\
+                    Rating: 4\n\
+                    Details:\n  Keywords: cat, Berlin, animal\n  Notes: indoor\n";
+        let sidecar: PhotoPrismSidecar = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(sidecar.description_text(), Some("Cat / Berlin / 2019"));
+        assert_eq!(sidecar.keyword_list(), vec!["cat", "Berlin", "animal"]);
+        assert_eq!(sidecar.gps(), Some((52.52, 13.0)));
+        assert_eq!(sidecar.taken_at_local().as_deref(), Some("2019-08-12 11:29:29"));
+        assert_eq!(sidecar.star_rating(), Some(4));
+
+        let empty: PhotoPrismSidecar = serde_yaml::from_str("Lat: 0\nLng: 0\nRating: -1\n").unwrap();
+        assert_eq!((empty.gps(), empty.star_rating(), empty.description_text()), (None, None, None));
+    }
+
+    #[test]
+    fn test_select_photos() {
+        let candidates = ["/photos/2019/08/IMG_1.jpg", "/photos/2019/08/IMG_1.cr2", "/photos/2020/01/IMG_1.jpg"];
+        assert_eq!(
+            select_photos(Path::new("2019/08/IMG_1.yml"), &candidates),
+            Some(vec!["/photos/2019/08/IMG_1.jpg", "/photos/2019/08/IMG_1.cr2"])
+        );
+        assert_eq!(select_photos(Path::new("IMG_1.yml"), &candidates), None);
+        assert_eq!(select_photos(Path::new("other/IMG_1.yml"), &candidates[2..]), Some(vec![candidates[2]]));
+        assert_eq!(select_photos(Path::new("IMG_1.yml"), &[]), None);
+    }
+}
@@ -4,19 +4,23 @@ use anyhow::Result;
 use postgres::NoTls;
 use r2d2::Pool;
 use r2d2_postgres::PostgresConnectionManager;
+use std::collections::HashMap;
 use std::path::Path;
 
-use super::{PhotoMetadata, ExportedPhotoRow, exif_orientation_to_degrees, read_exif_rotation_from_file};
-use super::embeddings::{SearchResult, EmbeddingRecord, embedding_to_bytes, bytes_to_embedding, cosine_similarity};
+use super::{DirectoryPhotoStats, DirectorySummary, PhotoMetadata, PhotoTimestamps, ExportedPhotoRow, exif_orientation_to_degrees, read_exif_rotation_from_file};
+use super::search_query::{SearchAst, parse_search_query};
+use super::embeddings::{SavedSearch, SearchFilter, SearchResult, SearchScope, EmbeddingRecord, embedding_to_bytes, bytes_to_embedding, cosine_similarity};
 use super::faces::{
-    BoundingBox, Face, FaceCluster, FaceWithPhoto, Person,
+    BoundingBox, Face, FaceCluster, FaceWithPhoto, Person, FACE_EMBEDDING_MODEL,
     embedding_to_bytes as face_embedding_to_bytes, bytes_to_embedding as face_bytes_to_embedding,
 };
-use super::similarity::{PhotoRecord, SimilarityGroup};
+use super::similarity::{EmbeddedPhoto, PhotoRecord, SimilarityGroup, group_by_embedding};
 use super::trash::TrashedPhoto;
 use super::schedule::{ScheduledTask, ScheduledTaskType, ScheduleStatus};
-use super::albums::{UserTag, Album};
-use super::postgres_schema::POSTGRES_SCHEMA;
+use super::albums::{smart_album_members, AlbumSort, GeoRadius, TagStat, UserTag, Album};
+use super::events::{Event, EventCandidate};
+use super::locations::{bounding_box, haversine_km};
+use super::postgres_schema::{POSTGRES_SCHEMA, POSTGRES_TRIGRAM_INDEX};
 
 pub struct PgDb {
     pool: Pool<PostgresConnectionManager<NoTls>>,
@@ -77,6 +81,9 @@ impl PgDb {
     pub fn initialize(&self) -> Result<()> {
         let mut client = self.pool.get()?;
         client.batch_execute(POSTGRES_SCHEMA)?;
+        if let Err(e) = client.batch_execute(POSTGRES_TRIGRAM_INDEX) {
+            tracing::warn!(error = %e, "Could not create the pg_trgm filename index; fuzzy filename search will be slower");
+        }
         Ok(())
     }
 
@@ -113,25 +120,83 @@ impl PgDb {
         let new_path_str = new_path.to_string_lossy();
         let mut client = self.pool.get()?;
         client.execute(
-            "UPDATE photos SET path = $1 WHERE path = $2",
+            "UPDATE photos SET path = $1, real_path = NULL WHERE path = $2",
             &[&new_path_str.as_ref(), &old_path_str.as_ref()],
         )?;
         Ok(())
     }
 
-    pub fn get_photos_mtime_in_dir(&self, directory: &str) -> Result<Vec<(String, Option<String>)>> {
+    pub fn get_photos_mtime_in_dir(&self, directory: &str) -> Result<Vec<PhotoTimestamps>> {
         let mut client = self.pool.get()?;
         let rows = client.query(
-            "SELECT path, modified_at FROM photos WHERE directory = $1",
+            "SELECT path, modified_at, taken_at FROM photos WHERE directory = $1",
             &[&directory],
         )?;
         let results = rows
             .iter()
-            .map(|row| (row.get(0), row.get(1)))
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
             .collect();
         Ok(results)
     }
 
+    pub fn get_photos_in_dir_date_range(&self, directory: &str, start: &str, end: &str) -> Result<Vec<String>> {
+        let mut client = self.pool.get()?;
+        // taken_at is "YYYY-MM-DD HH:MM:SS"; compare on the date part only
+        let rows = client.query(
+            "SELECT path FROM photos WHERE directory = $1 AND substr(taken_at, 1, 10) BETWEEN $2 AND $3",
+            &[&directory, &start, &end],
+        )?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    pub fn get_photo_counts_by_date(&self, year: &str) -> Result<Vec<(String, i64)>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT substr(taken_at, 1, 10) AS day, COUNT(*)
+            FROM photos
+            WHERE substr(taken_at, 1, 4) = $1 AND trashed_at IS NULL
+            GROUP BY day
+            "#,
+            &[&year],
+        )?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    pub fn get_photos_taken_on(&self, date: &str) -> Result<Vec<String>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            "SELECT path FROM photos WHERE substr(taken_at, 1, 10) = $1 AND trashed_at IS NULL ORDER BY taken_at, path",
+            &[&date],
+        )?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    pub fn get_camera_models_in_dir(&self, directory: &str) -> Result<Vec<(String, i64)>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT TRIM(COALESCE(camera_make, '') || ' ' || COALESCE(camera_model, '')) AS camera, COUNT(*)
+            FROM photos
+            WHERE directory = $1 AND trashed_at IS NULL
+            GROUP BY camera
+            HAVING TRIM(COALESCE(camera_make, '') || ' ' || COALESCE(camera_model, '')) <> ''
+            ORDER BY camera
+            "#,
+            &[&directory],
+        )?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    pub fn get_photos_in_dir_by_camera(&self, directory: &str, camera: &str) -> Result<Vec<String>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            "SELECT path FROM photos WHERE directory = $1 AND TRIM(COALESCE(camera_make, '') || ' ' || COALESCE(camera_model, '')) = $2 AND trashed_at IS NULL",
+            &[&directory, &camera],
+        )?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
     pub fn get_photo_metadata(&self, path: &Path) -> Result<Option<PhotoMetadata>> {
         let path_str = path.to_string_lossy();
         let mut client = self.pool.get()?;
@@ -143,7 +208,8 @@ impl PgDb {
                    gps_latitude, gps_longitude,
                    modified_at, scanned_at,
                    description, tags,
-                   sha256_hash, perceptual_hash
+                   sha256_hash, perceptual_hash, gps_source,
+                   rating, color_label, exif_orientation
             FROM photos
             WHERE path = $1
             "#,
@@ -174,12 +240,16 @@ impl PgDb {
                     taken_at: row.get(15),
                     gps_latitude: row.get(16),
                     gps_longitude: row.get(17),
+                    gps_source: row.get(24),
+                    exif_orientation: row.get(27),
                     modified_at: row.get(18),
                     scanned_at: row.get(19),
                     description: row.get(20),
                     tags: row.get(21),
                     sha256_hash: row.get(22),
                     perceptual_hash: row.get(23),
+                    rating: row.get(25),
+                    color_label: row.get(26),
                     face_count: 0,
                     people_names: Vec::new(),
                 };
@@ -209,39 +279,50 @@ impl PgDb {
         }
     }
 
-    pub fn semantic_search_by_text(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        let query_lower = query.to_lowercase();
-        let query_words: Vec<&str> = query_lower.split_whitespace().collect();
+    /// Full-text search over descriptions using the GIN-indexed
+    /// `description_tsvector` column, ranked by `ts_rank`. Similarity is
+    /// the rank relative to the best match.
+    pub fn semantic_search_by_text(&self, query: &str, limit: usize, filter: &SearchFilter) -> Result<Vec<SearchResult>> {
+        let query = parse_search_query(query);
+        let (with, conditions, mut params) = filter_sql("id", filter);
+        let conditions: String = conditions.iter().map(|c| format!(" AND {}", c)).collect();
+        let tsquery = tsquery_sql(&query, &mut params);
+        params.push(Box::new(limit as i64));
+        let sql = format!(
+            "{} SELECT id, path, filename, description, ts_rank(description_tsvector, q)
+             FROM photos, (SELECT {} AS q) AS query
+             WHERE description_tsvector @@ q{}
+             ORDER BY 5 DESC LIMIT ${}",
+            with,
+            tsquery,
+            conditions,
+            params.len()
+        );
+        let params: Vec<&(dyn postgres::types::ToSql + Sync)> = params.iter().map(|p| p.as_ref()).collect();
         let mut client = self.pool.get()?;
-        let rows = client.query(
-            "SELECT id, path, filename, description FROM photos WHERE description IS NOT NULL",
-            &[],
-        )?;
-        let mut results: Vec<SearchResult> = rows
+        let rows = client.query(&sql, &params)?;
+
+        let best = rows.iter().map(|row| row.get::<_, f32>(4)).fold(0.0, f32::max);
+        Ok(rows
             .iter()
-            .filter_map(|row| {
-                let id: i64 = row.get(0);
-                let path: String = row.get(1);
-                let filename: String = row.get(2);
-                let description: String = row.get(3);
-                let desc_lower = description.to_lowercase();
-                let mut score = 0.0f32;
-                for word in &query_words {
-                    if desc_lower.contains(word) {
-                        score += 1.0;
-                    }
-                }
-                if score > 0.0 {
-                    let similarity = score / query_words.len() as f32;
-                    Some(SearchResult { photo_id: id, path, filename, similarity, description: Some(description) })
-                } else {
-                    None
+            .map(|row| {
+                let rank: f32 = row.get(4);
+                SearchResult {
+                    photo_id: row.get(0),
+                    path: row.get(1),
+                    filename: row.get(2),
+                    // Negated queries rank every match at zero
+                    similarity: if best > 0.0 { rank / best } else { 1.0 },
+                    description: row.get(3),
                 }
             })
-            .collect();
-        results.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
-        results.truncate(limit);
-        Ok(results)
+            .collect())
+    }
+
+    pub fn get_all_exif(&self, path: &str) -> Result<Option<String>> {
+        let mut client = self.pool.get()?;
+        let row = client.query_opt("SELECT all_exif FROM photos WHERE path = $1", &[&path])?;
+        Ok(row.and_then(|row| row.get(0)))
     }
 
     pub fn get_photo_rotation(&self, path: &Path) -> Result<i32> {
@@ -273,6 +354,42 @@ impl PgDb {
         Ok(())
     }
 
+    pub fn set_photo_rating(&self, photo_id: i64, rating: Option<i32>) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE photos SET rating = $1 WHERE id = $2",
+            &[&rating, &photo_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_photo_color_label(&self, photo_id: i64, label: Option<&str>) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE photos SET color_label = $1 WHERE id = $2",
+            &[&label, &photo_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_manual_gps(&self, photo_id: i64, lat: f64, lon: f64) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE photos SET gps_latitude = $1, gps_longitude = $2, gps_source = 'manual' WHERE id = $3",
+            &[&lat, &lon, &photo_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_taken_at_if_missing(&self, photo_id: i64, taken_at: &str) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE photos SET taken_at = $1 WHERE id = $2 AND taken_at IS NULL",
+            &[&taken_at, &photo_id],
+        )?;
+        Ok(())
+    }
+
     pub fn rotate_photo_cw(&self, path: &Path) -> Result<i32> {
         self.ensure_photo_exists(path)?;
         let path_str = path.to_string_lossy();
@@ -444,16 +561,17 @@ impl PgDb {
     ) -> Result<i64> {
         let embedding_bytes = embedding.map(face_embedding_to_bytes);
         let embedding_dim = embedding.map(|e| e.len() as i32);
+        let embedding_model = embedding.map(|_| FACE_EMBEDDING_MODEL);
         let confidence_f64 = confidence.map(|c| c as f64);
         let mut client = self.pool.get()?;
         let row = client.query_one(
             r#"
-            INSERT INTO faces (photo_id, bbox_x, bbox_y, bbox_w, bbox_h, embedding, embedding_dim, confidence)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO faces (photo_id, bbox_x, bbox_y, bbox_w, bbox_h, embedding, embedding_dim, embedding_model, confidence)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             RETURNING id
             "#,
             &[&photo_id, &bbox.x, &bbox.y, &bbox.width, &bbox.height,
-              &embedding_bytes.as_deref(), &embedding_dim, &confidence_f64],
+              &embedding_bytes.as_deref(), &embedding_dim, &embedding_model, &confidence_f64],
         )?;
         Ok(row.get(0))
     }
@@ -654,6 +772,51 @@ impl PgDb {
         Ok(results)
     }
 
+    pub fn get_unclassified_faces(&self, limit: usize) -> Result<Vec<(i64, Vec<f32>)>> {
+        let limit_i64 = limit as i64;
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT f.id, f.embedding
+            FROM faces f
+            WHERE f.embedding IS NOT NULL AND f.embedding_model = $1 AND f.person_id IS NULL
+              AND NOT EXISTS (SELECT 1 FROM face_cluster_members m WHERE m.face_id = f.id)
+            ORDER BY f.id
+            LIMIT $2
+            "#,
+            &[&FACE_EMBEDDING_MODEL, &limit_i64],
+        )?;
+        let results = rows
+            .iter()
+            .map(|row| {
+                let bytes: Vec<u8> = row.get(1);
+                (row.get(0), face_bytes_to_embedding(&bytes))
+            })
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_cluster_member_embeddings(&self) -> Result<Vec<(i64, Vec<f32>)>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT m.cluster_id, f.embedding
+            FROM face_cluster_members m
+            JOIN faces f ON f.id = m.face_id
+            WHERE f.embedding IS NOT NULL
+            "#,
+            &[],
+        )?;
+        let results = rows
+            .iter()
+            .map(|row| {
+                let bytes: Vec<u8> = row.get(1);
+                (row.get(0), face_bytes_to_embedding(&bytes))
+            })
+            .collect();
+        Ok(results)
+    }
+
     pub fn get_faces_without_embeddings(&self, limit: usize) -> Result<Vec<(i64, i64, BoundingBox)>> {
         let limit_i64 = limit as i64;
         let mut client = self.pool.get()?;
@@ -661,10 +824,10 @@ impl PgDb {
             r#"
             SELECT id, photo_id, bbox_x, bbox_y, bbox_w, bbox_h
             FROM faces
-            WHERE embedding IS NULL
-            LIMIT $1
+            WHERE embedding IS NULL OR embedding_model IS DISTINCT FROM $1
+            LIMIT $2
             "#,
-            &[&limit_i64],
+            &[&FACE_EMBEDDING_MODEL, &limit_i64],
         )?;
         let results = rows
             .iter()
@@ -689,8 +852,8 @@ impl PgDb {
         let embedding_dim = embedding.len() as i32;
         let mut client = self.pool.get()?;
         client.execute(
-            "UPDATE faces SET embedding = $1, embedding_dim = $2 WHERE id = $3",
-            &[&embedding_bytes, &embedding_dim, &face_id],
+            "UPDATE faces SET embedding = $1, embedding_dim = $2, embedding_model = $3 WHERE id = $4",
+            &[&embedding_bytes, &embedding_dim, &FACE_EMBEDDING_MODEL, &face_id],
         )?;
         Ok(())
     }
@@ -698,8 +861,8 @@ impl PgDb {
     pub fn count_faces_without_embeddings(&self) -> Result<i64> {
         let mut client = self.pool.get()?;
         let row = client.query_one(
-            "SELECT COUNT(*) FROM faces WHERE embedding IS NULL",
-            &[],
+            "SELECT COUNT(*) FROM faces WHERE embedding IS NULL OR embedding_model IS DISTINCT FROM $1",
+            &[&FACE_EMBEDDING_MODEL],
         )?;
         Ok(row.get(0))
     }
@@ -863,14 +1026,178 @@ impl PgDb {
         Ok(records)
     }
 
-    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize, min_similarity: f32) -> Result<Vec<SearchResult>> {
-        let embeddings = self.get_all_embeddings()?;
-        let mut results: Vec<(i64, f32)> = embeddings
+    pub fn save_search(&self, name: &str, query: &str, scope: &SearchScope) -> Result<i64> {
+        let mut client = self.pool.get()?;
+        let row = client.query_one(
+            r#"
+            INSERT INTO saved_searches (name, query, scope) VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE SET query = EXCLUDED.query, scope = EXCLUDED.scope
+            RETURNING id
+            "#,
+            &[&name, &query, &scope.to_db_string()],
+        )?;
+        Ok(row.get(0))
+    }
+
+    pub fn get_saved_searches(&self) -> Result<Vec<SavedSearch>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query("SELECT id, name, query, scope FROM saved_searches ORDER BY name", &[])?;
+        let searches = rows
             .iter()
-            .map(|record| {
-                let similarity = cosine_similarity(query_embedding, &record.embedding);
-                (record.photo_id, similarity)
+            .map(|row| {
+                let scope: String = row.get(3);
+                SavedSearch {
+                    id: row.get(0),
+                    name: row.get(1),
+                    query: row.get(2),
+                    scope: SearchScope::from_db_string(&scope).unwrap_or_default(),
+                }
             })
+            .collect();
+        Ok(searches)
+    }
+
+    pub fn delete_saved_search(&self, id: i64) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute("DELETE FROM saved_searches WHERE id = $1", &[&id])?;
+        Ok(())
+    }
+
+    /// Photos matching a filter's face count limits, most faces first
+    pub fn set_color_palette(&self, path: &str, palette_json: &str) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE photos SET color_palette = $1 WHERE path = $2",
+            &[&palette_json, &path],
+        )?;
+        Ok(())
+    }
+
+    /// Photos in scope that have a color palette, with the palette's JSON
+    pub fn get_color_palettes(&self, filter: &SearchFilter) -> Result<Vec<(SearchResult, String)>> {
+        let (with, mut conditions, params) = filter_sql("id", filter);
+        conditions.push("color_palette IS NOT NULL AND trashed_at IS NULL".to_string());
+        let params: Vec<&(dyn postgres::types::ToSql + Sync)> = params.iter().map(|p| p.as_ref()).collect();
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            &format!(
+                "{} SELECT id, path, filename, description, color_palette FROM photos WHERE {}",
+                with,
+                conditions.join(" AND ")
+            ),
+            &params,
+        )?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let result = SearchResult {
+                    photo_id: row.get(0),
+                    path: row.get(1),
+                    filename: row.get(2),
+                    similarity: 0.0,
+                    description: row.get(3),
+                };
+                (result, row.get(4))
+            })
+            .collect())
+    }
+
+    pub fn get_photos_by_face_count(&self, filter: &SearchFilter, limit: usize) -> Result<Vec<SearchResult>> {
+        let (with, conditions, mut params) = filter_sql("id", filter);
+        params.push(Box::new(limit as i64));
+        let params: Vec<&(dyn postgres::types::ToSql + Sync)> = params.iter().map(|p| p.as_ref()).collect();
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            &format!(
+                r#"
+                {}
+                SELECT id, path, filename, description
+                FROM photos
+                WHERE {}
+                ORDER BY (SELECT face_count FROM face_counts WHERE photo_id = photos.id) DESC, path
+                LIMIT ${}
+                "#,
+                if with.is_empty() { FACE_COUNTS_CTE } else { with },
+                if conditions.is_empty() { "TRUE".to_string() } else { conditions.join(" AND ") },
+                params.len()
+            ),
+            &params,
+        )?;
+        Ok(rows
+            .iter()
+            .map(|row| SearchResult {
+                photo_id: row.get(0),
+                path: row.get(1),
+                filename: row.get(2),
+                similarity: 1.0,
+                description: row.get(3),
+            })
+            .collect())
+    }
+
+    fn get_embeddings_in_scope(&self, filter: &SearchFilter) -> Result<Vec<EmbeddingRecord>> {
+        let (with, conditions, params) = filter_sql("photo_id", filter);
+        if conditions.is_empty() {
+            return self.get_all_embeddings();
+        }
+        let params: Vec<&(dyn postgres::types::ToSql + Sync)> = params.iter().map(|p| p.as_ref()).collect();
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            &format!(
+                "{} SELECT photo_id, embedding, model_name FROM embeddings WHERE {}",
+                with,
+                conditions.join(" AND ")
+            ),
+            &params,
+        )?;
+        let records = rows
+            .iter()
+            .map(|row| {
+                let bytes: Vec<u8> = row.get(1);
+                EmbeddingRecord {
+                    photo_id: row.get(0),
+                    embedding: bytes_to_embedding(&bytes),
+                    model_name: row.get(2),
+                }
+            })
+            .collect();
+        Ok(records)
+    }
+
+    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize, min_similarity: f32, filter: &SearchFilter) -> Result<Vec<SearchResult>> {
+        self.rank_embeddings(filter, limit, min_similarity, |embedding| cosine_similarity(query_embedding, embedding))
+    }
+
+    /// Search with a boolean query, scoring each term against its own embedding.
+    pub fn semantic_search_query(
+        &self,
+        query: &SearchAst,
+        term_embeddings: &HashMap<String, Vec<f32>>,
+        limit: usize,
+        min_similarity: f32,
+        filter: &SearchFilter,
+    ) -> Result<Vec<SearchResult>> {
+        self.rank_embeddings(filter, limit, min_similarity, |embedding| {
+            query.score(&|term| {
+                term_embeddings
+                    .get(term)
+                    .map(|t| cosine_similarity(t, embedding))
+                    .unwrap_or(0.0)
+            })
+        })
+    }
+
+    fn rank_embeddings(
+        &self,
+        filter: &SearchFilter,
+        limit: usize,
+        min_similarity: f32,
+        score: impl Fn(&[f32]) -> f32,
+    ) -> Result<Vec<SearchResult>> {
+        let embeddings = self.get_embeddings_in_scope(filter)?;
+        let mut results: Vec<(i64, f32)> = embeddings
+            .iter()
+            .map(|record| (record.photo_id, score(&record.embedding)))
             .filter(|(_, sim)| *sim >= min_similarity)
             .collect();
         results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
@@ -942,6 +1269,44 @@ impl PgDb {
         Ok(results)
     }
 
+    pub fn get_photos_with_stale_clip_embeddings_in_dir(&self, directory: &str, model_name: &str, limit: usize) -> Result<Vec<(i64, String)>> {
+        let dir_pattern = if directory.ends_with('/') {
+            format!("{}%", directory)
+        } else {
+            format!("{}/%", directory)
+        };
+        let limit_i64 = limit as i64;
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT p.id, p.path
+            FROM photos p
+            LEFT JOIN embeddings e ON p.id = e.photo_id
+            WHERE (e.photo_id IS NULL OR (e.model_name LIKE 'clip%' AND e.model_name != $1))
+              AND p.path LIKE $2
+            LIMIT $3
+            "#,
+            &[&model_name, &dir_pattern, &limit_i64],
+        )?;
+        let results = rows.iter().map(|row| (row.get(0), row.get(1))).collect();
+        Ok(results)
+    }
+
+    pub fn get_latest_clip_model(&self) -> Result<Option<String>> {
+        let mut client = self.pool.get()?;
+        let row = client.query_opt(
+            "SELECT model_name FROM embeddings WHERE model_name LIKE 'clip%' ORDER BY created_at DESC LIMIT 1",
+            &[],
+        )?;
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    pub fn delete_embeddings_for_model(&self, model_name: &str) -> Result<usize> {
+        let mut client = self.pool.get()?;
+        let count = client.execute("DELETE FROM embeddings WHERE model_name = $1", &[&model_name])?;
+        Ok(count as usize)
+    }
+
     pub fn count_embeddings(&self) -> Result<i64> {
         let mut client = self.pool.get()?;
         let row = client.query_one("SELECT COUNT(*) FROM embeddings", &[])?;
@@ -956,11 +1321,11 @@ impl PgDb {
         let mut client = self.pool.get()?;
         let rows = client.query(
             r#"
-            SELECT sha256_hash, COUNT(*) as cnt
+            SELECT sha256_hash
             FROM photos
             WHERE sha256_hash IS NOT NULL
             GROUP BY sha256_hash
-            HAVING COUNT(*) > 1
+            HAVING COUNT(DISTINCT COALESCE(real_path, path)) > 1
             "#,
             &[],
         )?;
@@ -1021,6 +1386,52 @@ impl PgDb {
         Ok(groups)
     }
 
+    pub fn find_embedding_clusters(&self, threshold: f32) -> Result<Vec<SimilarityGroup>> {
+        let photos = self.get_all_photos_with_embeddings()?;
+        Ok(group_by_embedding(photos, threshold))
+    }
+
+    fn get_all_photos_with_embeddings(&self) -> Result<Vec<EmbeddedPhoto>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT p.id, p.path, p.filename, p.size_bytes, p.width, p.height,
+                   p.sha256_hash, p.perceptual_hash, p.taken_at, p.marked_for_deletion,
+                   e.model_name, e.embedding
+            FROM photos p
+            JOIN embeddings e ON e.photo_id = p.id
+            ORDER BY p.path
+            "#,
+            &[],
+        )?;
+        let photos = rows
+            .iter()
+            .map(|row| {
+                let width_i32: Option<i32> = row.get(4);
+                let height_i32: Option<i32> = row.get(5);
+                let marked: bool = row.get(9);
+                let bytes: Vec<u8> = row.get(11);
+                (
+                    PhotoRecord {
+                        id: row.get(0),
+                        path: row.get(1),
+                        filename: row.get(2),
+                        size_bytes: row.get(3),
+                        width: width_i32.map(|v| v as u32),
+                        height: height_i32.map(|v| v as u32),
+                        sha256_hash: row.get(6),
+                        perceptual_hash: row.get(7),
+                        taken_at: row.get(8),
+                        marked_for_deletion: marked,
+                    },
+                    row.get(10),
+                    bytes_to_embedding(&bytes),
+                )
+            })
+            .collect();
+        Ok(photos)
+    }
+
     fn get_photos_by_sha256(&self, sha256: &str) -> Result<Vec<PhotoRecord>> {
         let mut client = self.pool.get()?;
         let rows = client.query(
@@ -1028,7 +1439,11 @@ impl PgDb {
             SELECT id, path, filename, size_bytes, width, height,
                    sha256_hash, perceptual_hash, taken_at, marked_for_deletion
             FROM photos
-            WHERE sha256_hash = $1
+            WHERE id IN (
+                SELECT MIN(id) FROM photos
+                WHERE sha256_hash = $1
+                GROUP BY COALESCE(real_path, path)
+            )
             ORDER BY taken_at, path
             "#,
             &[&sha256],
@@ -1185,6 +1600,7 @@ impl PgDb {
             r#"
             UPDATE photos
             SET path = $1,
+                real_path = NULL,
                 original_path = $2,
                 trashed_at = $3,
                 marked_for_deletion = false
@@ -1233,6 +1649,7 @@ impl PgDb {
             r#"
             UPDATE photos
             SET path = original_path,
+                real_path = NULL,
                 original_path = NULL,
                 trashed_at = NULL
             WHERE id = $1
@@ -1543,12 +1960,65 @@ impl PgDb {
         }
     }
 
+    pub fn get_tag_stats(&self) -> Result<Vec<TagStat>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT t.id, t.name, t.color, COUNT(pt.photo_id) AS photo_count, t.parent_id
+            FROM user_tags t
+            LEFT JOIN photo_user_tags pt ON pt.tag_id = t.id
+            GROUP BY t.id, t.name, t.color, t.parent_id
+            ORDER BY photo_count DESC, t.name
+            "#,
+            &[],
+        )?;
+        let stats = rows
+            .iter()
+            .map(|row| TagStat {
+                tag: UserTag { id: row.get(0), name: row.get(1), color: row.get(2) },
+                photo_count: row.get(3),
+                parent_id: row.get(4),
+            })
+            .collect();
+        Ok(stats)
+    }
+
     pub fn delete_tag(&self, tag_id: i64) -> Result<()> {
         let mut client = self.pool.get()?;
+        // Nested tags move up to the deleted tag's parent
+        client.execute(
+            "UPDATE user_tags SET parent_id = (SELECT parent_id FROM user_tags WHERE id = $1) WHERE parent_id = $1",
+            &[&tag_id],
+        )?;
         client.execute("DELETE FROM user_tags WHERE id = $1", &[&tag_id])?;
         Ok(())
     }
 
+    pub fn set_tag_parent(&self, tag_id: i64, parent_id: Option<i64>) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE user_tags SET parent_id = $1 WHERE id = $2",
+            &[&parent_id, &tag_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_tag_descendants(&self, tag_id: i64) -> Result<Vec<i64>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            WITH RECURSIVE tag_tree(id) AS (
+                SELECT $1::BIGINT
+                UNION
+                SELECT t.id FROM user_tags t JOIN tag_tree tt ON t.parent_id = tt.id
+            )
+            SELECT id FROM tag_tree
+            "#,
+            &[&tag_id],
+        )?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
     pub fn rename_tag(&self, tag_id: i64, new_name: &str) -> Result<()> {
         let mut client = self.pool.get()?;
         client.execute(
@@ -1558,6 +2028,20 @@ impl PgDb {
         Ok(())
     }
 
+    pub fn get_photos_in_dir_with_tag(&self, directory: &str, tag_id: i64) -> Result<Vec<String>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT p.path
+            FROM photos p
+            JOIN photo_user_tags pt ON pt.photo_id = p.id
+            WHERE p.directory = $1 AND pt.tag_id = $2
+            "#,
+            &[&directory, &tag_id],
+        )?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
     pub fn get_photo_tags(&self, photo_id: i64) -> Result<Vec<UserTag>> {
         let mut client = self.pool.get()?;
         let rows = client.query(
@@ -1624,7 +2108,11 @@ impl PgDb {
         let rows = client.query(
             r#"
             SELECT a.id, a.name, a.description, a.cover_photo_id, a.is_smart, a.filter_tags,
-                   (SELECT COUNT(*) FROM album_photos WHERE album_id = a.id) as photo_count
+                   CASE WHEN a.is_smart
+                       THEN (SELECT COUNT(*) FROM smart_album_photos WHERE album_id = a.id)
+                       ELSE (SELECT COUNT(*) FROM album_photos WHERE album_id = a.id)
+                   END as photo_count,
+                   a.auto_sort, a.geo_radius
             FROM albums a
             ORDER BY a.name
             "#,
@@ -1645,7 +2133,9 @@ impl PgDb {
                     cover_photo_id: row.get(3),
                     is_smart,
                     filter_tags,
+                    geo_radius: row.get::<_, Option<String>>(8).and_then(|j| serde_json::from_str(&j).ok()),
                     photo_count: row.get(6),
+                    auto_sort: AlbumSort::from_db_str(row.get::<_, Option<String>>(7).as_deref()),
                 }
             })
             .collect();
@@ -1670,7 +2160,9 @@ impl PgDb {
     pub fn add_photo_to_album(&self, album_id: i64, photo_id: i64) -> Result<()> {
         let mut client = self.pool.get()?;
         client.execute(
-            "INSERT INTO album_photos (album_id, photo_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            "INSERT INTO album_photos (album_id, photo_id, position)
+             SELECT $1, $2, COALESCE(MAX(position) + 1, 0) FROM album_photos WHERE album_id = $1
+             ON CONFLICT DO NOTHING",
             &[&album_id, &photo_id],
         )?;
         Ok(())
@@ -1711,6 +2203,41 @@ impl PgDb {
         Ok(paths)
     }
 
+    /// Store `photo_ids` as the album's order, first photo at position 0
+    pub fn set_album_order(&self, album_id: i64, photo_ids: &[i64]) -> Result<()> {
+        let mut client = self.pool.get()?;
+        let mut tx = client.transaction()?;
+        for (position, photo_id) in photo_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE album_photos SET position = $1 WHERE album_id = $2 AND photo_id = $3",
+                &[&(position as i32), &album_id, photo_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn set_album_auto_sort(&self, album_id: i64, sort: AlbumSort) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE albums SET auto_sort = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+            &[&sort.as_db_str(), &album_id],
+        )?;
+        Ok(())
+    }
+
+    /// Limit a smart album to photos taken within `geo`, or lift the limit
+    pub fn set_album_geo_radius(&self, album_id: i64, geo: Option<&GeoRadius>) -> Result<()> {
+        let json = geo.map(serde_json::to_string).transpose()?;
+        let mut client = self.pool.get()?;
+        client.execute(
+            "UPDATE albums SET geo_radius = $1, is_smart = is_smart OR $1::TEXT IS NOT NULL,
+                 updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+            &[&json, &album_id],
+        )?;
+        Ok(())
+    }
+
     pub fn set_album_filter_tags(&self, album_id: i64, tag_ids: &[i64]) -> Result<()> {
         let json = serde_json::to_string(tag_ids)?;
         let mut client = self.pool.get()?;
@@ -1721,28 +2248,58 @@ impl PgDb {
         Ok(())
     }
 
-    pub fn get_smart_album_photos(&self, album_id: i64) -> Result<Vec<i64>> {
+    /// Store the current members of a smart album
+    pub fn replace_smart_album_photos(&self, album_id: i64, photo_ids: &[i64]) -> Result<()> {
         let mut client = self.pool.get()?;
-        let row = client.query_one(
-            "SELECT filter_tags FROM albums WHERE id = $1",
+        let mut tx = client.transaction()?;
+        tx.execute("DELETE FROM smart_album_photos WHERE album_id = $1", &[&album_id])?;
+        for photo_id in photo_ids {
+            tx.execute(
+                "INSERT INTO smart_album_photos (album_id, photo_id) VALUES ($1, $2)",
+                &[&album_id, photo_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Photos matching all of a smart album's criteria: every one of its
+    /// tags, and its geofence if it has one
+    pub fn get_smart_album_photos(&self, album_id: i64) -> Result<Vec<i64>> {
+        let row = self.pool.get()?.query_one(
+            "SELECT filter_tags, geo_radius FROM albums WHERE id = $1",
             &[&album_id],
         )?;
         let filter_json: Option<String> = row.get(0);
+        let geo_json: Option<String> = row.get(1);
         let tag_ids: Vec<i64> = filter_json
             .and_then(|j| serde_json::from_str(&j).ok())
             .unwrap_or_default();
-        if tag_ids.is_empty() {
-            return Ok(vec![]);
-        }
+        let nearby = match geo_json.and_then(|j| serde_json::from_str::<GeoRadius>(&j).ok()) {
+            Some(geo) => Some(self.get_photos_in_radius(geo.center_lat, geo.center_lon, geo.radius_km)?),
+            None => None,
+        };
+        let tagged = if tag_ids.is_empty() { None } else { Some(self.get_photos_with_all_tags(&tag_ids)?) };
+        Ok(smart_album_members(tagged, nearby))
+    }
+
+    /// Photos with each of `tag_ids` or a tag nested under it
+    fn get_photos_with_all_tags(&self, tag_ids: &[i64]) -> Result<Vec<i64>> {
+        let mut client = self.pool.get()?;
         let placeholders: Vec<String> = (1..=tag_ids.len()).map(|i| format!("${}", i)).collect();
         let count_param = format!("${}", tag_ids.len() + 1);
         let query = format!(
             r#"
-            SELECT photo_id
-            FROM photo_user_tags
-            WHERE tag_id IN ({})
-            GROUP BY photo_id
-            HAVING COUNT(DISTINCT tag_id) = {}
+            WITH RECURSIVE tag_tree(root, id) AS (
+                SELECT id, id FROM user_tags WHERE id IN ({})
+                UNION
+                SELECT tt.root, t.id FROM user_tags t JOIN tag_tree tt ON t.parent_id = tt.id
+            )
+            SELECT pt.photo_id
+            FROM photo_user_tags pt
+            JOIN tag_tree tt ON tt.id = pt.tag_id
+            GROUP BY pt.photo_id
+            HAVING COUNT(DISTINCT tt.root) = {}
             "#,
             placeholders.join(","),
             count_param,
@@ -1805,6 +2362,17 @@ impl PgDb {
         Ok(tasks)
     }
 
+    /// Photos outside the trash matching `condition`, built by
+    /// `missing_fields_condition`
+    pub fn get_photos_matching(&self, condition: &str) -> Result<Vec<(i64, String)>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            &format!("SELECT id, path FROM photos WHERE trashed_at IS NULL AND ({}) ORDER BY path ASC", condition),
+            &[],
+        )?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
     pub fn get_photo_description(&self, photo_id: i64) -> Result<Option<String>> {
         let mut client = self.pool.get()?;
         let row = client.query_opt(
@@ -1857,6 +2425,7 @@ impl PgDb {
     pub fn insert_scanned_photo(
         &self,
         path: &str,
+        real_path: Option<&str>,
         filename: &str,
         directory: &str,
         size_bytes: i64,
@@ -1883,6 +2452,7 @@ impl PgDb {
         let width_i32 = width.map(|w| w as i32);
         let height_i32 = height.map(|h| h as i32);
         let iso_i32 = iso.map(|i| i as i32);
+        let gps_source = gps_lat.map(|_| "exif");
         let mut client = self.pool.get()?;
         client.execute(
             r#"
@@ -1892,8 +2462,8 @@ impl PgDb {
                 camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at,
                 gps_latitude, gps_longitude, all_exif,
                 md5_hash, sha256_hash, perceptual_hash,
-                exif_orientation
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
+                exif_orientation, gps_source, real_path
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25)
             "#,
             &[
                 &path, &filename, &directory, &size_bytes, &modified_at,
@@ -1901,7 +2471,7 @@ impl PgDb {
                 &camera_make, &camera_model, &lens, &focal_length, &aperture, &shutter_speed, &iso_i32, &taken_at,
                 &gps_lat, &gps_lon, &all_exif,
                 &md5_hash, &sha256_hash, &perceptual_hash,
-                &exif_orientation,
+                &exif_orientation, &gps_source, &real_path,
             ],
         )?;
         Ok(())
@@ -1911,6 +2481,7 @@ impl PgDb {
     pub fn update_scanned_photo(
         &self,
         path: &str,
+        real_path: Option<&str>,
         filename: &str,
         directory: &str,
         size_bytes: i64,
@@ -1937,6 +2508,7 @@ impl PgDb {
         let width_i32 = width.map(|w| w as i32);
         let height_i32 = height.map(|h| h as i32);
         let iso_i32 = iso.map(|i| i as i32);
+        let gps_source = gps_lat.map(|_| "exif");
         let mut client = self.pool.get()?;
         client.execute(
             r#"
@@ -1944,9 +2516,13 @@ impl PgDb {
                 filename = $1, directory = $2, size_bytes = $3, modified_at = $4,
                 width = $5, height = $6, format = $7,
                 camera_make = $8, camera_model = $9, lens = $10, focal_length = $11, aperture = $12, shutter_speed = $13, iso = $14, taken_at = $15,
-                gps_latitude = $16, gps_longitude = $17, all_exif = $18,
+                gps_latitude = CASE WHEN gps_source = 'manual' THEN gps_latitude ELSE $16 END,
+                gps_longitude = CASE WHEN gps_source = 'manual' THEN gps_longitude ELSE $17 END,
+                all_exif = $18,
                 md5_hash = $19, sha256_hash = $20, perceptual_hash = $21,
                 exif_orientation = $22,
+                gps_source = CASE WHEN gps_source = 'manual' THEN gps_source ELSE $24 END,
+                real_path = $25,
                 scanned_at = CURRENT_TIMESTAMP
             WHERE path = $23
             "#,
@@ -1957,12 +2533,77 @@ impl PgDb {
                 &gps_lat, &gps_lon, &all_exif,
                 &md5_hash, &sha256_hash, &perceptual_hash,
                 &exif_orientation,
-                &path,
+                &path, &gps_source, &real_path,
             ],
         )?;
         Ok(())
     }
 
+    // ========================================================================
+    // Event operations
+    // ========================================================================
+
+    /// Described photos with a capture date, with their CLIP embedding if any
+    pub fn get_event_candidates(&self) -> Result<Vec<EventCandidate>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT p.id, p.taken_at, p.description, e.embedding
+            FROM photos p
+            LEFT JOIN embeddings e ON e.photo_id = p.id
+            WHERE p.description IS NOT NULL AND p.description != '' AND p.taken_at IS NOT NULL
+            "#,
+            &[],
+        )?;
+        let candidates = rows
+            .iter()
+            .map(|row| {
+                let embedding: Option<Vec<u8>> = row.get(3);
+                EventCandidate {
+                    photo_id: row.get(0),
+                    taken_at: row.get(1),
+                    description: row.get(2),
+                    embedding: embedding.map(|b| bytes_to_embedding(&b)),
+                }
+            })
+            .collect();
+        Ok(candidates)
+    }
+
+    pub fn get_events(&self) -> Result<Vec<Event>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            "SELECT id, name, start_date, end_date, photo_count FROM events ORDER BY start_date",
+            &[],
+        )?;
+        let events = rows
+            .iter()
+            .map(|row| Event {
+                id: row.get(0),
+                name: row.get(1),
+                start_date: row.get(2),
+                end_date: row.get(3),
+                photo_count: row.get(4),
+            })
+            .collect();
+        Ok(events)
+    }
+
+    /// Replace every stored event; the events' ids are ignored
+    pub fn replace_events(&self, events: &[Event]) -> Result<()> {
+        let mut client = self.pool.get()?;
+        let mut tx = client.transaction()?;
+        tx.execute("DELETE FROM events", &[])?;
+        for event in events {
+            tx.execute(
+                "INSERT INTO events (name, start_date, end_date, photo_count) VALUES ($1, $2, $3, $4)",
+                &[&event.name, &event.start_date, &event.end_date, &event.photo_count],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     // ========================================================================
     // Export operations
     // ========================================================================
@@ -1982,7 +2623,19 @@ impl PgDb {
                 camera_model,
                 taken_at,
                 description,
-                scanned_at
+                scanned_at,
+                id,
+                rating,
+                gps_latitude,
+                gps_longitude,
+                (SELECT string_agg(ut.name, chr(31) ORDER BY ut.name)
+                    FROM photo_user_tags put
+                    JOIN user_tags ut ON ut.id = put.tag_id
+                    WHERE put.photo_id = photos.id),
+                (SELECT string_agg(DISTINCT p.name, chr(31) ORDER BY p.name)
+                    FROM faces f
+                    JOIN people p ON p.id = f.person_id
+                    WHERE f.photo_id = photos.id)
             FROM photos
             ORDER BY path
             "#,
@@ -2000,6 +2653,7 @@ impl PgDb {
                 let height_i32: Option<i32> = row.get(2);
                 let size_bytes: Option<i64> = row.get(3);
                 ExportedPhotoRow {
+                    id: row.get(11),
                     path,
                     filename,
                     width: width_i32.map(|v| v as u32),
@@ -2012,6 +2666,11 @@ impl PgDb {
                     date_taken: row.get(8),
                     description: row.get(9),
                     scanned_at: row.get(10),
+                    rating: row.get(12),
+                    gps_latitude: row.get(13),
+                    gps_longitude: row.get(14),
+                    tags: super::split_name_list(row.get(15)),
+                    people: super::split_name_list(row.get(16)),
                 }
             })
             .collect();
@@ -2088,6 +2747,15 @@ impl PgDb {
         Ok(row.map(|r| r.get(0)))
     }
 
+    pub fn get_directory_prompts(&self) -> Result<Vec<(String, String)>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            "SELECT directory, custom_prompt FROM directory_prompts ORDER BY directory",
+            &[],
+        )?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
     pub fn set_directory_prompt(&self, directory: &str, prompt: &str) -> Result<()> {
         let mut client = self.pool.get()?;
         if prompt.is_empty() {
@@ -2108,6 +2776,113 @@ impl PgDb {
         Ok(())
     }
 
+    // ========================================================================
+    // Slideshow preference operations
+    // ========================================================================
+
+    pub fn get_slideshow_interval(&self, directory: &str) -> Result<Option<f64>> {
+        let mut client = self.pool.get()?;
+        let row = client.query_opt(
+            "SELECT interval_secs FROM slideshow_prefs WHERE directory = $1",
+            &[&directory],
+        )?;
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    pub fn set_slideshow_interval(&self, directory: &str, interval_secs: f64) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            r#"
+            INSERT INTO slideshow_prefs (directory, interval_secs, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (directory) DO UPDATE SET interval_secs = $2, updated_at = NOW()
+            "#,
+            &[&directory, &interval_secs],
+        )?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // App state operations
+    // ========================================================================
+
+    pub fn get_app_state(&self, key: &str) -> Result<Option<String>> {
+        let mut client = self.pool.get()?;
+        let row = client.query_opt(
+            "SELECT value FROM app_state WHERE key = $1",
+            &[&key],
+        )?;
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    pub fn set_app_state(&self, key: &str, value: &str) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            r#"
+            INSERT INTO app_state (key, value, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()
+            "#,
+            &[&key, &value],
+        )?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Location operations
+    // ========================================================================
+
+    /// Photos taken within `radius_km` of the coordinates. A bounding box
+    /// narrows the candidates in SQL; the haversine distance decides.
+    pub fn get_photos_in_radius(&self, lat: f64, lon: f64, radius_km: f64) -> Result<Vec<i64>> {
+        let (min_lat, max_lat, min_lon, max_lon) = bounding_box(lat, lon, radius_km);
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            "SELECT id, gps_latitude, gps_longitude FROM photos
+             WHERE gps_latitude BETWEEN $1 AND $2 AND gps_longitude BETWEEN $3 AND $4 AND trashed_at IS NULL",
+            &[&min_lat, &max_lat, &min_lon, &max_lon],
+        )?;
+        Ok(rows
+            .iter()
+            .map(|r| (r.get::<_, i64>(0), r.get::<_, f64>(1), r.get::<_, f64>(2)))
+            .filter(|&(_, plat, plon)| haversine_km(lat, lon, plat, plon) <= radius_km)
+            .map(|(id, _, _)| id)
+            .collect())
+    }
+
+    /// `(path, latitude, longitude)` of every photo with coordinates
+    pub fn get_gps_photos(&self) -> Result<Vec<(String, f64, f64)>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            "SELECT path, gps_latitude, gps_longitude FROM photos
+             WHERE gps_latitude IS NOT NULL AND gps_longitude IS NOT NULL AND trashed_at IS NULL",
+            &[],
+        )?;
+        Ok(rows.iter().map(|r| (r.get(0), r.get(1), r.get(2))).collect())
+    }
+
+    pub fn get_place_name(&self, lat_key: i64, lon_key: i64) -> Result<Option<String>> {
+        let mut client = self.pool.get()?;
+        let row = client.query_opt(
+            "SELECT name FROM place_names WHERE lat_key = $1 AND lon_key = $2",
+            &[&lat_key, &lon_key],
+        )?;
+        Ok(row.map(|r| r.get(0)))
+    }
+
+    pub fn save_place_name(&self, lat_key: i64, lon_key: i64, name: &str) -> Result<()> {
+        let mut client = self.pool.get()?;
+        client.execute(
+            r#"
+            INSERT INTO place_names (lat_key, lon_key, name, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (lat_key, lon_key) DO UPDATE SET name = $3, updated_at = NOW()
+            "#,
+            &[&lat_key, &lon_key, &name],
+        )?;
+        Ok(())
+    }
+
     pub fn count_photos_without_faces_in_dir(&self, directory: &str) -> Result<i64> {
         let mut client = self.pool.get()?;
         let row = client.query_one(
@@ -2121,4 +2896,101 @@ impl PgDb {
         )?;
         Ok(row.get(0))
     }
+
+    pub fn count_photos_in_dir_recursive(&self, directory: &str) -> Result<DirectoryPhotoStats> {
+        let mut client = self.pool.get()?;
+        let prefix = format!("{}/", directory.trim_end_matches('/'));
+        let row = client.query_one(
+            r#"
+            SELECT COUNT(*), COALESCE(SUM(size_bytes), 0)::BIGINT, MAX(taken_at)
+            FROM photos
+            WHERE (directory = $1 OR left(directory, length($2)) = $2)
+              AND trashed_at IS NULL
+            "#,
+            &[&directory, &prefix],
+        )?;
+        Ok(DirectoryPhotoStats {
+            photo_count: row.get(0),
+            total_bytes: row.get(1),
+            latest_taken_at: row.get(2),
+        })
+    }
+
+    pub fn list_directories(&self) -> Result<Vec<DirectorySummary>> {
+        let mut client = self.pool.get()?;
+        let rows = client.query(
+            r#"
+            SELECT directory, COUNT(*), COALESCE(SUM(size_bytes), 0)::BIGINT
+            FROM photos
+            WHERE trashed_at IS NULL
+            GROUP BY directory
+            ORDER BY directory
+            "#,
+            &[],
+        )?;
+        Ok(rows
+            .iter()
+            .map(|row| DirectorySummary {
+                directory: row.get(0),
+                photo_count: row.get(1),
+                total_bytes: row.get(2),
+            })
+            .collect())
+    }
+}
+
+/// Counts detected faces for every photo scanned for faces, including
+/// photos where none were found.
+const FACE_COUNTS_CTE: &str = "WITH face_counts AS (SELECT fs.photo_id, COUNT(f.id) AS face_count FROM face_scans fs LEFT JOIN faces f ON f.photo_id = fs.photo_id GROUP BY fs.photo_id)";
+
+/// SQL limiting a photo id column to a search filter: a WITH clause (empty
+/// unless face counts are needed), conditions to AND together, and their
+/// parameters numbered from $1.
+fn filter_sql(column: &str, filter: &SearchFilter) -> (&'static str, Vec<String>, Vec<Box<dyn postgres::types::ToSql + Sync>>) {
+    let mut conditions = Vec::new();
+    let mut params: Vec<Box<dyn postgres::types::ToSql + Sync>> = Vec::new();
+
+    let scope: Option<(&str, Box<dyn postgres::types::ToSql + Sync>)> = match &filter.scope {
+        SearchScope::All => None,
+        SearchScope::Album(id) => Some(("SELECT photo_id FROM album_photos WHERE album_id = $1", Box::new(*id))),
+        SearchScope::Tag(id) => Some(("SELECT photo_id FROM photo_user_tags WHERE tag_id = $1", Box::new(*id))),
+        SearchScope::Person(id) => Some(("SELECT photo_id FROM faces WHERE person_id = $1", Box::new(*id))),
+        SearchScope::Directory(dir) => Some((
+            "SELECT id FROM photos WHERE path LIKE $1 ESCAPE '\\'",
+            Box::new(SearchScope::directory_pattern(dir)),
+        )),
+    };
+    if let Some((subquery, param)) = scope {
+        conditions.push(format!("{} IN ({})", column, subquery));
+        params.push(param);
+    }
+
+    if !filter.has_face_limits() {
+        return ("", conditions, params);
+    }
+    let (min, max) = filter.face_range();
+    conditions.push(format!(
+        "{} IN (SELECT photo_id FROM face_counts WHERE face_count BETWEEN ${} AND ${})",
+        column,
+        params.len() + 1,
+        params.len() + 2
+    ));
+    params.push(Box::new(min));
+    params.push(Box::new(max));
+    (FACE_COUNTS_CTE, conditions, params)
+}
+
+/// A boolean search query as a tsquery expression: each term through
+/// `plainto_tsquery`, combined with `&&`, `||` and `!!`. Terms are added
+/// to `params`.
+fn tsquery_sql(query: &SearchAst, params: &mut Vec<Box<dyn postgres::types::ToSql + Sync>>) -> String {
+    match query {
+        SearchAst::Term(term) => {
+            params.push(Box::new(term.clone()));
+            format!("plainto_tsquery('english', ${})", params.len())
+        }
+        SearchAst::And(a, b) => format!("({} && {})", tsquery_sql(a, params), tsquery_sql(b, params)),
+        SearchAst::Or(a, b) => format!("({} || {})", tsquery_sql(a, params), tsquery_sql(b, params)),
+        SearchAst::Not(a) => format!("(!!{})", tsquery_sql(a, params)),
+    }
 }
@@ -4,6 +4,7 @@ pub const POSTGRES_SCHEMA: &str = r#"
 CREATE TABLE IF NOT EXISTS photos (
     id BIGSERIAL PRIMARY KEY,
     path TEXT NOT NULL UNIQUE,
+    real_path TEXT,
     filename TEXT NOT NULL,
     directory TEXT NOT NULL,
     size_bytes BIGINT NOT NULL,
@@ -25,8 +26,12 @@ CREATE TABLE IF NOT EXISTS photos (
     taken_at TEXT,
     gps_latitude DOUBLE PRECISION,
     gps_longitude DOUBLE PRECISION,
+    gps_source TEXT,
     exif_orientation INTEGER DEFAULT 1,
     user_rotation INTEGER DEFAULT 0,
+    rating INTEGER,
+    color_label TEXT,
+    color_palette TEXT,
 
     all_exif TEXT,
 
@@ -124,6 +129,7 @@ CREATE TABLE IF NOT EXISTS faces (
     bbox_h INTEGER NOT NULL,
     embedding BYTEA,
     embedding_dim INTEGER,
+    embedding_model TEXT,
     person_id BIGINT,
     confidence DOUBLE PRECISION,
     created_at TEXT NOT NULL DEFAULT NOW(),
@@ -182,6 +188,7 @@ CREATE TABLE IF NOT EXISTS user_tags (
     id BIGSERIAL PRIMARY KEY,
     name TEXT NOT NULL UNIQUE,
     color TEXT DEFAULT '#808080',
+    parent_id BIGINT,
     created_at TEXT NOT NULL DEFAULT NOW()
 );
 
@@ -205,6 +212,8 @@ CREATE TABLE IF NOT EXISTS albums (
     cover_photo_id BIGINT,
     is_smart BOOLEAN DEFAULT FALSE,
     filter_tags TEXT,
+    auto_sort TEXT,
+    geo_radius TEXT,
     created_at TEXT NOT NULL DEFAULT NOW(),
     updated_at TEXT NOT NULL DEFAULT NOW(),
     FOREIGN KEY (cover_photo_id) REFERENCES photos(id) ON DELETE SET NULL
@@ -224,10 +233,89 @@ CREATE TABLE IF NOT EXISTS album_photos (
 
 CREATE INDEX IF NOT EXISTS idx_album_photos_album ON album_photos(album_id);
 
+CREATE TABLE IF NOT EXISTS smart_album_photos (
+    album_id BIGINT NOT NULL,
+    photo_id BIGINT NOT NULL,
+    PRIMARY KEY (album_id, photo_id),
+    FOREIGN KEY (album_id) REFERENCES albums(id) ON DELETE CASCADE,
+    FOREIGN KEY (photo_id) REFERENCES photos(id) ON DELETE CASCADE
+);
+
 -- Per-directory custom prompts for LLM descriptions
 CREATE TABLE IF NOT EXISTS directory_prompts (
     directory TEXT PRIMARY KEY,
     custom_prompt TEXT NOT NULL,
     updated_at TEXT NOT NULL DEFAULT NOW()
 );
+
+-- Named semantic search queries
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    query TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT 'all',
+    created_at TEXT NOT NULL DEFAULT NOW()
+);
+
+-- Application bookkeeping such as when maintenance last ran
+CREATE TABLE IF NOT EXISTS app_state (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT NOW()
+);
+
+-- Events detected from clusters of described photos, named by the LLM
+CREATE TABLE IF NOT EXISTS events (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    photo_count BIGINT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_events_start ON events(start_date);
+
+-- Reverse geocoded place names, keyed by coordinates on a 0.01 degree grid
+CREATE TABLE IF NOT EXISTS place_names (
+    lat_key BIGINT NOT NULL,
+    lon_key BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (lat_key, lon_key)
+);
+
+-- Slideshow auto-advance interval last chosen in each directory
+CREATE TABLE IF NOT EXISTS slideshow_prefs (
+    directory TEXT PRIMARY KEY,
+    interval_secs DOUBLE PRECISION NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT NOW()
+);
+
+-- Columns added after the initial schema
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS gps_source TEXT;
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS rating INTEGER;
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS real_path TEXT;
+ALTER TABLE faces ADD COLUMN IF NOT EXISTS embedding_model TEXT;
+ALTER TABLE user_tags ADD COLUMN IF NOT EXISTS parent_id BIGINT;
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS color_label TEXT;
+ALTER TABLE albums ADD COLUMN IF NOT EXISTS auto_sort TEXT;
+ALTER TABLE albums ADD COLUMN IF NOT EXISTS geo_radius TEXT;
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS color_palette TEXT;
+
+-- Full-text search over descriptions
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS description_tsvector TSVECTOR
+    GENERATED ALWAYS AS (to_tsvector('english', COALESCE(description, ''))) STORED;
+CREATE INDEX IF NOT EXISTS idx_photos_description_tsvector ON photos USING GIN (description_tsvector);
+
+-- Embeddings stored before models were tracked came from ArcFace ResNet100
+UPDATE faces SET embedding_model = 'arcface-resnet100-int8' WHERE embedding IS NOT NULL AND embedding_model IS NULL;
+"#;
+
+/// Trigram index for fuzzy filename search. Creating the `pg_trgm`
+/// extension needs the CREATE privilege on the database, so this runs
+/// separately and is skipped when it fails.
+pub const POSTGRES_TRIGRAM_INDEX: &str = r#"
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE INDEX IF NOT EXISTS idx_photos_filename_trgm ON photos USING GIN (filename gin_trgm_ops);
 "#;
@@ -71,7 +71,7 @@ impl ScheduleStatus {
 }
 
 /// A scheduled task record.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct ScheduledTask {
     pub id: i64,
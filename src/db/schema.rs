@@ -3,6 +3,7 @@ pub const SCHEMA: &str = r#"
 CREATE TABLE IF NOT EXISTS photos (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     path TEXT NOT NULL UNIQUE,
+    real_path TEXT,                       -- Canonical path with symlinks resolved
     filename TEXT NOT NULL,
     directory TEXT NOT NULL,
     size_bytes INTEGER NOT NULL,
@@ -26,8 +27,12 @@ CREATE TABLE IF NOT EXISTS photos (
     taken_at TEXT,
     gps_latitude REAL,
     gps_longitude REAL,
+    gps_source TEXT,                      -- 'exif' or 'manual' (manual is kept on rescan)
     exif_orientation INTEGER DEFAULT 1,  -- EXIF orientation (1-8)
     user_rotation INTEGER DEFAULT 0,      -- User-applied rotation in degrees (0, 90, 180, 270)
+    rating INTEGER,                       -- User rating 0-5
+    color_label TEXT,                     -- red, yellow, green, blue or purple
+    color_palette TEXT,                   -- JSON array of dominant [r,g,b] colors
 
     -- Complete EXIF data as JSON
     all_exif TEXT,
@@ -138,6 +143,7 @@ CREATE TABLE IF NOT EXISTS faces (
     bbox_h INTEGER NOT NULL,  -- Bounding box height
     embedding BLOB,           -- Face embedding for similarity matching
     embedding_dim INTEGER,    -- Embedding dimension
+    embedding_model TEXT,     -- Model that produced the embedding
     person_id INTEGER,        -- NULL until assigned to a person
     confidence REAL,          -- Detection confidence (0-1)
     created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -201,6 +207,7 @@ CREATE TABLE IF NOT EXISTS user_tags (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     name TEXT NOT NULL UNIQUE,
     color TEXT DEFAULT '#808080',  -- Hex color for visual distinction
+    parent_id INTEGER,             -- Enclosing tag, NULL at the top level
     created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -226,6 +233,8 @@ CREATE TABLE IF NOT EXISTS albums (
     cover_photo_id INTEGER,  -- Photo to use as album cover
     is_smart INTEGER DEFAULT 0,  -- 1 if album uses tag filter rules
     filter_tags TEXT,  -- JSON array of tag IDs for smart albums
+    auto_sort TEXT,  -- Order to open the album in; NULL keeps album order
+    geo_radius TEXT,  -- JSON centre and radius for geofenced smart albums
     created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
     FOREIGN KEY (cover_photo_id) REFERENCES photos(id) ON DELETE SET NULL
@@ -246,12 +255,65 @@ CREATE TABLE IF NOT EXISTS album_photos (
 
 CREATE INDEX IF NOT EXISTS idx_album_photos_album ON album_photos(album_id);
 
+-- Smart album membership as of the last refresh (after each scan)
+CREATE TABLE IF NOT EXISTS smart_album_photos (
+    album_id INTEGER NOT NULL,
+    photo_id INTEGER NOT NULL,
+    PRIMARY KEY (album_id, photo_id),
+    FOREIGN KEY (album_id) REFERENCES albums(id) ON DELETE CASCADE,
+    FOREIGN KEY (photo_id) REFERENCES photos(id) ON DELETE CASCADE
+);
+
 -- Per-directory custom prompts for LLM descriptions
 CREATE TABLE IF NOT EXISTS directory_prompts (
     directory TEXT PRIMARY KEY,
     custom_prompt TEXT NOT NULL,
     updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
+
+-- Named semantic search queries
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    query TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT 'all',
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Application bookkeeping such as when maintenance last ran
+CREATE TABLE IF NOT EXISTS app_state (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Events detected from clusters of described photos, named by the LLM
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    start_date TEXT NOT NULL,  -- taken_at of the first photo
+    end_date TEXT NOT NULL,    -- taken_at of the last photo
+    photo_count INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_events_start ON events(start_date);
+
+-- Reverse geocoded place names, keyed by coordinates on a 0.01 degree grid
+CREATE TABLE IF NOT EXISTS place_names (
+    lat_key INTEGER NOT NULL,
+    lon_key INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (lat_key, lon_key)
+);
+
+-- Slideshow auto-advance interval last chosen in each directory
+CREATE TABLE IF NOT EXISTS slideshow_prefs (
+    directory TEXT PRIMARY KEY,
+    interval_secs REAL NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
 "#;
 
 /// Migration statements for existing databases.
@@ -263,4 +325,24 @@ pub const MIGRATIONS: &[&str] = &[
     "ALTER TABLE photos ADD COLUMN user_rotation INTEGER DEFAULT 0",
     // Add directory_prompts table (v0.3.0)
     "CREATE TABLE IF NOT EXISTS directory_prompts (directory TEXT PRIMARY KEY, custom_prompt TEXT NOT NULL, updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP)",
+    // Add gps_source column to tell manual coordinates from EXIF ones
+    "ALTER TABLE photos ADD COLUMN gps_source TEXT",
+    // Add rating column for user star ratings
+    "ALTER TABLE photos ADD COLUMN rating INTEGER",
+    // Add real_path column so symlinked copies are not reported as duplicates
+    "ALTER TABLE photos ADD COLUMN real_path TEXT",
+    // Add embedding_model column so stale face embeddings can be regenerated
+    "ALTER TABLE faces ADD COLUMN embedding_model TEXT",
+    // Embeddings stored before models were tracked came from ArcFace ResNet100
+    "UPDATE faces SET embedding_model = 'arcface-resnet100-int8' WHERE embedding IS NOT NULL AND embedding_model IS NULL",
+    // Add color_label column for color-coded culling
+    "ALTER TABLE photos ADD COLUMN color_label TEXT",
+    // Add auto_sort column so albums can open sorted
+    "ALTER TABLE albums ADD COLUMN auto_sort TEXT",
+    // Add geo_radius column for geofenced smart albums
+    "ALTER TABLE albums ADD COLUMN geo_radius TEXT",
+    // Add color_palette column for searching by color
+    "ALTER TABLE photos ADD COLUMN color_palette TEXT",
+    // Add parent_id column so tags can be nested
+    "ALTER TABLE user_tags ADD COLUMN parent_id INTEGER",
 ];
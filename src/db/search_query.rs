@@ -0,0 +1,236 @@
+//! Boolean search queries: `beach AND (sunset OR dusk) NOT dog`.
+//!
+//! Operators are only recognised in upper case, so ordinary phrases like
+//! "cats and dogs" are searched as written. Adjacent words form a single
+//! term, and adjacent terms or groups are combined with AND.
+
+/// Parsed search query
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchAst {
+    /// Words searched together, e.g. "golden hour"
+    Term(String),
+    And(Box<SearchAst>, Box<SearchAst>),
+    Or(Box<SearchAst>, Box<SearchAst>),
+    Not(Box<SearchAst>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+type Tokens = std::iter::Peekable<std::vec::IntoIter<Token>>;
+
+impl SearchAst {
+    /// A plain query without operators.
+    pub fn is_term(&self) -> bool {
+        matches!(self, SearchAst::Term(_))
+    }
+
+    /// Distinct terms in the order they appear.
+    pub fn terms(&self) -> Vec<&str> {
+        let mut terms = Vec::new();
+        self.collect_terms(&mut terms);
+        terms
+    }
+
+    fn collect_terms<'a>(&'a self, terms: &mut Vec<&'a str>) {
+        match self {
+            SearchAst::Term(t) => {
+                if !terms.contains(&t.as_str()) {
+                    terms.push(t);
+                }
+            }
+            SearchAst::And(a, b) | SearchAst::Or(a, b) => {
+                a.collect_terms(terms);
+                b.collect_terms(terms);
+            }
+            SearchAst::Not(a) => a.collect_terms(terms),
+        }
+    }
+
+    /// Combine per-term scores in 0..=1: AND takes the lowest, OR the
+    /// highest, and NOT inverts.
+    pub fn score<F: Fn(&str) -> f32>(&self, term_score: &F) -> f32 {
+        match self {
+            SearchAst::Term(t) => term_score(t),
+            SearchAst::And(a, b) => a.score(term_score).min(b.score(term_score)),
+            SearchAst::Or(a, b) => a.score(term_score).max(b.score(term_score)),
+            SearchAst::Not(a) => 1.0 - a.score(term_score),
+        }
+    }
+}
+
+/// Parse a search query. Malformed input is handled leniently: unclosed
+/// parentheses are closed at the end, and stray operators are ignored.
+pub fn parse_search_query(input: &str) -> SearchAst {
+    let mut tokens = tokenize(input).into_iter().peekable();
+    let mut ast = None;
+    while tokens.peek().is_some() {
+        let next = parse_or(&mut tokens);
+        ast = combine(ast, next, SearchAst::And);
+        // Skip a closing parenthesis without an opening one
+        if tokens.peek() == Some(&Token::Close) {
+            tokens.next();
+        }
+    }
+    ast.unwrap_or_else(|| SearchAst::Term(String::new()))
+}
+
+/// Fraction of a term's words found in already lowercased text.
+pub fn keyword_score(term: &str, text_lower: &str) -> f32 {
+    let term = term.to_lowercase();
+    let words: Vec<&str> = term.split_whitespace().collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+    let found = words.iter().filter(|w| text_lower.contains(*w)).count();
+    found as f32 / words.len() as f32
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, tokens: &mut Vec<Token>| {
+        if !word.is_empty() {
+            tokens.push(match word.as_str() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                _ => Token::Word(word.clone()),
+            });
+            word.clear();
+        }
+    };
+    for c in input.chars() {
+        match c {
+            '(' | ')' => {
+                flush(&mut word, &mut tokens);
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            c if c.is_whitespace() => flush(&mut word, &mut tokens),
+            c => word.push(c),
+        }
+    }
+    flush(&mut word, &mut tokens);
+    tokens
+}
+
+fn combine(
+    left: Option<SearchAst>,
+    right: Option<SearchAst>,
+    op: fn(Box<SearchAst>, Box<SearchAst>) -> SearchAst,
+) -> Option<SearchAst> {
+    match (left, right) {
+        (Some(l), Some(r)) => Some(op(Box::new(l), Box::new(r))),
+        (l, r) => l.or(r),
+    }
+}
+
+fn parse_or(tokens: &mut Tokens) -> Option<SearchAst> {
+    let mut left = parse_and(tokens);
+    while tokens.peek() == Some(&Token::Or) {
+        tokens.next();
+        let right = parse_and(tokens);
+        left = combine(left, right, SearchAst::Or);
+    }
+    left
+}
+
+fn parse_and(tokens: &mut Tokens) -> Option<SearchAst> {
+    let mut left = parse_unary(tokens);
+    loop {
+        match tokens.peek() {
+            Some(Token::And) => {
+                tokens.next();
+            }
+            // Terms and groups next to each other are ANDed
+            Some(Token::Not) | Some(Token::Open) | Some(Token::Word(_)) => {}
+            _ => break,
+        }
+        let right = parse_unary(tokens);
+        left = combine(left, right, SearchAst::And);
+    }
+    left
+}
+
+fn parse_unary(tokens: &mut Tokens) -> Option<SearchAst> {
+    match tokens.peek()? {
+        Token::Not => {
+            tokens.next();
+            parse_unary(tokens).map(|a| SearchAst::Not(Box::new(a)))
+        }
+        Token::Open => {
+            tokens.next();
+            let inner = parse_or(tokens);
+            if tokens.peek() == Some(&Token::Close) {
+                tokens.next();
+            }
+            inner
+        }
+        Token::Word(_) => {
+            let mut words = Vec::new();
+            while let Some(Token::Word(w)) = tokens.peek() {
+                words.push(w.clone());
+                tokens.next();
+            }
+            Some(SearchAst::Term(words.join(" ")))
+        }
+        Token::And | Token::Or | Token::Close => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(t: &str) -> Box<SearchAst> {
+        Box::new(SearchAst::Term(t.to_string()))
+    }
+
+    #[test]
+    fn test_plain_query_is_one_term() {
+        assert_eq!(parse_search_query("golden hour landscapes"), *term("golden hour landscapes"));
+        // Lowercase operators are ordinary words
+        assert_eq!(parse_search_query("cats and dogs"), *term("cats and dogs"));
+    }
+
+    #[test]
+    fn test_precedence_and_grouping() {
+        assert_eq!(
+            parse_search_query("beach OR lake AND sunset"),
+            SearchAst::Or(term("beach"), Box::new(SearchAst::And(term("lake"), term("sunset"))))
+        );
+        assert_eq!(
+            parse_search_query("beach AND (sunset OR dusk) NOT dog"),
+            SearchAst::And(
+                Box::new(SearchAst::And(term("beach"), Box::new(SearchAst::Or(term("sunset"), term("dusk"))))),
+                Box::new(SearchAst::Not(term("dog"))),
+            )
+        );
+    }
+
+    #[test]
+    fn test_malformed_input_is_lenient() {
+        assert_eq!(parse_search_query("(beach OR lake"), SearchAst::Or(term("beach"), term("lake")));
+        assert_eq!(parse_search_query("AND beach)"), *term("beach"));
+        assert_eq!(parse_search_query("beach NOT"), *term("beach"));
+    }
+
+    #[test]
+    fn test_score_and_terms() {
+        let ast = parse_search_query("beach AND (sunset OR dusk) NOT dog");
+        assert_eq!(ast.terms(), vec!["beach", "sunset", "dusk", "dog"]);
+
+        let text = "a dog on the beach at sunset";
+        assert_eq!(ast.score(&|t| keyword_score(t, text)), 0.0);
+        let text = "an empty beach at dusk";
+        assert_eq!(ast.score(&|t| keyword_score(t, text)), 1.0);
+        assert_eq!(keyword_score("Golden Hour", "golden light"), 0.5);
+    }
+}
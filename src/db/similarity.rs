@@ -1,6 +1,10 @@
 //! Types for duplicate detection and similarity grouping.
 
-#[derive(Debug, Clone)]
+use serde::Serialize;
+
+use super::embeddings::cosine_similarity;
+
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct PhotoRecord {
     pub id: i64,
@@ -15,7 +19,7 @@ pub struct PhotoRecord {
     pub marked_for_deletion: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct SimilarityGroup {
     pub id: i64,
@@ -35,6 +39,83 @@ pub fn hamming_distance(hash1: &str, hash2: &str) -> anyhow::Result<u32> {
     Ok(h1.dist(&h2))
 }
 
+/// A photo with the name of its embedding model and the embedding.
+pub type EmbeddedPhoto = (PhotoRecord, String, Vec<f32>);
+
+/// Group near-duplicate shots by embedding: photos whose cosine similarity
+/// is at least `threshold`. Only photos in the same directory embedded by
+/// the same model are compared, which keeps the pairwise comparison small.
+pub fn group_by_embedding(photos: Vec<EmbeddedPhoto>, threshold: f32) -> Vec<SimilarityGroup> {
+    use std::collections::BTreeMap;
+
+    // Bucket by directory and model
+    let mut buckets: BTreeMap<(String, String), Vec<EmbeddedPhoto>> = BTreeMap::new();
+    for entry in photos {
+        let dir = std::path::Path::new(&entry.0.path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        buckets.entry((dir, entry.1.clone())).or_default().push(entry);
+    }
+
+    let mut groups = Vec::new();
+    for bucket in buckets.into_values() {
+        let mut grouped = vec![false; bucket.len()];
+        for (i, (photo, _, embedding)) in bucket.iter().enumerate() {
+            if grouped[i] {
+                continue;
+            }
+            let mut similar_photos = vec![photo.clone()];
+            for (j, (other, _, other_embedding)) in bucket.iter().enumerate().skip(i + 1) {
+                if !grouped[j] && cosine_similarity(embedding, other_embedding) >= threshold {
+                    similar_photos.push(other.clone());
+                    grouped[j] = true;
+                }
+            }
+            if similar_photos.len() > 1 {
+                groups.push(SimilarityGroup {
+                    id: 0,
+                    group_type: "similar".to_string(),
+                    photos: similar_photos,
+                });
+            }
+        }
+    }
+    groups
+}
+
+/// Narrow duplicate groups to photos in or under `staging` and `library`,
+/// keeping only groups with a copy in each. Library copies come first and
+/// are kept; staging copies are marked for deletion.
+pub fn restrict_to_directories(
+    groups: Vec<SimilarityGroup>,
+    staging: &std::path::Path,
+    library: &std::path::Path,
+) -> Vec<SimilarityGroup> {
+    groups
+        .into_iter()
+        .filter_map(|mut group| {
+            let (mut kept, mut staged): (Vec<PhotoRecord>, Vec<PhotoRecord>) = group
+                .photos
+                .into_iter()
+                .filter(|p| {
+                    let path = std::path::Path::new(&p.path);
+                    path.starts_with(library) || path.starts_with(staging)
+                })
+                // A library nested inside the staging directory still wins
+                .partition(|p| std::path::Path::new(&p.path).starts_with(library));
+            if kept.is_empty() || staged.is_empty() {
+                return None;
+            }
+            kept.iter_mut().for_each(|p| p.marked_for_deletion = false);
+            staged.iter_mut().for_each(|p| p.marked_for_deletion = true);
+            kept.extend(staged);
+            group.photos = kept;
+            Some(group)
+        })
+        .collect()
+}
+
 pub fn calculate_quality_score(photo: &PhotoRecord) -> i32 {
     let mut score = 0;
 
@@ -53,3 +134,63 @@ pub fn calculate_quality_score(photo: &PhotoRecord) -> i32 {
 
     score
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn photo(id: i64, path: &str) -> PhotoRecord {
+        PhotoRecord {
+            id,
+            path: path.to_string(),
+            filename: path.rsplit('/').next().unwrap_or(path).to_string(),
+            size_bytes: 0,
+            width: None,
+            height: None,
+            sha256_hash: None,
+            perceptual_hash: None,
+            taken_at: None,
+            marked_for_deletion: false,
+        }
+    }
+
+    #[test]
+    fn test_group_by_embedding_within_directory_and_model() {
+        let clip = "clip".to_string();
+        let photos = vec![
+            (photo(1, "/a/1.jpg"), clip.clone(), vec![1.0, 0.0]),
+            (photo(2, "/a/2.jpg"), clip.clone(), vec![0.99, 0.05]),
+            (photo(3, "/a/3.jpg"), clip.clone(), vec![0.0, 1.0]),
+            // Same embedding, but another directory or model
+            (photo(4, "/b/4.jpg"), clip.clone(), vec![1.0, 0.0]),
+            (photo(5, "/a/5.jpg"), "text".to_string(), vec![1.0, 0.0]),
+        ];
+
+        let groups = group_by_embedding(photos, 0.95);
+        assert_eq!(groups.len(), 1);
+        let ids: Vec<i64> = groups[0].photos.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(groups[0].group_type, "similar");
+    }
+
+    #[test]
+    fn test_restrict_to_directories_keeps_library_copy() {
+        let group = |photos: Vec<PhotoRecord>| SimilarityGroup {
+            id: 0,
+            group_type: "exact".to_string(),
+            photos,
+        };
+        let groups = vec![
+            group(vec![photo(1, "/import/a.jpg"), photo(2, "/photos/2024/a.jpg"), photo(3, "/other/a.jpg")]),
+            // Both copies in the library: not a cross-directory duplicate
+            group(vec![photo(4, "/photos/b.jpg"), photo(5, "/photos/2023/b.jpg")]),
+            group(vec![photo(6, "/import/c.jpg"), photo(7, "/elsewhere/c.jpg")]),
+        ];
+
+        let groups = restrict_to_directories(groups, Path::new("/import"), Path::new("/photos"));
+        assert_eq!(groups.len(), 1);
+        let photos: Vec<(i64, bool)> = groups[0].photos.iter().map(|p| (p.id, p.marked_for_deletion)).collect();
+        assert_eq!(photos, vec![(2, false), (1, true)]);
+    }
+}
@@ -1,22 +1,30 @@
 //! SQLite backend implementation.
 
 use anyhow::Result;
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use super::{PhotoMetadata, exif_orientation_to_degrees, read_exif_rotation_from_file};
+use super::{DirectoryPhotoStats, DirectorySummary, PhotoMetadata, PhotoTimestamps, exif_orientation_to_degrees, read_exif_rotation_from_file};
 use super::schema::{SCHEMA, MIGRATIONS};
-use super::embeddings::{SearchResult, EmbeddingRecord, embedding_to_bytes, bytes_to_embedding, cosine_similarity};
+use super::search_query::{SearchAst, keyword_score, parse_search_query};
+use super::embeddings::{SavedSearch, SearchFilter, SearchResult, SearchScope, EmbeddingRecord, embedding_to_bytes, bytes_to_embedding, cosine_similarity};
 use super::faces::{
-    BoundingBox, Face, FaceCluster, FaceWithPhoto, Person,
+    BoundingBox, Face, FaceCluster, FaceWithPhoto, Person, FACE_EMBEDDING_MODEL,
     embedding_to_bytes as face_embedding_to_bytes, bytes_to_embedding as face_bytes_to_embedding,
 };
 use super::similarity::PhotoRecord;
 use super::similarity::SimilarityGroup;
 use super::trash::TrashedPhoto;
 use super::schedule::{ScheduledTask, ScheduledTaskType, ScheduleStatus};
-use super::albums::{UserTag, Album};
-use super::similarity::hamming_distance;
+use super::albums::{smart_album_members, AlbumSort, GeoRadius, TagStat, UserTag, Album};
+use super::events::{Event, EventCandidate};
+use super::locations::{bounding_box, haversine_km};
+use super::similarity::{EmbeddedPhoto, group_by_embedding, hamming_distance};
+
+/// How long a connection waits for another connection's lock before
+/// failing with `SQLITE_BUSY`
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
 pub struct SqliteDb {
     pub(crate) conn: Connection,
@@ -28,6 +36,18 @@ impl SqliteDb {
             std::fs::create_dir_all(parent)?;
         }
         let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(Self { conn })
+    }
+
+    /// A connection that can only read, for background threads that run
+    /// while the scanner writes
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
         Ok(Self { conn })
     }
 
@@ -86,25 +106,97 @@ impl SqliteDb {
         let old_path_str = old_path.to_string_lossy();
         let new_path_str = new_path.to_string_lossy();
         self.conn.execute(
-            "UPDATE photos SET path = ? WHERE path = ?",
+            "UPDATE photos SET path = ?, real_path = NULL WHERE path = ?",
             rusqlite::params![new_path_str, old_path_str],
         )?;
         Ok(())
     }
 
-    pub fn get_photos_mtime_in_dir(&self, directory: &str) -> Result<Vec<(String, Option<String>)>> {
+    pub fn get_photos_mtime_in_dir(&self, directory: &str) -> Result<Vec<PhotoTimestamps>> {
         let mut stmt = self.conn.prepare(
-            "SELECT path, modified_at FROM photos WHERE directory = ?",
+            "SELECT path, modified_at, taken_at FROM photos WHERE directory = ?",
         )?;
         let results = stmt
             .query_map([directory], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
             })?
             .filter_map(|r| r.ok())
             .collect();
         Ok(results)
     }
 
+    pub fn get_photos_in_dir_date_range(&self, directory: &str, start: &str, end: &str) -> Result<Vec<String>> {
+        // taken_at is "YYYY-MM-DD HH:MM:SS"; compare on the date part only
+        let mut stmt = self.conn.prepare(
+            "SELECT path FROM photos WHERE directory = ? AND substr(taken_at, 1, 10) BETWEEN ? AND ?",
+        )?;
+        let results = stmt
+            .query_map([directory, start, end], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_photo_counts_by_date(&self, year: &str) -> Result<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT substr(taken_at, 1, 10) AS day, COUNT(*)
+            FROM photos
+            WHERE substr(taken_at, 1, 4) = ? AND trashed_at IS NULL
+            GROUP BY day
+            "#,
+        )?;
+        let results = stmt
+            .query_map([year], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_photos_taken_on(&self, date: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT path FROM photos WHERE substr(taken_at, 1, 10) = ? AND trashed_at IS NULL ORDER BY taken_at, path",
+        )?;
+        let results = stmt
+            .query_map([date], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_camera_models_in_dir(&self, directory: &str) -> Result<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT TRIM(COALESCE(camera_make, '') || ' ' || COALESCE(camera_model, '')) AS camera, COUNT(*)
+            FROM photos
+            WHERE directory = ? AND trashed_at IS NULL
+            GROUP BY camera
+            HAVING camera <> ''
+            ORDER BY camera
+            "#,
+        )?;
+        let results = stmt
+            .query_map([directory], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_photos_in_dir_by_camera(&self, directory: &str, camera: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT path FROM photos WHERE directory = ? AND TRIM(COALESCE(camera_make, '') || ' ' || COALESCE(camera_model, '')) = ? AND trashed_at IS NULL",
+        )?;
+        let results = stmt
+            .query_map([directory, camera], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
     pub fn get_photo_metadata(&self, path: &Path) -> Result<Option<PhotoMetadata>> {
         let path_str = path.to_string_lossy();
         let result = self.conn.query_row(
@@ -115,7 +207,8 @@ impl SqliteDb {
                    gps_latitude, gps_longitude,
                    modified_at, scanned_at,
                    description, tags,
-                   sha256_hash, perceptual_hash
+                   sha256_hash, perceptual_hash, gps_source,
+                   rating, color_label, exif_orientation
             FROM photos
             WHERE path = ?
             "#,
@@ -140,12 +233,16 @@ impl SqliteDb {
                     taken_at: row.get(15)?,
                     gps_latitude: row.get(16)?,
                     gps_longitude: row.get(17)?,
+                    gps_source: row.get(24)?,
+                    exif_orientation: row.get(27)?,
                     modified_at: row.get(18)?,
                     scanned_at: row.get(19)?,
                     description: row.get(20)?,
                     tags: row.get(21)?,
                     sha256_hash: row.get(22)?,
                     perceptual_hash: row.get(23)?,
+                    rating: row.get(25)?,
+                    color_label: row.get(26)?,
                     face_count: 0,
                     people_names: Vec::new(),
                 })
@@ -182,18 +279,21 @@ impl SqliteDb {
         }
     }
 
-    pub fn semantic_search_by_text(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        let query_lower = query.to_lowercase();
-        let query_words: Vec<&str> = query_lower.split_whitespace().collect();
-        let mut stmt = self.conn.prepare(
+    pub fn semantic_search_by_text(&self, query: &str, limit: usize, filter: &SearchFilter) -> Result<Vec<SearchResult>> {
+        let query = parse_search_query(query);
+        let (with, conditions, params) = filter_sql("id", filter);
+        let mut stmt = self.conn.prepare(&format!(
             r#"
+            {}
             SELECT id, path, filename, description
             FROM photos
-            WHERE description IS NOT NULL
+            WHERE description IS NOT NULL {}
             "#,
-        )?;
+            with,
+            conditions.iter().map(|c| format!("AND {}", c)).collect::<Vec<_>>().join(" ")
+        ))?;
         let mut results: Vec<SearchResult> = stmt
-            .query_map([], |row| {
+            .query_map(rusqlite::params_from_iter(params), |row| {
                 let description: String = row.get(3)?;
                 Ok((
                     row.get::<_, i64>(0)?,
@@ -205,14 +305,8 @@ impl SqliteDb {
             .filter_map(|r| r.ok())
             .filter_map(|(id, path, filename, description)| {
                 let desc_lower = description.to_lowercase();
-                let mut score = 0.0f32;
-                for word in &query_words {
-                    if desc_lower.contains(word) {
-                        score += 1.0;
-                    }
-                }
-                if score > 0.0 {
-                    let similarity = score / query_words.len() as f32;
+                let similarity = query.score(&|term| keyword_score(term, &desc_lower));
+                if similarity > 0.0 {
                     Some(SearchResult { photo_id: id, path, filename, similarity, description: Some(description) })
                 } else {
                     None
@@ -224,6 +318,19 @@ impl SqliteDb {
         Ok(results)
     }
 
+    pub fn get_all_exif(&self, path: &str) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT all_exif FROM photos WHERE path = ?",
+            [path],
+            |row| row.get(0),
+        );
+        match result {
+            Ok(all_exif) => Ok(all_exif),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn get_photo_rotation(&self, path: &Path) -> Result<i32> {
         let path_str = path.to_string_lossy();
         let result = self.conn.query_row(
@@ -257,6 +364,38 @@ impl SqliteDb {
         Ok(())
     }
 
+    pub fn set_photo_rating(&self, photo_id: i64, rating: Option<i32>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE photos SET rating = ? WHERE id = ?",
+            rusqlite::params![rating, photo_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_photo_color_label(&self, photo_id: i64, label: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE photos SET color_label = ? WHERE id = ?",
+            rusqlite::params![label, photo_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_manual_gps(&self, photo_id: i64, lat: f64, lon: f64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE photos SET gps_latitude = ?, gps_longitude = ?, gps_source = 'manual' WHERE id = ?",
+            rusqlite::params![lat, lon, photo_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_taken_at_if_missing(&self, photo_id: i64, taken_at: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE photos SET taken_at = ? WHERE id = ? AND taken_at IS NULL",
+            rusqlite::params![taken_at, photo_id],
+        )?;
+        Ok(())
+    }
+
     fn ensure_photo_exists(&self, path: &Path) -> Result<()> {
         let path_str = path.to_string_lossy();
         let exists: bool = self.conn.query_row(
@@ -421,12 +560,13 @@ impl SqliteDb {
     ) -> Result<i64> {
         let embedding_bytes = embedding.map(face_embedding_to_bytes);
         let embedding_dim = embedding.map(|e| e.len() as i32);
+        let embedding_model = embedding.map(|_| FACE_EMBEDDING_MODEL);
         self.conn.execute(
             r#"
-            INSERT INTO faces (photo_id, bbox_x, bbox_y, bbox_w, bbox_h, embedding, embedding_dim, confidence)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO faces (photo_id, bbox_x, bbox_y, bbox_w, bbox_h, embedding, embedding_dim, embedding_model, confidence)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
-            rusqlite::params![photo_id, bbox.x, bbox.y, bbox.width, bbox.height, embedding_bytes, embedding_dim, confidence],
+            rusqlite::params![photo_id, bbox.x, bbox.y, bbox.width, bbox.height, embedding_bytes, embedding_dim, embedding_model, confidence],
         )?;
         Ok(self.conn.last_insert_rowid())
     }
@@ -605,17 +745,57 @@ impl SqliteDb {
         Ok(results)
     }
 
+    pub fn get_unclassified_faces(&self, limit: usize) -> Result<Vec<(i64, Vec<f32>)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.id, f.embedding
+            FROM faces f
+            WHERE f.embedding IS NOT NULL AND f.embedding_model IS ? AND f.person_id IS NULL
+              AND NOT EXISTS (SELECT 1 FROM face_cluster_members m WHERE m.face_id = f.id)
+            ORDER BY f.id
+            LIMIT ?
+            "#,
+        )?;
+        let results = stmt
+            .query_map(rusqlite::params![FACE_EMBEDDING_MODEL, limit as i64], |row| {
+                let bytes: Vec<u8> = row.get(1)?;
+                Ok((row.get(0)?, face_bytes_to_embedding(&bytes)))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_cluster_member_embeddings(&self) -> Result<Vec<(i64, Vec<f32>)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT m.cluster_id, f.embedding
+            FROM face_cluster_members m
+            JOIN faces f ON f.id = m.face_id
+            WHERE f.embedding IS NOT NULL
+            "#,
+        )?;
+        let results = stmt
+            .query_map([], |row| {
+                let bytes: Vec<u8> = row.get(1)?;
+                Ok((row.get(0)?, face_bytes_to_embedding(&bytes)))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
     pub fn get_faces_without_embeddings(&self, limit: usize) -> Result<Vec<(i64, i64, BoundingBox)>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT id, photo_id, bbox_x, bbox_y, bbox_w, bbox_h
             FROM faces
-            WHERE embedding IS NULL
+            WHERE embedding IS NULL OR embedding_model IS NOT ?
             LIMIT ?
             "#,
         )?;
         let results = stmt
-            .query_map([limit as i64], |row| {
+            .query_map(rusqlite::params![FACE_EMBEDDING_MODEL, limit as i64], |row| {
                 Ok((row.get(0)?, row.get(1)?, BoundingBox { x: row.get(2)?, y: row.get(3)?, width: row.get(4)?, height: row.get(5)? }))
             })?
             .filter_map(|r| r.ok())
@@ -640,16 +820,16 @@ impl SqliteDb {
         let embedding_bytes = face_embedding_to_bytes(embedding);
         let embedding_dim = embedding.len() as i32;
         self.conn.execute(
-            "UPDATE faces SET embedding = ?, embedding_dim = ? WHERE id = ?",
-            rusqlite::params![embedding_bytes, embedding_dim, face_id],
+            "UPDATE faces SET embedding = ?, embedding_dim = ?, embedding_model = ? WHERE id = ?",
+            rusqlite::params![embedding_bytes, embedding_dim, FACE_EMBEDDING_MODEL, face_id],
         )?;
         Ok(())
     }
 
     pub fn count_faces_without_embeddings(&self) -> Result<i64> {
         let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM faces WHERE embedding IS NULL",
-            [],
+            "SELECT COUNT(*) FROM faces WHERE embedding IS NULL OR embedding_model IS NOT ?",
+            [FACE_EMBEDDING_MODEL],
             |row| row.get(0),
         )?;
         Ok(count)
@@ -799,14 +979,166 @@ impl SqliteDb {
         Ok(records)
     }
 
-    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize, min_similarity: f32) -> Result<Vec<SearchResult>> {
-        let embeddings = self.get_all_embeddings()?;
+    pub fn save_search(&self, name: &str, query: &str, scope: &SearchScope) -> Result<i64> {
+        self.conn.execute(
+            r#"
+            INSERT INTO saved_searches (name, query, scope) VALUES (?, ?, ?)
+            ON CONFLICT(name) DO UPDATE SET query = excluded.query, scope = excluded.scope
+            "#,
+            rusqlite::params![name, query, scope.to_db_string()],
+        )?;
+        let id = self.conn.query_row(
+            "SELECT id FROM saved_searches WHERE name = ?",
+            [name],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    pub fn get_saved_searches(&self) -> Result<Vec<SavedSearch>> {
+        let mut stmt = self.conn.prepare("SELECT id, name, query, scope FROM saved_searches ORDER BY name")?;
+        let searches = stmt
+            .query_map([], |row| {
+                let scope: String = row.get(3)?;
+                Ok(SavedSearch {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    query: row.get(2)?,
+                    scope: SearchScope::from_db_string(&scope).unwrap_or_default(),
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(searches)
+    }
+
+    pub fn delete_saved_search(&self, id: i64) -> Result<()> {
+        self.conn.execute("DELETE FROM saved_searches WHERE id = ?", [id])?;
+        Ok(())
+    }
+
+    pub fn set_color_palette(&self, path: &str, palette_json: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE photos SET color_palette = ? WHERE path = ?",
+            rusqlite::params![palette_json, path],
+        )?;
+        Ok(())
+    }
+
+    /// Photos in scope that have a color palette, with the palette's JSON
+    pub fn get_color_palettes(&self, filter: &SearchFilter) -> Result<Vec<(SearchResult, String)>> {
+        let (with, mut conditions, params) = filter_sql("id", filter);
+        conditions.push("color_palette IS NOT NULL AND trashed_at IS NULL".to_string());
+        let mut stmt = self.conn.prepare(&format!(
+            "{} SELECT id, path, filename, description, color_palette FROM photos WHERE {}",
+            with,
+            conditions.join(" AND ")
+        ))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(params), |row| {
+                let result = SearchResult {
+                    photo_id: row.get(0)?,
+                    path: row.get(1)?,
+                    filename: row.get(2)?,
+                    similarity: 0.0,
+                    description: row.get(3)?,
+                };
+                Ok((result, row.get(4)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
+    /// Photos matching a filter's face count limits, most faces first
+    pub fn get_photos_by_face_count(&self, filter: &SearchFilter, limit: usize) -> Result<Vec<SearchResult>> {
+        let (with, conditions, mut params) = filter_sql("id", filter);
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            {}
+            SELECT id, path, filename, description
+            FROM photos
+            WHERE {}
+            ORDER BY (SELECT face_count FROM face_counts WHERE photo_id = photos.id) DESC, path
+            LIMIT ?
+            "#,
+            if with.is_empty() { FACE_COUNTS_CTE } else { with },
+            if conditions.is_empty() { "1 = 1".to_string() } else { conditions.join(" AND ") }
+        ))?;
+        params.push(rusqlite::types::Value::Integer(limit as i64));
+        let results = stmt
+            .query_map(rusqlite::params_from_iter(params), |row| {
+                Ok(SearchResult {
+                    photo_id: row.get(0)?,
+                    path: row.get(1)?,
+                    filename: row.get(2)?,
+                    similarity: 1.0,
+                    description: row.get(3)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    fn get_embeddings_in_scope(&self, filter: &SearchFilter) -> Result<Vec<EmbeddingRecord>> {
+        let (with, conditions, params) = filter_sql("photo_id", filter);
+        if conditions.is_empty() {
+            return self.get_all_embeddings();
+        }
+        let mut stmt = self.conn.prepare(&format!(
+            "{} SELECT photo_id, embedding, model_name FROM embeddings WHERE {}",
+            with,
+            conditions.join(" AND ")
+        ))?;
+        let records = stmt
+            .query_map(rusqlite::params_from_iter(params), |row| {
+                let bytes: Vec<u8> = row.get(1)?;
+                Ok(EmbeddingRecord {
+                    photo_id: row.get(0)?,
+                    embedding: bytes_to_embedding(&bytes),
+                    model_name: row.get(2)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(records)
+    }
+
+    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize, min_similarity: f32, filter: &SearchFilter) -> Result<Vec<SearchResult>> {
+        self.rank_embeddings(filter, limit, min_similarity, |embedding| cosine_similarity(query_embedding, embedding))
+    }
+
+    /// Search with a boolean query, scoring each term against its own embedding.
+    pub fn semantic_search_query(
+        &self,
+        query: &SearchAst,
+        term_embeddings: &HashMap<String, Vec<f32>>,
+        limit: usize,
+        min_similarity: f32,
+        filter: &SearchFilter,
+    ) -> Result<Vec<SearchResult>> {
+        self.rank_embeddings(filter, limit, min_similarity, |embedding| {
+            query.score(&|term| {
+                term_embeddings
+                    .get(term)
+                    .map(|t| cosine_similarity(t, embedding))
+                    .unwrap_or(0.0)
+            })
+        })
+    }
+
+    fn rank_embeddings(
+        &self,
+        filter: &SearchFilter,
+        limit: usize,
+        min_similarity: f32,
+        score: impl Fn(&[f32]) -> f32,
+    ) -> Result<Vec<SearchResult>> {
+        let embeddings = self.get_embeddings_in_scope(filter)?;
         let mut results: Vec<(i64, f32)> = embeddings
             .iter()
-            .map(|record| {
-                let similarity = cosine_similarity(query_embedding, &record.embedding);
-                (record.photo_id, similarity)
-            })
+            .map(|record| (record.photo_id, score(&record.embedding)))
             .filter(|(_, sim)| *sim >= min_similarity)
             .collect();
         results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
@@ -881,6 +1213,47 @@ impl SqliteDb {
         Ok(results)
     }
 
+    pub fn get_photos_with_stale_clip_embeddings_in_dir(&self, directory: &str, model_name: &str, limit: usize) -> Result<Vec<(i64, String)>> {
+        let dir_pattern = if directory.ends_with('/') {
+            format!("{}%", directory)
+        } else {
+            format!("{}/%", directory)
+        };
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT p.id, p.path
+            FROM photos p
+            LEFT JOIN embeddings e ON p.id = e.photo_id
+            WHERE (e.photo_id IS NULL OR (e.model_name LIKE 'clip%' AND e.model_name != ?))
+              AND p.path LIKE ?
+            LIMIT ?
+            "#,
+        )?;
+        let results = stmt
+            .query_map(rusqlite::params![model_name, dir_pattern, limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
+    pub fn get_latest_clip_model(&self) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT model_name FROM embeddings WHERE model_name LIKE 'clip%' ORDER BY created_at DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        );
+        match result {
+            Ok(name) => Ok(Some(name)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn delete_embeddings_for_model(&self, model_name: &str) -> Result<usize> {
+        let count = self.conn.execute("DELETE FROM embeddings WHERE model_name = ?", [model_name])?;
+        Ok(count)
+    }
+
     pub fn count_embeddings(&self) -> Result<i64> {
         let count: i64 = self.conn.query_row("SELECT COUNT(*) FROM embeddings", [], |row| row.get(0))?;
         Ok(count)
@@ -893,11 +1266,11 @@ impl SqliteDb {
     pub fn find_exact_duplicates(&self) -> Result<Vec<SimilarityGroup>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT sha256_hash, COUNT(*) as cnt
+            SELECT sha256_hash
             FROM photos
             WHERE sha256_hash IS NOT NULL
             GROUP BY sha256_hash
-            HAVING cnt > 1
+            HAVING COUNT(DISTINCT COALESCE(real_path, path)) > 1
             "#,
         )?;
         let duplicate_hashes: Vec<String> = stmt
@@ -958,13 +1331,58 @@ impl SqliteDb {
         Ok(groups)
     }
 
+    pub fn find_embedding_clusters(&self, threshold: f32) -> Result<Vec<SimilarityGroup>> {
+        let photos = self.get_all_photos_with_embeddings()?;
+        Ok(group_by_embedding(photos, threshold))
+    }
+
+    fn get_all_photos_with_embeddings(&self) -> Result<Vec<EmbeddedPhoto>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT p.id, p.path, p.filename, p.size_bytes, p.width, p.height,
+                   p.sha256_hash, p.perceptual_hash, p.taken_at, p.marked_for_deletion,
+                   e.model_name, e.embedding
+            FROM photos p
+            JOIN embeddings e ON e.photo_id = p.id
+            ORDER BY p.path
+            "#,
+        )?;
+        let photos = stmt
+            .query_map([], |row| {
+                let bytes: Vec<u8> = row.get(11)?;
+                Ok((
+                    PhotoRecord {
+                        id: row.get(0)?,
+                        path: row.get(1)?,
+                        filename: row.get(2)?,
+                        size_bytes: row.get(3)?,
+                        width: row.get(4)?,
+                        height: row.get(5)?,
+                        sha256_hash: row.get(6)?,
+                        perceptual_hash: row.get(7)?,
+                        taken_at: row.get(8)?,
+                        marked_for_deletion: row.get::<_, i32>(9)? != 0,
+                    },
+                    row.get(10)?,
+                    bytes_to_embedding(&bytes),
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(photos)
+    }
+
     fn get_photos_by_sha256(&self, sha256: &str) -> Result<Vec<PhotoRecord>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT id, path, filename, size_bytes, width, height,
                    sha256_hash, perceptual_hash, taken_at, marked_for_deletion
             FROM photos
-            WHERE sha256_hash = ?
+            WHERE id IN (
+                SELECT MIN(id) FROM photos
+                WHERE sha256_hash = ?
+                GROUP BY COALESCE(real_path, path)
+            )
             ORDER BY taken_at, path
             "#,
         )?;
@@ -1095,6 +1513,7 @@ impl SqliteDb {
             r#"
             UPDATE photos
             SET path = ?,
+                real_path = NULL,
                 original_path = ?,
                 trashed_at = ?,
                 marked_for_deletion = 0
@@ -1140,6 +1559,7 @@ impl SqliteDb {
             r#"
             UPDATE photos
             SET path = original_path,
+                real_path = NULL,
                 original_path = NULL,
                 trashed_at = NULL
             WHERE id = ?
@@ -1433,11 +1853,66 @@ impl SqliteDb {
         }
     }
 
+    pub fn get_tag_stats(&self) -> Result<Vec<TagStat>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT t.id, t.name, t.color, COUNT(pt.photo_id) AS photo_count, t.parent_id
+            FROM user_tags t
+            LEFT JOIN photo_user_tags pt ON pt.tag_id = t.id
+            GROUP BY t.id, t.name, t.color, t.parent_id
+            ORDER BY photo_count DESC, t.name
+            "#,
+        )?;
+        let stats = stmt
+            .query_map([], |row| {
+                Ok(TagStat {
+                    tag: UserTag { id: row.get(0)?, name: row.get(1)?, color: row.get(2)? },
+                    photo_count: row.get(3)?,
+                    parent_id: row.get(4)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(stats)
+    }
+
     pub fn delete_tag(&self, tag_id: i64) -> Result<()> {
+        // Foreign keys are not enforced, so the cascade has to be done here
+        self.conn.execute("DELETE FROM photo_user_tags WHERE tag_id = ?", [tag_id])?;
+        // Nested tags move up to the deleted tag's parent
+        self.conn.execute(
+            "UPDATE user_tags SET parent_id = (SELECT parent_id FROM user_tags WHERE id = ?1) WHERE parent_id = ?1",
+            [tag_id],
+        )?;
         self.conn.execute("DELETE FROM user_tags WHERE id = ?", [tag_id])?;
         Ok(())
     }
 
+    pub fn set_tag_parent(&self, tag_id: i64, parent_id: Option<i64>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE user_tags SET parent_id = ? WHERE id = ?",
+            rusqlite::params![parent_id, tag_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_tag_descendants(&self, tag_id: i64) -> Result<Vec<i64>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            WITH RECURSIVE tag_tree(id) AS (
+                SELECT ?
+                UNION
+                SELECT t.id FROM user_tags t JOIN tag_tree tt ON t.parent_id = tt.id
+            )
+            SELECT id FROM tag_tree
+            "#,
+        )?;
+        let ids = stmt
+            .query_map([tag_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<i64>>>()?;
+        Ok(ids)
+    }
+
     pub fn rename_tag(&self, tag_id: i64, new_name: &str) -> Result<()> {
         self.conn.execute(
             "UPDATE user_tags SET name = ? WHERE id = ?",
@@ -1446,6 +1921,22 @@ impl SqliteDb {
         Ok(())
     }
 
+    pub fn get_photos_in_dir_with_tag(&self, directory: &str, tag_id: i64) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT p.path
+            FROM photos p
+            JOIN photo_user_tags pt ON pt.photo_id = p.id
+            WHERE p.directory = ? AND pt.tag_id = ?
+            "#,
+        )?;
+        let results = stmt
+            .query_map(rusqlite::params![directory, tag_id], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(results)
+    }
+
     pub fn get_photo_tags(&self, photo_id: i64) -> Result<Vec<UserTag>> {
         let mut stmt = self.conn.prepare(
             r#"
@@ -1504,7 +1995,11 @@ impl SqliteDb {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT a.id, a.name, a.description, a.cover_photo_id, a.is_smart, a.filter_tags,
-                   (SELECT COUNT(*) FROM album_photos WHERE album_id = a.id) as photo_count
+                   CASE WHEN a.is_smart = 1
+                       THEN (SELECT COUNT(*) FROM smart_album_photos WHERE album_id = a.id)
+                       ELSE (SELECT COUNT(*) FROM album_photos WHERE album_id = a.id)
+                   END as photo_count,
+                   a.auto_sort, a.geo_radius
             FROM albums a
             ORDER BY a.name
             "#,
@@ -1522,7 +2017,9 @@ impl SqliteDb {
                     cover_photo_id: row.get(3)?,
                     is_smart: row.get::<_, i64>(4)? == 1,
                     filter_tags,
+                    geo_radius: row.get::<_, Option<String>>(8)?.and_then(|j| serde_json::from_str(&j).ok()),
                     photo_count: row.get(6)?,
+                    auto_sort: AlbumSort::from_db_str(row.get::<_, Option<String>>(7)?.as_deref()),
                 })
             })?
             .filter_map(|r| r.ok())
@@ -1545,7 +2042,8 @@ impl SqliteDb {
 
     pub fn add_photo_to_album(&self, album_id: i64, photo_id: i64) -> Result<()> {
         self.conn.execute(
-            "INSERT OR IGNORE INTO album_photos (album_id, photo_id) VALUES (?, ?)",
+            "INSERT OR IGNORE INTO album_photos (album_id, photo_id, position)
+             SELECT ?1, ?2, COALESCE(MAX(position) + 1, 0) FROM album_photos WHERE album_id = ?1",
             rusqlite::params![album_id, photo_id],
         )?;
         Ok(())
@@ -1587,6 +2085,38 @@ impl SqliteDb {
         Ok(paths)
     }
 
+    /// Store `photo_ids` as the album's order, first photo at position 0
+    pub fn set_album_order(&self, album_id: i64, photo_ids: &[i64]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for (position, photo_id) in photo_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE album_photos SET position = ? WHERE album_id = ? AND photo_id = ?",
+                rusqlite::params![position as i64, album_id, photo_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn set_album_auto_sort(&self, album_id: i64, sort: AlbumSort) -> Result<()> {
+        self.conn.execute(
+            "UPDATE albums SET auto_sort = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            rusqlite::params![sort.as_db_str(), album_id],
+        )?;
+        Ok(())
+    }
+
+    /// Limit a smart album to photos taken within `geo`, or lift the limit
+    pub fn set_album_geo_radius(&self, album_id: i64, geo: Option<&GeoRadius>) -> Result<()> {
+        let json = geo.map(serde_json::to_string).transpose()?;
+        self.conn.execute(
+            "UPDATE albums SET geo_radius = ?1, is_smart = CASE WHEN ?1 IS NULL THEN is_smart ELSE 1 END,
+                 updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            rusqlite::params![json, album_id],
+        )?;
+        Ok(())
+    }
+
     pub fn set_album_filter_tags(&self, album_id: i64, tag_ids: &[i64]) -> Result<()> {
         let json = serde_json::to_string(tag_ids)?;
         self.conn.execute(
@@ -1596,26 +2126,54 @@ impl SqliteDb {
         Ok(())
     }
 
+    /// Store the current members of a smart album
+    pub fn replace_smart_album_photos(&self, album_id: i64, photo_ids: &[i64]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM smart_album_photos WHERE album_id = ?", [album_id])?;
+        for photo_id in photo_ids {
+            tx.execute(
+                "INSERT INTO smart_album_photos (album_id, photo_id) VALUES (?, ?)",
+                [album_id, *photo_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Photos matching all of a smart album's criteria: every one of its
+    /// tags, and its geofence if it has one
     pub fn get_smart_album_photos(&self, album_id: i64) -> Result<Vec<i64>> {
-        let filter_json: Option<String> = self.conn.query_row(
-            "SELECT filter_tags FROM albums WHERE id = ?",
+        let (filter_json, geo_json): (Option<String>, Option<String>) = self.conn.query_row(
+            "SELECT filter_tags, geo_radius FROM albums WHERE id = ?",
             [album_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )?;
         let tag_ids: Vec<i64> = filter_json
             .and_then(|j| serde_json::from_str(&j).ok())
             .unwrap_or_default();
-        if tag_ids.is_empty() {
-            return Ok(vec![]);
-        }
+        let nearby = match geo_json.and_then(|j| serde_json::from_str::<GeoRadius>(&j).ok()) {
+            Some(geo) => Some(self.get_photos_in_radius(geo.center_lat, geo.center_lon, geo.radius_km)?),
+            None => None,
+        };
+        let tagged = if tag_ids.is_empty() { None } else { Some(self.get_photos_with_all_tags(&tag_ids)?) };
+        Ok(smart_album_members(tagged, nearby))
+    }
+
+    /// Photos with each of `tag_ids` or a tag nested under it
+    fn get_photos_with_all_tags(&self, tag_ids: &[i64]) -> Result<Vec<i64>> {
         let placeholders: Vec<String> = tag_ids.iter().map(|_| "?".to_string()).collect();
         let query = format!(
             r#"
-            SELECT photo_id
-            FROM photo_user_tags
-            WHERE tag_id IN ({})
-            GROUP BY photo_id
-            HAVING COUNT(DISTINCT tag_id) = ?
+            WITH RECURSIVE tag_tree(root, id) AS (
+                SELECT id, id FROM user_tags WHERE id IN ({})
+                UNION
+                SELECT tt.root, t.id FROM user_tags t JOIN tag_tree tt ON t.parent_id = tt.id
+            )
+            SELECT pt.photo_id
+            FROM photo_user_tags pt
+            JOIN tag_tree tt ON tt.id = pt.tag_id
+            GROUP BY pt.photo_id
+            HAVING COUNT(DISTINCT tt.root) = ?
             "#,
             placeholders.join(",")
         );
@@ -1681,6 +2239,20 @@ impl SqliteDb {
         Ok(tasks)
     }
 
+    /// Photos outside the trash matching `condition`, built by
+    /// `missing_fields_condition`
+    pub fn get_photos_matching(&self, condition: &str) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, path FROM photos WHERE trashed_at IS NULL AND ({}) ORDER BY path ASC",
+            condition
+        ))?;
+        let photos = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, String>(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(photos)
+    }
+
     pub fn get_photo_description(&self, photo_id: i64) -> Result<Option<String>> {
         let result: Option<String> = self.conn.query_row(
             "SELECT description FROM photos WHERE id = ?",
@@ -1731,6 +2303,7 @@ impl SqliteDb {
     pub fn insert_scanned_photo(
         &self,
         path: &str,
+        real_path: Option<&str>,
         filename: &str,
         directory: &str,
         size_bytes: i64,
@@ -1757,21 +2330,21 @@ impl SqliteDb {
         self.conn.execute(
             r#"
             INSERT INTO photos (
-                path, filename, directory, size_bytes, modified_at,
+                path, real_path, filename, directory, size_bytes, modified_at,
                 width, height, format,
                 camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at,
                 gps_latitude, gps_longitude, all_exif,
                 md5_hash, sha256_hash, perceptual_hash,
-                exif_orientation
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                exif_orientation, gps_source
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             rusqlite::params![
-                path, filename, directory, size_bytes, modified_at,
+                path, real_path, filename, directory, size_bytes, modified_at,
                 width, height, format,
                 camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at,
                 gps_lat, gps_lon, all_exif,
                 md5_hash, sha256_hash, perceptual_hash,
-                exif_orientation,
+                exif_orientation, gps_lat.map(|_| "exif"),
             ],
         )?;
         Ok(())
@@ -1780,6 +2353,7 @@ impl SqliteDb {
     pub fn update_scanned_photo(
         &self,
         path: &str,
+        real_path: Option<&str>,
         filename: &str,
         directory: &str,
         size_bytes: i64,
@@ -1806,20 +2380,23 @@ impl SqliteDb {
         self.conn.execute(
             r#"
             UPDATE photos SET
-                filename = ?, directory = ?, size_bytes = ?, modified_at = ?,
+                real_path = ?, filename = ?, directory = ?, size_bytes = ?, modified_at = ?,
                 width = ?, height = ?, format = ?,
                 camera_make = ?, camera_model = ?, lens = ?, focal_length = ?, aperture = ?, shutter_speed = ?, iso = ?, taken_at = ?,
-                gps_latitude = ?, gps_longitude = ?, all_exif = ?,
+                gps_latitude = CASE WHEN gps_source = 'manual' THEN gps_latitude ELSE ? END,
+                gps_longitude = CASE WHEN gps_source = 'manual' THEN gps_longitude ELSE ? END,
+                gps_source = CASE WHEN gps_source = 'manual' THEN gps_source ELSE ? END,
+                all_exif = ?,
                 md5_hash = ?, sha256_hash = ?, perceptual_hash = ?,
                 exif_orientation = ?,
                 scanned_at = CURRENT_TIMESTAMP
             WHERE path = ?
             "#,
             rusqlite::params![
-                filename, directory, size_bytes, modified_at,
+                real_path, filename, directory, size_bytes, modified_at,
                 width, height, format,
                 camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at,
-                gps_lat, gps_lon, all_exif,
+                gps_lat, gps_lon, gps_lat.map(|_| "exif"), all_exif,
                 md5_hash, sha256_hash, perceptual_hash,
                 exif_orientation,
                 path,
@@ -1828,6 +2405,68 @@ impl SqliteDb {
         Ok(())
     }
 
+    // ========================================================================
+    // Event operations
+    // ========================================================================
+
+    /// Described photos with a capture date, with their CLIP embedding if any
+    pub fn get_event_candidates(&self) -> Result<Vec<EventCandidate>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT p.id, p.taken_at, p.description, e.embedding
+            FROM photos p
+            LEFT JOIN embeddings e ON e.photo_id = p.id
+            WHERE p.description IS NOT NULL AND p.description != '' AND p.taken_at IS NOT NULL
+            "#,
+        )?;
+        let candidates = stmt
+            .query_map([], |row| {
+                let embedding: Option<Vec<u8>> = row.get(3)?;
+                Ok(EventCandidate {
+                    photo_id: row.get(0)?,
+                    taken_at: row.get(1)?,
+                    description: row.get(2)?,
+                    embedding: embedding.map(|b| bytes_to_embedding(&b)),
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(candidates)
+    }
+
+    pub fn get_events(&self) -> Result<Vec<Event>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, start_date, end_date, photo_count FROM events ORDER BY start_date",
+        )?;
+        let events = stmt
+            .query_map([], |row| {
+                Ok(Event {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    start_date: row.get(2)?,
+                    end_date: row.get(3)?,
+                    photo_count: row.get(4)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(events)
+    }
+
+    /// Replace every stored event; the events' ids are ignored
+    pub fn replace_events(&self, events: &[Event]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM events", [])?;
+        for event in events {
+            tx.execute(
+                "INSERT INTO events (name, start_date, end_date, photo_count) VALUES (?, ?, ?, ?)",
+                rusqlite::params![event.name, event.start_date, event.end_date, event.photo_count],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     // ========================================================================
     // Export operations (from export/mod.rs)
     // ========================================================================
@@ -1846,7 +2485,23 @@ impl SqliteDb {
                 camera_model,
                 taken_at,
                 description,
-                scanned_at
+                scanned_at,
+                id,
+                rating,
+                gps_latitude,
+                gps_longitude,
+                (SELECT GROUP_CONCAT(name, char(31)) FROM (
+                    SELECT ut.name FROM photo_user_tags put
+                    JOIN user_tags ut ON ut.id = put.tag_id
+                    WHERE put.photo_id = photos.id
+                    ORDER BY ut.name
+                )),
+                (SELECT GROUP_CONCAT(name, char(31)) FROM (
+                    SELECT DISTINCT p.name FROM faces f
+                    JOIN people p ON p.id = f.person_id
+                    WHERE f.photo_id = photos.id
+                    ORDER BY p.name
+                ))
             FROM photos
             ORDER BY path
             "#,
@@ -1859,6 +2514,7 @@ impl SqliteDb {
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default();
                 Ok(super::ExportedPhotoRow {
+                    id: row.get(11)?,
                     path,
                     filename,
                     width: row.get(1)?,
@@ -1871,6 +2527,11 @@ impl SqliteDb {
                     date_taken: row.get(8)?,
                     description: row.get(9)?,
                     scanned_at: row.get(10)?,
+                    rating: row.get(12)?,
+                    gps_latitude: row.get(13)?,
+                    gps_longitude: row.get(14)?,
+                    tags: super::split_name_list(row.get(15)?),
+                    people: super::split_name_list(row.get(16)?),
                 })
             })?
             .filter_map(|r| r.ok())
@@ -1942,6 +2603,16 @@ impl SqliteDb {
         }
     }
 
+    pub fn get_directory_prompts(&self) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT directory, custom_prompt FROM directory_prompts ORDER BY directory",
+        )?;
+        let prompts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(prompts)
+    }
+
     pub fn set_directory_prompt(&self, directory: &str, prompt: &str) -> Result<()> {
         if prompt.is_empty() {
             self.conn.execute(
@@ -1957,6 +2628,113 @@ impl SqliteDb {
         Ok(())
     }
 
+    // ========================================================================
+    // Slideshow preference operations
+    // ========================================================================
+
+    pub fn get_slideshow_interval(&self, directory: &str) -> Result<Option<f64>> {
+        let result = self.conn.query_row(
+            "SELECT interval_secs FROM slideshow_prefs WHERE directory = ?",
+            [directory],
+            |row| row.get::<_, f64>(0),
+        );
+        match result {
+            Ok(interval) => Ok(Some(interval)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn set_slideshow_interval(&self, directory: &str, interval_secs: f64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO slideshow_prefs (directory, interval_secs, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP)",
+            rusqlite::params![directory, interval_secs],
+        )?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // App state operations
+    // ========================================================================
+
+    pub fn get_app_state(&self, key: &str) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT value FROM app_state WHERE key = ?",
+            [key],
+            |row| row.get::<_, String>(0),
+        );
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn set_app_state(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO app_state (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP)",
+            rusqlite::params![key, value],
+        )?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Location operations
+    // ========================================================================
+
+    /// Photos taken within `radius_km` of the coordinates. A bounding box
+    /// narrows the candidates in SQL; the haversine distance decides.
+    pub fn get_photos_in_radius(&self, lat: f64, lon: f64, radius_km: f64) -> Result<Vec<i64>> {
+        let (min_lat, max_lat, min_lon, max_lon) = bounding_box(lat, lon, radius_km);
+        let mut stmt = self.conn.prepare(
+            "SELECT id, gps_latitude, gps_longitude FROM photos
+             WHERE gps_latitude BETWEEN ? AND ? AND gps_longitude BETWEEN ? AND ? AND trashed_at IS NULL",
+        )?;
+        let rows = stmt
+            .query_map([min_lat, max_lat, min_lon, max_lon], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?, row.get::<_, f64>(2)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows
+            .into_iter()
+            .filter(|&(_, plat, plon)| haversine_km(lat, lon, plat, plon) <= radius_km)
+            .map(|(id, _, _)| id)
+            .collect())
+    }
+
+    /// `(path, latitude, longitude)` of every photo with coordinates
+    pub fn get_gps_photos(&self) -> Result<Vec<(String, f64, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT path, gps_latitude, gps_longitude FROM photos
+             WHERE gps_latitude IS NOT NULL AND gps_longitude IS NOT NULL AND trashed_at IS NULL",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    pub fn get_place_name(&self, lat_key: i64, lon_key: i64) -> Result<Option<String>> {
+        let result = self.conn.query_row(
+            "SELECT name FROM place_names WHERE lat_key = ? AND lon_key = ?",
+            [lat_key, lon_key],
+            |row| row.get::<_, String>(0),
+        );
+        match result {
+            Ok(name) => Ok(Some(name)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save_place_name(&self, lat_key: i64, lon_key: i64, name: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO place_names (lat_key, lon_key, name, updated_at) VALUES (?, ?, ?, CURRENT_TIMESTAMP)",
+            rusqlite::params![lat_key, lon_key, name],
+        )?;
+        Ok(())
+    }
+
     pub fn count_photos_without_faces_in_dir(&self, directory: &str) -> Result<i64> {
         let count: i64 = self.conn.query_row(
             r#"
@@ -1970,6 +2748,49 @@ impl SqliteDb {
         ).unwrap_or(0);
         Ok(count)
     }
+
+    pub fn count_photos_in_dir_recursive(&self, directory: &str) -> Result<DirectoryPhotoStats> {
+        let prefix = format!("{}/", directory.trim_end_matches('/'));
+        let stats = self.conn.query_row(
+            r#"
+            SELECT COUNT(*), COALESCE(SUM(size_bytes), 0), MAX(taken_at)
+            FROM photos
+            WHERE (directory = ?1 OR substr(directory, 1, length(?2)) = ?2)
+              AND trashed_at IS NULL
+            "#,
+            rusqlite::params![directory, prefix],
+            |row| {
+                Ok(DirectoryPhotoStats {
+                    photo_count: row.get(0)?,
+                    total_bytes: row.get(1)?,
+                    latest_taken_at: row.get(2)?,
+                })
+            },
+        )?;
+        Ok(stats)
+    }
+
+    pub fn list_directories(&self) -> Result<Vec<DirectorySummary>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT directory, COUNT(*), COALESCE(SUM(size_bytes), 0)
+            FROM photos
+            WHERE trashed_at IS NULL
+            GROUP BY directory
+            ORDER BY directory
+            "#,
+        )?;
+        let directories = stmt
+            .query_map([], |row| {
+                Ok(DirectorySummary {
+                    directory: row.get(0)?,
+                    photo_count: row.get(1)?,
+                    total_bytes: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(directories)
+    }
 }
 
 /// Helper to convert a row to ScheduledTask.
@@ -1999,3 +2820,218 @@ fn row_to_scheduled_task(row: &rusqlite::Row) -> rusqlite::Result<ScheduledTask>
         error_message: row.get(11)?,
     })
 }
+
+/// Counts detected faces for every photo scanned for faces, including
+/// photos where none were found.
+const FACE_COUNTS_CTE: &str = "WITH face_counts AS (SELECT fs.photo_id, COUNT(f.id) AS face_count FROM face_scans fs LEFT JOIN faces f ON f.photo_id = fs.photo_id GROUP BY fs.photo_id)";
+
+/// SQL limiting a photo id column to a search filter: a WITH clause (empty
+/// unless face counts are needed), conditions to AND together, and their
+/// parameters in order.
+fn filter_sql(column: &str, filter: &SearchFilter) -> (&'static str, Vec<String>, Vec<rusqlite::types::Value>) {
+    use rusqlite::types::Value;
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+
+    let scope = match &filter.scope {
+        SearchScope::All => None,
+        SearchScope::Album(id) => Some(("SELECT photo_id FROM album_photos WHERE album_id = ?", Value::Integer(*id))),
+        SearchScope::Tag(id) => Some(("SELECT photo_id FROM photo_user_tags WHERE tag_id = ?", Value::Integer(*id))),
+        SearchScope::Person(id) => Some(("SELECT photo_id FROM faces WHERE person_id = ?", Value::Integer(*id))),
+        SearchScope::Directory(dir) => Some((
+            "SELECT id FROM photos WHERE path LIKE ? ESCAPE '\\'",
+            Value::Text(SearchScope::directory_pattern(dir)),
+        )),
+    };
+    if let Some((subquery, param)) = scope {
+        conditions.push(format!("{} IN ({})", column, subquery));
+        params.push(param);
+    }
+
+    if !filter.has_face_limits() {
+        return ("", conditions, params);
+    }
+    let (min, max) = filter.face_range();
+    conditions.push(format!(
+        "{} IN (SELECT photo_id FROM face_counts WHERE face_count BETWEEN ? AND ?)",
+        column
+    ));
+    params.push(Value::Integer(min));
+    params.push(Value::Integer(max));
+    (FACE_COUNTS_CTE, conditions, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{test_db, AlbumSort, Database, GeoRadius, SearchFilter, SearchScope};
+
+    #[test]
+    fn test_refresh_smart_albums() {
+        let (dir, db) = test_db();
+
+        let beach = db.get_or_create_tag("beach").unwrap();
+        let album = db.create_album("Beach", None, true).unwrap();
+        db.set_album_filter_tags(album, &[beach.id]).unwrap();
+        let mut ids = Vec::new();
+        for name in ["a.jpg", "b.jpg"] {
+            let path = dir.path().join(name);
+            db.insert_basic_photo(&path.to_string_lossy(), name, &dir.path().to_string_lossy(), 1).unwrap();
+            ids.push(db.get_photo_metadata(&path).unwrap().unwrap().id);
+        }
+        let count = |db: &Database| db.get_all_albums().unwrap()[0].photo_count;
+
+        db.add_tag_to_photo(ids[0], beach.id).unwrap();
+        assert_eq!(db.refresh_smart_albums().unwrap(), 1);
+        assert_eq!(count(&db), 1);
+
+        db.add_tag_to_photo(ids[1], beach.id).unwrap();
+        assert_eq!(count(&db), 1);
+        db.refresh_smart_albums().unwrap();
+        assert_eq!(count(&db), 2);
+
+        // Changing the album's criteria refreshes it straight away
+        let sand = db.get_or_create_tag("sand").unwrap();
+        db.add_tag_to_photo(ids[0], sand.id).unwrap();
+        db.set_album_filter_tags(album, &[beach.id, sand.id]).unwrap();
+        assert_eq!(count(&db), 1);
+        db.set_tag_parent(sand.id, Some(beach.id)).unwrap();
+        db.add_tag_to_photo(ids[1], sand.id).unwrap();
+        db.set_album_filter_tags(album, &[sand.id]).unwrap();
+        assert_eq!(count(&db), 2);
+    }
+
+    #[test]
+    fn test_missing_fields_skip_trashed_photos() {
+        let (dir, db) = test_db();
+
+        let mut ids = Vec::new();
+        for name in ["a.jpg", "b.jpg"] {
+            let path = dir.path().join(name);
+            db.insert_basic_photo(&path.to_string_lossy(), name, &dir.path().to_string_lossy(), 1).unwrap();
+            ids.push(db.get_photo_metadata(&path).unwrap().unwrap().id);
+        }
+        db.mark_trashed(ids[1], &dir.path().join("trash/b.jpg")).unwrap();
+
+        let missing = db.get_photos_with_missing_fields(&["gps", "dimensions"]).unwrap();
+        assert_eq!(missing.iter().map(|(id, _)| *id).collect::<Vec<_>>(), [ids[0]]);
+    }
+
+    #[test]
+    fn test_directory_scope_matches_wildcards_literally() {
+        let (dir, db) = test_db();
+
+        for sub in ["a_b", "axb"] {
+            let directory = dir.path().join(sub);
+            let path = directory.join("beach.jpg");
+            db.insert_basic_photo(&path.to_string_lossy(), "beach.jpg", &directory.to_string_lossy(), 1).unwrap();
+            let id = db.get_photo_metadata(&path).unwrap().unwrap().id;
+            db.save_photo_description_by_id(id, "A beach at dusk").unwrap();
+        }
+
+        let filter = SearchFilter {
+            scope: SearchScope::Directory(dir.path().join("a_b").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let results = db.semantic_search_by_text_scoped("beach", 10, &filter).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].path.contains("a_b"));
+    }
+
+    #[test]
+    fn test_reorder_album_photo() {
+        let (dir, db) = test_db();
+
+        let album = db.create_album("Trip", None, false).unwrap();
+        let mut ids = Vec::new();
+        for name in ["c.jpg", "a.jpg", "b.jpg"] {
+            let path = dir.path().join(name);
+            db.insert_basic_photo(&path.to_string_lossy(), name, &dir.path().to_string_lossy(), 1).unwrap();
+            let id = db.get_photo_metadata(&path).unwrap().unwrap().id;
+            db.add_photo_to_album(album, id).unwrap();
+            ids.push(id);
+        }
+        assert_eq!(db.get_album_photos(album).unwrap(), ids);
+
+        assert!(db.reorder_album_photo(album, ids[0], 2).unwrap());
+        assert_eq!(db.get_album_photos(album).unwrap(), [ids[1], ids[2], ids[0]]);
+        assert!(db.reorder_album_photo(album, ids[0], 1).unwrap());
+        assert_eq!(db.get_album_photos(album).unwrap(), [ids[1], ids[0], ids[2]]);
+        assert!(!db.reorder_album_photo(album, ids[0], 3).unwrap());
+
+        db.set_album_auto_sort(album, AlbumSort::Rating).unwrap();
+        assert_eq!(db.get_all_albums().unwrap()[0].auto_sort, AlbumSort::Rating);
+    }
+
+    #[test]
+    fn test_geofenced_smart_album() {
+        let (dir, db) = test_db();
+
+        // Westminster, Greenwich (about 9 km east) and Paris
+        let mut ids = Vec::new();
+        for (name, lat, lon) in [("a.jpg", 51.5007, -0.1246), ("b.jpg", 51.4826, -0.0077), ("c.jpg", 48.8566, 2.3522)] {
+            let path = dir.path().join(name);
+            db.insert_basic_photo(&path.to_string_lossy(), name, &dir.path().to_string_lossy(), 1).unwrap();
+            let id = db.get_photo_metadata(&path).unwrap().unwrap().id;
+            db.set_manual_gps(id, lat, lon).unwrap();
+            ids.push(id);
+        }
+        assert_eq!(db.get_photos_in_radius(51.5007, -0.1246, 5.0).unwrap(), [ids[0]]);
+        assert_eq!(db.get_photos_in_radius(51.5007, -0.1246, 10.0).unwrap(), [ids[0], ids[1]]);
+
+        let album = db.create_album("London", None, false).unwrap();
+        let geo = GeoRadius::parse("51.5007, -0.1246, 10 km").unwrap();
+        db.set_album_geo_radius(album, Some(&geo)).unwrap();
+        let stored = &db.get_all_albums().unwrap()[0];
+        assert!(stored.is_smart);
+        assert_eq!(stored.geo_radius, Some(geo));
+        assert_eq!(db.get_smart_album_photos(album).unwrap(), [ids[0], ids[1]]);
+
+        // With tags as well, photos must match both
+        let tag = db.get_or_create_tag("river").unwrap();
+        db.add_tag_to_photo(ids[1], tag.id).unwrap();
+        db.add_tag_to_photo(ids[2], tag.id).unwrap();
+        db.set_album_filter_tags(album, &[tag.id]).unwrap();
+        assert_eq!(db.get_smart_album_photos(album).unwrap(), [ids[1]]);
+
+        assert_eq!(GeoRadius::parse("91, 0, 5"), None);
+        assert_eq!(GeoRadius::parse("1, 2"), None);
+    }
+
+    #[test]
+    fn test_nested_tags_in_smart_album() {
+        let (dir, db) = test_db();
+
+        let animals = db.get_or_create_tag("animals").unwrap();
+        let dogs = db.get_or_create_tag("dogs").unwrap();
+        let puppies = db.get_or_create_tag("puppies").unwrap();
+        db.set_tag_parent(dogs.id, Some(animals.id)).unwrap();
+        db.set_tag_parent(puppies.id, Some(dogs.id)).unwrap();
+        assert!(db.set_tag_parent(animals.id, Some(puppies.id)).is_err());
+        assert!(db.set_tag_parent(dogs.id, Some(dogs.id)).is_err());
+
+        let mut ids = Vec::new();
+        for (name, tag) in [("a.jpg", &animals), ("b.jpg", &puppies), ("c.jpg", &dogs)] {
+            let path = dir.path().join(name);
+            db.insert_basic_photo(&path.to_string_lossy(), name, &dir.path().to_string_lossy(), 1).unwrap();
+            let id = db.get_photo_metadata(&path).unwrap().unwrap().id;
+            db.add_tag_to_photo(id, tag.id).unwrap();
+            ids.push(id);
+        }
+
+        let album = db.create_album("Animals", None, true).unwrap();
+        db.set_album_filter_tags(album, &[animals.id]).unwrap();
+        let mut members = db.get_smart_album_photos(album).unwrap();
+        members.sort();
+        assert_eq!(members, ids);
+
+        db.set_album_filter_tags(album, &[dogs.id]).unwrap();
+        let mut members = db.get_smart_album_photos(album).unwrap();
+        members.sort();
+        assert_eq!(members, [ids[1], ids[2]]);
+
+        // Deleting a tag moves the tags nested in it up a level
+        db.delete_tag(dogs.id).unwrap();
+        let puppies_parent = db.get_tag_stats().unwrap().into_iter().find(|s| s.tag.id == puppies.id).unwrap().parent_id;
+        assert_eq!(puppies_parent, Some(animals.id));
+    }
+}
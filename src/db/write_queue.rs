@@ -0,0 +1,94 @@
+//! Write queue for the database.
+//!
+//! SQLite allows one writer at a time, so threads that each write through
+//! their own connection wait on each other's locks or fail with
+//! `SQLITE_BUSY`. A `DbWriteQueue` owns a single writing connection on a
+//! thread of its own and runs queued writes one after another in the order
+//! they were submitted; submitting never waits for the write. Reads go
+//! through other connections, ideally ones from `Database::open_read_only`.
+
+use anyhow::Result;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use super::Database;
+
+/// A write to run on the writer thread
+pub type WriteCommand = Box<dyn FnOnce(&Database) + Send>;
+
+pub struct DbWriteQueue {
+    tx: Option<mpsc::Sender<WriteCommand>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl DbWriteQueue {
+    /// Start the writer thread, which takes over `db`
+    pub fn new(db: Database) -> Result<Self> {
+        let (tx, rx) = mpsc::channel::<WriteCommand>();
+        let writer = std::thread::Builder::new()
+            .name("clepho-db-writer".to_string())
+            .spawn(move || {
+                for command in rx {
+                    command(&db);
+                }
+            })?;
+        Ok(Self { tx: Some(tx), writer: Some(writer) })
+    }
+
+    /// Queue a write. Commands report their own errors, since nobody waits
+    /// for them.
+    pub fn submit(&self, command: impl FnOnce(&Database) + Send + 'static) {
+        if let Some(ref tx) = self.tx {
+            if tx.send(Box::new(command)).is_err() {
+                tracing::error!("Database writer thread has stopped; write dropped");
+            }
+        }
+    }
+
+    /// Wait until every queued write has run, then stop the writer
+    pub fn finish(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        // Closing the channel ends the writer's loop once it is drained
+        self.tx.take();
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                tracing::error!("Database writer thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for DbWriteQueue {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+
+    #[test]
+    fn test_writes_run_in_order_and_are_visible_to_readers() {
+        let (dir, db) = crate::db::test_db();
+
+        let queue = DbWriteQueue::new(db).unwrap();
+        for name in ["beach", "sunset", "beach"] {
+            queue.submit(move |db| {
+                db.get_or_create_tag(name).unwrap();
+            });
+        }
+        queue.finish();
+
+        let config = DatabaseConfig { sqlite_path: dir.path().join("photos.db"), ..Default::default() };
+        let reader = Database::open_read_only(&config).unwrap();
+        let names: Vec<String> = reader.get_tag_stats().unwrap().into_iter().map(|t| t.tag.name).collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"sunset".to_string()));
+        assert!(reader.get_or_create_tag("dog").is_err());
+    }
+}
@@ -1,8 +1,13 @@
+mod photoprism;
+mod xmp;
+
+pub(crate) use xmp::xml_escape;
+
 use anyhow::Result;
 use serde::Serialize;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::db::Database;
 
@@ -12,6 +17,10 @@ pub enum ExportFormat {
     Json,
     Csv,
     Html,
+    /// XMP sidecars Lightroom picks up on import
+    LightroomCatalog,
+    /// YAML sidecars in PhotoPrism's format
+    PhotoPrism,
 }
 
 impl ExportFormat {
@@ -20,21 +29,43 @@ impl ExportFormat {
             ExportFormat::Json => "json",
             ExportFormat::Csv => "csv",
             ExportFormat::Html => "html",
+            // A directory of sidecars
+            ExportFormat::LightroomCatalog | ExportFormat::PhotoPrism => "",
         }
     }
 
-    #[allow(dead_code)]
     pub fn name(&self) -> &'static str {
         match self {
             ExportFormat::Json => "JSON",
             ExportFormat::Csv => "CSV",
             ExportFormat::Html => "HTML",
+            ExportFormat::LightroomCatalog => "Lightroom XMP",
+            ExportFormat::PhotoPrism => "PhotoPrism YAML",
+        }
+    }
+
+    /// File format matching an output path's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            "html" | "htm" => Some(ExportFormat::Html),
+            _ => None,
         }
     }
 }
 
-/// Photo data for export
+/// Outcome of an export, as reported by `clepho export --json`
 #[derive(Debug, Serialize)]
+pub struct ExportResult {
+    pub path: PathBuf,
+    pub format: &'static str,
+    pub photos: usize,
+}
+
+/// Photo data for export
+#[derive(Debug, Default, Serialize)]
 pub struct ExportedPhoto {
     pub path: String,
     pub filename: String,
@@ -48,6 +79,26 @@ pub struct ExportedPhoto {
     pub date_taken: Option<String>,
     pub description: Option<String>,
     pub scanned_at: Option<String>,
+    pub rating: Option<u8>,
+    pub gps_latitude: Option<f64>,
+    pub gps_longitude: Option<f64>,
+    pub tags: Option<Vec<String>>,
+    /// People recognised in the photo
+    pub people: Option<Vec<String>>,
+}
+
+/// None for an empty list, so a photo without tags or people exports null
+fn non_empty(names: Vec<String>) -> Option<Vec<String>> {
+    if names.is_empty() {
+        None
+    } else {
+        Some(names)
+    }
+}
+
+/// Names joined for a single CSV cell
+fn join_names(names: &Option<Vec<String>>) -> String {
+    names.as_deref().map(|n| n.join(", ")).unwrap_or_default()
 }
 
 /// Export photos from database to a file
@@ -59,14 +110,25 @@ pub fn export_photos(db: &Database, output_path: &Path, format: ExportFormat) ->
         ExportFormat::Json => export_json(&photos, output_path)?,
         ExportFormat::Csv => export_csv(&photos, output_path)?,
         ExportFormat::Html => export_html(&photos, output_path)?,
+        ExportFormat::LightroomCatalog => return Ok(xmp::write_sidecars(&photos, Some(output_path))?.written),
+        ExportFormat::PhotoPrism => return photoprism::write_sidecars(&photos, output_path),
     }
 
     Ok(count)
 }
 
+/// Write an XMP sidecar for every photo, into `target_dir` or, when it is
+/// None, next to each photo without replacing existing sidecars.
+pub fn export_xmp_sidecars(db: &Database, target_dir: Option<PathBuf>) -> Result<xmp::SidecarReport> {
+    let photos = get_photos_for_export(db)?;
+    xmp::write_sidecars(&photos, target_dir.as_deref())
+}
+
 fn get_photos_for_export(db: &Database) -> Result<Vec<ExportedPhoto>> {
     let rows = db.get_photos_for_export()?;
     Ok(rows.into_iter().map(|r| ExportedPhoto {
+        tags: non_empty(r.tags),
+        people: non_empty(r.people),
         path: r.path,
         filename: r.filename,
         width: r.width,
@@ -79,6 +141,9 @@ fn get_photos_for_export(db: &Database) -> Result<Vec<ExportedPhoto>> {
         date_taken: r.date_taken,
         description: r.description,
         scanned_at: r.scanned_at,
+        rating: r.rating.and_then(|v| u8::try_from(v).ok()),
+        gps_latitude: r.gps_latitude,
+        gps_longitude: r.gps_longitude,
     }).collect())
 }
 
@@ -106,6 +171,9 @@ fn export_csv(photos: &[ExportedPhoto], output_path: &Path) -> Result<()> {
         "date_taken",
         "description",
         "scanned_at",
+        "rating",
+        "tags",
+        "people",
     ])?;
 
     // Write data
@@ -123,6 +191,9 @@ fn export_csv(photos: &[ExportedPhoto], output_path: &Path) -> Result<()> {
             photo.date_taken.as_deref().unwrap_or(""),
             photo.description.as_deref().unwrap_or(""),
             photo.scanned_at.as_deref().unwrap_or(""),
+            &photo.rating.map(|v| v.to_string()).unwrap_or_default(),
+            &join_names(&photo.tags),
+            &join_names(&photo.people),
         ])?;
     }
 
@@ -283,6 +354,17 @@ fn export_html(photos: &[ExportedPhoto], output_path: &Path) -> Result<()> {
             ));
         }
 
+        for (label, names) in [("Tags", &photo.tags), ("People", &photo.people)] {
+            if names.is_some() {
+                html.push_str(&format!(
+                    r#"                <div><span>{}:</span> {}</div>
+"#,
+                    label,
+                    html_escape(&join_names(names))
+                ));
+            }
+        }
+
         html.push_str(r#"            </div>
 "#);
 
@@ -308,7 +390,7 @@ fn export_html(photos: &[ExportedPhoto], output_path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn html_escape(s: &str) -> String {
+pub(crate) fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -331,3 +413,46 @@ fn format_size(size: u64) -> String {
         format!("{} bytes", size)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_are_arrays_in_json_and_joined_in_csv() {
+        let photo = ExportedPhoto {
+            path: "/p/beach.jpg".to_string(),
+            filename: "beach.jpg".to_string(),
+            width: None,
+            height: None,
+            file_size: None,
+            sha256: None,
+            perceptual_hash: None,
+            camera_make: None,
+            camera_model: None,
+            date_taken: None,
+            description: None,
+            scanned_at: None,
+            rating: Some(5),
+            gps_latitude: None,
+            gps_longitude: None,
+            tags: non_empty(vec!["beach".to_string(), "family".to_string()]),
+            people: non_empty(Vec::new()),
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&photo).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["beach", "family"]));
+        assert!(json["people"].is_null());
+
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("photos.csv");
+        export_csv(&[photo], &csv_path).unwrap();
+        let mut reader = csv::Reader::from_path(&csv_path).unwrap();
+        let headers = reader.headers().unwrap().clone();
+        let row = reader.records().next().unwrap().unwrap();
+        let cell = |name: &str| row.get(headers.iter().position(|h| h == name).unwrap()).unwrap().to_string();
+        assert_eq!(cell("rating"), "5");
+        assert_eq!(cell("tags"), "beach, family");
+        assert_eq!(cell("people"), "");
+    }
+}
@@ -0,0 +1,91 @@
+//! PhotoPrism YAML sidecars, for moving a clepho library into PhotoPrism
+//! or back with `--import-photoprism`.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::xmp::{unique_sidecar_path, xmp_date};
+use super::ExportedPhoto;
+use crate::db::photoprism::{PhotoPrismDetails, PhotoPrismSidecar};
+
+/// Write one `.yml` sidecar per photo into `target_dir`. Returns the number
+/// of sidecars written.
+pub fn write_sidecars(photos: &[ExportedPhoto], target_dir: &Path) -> Result<usize> {
+    std::fs::create_dir_all(target_dir)?;
+
+    let mut used_names = HashSet::new();
+    let mut count = 0;
+    for photo in photos {
+        let sidecar = unique_sidecar_path(target_dir, &photo.filename, "yml", &mut used_names);
+        let written = serde_yaml::to_string(&sidecar_for(photo))
+            .map_err(anyhow::Error::from)
+            .and_then(|yaml| Ok(std::fs::write(&sidecar, yaml)?));
+        match written {
+            Ok(()) => count += 1,
+            Err(e) => {
+                tracing::warn!(path = %sidecar.display(), error = %e, "Failed to write PhotoPrism sidecar");
+            }
+        }
+    }
+    Ok(count)
+}
+
+/// The sidecar PhotoPrism would keep for the photo. Keywords go under
+/// `Details` where PhotoPrism reads them; clepho's capture times carry no
+/// offset, so they are written as UTC with the clock time unchanged.
+pub fn sidecar_for(photo: &ExportedPhoto) -> PhotoPrismSidecar {
+    PhotoPrismSidecar {
+        taken_at: photo
+            .date_taken
+            .as_deref()
+            .and_then(xmp_date)
+            .filter(|date| date.contains('T'))
+            .map(|date| format!("{}Z", date)),
+        description: photo.description.clone(),
+        lat: photo.gps_latitude,
+        lng: photo.gps_longitude,
+        rating: photo.rating.map(i32::from),
+        details: photo.tags.as_ref().map(|tags| PhotoPrismDetails { keywords: Some(tags.join(", ")) }),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let photo = ExportedPhoto {
+            path: "/photos/IMG_1.jpg".to_string(),
+            filename: "IMG_1.jpg".to_string(),
+            width: None,
+            height: None,
+            file_size: None,
+            sha256: None,
+            perceptual_hash: None,
+            camera_make: None,
+            camera_model: None,
+            date_taken: Some("2024-05-01 10:30:00".to_string()),
+            description: Some("Waves: at dusk".to_string()),
+            scanned_at: None,
+            rating: Some(4),
+            gps_latitude: Some(-33.9),
+            gps_longitude: Some(151.2),
+            tags: Some(vec!["sea".to_string(), "holiday".to_string()]),
+            people: None,
+        };
+
+        let yaml = serde_yaml::to_string(&sidecar_for(&photo)).unwrap();
+        assert!(yaml.contains("TakenAt: 2024-05-01T10:30:00Z"));
+        assert!(!yaml.contains("Title"));
+
+        let sidecar: PhotoPrismSidecar = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(sidecar.description_text(), Some("Waves: at dusk"));
+        assert_eq!(sidecar.keyword_list(), vec!["sea", "holiday"]);
+        assert_eq!(sidecar.gps(), Some((-33.9, 151.2)));
+        assert_eq!(sidecar.star_rating(), Some(4));
+        assert_eq!(sidecar.taken_at_local().as_deref(), Some("2024-05-01 10:30:00"));
+    }
+}
@@ -0,0 +1,240 @@
+//! XMP sidecar files that Lightroom and other Adobe tools read on import.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::ExportedPhoto;
+
+/// Outcome of writing sidecars
+#[derive(Debug, Default, PartialEq)]
+pub struct SidecarReport {
+    pub written: usize,
+    /// Photos whose sidecar next to them already existed, or was claimed by
+    /// another photo with the same stem (`IMG_1.jpg` and `IMG_1.cr2`)
+    pub skipped: usize,
+}
+
+/// Write one `.xmp` sidecar per photo. With a target directory all sidecars
+/// go there; otherwise each is written next to its photo, where Lightroom
+/// looks for it. Sidecars next to photos may belong to Lightroom or
+/// darktable, so existing ones are never replaced.
+pub fn write_sidecars(photos: &[ExportedPhoto], target_dir: Option<&Path>) -> Result<SidecarReport> {
+    if let Some(dir) = target_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut used_names = HashSet::new();
+    let mut claimed = HashSet::new();
+    let mut report = SidecarReport::default();
+    for photo in photos {
+        let xml = sidecar_xml(photo);
+        let (sidecar, result) = match target_dir {
+            Some(dir) => {
+                let sidecar = unique_sidecar_path(dir, &photo.filename, "xmp", &mut used_names);
+                let result = std::fs::write(&sidecar, xml);
+                (sidecar, result)
+            }
+            None => {
+                let sidecar = Path::new(&photo.path).with_extension("xmp");
+                if !claimed.insert(sidecar.clone()) {
+                    tracing::warn!(path = %sidecar.display(), photo = %photo.path, "Another photo shares this XMP sidecar, skipping");
+                    report.skipped += 1;
+                    continue;
+                }
+                let result = write_new(&sidecar, &xml);
+                (sidecar, result)
+            }
+        };
+        match result {
+            Ok(()) => report.written += 1,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                tracing::warn!(path = %sidecar.display(), "XMP sidecar already exists, skipping");
+                report.skipped += 1;
+            }
+            Err(e) => {
+                tracing::warn!(path = %sidecar.display(), error = %e, "Failed to write XMP sidecar");
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Write `contents` to `path`, failing if it already exists
+fn write_new(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::OpenOptions::new().write(true).create_new(true).open(path)?.write_all(contents.as_bytes())
+}
+
+/// `photo.<extension>` in the target directory, numbered when photos from
+/// different directories share a name.
+pub(super) fn unique_sidecar_path(dir: &Path, filename: &str, extension: &str, used: &mut HashSet<String>) -> PathBuf {
+    let stem = Path::new(filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| filename.to_string());
+    let mut name = format!("{}.{}", stem, extension);
+    let mut n = 1;
+    while !used.insert(name.clone()) {
+        name = format!("{}_{}.{}", stem, n, extension);
+        n += 1;
+    }
+    dir.join(name)
+}
+
+/// XMP packet with the photo's rating, description, tags, date and location.
+pub fn sidecar_xml(photo: &ExportedPhoto) -> String {
+    let mut attributes = String::new();
+    if let Some(rating) = photo.rating {
+        attributes.push_str(&format!("\n    xmp:Rating=\"{}\"", rating));
+    }
+    if let Some(date) = photo.date_taken.as_deref().and_then(xmp_date) {
+        attributes.push_str(&format!("\n    photoshop:DateCreated=\"{}\"", date));
+    }
+    if let (Some(lat), Some(lon)) = (photo.gps_latitude, photo.gps_longitude) {
+        attributes.push_str(&format!(
+            "\n    exif:GPSLatitude=\"{}\"\n    exif:GPSLongitude=\"{}\"",
+            xmp_gps(lat, 'N', 'S'),
+            xmp_gps(lon, 'E', 'W')
+        ));
+    }
+
+    let mut elements = String::new();
+    if let Some(ref description) = photo.description {
+        elements.push_str(&format!(
+            "   <dc:description>\n    <rdf:Alt>\n     <rdf:li xml:lang=\"x-default\">{}</rdf:li>\n    </rdf:Alt>\n   </dc:description>\n",
+            xml_escape(description)
+        ));
+    }
+    if let Some(ref tags) = photo.tags {
+        let items: String = tags
+            .iter()
+            .map(|t| format!("     <rdf:li>{}</rdf:li>\n", xml_escape(t)))
+            .collect();
+        elements.push_str(&format!(
+            "   <dc:subject>\n    <rdf:Bag>\n{items}    </rdf:Bag>\n   </dc:subject>\n   <lr:hierarchicalSubject>\n    <rdf:Bag>\n{items}    </rdf:Bag>\n   </lr:hierarchicalSubject>\n",
+        ));
+    }
+
+    format!(
+        r#"<?xpacket begin="{bom}" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/" x:xmptk="clepho {version}">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+    xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    xmlns:lr="http://ns.adobe.com/lightroom/1.0/"{attributes}>
+{elements}  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>
+"#,
+        bom = '\u{feff}',
+        version = env!("CARGO_PKG_VERSION"),
+    )
+}
+
+/// EXIF dates ("2024:05:01 10:30:00") as XMP dates ("2024-05-01T10:30:00").
+pub(super) fn xmp_date(taken_at: &str) -> Option<String> {
+    let taken_at = taken_at.trim();
+    let (date, time) = match taken_at.split_once(' ') {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (taken_at, None),
+    };
+    let date = date.replace(':', "-");
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3 || !parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    Some(match time {
+        Some(time) if !time.is_empty() => format!("{}T{}", date, time),
+        _ => date,
+    })
+}
+
+/// Decimal degrees as XMP GPS coordinates: degrees, decimal minutes and a
+/// hemisphere letter, e.g. "51,30.0000N".
+fn xmp_gps(value: f64, positive: char, negative: char) -> String {
+    let hemisphere = if value < 0.0 { negative } else { positive };
+    let value = value.abs();
+    let degrees = value.trunc();
+    let minutes = (value - degrees) * 60.0;
+    format!("{},{:.4}{}", degrees as u32, minutes, hemisphere)
+}
+
+pub(crate) fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xmp_date_and_gps() {
+        assert_eq!(xmp_date("2024:05:01 10:30:00").as_deref(), Some("2024-05-01T10:30:00"));
+        assert_eq!(xmp_date("2024-05-01").as_deref(), Some("2024-05-01"));
+        assert_eq!(xmp_date("unknown"), None);
+        assert_eq!(xmp_gps(51.5, 'N', 'S'), "51,30.0000N");
+        assert_eq!(xmp_gps(-0.125, 'E', 'W'), "0,7.5000W");
+    }
+
+    #[test]
+    fn test_sidecar_xml() {
+        let photo = ExportedPhoto {
+            path: "/p/beach.jpg".to_string(),
+            filename: "beach.jpg".to_string(),
+            width: None,
+            height: None,
+            file_size: None,
+            sha256: None,
+            perceptual_hash: None,
+            camera_make: None,
+            camera_model: None,
+            date_taken: Some("2024:05:01 10:30:00".to_string()),
+            description: Some("Fish & chips".to_string()),
+            scanned_at: None,
+            rating: Some(4),
+            gps_latitude: None,
+            gps_longitude: None,
+            tags: Some(vec!["holiday".to_string()]),
+            people: None,
+        };
+        let xml = sidecar_xml(&photo);
+        assert!(xml.contains(r#"xmp:Rating="4""#));
+        assert!(xml.contains(r#"photoshop:DateCreated="2024-05-01T10:30:00""#));
+        assert!(xml.contains("Fish &amp; chips"));
+        assert!(xml.contains("<lr:hierarchicalSubject>\n    <rdf:Bag>\n     <rdf:li>holiday</rdf:li>"));
+        assert!(!xml.contains("GPSLatitude"));
+    }
+
+    #[test]
+    fn test_unique_sidecar_path() {
+        let mut used = HashSet::new();
+        let dir = Path::new("/out");
+        assert_eq!(unique_sidecar_path(dir, "a.jpg", "xmp", &mut used), dir.join("a.xmp"));
+        assert_eq!(unique_sidecar_path(dir, "a.png", "xmp", &mut used), dir.join("a_1.xmp"));
+    }
+
+    #[test]
+    fn test_sidecars_beside_photos_keep_existing_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("IMG_1.xmp"), "darktable").unwrap();
+        let photo = |name: &str| ExportedPhoto {
+            path: dir.path().join(name).to_string_lossy().to_string(),
+            filename: name.to_string(),
+            ..Default::default()
+        };
+        let photos = [photo("IMG_1.jpg"), photo("IMG_2.jpg"), photo("IMG_2.cr2")];
+
+        let report = write_sidecars(&photos, None).unwrap();
+        assert_eq!(report, SidecarReport { written: 1, skipped: 2 });
+        assert_eq!(std::fs::read_to_string(dir.path().join("IMG_1.xmp")).unwrap(), "darktable");
+        assert!(std::fs::read_to_string(dir.path().join("IMG_2.xmp")).unwrap().contains("<x:xmpmeta"));
+    }
+}
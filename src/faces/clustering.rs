@@ -1,15 +1,22 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
-use crate::db::Database;
+use crate::db::{BoundingBox, Database};
 use crate::tasks::{TaskProgress, TaskUpdate};
 use super::detector;
 
+/// Cosine similarity a face needs to join a cluster
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.6;
+
+/// Faces read from the database per round of incremental clustering
+const INCREMENTAL_BATCH: usize = 500;
+
 /// Result of face clustering
 #[derive(Debug, Clone)]
 pub struct FaceClusteringResult {
@@ -190,6 +197,101 @@ pub fn cluster_faces(
     })
 }
 
+/// Generate embeddings for the given faces, reporting progress as it goes.
+/// Returns the number generated and failed, or `None` if cancelled.
+fn generate_embeddings_with_progress(
+    db: &Database,
+    faces: &[(i64, i64, BoundingBox)],
+    tx: &Sender<TaskUpdate>,
+    cancel_flag: &AtomicBool,
+) -> Option<(usize, usize)> {
+    let mut generated = 0;
+    let mut failed = 0;
+
+    for (idx, (face_id, photo_id, bbox)) in faces.iter().enumerate() {
+        if cancel_flag.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        let _ = tx.send(TaskUpdate::Progress(
+            TaskProgress::new(idx, faces.len())
+                .with_message(format!("Generating embedding {}/{}", idx + 1, faces.len()))
+        ));
+
+        let photo_path = match db.get_photo_path(*photo_id) {
+            Ok(Some(path)) => path,
+            _ => {
+                failed += 1;
+                continue;
+            }
+        };
+
+        let path = Path::new(&photo_path);
+        if !path.exists() {
+            failed += 1;
+            continue;
+        }
+
+        match detector::generate_embedding_for_face(path, bbox) {
+            Ok(embedding) if db.update_face_embedding(*face_id, &embedding).is_ok() => generated += 1,
+            _ => failed += 1,
+        }
+    }
+
+    Some((generated, failed))
+}
+
+/// Regenerate face embeddings that are missing or were produced by an older
+/// embedding model, in a background task
+pub fn regenerate_face_embeddings_background(
+    db: &Database,
+    tx: Sender<TaskUpdate>,
+    cancel_flag: Arc<AtomicBool>,
+) {
+    let faces = match db
+        .count_faces_without_embeddings()
+        .and_then(|count| db.get_faces_without_embeddings(count as usize))
+    {
+        Ok(faces) => faces,
+        Err(e) => {
+            let _ = tx.send(TaskUpdate::Failed {
+                error: format!("Failed to get faces: {}", e),
+            });
+            return;
+        }
+    };
+
+    if faces.is_empty() {
+        let _ = tx.send(TaskUpdate::Completed {
+            message: "All face embeddings are up to date".to_string(),
+        });
+        return;
+    }
+
+    let _ = tx.send(TaskUpdate::Started { total: faces.len() });
+
+    if let Err(e) = detector::ensure_embedding_model() {
+        let _ = tx.send(TaskUpdate::Failed {
+            error: format!("Failed to load embedding model: {}", e),
+        });
+        return;
+    }
+
+    match generate_embeddings_with_progress(db, &faces, &tx, &cancel_flag) {
+        Some((generated, failed)) => {
+            let message = if failed > 0 {
+                format!("Regenerated {} face embeddings, {} failed", generated, failed)
+            } else {
+                format!("Regenerated {} face embeddings", generated)
+            };
+            let _ = tx.send(TaskUpdate::Completed { message });
+        }
+        None => {
+            let _ = tx.send(TaskUpdate::Cancelled);
+        }
+    }
+}
+
 /// Cluster faces in a background task with progress reporting and cancellation support
 pub fn cluster_faces_background(
     db: &Database,
@@ -243,45 +345,15 @@ pub fn cluster_faces_background(
             }
         };
 
-        for (idx, (face_id, photo_id, bbox)) in faces_without_embeddings.iter().enumerate() {
-            // Check for cancellation
-            if cancel_flag.load(Ordering::SeqCst) {
+        match generate_embeddings_with_progress(db, &faces_without_embeddings, &tx, &cancel_flag) {
+            Some((generated, failed)) => {
+                embeddings_generated = generated;
+                embeddings_failed = failed;
+            }
+            None => {
                 let _ = tx.send(TaskUpdate::Cancelled);
                 return;
             }
-
-            // Send progress
-            let _ = tx.send(TaskUpdate::Progress(
-                TaskProgress::new(idx, faces_needing_embeddings)
-                    .with_message(format!("Generating embedding {}/{}", idx + 1, faces_needing_embeddings))
-            ));
-
-            let photo_path = match db.get_photo_path(*photo_id) {
-                Ok(Some(path)) => path,
-                _ => {
-                    embeddings_failed += 1;
-                    continue;
-                }
-            };
-
-            let path = Path::new(&photo_path);
-            if !path.exists() {
-                embeddings_failed += 1;
-                continue;
-            }
-
-            match detector::generate_embedding_for_face(path, bbox) {
-                Ok(embedding) => {
-                    if db.update_face_embedding(*face_id, &embedding).is_ok() {
-                        embeddings_generated += 1;
-                    } else {
-                        embeddings_failed += 1;
-                    }
-                }
-                Err(_) => {
-                    embeddings_failed += 1;
-                }
-            }
         }
     }
 
@@ -396,6 +468,131 @@ pub fn cluster_faces_background(
     let _ = tx.send(TaskUpdate::Completed { message: msg });
 }
 
+/// Where incremental clustering put a face
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClusterAssignment {
+    /// Joined an existing cluster, with its similarity to the centroid
+    Joined { cluster_id: i64, similarity: f32 },
+    /// Started a cluster of its own
+    Created { cluster_id: i64 },
+}
+
+/// A cluster's centroid: the sum of its members' unit-length embeddings.
+/// Cosine similarity ignores length, so the sum stands in for the mean.
+struct ClusterCentroid {
+    cluster_id: i64,
+    sum: Vec<f32>,
+}
+
+/// Adds new faces to the existing clusters, leaving faces that are already
+/// clustered where they are. Unlike [`cluster_faces`], nothing is cleared,
+/// so only the new faces are compared, and only against one centroid per
+/// cluster.
+pub struct FaceClusteringIncremental<'a> {
+    db: &'a Database,
+    centroids: Vec<ClusterCentroid>,
+    /// Number in the next new cluster's "Person N" name
+    next_number: usize,
+}
+
+impl<'a> FaceClusteringIncremental<'a> {
+    /// Compute the centroids of the clusters in the database.
+    pub fn load(db: &'a Database) -> Result<Self> {
+        let mut sums: HashMap<i64, Vec<f32>> = HashMap::new();
+        for (cluster_id, embedding) in db.get_cluster_member_embeddings()? {
+            add_unit_vector(sums.entry(cluster_id).or_default(), &embedding);
+        }
+        let mut centroids: Vec<ClusterCentroid> = sums
+            .into_iter()
+            .map(|(cluster_id, sum)| ClusterCentroid { cluster_id, sum })
+            .collect();
+        centroids.sort_by_key(|c| c.cluster_id);
+
+        let clusters = db.get_all_face_clusters()?;
+        let next_number = clusters
+            .iter()
+            .filter_map(|c| c.auto_name.strip_prefix("Person ")?.parse::<usize>().ok())
+            .max()
+            .unwrap_or(0)
+            .max(clusters.len())
+            + 1;
+
+        Ok(Self { db, centroids, next_number })
+    }
+
+    /// Put a face in the cluster whose centroid it is most similar to, if
+    /// that similarity reaches `threshold`, or else in a new cluster.
+    pub fn add_face_to_best_cluster(&mut self, face_id: i64, embedding: &[f32], threshold: f32) -> Result<ClusterAssignment> {
+        if let Some((index, similarity)) = best_cluster(&self.centroids, embedding, threshold) {
+            let centroid = &mut self.centroids[index];
+            self.db.add_face_to_cluster(face_id, centroid.cluster_id, similarity)?;
+            add_unit_vector(&mut centroid.sum, embedding);
+            return Ok(ClusterAssignment::Joined { cluster_id: centroid.cluster_id, similarity });
+        }
+
+        let auto_name = format!("Person {}", self.next_number);
+        let cluster_id = self.db.create_face_cluster(Some(face_id), &auto_name)?;
+        self.db.add_face_to_cluster(face_id, cluster_id, 1.0)?;
+        self.next_number += 1;
+
+        let mut sum = Vec::new();
+        add_unit_vector(&mut sum, embedding);
+        self.centroids.push(ClusterCentroid { cluster_id, sum });
+        Ok(ClusterAssignment::Created { cluster_id })
+    }
+}
+
+/// Index and similarity of the centroid most similar to `embedding`, if it
+/// reaches `threshold`
+fn best_cluster(centroids: &[ClusterCentroid], embedding: &[f32], threshold: f32) -> Option<(usize, f32)> {
+    centroids
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i, cosine_similarity(&c.sum, embedding)))
+        .filter(|&(_, similarity)| similarity >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Add `embedding`, scaled to unit length, to `sum`
+fn add_unit_vector(sum: &mut Vec<f32>, embedding: &[f32]) {
+    let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return;
+    }
+    if sum.is_empty() {
+        sum.resize(embedding.len(), 0.0);
+    }
+    for (s, x) in sum.iter_mut().zip(embedding) {
+        *s += x / norm;
+    }
+}
+
+/// Cluster the faces that are in no cluster yet, generating their
+/// embeddings first if needed. Returns how many faces joined an existing
+/// cluster and how many clusters were created.
+pub fn cluster_new_faces(db: &Database, similarity_threshold: f32) -> Result<(usize, usize)> {
+    if db.count_faces_without_embeddings()? > 0 {
+        generate_missing_embeddings(db)?;
+    }
+
+    let mut clustering = FaceClusteringIncremental::load(db)?;
+    let (mut joined, mut created) = (0, 0);
+    loop {
+        // Every face handled leaves the unclassified set, so this ends
+        let faces = db.get_unclassified_faces(INCREMENTAL_BATCH)?;
+        if faces.is_empty() {
+            break;
+        }
+        for (face_id, embedding) in faces {
+            match clustering.add_face_to_best_cluster(face_id, &embedding, similarity_threshold)? {
+                ClusterAssignment::Joined { .. } => joined += 1,
+                ClusterAssignment::Created { .. } => created += 1,
+            }
+        }
+    }
+    Ok((joined, created))
+}
+
 /// Calculate cosine similarity between two vectors
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
@@ -455,4 +652,23 @@ mod tests {
         let d = vec![-1.0, 0.0, 0.0];
         assert!((cosine_similarity(&a, &d) - (-1.0)).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_best_cluster() {
+        let mut first = ClusterCentroid { cluster_id: 1, sum: Vec::new() };
+        add_unit_vector(&mut first.sum, &[2.0, 0.0, 0.0]);
+        add_unit_vector(&mut first.sum, &[0.0, 1.0, 0.0]);
+        assert_eq!(first.sum, vec![1.0, 1.0, 0.0]);
+
+        let mut second = ClusterCentroid { cluster_id: 2, sum: Vec::new() };
+        add_unit_vector(&mut second.sum, &[0.0, 0.0, 3.0]);
+        let centroids = [first, second];
+
+        let (index, similarity) = best_cluster(&centroids, &[1.0, 0.9, 0.1], 0.6).unwrap();
+        assert_eq!(index, 0);
+        assert!(similarity > 0.9);
+        assert_eq!(best_cluster(&centroids, &[0.1, 0.0, 1.0], 0.6).map(|(i, _)| i), Some(1));
+        // Halfway between the clusters is close to neither
+        assert_eq!(best_cluster(&centroids, &[0.5, 0.5, 1.0], 0.9), None);
+    }
 }
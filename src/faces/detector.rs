@@ -5,6 +5,7 @@ use ort::value::Tensor;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 
+use crate::db::face_crops::crop_with_padding;
 use crate::db::BoundingBox;
 
 /// A detected face with bounding box and embedding
@@ -160,8 +161,6 @@ fn detect_faces_in_image_impl(img: &DynamicImage, generate_embeddings: bool) ->
         .lock()
         .map_err(|e| anyhow!("Failed to lock detection model: {}", e))?;
 
-    let (orig_width, orig_height) = img.dimensions();
-
     // Detect faces using UltraFace
     let face_boxes = run_ultraface_detection(&mut *detection_model, img)?;
 
@@ -201,7 +200,7 @@ fn detect_faces_in_image_impl(img: &DynamicImage, generate_embeddings: bool) ->
         }
 
         // Crop face region for embedding (with some padding)
-        let face_crop = crop_face(img, &bbox, orig_width, orig_height);
+        let face_crop = crop_with_padding(img, &bbox);
 
         // Generate embedding
         let embedding = match run_arcface_embedding(&mut *embedding_model, &face_crop) {
@@ -346,20 +345,6 @@ fn compute_iou(a: &BoundingBox, b: &BoundingBox) -> f32 {
     }
 }
 
-/// Crop face region from image with padding
-fn crop_face(img: &DynamicImage, bbox: &BoundingBox, img_width: u32, img_height: u32) -> DynamicImage {
-    // Add 20% padding around the face
-    let padding_x = (bbox.width as f32 * 0.2) as i32;
-    let padding_y = (bbox.height as f32 * 0.2) as i32;
-
-    let x = (bbox.x - padding_x).max(0) as u32;
-    let y = (bbox.y - padding_y).max(0) as u32;
-    let w = ((bbox.width + padding_x * 2) as u32).min(img_width - x);
-    let h = ((bbox.height + padding_y * 2) as u32).min(img_height - y);
-
-    img.crop_imm(x, y, w.max(1), h.max(1))
-}
-
 /// Run ArcFace embedding model
 fn run_arcface_embedding(session: &mut Session, face_img: &DynamicImage) -> Result<Vec<f32>> {
     const INPUT_SIZE: u32 = 112;
@@ -458,10 +443,9 @@ pub fn generate_embedding_for_face(image_path: &Path, bbox: &BoundingBox) -> Res
     }
 
     let img = load_image_for_detection(image_path)?;
-    let (orig_width, orig_height) = img.dimensions();
 
     // Crop face region
-    let face_crop = crop_face(&img, bbox, orig_width, orig_height);
+    let face_crop = crop_with_padding(&img, bbox);
 
     // Get embedding model
     let mut embedding_model = EMBEDDING_MODEL.get()
@@ -2,5 +2,5 @@ pub mod clustering;
 pub mod detector;
 pub mod processor;
 
-pub use clustering::cluster_faces_background;
+pub use clustering::{cluster_faces_background, regenerate_face_embeddings_background, DEFAULT_SIMILARITY_THRESHOLD};
 pub use processor::FaceProcessor;
@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use crate::db::Database;
 use crate::tasks::{TaskUpdate, TaskProgress};
-use super::detector;
+use super::{clustering, detector};
 
 /// Face processor that detects and stores faces using dlib
 pub struct FaceProcessor {
@@ -119,9 +119,24 @@ impl FaceProcessor {
             }
         }
 
-        let _ = tx.send(TaskUpdate::Completed {
-            message: format!("{} photos, {} faces found", photos_processed, total_faces),
-        });
+        let mut message = format!("{} photos, {} faces found", photos_processed, total_faces);
+
+        // Sort the new faces into the existing clusters rather than waiting
+        // for a full re-cluster
+        if total_faces > 0 && !cancel_flag.load(Ordering::SeqCst) {
+            let _ = tx.send(TaskUpdate::Progress(
+                TaskProgress::new(total, total).with_message("Adding new faces to clusters...")
+            ));
+            match clustering::cluster_new_faces(db, clustering::DEFAULT_SIMILARITY_THRESHOLD) {
+                Ok((joined, created)) if joined + created > 0 => {
+                    message.push_str(&format!(", {} joined clusters, {} new clusters", joined, created));
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "Incremental face clustering failed"),
+            }
+        }
+
+        let _ = tx.send(TaskUpdate::Completed { message });
     }
 }
 
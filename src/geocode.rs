@@ -0,0 +1,64 @@
+//! Reverse geocoding of coordinates to a place name.
+//!
+//! Uses the public OpenStreetMap Nominatim service. Lookups are only made
+//! when the user asks for one from the GPS dialog.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::time::Duration;
+
+const NOMINATIM_REVERSE_URL: &str = "https://nominatim.openstreetmap.org/reverse";
+
+#[derive(Debug, Deserialize)]
+struct ReverseResponse {
+    address: Option<Address>,
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Address {
+    city: Option<String>,
+    town: Option<String>,
+    village: Option<String>,
+    municipality: Option<String>,
+    county: Option<String>,
+    state: Option<String>,
+    country: Option<String>,
+}
+
+/// Look up a "City, Country" suggestion for the given coordinates.
+pub fn reverse_geocode(lat: f64, lon: f64) -> Result<String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(15))
+        .user_agent(concat!("clepho/", env!("CARGO_PKG_VERSION")))
+        .build();
+
+    let response: ReverseResponse = agent
+        .get(NOMINATIM_REVERSE_URL)
+        .query("format", "jsonv2")
+        .query("lat", &lat.to_string())
+        .query("lon", &lon.to_string())
+        .query("zoom", "10")
+        .call()
+        .context("Geocoding request failed")?
+        .into_json()
+        .context("Invalid geocoding response")?;
+
+    let place = response.address.and_then(|a| {
+        let locality = a
+            .city
+            .or(a.town)
+            .or(a.village)
+            .or(a.municipality)
+            .or(a.county)
+            .or(a.state);
+        match (locality, a.country) {
+            (Some(locality), Some(country)) => Some(format!("{}, {}", locality, country)),
+            (locality, country) => locality.or(country),
+        }
+    });
+
+    place
+        .or(response.display_name)
+        .context("No place found at these coordinates")
+}
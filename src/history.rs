@@ -0,0 +1,132 @@
+//! Undo/redo history for file operations.
+//!
+//! Moves, renames and trashing are recorded as they happen so the browser
+//! can put files back where they were. Only the file locations and the
+//! database paths are restored; anything overwritten along the way is gone.
+
+use std::path::PathBuf;
+
+/// Operations kept for undo
+pub const HISTORY_LIMIT: usize = 20;
+
+/// A file moved to the trash by the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedFile {
+    pub original_path: PathBuf,
+    pub trash_path: PathBuf,
+    /// Database record marked as trashed, if the file had one
+    pub photo_id: Option<i64>,
+}
+
+/// A completed file operation that can be reversed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReversibleOperation {
+    Trash(Vec<TrashedFile>),
+    /// `(source, destination)` of each moved file, in move order
+    Move(Vec<(PathBuf, PathBuf)>),
+    /// `(old, new)` path of each renamed file, in rename order
+    Rename(Vec<(PathBuf, PathBuf)>),
+}
+
+impl ReversibleOperation {
+    /// Short description for status messages, e.g. "move of 3 files"
+    pub fn description(&self) -> String {
+        let (verb, count) = match self {
+            ReversibleOperation::Trash(files) => ("trash", files.len()),
+            ReversibleOperation::Move(moves) => ("move", moves.len()),
+            ReversibleOperation::Rename(moves) => ("rename", moves.len()),
+        };
+        format!("{} of {} file{}", verb, count, if count == 1 { "" } else { "s" })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ReversibleOperation::Trash(files) => files.is_empty(),
+            ReversibleOperation::Move(moves) | ReversibleOperation::Rename(moves) => moves.is_empty(),
+        }
+    }
+}
+
+/// Status line after undoing or redoing, e.g. "Undid move of 3 files"
+pub fn outcome_message(verb: &str, description: &str, failed: usize) -> String {
+    if failed > 0 {
+        format!("{} {} ({} failed)", verb, description, failed)
+    } else {
+        format!("{} {}", verb, description)
+    }
+}
+
+/// Undo and redo stacks, most recent operation last
+#[derive(Debug, Default)]
+pub struct OperationHistory {
+    pub stack: Vec<ReversibleOperation>,
+    pub redo_stack: Vec<ReversibleOperation>,
+}
+
+impl OperationHistory {
+    /// Record a new operation. Anything undone before it can no longer be
+    /// redone, and the oldest entry is dropped past the limit.
+    pub fn record(&mut self, operation: ReversibleOperation) {
+        if operation.is_empty() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.push_undo(operation);
+    }
+
+    /// Put an operation back on the undo stack after redoing it.
+    pub fn push_undo(&mut self, operation: ReversibleOperation) {
+        self.stack.push(operation);
+        if self.stack.len() > HISTORY_LIMIT {
+            self.stack.remove(0);
+        }
+    }
+
+    /// Put an operation on the redo stack after undoing it.
+    pub fn push_redo(&mut self, operation: ReversibleOperation) {
+        self.redo_stack.push(operation);
+        if self.redo_stack.len() > HISTORY_LIMIT {
+            self.redo_stack.remove(0);
+        }
+    }
+
+    pub fn take_undo(&mut self) -> Option<ReversibleOperation> {
+        self.stack.pop()
+    }
+
+    pub fn take_redo(&mut self) -> Option<ReversibleOperation> {
+        self.redo_stack.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(from: &str, to: &str) -> ReversibleOperation {
+        ReversibleOperation::Rename(vec![(PathBuf::from(from), PathBuf::from(to))])
+    }
+
+    #[test]
+    fn test_history_limit_and_redo() {
+        let mut history = OperationHistory::default();
+        history.record(ReversibleOperation::Move(Vec::new()));
+        assert!(history.stack.is_empty());
+
+        for i in 0..HISTORY_LIMIT + 5 {
+            history.record(rename(&format!("/{}.jpg", i), &format!("/{}_new.jpg", i)));
+        }
+        assert_eq!(history.stack.len(), HISTORY_LIMIT);
+        assert_eq!(history.stack[0], rename("/5.jpg", "/5_new.jpg"));
+
+        let undone = history.take_undo().unwrap();
+        assert_eq!(undone.description(), "rename of 1 file");
+        history.push_redo(undone.clone());
+        assert_eq!(history.take_redo(), Some(undone.clone()));
+        history.push_redo(undone);
+
+        // A new operation makes the undone one unreachable
+        history.record(rename("/x.jpg", "/y.jpg"));
+        assert!(history.take_redo().is_none());
+    }
+}
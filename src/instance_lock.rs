@@ -0,0 +1,122 @@
+//! Lock file that keeps a second TUI from opening the same SQLite database.
+//!
+//! Two instances writing one SQLite file run into `SQLITE_BUSY`, so the TUI
+//! takes an exclusive `flock(2)` on `$XDG_RUNTIME_DIR/clepho-{db}.lock` (or
+//! `clepho-{uid}-{db}.lock` in the temp directory) at startup and writes its
+//! PID into it, where `{db}` is a hash of the database's canonical path. TUIs
+//! on separate databases therefore run side by side. The kernel drops the lock when the process exits, even after a
+//! crash, so a stale file never blocks startup. The file itself is left in
+//! place: deleting it could let a process that already opened it lock an
+//! orphaned copy while a third creates a new one.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Holds the instance lock until dropped
+pub struct LockGuard {
+    file: File,
+}
+
+impl LockGuard {
+    /// Take the lock at `path`, failing with the holder's PID when another
+    /// instance has it.
+    pub fn acquire(path: &Path) -> Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut pid = String::new();
+                let _ = file.read_to_string(&mut pid);
+                match pid.trim() {
+                    "" => bail!("Another clepho instance is using this database"),
+                    pid => bail!("Another clepho instance is using this database (PID {})", pid),
+                }
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+            }
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", std::process::id())?;
+        file.flush()?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// Lock file for the SQLite database at `db_path`, in `$XDG_RUNTIME_DIR`
+/// or, when there is no runtime directory, per user in the temp directory
+pub fn lock_path(db_path: &Path) -> PathBuf {
+    let db = database_key(db_path);
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir).join(format!("clepho-{}.lock", db));
+    }
+    #[cfg(unix)]
+    let name = format!("clepho-{}-{}.lock", clepho::ipc::current_uid(), db);
+    #[cfg(not(unix))]
+    let name = format!("clepho-{}.lock", db);
+    std::env::temp_dir().join(name)
+}
+
+/// Short hash of the database's canonical path, so different spellings of
+/// one file share a lock. A database not created yet is named by its
+/// canonical folder.
+fn database_key(db_path: &Path) -> String {
+    let canonical = db_path.canonicalize().unwrap_or_else(|_| {
+        match (db_path.parent().and_then(|p| p.canonicalize().ok()), db_path.file_name()) {
+            (Some(parent), Some(name)) => parent.join(name),
+            _ => db_path.to_path_buf(),
+        }
+    });
+    let digest = Sha256::digest(canonical.to_string_lossy().as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_lock_fails_until_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clepho.lock");
+
+        let guard = LockGuard::acquire(&path).unwrap();
+        let error = LockGuard::acquire(&path).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            format!("Another clepho instance is using this database (PID {})", std::process::id())
+        );
+
+        drop(guard);
+        assert!(LockGuard::acquire(&path).is_ok());
+    }
+
+    #[test]
+    fn test_lock_path_follows_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let db = dir.path().join("photos.db");
+
+        assert_eq!(lock_path(&db), lock_path(&dir.path().join("sub/../photos.db")));
+        assert_ne!(lock_path(&db), lock_path(&dir.path().join("other.db")));
+    }
+}
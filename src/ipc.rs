@@ -0,0 +1,231 @@
+//! Control socket of `clepho-daemon`.
+//!
+//! Shell scripts and `clepho --daemon-status` talk to a running daemon over
+//! the unix socket at `$XDG_RUNTIME_DIR/clepho.sock`, or
+//! `/tmp/clepho-{uid}.sock` without a runtime dir. Clients try both, since a
+//! daemon started by systemd often has no runtime dir while a login shell
+//! does. Each connection carries one
+//! request and one response, each a single line of JSON:
+//!
+//! ```text
+//! → {"command": "status"}
+//! ← {"tasks": [...], "last_scan": "2024-03-15 02:00:12"}
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader as AsyncBufReader};
+use tokio::net::UnixListener;
+
+use crate::db::{PhotoMetadata, ScheduledTask};
+
+/// How long a client waits for the daemon to answer
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the daemon waits for a client to send its request or take the
+/// response, so a stalled client cannot hold up the others
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest request line the daemon reads
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum Request {
+    /// Pending and running tasks, and when the last scan finished
+    Status,
+    /// Queue a scan of `path` to run now
+    Scan { path: PathBuf },
+    /// Cancel a pending task
+    Cancel { task_id: i64 },
+    /// Everything the database knows about the photo at `path`
+    GetPhoto { path: PathBuf },
+}
+
+/// Answer to a [`Request`]. Untagged, so `Error` must come before `Photo`,
+/// whose only field may be missing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Response {
+    Status { tasks: Vec<ScheduledTask>, last_scan: Option<String> },
+    Scheduled { scheduled: i64 },
+    Cancelled { cancelled: i64 },
+    Error { error: String },
+    Photo { photo: Option<Box<PhotoMetadata>> },
+}
+
+impl Response {
+    pub fn error(error: impl std::fmt::Display) -> Self {
+        Response::Error { error: error.to_string() }
+    }
+}
+
+/// Where the daemon listens
+pub fn socket_path() -> PathBuf {
+    socket_paths().remove(0)
+}
+
+/// Where a daemon of this user may listen, most likely first
+fn socket_paths() -> Vec<PathBuf> {
+    let fallback = PathBuf::from(format!("/tmp/clepho-{}.sock", current_uid()));
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        Some(dir) => vec![PathBuf::from(dir).join("clepho.sock"), fallback],
+        None => vec![fallback],
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
+/// libc is only a dependency on Linux; elsewhere the owner of the home
+/// directory stands in for the user id
+#[cfg(not(target_os = "linux"))]
+pub fn current_uid() -> u32 {
+    use std::os::unix::fs::MetadataExt;
+    dirs::home_dir()
+        .and_then(|home| std::fs::metadata(home).ok())
+        .map(|m| m.uid())
+        .unwrap_or(0)
+}
+
+/// Send one request to the running daemon and wait for its response.
+pub fn send_request(request: &Request) -> Result<Response> {
+    let paths = socket_paths();
+    let Some(mut stream) = paths.iter().find_map(|path| UnixStream::connect(path).ok()) else {
+        let tried: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        bail!("Failed to connect to the daemon at {} (is clepho-daemon running?)", tried.join(" or "));
+    };
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response).context("No response from the daemon")?;
+    serde_json::from_str(&response).context("Invalid response from the daemon")
+}
+
+/// Listen on the daemon socket, replacing a stale socket file left by a
+/// daemon that did not shut down cleanly. Must be called inside a tokio
+/// runtime.
+pub fn bind() -> Result<UnixListener> {
+    let path = socket_path();
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            bail!("Another daemon is already listening on {}", path.display());
+        }
+        std::fs::remove_file(&path).with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    // Created owner-only, so other users cannot connect before the chmod
+    #[cfg(target_os = "linux")]
+    // SAFETY: umask has no preconditions and cannot fail
+    let old_umask = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(&path);
+    #[cfg(target_os = "linux")]
+    // SAFETY: as above
+    unsafe {
+        libc::umask(old_umask);
+    }
+    let listener = listener.with_context(|| format!("Failed to listen on {}", path.display()))?;
+
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Answer connections one at a time until the listener fails. Requests are
+/// small and quick, so there is no need to handle them concurrently; clients
+/// that stall or send oversized requests are dropped after [`CLIENT_TIMEOUT`]
+/// or [`MAX_REQUEST_BYTES`].
+pub async fn serve(listener: UnixListener, mut handle: impl FnMut(Request) -> Response) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let (reader, mut writer) = stream.into_split();
+
+        let mut line = String::new();
+        let mut reader = AsyncBufReader::new(reader.take(MAX_REQUEST_BYTES));
+        match tokio::time::timeout(CLIENT_TIMEOUT, reader.read_line(&mut line)).await {
+            Ok(Ok(_)) if !line.ends_with('\n') && reader.get_ref().limit() == 0 => {
+                tracing::warn!("IPC request too long");
+                continue;
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => {
+                tracing::warn!(error = %e, "Failed to read IPC request");
+                continue;
+            }
+            Err(_) => {
+                tracing::warn!("Timed out reading IPC request");
+                continue;
+            }
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => handle(request),
+            Err(e) => Response::error(format!("Invalid request: {}", e)),
+        };
+
+        let mut reply = serde_json::to_string(&response)?;
+        reply.push('\n');
+        match tokio::time::timeout(CLIENT_TIMEOUT, writer.write_all(reply.as_bytes())).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => tracing::warn!(error = %e, "Failed to send IPC response"),
+            Err(_) => tracing::warn!("Timed out sending IPC response"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol() {
+        let request: Request = serde_json::from_str(r#"{"command": "status"}"#).unwrap();
+        assert_eq!(request, Request::Status);
+        let request: Request = serde_json::from_str(r#"{"command": "get-photo", "path": "/p/a.jpg"}"#).unwrap();
+        assert_eq!(request, Request::GetPhoto { path: PathBuf::from("/p/a.jpg") });
+        assert_eq!(
+            serde_json::to_string(&Request::Cancel { task_id: 3 }).unwrap(),
+            r#"{"command":"cancel","task_id":3}"#
+        );
+
+        let status = serde_json::to_string(&Response::Status { tasks: Vec::new(), last_scan: None }).unwrap();
+        assert_eq!(status, r#"{"tasks":[],"last_scan":null}"#);
+        let parse = |json: &str| serde_json::from_str::<Response>(json).unwrap();
+        assert!(matches!(parse(&status), Response::Status { .. }));
+        assert!(matches!(parse(r#"{"scheduled":4}"#), Response::Scheduled { scheduled: 4 }));
+        assert!(matches!(parse(r#"{"error":"no"}"#), Response::Error { .. }));
+        assert!(matches!(parse(r#"{"photo":null}"#), Response::Photo { photo: None }));
+    }
+
+    #[tokio::test]
+    async fn test_serve_drops_oversized_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(serve(listener, |_| Response::Scheduled { scheduled: 1 }));
+
+        let oversized = vec![b'x'; MAX_REQUEST_BYTES as usize + 1];
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        // The daemon may hang up before taking everything
+        let _ = stream.write_all(&oversized).await;
+        let mut reply = String::new();
+        let _ = AsyncBufReader::new(stream).read_line(&mut reply).await;
+        assert!(reply.is_empty());
+
+        // Later clients are still answered
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream.write_all(b"{\"command\": \"status\"}\n").await.unwrap();
+        let mut reply = String::new();
+        AsyncBufReader::new(stream).read_line(&mut reply).await.unwrap();
+        assert_eq!(reply, "{\"scheduled\":1}\n");
+    }
+}
@@ -1,4 +1,6 @@
 pub mod config;
 pub mod db;
+#[cfg(unix)]
+pub mod ipc;
 pub mod llm;
 pub mod tasks;
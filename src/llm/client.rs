@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::Deserialize;
 use std::path::Path;
 use std::sync::Arc;
@@ -13,6 +13,12 @@ pub struct ImageDescription {
     pub tags: Vec<String>,
 }
 
+/// Descriptions sent when naming an event
+const EVENT_PROMPT_DESCRIPTIONS: usize = 30;
+
+/// Question asked about a pair of duplicates
+const DUPLICATE_PROMPT: &str = "These two photos appear to be duplicates. In one sentence, explain the primary difference between them.";
+
 /// LLM client that wraps a provider implementation
 pub struct LlmClient {
     provider: Arc<dyn LlmProvider>,
@@ -87,6 +93,42 @@ impl LlmClient {
         }
     }
 
+    /// Name the event a cluster of photos shows, from their descriptions.
+    /// At most `EVENT_PROMPT_DESCRIPTIONS` descriptions, spread across the
+    /// cluster, go into the prompt.
+    pub fn infer_cluster_event(&self, descriptions: &[String]) -> Result<String> {
+        let step = descriptions.len().div_ceil(EVENT_PROMPT_DESCRIPTIONS).max(1);
+        let listed: String = descriptions
+            .iter()
+            .step_by(step)
+            .map(|d| format!("- {}\n", d.chars().take(200).collect::<String>().replace('\n', " ")))
+            .collect();
+        let prompt = format!(
+            "These are descriptions of photos taken together at one event:\n{}\n\
+             Name the event in 2 to 6 words, e.g. \"Beach day in Brighton\" or \"Sarah's birthday party\". \
+             Reply with the name only.",
+            listed
+        );
+
+        let response = self.provider.complete_text(&prompt)?;
+        clean_event_name(&response).ok_or_else(|| anyhow!("LLM returned no event name"))
+    }
+
+    /// One sentence on how two near-duplicate photos differ, such as
+    /// "IMG_0002.jpg has slightly better focus on the subject's eyes", to
+    /// help pick the one to keep. The photos are named by file name.
+    pub fn explain_duplicate_difference(&self, first: &Path, second: &Path) -> Result<String> {
+        let name = |path: &Path| path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let prompt = format!(
+            "{} The first image is {} and the second is {}; refer to them by these names.",
+            DUPLICATE_PROMPT,
+            name(first),
+            name(second)
+        );
+        let response = self.provider.compare_images(first, second, &prompt)?;
+        clean_explanation(&response).ok_or_else(|| anyhow!("LLM returned no explanation"))
+    }
+
     /// Get text embedding for semantic search
     pub fn get_text_embedding(&self, text: &str) -> Result<Vec<f32>> {
         self.provider.get_text_embedding(text)
@@ -105,3 +147,49 @@ impl Clone for LlmClient {
         }
     }
 }
+
+/// The first line of an event naming reply, without a label, quotes or a
+/// trailing full stop
+fn clean_event_name(response: &str) -> Option<String> {
+    let line = response.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let line = match line.split_once(':') {
+        Some((label, rest)) if label.trim_matches(['*', ' ']).eq_ignore_ascii_case("event") => rest,
+        _ => line,
+    };
+    let name = line
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '*' | '`'))
+        .trim_end_matches('.');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// A duplicate explanation on one line, without quotes or markdown
+fn clean_explanation(response: &str) -> Option<String> {
+    let text = response
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("**", "");
+    let text = text.trim_matches(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`'));
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_event_name() {
+        assert_eq!(clean_event_name("\"Beach day in Brighton.\"\n"), Some("Beach day in Brighton".to_string()));
+        assert_eq!(clean_event_name("\n**Event:** Sarah's birthday party"), Some("Sarah's birthday party".to_string()));
+        assert_eq!(clean_event_name("  \n "), None);
+    }
+
+    #[test]
+    fn test_clean_explanation() {
+        assert_eq!(
+            clean_explanation("\"**Left photo** has slightly better focus\non the subject's eyes.\"\n"),
+            Some("Left photo has slightly better focus on the subject's eyes.".to_string())
+        );
+        assert_eq!(clean_explanation(" `` "), None);
+    }
+}
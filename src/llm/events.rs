@@ -0,0 +1,81 @@
+//! Background event detection: cluster described photos and have the LLM
+//! name each cluster.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+use super::client::LlmClient;
+use crate::config::{DatabaseConfig, LlmConfig};
+use crate::db::events::cluster_events;
+use crate::db::Database;
+use crate::tasks::{TaskProgress, TaskUpdate};
+
+/// Rebuild the events table from the described photos. Clusters whose
+/// photos have not changed keep their stored name; the rest are named by
+/// the LLM, falling back to the date when it cannot answer.
+pub fn detect_events(
+    db_config: &DatabaseConfig,
+    llm_config: &LlmConfig,
+    tx: mpsc::Sender<TaskUpdate>,
+    cancel_flag: Arc<AtomicBool>,
+) {
+    let db = match Database::open(db_config) {
+        Ok(db) => db,
+        Err(e) => {
+            let _ = tx.send(TaskUpdate::Failed { error: format!("Failed to open database: {}", e) });
+            return;
+        }
+    };
+    let (candidates, existing) = match db.get_event_candidates().and_then(|c| Ok((c, db.get_events()?))) {
+        Ok(rows) => rows,
+        Err(e) => {
+            let _ = tx.send(TaskUpdate::Failed { error: format!("Failed to load photos: {}", e) });
+            return;
+        }
+    };
+
+    let clusters = cluster_events(candidates);
+    let total = clusters.len();
+    let _ = tx.send(TaskUpdate::Started { total });
+
+    let client = LlmClient::from_config(llm_config);
+    let mut events = Vec::with_capacity(total);
+    let (mut named, mut failed) = (0, 0);
+    for (idx, cluster) in clusters.into_iter().enumerate() {
+        if cancel_flag.load(Ordering::SeqCst) {
+            let _ = tx.send(TaskUpdate::Cancelled);
+            return;
+        }
+        let _ = tx.send(TaskUpdate::Progress(
+            TaskProgress::new(idx, total).with_message(format!("Naming event {}/{}", idx + 1, total)),
+        ));
+
+        let name = match existing.iter().find(|e| cluster.matches(e)) {
+            Some(event) => event.name.clone(),
+            None => match client.infer_cluster_event(&cluster.descriptions) {
+                Ok(name) => {
+                    named += 1;
+                    name
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to name event");
+                    failed += 1;
+                    format!("Event on {}", cluster.start.format("%Y-%m-%d"))
+                }
+            },
+        };
+        events.push(cluster.into_event(name));
+    }
+
+    if let Err(e) = db.replace_events(&events) {
+        let _ = tx.send(TaskUpdate::Failed { error: format!("Failed to save events: {}", e) });
+        return;
+    }
+
+    let message = if failed > 0 {
+        format!("{} events, {} newly named, {} unnamed", events.len(), named, failed)
+    } else {
+        format!("{} events, {} newly named", events.len(), named)
+    };
+    let _ = tx.send(TaskUpdate::Completed { message });
+}
@@ -1,4 +1,6 @@
 pub mod client;
+pub mod events;
+pub mod multi;
 pub mod provider;
 pub mod queue;
 
@@ -0,0 +1,218 @@
+//! Spreading LLM requests over several providers.
+//!
+//! Each request starts with the provider the strategy picks and falls
+//! through to the others in list order when it fails, so a local server
+//! that is down or busy does not stop a batch.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::LlmStrategy;
+use super::provider::{FaceDetectionResponse, LlmProvider};
+
+/// How long the winner of a ping race is reused before racing again
+const FASTEST_RECHECK: Duration = Duration::from_secs(60);
+
+pub struct MultiProvider {
+    providers: Vec<Arc<dyn LlmProvider>>,
+    strategy: LlmStrategy,
+    /// Starting provider of the next round-robin request
+    next: AtomicUsize,
+    /// Winner of the last ping race and when it ran
+    fastest: Mutex<Option<(usize, Instant)>>,
+}
+
+impl MultiProvider {
+    pub fn new(providers: Vec<Box<dyn LlmProvider>>, strategy: LlmStrategy) -> Self {
+        Self {
+            providers: providers.into_iter().map(Arc::from).collect(),
+            strategy,
+            next: AtomicUsize::new(0),
+            fastest: Mutex::new(None),
+        }
+    }
+
+    /// Provider indices in the order this request tries them
+    fn attempt_order(&self) -> Vec<usize> {
+        let start = match self.strategy {
+            LlmStrategy::Fallback => 0,
+            LlmStrategy::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % self.providers.len(),
+            LlmStrategy::FastestFirst => self.fastest_provider(),
+        };
+        attempt_order(start, self.providers.len())
+    }
+
+    /// Index of the provider that answered a ping first. Concurrent
+    /// requests wait for one race rather than starting their own.
+    fn fastest_provider(&self) -> usize {
+        let mut fastest = self.fastest.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((index, checked)) = *fastest {
+            if checked.elapsed() < FASTEST_RECHECK {
+                return index;
+            }
+        }
+        let index = race_pings(&self.providers).unwrap_or(0);
+        *fastest = Some((index, Instant::now()));
+        index
+    }
+
+    fn try_each<T>(&self, request: impl Fn(&dyn LlmProvider) -> Result<T>) -> Result<T> {
+        let mut errors = Vec::new();
+        for index in self.attempt_order() {
+            let provider = self.providers[index].as_ref();
+            match request(provider) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    tracing::warn!(provider = provider.provider_name(), error = %e, "LLM provider failed, trying the next");
+                    errors.push(format!("{}: {}", provider.provider_name(), e));
+                }
+            }
+        }
+        Err(anyhow!("All LLM providers failed: {}", errors.join("; ")))
+    }
+
+    /// Embeddings from different models are not comparable, so they always
+    /// come from the first provider that can make them.
+    fn embedding_provider(&self) -> Option<&dyn LlmProvider> {
+        self.providers.iter().map(|p| p.as_ref()).find(|p| p.supports_embeddings())
+    }
+}
+
+impl LlmProvider for MultiProvider {
+    fn describe_image(&self, image_path: &Path) -> Result<String> {
+        self.try_each(|provider| provider.describe_image(image_path))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Multiple providers"
+    }
+
+    fn complete_text(&self, prompt: &str) -> Result<String> {
+        self.try_each(|provider| provider.complete_text(prompt))
+    }
+
+    fn compare_images(&self, first: &Path, second: &Path, prompt: &str) -> Result<String> {
+        self.try_each(|provider| provider.compare_images(first, second, prompt))
+    }
+
+    fn ping(&self) -> Result<()> {
+        race_pings(&self.providers)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("No LLM provider answered"))
+    }
+
+    fn get_text_embedding(&self, text: &str) -> Result<Vec<f32>> {
+        match self.embedding_provider() {
+            Some(provider) => provider.get_text_embedding(text),
+            None => Err(anyhow!("Embeddings not supported by any configured provider")),
+        }
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.embedding_provider().is_some()
+    }
+
+    fn detect_faces(&self, image_path: &Path) -> Result<FaceDetectionResponse> {
+        self.try_each(|provider| provider.detect_faces(image_path))
+    }
+
+    fn supports_face_detection(&self) -> bool {
+        self.providers.iter().any(|p| p.supports_face_detection())
+    }
+}
+
+/// `start` first, then the rest in list order
+fn attempt_order(start: usize, len: usize) -> Vec<usize> {
+    std::iter::once(start).chain((0..len).filter(|&i| i != start)).collect()
+}
+
+/// Ping every provider at once and return the first to answer
+fn race_pings(providers: &[Arc<dyn LlmProvider>]) -> Option<usize> {
+    let (tx, rx) = mpsc::channel();
+    for (index, provider) in providers.iter().enumerate() {
+        let provider = Arc::clone(provider);
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send((index, provider.ping()));
+        });
+    }
+    drop(tx);
+
+    // Slower pings finish in the background and go unheard
+    for (index, result) in rx {
+        match result {
+            Ok(()) => return Some(index),
+            Err(e) => tracing::debug!(provider = providers[index].provider_name(), error = %e, "LLM ping failed"),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeProvider {
+        name: &'static str,
+        works: bool,
+        ping_delay: Duration,
+    }
+
+    impl LlmProvider for FakeProvider {
+        fn describe_image(&self, _image_path: &Path) -> Result<String> {
+            self.complete_text("")
+        }
+
+        fn provider_name(&self) -> &'static str {
+            self.name
+        }
+
+        fn complete_text(&self, _prompt: &str) -> Result<String> {
+            if self.works {
+                Ok(self.name.to_string())
+            } else {
+                Err(anyhow!("offline"))
+            }
+        }
+
+        fn ping(&self) -> Result<()> {
+            std::thread::sleep(self.ping_delay);
+            Ok(())
+        }
+    }
+
+    fn multi(strategy: LlmStrategy, works: [bool; 3]) -> MultiProvider {
+        let providers: Vec<Box<dyn LlmProvider>> = ["a", "b", "c"]
+            .into_iter()
+            .zip(works)
+            .zip([300, 300, 0])
+            .map(|((name, works), delay)| {
+                Box::new(FakeProvider { name, works, ping_delay: Duration::from_millis(delay) }) as Box<dyn LlmProvider>
+            })
+            .collect();
+        MultiProvider::new(providers, strategy)
+    }
+
+    #[test]
+    fn test_strategies() {
+        assert_eq!(attempt_order(1, 3), vec![1, 0, 2]);
+
+        let fallback = multi(LlmStrategy::Fallback, [false, true, true]);
+        assert_eq!(fallback.complete_text("").unwrap(), "b");
+        assert_eq!(fallback.complete_text("").unwrap(), "b");
+
+        let round_robin = multi(LlmStrategy::RoundRobin, [true, true, true]);
+        let answers: Vec<String> = (0..4).map(|_| round_robin.complete_text("").unwrap()).collect();
+        assert_eq!(answers, ["a", "b", "c", "a"]);
+
+        let fastest = multi(LlmStrategy::FastestFirst, [true, true, true]);
+        assert_eq!(fastest.complete_text("").unwrap(), "c");
+
+        let offline = multi(LlmStrategy::Fallback, [false, false, false]);
+        let error = offline.complete_text("").unwrap_err().to_string();
+        assert!(error.contains("a: offline") && error.contains("c: offline"));
+    }
+}
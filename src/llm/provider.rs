@@ -30,6 +30,9 @@ pub struct FaceDetectionResponse {
     pub image_height: Option<u32>,
 }
 
+/// How long a reachability check may take
+const PING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Trait for LLM providers that can describe images
 pub trait LlmProvider: Send + Sync {
     /// Describe an image at the given path
@@ -38,6 +41,18 @@ pub trait LlmProvider: Send + Sync {
     /// Get the provider name for display
     fn provider_name(&self) -> &'static str;
 
+    /// Answer a text-only prompt
+    fn complete_text(&self, prompt: &str) -> Result<String>;
+
+    /// Answer a prompt about two images, sent in the order given (optional)
+    fn compare_images(&self, _first: &Path, _second: &Path, _prompt: &str) -> Result<String> {
+        Err(anyhow!("Comparing images not supported by this provider"))
+    }
+
+    /// Cheap request to check the provider is reachable, without running
+    /// the model
+    fn ping(&self) -> Result<()>;
+
     /// Get text embedding for semantic search (optional)
     fn get_text_embedding(&self, _text: &str) -> Result<Vec<f32>> {
         Err(anyhow!("Embeddings not supported by this provider"))
@@ -258,6 +273,105 @@ impl LlmProvider for OpenAICompatibleProvider {
         "OpenAI-compatible"
     }
 
+    fn ping(&self) -> Result<()> {
+        let mut req = self.agent.get(&format!("{}/models", self.endpoint)).timeout(PING_TIMEOUT);
+        if let Some(ref api_key) = self.api_key {
+            req = req.set("Authorization", &format!("Bearer {}", api_key));
+        }
+        req.call().map_err(|e| anyhow!("LLM ping failed: {}", e))?;
+        Ok(())
+    }
+
+    fn complete_text(&self, prompt: &str) -> Result<String> {
+        let request = OpenAIChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                OpenAIMessage {
+                    role: "system".to_string(),
+                    content: OpenAIContent::Text(TEXT_SYSTEM_PROMPT.to_string()),
+                },
+                OpenAIMessage {
+                    role: "user".to_string(),
+                    content: OpenAIContent::Text(prompt.to_string()),
+                },
+            ],
+            max_tokens: 100,
+            temperature: 0.3,
+            response_format: None,
+        };
+
+        let url = format!("{}/chat/completions", self.endpoint);
+
+        let mut req = self.agent.post(&url).set("Content-Type", "application/json");
+
+        if let Some(ref api_key) = self.api_key {
+            req = req.set("Authorization", &format!("Bearer {}", api_key));
+        }
+
+        let response = req
+            .send_json(&request)
+            .map_err(|e| anyhow!("LLM request failed: {}", e))?;
+
+        let chat_response: OpenAIChatResponse = response
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse LLM response: {}", e))?;
+
+        chat_response
+            .choices
+            .first()
+            .map(|c| c.message.content.clone())
+            .ok_or_else(|| anyhow!("No response from LLM"))
+    }
+
+    fn compare_images(&self, first: &Path, second: &Path, prompt: &str) -> Result<String> {
+        let mut parts = vec![OpenAIContentPart::Text { text: prompt.to_string() }];
+        for path in [first, second] {
+            let (base64_image, mime_type) = load_and_encode_image(path, 1024)?;
+            parts.push(OpenAIContentPart::ImageUrl {
+                image_url: ImageUrl { url: format!("data:{};base64,{}", mime_type, base64_image) },
+            });
+        }
+
+        let request = OpenAIChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                OpenAIMessage {
+                    role: "system".to_string(),
+                    content: OpenAIContent::Text(TEXT_SYSTEM_PROMPT.to_string()),
+                },
+                OpenAIMessage {
+                    role: "user".to_string(),
+                    content: OpenAIContent::Parts(parts),
+                },
+            ],
+            max_tokens: 100,
+            temperature: 0.3,
+            response_format: None,
+        };
+
+        let url = format!("{}/chat/completions", self.endpoint);
+
+        let mut req = self.agent.post(&url).set("Content-Type", "application/json");
+
+        if let Some(ref api_key) = self.api_key {
+            req = req.set("Authorization", &format!("Bearer {}", api_key));
+        }
+
+        let response = req
+            .send_json(&request)
+            .map_err(|e| anyhow!("LLM request failed: {}", e))?;
+
+        let chat_response: OpenAIChatResponse = response
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse LLM response: {}", e))?;
+
+        chat_response
+            .choices
+            .first()
+            .map(|c| c.message.content.clone())
+            .ok_or_else(|| anyhow!("No response from LLM"))
+    }
+
     fn get_text_embedding(&self, text: &str) -> Result<Vec<f32>> {
         let request = EmbeddingRequest {
             model: self.embedding_model.clone(),
@@ -386,6 +500,8 @@ fn load_and_encode_image(image_path: &Path, max_dimension: u32) -> Result<(Strin
 /// System prompt for photo cataloguing tasks.
 const SYSTEM_PROMPT: &str = "You are a photo cataloguing assistant. Describe images factually and concisely. Always respond with valid JSON in the exact format requested.";
 
+const TEXT_SYSTEM_PROMPT: &str = "You are a photo cataloguing assistant. Answer concisely, with no preamble.";
+
 const FACE_DETECTION_PROMPT: &str = r#"Analyze this image and detect all human faces present.
 
 For each face found, provide:
@@ -580,6 +696,88 @@ impl LlmProvider for AnthropicProvider {
         "Anthropic Claude"
     }
 
+    fn ping(&self) -> Result<()> {
+        self.agent
+            .get("https://api.anthropic.com/v1/models")
+            .timeout(PING_TIMEOUT)
+            .set("x-api-key", &self.api_key)
+            .set("anthropic-version", "2023-06-01")
+            .call()
+            .map_err(|e| anyhow!("Anthropic ping failed: {}", e))?;
+        Ok(())
+    }
+
+    fn complete_text(&self, prompt: &str) -> Result<String> {
+        let request = AnthropicRequest {
+            model: self.model.clone(),
+            max_tokens: 100,
+            system: TEXT_SYSTEM_PROMPT.to_string(),
+            messages: vec![AnthropicMessage {
+                role: "user".to_string(),
+                content: vec![AnthropicContent::Text { text: prompt.to_string() }],
+            }],
+        };
+
+        let response = self.agent.post("https://api.anthropic.com/v1/messages")
+            .set("Content-Type", "application/json")
+            .set("x-api-key", &self.api_key)
+            .set("anthropic-version", "2023-06-01")
+            .send_json(&request)
+            .map_err(|e| anyhow!("Anthropic request failed: {}", e))?;
+
+        let anthropic_response: AnthropicResponse = response
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse Anthropic response: {}", e))?;
+
+        anthropic_response
+            .content
+            .first()
+            .and_then(|c| c.text.clone())
+            .ok_or_else(|| anyhow!("No response from Anthropic"))
+    }
+
+    fn compare_images(&self, first: &Path, second: &Path, prompt: &str) -> Result<String> {
+        let mut content = Vec::new();
+        for path in [first, second] {
+            let (base64_image, media_type) = load_and_encode_image(path, 1024)?;
+            content.push(AnthropicContent::Image {
+                source: AnthropicImageSource {
+                    source_type: "base64".to_string(),
+                    media_type: media_type.to_string(),
+                    data: base64_image,
+                },
+            });
+        }
+        content.push(AnthropicContent::Text { text: prompt.to_string() });
+
+        let request = AnthropicRequest {
+            model: self.model.clone(),
+            max_tokens: 100,
+            system: TEXT_SYSTEM_PROMPT.to_string(),
+            messages: vec![AnthropicMessage {
+                role: "user".to_string(),
+                content,
+            }],
+        };
+
+        let response = self.agent.post("https://api.anthropic.com/v1/messages")
+            .set("Content-Type", "application/json")
+            .set("x-api-key", &self.api_key)
+            .set("anthropic-version", "2023-06-01")
+            .send_json(&request)
+            .map_err(|e| anyhow!("Anthropic request failed: {}", e))?;
+
+        let anthropic_response: AnthropicResponse = response
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse Anthropic response: {}", e))?;
+
+        anthropic_response
+            .content
+            .first()
+            .and_then(|c| c.text.clone())
+            .ok_or_else(|| anyhow!("No response from Anthropic"))
+    }
+
     fn detect_faces(&self, image_path: &Path) -> Result<FaceDetectionResponse> {
         let (base64_image, media_type) = load_and_encode_image(image_path, 2048)?;
 
@@ -750,6 +948,68 @@ impl LlmProvider for OllamaProvider {
         "Ollama"
     }
 
+    fn ping(&self) -> Result<()> {
+        self.agent
+            .get(&format!("{}/api/tags", self.endpoint))
+            .timeout(PING_TIMEOUT)
+            .call()
+            .map_err(|e| anyhow!("Ollama ping failed: {}", e))?;
+        Ok(())
+    }
+
+    fn complete_text(&self, prompt: &str) -> Result<String> {
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            prompt: prompt.to_string(),
+            system: TEXT_SYSTEM_PROMPT.to_string(),
+            images: Vec::new(),
+            stream: false,
+            format: None,
+        };
+
+        let url = format!("{}/api/generate", self.endpoint);
+
+        let response = self.agent.post(&url)
+            .set("Content-Type", "application/json")
+            .send_json(&request)
+            .map_err(|e| anyhow!("Ollama request failed: {}", e))?;
+
+        let ollama_response: OllamaResponse = response
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse Ollama response: {}", e))?;
+
+        Ok(ollama_response.response)
+    }
+
+    fn compare_images(&self, first: &Path, second: &Path, prompt: &str) -> Result<String> {
+        let images = [first, second]
+            .into_iter()
+            .map(|path| load_and_encode_image(path, 1024).map(|(base64_image, _mime_type)| base64_image))
+            .collect::<Result<Vec<_>>>()?;
+
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            prompt: prompt.to_string(),
+            system: TEXT_SYSTEM_PROMPT.to_string(),
+            images,
+            stream: false,
+            format: None,
+        };
+
+        let url = format!("{}/api/generate", self.endpoint);
+
+        let response = self.agent.post(&url)
+            .set("Content-Type", "application/json")
+            .send_json(&request)
+            .map_err(|e| anyhow!("Ollama request failed: {}", e))?;
+
+        let ollama_response: OllamaResponse = response
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse Ollama response: {}", e))?;
+
+        Ok(ollama_response.response)
+    }
+
     fn get_text_embedding(&self, text: &str) -> Result<Vec<f32>> {
         let request = OllamaEmbeddingRequest {
             model: self.embedding_model.clone(),
@@ -814,20 +1074,34 @@ impl LlmProvider for OllamaProvider {
 // Factory function
 // ============================================================================
 
-use crate::config::{LlmConfig, LlmProviderType};
+use crate::config::{LlmConfig, LlmProviderConfig, LlmProviderType};
+use super::multi::MultiProvider;
 
-/// Create an LLM provider based on configuration
+/// Create an LLM provider based on configuration. Several configured
+/// providers are wrapped in a [`MultiProvider`].
 pub fn create_provider(config: &LlmConfig) -> Box<dyn LlmProvider> {
+    let mut providers: Vec<Box<dyn LlmProvider>> = config
+        .provider_list()
+        .iter()
+        .map(|provider| create_single_provider(provider, config))
+        .collect();
+    if providers.len() == 1 {
+        return providers.remove(0);
+    }
+    Box::new(MultiProvider::new(providers, config.strategy))
+}
+
+fn create_single_provider(provider: &LlmProviderConfig, config: &LlmConfig) -> Box<dyn LlmProvider> {
     let custom_prompt = config.custom_prompt.clone();
     let base_prompt = config.base_prompt.clone();
     let json_mode = config.json_mode;
 
-    match config.provider {
+    match provider.provider {
         LlmProviderType::LmStudio => Box::new(
             OpenAICompatibleProvider::new(
-                &config.endpoint,
-                &config.model,
-                config.api_key.as_deref(),
+                &provider.endpoint,
+                &provider.model,
+                provider.api_key.as_deref(),
             )
             .with_custom_prompt(custom_prompt)
             .with_base_prompt(base_prompt)
@@ -836,23 +1110,23 @@ pub fn create_provider(config: &LlmConfig) -> Box<dyn LlmProvider> {
         LlmProviderType::OpenAI => Box::new(
             OpenAICompatibleProvider::new(
                 "https://api.openai.com/v1",
-                &config.model,
-                config.api_key.as_deref(),
+                &provider.model,
+                provider.api_key.as_deref(),
             )
             .with_custom_prompt(custom_prompt)
             .with_base_prompt(base_prompt)
             .with_json_mode(json_mode),
         ),
         LlmProviderType::Anthropic => {
-            let api_key = config.api_key.as_deref().unwrap_or("");
+            let api_key = provider.api_key.as_deref().unwrap_or("");
             Box::new(
-                AnthropicProvider::new(api_key, Some(&config.model))
+                AnthropicProvider::new(api_key, Some(&provider.model))
                     .with_custom_prompt(custom_prompt)
                     .with_base_prompt(base_prompt),
             )
         }
         LlmProviderType::Ollama => Box::new(
-            OllamaProvider::new(Some(&config.endpoint), &config.model)
+            OllamaProvider::new(Some(&provider.endpoint), &provider.model)
                 .with_custom_prompt(custom_prompt)
                 .with_base_prompt(base_prompt)
                 .with_json_mode(json_mode),
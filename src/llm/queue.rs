@@ -5,7 +5,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 
 use super::client::LlmClient;
-use crate::config::DatabaseConfig;
+use crate::config::{DatabaseConfig, MAX_LLM_CONCURRENCY};
 use crate::db::Database;
 use crate::tasks::{TaskUpdate, TaskProgress};
 
@@ -50,14 +50,17 @@ impl LlmQueue {
     }
 
     /// Process all tasks in parallel with configurable concurrency.
+    /// Each worker owns a slot showing the photo it is describing; progress
+    /// reports completed photos across all workers plus every busy slot.
+    /// Returns the number of photos processed and failed.
     pub fn process_all_parallel(
         &mut self,
         db_config: &DatabaseConfig,
         tx: mpsc::Sender<TaskUpdate>,
         cancel_flag: Arc<AtomicBool>,
         concurrency: usize,
-    ) {
-        let concurrency = concurrency.max(1);
+    ) -> (usize, usize) {
+        let concurrency = concurrency.clamp(1, MAX_LLM_CONCURRENCY);
         let total = self.tasks.len();
 
         let _ = tx.send(TaskUpdate::Started { total });
@@ -68,20 +71,31 @@ impl LlmQueue {
         let failed = Arc::new(AtomicUsize::new(0));
         let consecutive_failures = Arc::new(AtomicUsize::new(0));
         let abort_flag = Arc::new(AtomicBool::new(false));
+        let slots: Arc<Mutex<Vec<Option<String>>>> = Arc::new(Mutex::new(vec![None; concurrency]));
 
         const MAX_CONSECUTIVE_FAILURES: usize = 3;
 
+        let report_progress = |slots: &Mutex<Vec<Option<String>>>, done: usize| {
+            let progress = TaskProgress::new(done, total);
+            let progress = match active_items(&slots.lock().unwrap()) {
+                Some(items) => progress.with_item(items),
+                None => progress,
+            };
+            let _ = tx.send(TaskUpdate::Progress(progress));
+        };
+
         std::thread::scope(|scope| {
-            for _ in 0..concurrency {
+            for slot in 0..concurrency {
                 let work_queue = work_queue.clone();
                 let client = self.client.clone();
                 let db_config = db_config.clone();
-                let tx = tx.clone();
                 let cancel_flag = cancel_flag.clone();
                 let abort_flag = abort_flag.clone();
                 let processed = processed.clone();
                 let failed = failed.clone();
                 let consecutive_failures = consecutive_failures.clone();
+                let slots = slots.clone();
+                let report_progress = &report_progress;
 
                 scope.spawn(move || {
                     let db = match Database::open(&db_config) {
@@ -107,23 +121,29 @@ impl LlmQueue {
                             None => break, // No more work
                         };
 
-                        let done = processed.load(Ordering::SeqCst) + failed.load(Ordering::SeqCst) + 1;
                         let filename = task.photo_path.file_name()
                             .map(|n| n.to_string_lossy().to_string())
                             .unwrap_or_else(|| task.photo_path.to_string_lossy().to_string());
 
-                        let _ = tx.send(TaskUpdate::Progress(
-                            TaskProgress::new(done, total).with_item(&filename)
-                        ));
+                        slots.lock().unwrap()[slot] = Some(filename);
+                        let done = processed.load(Ordering::SeqCst) + failed.load(Ordering::SeqCst);
+                        report_progress(&slots, done);
 
-                        match process_task(&client, &task, &db) {
+                        let result = process_task(&client, &task, &db);
+                        slots.lock().unwrap()[slot] = None;
+
+                        match result {
                             Ok(_) => {
                                 processed.fetch_add(1, Ordering::SeqCst);
                                 consecutive_failures.store(0, Ordering::SeqCst);
+                                let done = processed.load(Ordering::SeqCst) + failed.load(Ordering::SeqCst);
+                                report_progress(&slots, done);
                             }
                             Err(e) => {
                                 failed.fetch_add(1, Ordering::SeqCst);
                                 let cf = consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+                                let done = processed.load(Ordering::SeqCst) + failed.load(Ordering::SeqCst);
+                                report_progress(&slots, done);
 
                                 if cf <= MAX_CONSECUTIVE_FAILURES {
                                     tracing::error!(path = %task.photo_path.display(), error = %e, "LLM processing error");
@@ -165,6 +185,8 @@ impl LlmQueue {
                 message: format!("{} photos processed", p),
             });
         }
+
+        (p, f)
     }
 
     /// Process all tasks sequentially with cancellation support (legacy).
@@ -240,6 +262,16 @@ impl LlmQueue {
     }
 }
 
+/// Names of the photos currently being described, for the task list
+fn active_items(slots: &[Option<String>]) -> Option<String> {
+    let items: Vec<&str> = slots.iter().flatten().map(String::as_str).collect();
+    if items.is_empty() {
+        None
+    } else {
+        Some(items.join(", "))
+    }
+}
+
 fn process_task(client: &LlmClient, task: &LlmTask, db: &Database) -> Result<()> {
     let (description, tags) = client.describe_and_tag_image(&task.photo_path)?;
 
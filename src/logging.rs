@@ -11,7 +11,7 @@ use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Env
 /// Initialize the logging system.
 ///
 /// On Linux, this will attempt to connect to systemd-journald.
-/// If unavailable or on other platforms, logs go to a file in the config directory.
+/// If unavailable or on other platforms, logs go to a file in the state directory.
 ///
 /// Log level can be controlled via the `CLEPHO_LOG` environment variable:
 /// - `CLEPHO_LOG=debug` for verbose output
@@ -37,12 +37,7 @@ pub fn init(log_dir: Option<PathBuf>) -> Result<()> {
     }
 
     // Fallback to file-based logging
-    let log_dir = log_dir.unwrap_or_else(|| {
-        dirs::data_local_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("clepho")
-            .join("logs")
-    });
+    let log_dir = log_dir.unwrap_or_else(crate::config::Config::state_dir);
 
     std::fs::create_dir_all(&log_dir)?;
 
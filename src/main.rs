@@ -1,13 +1,22 @@
 mod app;
+mod batch;
 mod centralise;
 mod clip;
 mod export;
 mod faces;
+mod geocode;
+mod history;
+mod instance_lock;
 mod logging;
+mod notifications;
 mod scanner;
 mod schedule;
+mod session;
+mod share;
+mod transfer;
 mod trash;
 mod ui;
+mod watch;
 
 // Re-export shared modules from library crate so binary submodules
 // can use them via `crate::config`, `crate::db`, `crate::llm`, `crate::tasks`.
@@ -16,7 +25,7 @@ pub(crate) use clepho::db;
 pub(crate) use clepho::llm;
 pub(crate) use clepho::tasks;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -27,19 +36,58 @@ use std::io;
 use std::path::PathBuf;
 
 use app::App;
+use batch::BatchAction;
 use config::Config;
 
 enum CliAction {
-    RunTui(Option<PathBuf>),
+    RunTui { config_path: Option<PathBuf>, theme: Option<String>, dry_run: bool, force: bool },
+    ImportMetadata { config_path: Option<PathBuf>, csv_path: PathBuf, directory: PathBuf },
+    ImportPhotoPrism { config_path: Option<PathBuf>, sidecar_dir: PathBuf },
+    ExportFaceCrops { config_path: Option<PathBuf>, output_dir: PathBuf, person_id: Option<i64>, min_size: u32 },
+    ExportPeople { config_path: Option<PathBuf>, output: PathBuf, include_embeddings: bool },
+    ImportPeople { config_path: Option<PathBuf>, input: PathBuf },
+    ExportAlbum { config_path: Option<PathBuf>, album_id: i64, dest_dir: PathBuf, rename: Option<String> },
+    Batch { config_path: Option<PathBuf>, action: BatchAction, json: bool },
+    Centralise { config_path: Option<PathBuf>, source: PathBuf, library: Option<PathBuf>, dry_run: bool },
     #[cfg(feature = "postgres")]
     MigrateToPostgres { config_path: Option<PathBuf>, postgres_url: String },
+    #[cfg(feature = "postgres")]
+    MigrateFromPostgres { postgres_url: String, output: PathBuf },
+    #[cfg(unix)]
+    DaemonStatus,
+    DetectProtocol,
 }
 
 fn parse_args() -> CliAction {
     let args: Vec<String> = std::env::args().collect();
     let mut config_path = None;
+    let mut theme = None;
+    let mut import: Option<(PathBuf, PathBuf)> = None;
+    let mut photoprism_dir: Option<PathBuf> = None;
+    let mut face_crops_dir: Option<PathBuf> = None;
+    let mut face_crops_person: Option<i64> = None;
+    let mut face_crops_min_size: u32 = 112;
+    let mut people_export: Option<PathBuf> = None;
+    let mut people_import: Option<PathBuf> = None;
+    let mut with_embeddings = false;
+    let mut album_export: Option<(i64, PathBuf)> = None;
+    let mut rename_pattern: Option<String> = None;
+    let mut batch: Option<BatchAction> = None;
+    let mut no_tui = false;
+    let mut json = false;
+    let mut centralise_source: Option<PathBuf> = None;
+    let mut library: Option<PathBuf> = None;
+    let mut dry_run = false;
+    let mut force = false;
+    let mut detect_protocol = false;
+    #[cfg(unix)]
+    let mut daemon_status = false;
     #[cfg(feature = "postgres")]
     let mut migrate_url: Option<String> = None;
+    #[cfg(feature = "postgres")]
+    let mut migrate_from_url: Option<String> = None;
+    #[cfg(feature = "postgres")]
+    let mut output: Option<PathBuf> = None;
 
     let mut i = 1;
     while i < args.len() {
@@ -61,6 +109,150 @@ fn parse_args() -> CliAction {
                     std::process::exit(1);
                 }
             }
+            "--theme" | "-t" => {
+                if i + 1 < args.len() {
+                    let name = args[i + 1].clone();
+                    if ui::theme::Theme::builtin(&name).is_none() {
+                        eprintln!("Error: unknown theme '{}'", name);
+                        eprintln!("Available themes: {}", ui::theme::BUILTIN_THEMES.join(", "));
+                        std::process::exit(1);
+                    }
+                    theme = Some(name);
+                    i += 1;
+                } else {
+                    eprintln!("Error: --theme requires a theme name");
+                    std::process::exit(1);
+                }
+            }
+            "--import-metadata" => {
+                if i + 2 < args.len() {
+                    import = Some((PathBuf::from(&args[i + 1]), PathBuf::from(&args[i + 2])));
+                    i += 2;
+                } else {
+                    eprintln!("Error: --import-metadata requires a CSV file and a directory");
+                    std::process::exit(1);
+                }
+            }
+            "--import-photoprism" => {
+                if i + 1 < args.len() {
+                    photoprism_dir = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --import-photoprism requires PhotoPrism's sidecar directory");
+                    std::process::exit(1);
+                }
+            }
+            "--export-face-crops" => {
+                if i + 1 < args.len() {
+                    face_crops_dir = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --export-face-crops requires an output directory");
+                    std::process::exit(1);
+                }
+            }
+            "--person" => {
+                match args.get(i + 1).and_then(|v| v.parse().ok()) {
+                    Some(id) => face_crops_person = Some(id),
+                    None => {
+                        eprintln!("Error: --person requires a numeric person id");
+                        std::process::exit(1);
+                    }
+                }
+                i += 1;
+            }
+            "--min-size" => {
+                match args.get(i + 1).and_then(|v| v.parse().ok()) {
+                    Some(size) => face_crops_min_size = size,
+                    None => {
+                        eprintln!("Error: --min-size requires a size in pixels");
+                        std::process::exit(1);
+                    }
+                }
+                i += 1;
+            }
+            "--export-people" => {
+                if i + 1 < args.len() {
+                    people_export = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --export-people requires an output file");
+                    std::process::exit(1);
+                }
+            }
+            "--with-embeddings" => with_embeddings = true,
+            "--import-people" => {
+                if i + 1 < args.len() {
+                    people_import = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --import-people requires a JSON file");
+                    std::process::exit(1);
+                }
+            }
+            "--export-album" => {
+                match args.get(i + 1).and_then(|v| v.parse().ok()) {
+                    Some(id) if i + 2 < args.len() => {
+                        album_export = Some((id, PathBuf::from(&args[i + 2])));
+                        i += 2;
+                    }
+                    _ => {
+                        eprintln!("Error: --export-album requires a numeric album id and a directory");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--rename" => {
+                if i + 1 < args.len() {
+                    rename_pattern = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Error: --rename requires a pattern such as {{album}}_{{seq:04}}.{{ext}}");
+                    std::process::exit(1);
+                }
+            }
+            "--centralise" => {
+                if i + 1 < args.len() {
+                    centralise_source = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --centralise requires a directory");
+                    std::process::exit(1);
+                }
+            }
+            "--library" => {
+                if i + 1 < args.len() {
+                    library = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --library requires a directory");
+                    std::process::exit(1);
+                }
+            }
+            "--dry-run" => dry_run = true,
+            "--force" => force = true,
+            "--detect-protocol" => detect_protocol = true,
+            #[cfg(unix)]
+            "--daemon-status" => daemon_status = true,
+            "--no-tui" => no_tui = true,
+            "--json" => json = true,
+            "--scan-all" if batch.is_none() => batch = Some(BatchAction::ScanAll),
+            "--list-directories" if batch.is_none() => batch = Some(BatchAction::ListDirectories),
+            name @ ("scan" | "describe" | "find-duplicates" | "export") if batch.is_none() => {
+                let arg = if BatchAction::takes_argument(name) {
+                    i += 1;
+                    args.get(i).map(String::as_str)
+                } else {
+                    None
+                };
+                match BatchAction::parse(name, arg) {
+                    Ok(action) => batch = Some(action),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
             #[cfg(feature = "postgres")]
             "--migrate-to-postgres" => {
                 if i + 1 < args.len() {
@@ -77,6 +269,32 @@ fn parse_args() -> CliAction {
                 eprintln!("Rebuild with: cargo build --features postgres");
                 std::process::exit(1);
             }
+            #[cfg(feature = "postgres")]
+            "--migrate-from-postgres" => {
+                if i + 1 < args.len() {
+                    migrate_from_url = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Error: --migrate-from-postgres requires a PostgreSQL URL argument");
+                    std::process::exit(1);
+                }
+            }
+            #[cfg(not(feature = "postgres"))]
+            "--migrate-from-postgres" => {
+                eprintln!("Error: --migrate-from-postgres requires the 'postgres' feature");
+                eprintln!("Rebuild with: cargo build --features postgres");
+                std::process::exit(1);
+            }
+            #[cfg(feature = "postgres")]
+            "--output" => {
+                if i + 1 < args.len() {
+                    output = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Error: --output requires a path argument");
+                    std::process::exit(1);
+                }
+            }
             _ => {
                 eprintln!("Unknown argument: {}", args[i]);
                 print_help();
@@ -91,7 +309,66 @@ fn parse_args() -> CliAction {
         return CliAction::MigrateToPostgres { config_path, postgres_url: url };
     }
 
-    CliAction::RunTui(config_path)
+    #[cfg(feature = "postgres")]
+    if let Some(url) = migrate_from_url {
+        let Some(output) = output else {
+            eprintln!("Error: --migrate-from-postgres requires --output PATH for the new SQLite database");
+            std::process::exit(1);
+        };
+        return CliAction::MigrateFromPostgres { postgres_url: url, output };
+    }
+
+    #[cfg(unix)]
+    if daemon_status {
+        return CliAction::DaemonStatus;
+    }
+
+    if detect_protocol {
+        return CliAction::DetectProtocol;
+    }
+
+    if let Some((csv_path, directory)) = import {
+        return CliAction::ImportMetadata { config_path, csv_path, directory };
+    }
+
+    if let Some(sidecar_dir) = photoprism_dir {
+        return CliAction::ImportPhotoPrism { config_path, sidecar_dir };
+    }
+
+    if let Some(output_dir) = face_crops_dir {
+        return CliAction::ExportFaceCrops {
+            config_path,
+            output_dir,
+            person_id: face_crops_person,
+            min_size: face_crops_min_size,
+        };
+    }
+
+    if let Some(output) = people_export {
+        return CliAction::ExportPeople { config_path, output, include_embeddings: with_embeddings };
+    }
+
+    if let Some(input) = people_import {
+        return CliAction::ImportPeople { config_path, input };
+    }
+
+    if let Some((album_id, dest_dir)) = album_export {
+        return CliAction::ExportAlbum { config_path, album_id, dest_dir, rename: rename_pattern };
+    }
+
+    if let Some(source) = centralise_source {
+        return CliAction::Centralise { config_path, source, library, dry_run };
+    }
+
+    if let Some(action) = batch {
+        return CliAction::Batch { config_path, action, json };
+    }
+    if no_tui || json {
+        eprintln!("Error: --no-tui and --json need an action: scan, --scan-all, describe, find-duplicates, export or --list-directories");
+        std::process::exit(1);
+    }
+
+    CliAction::RunTui { config_path, theme, dry_run, force }
 }
 
 fn print_help() {
@@ -100,10 +377,50 @@ fn print_help() {
 
 USAGE:
     clepho [OPTIONS]
+    clepho [OPTIONS] ACTION [--json]
+
+ACTIONS (run without the TUI, progress on stderr, result on stdout):
+    scan DIR                          Scan DIR for photos
+    --scan-all                        Scan each directory in scanner.auto_scan_dirs in turn
+    describe DIR                      Describe undescribed photos in DIR with the LLM
+    find-duplicates                   List exact, perceptual and similar duplicate groups
+    export FILE                       Export the database to FILE (.json, .csv or .html)
+    --list-directories                List each directory with scanned photos, one per line:
+                                      path, photo count and total bytes, tab-separated
 
 OPTIONS:
     --config, -c PATH                 Path to config file
+    --theme, -t NAME                  Color theme for this session (dark, light, solarized)
+    --import-metadata CSV DIR         Apply descriptions, tags and ratings from a CSV
+                                      (columns: filename, description, tags, rating)
+                                      to scanned photos in DIR
+    --import-photoprism DIR           Apply PhotoPrism .yml sidecars in DIR (storage/sidecar)
+                                      to scanned photos with the same file names
+    --export-face-crops DIR           Save each detected face as DIR/<person>/<face_id>.jpg
+                                      (unassigned faces go in DIR/unknown)
+      --person ID                     Only export faces of this person
+      --min-size N                    Upscale crops smaller than N×N pixels (default: 112)
+    --export-people FILE              Write people and their faces to a JSON FILE
+      --with-embeddings               Include face embeddings (base64)
+    --import-people FILE              Recreate people from an --export-people FILE
+                                      (the photos must already be scanned)
+    --export-album ID DIR             Copy the photos of album ID into DIR, in album order
+      --rename PATTERN                Name the copies from PATTERN, e.g. {{album}}_{{seq:04}}.{{ext}}
+                                      ({{album}}, {{name}}, {{ext}}, {{seq}}, {{seq:N}})
+    --centralise DIR                  Organise the scanned photos under DIR into the library
+      --library DIR                   Library root (default: library.path from the config)
+    --dry-run                         Print file operations instead of performing them;
+                                      in the TUI, log them (move, paste, trash, centralise)
+    --force                           Start the TUI even if another instance is using
+                                      the same SQLite database
+    --no-tui                          Run ACTION without the TUI (implied by giving an action)
+    --json                            Print the result of ACTION as JSON
     --migrate-to-postgres URL         Migrate SQLite database to PostgreSQL (requires postgres feature)
+    --migrate-from-postgres URL       Copy a PostgreSQL database into a new SQLite database
+      --output PATH                   Where to create the SQLite database (must not exist)
+    --daemon-status                   Print the running daemon's tasks and last scan as JSON
+    --detect-protocol                 Print the image protocol preview.protocol = "auto"
+                                      picks in this terminal
     --version, -V                     Show version
     --help, -h                        Show this help message
 
@@ -122,16 +439,30 @@ async fn main() -> Result<()> {
     let action = parse_args();
 
     // Initialize logging (uses journald on Linux, file fallback otherwise)
-    let _ = logging::init(Some(Config::config_dir().join("logs")));
+    let _ = logging::init(Some(Config::state_dir()));
 
     match action {
-        CliAction::RunTui(config_path) => {
+        CliAction::RunTui { config_path, theme, dry_run, force } => {
             // Load configuration
-            let config = match config_path {
-                Some(path) => Config::load_from(&path)?,
+            let config = match &config_path {
+                Some(path) => Config::load_from(path)?,
                 None => Config::load()?,
             };
 
+            // PostgreSQL handles concurrent clients itself
+            let _lock = if force || config.database.backend == config::DatabaseType::Postgresql {
+                None
+            } else {
+                match instance_lock::LockGuard::acquire(&instance_lock::lock_path(&config.database.sqlite_path)) {
+                    Ok(lock) => Some(lock),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        eprintln!("Use --force to start anyway");
+                        std::process::exit(1);
+                    }
+                }
+            };
+
             // Initialize database
             let db = db::Database::open(&config.database)?;
             db.initialize()?;
@@ -145,6 +476,11 @@ async fn main() -> Result<()> {
 
             // Create and run app
             let mut app = App::new(config, db)?;
+            app.dry_run = dry_run;
+            app.config_file = config_path;
+            if let Some(name) = theme {
+                app.apply_theme(&name);
+            }
             let result = app.run(&mut terminal).await;
 
             // Restore terminal
@@ -158,6 +494,171 @@ async fn main() -> Result<()> {
 
             result
         }
+        CliAction::ImportMetadata { config_path, csv_path, directory } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            // Photos are stored under absolute paths
+            let directory = std::fs::canonicalize(&directory).unwrap_or(directory);
+            let report = db.import_metadata_from_csv(&csv_path, &directory.to_string_lossy())?;
+            println!(
+                "Imported {}: {} matched, {} unmatched, {} errors",
+                csv_path.display(),
+                report.matched,
+                report.unmatched,
+                report.errors
+            );
+            Ok(())
+        }
+        CliAction::ImportPhotoPrism { config_path, sidecar_dir } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            let report = db.import_photoprism_sidecars(&sidecar_dir)?;
+            println!(
+                "Imported {}: {} matched, {} unmatched, {} errors",
+                sidecar_dir.display(),
+                report.matched,
+                report.unmatched,
+                report.errors
+            );
+            Ok(())
+        }
+        CliAction::ExportFaceCrops { config_path, output_dir, person_id, min_size } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            let report = db.export_face_crops(person_id, &output_dir, min_size)?;
+            println!(
+                "Exported {} face crops to {} ({} skipped)",
+                report.exported,
+                output_dir.display(),
+                report.skipped
+            );
+            Ok(())
+        }
+        CliAction::ExportPeople { config_path, output, include_embeddings } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            let json = db.export_people_json(include_embeddings)?;
+            std::fs::write(&output, json)
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            println!("Exported {} people to {}", db.count_people()?, output.display());
+            Ok(())
+        }
+        CliAction::ImportPeople { config_path, input } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            let json = std::fs::read_to_string(&input)
+                .with_context(|| format!("Failed to read {}", input.display()))?;
+            let report = db.import_people_json(&json)?;
+            println!(
+                "Imported {}: {} people, {} faces, {} faces in photos not in the database",
+                input.display(),
+                report.people,
+                report.faces,
+                report.missing_photos
+            );
+            Ok(())
+        }
+        CliAction::ExportAlbum { config_path, album_id, dest_dir, rename } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            let copied = db.export_album_to_dir(album_id, &dest_dir, rename.as_deref())?;
+            println!("Copied {} photos to {}", copied, dest_dir.display());
+            Ok(())
+        }
+        CliAction::Centralise { config_path, source, library, dry_run } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+            let Some(library) = library.or_else(|| config.library.path.clone()) else {
+                anyhow::bail!("No library given: pass --library DIR or set library.path in the config");
+            };
+
+            let db = db::Database::open(&config.database)?;
+            db.initialize()?;
+
+            let source = std::fs::canonicalize(&source).unwrap_or(source);
+            let files = scanner::discover_images(&source, &config.scanner.extensions())?;
+            let preview = centralise::preview_centralise(&db, &library, &files, &config.library)?;
+            let operation = config.library.operation;
+            let result = centralise::execute_centralise(&db, &preview, operation, dry_run)?;
+
+            let verb = match operation {
+                config::CentraliseOperation::Copy => "COPY",
+                config::CentraliseOperation::Move => "MOVE",
+            };
+            let prefix = if dry_run { "WOULD " } else { "" };
+            for op in &result.succeeded {
+                println!("{}{}: {} → {}", prefix, verb, op.source.display(), op.destination.display());
+            }
+            for (path, reason) in &result.skipped {
+                println!("{}SKIP: {} ({})", prefix, path.display(), reason);
+            }
+            for (path, error) in &result.failed {
+                eprintln!("FAILED: {} ({})", path.display(), error);
+            }
+
+            let done = match (dry_run, operation) {
+                (true, _) => format!("Dry run: would {}", verb.to_lowercase()),
+                (false, config::CentraliseOperation::Copy) => "Copied".to_string(),
+                (false, config::CentraliseOperation::Move) => "Moved".to_string(),
+            };
+            println!(
+                "{} {} files, {} skipped, {} failed",
+                done,
+                result.succeeded.len(),
+                result.skipped.len(),
+                result.failed.len()
+            );
+            if let Some(conflicts) = result.conflict_summary() {
+                println!("{}", conflicts);
+            }
+            Ok(())
+        }
+        CliAction::Batch { config_path, action, json } => {
+            let config = match config_path {
+                Some(path) => Config::load_from(&path)?,
+                None => Config::load()?,
+            };
+
+            batch::run(config, action, json)
+        }
         #[cfg(feature = "postgres")]
         CliAction::MigrateToPostgres { config_path, postgres_url } => {
             let config = match config_path {
@@ -170,5 +671,25 @@ async fn main() -> Result<()> {
             db::migrate::migrate_sqlite_to_postgres(sqlite_path, &postgres_url)?;
             Ok(())
         }
+        #[cfg(feature = "postgres")]
+        CliAction::MigrateFromPostgres { postgres_url, output } => {
+            eprintln!("Migrating from PostgreSQL to SQLite ({})...", output.display());
+            db::migrate::migrate_postgres_to_sqlite(&postgres_url, &output)?;
+            eprintln!("Set database.sqlite_path = \"{}\" to use it", output.display());
+            Ok(())
+        }
+        #[cfg(unix)]
+        CliAction::DaemonStatus => {
+            let response = clepho::ipc::send_request(&clepho::ipc::Request::Status)?;
+            if let clepho::ipc::Response::Error { error } = response {
+                anyhow::bail!("Daemon error: {}", error);
+            }
+            println!("{}", serde_json::to_string_pretty(&response)?);
+            Ok(())
+        }
+        CliAction::DetectProtocol => {
+            println!("{}", ui::protocol::config_name(ui::protocol::detect_best_protocol()));
+            Ok(())
+        }
     }
 }
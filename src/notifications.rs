@@ -0,0 +1,85 @@
+//! Desktop notifications for finished background tasks.
+//!
+//! Notifications go through the platform's command line tool, so nothing
+//! extra has to be linked: `notify-send` on Linux, `osascript` on macOS
+//! and PowerShell on Windows.
+
+use anyhow::{Context, Result};
+use std::process::{Command, Stdio};
+
+/// Show a desktop notification. Returns once the notifier has started;
+/// its exit status is not waited for.
+pub fn send(title: &str, body: &str) -> Result<()> {
+    let mut command = notifier_command(title, body);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+
+    // Reap the process in the background so it does not linger as a zombie
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn notifier_command(title: &str, body: &str) -> Command {
+    let script = format!(
+        "display notification {} with title {}",
+        applescript_string(body),
+        applescript_string(title)
+    );
+    let mut command = Command::new("osascript");
+    command.args(["-e", &script]);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn notifier_command(title: &str, body: &str) -> Command {
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $n = New-Object System.Windows.Forms.NotifyIcon; \
+         $n.Icon = [System.Drawing.SystemIcons]::Information; \
+         $n.Visible = $true; \
+         $n.ShowBalloonTip(5000, {}, {}, 'Info'); \
+         Start-Sleep -Seconds 5; $n.Dispose()",
+        powershell_string(title),
+        powershell_string(body)
+    );
+    let mut command = Command::new("powershell");
+    command.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn notifier_command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name=clepho", title, body]);
+    command
+}
+
+/// Quote a string for an AppleScript literal
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quote a string for a single-quoted PowerShell literal
+#[cfg_attr(not(any(target_os = "windows", test)), allow(dead_code))]
+fn powershell_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quotes_script_strings() {
+        assert_eq!(applescript_string(r#"Scan "2024" \ done"#), r#""Scan \"2024\" \\ done""#);
+        assert_eq!(powershell_string("Bob's photos"), "'Bob''s photos'");
+    }
+}
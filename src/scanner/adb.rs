@@ -0,0 +1,270 @@
+//! Importing photos from an Android device over ADB.
+//!
+//! Everything goes through the `adb` executable, which must be in `PATH`:
+//! `adb devices` lists phones, `adb shell find` lists the camera folders,
+//! `adb exec-out cat` reads a file for previewing and `adb pull` copies it.
+//! Pulled files are staged locally, scanned, and then centralised into the
+//! library like any other photo.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+use crate::config::{CentraliseOperation, Config};
+use crate::db::Database;
+use crate::tasks::{TaskProgress, TaskUpdate};
+
+use super::Scanner;
+
+/// ADB executable.
+const ADB: &str = "adb";
+
+/// Where Android keeps camera photos.
+pub const DCIM_ROOT: &str = "/sdcard/DCIM";
+
+/// A device listed by `adb devices`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdbDevice {
+    pub serial: String,
+    /// "device" when usable; "unauthorized" until USB debugging is allowed
+    pub state: String,
+}
+
+impl AdbDevice {
+    pub fn is_ready(&self) -> bool {
+        self.state == "device"
+    }
+}
+
+/// A file on the device, by its absolute path there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdbFile {
+    pub path: String,
+}
+
+impl AdbFile {
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// Folder below DCIM, e.g. "Camera"
+    pub fn folder(&self) -> &str {
+        let relative = self.path.strip_prefix(DCIM_ROOT).unwrap_or(&self.path).trim_start_matches('/');
+        relative.rsplit_once('/').map(|(folder, _)| folder).unwrap_or("")
+    }
+
+    pub fn has_extension(&self, extensions: &[String]) -> bool {
+        self.name()
+            .rsplit_once('.')
+            .map(|(_, ext)| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+            .unwrap_or(false)
+    }
+}
+
+/// Thin wrapper over the `adb` command line.
+pub struct AdbScanner;
+
+impl AdbScanner {
+    /// Devices currently connected, authorised or not.
+    pub fn list_devices() -> Result<Vec<AdbDevice>> {
+        let output = Command::new(ADB)
+            .arg("devices")
+            .output()
+            .context("Failed to run adb; is it installed and in PATH?")?;
+        if !output.status.success() {
+            anyhow::bail!("adb devices failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(parse_devices(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Every file under DCIM, sorted by path. Without a serial adb picks the
+    /// only connected device.
+    pub fn list_dcim_files(serial: Option<&str>) -> Result<Vec<AdbFile>> {
+        let output = adb(serial)
+            .args(["shell", "find", DCIM_ROOT, "-type", "f"])
+            .output()
+            .context("Failed to run adb; is it installed and in PATH?")?;
+        // find exits non-zero on unreadable folders but still lists the rest
+        if !output.status.success() && output.stdout.is_empty() {
+            anyhow::bail!("Listing {} failed: {}", DCIM_ROOT, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(parse_find_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Read a file's bytes, for previewing without pulling it.
+    pub fn read_file(serial: Option<&str>, remote: &str) -> Result<Vec<u8>> {
+        // exec-out hands the command to the device shell, so the path is quoted
+        let output = adb(serial)
+            .args(["exec-out", &format!("cat {}", shell_quote(remote))])
+            .output()
+            .context("Failed to run adb; is it installed and in PATH?")?;
+        if !output.status.success() {
+            anyhow::bail!("Reading {} failed: {}", remote, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(output.stdout)
+    }
+
+    /// Copy a file from the device to `local`.
+    pub fn pull(serial: Option<&str>, remote: &str, local: &Path) -> Result<()> {
+        if let Some(parent) = local.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let output = adb(serial)
+            .arg("pull")
+            .arg(remote)
+            .arg(local)
+            .output()
+            .context("Failed to run adb; is it installed and in PATH?")?;
+        if !output.status.success() {
+            anyhow::bail!("Pulling {} failed: {}", remote, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(())
+    }
+}
+
+fn adb(serial: Option<&str>) -> Command {
+    let mut command = Command::new(ADB);
+    if let Some(serial) = serial {
+        command.args(["-s", serial]);
+    }
+    command
+}
+
+/// Quote `s` as a single POSIX shell word.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Parse `adb devices` output: a header line, then `serial<TAB>state` lines.
+fn parse_devices(output: &str) -> Vec<AdbDevice> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("List of devices"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let serial = parts.next()?;
+            let state = parts.next()?;
+            Some(AdbDevice { serial: serial.to_string(), state: state.to_string() })
+        })
+        .collect()
+}
+
+/// Parse `find` output, one path per line. Hidden files and folders such as
+/// `.thumbnails` are left out.
+fn parse_find_output(output: &str) -> Vec<AdbFile> {
+    let mut files: Vec<AdbFile> = output
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| line.starts_with('/'))
+        .filter(|line| !line.split('/').any(|part| part.starts_with('.')))
+        .map(|line| AdbFile { path: line.to_string() })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+/// Local folder pulled files are staged in before centralising.
+pub fn staging_dir(serial: Option<&str>) -> PathBuf {
+    Config::state_dir().join("adb_import").join(serial.unwrap_or("default"))
+}
+
+/// Pull `files` into the staging folder, scan them and move them into the
+/// library. Runs as a background task reporting on `tx`.
+pub fn import_to_library(
+    config: &Config,
+    db: &Database,
+    library: &Path,
+    serial: Option<&str>,
+    files: &[AdbFile],
+    tx: mpsc::Sender<TaskUpdate>,
+    cancel_flag: Arc<AtomicBool>,
+) {
+    let total = files.len();
+    let _ = tx.send(TaskUpdate::Started { total });
+
+    let staging = staging_dir(serial);
+    let mut pulled = Vec::new();
+    let mut failed = 0;
+    for (i, file) in files.iter().enumerate() {
+        if cancel_flag.load(Ordering::SeqCst) {
+            let _ = tx.send(TaskUpdate::Cancelled);
+            return;
+        }
+        let _ = tx.send(TaskUpdate::Progress(
+            TaskProgress::new(i, total).with_item(file.name()).with_message("Pulling from device"),
+        ));
+        let local = staging.join(file.folder()).join(file.name());
+        match AdbScanner::pull(serial, &file.path, &local) {
+            Ok(()) => pulled.push(local),
+            Err(e) => {
+                tracing::warn!(error = %e, path = %file.path, "Failed to pull file");
+                failed += 1;
+            }
+        }
+    }
+
+    let _ = tx.send(TaskUpdate::Progress(
+        TaskProgress::new(total, total).with_message("Scanning imported photos"),
+    ));
+    // The scan reports on its own channel; this task's progress stays put
+    let (scan_tx, _scan_rx) = mpsc::channel();
+    Scanner::new(config.clone()).scan_directory_cancellable(&staging, db, scan_tx, cancel_flag.clone());
+    if cancel_flag.load(Ordering::SeqCst) {
+        let _ = tx.send(TaskUpdate::Cancelled);
+        return;
+    }
+
+    let result = crate::centralise::preview_centralise(db, library, &pulled, &config.library)
+        .and_then(|preview| crate::centralise::execute_centralise(db, &preview, CentraliseOperation::Move, false));
+    match result {
+        Ok(result) => {
+            let mut message = format!("Imported {} photos into {}", result.succeeded.len(), library.display());
+            let not_imported = failed + result.failed.len() + result.skipped.len();
+            if not_imported > 0 {
+                message.push_str(&format!(", {} not imported", not_imported));
+            }
+            let _ = tx.send(TaskUpdate::Completed { message });
+        }
+        Err(e) => {
+            let _ = tx.send(TaskUpdate::Failed { error: e.to_string() });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_devices() {
+        let output = "* daemon started successfully\nList of devices attached\nR58M12ABC\tdevice\nemulator-5554\tunauthorized\n\n";
+        let devices = parse_devices(output);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].serial, "R58M12ABC");
+        assert!(devices[0].is_ready());
+        assert!(!devices[1].is_ready());
+        assert!(parse_devices("List of devices attached\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/sdcard/DCIM/IMG 1.jpg"), "'/sdcard/DCIM/IMG 1.jpg'");
+        assert_eq!(shell_quote("it's; rm -rf $HOME"), "'it'\\''s; rm -rf $HOME'");
+    }
+
+    #[test]
+    fn test_parse_find_output() {
+        let output = "/sdcard/DCIM/Camera/IMG_2.jpg\r\n/sdcard/DCIM/.thumbnails/1.jpg\r\n/sdcard/DCIM/Camera/IMG_1.jpg\r\nfind: /sdcard/DCIM/x: Permission denied\r\n/sdcard/DCIM/Screenshots/shot.png\r\n";
+        let files = parse_find_output(output);
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].path, "/sdcard/DCIM/Camera/IMG_1.jpg");
+        assert_eq!(files[0].folder(), "Camera");
+        assert_eq!(files[0].name(), "IMG_1.jpg");
+        assert_eq!(files[2].folder(), "Screenshots");
+        assert!(files[0].has_extension(&["jpg".to_string()]));
+        assert!(!files[2].has_extension(&["jpg".to_string()]));
+    }
+}
@@ -1,14 +1,16 @@
 //! Change detection for files in a directory.
 //!
 //! Detects new and modified files by comparing filesystem state against
-//! the database records.
+//! the database records, and shows what a rescan of a modified file would
+//! change.
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::db::Database;
+use super::metadata::{self, ImageMetadata};
+use crate::db::{Database, PhotoMetadata};
 
 /// Tolerance in seconds for timestamp comparison.
 /// This accounts for filesystem timestamp granularity (some filesystems
@@ -51,7 +53,10 @@ pub fn detect_changes(
     let db_records = db.get_photos_mtime_in_dir(&dir_str)?;
 
     // Build a map of path -> mtime from database
-    let db_map: HashMap<String, Option<String>> = db_records.into_iter().collect();
+    let db_map: HashMap<String, Option<String>> = db_records
+        .into_iter()
+        .map(|(path, mtime, _)| (path, mtime))
+        .collect();
 
     // Read directory entries
     let entries = match std::fs::read_dir(directory) {
@@ -118,6 +123,70 @@ pub fn detect_changes(
     Ok(result)
 }
 
+/// A field whose stored value differs from the file's; None is a value
+/// that is missing on that side
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub stored: Option<String>,
+    pub current: Option<String>,
+}
+
+/// The fields a rescan of `path` would change, from its stored record and
+/// the metadata extracted from the file now
+pub fn diff_metadata(path: &Path, stored: &PhotoMetadata) -> Result<Vec<FieldChange>> {
+    let file_metadata = std::fs::metadata(path)?;
+    let current = metadata::extract_metadata(&path.to_path_buf()).unwrap_or_default();
+    Ok(compare_metadata(
+        stored,
+        file_metadata.len(),
+        super::modified_timestamp(&file_metadata),
+        &current,
+    ))
+}
+
+fn compare_metadata(
+    stored: &PhotoMetadata,
+    size_bytes: u64,
+    modified_at: Option<String>,
+    current: &ImageMetadata,
+) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut field = |field: &'static str, stored: Option<String>, current: Option<String>| {
+        if stored != current {
+            changes.push(FieldChange { field, stored, current });
+        }
+    };
+    let num = |v: Option<f64>| v.map(|v| v.to_string());
+    let coord = |v: Option<f64>| v.map(|v| format!("{:.6}", v));
+
+    field("size_bytes", Some(stored.size_bytes.to_string()), Some(size_bytes.to_string()));
+    field("modified_at", stored.modified_at.clone(), modified_at);
+    field("width", stored.width.map(|v| v.to_string()), current.width.map(|v| v.to_string()));
+    field("height", stored.height.map(|v| v.to_string()), current.height.map(|v| v.to_string()));
+    field("format", stored.format.clone(), current.format.clone());
+    field("camera_make", stored.camera_make.clone(), current.camera_make.clone());
+    field("camera_model", stored.camera_model.clone(), current.camera_model.clone());
+    field("lens", stored.lens.clone(), current.lens.clone());
+    field("focal_length", num(stored.focal_length), num(current.focal_length));
+    field("aperture", num(stored.aperture), num(current.aperture));
+    field("shutter_speed", stored.shutter_speed.clone(), current.shutter_speed.clone());
+    field("iso", stored.iso.map(|v| v.to_string()), current.iso.map(|v| v.to_string()));
+    field("taken_at", stored.taken_at.clone(), current.taken_at.clone());
+    // A rescan keeps coordinates placed by hand, so they are not a change
+    if stored.gps_source.as_deref() != Some("manual") {
+        field("gps_latitude", coord(stored.gps_latitude), coord(current.gps_latitude));
+        field("gps_longitude", coord(stored.gps_longitude), coord(current.gps_longitude));
+    }
+    // Stored as 1 when the file had none
+    field(
+        "exif_orientation",
+        Some(stored.exif_orientation.unwrap_or(1).to_string()),
+        Some(current.orientation.unwrap_or(1).to_string()),
+    );
+    changes
+}
+
 /// Parse a database timestamp string into a DateTime.
 /// Handles the ISO 8601 format used by the scanner: "YYYY-MM-DDTHH:MM:SS"
 fn parse_db_timestamp(timestamp: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
@@ -141,6 +210,42 @@ mod tests {
         assert_eq!(result.total_count(), 3);
     }
 
+    #[test]
+    fn test_compare_metadata() {
+        let stored = PhotoMetadata {
+            size_bytes: 4201344,
+            modified_at: Some("2024-05-01T10:30:00".to_string()),
+            width: Some(4000),
+            height: Some(3000),
+            camera_model: Some("ILCE-7M4".to_string()),
+            aperture: Some(2.8),
+            gps_latitude: Some(51.5),
+            gps_longitude: Some(-0.12),
+            gps_source: Some("manual".to_string()),
+            exif_orientation: Some(1),
+            ..Default::default()
+        };
+        let current = ImageMetadata {
+            width: Some(4000),
+            height: Some(3000),
+            camera_model: Some("ILCE-7M4".to_string()),
+            aperture: Some(2.8),
+            orientation: Some(6),
+            ..Default::default()
+        };
+
+        let changes = compare_metadata(&stored, 4211200, stored.modified_at.clone(), &current);
+        let change = |field: &'static str, stored: &str, current: &str| FieldChange {
+            field,
+            stored: Some(stored.to_string()),
+            current: Some(current.to_string()),
+        };
+        assert_eq!(
+            changes,
+            vec![change("size_bytes", "4201344", "4211200"), change("exif_orientation", "1", "6")]
+        );
+    }
+
     #[test]
     fn test_empty_result() {
         let result = ChangeDetectionResult::default();
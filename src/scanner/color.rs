@@ -0,0 +1,199 @@
+//! Dominant colors of a photo, for finding "warm sunset" or "cool ocean"
+//! shots by color.
+//!
+//! Pixels of a small copy of the image are grouped with k-means in CIELAB
+//! space, where Euclidean distance (ΔE 1976) roughly follows how different
+//! two colors look. The palette is the mean color of each group, largest
+//! group first.
+//!
+//! The clustering is written out here rather than using `kmeans_colors`:
+//! it seeds deterministically so rescans give the same palette, and needs
+//! nothing beyond the Lab conversion the search already uses.
+
+use anyhow::Result;
+use image::DynamicImage;
+use std::path::Path;
+
+use super::{heif, pdf};
+
+/// Colors stored per photo
+pub const PALETTE_SIZE: usize = 5;
+
+/// Longest side images are scaled to before clustering
+const SAMPLE_SIZE: u32 = 64;
+
+/// k-means rounds before giving up on convergence
+const MAX_ITERATIONS: usize = 20;
+
+/// Largest ΔE between a palette color and the picked color that still
+/// counts as a match
+pub const MATCH_DISTANCE: f64 = 25.0;
+
+/// Colors offered by the search dialog's color picker
+pub const BASIC_COLORS: [(&str, [u8; 3]); 12] = [
+    ("red", [200, 30, 30]),
+    ("orange", [240, 130, 30]),
+    ("yellow", [240, 210, 40]),
+    ("green", [50, 150, 50]),
+    ("teal", [30, 140, 140]),
+    ("blue", [40, 80, 200]),
+    ("purple", [120, 50, 160]),
+    ("pink", [240, 130, 180]),
+    ("brown", [120, 80, 40]),
+    ("white", [240, 240, 240]),
+    ("gray", [128, 128, 128]),
+    ("black", [20, 20, 20]),
+];
+
+pub fn extract_palette(path: &Path, n: usize) -> Result<Vec<[u8; 3]>> {
+    let image = if pdf::is_pdf(path) {
+        pdf::render_first_page(path, SAMPLE_SIZE)?
+    } else {
+        match image::open(path) {
+            Ok(img) => img.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE),
+            Err(e) => heif::embedded_thumbnail(path).ok_or(e)?.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE),
+        }
+    };
+    Ok(palette_of(&image, n))
+}
+
+pub fn palette_of(image: &DynamicImage, n: usize) -> Vec<[u8; 3]> {
+    let pixels: Vec<[u8; 3]> = image.to_rgb8().pixels().map(|p| p.0).collect();
+    if pixels.is_empty() || n == 0 {
+        return Vec::new();
+    }
+    let labs: Vec<[f64; 3]> = pixels.iter().map(|&p| rgb_to_lab(p)).collect();
+
+    let mut centers = initial_centers(&labs, n);
+    let mut assignment = vec![0; labs.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (i, lab) in labs.iter().enumerate() {
+            let nearest = nearest_center(&centers, lab);
+            if nearest != assignment[i] {
+                assignment[i] = nearest;
+                changed = true;
+            }
+        }
+
+        let mut sums = vec![([0.0; 3], 0usize); centers.len()];
+        for (lab, &cluster) in labs.iter().zip(&assignment) {
+            let (sum, count) = &mut sums[cluster];
+            (0..3).for_each(|c| sum[c] += lab[c]);
+            *count += 1;
+        }
+        for (center, (sum, count)) in centers.iter_mut().zip(sums) {
+            if count > 0 {
+                *center = sum.map(|s| s / count as f64);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Report each group's mean sRGB color rather than converting the
+    // centre back out of Lab
+    let mut groups = vec![([0u64; 3], 0u64); centers.len()];
+    for (pixel, &cluster) in pixels.iter().zip(&assignment) {
+        let (sum, count) = &mut groups[cluster];
+        (0..3).for_each(|c| sum[c] += u64::from(pixel[c]));
+        *count += 1;
+    }
+    groups.retain(|(_, count)| *count > 0);
+    groups.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    groups
+        .into_iter()
+        .map(|(sum, count)| sum.map(|s| (s / count) as u8))
+        .collect()
+}
+
+/// Farthest-first seeding: start from the pixel closest to the mean, then
+/// repeatedly take the pixel farthest from every chosen centre. Unlike
+/// random seeding the palette is the same on every scan.
+fn initial_centers(labs: &[[f64; 3]], n: usize) -> Vec<[f64; 3]> {
+    let mut mean = [0.0; 3];
+    for lab in labs {
+        (0..3).for_each(|c| mean[c] += lab[c] / labs.len() as f64);
+    }
+    let first = labs
+        .iter()
+        .min_by(|a, b| delta_e(a, &mean).total_cmp(&delta_e(b, &mean)))
+        .copied()
+        .unwrap_or(mean);
+
+    let mut centers = vec![first];
+    while centers.len() < n {
+        let farthest = labs
+            .iter()
+            .map(|lab| (lab, centers.iter().map(|c| delta_e(lab, c)).fold(f64::MAX, f64::min)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match farthest {
+            // Fewer distinct colors than requested
+            Some((_, distance)) if distance < 1.0 => break,
+            Some((lab, _)) => centers.push(*lab),
+            None => break,
+        }
+    }
+    centers
+}
+
+fn nearest_center(centers: &[[f64; 3]], lab: &[f64; 3]) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|a, b| delta_e(a.1, lab).total_cmp(&delta_e(b.1, lab)))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// sRGB (D65) to CIELAB
+pub fn rgb_to_lab(rgb: [u8; 3]) -> [f64; 3] {
+    let linear = rgb.map(|c| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    });
+    let [r, g, b] = linear;
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+
+    let f = |t: f64| if t > 0.008856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// ΔE 1976: Euclidean distance between two Lab colors
+pub fn delta_e(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// How far the closest palette color is from `color`, in ΔE
+pub fn palette_distance(palette: &[[u8; 3]], color: [u8; 3]) -> f64 {
+    let target = rgb_to_lab(color);
+    palette
+        .iter()
+        .map(|&c| delta_e(&rgb_to_lab(c), &target))
+        .fold(f64::MAX, f64::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_palette_of() {
+        // Three quarters orange sky over a blue sea
+        let image = RgbImage::from_fn(40, 40, |_, y| if y < 30 { Rgb([240, 130, 30]) } else { Rgb([40, 80, 200]) });
+        let palette = palette_of(&DynamicImage::ImageRgb8(image), PALETTE_SIZE);
+        assert_eq!(palette, vec![[240, 130, 30], [40, 80, 200]]);
+
+        assert!(palette_distance(&palette, [250, 140, 40]) < MATCH_DISTANCE);
+        assert!(palette_distance(&palette, [50, 150, 50]) > MATCH_DISTANCE);
+        assert_eq!(palette_distance(&[], [0, 0, 0]), f64::MAX);
+
+        let white = rgb_to_lab([255, 255, 255]);
+        assert!((white[0] - 100.0).abs() < 0.01 && white[1].abs() < 0.05 && white[2].abs() < 0.05);
+    }
+}
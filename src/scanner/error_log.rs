@@ -0,0 +1,160 @@
+//! Per-run log of files that could not be scanned.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Scan error logs kept in the state directory; older ones are removed
+pub const MAX_SCAN_ERROR_LOGS: usize = 20;
+
+/// Names tried for one timestamp before giving up
+const MAX_NAME_ATTEMPTS: u32 = 100;
+
+/// A file that failed to scan or to be saved to the database
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScanErrorLog {
+    pub path: PathBuf,
+    pub error: String,
+    pub timestamp: String,
+}
+
+impl ScanErrorLog {
+    pub fn new(path: &Path, error: impl ToString) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            error: error.to_string(),
+            timestamp: chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        }
+    }
+}
+
+/// Write the errors of one scan to `log_dir/scan_errors_<timestamp>.txt`,
+/// one tab-separated `timestamp, path, error` line per file. A scan
+/// finishing in the same second gets a `_2`, `_3`, ... suffix instead of
+/// overwriting the earlier log. Only the newest `MAX_SCAN_ERROR_LOGS` logs
+/// are kept.
+pub fn write_scan_error_log(log_dir: &Path, directory: &Path, errors: &[ScanErrorLog]) -> Result<PathBuf> {
+    std::fs::create_dir_all(log_dir)
+        .with_context(|| format!("Failed to create {}", log_dir.display()))?;
+
+    let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let (log_path, mut file) = create_log_file(log_dir, &stamp)?;
+
+    writeln!(file, "# Scan of {}: {} errors", directory.display(), errors.len())?;
+    for entry in errors {
+        // Keep one error per line so the log stays greppable
+        let error = entry.error.replace(['\n', '\r'], " ");
+        writeln!(file, "{}\t{}\t{}", entry.timestamp, entry.path.display(), error)?;
+    }
+
+    if let Err(e) = prune_scan_error_logs(log_dir, MAX_SCAN_ERROR_LOGS) {
+        tracing::warn!(error = %e, "Failed to remove old scan error logs");
+    }
+    Ok(log_path)
+}
+
+/// Create the next unused log for `stamp`. Numbering continues after the
+/// highest existing suffix, so a name freed by pruning is never reused for
+/// a newer log.
+fn create_log_file(log_dir: &Path, stamp: &str) -> Result<(PathBuf, File)> {
+    let first = std::fs::read_dir(log_dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| log_order(&e.file_name().to_string_lossy()))
+        .filter(|(s, _)| s == stamp)
+        .map(|(_, n)| n + 1)
+        .max()
+        .unwrap_or(1);
+    for n in first..first + MAX_NAME_ATTEMPTS {
+        let name = if n == 1 {
+            format!("scan_errors_{}.txt", stamp)
+        } else {
+            format!("scan_errors_{}_{}.txt", stamp, n)
+        };
+        let log_path = log_dir.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&log_path) {
+            Ok(file) => return Ok((log_path, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to create {}", log_path.display())),
+        }
+    }
+    anyhow::bail!("Too many scan error logs for {} in {}", stamp, log_dir.display())
+}
+
+/// Timestamp and collision suffix of a scan error log name, for ordering
+fn log_order(name: &str) -> Option<(String, u32)> {
+    let stem = name.strip_prefix("scan_errors_")?.strip_suffix(".txt")?;
+    // `<date>_<time>` or `<date>_<time>_<n>`
+    match stem.splitn(3, '_').collect::<Vec<_>>()[..] {
+        [date, time] => Some((format!("{}_{}", date, time), 1)),
+        [date, time, n] => Some((format!("{}_{}", date, time), n.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// Remove all but the newest `keep` scan error logs in `log_dir`
+fn prune_scan_error_logs(log_dir: &Path, keep: usize) -> Result<()> {
+    let mut logs: Vec<((String, u32), PathBuf)> = std::fs::read_dir(log_dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| Some((log_order(&e.file_name().to_string_lossy())?, e.path())))
+        .collect();
+    if logs.len() <= keep {
+        return Ok(());
+    }
+    logs.sort();
+    let excess = logs.len() - keep;
+    for (_, path) in logs.into_iter().take(excess) {
+        std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_one_line_per_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let errors = vec![
+            ScanErrorLog::new(Path::new("/photos/a.jpg"), "Permission denied"),
+            ScanErrorLog::new(Path::new("/photos/b.jpg"), "truncated\nfile"),
+        ];
+
+        let log_path = write_scan_error_log(dir.path(), Path::new("/photos"), &errors).unwrap();
+        let name = log_path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("scan_errors_") && name.ends_with(".txt"));
+
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines[0], "# Scan of /photos: 2 errors");
+        assert!(lines[1].ends_with("\t/photos/a.jpg\tPermission denied"));
+        assert!(lines[2].ends_with("\t/photos/b.jpg\ttruncated file"));
+    }
+
+    #[test]
+    fn test_logs_are_never_overwritten_and_old_ones_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let errors = vec![ScanErrorLog::new(Path::new("/photos/a.jpg"), "Permission denied")];
+        std::fs::write(dir.path().join("notes.txt"), "kept").unwrap();
+
+        // Most of these land in the same second and need a suffix
+        let paths: Vec<PathBuf> = (0..MAX_SCAN_ERROR_LOGS + 3)
+            .map(|_| write_scan_error_log(dir.path(), Path::new("/photos"), &errors).unwrap())
+            .collect();
+
+        let mut remaining: Vec<PathBuf> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.file_name().unwrap() != "notes.txt")
+            .collect();
+        remaining.sort_by_key(|p| log_order(&p.file_name().unwrap().to_string_lossy()));
+        assert_eq!(remaining, paths[3..]);
+        assert!(dir.path().join("notes.txt").exists());
+
+        assert_eq!(log_order("scan_errors_20250101_120000.txt"), Some(("20250101_120000".to_string(), 1)));
+        assert_eq!(log_order("scan_errors_20250101_120000_10.txt"), Some(("20250101_120000".to_string(), 10)));
+        assert_eq!(log_order("scan_errors_notes.txt"), None);
+    }
+}
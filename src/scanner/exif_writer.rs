@@ -0,0 +1,780 @@
+//! Write descriptions, tags and ratings from the database back into image
+//! files, so other tools see corrections made in clepho.
+//!
+//! JPEG and TIFF files get the description as the IPTC caption
+//! (`Iptc.Application2.Caption`) and as `dc:description`, tags as IPTC
+//! keywords and `dc:subject`, and the rating as `xmp:Rating`. An existing
+//! XMP packet is updated in place so other properties (camera data, motion
+//! photo markers) survive. The file's modification time is restored
+//! afterwards so the scanner does not treat it as changed.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{self, File, FileTimes};
+use std::path::Path;
+
+use crate::export::xml_escape;
+
+/// Signature that starts an XMP APP1 segment
+const XMP_SIGNATURE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// Signature that starts a Photoshop APP13 segment holding IPTC data
+const PHOTOSHOP_SIGNATURE: &[u8] = b"Photoshop 3.0\0";
+
+/// Photoshop image resource holding IPTC-IIM data
+const IPTC_RESOURCE: u16 = 0x0404;
+
+/// Photoshop image resource with an MD5 digest of the IPTC data, stale once
+/// the IPTC block changes
+const IPTC_DIGEST_RESOURCE: u16 = 0x0425;
+
+/// TIFF tags for embedded XMP and IPTC
+const TIFF_XMP_TAG: u16 = 700;
+const TIFF_IPTC_TAG: u16 = 33723;
+
+/// Longest caption the IPTC-IIM spec allows, in bytes
+const MAX_CAPTION_BYTES: usize = 2000;
+
+/// Longest keyword the IPTC-IIM spec allows, in bytes
+const MAX_KEYWORD_BYTES: usize = 64;
+
+/// Metadata written into a file. Fields that are None or empty leave the
+/// file's existing values alone.
+#[derive(Debug, Clone, Default)]
+pub struct EmbeddedMetadata {
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub rating: Option<u8>,
+}
+
+impl EmbeddedMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.tags.is_empty() && self.rating.is_none()
+    }
+}
+
+/// Whether metadata can be written into this file
+pub fn is_supported(path: &Path) -> bool {
+    file_kind(path).is_some()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Jpeg,
+    Tiff,
+}
+
+fn file_kind(path: &Path) -> Option<FileKind> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => Some(FileKind::Jpeg),
+        "tif" | "tiff" => Some(FileKind::Tiff),
+        _ => None,
+    }
+}
+
+/// Write the metadata into the file, keeping its modification time.
+/// Returns false, after logging a warning, for formats that cannot carry it
+/// (HEIC, PNG and others).
+pub fn write_metadata(path: &Path, metadata: &EmbeddedMetadata) -> Result<bool> {
+    let Some(kind) = file_kind(path) else {
+        tracing::warn!(path = %path.display(), "Skipping metadata write: unsupported format");
+        return Ok(false);
+    };
+
+    let original = fs::metadata(path)?;
+    let data = fs::read(path)?;
+    let updated = match kind {
+        FileKind::Jpeg => write_jpeg(&data, metadata)?,
+        FileKind::Tiff => write_tiff(&data, metadata)?,
+    };
+
+    // Write next to the original and rename over it, so a failed write
+    // never leaves a truncated photo behind
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.clepho-tmp", file_name));
+    fs::write(&temp, &updated)?;
+    let finish = || -> Result<()> {
+        fs::set_permissions(&temp, original.permissions())?;
+        fs::rename(&temp, path)?;
+        let times = FileTimes::new()
+            .set_accessed(original.accessed()?)
+            .set_modified(original.modified()?);
+        File::options().write(true).open(path)?.set_times(times)?;
+        Ok(())
+    };
+    if let Err(e) = finish() {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(true)
+}
+
+// --- JPEG ---
+
+/// One marker segment before the image data: the marker byte and the
+/// segment's bytes including the 0xFF marker prefix and length
+struct Segment<'a> {
+    marker: u8,
+    bytes: &'a [u8],
+}
+
+impl Segment<'_> {
+    fn payload(&self) -> &[u8] {
+        &self.bytes[4..]
+    }
+}
+
+fn write_jpeg(data: &[u8], metadata: &EmbeddedMetadata) -> Result<Vec<u8>> {
+    let (segments, image_data) = split_jpeg(data)?;
+
+    let existing_xmp = segments
+        .iter()
+        .find(|s| s.marker == 0xE1 && s.payload().starts_with(XMP_SIGNATURE))
+        .map(|s| String::from_utf8_lossy(&s.payload()[XMP_SIGNATURE.len()..]).to_string());
+    let photoshop: Vec<u8> = segments
+        .iter()
+        .filter(|s| s.marker == 0xED && s.payload().starts_with(PHOTOSHOP_SIGNATURE))
+        .flat_map(|s| s.payload()[PHOTOSHOP_SIGNATURE.len()..].iter().copied())
+        .collect();
+
+    let xmp = update_xmp(existing_xmp.as_deref(), metadata);
+    let mut xmp_payload = XMP_SIGNATURE.to_vec();
+    xmp_payload.extend_from_slice(xmp.as_bytes());
+
+    let existing_iptc = photoshop_resource(&photoshop, IPTC_RESOURCE)?;
+    let iptc = update_iptc(existing_iptc.unwrap_or_default(), metadata)?;
+    let mut photoshop_payload = PHOTOSHOP_SIGNATURE.to_vec();
+    photoshop_payload.extend(replace_photoshop_resource(&photoshop, IPTC_RESOURCE, &iptc)?);
+
+    // New segments go after JFIF (APP0) and EXIF (APP1), where readers
+    // expect them
+    let kept: Vec<&Segment> = segments
+        .iter()
+        .filter(|s| !(s.marker == 0xE1 && s.payload().starts_with(XMP_SIGNATURE)))
+        .filter(|s| !(s.marker == 0xED && s.payload().starts_with(PHOTOSHOP_SIGNATURE)))
+        .collect();
+    let insert_at = kept.iter().take_while(|s| s.marker == 0xE0 || s.marker == 0xE1).count();
+
+    let mut out = Vec::with_capacity(data.len() + xmp_payload.len() + photoshop_payload.len());
+    out.extend_from_slice(&[0xFF, 0xD8]);
+    for (i, segment) in kept.iter().enumerate() {
+        if i == insert_at {
+            push_segment(&mut out, 0xE1, &xmp_payload)?;
+            push_segment(&mut out, 0xED, &photoshop_payload)?;
+        }
+        out.extend_from_slice(segment.bytes);
+    }
+    if insert_at == kept.len() {
+        push_segment(&mut out, 0xE1, &xmp_payload)?;
+        push_segment(&mut out, 0xED, &photoshop_payload)?;
+    }
+    out.extend_from_slice(image_data);
+    Ok(out)
+}
+
+/// The segments before the first scan, and everything from the scan on
+fn split_jpeg(data: &[u8]) -> Result<(Vec<Segment<'_>>, &[u8])> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        bail!("Not a JPEG file");
+    }
+    let mut segments = Vec::new();
+    let mut pos = 2;
+    loop {
+        if data.get(pos) != Some(&0xFF) {
+            bail!("Corrupt JPEG: expected a marker at byte {}", pos);
+        }
+        // Markers may be preceded by fill bytes
+        let mut marker_pos = pos + 1;
+        while data.get(marker_pos) == Some(&0xFF) {
+            marker_pos += 1;
+        }
+        let marker = *data.get(marker_pos).context("Truncated JPEG")?;
+        if marker == 0xDA || marker == 0xD9 {
+            return Ok((segments, &data[pos..]));
+        }
+        let len_bytes = data.get(marker_pos + 1..marker_pos + 3).context("Truncated JPEG")?;
+        let len = u16::from_be_bytes([len_bytes[0], len_bytes[1]]) as usize;
+        let end = marker_pos + 1 + len;
+        if len < 2 || end > data.len() {
+            bail!("Corrupt JPEG: segment overruns the file");
+        }
+        // Keep the segment without its fill bytes
+        segments.push(Segment { marker, bytes: &data[marker_pos - 1..end] });
+        pos = end;
+    }
+}
+
+fn push_segment(out: &mut Vec<u8>, marker: u8, payload: &[u8]) -> Result<()> {
+    let len = u16::try_from(payload.len() + 2)
+        .ok()
+        .context("Metadata too large for a JPEG segment")?;
+    out.extend_from_slice(&[0xFF, marker]);
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(payload);
+    Ok(())
+}
+
+// --- Photoshop image resources ---
+
+/// A `8BIM` resource block: id, the whole block and its data
+type Resource<'a> = (u16, &'a [u8], &'a [u8]);
+
+fn photoshop_resources(data: &[u8]) -> Result<Vec<Resource<'_>>> {
+    let mut resources = Vec::new();
+    let mut pos = 0;
+    while pos + 4 <= data.len() && &data[pos..pos + 4] == b"8BIM" {
+        let start = pos;
+        let id_bytes = data.get(pos + 4..pos + 6).context("Truncated Photoshop resource")?;
+        let id = u16::from_be_bytes([id_bytes[0], id_bytes[1]]);
+        // Pascal string name, padded to an even length
+        let name_len = *data.get(pos + 6).context("Truncated Photoshop resource")? as usize;
+        pos += 6 + (name_len + 1).div_ceil(2) * 2;
+        let size_bytes = data.get(pos..pos + 4).context("Truncated Photoshop resource")?;
+        let size = u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]]) as usize;
+        pos += 4;
+        let body = data.get(pos..pos + size).context("Truncated Photoshop resource")?;
+        pos = (pos + size).div_ceil(2) * 2;
+        resources.push((id, &data[start..pos.min(data.len())], body));
+    }
+    Ok(resources)
+}
+
+fn photoshop_resource(data: &[u8], id: u16) -> Result<Option<&[u8]>> {
+    Ok(photoshop_resources(data)?.into_iter().find(|r| r.0 == id).map(|r| r.2))
+}
+
+/// The resource blocks with `id` replaced by `body` and the IPTC digest
+/// dropped
+fn replace_photoshop_resource(data: &[u8], id: u16, body: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for (resource_id, block, _) in photoshop_resources(data)? {
+        if resource_id != id && resource_id != IPTC_DIGEST_RESOURCE {
+            out.extend_from_slice(block);
+        }
+    }
+    out.extend_from_slice(b"8BIM");
+    out.extend_from_slice(&id.to_be_bytes());
+    out.extend_from_slice(&[0, 0]); // empty name, padded
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    out.extend_from_slice(body);
+    if body.len() % 2 == 1 {
+        out.push(0);
+    }
+    Ok(out)
+}
+
+// --- IPTC-IIM ---
+
+/// One IPTC dataset: record, dataset number and value
+type Dataset = (u8, u8, Vec<u8>);
+
+fn parse_iptc(data: &[u8]) -> Result<Vec<Dataset>> {
+    let mut datasets = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        if data[pos] != 0x1C {
+            // Padding after the last dataset
+            break;
+        }
+        let header = data.get(pos + 1..pos + 5).context("Truncated IPTC data")?;
+        let (record, dataset) = (header[0], header[1]);
+        let mut len = u16::from_be_bytes([header[2], header[3]]) as usize;
+        pos += 5;
+        // Extended datasets store the length in the following bytes
+        if len & 0x8000 != 0 {
+            let count = len & 0x7FFF;
+            let bytes = data.get(pos..pos + count).context("Truncated IPTC data")?;
+            len = bytes.iter().fold(0, |acc, &b| (acc << 8) | b as usize);
+            pos += count;
+        }
+        let value = data.get(pos..pos + len).context("Truncated IPTC data")?;
+        datasets.push((record, dataset, value.to_vec()));
+        pos += len;
+    }
+    Ok(datasets)
+}
+
+/// Character set of existing IPTC text, from its 1:90 dataset
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IptcCharset {
+    Utf8,
+    Latin1,
+    /// No 1:90: older writers used Latin-1 or, without saying, UTF-8
+    Undeclared,
+    Other,
+}
+
+fn iptc_charset(datasets: &[Dataset]) -> IptcCharset {
+    match datasets.iter().find(|d| d.0 == 1 && d.1 == 90).map(|d| d.2.as_slice()) {
+        None => IptcCharset::Undeclared,
+        Some(b"\x1B%G") => IptcCharset::Utf8,
+        // ISO 8859-1 designated as G1 or G2
+        Some(b"\x1B-A") | Some(b"\x1B.A") => IptcCharset::Latin1,
+        Some(_) => IptcCharset::Other,
+    }
+}
+
+/// Record 2 datasets holding binary values rather than text: the record
+/// version, rasterized caption and object preview
+const IPTC_BINARY_DATASETS: [u8; 5] = [0, 125, 200, 201, 202];
+
+/// `value` of a record 2 text dataset as UTF-8
+fn iptc_text_to_utf8(value: &[u8], charset: IptcCharset) -> Vec<u8> {
+    let latin1 = |v: &[u8]| v.iter().map(|&b| b as char).collect::<String>().into_bytes();
+    match charset {
+        IptcCharset::Latin1 => latin1(value),
+        IptcCharset::Undeclared if std::str::from_utf8(value).is_err() => latin1(value),
+        _ => value.to_vec(),
+    }
+}
+
+/// IPTC data with the caption and keywords replaced, declared as UTF-8.
+/// Kept text in Latin-1 is converted to match; IPTC in any other character
+/// set is returned unchanged, leaving the new values to the XMP packet.
+fn update_iptc(existing_bytes: &[u8], metadata: &EmbeddedMetadata) -> Result<Vec<u8>> {
+    let existing = parse_iptc(existing_bytes)?;
+    let charset = iptc_charset(&existing);
+    if charset == IptcCharset::Other {
+        tracing::warn!("Not updating IPTC data in an unsupported character set");
+        return Ok(existing_bytes.to_vec());
+    }
+    let existing: Vec<Dataset> = existing
+        .into_iter()
+        .map(|(record, dataset, value)| {
+            if record == 2 && !IPTC_BINARY_DATASETS.contains(&dataset) {
+                (record, dataset, iptc_text_to_utf8(&value, charset))
+            } else {
+                (record, dataset, value)
+            }
+        })
+        .collect();
+    let replace_caption = metadata.description.is_some();
+    let replace_keywords = !metadata.tags.is_empty();
+
+    let mut datasets: Vec<Dataset> = vec![(1, 90, b"\x1B%G".to_vec())];
+    datasets.extend(existing.iter().filter(|d| d.0 == 1 && d.1 != 90).cloned());
+    if !existing.iter().any(|d| d.0 == 2 && d.1 == 0) {
+        // Record version 4
+        datasets.push((2, 0, vec![0, 4]));
+    }
+    datasets.extend(
+        existing
+            .iter()
+            .filter(|d| d.0 == 2)
+            .filter(|d| !(replace_caption && d.1 == 120 || replace_keywords && d.1 == 25))
+            .cloned(),
+    );
+    if let Some(description) = &metadata.description {
+        datasets.push((2, 120, truncate_bytes(description, MAX_CAPTION_BYTES).as_bytes().to_vec()));
+    }
+    for tag in &metadata.tags {
+        datasets.push((2, 25, truncate_bytes(tag, MAX_KEYWORD_BYTES).as_bytes().to_vec()));
+    }
+    datasets.extend(existing.into_iter().filter(|d| d.0 > 2));
+
+    let mut out = Vec::new();
+    for (record, dataset, value) in datasets {
+        let len = u16::try_from(value.len()).ok().filter(|&l| l < 0x8000).context("IPTC value too long")?;
+        out.extend_from_slice(&[0x1C, record, dataset]);
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(&value);
+    }
+    Ok(out)
+}
+
+/// Longest prefix of `s` within `max` bytes, ending on a character boundary
+fn truncate_bytes(s: &str, max: usize) -> &str {
+    let mut end = s.len().min(max);
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+// --- XMP ---
+
+/// XMP properties managed here, as (opening tag, closing tag)
+const XMP_ELEMENTS: [(&str, &str); 3] = [
+    ("<dc:description", "</dc:description>"),
+    ("<dc:subject", "</dc:subject>"),
+    ("<xmp:Rating", "</xmp:Rating>"),
+];
+
+/// The XMP packet with our properties set: the existing packet updated in
+/// place when there is one, otherwise a new packet
+fn update_xmp(existing: Option<&str>, metadata: &EmbeddedMetadata) -> String {
+    existing
+        .and_then(|packet| merge_xmp(packet, metadata))
+        .unwrap_or_else(|| new_xmp(metadata))
+}
+
+fn xmp_properties(metadata: &EmbeddedMetadata) -> String {
+    let mut elements = String::new();
+    if let Some(ref description) = metadata.description {
+        elements.push_str(&format!(
+            "\n   <dc:description>\n    <rdf:Alt>\n     <rdf:li xml:lang=\"x-default\">{}</rdf:li>\n    </rdf:Alt>\n   </dc:description>",
+            xml_escape(description)
+        ));
+    }
+    if !metadata.tags.is_empty() {
+        let items: String = metadata
+            .tags
+            .iter()
+            .map(|t| format!("\n     <rdf:li>{}</rdf:li>", xml_escape(t)))
+            .collect();
+        elements.push_str(&format!("\n   <dc:subject>\n    <rdf:Bag>{items}\n    </rdf:Bag>\n   </dc:subject>"));
+    }
+    if let Some(rating) = metadata.rating {
+        elements.push_str(&format!("\n   <xmp:Rating>{}</xmp:Rating>", rating));
+    }
+    elements
+}
+
+fn new_xmp(metadata: &EmbeddedMetadata) -> String {
+    format!(
+        r#"<?xpacket begin="{bom}" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/" x:xmptk="clepho {version}">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/">{properties}
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#,
+        bom = '\u{feff}',
+        version = env!("CARGO_PKG_VERSION"),
+        properties = xmp_properties(metadata),
+    )
+}
+
+/// Replace our properties in an existing packet, leaving everything else.
+/// None when the packet has no `rdf:Description` to add them to.
+fn merge_xmp(packet: &str, metadata: &EmbeddedMetadata) -> Option<String> {
+    let mut packet = packet.to_string();
+    for (open, close) in XMP_ELEMENTS {
+        let replaced = match open {
+            "<dc:description" => metadata.description.is_some(),
+            "<dc:subject" => !metadata.tags.is_empty(),
+            _ => metadata.rating.is_some(),
+        };
+        if !replaced {
+            continue;
+        }
+        while let Some(start) = packet.find(open) {
+            let end = match packet[start..].find('>') {
+                // Empty element
+                Some(i) if packet[start..start + i].ends_with('/') => start + i + 1,
+                _ => start + packet[start..].find(close)? + close.len(),
+            };
+            // Along with the line break and indent before it, so rewriting
+            // the packet does not leave blank lines behind
+            let before = packet[..start].trim_end_matches([' ', '\t']);
+            let start = before.strip_suffix('\n').map_or(start, str::len);
+            packet.replace_range(start..end, "");
+        }
+    }
+    if metadata.rating.is_some() {
+        // Rating is often written as an attribute
+        while let Some(start) = packet.find(" xmp:Rating=").or_else(|| packet.find("\nxmp:Rating=")) {
+            let value_start = start + " xmp:Rating=".len();
+            let quote = packet[value_start..].chars().next()?;
+            let value_end = value_start + 1 + packet[value_start + 1..].find(quote)?;
+            packet.replace_range(start..value_end + 1, "");
+        }
+    }
+
+    let start = packet.find("<rdf:Description")?;
+    let tag_end = start + packet[start..].find('>')?;
+    let self_closing = packet[..tag_end].ends_with('/');
+    let attributes_end = if self_closing { tag_end - 1 } else { tag_end };
+
+    let mut namespaces = String::new();
+    let tag = &packet[start..attributes_end];
+    if !tag.contains("xmlns:dc=") {
+        namespaces.push_str("\n    xmlns:dc=\"http://purl.org/dc/elements/1.1/\"");
+    }
+    if !tag.contains("xmlns:xmp=") {
+        namespaces.push_str("\n    xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"");
+    }
+
+    let mut merged = String::with_capacity(packet.len() + 512);
+    merged.push_str(packet[..attributes_end].trim_end());
+    merged.push_str(&namespaces);
+    merged.push('>');
+    merged.push_str(&xmp_properties(metadata));
+    if self_closing {
+        merged.push_str("\n  </rdf:Description>");
+    }
+    merged.push_str(&packet[tag_end + 1..]);
+    Some(merged)
+}
+
+// --- TIFF ---
+
+/// Byte order of a TIFF file
+#[derive(Clone, Copy)]
+struct ByteOrder {
+    little_endian: bool,
+}
+
+impl ByteOrder {
+    fn u16(self, data: &[u8], pos: usize) -> Result<u16> {
+        let b = data.get(pos..pos + 2).context("Truncated TIFF")?;
+        Ok(if self.little_endian { u16::from_le_bytes([b[0], b[1]]) } else { u16::from_be_bytes([b[0], b[1]]) })
+    }
+
+    fn u32(self, data: &[u8], pos: usize) -> Result<u32> {
+        let b = data.get(pos..pos + 4).context("Truncated TIFF")?;
+        let b = [b[0], b[1], b[2], b[3]];
+        Ok(if self.little_endian { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    }
+
+    fn put_u16(self, out: &mut Vec<u8>, v: u16) {
+        out.extend_from_slice(&if self.little_endian { v.to_le_bytes() } else { v.to_be_bytes() });
+    }
+
+    fn put_u32(self, out: &mut Vec<u8>, v: u32) {
+        out.extend_from_slice(&if self.little_endian { v.to_le_bytes() } else { v.to_be_bytes() });
+    }
+}
+
+/// Write IFD0 with new XMP and IPTC entries. The old IFD0, XMP and IPTC
+/// blocks are overwritten where the new ones fit; blocks that do not fit go
+/// at the end of the file, and blocks already at the end (from an earlier
+/// write) are cut off first, so writing again does not grow the file.
+/// Image data and other IFDs stay where they are.
+fn write_tiff(data: &[u8], metadata: &EmbeddedMetadata) -> Result<Vec<u8>> {
+    let order = match data.get(..4) {
+        Some([b'I', b'I', 42, 0]) => ByteOrder { little_endian: true },
+        Some([b'M', b'M', 0, 42]) => ByteOrder { little_endian: false },
+        Some([b'I', b'I', 43, 0]) | Some([b'M', b'M', 0, 43]) => bail!("BigTIFF is not supported"),
+        _ => bail!("Not a TIFF file"),
+    };
+    let ifd = order.u32(data, 4)? as usize;
+    let count = order.u16(data, ifd)? as usize;
+    let entries_end = ifd + 2 + count * 12;
+    let next_ifd = order.u32(data, entries_end)?;
+
+    let mut entries: Vec<(u16, &[u8])> = Vec::with_capacity(count + 2);
+    let mut existing_xmp = None;
+    let mut existing_iptc = None;
+    // Byte ranges of the old blocks, for those stored outside the entry
+    let mut xmp_block = None;
+    let mut iptc_block = None;
+    for i in 0..count {
+        let pos = ifd + 2 + i * 12;
+        let entry = data.get(pos..pos + 12).context("Truncated TIFF")?;
+        let tag = order.u16(data, pos)?;
+        if tag == TIFF_XMP_TAG || tag == TIFF_IPTC_TAG {
+            let len = order.u32(data, pos + 4)? as usize * type_size(order.u16(data, pos + 2)?);
+            let (value, block) = if len <= 4 {
+                (&data[pos + 8..pos + 8 + len], None)
+            } else {
+                let offset = order.u32(data, pos + 8)? as usize;
+                (data.get(offset..offset + len).context("Truncated TIFF")?, Some((offset, offset + len)))
+            };
+            if tag == TIFF_XMP_TAG {
+                existing_xmp = Some(String::from_utf8_lossy(value).to_string());
+                xmp_block = block;
+            } else {
+                existing_iptc = Some(value);
+                iptc_block = block;
+            }
+        } else {
+            entries.push((tag, entry));
+        }
+    }
+
+    let xmp = update_xmp(existing_xmp.as_deref(), metadata).into_bytes();
+    let iptc = update_iptc(existing_iptc.unwrap_or_default(), metadata)?;
+
+    let mut out = data.to_vec();
+    let mut blocks = [xmp_block, iptc_block, Some((ifd, entries_end + 4))];
+    let mut by_position: Vec<usize> = (0..blocks.len()).filter(|&i| blocks[i].is_some()).collect();
+    by_position.sort_by_key(|&i| std::cmp::Reverse(blocks[i].map(|(start, _)| start)));
+    for i in by_position {
+        let Some((start, end)) = blocks[i] else { continue };
+        // At most a padding byte may follow a block at the end
+        if end > out.len() || out.len() - end > 1 {
+            break;
+        }
+        out.truncate(start);
+        blocks[i] = None;
+    }
+    let [xmp_block, iptc_block, ifd_block] = blocks;
+
+    // TIFF offsets must be even
+    let place = |out: &mut Vec<u8>, block: Option<(usize, usize)>, bytes: &[u8]| -> Result<u32> {
+        if let Some((start, end)) = block.filter(|(start, end)| end - start >= bytes.len()) {
+            out[start..start + bytes.len()].copy_from_slice(bytes);
+            out[start + bytes.len()..end].fill(0);
+            return u32::try_from(start).ok().context("TIFF too large");
+        }
+        if out.len() % 2 == 1 {
+            out.push(0);
+        }
+        let offset = u32::try_from(out.len()).ok().context("TIFF too large")?;
+        out.extend_from_slice(bytes);
+        Ok(offset)
+    };
+    let xmp_offset = place(&mut out, xmp_block, &xmp)?;
+    let iptc_offset = place(&mut out, iptc_block, &iptc)?;
+
+    let mut new_entries: Vec<(u16, Vec<u8>)> = entries.into_iter().map(|(tag, e)| (tag, e.to_vec())).collect();
+    for (tag, type_id, len, offset) in [
+        (TIFF_XMP_TAG, 1u16, xmp.len(), xmp_offset),
+        (TIFF_IPTC_TAG, 7u16, iptc.len(), iptc_offset),
+    ] {
+        let mut entry = Vec::with_capacity(12);
+        order.put_u16(&mut entry, tag);
+        order.put_u16(&mut entry, type_id);
+        order.put_u32(&mut entry, len as u32);
+        order.put_u32(&mut entry, offset);
+        new_entries.push((tag, entry));
+    }
+    new_entries.sort_by_key(|(tag, _)| *tag);
+
+    let mut table = Vec::with_capacity(2 + new_entries.len() * 12 + 4);
+    order.put_u16(&mut table, new_entries.len() as u16);
+    for (_, entry) in &new_entries {
+        table.extend_from_slice(entry);
+    }
+    order.put_u32(&mut table, next_ifd);
+    let ifd_offset = place(&mut out, ifd_block, &table)?;
+
+    let mut header = Vec::with_capacity(4);
+    order.put_u32(&mut header, ifd_offset);
+    out[4..8].copy_from_slice(&header);
+    Ok(out)
+}
+
+/// Size in bytes of one value of a TIFF field type
+fn type_size(type_id: u16) -> usize {
+    match type_id {
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn sample_metadata() -> EmbeddedMetadata {
+        EmbeddedMetadata {
+            description: Some("Sunset over the bay & pier".to_string()),
+            tags: vec!["beach".to_string(), "sunset".to_string()],
+            rating: Some(4),
+        }
+    }
+
+    fn encode(format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(8, 8))
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_write_jpeg_metadata() {
+        let written = write_jpeg(&encode(ImageFormat::Jpeg), &sample_metadata()).unwrap();
+        assert!(image::load_from_memory(&written).is_ok());
+
+        let (segments, _) = split_jpeg(&written).unwrap();
+        let xmp = segments.iter().find(|s| s.payload().starts_with(XMP_SIGNATURE)).unwrap();
+        let xmp = String::from_utf8_lossy(xmp.payload());
+        assert!(xmp.contains("Sunset over the bay &amp; pier"));
+        assert!(xmp.contains("<rdf:li>sunset</rdf:li>"));
+        assert!(xmp.contains("<xmp:Rating>4</xmp:Rating>"));
+
+        let photoshop = segments.iter().find(|s| s.marker == 0xED).unwrap();
+        let iptc = photoshop_resource(&photoshop.payload()[PHOTOSHOP_SIGNATURE.len()..], IPTC_RESOURCE)
+            .unwrap()
+            .unwrap();
+        let datasets = parse_iptc(iptc).unwrap();
+        assert!(datasets.contains(&(2, 120, b"Sunset over the bay & pier".to_vec())));
+        assert_eq!(datasets.iter().filter(|d| d.0 == 2 && d.1 == 25).count(), 2);
+
+        // Writing again replaces rather than duplicates
+        let metadata = EmbeddedMetadata { rating: Some(2), ..sample_metadata() };
+        let rewritten = write_jpeg(&written, &metadata).unwrap();
+        let (segments, _) = split_jpeg(&rewritten).unwrap();
+        assert_eq!(segments.iter().filter(|s| s.marker == 0xED).count(), 1);
+        let xmp = segments.iter().find(|s| s.payload().starts_with(XMP_SIGNATURE)).unwrap();
+        let xmp = String::from_utf8_lossy(xmp.payload());
+        assert_eq!(xmp.matches("xmp:Rating>").count(), 2);
+        assert!(xmp.contains("<xmp:Rating>2</xmp:Rating>"));
+        assert_eq!(xmp.matches("<dc:description>").count(), 1);
+    }
+
+    #[test]
+    fn test_merge_xmp_keeps_other_properties() {
+        let packet = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about="" xmlns:GCamera="http://ns.google.com/photos/1.0/camera/" xmlns:xmp="http://ns.adobe.com/xap/1.0/" GCamera:MotionPhoto="1" xmp:Rating="1"/></rdf:RDF></x:xmpmeta>"#;
+        let merged = merge_xmp(packet, &sample_metadata()).unwrap();
+        assert!(merged.contains("GCamera:MotionPhoto=\"1\""));
+        assert!(!merged.contains("xmp:Rating=\"1\""));
+        assert!(merged.contains("<xmp:Rating>4</xmp:Rating>"));
+        assert!(merged.contains("xmlns:dc="));
+        assert!(merged.contains("</rdf:Description></rdf:RDF>"));
+    }
+
+    #[test]
+    fn test_write_tiff_metadata() {
+        let written = write_tiff(&encode(ImageFormat::Tiff), &sample_metadata()).unwrap();
+        assert!(image::load_from_memory(&written).is_ok());
+
+        let rewritten = write_tiff(&written, &EmbeddedMetadata { rating: Some(5), ..Default::default() }).unwrap();
+        assert!(image::load_from_memory(&rewritten).is_ok());
+        let text = String::from_utf8_lossy(&rewritten);
+        assert!(text.contains("<xmp:Rating>5</xmp:Rating>"));
+        assert!(!text.contains("<xmp:Rating>4</xmp:Rating>"));
+        assert!(text.contains("Sunset over the bay &amp; pier"));
+
+        // The blocks an earlier write appended are replaced, not left behind
+        let again = write_tiff(&rewritten, &EmbeddedMetadata { rating: Some(5), ..Default::default() }).unwrap();
+        assert_eq!(again, rewritten);
+    }
+
+    #[test]
+    fn test_update_iptc_converts_latin1() {
+        let dataset = |record: u8, number: u8, value: &[u8]| {
+            let mut bytes = vec![0x1C, record, number];
+            bytes.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            bytes.extend_from_slice(value);
+            bytes
+        };
+        let tags_only = EmbeddedMetadata { tags: vec!["café".to_string()], ..Default::default() };
+
+        // Byline "Jürgen" in Latin-1, declared and undeclared
+        let declared = [dataset(1, 90, b"\x1B.A"), dataset(2, 80, b"J\xFCrgen")].concat();
+        for existing in [declared, dataset(2, 80, b"J\xFCrgen")] {
+            let datasets = parse_iptc(&update_iptc(&existing, &tags_only).unwrap()).unwrap();
+            assert!(datasets.contains(&(1, 90, b"\x1B%G".to_vec())));
+            assert!(datasets.contains(&(2, 80, "Jürgen".as_bytes().to_vec())));
+            assert!(datasets.contains(&(2, 25, "café".as_bytes().to_vec())));
+        }
+
+        // Undeclared UTF-8 is kept as it is
+        let utf8 = dataset(2, 80, "Jürgen".as_bytes());
+        let datasets = parse_iptc(&update_iptc(&utf8, &tags_only).unwrap()).unwrap();
+        assert!(datasets.contains(&(2, 80, "Jürgen".as_bytes().to_vec())));
+
+        // Other character sets are not touched
+        let shift_jis = [dataset(1, 90, b"\x1B$B"), dataset(2, 80, b"\x93\xfa")].concat();
+        assert_eq!(update_iptc(&shift_jis, &tags_only).unwrap(), shift_jis);
+    }
+
+    #[test]
+    fn test_unsupported_formats_are_skipped() {
+        assert!(!is_supported(Path::new("/photos/IMG_0001.HEIC")));
+        assert!(!is_supported(Path::new("/photos/screenshot.png")));
+        assert!(is_supported(Path::new("/photos/IMG_0001.JPG")));
+        assert!(!write_metadata(Path::new("/nonexistent/photo.png"), &sample_metadata()).unwrap());
+    }
+}
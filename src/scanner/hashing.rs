@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use image::DynamicImage;
 use md5::{Digest, Md5};
 use sha2::Sha256;
 use std::fs::File;
@@ -13,6 +14,15 @@ pub struct HashResult {
 }
 
 pub fn calculate_hashes(path: &PathBuf) -> Result<HashResult> {
+    let decoded = image::open(path).ok();
+    hash_file(path, decoded.as_ref())
+}
+
+/// MD5 and SHA-256 of the file, from a single read, and the perceptual
+/// hash of `decoded`, the already decoded image. The scanner decodes each
+/// photo once and shares it with thumbnail generation; without an image
+/// there is no perceptual hash.
+pub fn hash_file(path: &PathBuf, decoded: Option<&DynamicImage>) -> Result<HashResult> {
     // Calculate cryptographic hashes
     let file = File::open(path)?;
     let mut reader = BufReader::new(file);
@@ -34,7 +44,7 @@ pub fn calculate_hashes(path: &PathBuf) -> Result<HashResult> {
     let sha256 = format!("{:x}", sha256_hasher.finalize());
 
     // Calculate perceptual hash for images
-    let perceptual = calculate_perceptual_hash(path).ok();
+    let perceptual = decoded.and_then(|img| perceptual_hash(img).ok());
 
     Ok(HashResult {
         md5,
@@ -43,12 +53,9 @@ pub fn calculate_hashes(path: &PathBuf) -> Result<HashResult> {
     })
 }
 
-fn calculate_perceptual_hash(path: &PathBuf) -> Result<String> {
+fn perceptual_hash(img: &DynamicImage) -> Result<String> {
     use img_hash::HasherConfig;
 
-    // Open and decode image - use thumbnail() which is optimized for small output
-    let img = image::open(path)?;
-
     // Create small thumbnail - this is what we'll hash
     // thumbnail() preserves aspect ratio and is faster than resize for large images
     let thumbnail = img.thumbnail(64, 64);
@@ -79,4 +86,23 @@ mod tests {
         // This test would need actual image files
         // For now, we just verify the function signatures are correct
     }
+
+    #[test]
+    fn test_hash_file_uses_decoded_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gradient.png");
+        image::RgbImage::from_fn(32, 32, |x, y| image::Rgb([(x * 8) as u8, (y * 8) as u8, 128]))
+            .save(&path)
+            .unwrap();
+
+        let decoded = image::open(&path).unwrap();
+        let with_image = hash_file(&path, Some(&decoded)).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(with_image.sha256, format!("{:x}", Sha256::digest(&bytes)));
+        assert_eq!(with_image.md5, format!("{:x}", Md5::digest(&bytes)));
+        assert_eq!(with_image.perceptual, calculate_hashes(&path).unwrap().perceptual);
+        assert!(with_image.perceptual.is_some());
+
+        assert_eq!(hash_file(&path, None).unwrap().perceptual, None);
+    }
 }
@@ -0,0 +1,291 @@
+//! HEIC/HEIF support without a libheif dependency.
+//!
+//! The `image` crate cannot decode HEIF, so dimensions are read from the
+//! primary item's `ispe` property in the ISOBMFF container, and thumbnails
+//! come from the JPEG preview embedded in the EXIF data (IFD1). EXIF itself
+//! is read from the primary item's `Exif` box by kamadak-exif.
+
+use anyhow::Result;
+use image::DynamicImage;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use super::metadata::{read_exif, ImageMetadata};
+
+/// Largest `meta` box we are willing to read into memory.
+const MAX_META_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Whether the path has a HEIC/HEIF extension.
+pub fn is_heif(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+            ext == "heic" || ext == "heif"
+        })
+        .unwrap_or(false)
+}
+
+/// Extract metadata from a HEIF file: dimensions from the container, and
+/// orientation, GPS and camera info from the primary item's EXIF.
+pub fn extract_heif_metadata(path: &Path) -> Result<ImageMetadata> {
+    let mut metadata = ImageMetadata {
+        format: Some("Heif".to_string()),
+        ..Default::default()
+    };
+
+    if let Some((width, height)) = primary_dimensions(path)? {
+        metadata.width = Some(width);
+        metadata.height = Some(height);
+    }
+
+    read_exif(path, &mut metadata);
+
+    Ok(metadata)
+}
+
+/// Decode the JPEG thumbnail stored in the EXIF data, if there is one.
+pub fn embedded_thumbnail(path: &Path) -> Option<DynamicImage> {
+    let file = File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+
+    let offset = exif
+        .get_field(exif::Tag::JPEGInterchangeFormat, exif::In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+    let length = exif
+        .get_field(exif::Tag::JPEGInterchangeFormatLength, exif::In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+
+    let jpeg = exif.buf().get(offset..offset.checked_add(length)?)?;
+    image::load_from_memory_with_format(jpeg, image::ImageFormat::Jpeg).ok()
+}
+
+/// Width and height of the primary image, from its `ispe` property.
+pub fn primary_dimensions(path: &Path) -> Result<Option<(u32, u32)>> {
+    let mut file = BufReader::new(File::open(path)?);
+
+    // Walk top-level boxes until we find `meta`
+    loop {
+        let Some((box_type, payload_len)) = read_box_header(&mut file)? else {
+            return Ok(None);
+        };
+        if &box_type == b"meta" {
+            if payload_len > MAX_META_SIZE {
+                return Ok(None);
+            }
+            let mut meta = vec![0u8; payload_len as usize];
+            file.read_exact(&mut meta)?;
+            return Ok(parse_meta(&meta));
+        }
+        std::io::copy(&mut (&mut file).take(payload_len), &mut std::io::sink())?;
+    }
+}
+
+/// Read a box header, returning its type and payload length.
+fn read_box_header(reader: &mut impl Read) -> Result<Option<([u8; 4], u64)>> {
+    let mut header = [0u8; 8];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+    let box_type = [header[4], header[5], header[6], header[7]];
+
+    let payload_len = match size {
+        // Box extends to the end of the file; `meta` never does this
+        0 => return Ok(None),
+        1 => {
+            let mut large = [0u8; 8];
+            reader.read_exact(&mut large)?;
+            u64::from_be_bytes(large).saturating_sub(16)
+        }
+        _ => size.saturating_sub(8),
+    };
+    Ok(Some((box_type, payload_len)))
+}
+
+/// Iterate over the child boxes in `data` as (type, payload) pairs.
+fn boxes(mut data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    std::iter::from_fn(move || {
+        if data.len() < 8 {
+            return None;
+        }
+        let size = u32::from_be_bytes(data[0..4].try_into().ok()?) as usize;
+        let box_type: [u8; 4] = data[4..8].try_into().ok()?;
+        let (header_len, size) = match size {
+            0 => (8, data.len()),
+            1 => {
+                let large = u64::from_be_bytes(data.get(8..16)?.try_into().ok()?) as usize;
+                (16, large)
+            }
+            _ => (8, size),
+        };
+        if size < header_len || size > data.len() {
+            return None;
+        }
+        let payload = &data[header_len..size];
+        data = &data[size..];
+        Some((box_type, payload))
+    })
+}
+
+fn be_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn be_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+/// Find the `ispe` dimensions of the primary item in a `meta` payload.
+fn parse_meta(meta: &[u8]) -> Option<(u32, u32)> {
+    // `meta` is a full box: skip version and flags
+    let children = meta.get(4..)?;
+
+    let mut primary_id = None;
+    let mut properties: Vec<([u8; 4], &[u8])> = Vec::new();
+    let mut associations: Option<&[u8]> = None;
+
+    for (box_type, payload) in boxes(children) {
+        match &box_type {
+            b"pitm" => {
+                let version = *payload.first()?;
+                primary_id = if version == 0 {
+                    be_u16(payload, 4).map(u32::from)
+                } else {
+                    be_u32(payload, 4)
+                };
+            }
+            b"iprp" => {
+                for (child_type, child) in boxes(payload) {
+                    match &child_type {
+                        b"ipco" => properties = boxes(child).collect(),
+                        b"ipma" => associations = Some(child),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let ispe = |index: usize| -> Option<(u32, u32)> {
+        let (box_type, payload) = properties.get(index.checked_sub(1)?)?;
+        if box_type != b"ispe" {
+            return None;
+        }
+        Some((be_u32(payload, 4)?, be_u32(payload, 8)?))
+    };
+
+    // Properties associated with the primary item
+    if let (Some(primary_id), Some(ipma)) = (primary_id, associations) {
+        for index in item_properties(ipma, primary_id).unwrap_or_default() {
+            if let Some(dims) = ispe(index) {
+                return Some(dims);
+            }
+        }
+    }
+
+    // Fall back to the largest `ispe` in the file (tiles and thumbnails are smaller)
+    (1..=properties.len())
+        .filter_map(ispe)
+        .max_by_key(|(w, h)| *w as u64 * *h as u64)
+}
+
+/// Property indices (1-based) associated with `item_id` in an `ipma` payload.
+fn item_properties(ipma: &[u8], item_id: u32) -> Option<Vec<usize>> {
+    let version = *ipma.first()?;
+    let flags = be_u32(ipma, 0)? & 0x00ff_ffff;
+    let entry_count = be_u32(ipma, 4)?;
+    let mut pos = 8;
+
+    for _ in 0..entry_count {
+        let id = if version < 1 {
+            let id = be_u16(ipma, pos)? as u32;
+            pos += 2;
+            id
+        } else {
+            let id = be_u32(ipma, pos)?;
+            pos += 4;
+            id
+        };
+        let count = *ipma.get(pos)? as usize;
+        pos += 1;
+
+        let mut indices = Vec::with_capacity(count);
+        for _ in 0..count {
+            if flags & 1 != 0 {
+                indices.push((be_u16(ipma, pos)? & 0x7fff) as usize);
+                pos += 2;
+            } else {
+                indices.push((*ipma.get(pos)? & 0x7f) as usize);
+                pos += 1;
+            }
+        }
+        if id == item_id {
+            return Some(indices);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_box(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(box_type);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn ispe(width: u32, height: u32) -> Vec<u8> {
+        let mut payload = vec![0u8; 4];
+        payload.extend_from_slice(&width.to_be_bytes());
+        payload.extend_from_slice(&height.to_be_bytes());
+        make_box(b"ispe", &payload)
+    }
+
+    #[test]
+    fn test_primary_item_dimensions() {
+        // Primary item 1 uses property 2; property 1 is a small thumbnail ispe
+        let pitm = make_box(b"pitm", &[0, 0, 0, 0, 0, 1]);
+        let ipco = make_box(b"ipco", &[ispe(320, 240), ispe(4032, 3024)].concat());
+        let ipma = make_box(
+            b"ipma",
+            &[
+                &[0u8, 0, 0, 0][..],
+                &2u32.to_be_bytes(),
+                &[0, 2, 1, 1],    // item 2 -> property 1
+                &[0, 1, 1, 0x82], // item 1 -> property 2 (essential)
+            ]
+            .concat(),
+        );
+        let iprp = make_box(b"iprp", &[ipco, ipma].concat());
+        let meta = [&[0u8, 0, 0, 0][..], &pitm, &iprp].concat();
+
+        assert_eq!(parse_meta(&meta), Some((4032, 3024)));
+    }
+
+    #[test]
+    fn test_dimensions_without_associations() {
+        let ipco = make_box(b"ipco", &[ispe(320, 240), ispe(1920, 1080)].concat());
+        let iprp = make_box(b"iprp", &ipco);
+        let meta = [&[0u8, 0, 0, 0][..], &iprp].concat();
+
+        assert_eq!(parse_meta(&meta), Some((1920, 1080)));
+    }
+
+    #[test]
+    fn test_is_heif() {
+        assert!(is_heif(Path::new("/photos/IMG_0001.HEIC")));
+        assert!(is_heif(Path::new("a.heif")));
+        assert!(!is_heif(Path::new("a.jpg")));
+    }
+}
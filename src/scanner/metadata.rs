@@ -2,7 +2,9 @@ use anyhow::Result;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use super::{heif, pdf};
 
 #[derive(Debug, Clone, Default)]
 pub struct ImageMetadata {
@@ -121,6 +123,14 @@ impl ImageOrientation {
 }
 
 pub fn extract_metadata(path: &PathBuf) -> Result<ImageMetadata> {
+    // The image crate cannot decode HEIF, so read it from the container
+    if heif::is_heif(path) {
+        return heif::extract_heif_metadata(path);
+    }
+    if pdf::is_pdf(path) {
+        return pdf::extract_pdf_metadata(path);
+    }
+
     let mut metadata = ImageMetadata::default();
 
     // Get image format
@@ -138,7 +148,13 @@ pub fn extract_metadata(path: &PathBuf) -> Result<ImageMetadata> {
         }
     }
 
-    // Extract EXIF data
+    read_exif(path, &mut metadata);
+
+    Ok(metadata)
+}
+
+/// Fill camera, exposure, date, orientation and GPS fields from the file's EXIF data
+pub(super) fn read_exif(path: &Path, metadata: &mut ImageMetadata) {
     if let Ok(file) = File::open(path) {
         let mut bufreader = BufReader::new(file);
         if let Ok(exif) = exif::Reader::new().read_from_container(&mut bufreader) {
@@ -238,8 +254,6 @@ pub fn extract_metadata(path: &PathBuf) -> Result<ImageMetadata> {
             metadata.all_exif = extract_all_exif(&exif);
         }
     }
-
-    Ok(metadata)
 }
 
 /// Extract all EXIF fields from the image and serialize to JSON
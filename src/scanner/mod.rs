@@ -1,22 +1,34 @@
+pub mod adb;
 pub mod change_detection;
+pub mod color;
 pub mod discovery;
+pub mod error_log;
+pub mod exif_writer;
 pub mod hashing;
+pub mod heif;
 pub mod metadata;
+pub mod pdf;
+pub mod quality;
 pub mod thumbnails;
+pub mod video;
 
 use anyhow::Result;
 use rayon::prelude::*;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use crate::config::Config;
+use crate::config::{Config, IoPriority};
+use crate::db::write_queue::DbWriteQueue;
 use crate::db::Database;
 use crate::tasks::{TaskUpdate, TaskProgress};
 
-pub use change_detection::{detect_changes, ChangeDetectionResult};
+pub use change_detection::{detect_changes, diff_metadata, ChangeDetectionResult, FieldChange};
 pub use discovery::discover_images;
+pub use error_log::{write_scan_error_log, ScanErrorLog};
 pub use hashing::HashResult;
 pub use metadata::ImageMetadata;
 #[allow(unused_imports)]
@@ -26,12 +38,15 @@ pub use thumbnails::ThumbnailManager;
 #[derive(Debug, Clone)]
 pub struct ScannedPhoto {
     pub path: PathBuf,
+    pub real_path: PathBuf,
     pub filename: String,
     pub directory: String,
     pub size_bytes: u64,
     pub modified_at: Option<String>,
     pub metadata: Option<ImageMetadata>,
     pub hashes: Option<HashResult>,
+    /// Dominant colors of the thumbnail
+    pub palette: Option<Vec<[u8; 3]>>,
 }
 
 pub struct Scanner {
@@ -46,22 +61,26 @@ impl Scanner {
     }
 
     /// Scan directory with cancellation support via TaskUpdate protocol.
-    /// Uses parallel processing for faster scanning.
+    /// Uses parallel processing for faster scanning. Files that fail are
+    /// returned and written to a `scan_errors_<timestamp>.txt` log in the
+    /// state directory.
     pub fn scan_directory_cancellable(
         &self,
         directory: &PathBuf,
         db: &Database,
         tx: mpsc::Sender<TaskUpdate>,
         cancel_flag: Arc<AtomicBool>,
-    ) {
+    ) -> (ScanResult, Vec<ScanErrorLog>) {
+        let mut errors = Vec::new();
+
         // Discover all image files
-        let image_paths = match discover_images(directory, &self.config.scanner.image_extensions) {
+        let image_paths = match discover_images(directory, &self.config.scanner.extensions()) {
             Ok(paths) => paths,
             Err(e) => {
                 let _ = tx.send(TaskUpdate::Failed {
                     error: format!("Failed to discover images: {}", e),
                 });
-                return;
+                return (ScanResult::default(), errors);
             }
         };
 
@@ -72,87 +91,234 @@ impl Scanner {
             let _ = tx.send(TaskUpdate::Completed {
                 message: "No images found".to_string(),
             });
-            return;
+            return (ScanResult::default(), errors);
+        }
+
+        let result = match self.scan_paths(&image_paths, db, &tx, &cancel_flag, 0, total, &mut errors) {
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                let _ = tx.send(TaskUpdate::Cancelled);
+                return (ScanResult { total_found: total, ..ScanResult::default() }, errors);
+            }
+            Err(e) => {
+                let _ = tx.send(TaskUpdate::Failed { error: format!("Failed to open database for writing: {}", e) });
+                return (ScanResult { total_found: total, ..ScanResult::default() }, errors);
+            }
+        };
+
+        let message = scan_summary(directory, &result, &errors);
+        let _ = tx.send(TaskUpdate::Completed { message });
+        (result, errors)
+    }
+
+    /// Scan several directories as one task, e.g. `scanner.auto_scan_dirs`.
+    /// Progress counts across all of them and a single completion reports
+    /// the totals. The directories are scanned one after another so only
+    /// one scan's workers are reading from disk at a time; a directory that
+    /// cannot be read is logged as an error and skipped.
+    pub fn scan_directories_cancellable(
+        &self,
+        directories: &[PathBuf],
+        db: &Database,
+        tx: mpsc::Sender<TaskUpdate>,
+        cancel_flag: Arc<AtomicBool>,
+    ) -> (ScanResult, Vec<ScanErrorLog>) {
+        let mut errors = Vec::new();
+        let extensions = self.config.scanner.extensions();
+
+        let mut batches = Vec::new();
+        for directory in directories {
+            match discover_images(directory, &extensions) {
+                Ok(paths) => batches.push(paths),
+                Err(e) => {
+                    tracing::error!(path = %directory.display(), error = %e, "Failed to discover images");
+                    errors.push(ScanErrorLog::new(directory, format!("Failed to discover images: {}", e)));
+                }
+            }
+        }
+
+        let total = batches.iter().map(Vec::len).sum();
+        let _ = tx.send(TaskUpdate::Started { total });
+
+        let mut result = ScanResult { total_found: total, ..ScanResult::default() };
+        let mut done = 0;
+        for image_paths in &batches {
+            let batch = match self.scan_paths(image_paths, db, &tx, &cancel_flag, done, total, &mut errors) {
+                Ok(Some(batch)) => batch,
+                Ok(None) => {
+                    let _ = tx.send(TaskUpdate::Cancelled);
+                    return (result, errors);
+                }
+                Err(e) => {
+                    let _ = tx.send(TaskUpdate::Failed { error: format!("Failed to open database for writing: {}", e) });
+                    return (result, errors);
+                }
+            };
+            result.scanned += batch.scanned;
+            result.new += batch.new;
+            result.updated += batch.updated;
+            done += image_paths.len();
+        }
+
+        let names: Vec<String> = directories.iter().map(|d| d.display().to_string()).collect();
+        let message = format!(
+            "{} directories: {}",
+            directories.len(),
+            scan_summary(Path::new(&names.join(", ")), &result, &errors)
+        );
+        let _ = tx.send(TaskUpdate::Completed { message });
+        (result, errors)
+    }
+
+    /// Scan `paths` again, e.g. photos missing metadata that an older scan
+    /// did not extract. Files that no longer exist are skipped.
+    pub fn rescan_files_cancellable(
+        &self,
+        paths: &[PathBuf],
+        db: &Database,
+        tx: mpsc::Sender<TaskUpdate>,
+        cancel_flag: Arc<AtomicBool>,
+    ) -> (ScanResult, Vec<ScanErrorLog>) {
+        let mut errors = Vec::new();
+        let existing: Vec<PathBuf> = paths.iter().filter(|p| p.exists()).cloned().collect();
+        let missing = paths.len() - existing.len();
+
+        let total = existing.len();
+        let _ = tx.send(TaskUpdate::Started { total });
+
+        let result = match self.scan_paths(&existing, db, &tx, &cancel_flag, 0, total, &mut errors) {
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                let _ = tx.send(TaskUpdate::Cancelled);
+                return (ScanResult { total_found: total, ..ScanResult::default() }, errors);
+            }
+            Err(e) => {
+                let _ = tx.send(TaskUpdate::Failed { error: format!("Failed to open database for writing: {}", e) });
+                return (ScanResult { total_found: total, ..ScanResult::default() }, errors);
+            }
+        };
+
+        let mut message = scan_summary(Path::new("photos missing metadata"), &result, &errors);
+        if missing > 0 {
+            message.push_str(&format!(", {} missing files skipped", missing));
+        }
+        let _ = tx.send(TaskUpdate::Completed { message });
+        (result, errors)
+    }
+
+    /// Scan `image_paths` in parallel and save them to the database,
+    /// reporting progress as `done + n` of `total`. Each photo is written
+    /// through a `DbWriteQueue` as soon as it is scanned, so writes overlap
+    /// the hashing of later files; `db` is only read. Failures are appended
+    /// to `errors`. Returns None when cancelled, with the photos scanned
+    /// before then saved.
+    #[allow(clippy::too_many_arguments)]
+    fn scan_paths(
+        &self,
+        image_paths: &[PathBuf],
+        db: &Database,
+        tx: &mpsc::Sender<TaskUpdate>,
+        cancel_flag: &Arc<AtomicBool>,
+        done: usize,
+        total: usize,
+        errors: &mut Vec<ScanErrorLog>,
+    ) -> Result<Option<ScanResult>> {
+        let writes = DbWriteQueue::new(Database::open(&self.config.database)?)?;
+        let tally = Arc::new(Mutex::new(WriteTally::default()));
+
+        // Whether each photo is new decides insert or update
+        let mut known = HashSet::new();
+        let mut to_scan = Vec::with_capacity(image_paths.len());
+        for path in image_paths {
+            match db.photo_exists(path) {
+                Ok(exists) => {
+                    if exists {
+                        known.insert(path.clone());
+                    }
+                    to_scan.push(path);
+                }
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "Error checking photo existence");
+                    errors.push(ScanErrorLog::new(path, format!("Error checking photo existence: {}", e)));
+                }
+            }
         }
 
         // Progress counter for parallel processing
-        let progress_counter = Arc::new(AtomicUsize::new(0));
-
-        // Process images in parallel
-        let tx_clone = tx.clone();
-        let cancel_clone = cancel_flag.clone();
-        let progress_clone = progress_counter.clone();
-
-        let scanned_photos: Vec<(PathBuf, Result<ScannedPhoto>)> = image_paths
-            .par_iter()
-            .map(|path| {
-                // Check for cancellation
-                if cancel_clone.load(Ordering::SeqCst) {
-                    return (path.clone(), Err(anyhow::anyhow!("Cancelled")));
+        let progress_counter = Arc::new(AtomicUsize::new(done));
+
+        let scan_all = || {
+            to_scan.par_iter().for_each(|path| {
+                if cancel_flag.load(Ordering::SeqCst) {
+                    return;
                 }
 
                 // Update progress
-                let current = progress_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                let current = progress_counter.fetch_add(1, Ordering::SeqCst) + 1;
                 let filename = path.file_name()
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default();
-                let _ = tx_clone.send(TaskUpdate::Progress(
+                let _ = tx.send(TaskUpdate::Progress(
                     TaskProgress::new(current, total).with_item(&filename)
                 ));
 
                 // Scan the file (expensive operation - done in parallel)
-                let result = self.scan_single_file(path);
-                (path.clone(), result)
+                let photo = match self.scan_single_file(path) {
+                    Ok(photo) => photo,
+                    Err(e) => {
+                        tracing::error!(path = %path.display(), error = %e, "Error scanning photo");
+                        tally.lock().unwrap().errors.push(ScanErrorLog::new(path, e));
+                        return;
+                    }
+                };
+
+                let exists = known.contains(*path);
+                let tally = tally.clone();
+                writes.submit(move |db| save_photo(db, &photo, exists, &mut tally.lock().unwrap()));
             })
-            .collect();
+        };
+
+        match self.build_thread_pool() {
+            Ok(pool) => pool.install(scan_all),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to build scan thread pool, using the global pool");
+                scan_all()
+            }
+        }
+        writes.finish();
+
+        let tally = std::mem::take(&mut *tally.lock().unwrap());
+        errors.extend(tally.errors);
 
         // Check if cancelled during parallel processing
         if cancel_flag.load(Ordering::SeqCst) {
-            let _ = tx.send(TaskUpdate::Cancelled);
-            return;
+            return Ok(None);
         }
 
-        // Insert/update database sequentially (SQLite prefers this)
-        let mut scanned = 0;
-        let mut new_count = 0;
-        let mut updated_count = 0;
-
-        for (path, result) in scanned_photos {
-            match result {
-                Ok(photo) => {
-                    match db.photo_exists(&path) {
-                        Ok(exists) => {
-                            if exists {
-                                if let Err(e) = self.update_photo(db, &photo) {
-                                    tracing::error!(path = %path.display(), error = %e, "Error updating photo");
-                                } else {
-                                    updated_count += 1;
-                                }
-                            } else {
-                                if let Err(e) = self.insert_photo(db, &photo) {
-                                    tracing::error!(path = %path.display(), error = %e, "Error inserting photo");
-                                } else {
-                                    new_count += 1;
-                                }
-                            }
-                            scanned += 1;
-                        }
-                        Err(e) => {
-                            tracing::error!(path = %path.display(), error = %e, "Error checking photo existence");
-                        }
-                    }
-                }
-                Err(e) => {
-                    if !e.to_string().contains("Cancelled") {
-                        tracing::error!(path = %path.display(), error = %e, "Error scanning photo");
-                    }
-                }
-            }
+        Ok(Some(ScanResult {
+            total_found: image_paths.len(),
+            scanned: tally.scanned,
+            new: tally.new,
+            updated: tally.updated,
+        }))
+    }
+
+    /// Thread pool for scan workers, sized by `scanner.max_threads` and
+    /// reniced when `scanner.io_priority` is low.
+    fn build_thread_pool(&self) -> Result<rayon::ThreadPool> {
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.config.scanner.max_threads.unwrap_or(0))
+            .thread_name(|i| format!("clepho-scan-{}", i));
+        if self.config.scanner.io_priority == IoPriority::Low {
+            builder = builder.start_handler(|_| lower_thread_priority());
         }
+        Ok(builder.build()?)
+    }
 
-        let _ = tx.send(TaskUpdate::Completed {
-            message: format!("{} scanned, {} new, {} updated", scanned, new_count, updated_count),
-        });
+    /// Resolve symlinks so the same file reached through two directories
+    /// can be recognised. Falls back to `path` when it cannot be resolved.
+    pub fn resolve_realpath(path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
     }
 
     fn scan_single_file(&self, path: &PathBuf) -> Result<ScannedPhoto> {
@@ -166,20 +332,18 @@ impl Scanner {
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        // Get file modification time as ISO timestamp
-        let modified_at = file_metadata
-            .modified()
-            .ok()
-            .and_then(|t| {
-                let datetime: chrono::DateTime<chrono::Utc> = t.into();
-                Some(datetime.format("%Y-%m-%dT%H:%M:%S").to_string())
-            });
+        let modified_at = modified_timestamp(&file_metadata);
 
         // Extract image metadata (EXIF, dimensions)
         let metadata = metadata::extract_metadata(path).ok();
 
+        // Decode once for both the perceptual hash and the thumbnail. PDFs,
+        // and HEIF files the image crate cannot read, are left to the
+        // thumbnailer's fallbacks.
+        let decoded = image::open(path).ok();
+
         // Calculate hashes
-        let hashes = hashing::calculate_hashes(path).ok();
+        let hashes = hashing::hash_file(path, decoded.as_ref()).ok();
 
         // Generate thumbnail with EXIF rotation applied
         let rotation_degrees = metadata
@@ -192,20 +356,26 @@ impl Scanner {
                 _ => 0,
             })
             .unwrap_or(0);
-        let _ = self.thumbnail_manager.generate(path, rotation_degrees);
+        let palette = self
+            .thumbnail_manager
+            .generate_from(path, rotation_degrees, decoded.as_ref())
+            .and_then(|thumb| color::extract_palette(&thumb, color::PALETTE_SIZE))
+            .ok();
 
         Ok(ScannedPhoto {
             path: path.clone(),
+            real_path: Self::resolve_realpath(path),
             filename,
             directory,
             size_bytes: file_metadata.len(),
             modified_at,
             metadata,
             hashes,
+            palette,
         })
     }
 
-    fn insert_photo(&self, db: &Database, photo: &ScannedPhoto) -> Result<()> {
+    fn insert_photo(db: &Database, photo: &ScannedPhoto) -> Result<()> {
         let path_str = photo.path.to_string_lossy();
 
         let (width, height, format, camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at, gps_lat, gps_lon, all_exif, orientation) =
@@ -243,6 +413,7 @@ impl Scanner {
 
         db.insert_scanned_photo(
             path_str.as_ref(),
+            Some(photo.real_path.to_string_lossy().as_ref()),
             &photo.filename,
             &photo.directory,
             photo.size_bytes as i64,
@@ -255,7 +426,7 @@ impl Scanner {
         )
     }
 
-    fn update_photo(&self, db: &Database, photo: &ScannedPhoto) -> Result<()> {
+    fn update_photo(db: &Database, photo: &ScannedPhoto) -> Result<()> {
         let path_str = photo.path.to_string_lossy();
 
         let (width, height, format, camera_make, camera_model, lens, focal_length, aperture, shutter_speed, iso, taken_at, gps_lat, gps_lon, all_exif, orientation) =
@@ -293,6 +464,7 @@ impl Scanner {
 
         db.update_scanned_photo(
             path_str.as_ref(),
+            Some(photo.real_path.to_string_lossy().as_ref()),
             &photo.filename,
             &photo.directory,
             photo.size_bytes as i64,
@@ -306,7 +478,79 @@ impl Scanner {
     }
 }
 
-#[derive(Debug, Clone)]
+/// A file's modification time as stored in `photos.modified_at`
+pub(crate) fn modified_timestamp(file_metadata: &std::fs::Metadata) -> Option<String> {
+    let datetime: chrono::DateTime<chrono::Utc> = file_metadata.modified().ok()?.into();
+    Some(datetime.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// Raise the calling thread's niceness. On Linux `PRIO_PROCESS` with id 0
+/// applies to the calling thread only, leaving the TUI thread untouched.
+#[cfg(target_os = "linux")]
+fn lower_thread_priority() {
+    const LOW_PRIORITY_NICE: libc::c_int = 10;
+    // SAFETY: setpriority has no memory-safety preconditions
+    let ret = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, LOW_PRIORITY_NICE) };
+    if ret != 0 {
+        tracing::warn!(error = %std::io::Error::last_os_error(), "Failed to lower scan thread priority");
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn lower_thread_priority() {}
+
+/// Completion message for a scan: the counts, and where the error log
+/// was written when files failed
+fn scan_summary(directory: &Path, result: &ScanResult, errors: &[ScanErrorLog]) -> String {
+    let mut message = format!("{} scanned, {} new, {} updated", result.scanned, result.new, result.updated);
+    if !errors.is_empty() {
+        match write_scan_error_log(&Config::state_dir(), directory, errors) {
+            Ok(log_path) => {
+                message.push_str(&format!(", {} errors — see {}", errors.len(), log_path.display()));
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to write scan error log");
+                message.push_str(&format!(", {} errors", errors.len()));
+            }
+        }
+    }
+    message
+}
+
+/// What the writer thread has saved so far
+#[derive(Default)]
+struct WriteTally {
+    scanned: usize,
+    new: usize,
+    updated: usize,
+    errors: Vec<ScanErrorLog>,
+}
+
+/// Insert or update a scanned photo; runs on the write queue's thread
+fn save_photo(db: &Database, photo: &ScannedPhoto, exists: bool, tally: &mut WriteTally) {
+    let path = &photo.path;
+    if exists {
+        if let Err(e) = Scanner::update_photo(db, photo) {
+            tracing::error!(path = %path.display(), error = %e, "Error updating photo");
+            tally.errors.push(ScanErrorLog::new(path, format!("Error updating photo: {}", e)));
+        } else {
+            tally.updated += 1;
+        }
+    } else if let Err(e) = Scanner::insert_photo(db, photo) {
+        tracing::error!(path = %path.display(), error = %e, "Error inserting photo");
+        tally.errors.push(ScanErrorLog::new(path, format!("Error inserting photo: {}", e)));
+    } else {
+        tally.new += 1;
+    }
+    if let Some(ref palette) = photo.palette {
+        if let Err(e) = db.set_color_palette(path, palette) {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to store color palette");
+        }
+    }
+    tally.scanned += 1;
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 #[allow(dead_code)]
 pub struct ScanResult {
     pub total_found: usize,
@@ -0,0 +1,154 @@
+//! PDF support for mixed document and photo directories.
+//!
+//! The first page is rendered with a Ghostscript (`gs`) subprocess, which
+//! is used for thumbnails and the preview pane. Page size is read from the
+//! first `/MediaBox` in the file where possible, so scanning does not need
+//! a render just to fill in dimensions.
+
+use anyhow::{Context, Result};
+use image::{imageops::FilterType, DynamicImage};
+use std::path::Path;
+use std::process::Command;
+
+use super::metadata::ImageMetadata;
+
+/// Ghostscript executable.
+const GHOSTSCRIPT: &str = "gs";
+
+/// Only the start of the file is searched for a page size.
+const MEDIA_BOX_SEARCH_BYTES: usize = 256 * 1024;
+
+/// Long side of an A4 page in points, assumed when the size is unknown.
+const DEFAULT_PAGE_POINTS: f64 = 842.0;
+
+/// Whether the path has a `.pdf` extension.
+pub fn is_pdf(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false)
+}
+
+/// Metadata for a PDF: `format = "pdf"` and the first page's size in points.
+pub fn extract_pdf_metadata(path: &Path) -> Result<ImageMetadata> {
+    let mut metadata = ImageMetadata {
+        format: Some("pdf".to_string()),
+        ..Default::default()
+    };
+
+    let size = match page_size(path) {
+        Some(size) => Some(size),
+        // At 72 DPI one pixel is one point
+        None => render_at_dpi(path, 72.0)
+            .ok()
+            .map(|img| (img.width(), img.height())),
+    };
+    if let Some((width, height)) = size {
+        metadata.width = Some(width);
+        metadata.height = Some(height);
+    }
+
+    Ok(metadata)
+}
+
+/// Render the first page so that it fits within `max_size` pixels.
+pub fn render_first_page(path: &Path, max_size: u32) -> Result<DynamicImage> {
+    let long_side = page_size(path)
+        .map(|(w, h)| w.max(h) as f64)
+        .unwrap_or(DEFAULT_PAGE_POINTS);
+    let dpi = (max_size as f64 * 72.0 / long_side).clamp(10.0, 600.0);
+
+    let img = render_at_dpi(path, dpi)?;
+    if img.width() > max_size || img.height() > max_size {
+        Ok(img.resize(max_size, max_size, FilterType::Triangle))
+    } else {
+        Ok(img)
+    }
+}
+
+fn render_at_dpi(path: &Path, dpi: f64) -> Result<DynamicImage> {
+    let output = Command::new(GHOSTSCRIPT)
+        .args([
+            "-q",
+            "-dSAFER",
+            "-dBATCH",
+            "-dNOPAUSE",
+            "-dFirstPage=1",
+            "-dLastPage=1",
+            "-dTextAlphaBits=4",
+            "-dGraphicsAlphaBits=4",
+            "-sDEVICE=png16m",
+            &format!("-r{:.0}", dpi),
+            "-sOutputFile=-",
+        ])
+        .arg(path)
+        .output()
+        .context("Failed to run Ghostscript (gs); is it installed?")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Ghostscript failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    image::load_from_memory_with_format(&output.stdout, image::ImageFormat::Png)
+        .context("Ghostscript produced no image")
+}
+
+/// Size in points of the first `/MediaBox` near the start of the file.
+fn page_size(path: &Path) -> Option<(u32, u32)> {
+    use std::io::Read;
+
+    let mut buf = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(MEDIA_BOX_SEARCH_BYTES as u64)
+        .read_to_end(&mut buf)
+        .ok()?;
+    parse_media_box(&buf)
+}
+
+/// Parse `/MediaBox [x0 y0 x1 y1]` into a width and height.
+fn parse_media_box(data: &[u8]) -> Option<(u32, u32)> {
+    const KEY: &[u8] = b"/MediaBox";
+    let start = data.windows(KEY.len()).position(|w| w == KEY)? + KEY.len();
+    let rest = &data[start..];
+    let open = rest.iter().position(|&b| b == b'[')?;
+    let close = open + rest[open..].iter().position(|&b| b == b']')?;
+
+    let values: Vec<f64> = std::str::from_utf8(&rest[open + 1..close])
+        .ok()?
+        .split_whitespace()
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    let [x0, y0, x1, y1] = values[..] else {
+        return None;
+    };
+
+    let width = (x1 - x0).abs().round() as u32;
+    let height = (y1 - y0).abs().round() as u32;
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_media_box() {
+        let data = b"1 0 obj << /Type /Page /MediaBox [0 0 612 792] >> endobj";
+        assert_eq!(parse_media_box(data), Some((612, 792)));
+
+        let data = b"<< /MediaBox [ 0.0 0.0 595.28 841.89 ] >>";
+        assert_eq!(parse_media_box(data), Some((595, 842)));
+
+        assert_eq!(parse_media_box(b"<< /MediaBox [0 0 612] >>"), None);
+        assert_eq!(parse_media_box(b"no page here"), None);
+    }
+
+    #[test]
+    fn test_is_pdf() {
+        assert!(is_pdf(Path::new("/scans/invoice.PDF")));
+        assert!(!is_pdf(Path::new("/scans/invoice.jpg")));
+    }
+}
@@ -0,0 +1,112 @@
+//! Technical quality checks for culling bad frames.
+//!
+//! Sharpness is the variance of the Laplacian over the grayscale image:
+//! blurry frames have few strong edges and score low. Exposure is the mean
+//! luminance from 0.0 (black) to 1.0 (white).
+
+use anyhow::Result;
+use image::{DynamicImage, GrayImage};
+use std::path::Path;
+
+use crate::config::QualityConfig;
+
+use super::{heif, pdf};
+
+/// Longest side images are scaled to before measuring, so scores compare
+/// across resolutions and large RAW files stay quick
+const ASSESS_SIZE: u32 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualityAssessment {
+    /// Variance of the Laplacian; higher is sharper
+    pub sharpness: f64,
+    /// Mean luminance, 0.0 to 1.0
+    pub exposure: f64,
+}
+
+impl QualityAssessment {
+    /// Blurry, or too dark or bright on average
+    pub fn is_poor(&self, config: &QualityConfig) -> bool {
+        let [low, high] = config.exposure_range;
+        self.sharpness < config.min_sharpness || self.exposure < low || self.exposure > high
+    }
+}
+
+pub fn assess_quality(path: &Path) -> Result<QualityAssessment> {
+    let image = if pdf::is_pdf(path) {
+        pdf::render_first_page(path, ASSESS_SIZE)?
+    } else {
+        match image::open(path) {
+            Ok(img) => img.thumbnail(ASSESS_SIZE, ASSESS_SIZE),
+            Err(e) => heif::embedded_thumbnail(path).ok_or(e)?,
+        }
+    };
+    Ok(assess_image(&image))
+}
+
+pub fn assess_image(image: &DynamicImage) -> QualityAssessment {
+    let gray = image.to_luma8();
+    QualityAssessment {
+        sharpness: laplacian_variance(&gray),
+        exposure: mean_luminance(&gray),
+    }
+}
+
+fn mean_luminance(gray: &GrayImage) -> f64 {
+    let pixels = gray.as_raw();
+    if pixels.is_empty() {
+        return 0.0;
+    }
+    let sum: u64 = pixels.iter().map(|&p| p as u64).sum();
+    sum as f64 / pixels.len() as f64 / 255.0
+}
+
+/// Variance of the 4-neighbour Laplacian over the interior pixels
+fn laplacian_variance(gray: &GrayImage) -> f64 {
+    let (width, height) = gray.dimensions();
+    if width < 3 || height < 3 {
+        return 0.0;
+    }
+    let at = |x: u32, y: u32| gray.get_pixel(x, y)[0] as f64;
+
+    let mut sum = 0.0;
+    let mut sum_sq = 0.0;
+    let mut count = 0.0;
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let value = at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1) - 4.0 * at(x, y);
+            sum += value;
+            sum_sq += value * value;
+            count += 1.0;
+        }
+    }
+    let mean = sum / count;
+    sum_sq / count - mean * mean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    #[test]
+    fn test_assess_image() {
+        let config = QualityConfig::default();
+
+        let checkerboard = GrayImage::from_fn(64, 64, |x, y| Luma([if (x / 4 + y / 4) % 2 == 0 { 40 } else { 200 }]));
+        let sharp = assess_image(&DynamicImage::ImageLuma8(checkerboard));
+        assert!(sharp.sharpness > config.min_sharpness);
+        assert!((sharp.exposure - 0.47).abs() < 0.01);
+        assert!(!sharp.is_poor(&config));
+
+        let flat = assess_image(&DynamicImage::ImageLuma8(GrayImage::from_pixel(64, 64, Luma([128]))));
+        assert_eq!(flat.sharpness, 0.0);
+        assert!(flat.is_poor(&config));
+
+        let white = GrayImage::from_fn(64, 64, |x, y| Luma([if (x / 4 + y / 4) % 2 == 0 { 235 } else { 255 }]));
+        let overexposed = assess_image(&DynamicImage::ImageLuma8(white));
+        assert!(overexposed.sharpness > config.min_sharpness);
+        assert!(overexposed.exposure > config.exposure_range[1]);
+        assert!(overexposed.is_poor(&config));
+    }
+}
@@ -1,11 +1,17 @@
 use anyhow::Result;
 use image::DynamicImage;
+use rayon::prelude::*;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
 
 use crate::config::ThumbnailConfig;
+use crate::tasks::{TaskProgress, TaskUpdate};
 
 /// Manages thumbnail generation and caching
+#[derive(Clone)]
 pub struct ThumbnailManager {
     cache_dir: PathBuf,
     size: u32,
@@ -67,7 +73,6 @@ impl ThumbnailManager {
     }
 
     /// Check if a cached thumbnail exists for the given path and rotation
-    #[allow(dead_code)]
     pub fn has_cached(&self, original: &Path, rotation_degrees: i32) -> bool {
         self.cache_path(original, rotation_degrees).exists()
     }
@@ -86,6 +91,12 @@ impl ThumbnailManager {
     /// rotation_degrees: 0, 90, 180, or 270 degrees clockwise
     /// Returns the path to the cached thumbnail
     pub fn generate(&self, original: &Path, rotation_degrees: i32) -> Result<PathBuf> {
+        self.generate_from(original, rotation_degrees, None)
+    }
+
+    /// Like `generate`, but shrinks `decoded`, the original already decoded
+    /// by the caller, instead of reading the file again
+    pub fn generate_from(&self, original: &Path, rotation_degrees: i32, decoded: Option<&DynamicImage>) -> Result<PathBuf> {
         self.ensure_cache_dir()?;
 
         let cache_path = self.cache_path(original, rotation_degrees);
@@ -95,8 +106,14 @@ impl ThumbnailManager {
             return Ok(cache_path);
         }
 
-        // Open and resize image
-        let img = image::open(original)?;
+        let opened;
+        let img = match decoded {
+            Some(img) => img,
+            None => {
+                opened = self.open_original(original)?;
+                &opened
+            }
+        };
         let thumbnail = img.thumbnail(self.size, self.size);
 
         // Apply rotation (from EXIF orientation + user rotation)
@@ -108,6 +125,62 @@ impl ThumbnailManager {
         Ok(cache_path)
     }
 
+    /// Decode the original; HEIF falls back to its embedded EXIF preview
+    /// and PDFs are rendered by Ghostscript
+    fn open_original(&self, original: &Path) -> Result<DynamicImage> {
+        if super::pdf::is_pdf(original) {
+            return super::pdf::render_first_page(original, self.size);
+        }
+        match image::open(original) {
+            Ok(img) => Ok(img),
+            Err(e) => match super::heif::is_heif(original)
+                .then(|| super::heif::embedded_thumbnail(original))
+                .flatten()
+            {
+                Some(img) => Ok(img),
+                None => Err(e.into()),
+            },
+        }
+    }
+
+    /// Generate the thumbnails `jobs` (image and rotation) are missing, in
+    /// parallel, reporting progress on `tx` until done or cancelled.
+    pub fn warm_up(&self, jobs: Vec<(PathBuf, i32)>, tx: mpsc::Sender<TaskUpdate>, cancel_flag: Arc<AtomicBool>) {
+        let total = jobs.len();
+        let _ = tx.send(TaskUpdate::Started { total });
+
+        let done = AtomicUsize::new(0);
+        let failed = AtomicUsize::new(0);
+        jobs.par_iter().for_each(|(path, rotation)| {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return;
+            }
+            if self.generate(path, *rotation).is_err() {
+                failed.fetch_add(1, Ordering::SeqCst);
+            }
+            let current = done.fetch_add(1, Ordering::SeqCst) + 1;
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let _ = tx.send(TaskUpdate::Progress(TaskProgress::new(current, total).with_item(name)));
+        });
+
+        if cancel_flag.load(Ordering::SeqCst) {
+            let _ = tx.send(TaskUpdate::Cancelled);
+            return;
+        }
+        let failed = failed.into_inner();
+        let message = if failed == 0 {
+            format!("{} thumbnails generated", total)
+        } else {
+            format!("{} thumbnails generated, {} unreadable", total - failed, failed)
+        };
+        let _ = tx.send(TaskUpdate::Completed { message });
+    }
+
+    /// Size of the cached thumbnails' longest side in pixels
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
     /// Invalidate cached thumbnail for an image (all rotations)
     /// Call this when user changes rotation to force regeneration
     pub fn invalidate(&self, original: &Path) {
@@ -127,3 +200,33 @@ impl ThumbnailManager {
         &self.cache_dir
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warm_up_generates_missing_thumbnails() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ThumbnailManager::new(&ThumbnailConfig {
+            path: dir.path().join("thumbs"),
+            size: 16,
+        });
+        let photo = dir.path().join("red.png");
+        image::RgbImage::from_pixel(32, 24, image::Rgb([255, 0, 0])).save(&photo).unwrap();
+        let broken = dir.path().join("broken.jpg");
+        fs::write(&broken, "not an image").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let jobs = vec![(photo.clone(), 90), (broken, 0)];
+        manager.warm_up(jobs, tx, Arc::new(AtomicBool::new(false)));
+
+        assert!(manager.has_cached(&photo, 90));
+        assert!(!manager.has_cached(&photo, 0));
+        let last = rx.try_iter().last();
+        assert!(matches!(
+            last,
+            Some(TaskUpdate::Completed { ref message }) if message == "1 thumbnails generated, 1 unreadable"
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+//! Video files in the preview pane.
+//!
+//! Videos are not indexed, but with `show_all_files` the preview shows
+//! their first frame, extracted with an `ffmpeg` subprocess.
+
+use anyhow::{Context, Result};
+use image::{imageops::FilterType, DynamicImage};
+use std::path::Path;
+use std::process::Command;
+
+/// ffmpeg executable.
+const FFMPEG: &str = "ffmpeg";
+
+/// Extensions previewed as video.
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "m4v", "mov", "mkv", "webm", "avi", "3gp", "mts", "m2ts"];
+
+/// Whether the path has a video extension.
+pub fn is_video(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+            VIDEO_EXTENSIONS.contains(&ext.as_str())
+        })
+        .unwrap_or(false)
+}
+
+/// Extract the first frame, scaled to fit within `max_size` pixels.
+pub fn first_frame(path: &Path, max_size: u32) -> Result<DynamicImage> {
+    let output = Command::new(FFMPEG)
+        .args(["-v", "error", "-i"])
+        .arg(path)
+        .args(["-frames:v", "1", "-f", "image2pipe", "-vcodec", "png", "-"])
+        .output()
+        .context("Failed to run ffmpeg; is it installed?")?;
+
+    if !output.status.success() {
+        anyhow::bail!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let img = image::load_from_memory_with_format(&output.stdout, image::ImageFormat::Png)
+        .context("ffmpeg produced no frame")?;
+    if img.width() > max_size || img.height() > max_size {
+        Ok(img.resize(max_size, max_size, FilterType::Triangle))
+    } else {
+        Ok(img)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_video() {
+        assert!(is_video(Path::new("/camera/clip.MP4")));
+        assert!(is_video(Path::new("/camera/clip.mov")));
+        assert!(!is_video(Path::new("/camera/IMG_0001.jpg")));
+        assert!(!is_video(Path::new("/camera/notes")));
+    }
+}
@@ -4,6 +4,10 @@ use chrono::{Local, Timelike, Utc};
 use std::time::Instant;
 
 use crate::db::{Database, ScheduleStatus, ScheduledTask};
+use crate::trash::{TrashCleanupTask, LAST_CLEANUP_KEY};
+
+/// Seconds between checks of the trash cleanup interval.
+const TRASH_CHECK_INTERVAL_SECS: u64 = 60;
 
 /// Manages the polling and execution of scheduled tasks.
 pub struct ScheduleManager {
@@ -11,6 +15,8 @@ pub struct ScheduleManager {
     last_check: Option<Instant>,
     /// Minimum interval between checks (1 second).
     check_interval_ms: u64,
+    /// Last time we checked whether the trash is due for cleanup.
+    last_trash_check: Option<Instant>,
 }
 
 impl ScheduleManager {
@@ -18,6 +24,7 @@ impl ScheduleManager {
         Self {
             last_check: None,
             check_interval_ms: 1000,
+            last_trash_check: None,
         }
     }
 
@@ -73,6 +80,23 @@ impl ScheduleManager {
             .collect()
     }
 
+    /// Whether the periodic trash cleanup should run now. Checked at most
+    /// once a minute against the `last_trash_cleanup` app state.
+    pub fn trash_cleanup_due(&mut self, db: &Database, interval_hours: u32) -> bool {
+        if let Some(last) = self.last_trash_check {
+            if last.elapsed().as_secs() < TRASH_CHECK_INTERVAL_SECS {
+                return false;
+            }
+        }
+        self.last_trash_check = Some(Instant::now());
+
+        let last_cleanup = match db.get_app_state(LAST_CLEANUP_KEY) {
+            Ok(value) => value,
+            Err(_) => return false,
+        };
+        TrashCleanupTask::is_due(last_cleanup.as_deref(), interval_hours, Utc::now())
+    }
+
     /// Check for overdue schedules (for startup prompt).
     pub fn check_overdue(&self, db: &Database) -> Vec<ScheduledTask> {
         let now = Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
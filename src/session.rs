@@ -0,0 +1,107 @@
+//! Session state persistence for crash recovery.
+//!
+//! The browser position is written to `$XDG_STATE_HOME/clepho/session.json`
+//! while navigating, so that restarting after a crash returns to the same
+//! directory, selection and view.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// View that was active when the session was saved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionView {
+    #[default]
+    Browser,
+    Gallery,
+    Slideshow,
+}
+
+/// Snapshot of the browser state written to the session file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionState {
+    pub current_dir: PathBuf,
+    #[serde(default)]
+    pub selected_index: usize,
+    #[serde(default)]
+    pub selected_files: Vec<PathBuf>,
+    #[serde(default)]
+    pub view: SessionView,
+}
+
+impl SessionState {
+    /// Location of the session file.
+    pub fn path() -> PathBuf {
+        crate::config::Config::state_dir().join("session.json")
+    }
+
+    /// Load the saved session, if any. Unreadable or corrupt files are ignored.
+    pub fn load() -> Option<Self> {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    /// Write the session via a temporary file so a crash mid-write
+    /// never leaves a truncated session behind.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("session.json");
+        let state = SessionState {
+            current_dir: PathBuf::from("/photos/2024"),
+            selected_index: 7,
+            selected_files: vec![PathBuf::from("/photos/2024/a.jpg")],
+            view: SessionView::Gallery,
+        };
+
+        state.save_to(&path).unwrap();
+        assert_eq!(SessionState::load_from(&path), Some(state));
+    }
+
+    #[test]
+    fn test_missing_fields_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        std::fs::write(&path, r#"{"current_dir": "/photos"}"#).unwrap();
+
+        let state = SessionState::load_from(&path).unwrap();
+        assert_eq!(state.selected_index, 0);
+        assert!(state.selected_files.is_empty());
+        assert_eq!(state.view, SessionView::Browser);
+    }
+
+    #[test]
+    fn test_corrupt_file_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        std::fs::write(&path, "{not json").unwrap();
+
+        assert_eq!(SessionState::load_from(&path), None);
+    }
+}
@@ -0,0 +1,324 @@
+//! Sharing selected photos as a temporary web gallery.
+//!
+//! The gallery is a single `index.html` with every thumbnail embedded as a
+//! data URI, served together with the full-size photos by a small HTTP
+//! server on a random port. The server listens on the interface facing the
+//! local network so phones can open it from the QR code, and stops when the
+//! task is cancelled. Every path starts with a random token that only the
+//! shared URL carries, so other devices on the network cannot browse it.
+
+use anyhow::{Context, Result};
+use axum::extract::{Path as UrlPath, State};
+use axum::http::{header, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::export::html_escape;
+use crate::scanner::ThumbnailManager;
+use crate::tasks::{TaskProgress, TaskUpdate};
+
+/// A photo to share and the rotation its thumbnail is drawn with
+pub struct SharedPhoto {
+    pub path: PathBuf,
+    pub rotation: i32,
+}
+
+/// Gallery entry once its thumbnail is encoded
+struct GalleryEntry {
+    name: String,
+    thumbnail: Option<String>,
+}
+
+/// What the server hands out: the page and the originals it links to
+struct Gallery {
+    html: String,
+    photos: Vec<PathBuf>,
+}
+
+/// Where the last shared gallery page is written
+pub fn share_dir() -> PathBuf {
+    Config::state_dir().join("share")
+}
+
+/// Build the gallery, then serve it until `cancel_flag` is set. The URL is
+/// sent on `ready` once the server is listening, or the error if it could
+/// not start. Runs as a background task reporting on `tx`.
+pub fn serve_gallery(
+    photos: Vec<SharedPhoto>,
+    thumbnails: ThumbnailManager,
+    runtime: tokio::runtime::Handle,
+    ready: mpsc::Sender<Result<String, String>>,
+    tx: mpsc::Sender<TaskUpdate>,
+    cancel_flag: Arc<AtomicBool>,
+) {
+    let total = photos.len();
+    let _ = tx.send(TaskUpdate::Started { total });
+
+    let mut entries = Vec::with_capacity(total);
+    for (i, photo) in photos.iter().enumerate() {
+        if cancel_flag.load(Ordering::SeqCst) {
+            let _ = tx.send(TaskUpdate::Cancelled);
+            return;
+        }
+        let name = photo.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let _ = tx.send(TaskUpdate::Progress(
+            TaskProgress::new(i, total).with_item(name.clone()).with_message("Embedding thumbnails"),
+        ));
+        let thumbnail = match thumbnail_data_uri(&thumbnails, &photo.path, photo.rotation) {
+            Ok(uri) => Some(uri),
+            Err(e) => {
+                tracing::warn!(error = %e, path = ?photo.path, "Failed to embed thumbnail");
+                None
+            }
+        };
+        entries.push(GalleryEntry { name, thumbnail });
+    }
+
+    let html = gallery_html(&entries);
+    let dir = share_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(dir.join("index.html"), &html)) {
+        tracing::warn!(error = %e, "Failed to save shared gallery page");
+    }
+
+    let gallery = Arc::new(Gallery {
+        html,
+        photos: photos.into_iter().map(|p| p.path).collect(),
+    });
+    let server_ready = ready.clone();
+    let server_tx = tx.clone();
+    let result = runtime.block_on(async move {
+        let token = share_token()?;
+        let listener = tokio::net::TcpListener::bind((lan_address(), 0))
+            .await
+            .context("Failed to open a port for sharing")?;
+        let url = format!("http://{}/{}/", listener.local_addr()?, token);
+        let _ = server_ready.send(Ok(url.clone()));
+        let _ = server_tx.send(TaskUpdate::Progress(
+            TaskProgress::new(total, total).with_message(format!("Serving at {}", url)),
+        ));
+
+        axum::serve(listener, router(gallery, &token))
+            .with_graceful_shutdown(wait_for_cancel(cancel_flag))
+            .await
+            .context("Sharing server failed")
+    });
+
+    match result {
+        Ok(()) => {
+            let _ = tx.send(TaskUpdate::Completed {
+                message: format!("Stopped sharing {} photos", total),
+            });
+        }
+        Err(e) => {
+            let _ = ready.send(Err(e.to_string()));
+            let _ = tx.send(TaskUpdate::Failed { error: e.to_string() });
+        }
+    }
+}
+
+/// Random hex token that gates every page of the gallery
+fn share_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| anyhow::anyhow!("Failed to generate a share token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Routes under `/{token}/`; anything else is not found
+fn router(gallery: Arc<Gallery>, token: &str) -> Router {
+    Router::new()
+        .route(&format!("/{}/", token), get(index))
+        .route(&format!("/{}/photo/{{index}}", token), get(photo))
+        .with_state(gallery)
+}
+
+async fn wait_for_cancel(cancel_flag: Arc<AtomicBool>) {
+    while !cancel_flag.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+async fn index(State(gallery): State<Arc<Gallery>>) -> Html<String> {
+    Html(gallery.html.clone())
+}
+
+async fn photo(State(gallery): State<Arc<Gallery>>, UrlPath(index): UrlPath<usize>) -> Response {
+    let Some(path) = gallery.photos.get(index) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    match tokio::fs::read(path).await {
+        Ok(bytes) => ([(header::CONTENT_TYPE, content_type(path))], bytes).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn content_type(path: &Path) -> &'static str {
+    let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "heic" | "heif" => "image/heic",
+        _ => "application/octet-stream",
+    }
+}
+
+fn thumbnail_data_uri(thumbnails: &ThumbnailManager, path: &Path, rotation: i32) -> Result<String> {
+    let thumb = match thumbnails.get_cached_path(path, rotation) {
+        Some(thumb) => thumb,
+        None => thumbnails.generate(path, rotation)?,
+    };
+    let bytes = std::fs::read(&thumb)?;
+    Ok(format!("data:image/jpeg;base64,{}", BASE64.encode(bytes)))
+}
+
+/// Address other devices on the network can reach this machine at. No
+/// packet is sent: connecting a UDP socket only picks the outgoing
+/// interface.
+fn lan_address() -> IpAddr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 80))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
+
+/// The URL as a QR code, one string per terminal row
+pub fn qr_lines(url: &str) -> Vec<String> {
+    match QrCode::new(url.as_bytes()) {
+        Ok(code) => code
+            .render::<Dense1x2>()
+            .quiet_zone(true)
+            .build()
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to render QR code");
+            Vec::new()
+        }
+    }
+}
+
+fn gallery_html(entries: &[GalleryEntry]) -> String {
+    let mut html = String::from(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Shared Photos</title>
+    <style>
+        body {
+            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
+            margin: 0 auto;
+            padding: 20px;
+            background: #1a1a1a;
+            color: #e0e0e0;
+        }
+        .grid {
+            display: grid;
+            grid-template-columns: repeat(auto-fill, minmax(200px, 1fr));
+            gap: 12px;
+        }
+        .grid a {
+            color: #888;
+            font-size: 12px;
+            text-decoration: none;
+            word-break: break-all;
+        }
+        .grid img {
+            width: 100%;
+            border-radius: 6px;
+            display: block;
+            margin-bottom: 4px;
+        }
+    </style>
+</head>
+<body>
+    <div class="grid">
+"#,
+    );
+
+    for (i, entry) in entries.iter().enumerate() {
+        let image = entry
+            .thumbnail
+            .as_deref()
+            .map(|uri| format!(r#"<img src="{}" alt="" loading="lazy">"#, uri))
+            .unwrap_or_default();
+        html.push_str(&format!(
+            "        <a href=\"photo/{}\">{}{}</a>\n",
+            i,
+            image,
+            html_escape(&entry.name)
+        ));
+    }
+
+    html.push_str(
+        r#"    </div>
+</body>
+</html>
+"#,
+    );
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gallery_html() {
+        let entries = vec![
+            GalleryEntry { name: "a<b>.jpg".to_string(), thumbnail: Some("data:image/jpeg;base64,AAAA".to_string()) },
+            GalleryEntry { name: "c.jpg".to_string(), thumbnail: None },
+        ];
+        let html = gallery_html(&entries);
+        assert!(html.contains(r#"<a href="photo/0"><img src="data:image/jpeg;base64,AAAA""#));
+        assert!(html.contains("a&lt;b&gt;.jpg"));
+        assert!(html.contains(r#"<a href="photo/1">c.jpg</a>"#));
+
+        let qr = qr_lines("http://192.168.1.20:40123/");
+        assert!(!qr.is_empty());
+        assert!(qr.iter().all(|line| line.chars().count() == qr[0].chars().count()));
+    }
+
+    #[tokio::test]
+    async fn test_gallery_needs_the_token() {
+        let token = share_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, share_token().unwrap());
+
+        let gallery = Arc::new(Gallery { html: "gallery".to_string(), photos: Vec::new() });
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = router(gallery, &token);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let status = |path: String| async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            let request = format!("GET {} HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n", path);
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response.lines().next().unwrap_or_default().to_string()
+        };
+        assert!(status("/".to_string()).await.contains("404"));
+        assert!(status("/photo/0".to_string()).await.contains("404"));
+        assert!(status(format!("/{}/", "0".repeat(32))).await.contains("404"));
+        assert!(status(format!("/{}/", token)).await.contains("200"));
+    }
+}
@@ -5,13 +5,19 @@ use std::sync::atomic::AtomicBool;
 use std::sync::mpsc;
 use std::sync::Arc;
 
-use super::{BackgroundTask, TaskCompletionInfo, TaskId, TaskProgress, TaskState, TaskType, TaskUpdate};
+use super::{
+    BackgroundTask, TaskCompletionInfo, TaskDependency, TaskId, TaskProgress, TaskState, TaskType, TaskUpdate,
+};
 
 /// Manages all background tasks, providing centralized control and status.
 pub struct BackgroundTaskManager {
     tasks: HashMap<TaskId, BackgroundTask>,
     /// Order in which tasks were added (for "most recent" cancellation).
     task_order: Vec<TaskId>,
+    /// Tasks waiting on another task, in the order they were queued.
+    queued: Vec<TaskDependency>,
+    /// Tasks released by `take_ready` that the caller has yet to register.
+    released: Vec<TaskDependency>,
 }
 
 impl BackgroundTaskManager {
@@ -19,16 +25,23 @@ impl BackgroundTaskManager {
         Self {
             tasks: HashMap::new(),
             task_order: Vec::new(),
+            queued: Vec::new(),
+            released: Vec::new(),
         }
     }
 
     /// Register a new background task.
     /// Returns the TaskId and a sender for the task to send updates.
+    /// A task of a type just released by `take_ready` runs under the id it
+    /// was queued with, so the tasks depending on it keep waiting.
     pub fn register_task(&mut self, task_type: TaskType) -> (TaskId, mpsc::Sender<TaskUpdate>, Arc<AtomicBool>) {
+        let id = match self.released.iter().position(|t| t.task_type == task_type) {
+            Some(pos) => self.released.remove(pos).id,
+            None => TaskId::new(),
+        };
         let (tx, rx) = mpsc::channel();
         let cancel_flag = Arc::new(AtomicBool::new(false));
-        let task = BackgroundTask::new(task_type, cancel_flag.clone(), rx);
-        let id = task.id;
+        let task = BackgroundTask::new(id, task_type, cancel_flag.clone(), rx);
 
         self.tasks.insert(id, task);
         self.task_order.push(id);
@@ -36,6 +49,63 @@ impl BackgroundTaskManager {
         (id, tx, cancel_flag)
     }
 
+    /// Queue a task to start once `depends_on` has completed successfully.
+    /// Returns the id it will run under, for chaining further tasks. If the
+    /// dependency fails or is cancelled, the task is skipped.
+    pub fn queue_task(&mut self, task_type: TaskType, depends_on: Option<TaskId>) -> TaskId {
+        let id = TaskId::new();
+        self.queued.push(TaskDependency { id, task_type, depends_on });
+        id
+    }
+
+    /// Take the queued tasks whose dependency is no longer pending; the
+    /// caller starts each with `register_task`. A released task that was not
+    /// registered by the next call, because it had nothing to do, counts as
+    /// completed.
+    pub fn take_ready(&mut self) -> Vec<TaskType> {
+        self.released.clear();
+        let pending: Vec<TaskId> = self
+            .tasks
+            .values()
+            .filter(|t| t.is_running())
+            .map(|t| t.id)
+            .chain(self.queued.iter().map(|t| t.id))
+            .collect();
+
+        let (ready, waiting): (Vec<TaskDependency>, Vec<TaskDependency>) = self
+            .queued
+            .drain(..)
+            .partition(|t| t.depends_on.is_none_or(|dep| !pending.contains(&dep)));
+        self.queued = waiting;
+        self.released = ready;
+        self.released.iter().map(|t| t.task_type).collect()
+    }
+
+    /// Tasks waiting on another task.
+    pub fn queued_tasks(&self) -> &[TaskDependency] {
+        &self.queued
+    }
+
+    /// Drop queued tasks that depend, directly or through other queued
+    /// tasks, on a task that did not complete.
+    fn skip_dependents(&mut self, failed: TaskId, failed_type: TaskType, completed: &mut Vec<TaskCompletionInfo>) {
+        let mut failed = vec![failed];
+        while let Some(dep) = failed.pop() {
+            let (skipped, kept): (Vec<TaskDependency>, Vec<TaskDependency>) =
+                self.queued.drain(..).partition(|t| t.depends_on == Some(dep));
+            self.queued = kept;
+            for task in skipped {
+                failed.push(task.id);
+                completed.push(TaskCompletionInfo {
+                    id: task.id,
+                    task_type: task.task_type,
+                    message: format!("Skipped because {} did not complete", failed_type.display_name()),
+                    success: false,
+                });
+            }
+        }
+    }
+
     /// Check if a task of the given type is already running.
     pub fn is_running(&self, task_type: TaskType) -> bool {
         self.tasks.values().any(|t| t.task_type == task_type && t.is_running())
@@ -67,8 +137,9 @@ impl BackgroundTaskManager {
         false
     }
 
-    /// Cancel all running tasks.
+    /// Cancel all running tasks and drop queued ones.
     pub fn cancel_all(&mut self) {
+        self.queued.clear();
         for task in self.tasks.values() {
             if task.is_running() {
                 task.cancel();
@@ -127,6 +198,12 @@ impl BackgroundTaskManager {
             }
         }
 
+        let failed: Vec<(TaskId, TaskType)> =
+            completed.iter().filter(|c| !c.success).map(|c| (c.id, c.task_type)).collect();
+        for (id, task_type) in failed {
+            self.skip_dependents(id, task_type, &mut completed);
+        }
+
         // Remove completed tasks from tracking
         for info in &completed {
             self.tasks.remove(&info.id);
@@ -161,3 +238,38 @@ impl Default for BackgroundTaskManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_tasks_wait_for_their_dependency() {
+        let mut manager = BackgroundTaskManager::new();
+        let (scan, scan_tx, _) = manager.register_task(TaskType::Scan);
+        let llm = manager.queue_task(TaskType::LlmBatch, Some(scan));
+        manager.queue_task(TaskType::FaceDetection, Some(llm));
+        assert!(manager.take_ready().is_empty());
+
+        scan_tx.send(TaskUpdate::Completed { message: String::new() }).unwrap();
+        manager.poll_updates();
+        assert_eq!(manager.take_ready(), vec![TaskType::LlmBatch]);
+
+        // The LLM batch runs under its queued id, so faces keep waiting
+        let (id, llm_tx, _) = manager.register_task(TaskType::LlmBatch);
+        assert_eq!(id, llm);
+        assert!(manager.take_ready().is_empty());
+
+        llm_tx.send(TaskUpdate::Failed { error: "offline".to_string() }).unwrap();
+        let completions = manager.poll_updates();
+        assert_eq!(completions.len(), 2);
+        assert_eq!(completions[1].task_type, TaskType::FaceDetection);
+        assert!(manager.queued_tasks().is_empty());
+
+        // A released task that is never registered counts as done
+        let first = manager.queue_task(TaskType::LlmBatch, None);
+        manager.queue_task(TaskType::FaceDetection, Some(first));
+        assert_eq!(manager.take_ready(), vec![TaskType::LlmBatch]);
+        assert_eq!(manager.take_ready(), vec![TaskType::FaceDetection]);
+    }
+}
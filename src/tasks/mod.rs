@@ -40,6 +40,16 @@ pub enum TaskType {
     FaceClustering,
     ClipEmbedding,
     FindDuplicates,
+    DirSize,
+    EventDetection,
+    FaceEmbeddingRegen,
+    ThumbnailWarmup,
+    MoveFiles,
+    Centralise,
+    AdbImport,
+    AutoCull,
+    Share,
+    WriteMetadata,
 }
 
 impl TaskType {
@@ -53,6 +63,16 @@ impl TaskType {
             TaskType::FaceClustering => "C",
             TaskType::ClipEmbedding => "E",
             TaskType::FindDuplicates => "D",
+            TaskType::DirSize => "Z",
+            TaskType::EventDetection => "V",
+            TaskType::FaceEmbeddingRegen => "R",
+            TaskType::ThumbnailWarmup => "T",
+            TaskType::MoveFiles => "M",
+            TaskType::Centralise => "G",
+            TaskType::AdbImport => "A",
+            TaskType::AutoCull => "Q",
+            TaskType::Share => "W",
+            TaskType::WriteMetadata => "X",
         }
     }
 
@@ -66,6 +86,16 @@ impl TaskType {
             TaskType::FaceClustering => "Face Clustering",
             TaskType::ClipEmbedding => "CLIP Embedding",
             TaskType::FindDuplicates => "Find Duplicates",
+            TaskType::DirSize => "Directory Sizes",
+            TaskType::EventDetection => "Event Detection",
+            TaskType::FaceEmbeddingRegen => "Face Embedding Regeneration",
+            TaskType::ThumbnailWarmup => "Thumbnail Warm-up",
+            TaskType::MoveFiles => "Move Files",
+            TaskType::Centralise => "Centralise",
+            TaskType::AdbImport => "ADB Import",
+            TaskType::AutoCull => "Auto-cull",
+            TaskType::Share => "Web Gallery",
+            TaskType::WriteMetadata => "Write Metadata",
         }
     }
 }
@@ -147,12 +177,13 @@ pub struct BackgroundTask {
 impl BackgroundTask {
     /// Create a new background task.
     pub fn new(
+        id: TaskId,
         task_type: TaskType,
         cancel_flag: Arc<AtomicBool>,
         receiver: mpsc::Receiver<TaskUpdate>,
     ) -> Self {
         Self {
-            id: TaskId::new(),
+            id,
             task_type,
             state: TaskState::Running,
             progress: None,
@@ -179,6 +210,16 @@ impl BackgroundTask {
     }
 }
 
+/// A task waiting for another to finish before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskDependency {
+    /// Id the task will run under once started
+    pub id: TaskId,
+    pub task_type: TaskType,
+    /// Task that must complete successfully first; `None` starts right away
+    pub depends_on: Option<TaskId>,
+}
+
 /// Result of polling task updates.
 #[derive(Debug, Clone)]
 pub struct TaskCompletionInfo {
@@ -0,0 +1,269 @@
+//! Batch file transfers run as background tasks: moves from the move dialog
+//! and centralising into the library. Each file is announced to the task
+//! manager with the bytes transferred so far.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+
+use crate::centralise::CentraliseResult;
+use crate::db::Database;
+use crate::tasks::{TaskProgress, TaskUpdate};
+use crate::ui::conflict_dialog::Resolution;
+use crate::ui::rename_dialog::next_numbered_name;
+
+/// What a finished batch operation hands back to the app
+pub enum BatchOutcome {
+    Move { target_dir: PathBuf, summary: MoveSummary },
+    Centralise(Result<CentraliseResult, String>),
+}
+
+/// Per-file progress reporting for a batch transfer
+pub struct TransferProgress {
+    tx: Sender<TaskUpdate>,
+    total_files: usize,
+    total_bytes: u64,
+    done_files: usize,
+    done_bytes: u64,
+}
+
+impl TransferProgress {
+    pub fn start(tx: Sender<TaskUpdate>, total_files: usize, total_bytes: u64) -> Self {
+        let _ = tx.send(TaskUpdate::Started { total: total_files });
+        Self {
+            tx,
+            total_files,
+            total_bytes,
+            done_files: 0,
+            done_bytes: 0,
+        }
+    }
+
+    /// Announce the file about to be transferred, then count it as done.
+    pub fn file(&mut self, path: &Path, size: u64) {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let progress = TaskProgress::new(self.done_files, self.total_files)
+            .with_item(name)
+            .with_message(self.bytes_label());
+        let _ = self.tx.send(TaskUpdate::Progress(progress));
+        self.done_files += 1;
+        self.done_bytes += size;
+    }
+
+    /// Bytes transferred so far, e.g. "12.5M of 1.2G"
+    pub fn bytes_label(&self) -> String {
+        format!("{} of {}", format_size(self.done_bytes), format_size(self.total_bytes))
+    }
+}
+
+/// Files moved, skipped over a conflict, and failed
+#[derive(Debug, Default)]
+pub struct MoveSummary {
+    /// `(source, destination)` of each moved file, in move order
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    pub skipped: usize,
+    pub failed: usize,
+    /// Stopped before every file was handled
+    pub cancelled: bool,
+}
+
+impl MoveSummary {
+    pub fn message(&self, target_dir: &Path) -> String {
+        if self.failed == 0 && self.skipped == 0 && !self.cancelled {
+            return format!("Moved {} files to {}", self.moved.len(), target_dir.display());
+        }
+        let mut message = format!("Moved {} files", self.moved.len());
+        if self.skipped > 0 {
+            message.push_str(&format!(", {} skipped", self.skipped));
+        }
+        if self.failed > 0 {
+            message.push_str(&format!(", {} failed", self.failed));
+        }
+        if self.cancelled {
+            message.push_str(" before the move was cancelled");
+        }
+        message
+    }
+}
+
+/// Size of each file that exists, for progress totals
+pub fn file_sizes(files: &[PathBuf]) -> HashMap<PathBuf, u64> {
+    files
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok().map(|m| (p.clone(), m.len())))
+        .collect()
+}
+
+/// Move files into `target_dir`, resolving taken names as chosen in the
+/// conflict dialog, and keep their database records pointing at them.
+pub fn move_files(
+    db: &Database,
+    files: &[PathBuf],
+    target_dir: &Path,
+    resolutions: &HashMap<PathBuf, Resolution>,
+    sizes: &HashMap<PathBuf, u64>,
+    progress: &mut TransferProgress,
+    cancel_flag: &AtomicBool,
+) -> MoveSummary {
+    let mut summary = MoveSummary::default();
+    // Names given to files of this move, for numbering kept copies
+    let mut claimed: HashSet<String> = HashSet::new();
+
+    for source_path in files {
+        if cancel_flag.load(Ordering::SeqCst) {
+            summary.cancelled = true;
+            break;
+        }
+        progress.file(source_path, sizes.get(source_path).copied().unwrap_or(0));
+
+        let Some(filename) = source_path.file_name() else { continue };
+        let mut target_path = target_dir.join(filename);
+        // Record of the photo this move replaces
+        let mut replaced = None;
+
+        match resolutions.get(source_path) {
+            Some(Resolution::Skip) => {
+                summary.skipped += 1;
+                continue;
+            }
+            Some(Resolution::KeepBoth) => {
+                let name = next_numbered_name(target_dir, &filename.to_string_lossy(), &claimed);
+                target_path = target_dir.join(name);
+            }
+            Some(Resolution::Overwrite) => {
+                replaced = db.get_photo_metadata(&target_path).ok().flatten().map(|existing| existing.id);
+            }
+            None => {
+                // Appeared after the conflict check, so nobody chose to replace it
+                if target_path.exists() {
+                    tracing::warn!(path = ?target_path, "Destination appeared during move, skipping");
+                    summary.skipped += 1;
+                    continue;
+                }
+            }
+        }
+        if let Some(name) = target_path.file_name() {
+            claimed.insert(name.to_string_lossy().to_string());
+        }
+
+        // Try rename first, then copy + delete for cross-filesystem moves
+        if std::fs::rename(source_path, &target_path).is_err() {
+            if let Err(e) = std::fs::copy(source_path, &target_path) {
+                tracing::error!(error = %e, path = ?source_path, "Failed to move file");
+                summary.failed += 1;
+                continue;
+            }
+            if let Err(e) = std::fs::remove_file(source_path) {
+                tracing::warn!(error = %e, path = ?source_path, "Copied but failed to delete original");
+            }
+        }
+        // The replaced photo's record would clash with the moved one's path,
+        // and goes only now that its file is really gone
+        if let Some(id) = replaced {
+            if let Err(e) = db.delete_photos_by_ids(&[id]) {
+                tracing::warn!(error = %e, "Failed to remove overwritten photo from DB");
+            }
+        }
+        if let Err(e) = db.update_photo_path(source_path, &target_path) {
+            tracing::warn!(error = %e, "Failed to update DB path");
+        }
+        summary.moved.push((source_path.clone(), target_path));
+    }
+
+    summary
+}
+
+fn format_size(size: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if size >= GB {
+        format!("{:.1}G", size as f64 / GB as f64)
+    } else if size >= MB {
+        format!("{:.1}M", size as f64 / MB as f64)
+    } else if size >= KB {
+        format!("{:.1}K", size as f64 / KB as f64)
+    } else {
+        format!("{}B", size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_move_files_reports_progress() {
+        let (dir, db) = crate::db::test_db();
+        let target = dir.path().join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(dir.path().join("a.jpg"), vec![0u8; 2048]).unwrap();
+        std::fs::write(dir.path().join("b.jpg"), "b").unwrap();
+        std::fs::write(target.join("b.jpg"), "taken").unwrap();
+
+        let files = vec![dir.path().join("a.jpg"), dir.path().join("b.jpg")];
+        let sizes = file_sizes(&files);
+        let resolutions = HashMap::from([(files[1].clone(), Resolution::KeepBoth)]);
+        let (tx, rx) = mpsc::channel();
+        let mut progress = TransferProgress::start(tx, files.len(), sizes.values().sum());
+        let cancel = AtomicBool::new(false);
+
+        let summary = move_files(&db, &files, &target, &resolutions, &sizes, &mut progress, &cancel);
+        assert_eq!(summary.moved.len(), 2);
+        assert_eq!(summary.moved[1].1, target.join("b_001.jpg"));
+        assert_eq!(summary.message(&target), format!("Moved 2 files to {}", target.display()));
+        assert_eq!(progress.bytes_label(), "2.0K of 2.0K");
+
+        let updates: Vec<TaskUpdate> = rx.try_iter().collect();
+        assert!(matches!(updates[0], TaskUpdate::Started { total: 2 }));
+        let TaskUpdate::Progress(ref second) = updates[2] else { panic!("expected progress") };
+        assert_eq!(second.current, 1);
+        assert_eq!(second.current_item.as_deref(), Some("b.jpg"));
+        assert_eq!(second.message.as_deref(), Some("2.0K of 2.0K"));
+    }
+
+    #[test]
+    fn test_move_files_skips_unresolved_conflict() {
+        let (dir, db) = crate::db::test_db();
+        let target = dir.path().join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(dir.path().join("a.jpg"), "new").unwrap();
+        // Created after the conflict check, so there is no resolution for it
+        std::fs::write(target.join("a.jpg"), "taken").unwrap();
+
+        let files = vec![dir.path().join("a.jpg")];
+        let sizes = file_sizes(&files);
+        let (tx, _rx) = mpsc::channel();
+        let mut progress = TransferProgress::start(tx, files.len(), sizes.values().sum());
+        let cancel = AtomicBool::new(false);
+
+        let summary = move_files(&db, &files, &target, &HashMap::new(), &sizes, &mut progress, &cancel);
+        assert!(summary.moved.is_empty());
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(std::fs::read_to_string(target.join("a.jpg")).unwrap(), "taken");
+        assert!(files[0].exists());
+    }
+
+    #[test]
+    fn test_failed_overwrite_keeps_the_replaced_record() {
+        let (dir, db) = crate::db::test_db();
+        let target = dir.path().join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("a.jpg"), "taken").unwrap();
+        db.insert_basic_photo(&target.join("a.jpg").to_string_lossy(), "a.jpg", &target.to_string_lossy(), 5).unwrap();
+
+        // The source is gone, so the move fails
+        let files = vec![dir.path().join("a.jpg")];
+        let resolutions = HashMap::from([(files[0].clone(), Resolution::Overwrite)]);
+        let (tx, _rx) = mpsc::channel();
+        let mut progress = TransferProgress::start(tx, 1, 0);
+        let cancel = AtomicBool::new(false);
+
+        let summary = move_files(&db, &files, &target, &resolutions, &HashMap::new(), &mut progress, &cancel);
+        assert_eq!(summary.failed, 1);
+        assert!(db.get_photo_metadata(&target.join("a.jpg")).unwrap().is_some());
+    }
+}
@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::config::{TrashConfig, DuplicateTrashConfig};
+use crate::db::Database;
 
 pub struct TrashManager {
     config: TrashConfig,
@@ -26,6 +27,7 @@ pub struct TrashEntry {
 pub struct CleanupResult {
     pub files_deleted: usize,
     pub bytes_freed: u64,
+    pub deleted_paths: Vec<PathBuf>,
 }
 
 impl TrashManager {
@@ -40,6 +42,8 @@ impl TrashManager {
                 path: dup_config.path,
                 max_age_days: dup_config.max_age_days,
                 max_size_bytes: dup_config.max_size_bytes,
+                // Duplicate trash is emptied by its own auto_empty setting
+                cleanup_interval_hours: 0,
             },
         }
     }
@@ -146,7 +150,6 @@ impl TrashManager {
     }
 
     /// Get total trash size in bytes (file-system based, alternative to DB query)
-    #[allow(dead_code)]
     pub fn total_size(&self) -> Result<u64> {
         if !self.config.path.exists() {
             return Ok(0);
@@ -223,6 +226,7 @@ impl TrashManager {
                 if fs::remove_file(&path).is_ok() {
                     result.files_deleted += 1;
                     result.bytes_freed += size;
+                    result.deleted_paths.push(path);
                     total_size -= size;
                 } else {
                     remaining_entries.push((path, size, file_time));
@@ -238,6 +242,7 @@ impl TrashManager {
             if fs::remove_file(&path).is_ok() {
                 result.files_deleted += 1;
                 result.bytes_freed += size;
+                result.deleted_paths.push(path);
                 total_size -= size;
             }
         }
@@ -245,3 +250,78 @@ impl TrashManager {
         Ok(result)
     }
 }
+
+/// `app_state` key holding when the trash was last cleaned up automatically
+pub const LAST_CLEANUP_KEY: &str = "last_trash_cleanup";
+
+/// Periodic enforcement of the trash age and size limits.
+pub struct TrashCleanupTask;
+
+impl TrashCleanupTask {
+    /// Whether `interval_hours` have passed since `last_cleanup` (RFC 3339).
+    /// A missing or unreadable timestamp counts as due; an interval of 0
+    /// never is.
+    pub fn is_due(last_cleanup: Option<&str>, interval_hours: u32, now: DateTime<Utc>) -> bool {
+        if interval_hours == 0 {
+            return false;
+        }
+        match last_cleanup.and_then(|s| DateTime::parse_from_rfc3339(s).ok()) {
+            Some(last) => now.signed_duration_since(last) >= chrono::Duration::hours(interval_hours as i64),
+            None => true,
+        }
+    }
+
+    /// Empty the trash down to its limits, forget the deleted photos and
+    /// record the time of the cleanup.
+    pub fn run(manager: &TrashManager, db: &Database) -> Result<CleanupResult> {
+        let result = manager.auto_empty()?;
+        Self::forget_deleted(db, &result);
+        db.set_app_state(LAST_CLEANUP_KEY, &Utc::now().to_rfc3339())?;
+
+        if result.files_deleted > 0 {
+            tracing::info!(
+                files = result.files_deleted,
+                bytes = result.bytes_freed,
+                "Trash cleanup freed space"
+            );
+        }
+        Ok(result)
+    }
+
+    /// Remove database records of trashed photos whose files a cleanup deleted
+    pub fn forget_deleted(db: &Database, result: &CleanupResult) {
+        if result.deleted_paths.is_empty() {
+            return;
+        }
+        let trashed = match db.get_trashed_photos() {
+            Ok(trashed) => trashed,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to list trashed photos after cleanup");
+                return;
+            }
+        };
+        for photo in trashed {
+            if result.deleted_paths.iter().any(|p| p == Path::new(&photo.path)) {
+                if let Err(e) = db.delete_trashed_photo(photo.id) {
+                    tracing::warn!(path = %photo.path, error = %e, "Failed to forget deleted trash entry");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_is_due_after_the_interval() {
+        let now = DateTime::parse_from_rfc3339("2024-06-15T12:00:00Z").unwrap().with_timezone(&Utc);
+
+        assert!(TrashCleanupTask::is_due(None, 24, now));
+        assert!(TrashCleanupTask::is_due(Some("garbage"), 24, now));
+        assert!(TrashCleanupTask::is_due(Some("2024-06-14T12:00:00+00:00"), 24, now));
+        assert!(!TrashCleanupTask::is_due(Some("2024-06-14T13:00:00+00:00"), 24, now));
+        assert!(!TrashCleanupTask::is_due(None, 0, now));
+    }
+}
@@ -0,0 +1,364 @@
+//! Import wizard for Android devices: pick a device, browse its camera
+//! folders, pick photos and import them into the library.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::mpsc;
+
+use image::DynamicImage;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use ratatui_image::{protocol::StatefulProtocol, StatefulImage};
+
+use crate::scanner::adb::{AdbDevice, AdbFile, AdbScanner};
+use crate::ui::theme::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdbImportStep {
+    /// Choosing one of the connected devices
+    Devices,
+    /// Browsing the chosen device's DCIM folders
+    Browse,
+}
+
+/// A photo shown from the device without pulling it
+pub struct AdbPreview {
+    pub path: String,
+    /// None when the terminal cannot show images or the file did not decode
+    pub image: Option<StatefulProtocol>,
+}
+
+/// State for the ADB import dialog
+pub struct AdbImportDialog {
+    pub step: AdbImportStep,
+    pub devices: Vec<AdbDevice>,
+    /// Serial of the device being browsed
+    pub serial: Option<String>,
+    /// Importable files under DCIM on that device
+    pub files: Vec<AdbFile>,
+    /// Folder being browsed; None lists the folders
+    pub folder: Option<String>,
+    pub selected: usize,
+    /// Device paths of the files picked for import
+    pub picked: HashSet<String>,
+    pub preview: Option<AdbPreview>,
+    pending_preview: Option<(String, mpsc::Receiver<Result<DynamicImage, String>>)>,
+}
+
+impl AdbImportDialog {
+    pub fn new(devices: Vec<AdbDevice>) -> Self {
+        Self {
+            step: AdbImportStep::Devices,
+            devices,
+            serial: None,
+            files: Vec::new(),
+            folder: None,
+            selected: 0,
+            picked: HashSet::new(),
+            preview: None,
+            pending_preview: None,
+        }
+    }
+
+    /// Start browsing a device's files, keeping those with one of
+    /// `extensions`.
+    pub fn set_files(&mut self, serial: String, files: Vec<AdbFile>, extensions: &[String]) {
+        self.serial = Some(serial);
+        self.files = files.into_iter().filter(|f| f.has_extension(extensions)).collect();
+        self.step = AdbImportStep::Browse;
+        self.folder = None;
+        self.selected = 0;
+        self.picked.clear();
+        self.preview = None;
+    }
+
+    pub fn selected_device(&self) -> Option<&AdbDevice> {
+        self.devices.get(self.selected)
+    }
+
+    /// Folders below DCIM with their file counts, in path order
+    pub fn folders(&self) -> Vec<(&str, usize)> {
+        let mut folders: BTreeMap<&str, usize> = BTreeMap::new();
+        for file in &self.files {
+            *folders.entry(file.folder()).or_default() += 1;
+        }
+        folders.into_iter().collect()
+    }
+
+    /// Files of the folder being browsed
+    pub fn folder_files(&self) -> Vec<&AdbFile> {
+        match self.folder {
+            Some(ref folder) => self.files.iter().filter(|f| f.folder() == folder).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        match (self.step, &self.folder) {
+            (AdbImportStep::Devices, _) => self.devices.len(),
+            (AdbImportStep::Browse, None) => self.folders().len(),
+            (AdbImportStep::Browse, Some(_)) => self.folder_files().len(),
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.row_count() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Enter the selected folder.
+    pub fn open_selected(&mut self) {
+        if self.step != AdbImportStep::Browse || self.folder.is_some() {
+            return;
+        }
+        let folder = self.folders().get(self.selected).map(|(name, _)| name.to_string());
+        if let Some(folder) = folder {
+            self.folder = Some(folder);
+            self.selected = 0;
+        }
+    }
+
+    /// Go back to the folder list. Returns false when already there.
+    pub fn back(&mut self) -> bool {
+        let Some(folder) = self.folder.take() else { return false };
+        self.selected = self.folders().iter().position(|(name, _)| *name == folder).unwrap_or(0);
+        self.preview = None;
+        true
+    }
+
+    /// Pick or unpick the selected file, or every file of the selected folder.
+    pub fn toggle_selected(&mut self) {
+        let paths: Vec<String> = match self.folder {
+            Some(_) => self.folder_files().get(self.selected).map(|f| vec![f.path.clone()]).unwrap_or_default(),
+            None => {
+                let Some((folder, _)) = self.folders().get(self.selected).copied() else { return };
+                self.files.iter().filter(|f| f.folder() == folder).map(|f| f.path.clone()).collect()
+            }
+        };
+        if paths.iter().all(|p| self.picked.contains(p)) {
+            for path in &paths {
+                self.picked.remove(path);
+            }
+        } else {
+            self.picked.extend(paths);
+        }
+    }
+
+    /// Picked files in path order
+    pub fn picked_files(&self) -> Vec<AdbFile> {
+        self.files.iter().filter(|f| self.picked.contains(&f.path)).cloned().collect()
+    }
+
+    /// Read the selected file from the device in a background thread.
+    pub fn start_preview(&mut self) {
+        let Some(file) = self.folder_files().get(self.selected).map(|f| f.path.clone()) else { return };
+        let serial = self.serial.clone();
+        let (tx, rx) = mpsc::channel();
+        let remote = file.clone();
+        std::thread::spawn(move || {
+            let result = AdbScanner::read_file(serial.as_deref(), &remote)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| image::load_from_memory(&bytes).map_err(|e| e.to_string()));
+            let _ = tx.send(result);
+        });
+        self.pending_preview = Some((file, rx));
+    }
+
+    pub fn is_loading_preview(&self) -> bool {
+        self.pending_preview.is_some()
+    }
+
+    /// Pick up a finished preview read as `(device path, image)`.
+    pub fn poll_preview(&mut self) -> Option<(String, Result<DynamicImage, String>)> {
+        let (_, rx) = self.pending_preview.as_ref()?;
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return None,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Preview failed".to_string()),
+        };
+        let (path, _) = self.pending_preview.take()?;
+        Some((path, result))
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &mut AdbImportDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 90.min(area.width.saturating_sub(4));
+    let dialog_height = 26.min(area.height.saturating_sub(2));
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .title(" Import from Android ")
+        .title_style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(5),    // List and preview
+            Constraint::Length(1), // Footer
+        ])
+        .split(dialog_area);
+
+    let header = match (dialog.step, &dialog.folder) {
+        (AdbImportStep::Devices, _) => "Connected devices".to_string(),
+        (AdbImportStep::Browse, folder) => format!(
+            "{} · DCIM/{} · {} picked",
+            dialog.serial.as_deref().unwrap_or(""),
+            folder.as_deref().unwrap_or(""),
+            dialog.picked.len()
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(header).style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        chunks[0],
+    );
+
+    let body = if dialog.preview.is_some() || dialog.is_loading_preview() {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1])
+    } else {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(100)])
+            .split(chunks[1])
+    };
+
+    let items: Vec<ListItem> = match (dialog.step, &dialog.folder) {
+        (AdbImportStep::Devices, _) => dialog
+            .devices
+            .iter()
+            .map(|device| {
+                let style = if device.is_ready() { Style::default() } else { Style::default().fg(theme.muted) };
+                ListItem::new(format!("{}  ({})", device.serial, device.state)).style(style)
+            })
+            .collect(),
+        (AdbImportStep::Browse, None) => dialog
+            .folders()
+            .iter()
+            .map(|(folder, count)| {
+                let picked = dialog
+                    .files
+                    .iter()
+                    .filter(|f| f.folder() == *folder && dialog.picked.contains(&f.path))
+                    .count();
+                let name = if folder.is_empty() { "(DCIM)" } else { folder };
+                ListItem::new(format!("{}/  {} photos, {} picked", name, count, picked))
+            })
+            .collect(),
+        (AdbImportStep::Browse, Some(_)) => dialog
+            .folder_files()
+            .iter()
+            .map(|file| {
+                let mark = if dialog.picked.contains(&file.path) { "[x]" } else { "[ ]" };
+                ListItem::new(format!("{} {}", mark, file.name()))
+            })
+            .collect(),
+    };
+
+    let empty = items.is_empty();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted)))
+        .highlight_style(Style::default().bg(theme.selected_bg).fg(theme.selected_fg));
+    let mut state = ListState::default();
+    state.select(Some(dialog.selected));
+    frame.render_stateful_widget(list, body[0], &mut state);
+
+    if empty {
+        let text = match dialog.step {
+            AdbImportStep::Devices => "No devices. Connect a phone with USB debugging on and press r.",
+            AdbImportStep::Browse => "No photos in DCIM",
+        };
+        let inner = Rect::new(body[0].x + 1, body[0].y + 1, body[0].width.saturating_sub(2), 1);
+        frame.render_widget(Paragraph::new(text).style(Style::default().fg(theme.muted)), inner);
+    }
+
+    if body.len() > 1 {
+        let loading = dialog.is_loading_preview();
+        let title = dialog
+            .preview
+            .as_ref()
+            .map(|p| p.path.rsplit('/').next().unwrap_or(&p.path).to_string())
+            .unwrap_or_default();
+        let preview_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.muted))
+            .title(title);
+        let inner = preview_block.inner(body[1]);
+        frame.render_widget(preview_block, body[1]);
+        match dialog.preview.as_mut().and_then(|p| p.image.as_mut()) {
+            Some(protocol) if !loading => {
+                frame.render_stateful_widget(StatefulImage::new(None), inner, protocol);
+            }
+            _ => {
+                let text = if loading { "Loading..." } else { "No preview" };
+                let middle = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                frame.render_widget(
+                    Paragraph::new(text).style(Style::default().fg(theme.muted)).alignment(Alignment::Center),
+                    middle,
+                );
+            }
+        }
+    }
+
+    let footer = match (dialog.step, &dialog.folder) {
+        (AdbImportStep::Devices, _) => "j/k: navigate | Enter: browse device | r: refresh | Esc: close",
+        (AdbImportStep::Browse, None) => "j/k: navigate | l/Enter: open folder | Space: pick folder | i: import | Esc: close",
+        (AdbImportStep::Browse, Some(_)) => "Space: pick | p: preview | h: folders | i: import | Esc: back",
+    };
+    frame.render_widget(Paragraph::new(footer).style(Style::default().fg(theme.muted)), chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> AdbFile {
+        AdbFile { path: path.to_string() }
+    }
+
+    #[test]
+    fn test_browse_and_pick() {
+        let mut dialog = AdbImportDialog::new(Vec::new());
+        let files = vec![
+            file("/sdcard/DCIM/Camera/a.jpg"),
+            file("/sdcard/DCIM/Camera/b.jpg"),
+            file("/sdcard/DCIM/Camera/clip.mp4"),
+            file("/sdcard/DCIM/Screenshots/s.png"),
+        ];
+        dialog.set_files("R58M".to_string(), files, &["jpg".to_string(), "png".to_string()]);
+        assert_eq!(dialog.folders(), vec![("Camera", 2), ("Screenshots", 1)]);
+
+        // Space on a folder picks all of it, and again unpicks it
+        dialog.toggle_selected();
+        assert_eq!(dialog.picked.len(), 2);
+        dialog.toggle_selected();
+        assert!(dialog.picked.is_empty());
+
+        dialog.move_down();
+        dialog.open_selected();
+        assert_eq!(dialog.folder.as_deref(), Some("Screenshots"));
+        dialog.toggle_selected();
+        assert!(dialog.back());
+        assert_eq!(dialog.selected, 1);
+        assert!(!dialog.back());
+        assert_eq!(dialog.picked_files(), vec![file("/sdcard/DCIM/Screenshots/s.png")]);
+    }
+}
@@ -0,0 +1,304 @@
+//! Album browser: a grid of albums, each shown by its cover thumbnail.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+use ratatui_image::StatefulImage;
+
+use crate::app::App;
+use crate::config::ImageProtocol;
+use crate::db::albums::{Album, AlbumSort};
+use crate::ui::gallery::GalleryView;
+use crate::ui::theme::Theme;
+
+/// An album with what the grid shows about it
+#[derive(Debug, Clone)]
+pub struct AlbumEntry {
+    pub album: Album,
+    /// Cover photo, or the album's first photo when no cover is set
+    pub cover: Option<PathBuf>,
+    /// Photos in the album, counting a smart album's tag matches
+    pub count: usize,
+}
+
+/// Line of input the album browser is waiting for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlbumPrompt {
+    /// Name for a new album
+    NewAlbum(String),
+    /// y/n before deleting the selected album
+    ConfirmDelete,
+    /// Comma separated tags a smart album's photos must all carry
+    SmartTags(String),
+    /// `lat, lon, radius_km` a smart album's photos must be taken within
+    GeoRadius(String),
+    /// Directory to export the selected album to
+    ExportDir(String),
+    /// Optional rename pattern, asked once the directory is known
+    ExportPattern { dir: String, input: String },
+}
+
+impl AlbumPrompt {
+    /// The text being typed, for prompts that take text
+    pub fn input_mut(&mut self) -> Option<&mut String> {
+        match self {
+            AlbumPrompt::NewAlbum(input)
+            | AlbumPrompt::SmartTags(input)
+            | AlbumPrompt::GeoRadius(input)
+            | AlbumPrompt::ExportDir(input)
+            | AlbumPrompt::ExportPattern { input, .. } => Some(input),
+            AlbumPrompt::ConfirmDelete => None,
+        }
+    }
+}
+
+/// State for the album browser.
+pub struct AlbumsView {
+    pub entries: Vec<AlbumEntry>,
+    /// Cover thumbnails and cursor: `grid.images[i]` is the cover of
+    /// `entries[i]`, or an empty path for an album without photos
+    pub grid: GalleryView,
+    pub prompt: Option<AlbumPrompt>,
+}
+
+impl AlbumsView {
+    pub fn new(directory: PathBuf, entries: Vec<AlbumEntry>, protocol: ImageProtocol) -> Self {
+        let covers = Self::covers(&entries);
+        Self {
+            entries,
+            grid: GalleryView::new(directory, covers, protocol),
+            prompt: None,
+        }
+    }
+
+    /// Replace the albums after a change, keeping the cursor in range.
+    pub fn set_entries(&mut self, entries: Vec<AlbumEntry>) {
+        self.grid.images = Self::covers(&entries);
+        self.grid.selected = self.grid.selected.min(entries.len().saturating_sub(1));
+        self.entries = entries;
+    }
+
+    pub fn selected(&self) -> Option<&AlbumEntry> {
+        self.entries.get(self.grid.selected)
+    }
+
+    /// Move the cursor to the album with `album_id`, if it is listed.
+    pub fn select_album(&mut self, album_id: i64) {
+        if let Some(idx) = self.entries.iter().position(|e| e.album.id == album_id) {
+            self.grid.selected = idx;
+        }
+    }
+
+    fn covers(entries: &[AlbumEntry]) -> Vec<PathBuf> {
+        entries.iter().map(|e| e.cover.clone().unwrap_or_default()).collect()
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
+    let (columns, visible_rows, visible_covers) = {
+        let view = match app.albums_view.as_mut() {
+            Some(v) => v,
+            None => return,
+        };
+        view.grid.poll_async_loads();
+
+        let columns = view.grid.columns(area.width);
+        let visible_rows = view.grid.visible_rows(area.height.saturating_sub(3));
+        view.grid.update_layout_cache(columns, visible_rows);
+        view.grid.ensure_visible(columns, visible_rows);
+
+        let start = (view.grid.scroll_offset * columns).min(view.entries.len());
+        let end = (start + visible_rows * columns).min(view.entries.len());
+        (columns, visible_rows, view.grid.images[start..end].to_vec())
+    };
+
+    // Covers honour the same rotations as the gallery
+    let rotations: HashMap<PathBuf, i32> = visible_covers
+        .iter()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(|p| (p.clone(), app.get_photo_rotation(p)))
+        .collect();
+
+    let view = match app.albums_view.as_mut() {
+        Some(v) => v,
+        None => return,
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Grid
+            Constraint::Length(2),  // Footer
+        ])
+        .split(area);
+
+    let smart = view.entries.iter().filter(|e| e.album.is_smart).count();
+    let header = format!(" Albums: {} ({} smart)", view.entries.len(), smart);
+    frame.render_widget(
+        Paragraph::new(header).style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        chunks[0],
+    );
+
+    if view.entries.is_empty() {
+        let empty = Paragraph::new("No albums yet. Press n to create one.")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, Rect::new(chunks[1].x, chunks[1].y + chunks[1].height / 2, chunks[1].width, 1));
+    }
+
+    let cell_width = view.grid.thumbnail_size.cell_width();
+    let cell_height = view.grid.thumbnail_size.cell_height();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints((0..visible_rows).map(|_| Constraint::Length(cell_height)).collect::<Vec<_>>())
+        .split(chunks[1]);
+    for (row_idx, row_area) in rows.iter().enumerate() {
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints((0..columns).map(|_| Constraint::Length(cell_width)).collect::<Vec<_>>())
+            .split(*row_area);
+        for (col_idx, cell_area) in cols.iter().enumerate() {
+            let idx = (view.grid.scroll_offset + row_idx) * columns + col_idx;
+            if idx < view.entries.len() {
+                let rotation = rotations.get(&view.grid.images[idx]).copied().unwrap_or(0);
+                render_album_cell(frame, view, idx, *cell_area, rotation, &theme);
+            }
+        }
+    }
+
+    render_footer(frame, view, chunks[2], &theme);
+}
+
+fn render_album_cell(frame: &mut Frame, view: &mut AlbumsView, idx: usize, area: Rect, rotation: i32, theme: &Theme) {
+    let entry = &view.entries[idx];
+    let border_color = if idx == view.grid.selected { theme.accent } else { theme.muted };
+
+    let max_name_len = (area.width as usize).saturating_sub(4);
+    let name = if entry.album.name.chars().count() > max_name_len {
+        let kept: String = entry.album.name.chars().take(max_name_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    } else {
+        entry.album.name.clone()
+    };
+    let mut info = format!(
+        "{} photo{} · {}",
+        entry.count,
+        if entry.count == 1 { "" } else { "s" },
+        if entry.album.is_smart { "smart" } else { "manual" }
+    );
+    if entry.album.auto_sort != AlbumSort::Manual {
+        info.push_str(&format!(" · {}", entry.album.auto_sort.label()));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(name);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width < 2 || inner.height < 3 {
+        return;
+    }
+
+    let info_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+    let info_style = if entry.album.is_smart { theme.emphasis } else { theme.secondary };
+    frame.render_widget(
+        Paragraph::new(info).style(Style::default().fg(info_style)).alignment(Alignment::Center),
+        info_area,
+    );
+
+    let image_area = Rect::new(inner.x, inner.y, inner.width, inner.height - 1);
+    let middle = Rect::new(image_area.x, image_area.y + image_area.height / 2, image_area.width, 1);
+    let cover = view.grid.images[idx].clone();
+    if cover.as_os_str().is_empty() {
+        let empty = Paragraph::new("(empty)")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, middle);
+    } else if let Some(protocol) = view.grid.load_thumbnail(&cover, rotation) {
+        frame.render_stateful_widget(StatefulImage::new(None), image_area, protocol);
+    } else {
+        let text = if view.grid.is_loading(&cover, rotation) { "Loading..." } else { "[ ]" };
+        let placeholder = Paragraph::new(text)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(placeholder, middle);
+    }
+}
+
+fn render_footer(frame: &mut Frame, view: &AlbumsView, area: Rect, theme: &Theme) {
+    let album_name = view.selected().map(|e| e.album.name.as_str()).unwrap_or_default();
+    let (text, style) = match &view.prompt {
+        Some(AlbumPrompt::NewAlbum(input)) => (format!(" New album name: {}_", input), theme.highlight),
+        Some(AlbumPrompt::ConfirmDelete) => (
+            format!(" Delete album \"{}\"? Photos stay on disk. (y/n)", album_name),
+            theme.error,
+        ),
+        Some(AlbumPrompt::SmartTags(input)) => (
+            format!(" Tags every photo must carry (comma separated): {}_", input),
+            theme.highlight,
+        ),
+        Some(AlbumPrompt::GeoRadius(input)) => (
+            format!(" Photos taken within lat, lon, km (empty removes the area): {}_", input),
+            theme.highlight,
+        ),
+        Some(AlbumPrompt::ExportDir(input)) => (format!(" Export \"{}\" to: {}_", album_name, input), theme.highlight),
+        Some(AlbumPrompt::ExportPattern { input, .. }) => (
+            format!(" Rename pattern, e.g. {{album}}_{{seq:04}}.{{ext}} (empty keeps names): {}_", input),
+            theme.highlight,
+        ),
+        None => (
+            " hjkl/arrows:navigate  Enter:open  n:new  d:delete  e:smart tags  r:area  o:sort  x:export  q/Esc:close".to_string(),
+            theme.muted,
+        ),
+    };
+
+    let footer = Paragraph::new(text)
+        .style(Style::default().fg(style))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(footer, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i64, cover: Option<&str>) -> AlbumEntry {
+        AlbumEntry {
+            album: Album {
+                id,
+                name: format!("Album {}", id),
+                description: None,
+                cover_photo_id: None,
+                is_smart: false,
+                filter_tags: Vec::new(),
+                geo_radius: None,
+                photo_count: 0,
+                auto_sort: AlbumSort::Manual,
+            },
+            cover: cover.map(PathBuf::from),
+            count: 0,
+        }
+    }
+
+    #[test]
+    fn test_cursor_follows_album_changes() {
+        let entries = vec![entry(1, Some("/a.jpg")), entry(2, None), entry(3, Some("/c.jpg"))];
+        let mut view = AlbumsView::new(PathBuf::from("/"), entries, ImageProtocol::None);
+        assert_eq!(view.grid.images[1], PathBuf::new());
+
+        view.select_album(3);
+        assert_eq!(view.selected().unwrap().album.id, 3);
+
+        // Deleting the last album moves the cursor back onto one that exists
+        view.set_entries(vec![entry(1, Some("/a.jpg")), entry(2, None)]);
+        assert_eq!(view.selected().unwrap().album.id, 2);
+        assert_eq!(view.grid.images.len(), 2);
+    }
+}
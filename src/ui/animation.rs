@@ -0,0 +1,222 @@
+//! Animated GIF and WebP playback for the preview pane and slideshow.
+
+use image::codecs::gif::GifDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{imageops::FilterType, AnimationDecoder, DynamicImage, Frame};
+use ratatui_image::protocol::StatefulProtocol;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Upper bound on the decoded RGBA frames of one animation, to keep
+/// memory in check for long or large clips. Playback loops over the frames
+/// that fit.
+const MAX_ANIMATION_BYTES: usize = 128 * 1024 * 1024;
+
+/// Browsers treat delays this short as "unspecified" and slow them down.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Decoded frames of an animation, before conversion to terminal protocols.
+pub struct DecodedAnimation {
+    /// Short format label for the UI ("GIF" or "WebP")
+    pub label: &'static str,
+    pub frames: Vec<(DynamicImage, Duration)>,
+}
+
+/// Decode the frames of an animated GIF or WebP, resized to fit `max_size`
+/// and rotated, up to `MAX_ANIMATION_BYTES`. Each frame is scaled down as
+/// it is decoded. Returns None for other formats and single-frame images,
+/// which are loaded through the normal still-image path.
+pub fn decode(path: &Path, max_size: u32, rotation: i32) -> Option<DecodedAnimation> {
+    decode_within(path, max_size, rotation, MAX_ANIMATION_BYTES)
+}
+
+fn decode_within(path: &Path, max_size: u32, rotation: i32, max_bytes: usize) -> Option<DecodedAnimation> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    let reader = BufReader::new(File::open(path).ok()?);
+
+    let (label, frames) = match ext.as_str() {
+        "gif" => ("GIF", GifDecoder::new(reader).ok()?.into_frames()),
+        "webp" => {
+            let decoder = WebPDecoder::new(reader).ok()?;
+            if !decoder.has_animation() {
+                return None;
+            }
+            ("WebP", decoder.into_frames())
+        }
+        _ => return None,
+    };
+
+    let mut decoded = Vec::new();
+    let mut bytes = 0;
+    for frame in frames {
+        let (img, delay) = prepare_frame(frame.ok()?, max_size, rotation);
+        bytes += frame_bytes(&img);
+        if bytes > max_bytes && !decoded.is_empty() {
+            break;
+        }
+        decoded.push((img, delay));
+    }
+    if decoded.len() < 2 {
+        return None;
+    }
+
+    Some(DecodedAnimation { label, frames: decoded })
+}
+
+fn prepare_frame(frame: Frame, max_size: u32, rotation: i32) -> (DynamicImage, Duration) {
+    let delay = frame_delay(frame.delay().into());
+    let mut img = DynamicImage::ImageRgba8(frame.into_buffer());
+    // Animations are usually small; only ever scale down
+    if img.width() > max_size || img.height() > max_size {
+        img = img.resize(max_size, max_size, FilterType::Triangle);
+    }
+    let img = match rotation {
+        90 => img.rotate90(),
+        180 => img.rotate180(),
+        270 => img.rotate270(),
+        _ => img,
+    };
+    (img, delay)
+}
+
+fn frame_bytes(img: &DynamicImage) -> usize {
+    img.width() as usize * img.height() as usize * 4
+}
+
+fn frame_delay(delay: Duration) -> Duration {
+    if delay < MIN_FRAME_DELAY {
+        DEFAULT_FRAME_DELAY
+    } else {
+        delay
+    }
+}
+
+/// Frame timing for a playing animation.
+#[derive(Debug, Clone)]
+pub struct FrameClock {
+    delays: Vec<Duration>,
+    /// Index of the frame currently shown
+    pub frame_index: usize,
+    /// Set once the last frame has been shown for its full delay
+    pub completed: bool,
+    frame_started: Instant,
+}
+
+impl FrameClock {
+    pub fn new(delays: Vec<Duration>) -> Self {
+        Self {
+            delays,
+            frame_index: 0,
+            completed: false,
+            frame_started: Instant::now(),
+        }
+    }
+
+    /// Start again from the first frame.
+    pub fn restart(&mut self) {
+        self.frame_index = 0;
+        self.completed = false;
+        self.frame_started = Instant::now();
+    }
+
+    /// Move to the next frame if the current one has been shown long
+    /// enough. Returns true when the frame changed.
+    pub fn tick(&mut self) -> bool {
+        self.advance(self.frame_started.elapsed())
+    }
+
+    fn advance(&mut self, elapsed: Duration) -> bool {
+        let Some(&delay) = self.delays.get(self.frame_index) else {
+            return false;
+        };
+        if elapsed < delay {
+            return false;
+        }
+        self.frame_index += 1;
+        if self.frame_index >= self.delays.len() {
+            self.frame_index = 0;
+            self.completed = true;
+        }
+        self.frame_started = Instant::now();
+        true
+    }
+}
+
+/// An animation converted for display, with its playback position.
+pub struct Animation {
+    pub label: &'static str,
+    pub frames: Vec<StatefulProtocol>,
+    pub clock: FrameClock,
+}
+
+impl Animation {
+    pub fn current_frame(&mut self) -> Option<&mut StatefulProtocol> {
+        self.frames.get_mut(self.clock.frame_index)
+    }
+
+    /// Title indicator such as `[GIF 12 frames]`.
+    pub fn indicator(&self) -> String {
+        format!("[{} {} frames]", self.label, self.frames.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_delay_floor() {
+        assert_eq!(frame_delay(Duration::ZERO), DEFAULT_FRAME_DELAY);
+        assert_eq!(frame_delay(Duration::from_millis(10)), DEFAULT_FRAME_DELAY);
+        assert_eq!(frame_delay(Duration::from_millis(40)), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_decode_stays_within_budget() {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, RgbaImage};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("long.gif");
+        let mut encoder = GifEncoder::new(File::create(&path).unwrap());
+        let frames = (0..10u8).map(|i| {
+            let img = RgbaImage::from_pixel(64, 64, image::Rgba([i * 20, 0, 0, 255]));
+            Frame::from_parts(img, 0, 0, Delay::from_numer_denom_ms(50, 1))
+        });
+        encoder.encode_frames(frames).unwrap();
+        drop(encoder);
+
+        // Each 64x64 frame is 16 KiB
+        let decoded = decode_within(&path, 64, 0, 100 * 1024).unwrap();
+        assert_eq!(decoded.frames.len(), 6);
+        assert_eq!(decoded.frames[0].1, Duration::from_millis(50));
+
+        // Frames are budgeted at the size they are kept at
+        let small = decode_within(&path, 32, 0, 100 * 1024).unwrap();
+        assert_eq!(small.frames.len(), 10);
+        assert_eq!((small.frames[0].0.width(), small.frames[0].0.height()), (32, 32));
+    }
+
+    #[test]
+    fn test_clock_wraps_and_completes() {
+        let ms = Duration::from_millis;
+        let mut clock = FrameClock::new(vec![ms(50), ms(100)]);
+
+        assert!(!clock.advance(ms(30)));
+        assert!(clock.advance(ms(50)));
+        assert_eq!(clock.frame_index, 1);
+        assert!(!clock.completed);
+
+        assert!(!clock.advance(ms(60)));
+        assert!(clock.advance(ms(100)));
+        assert_eq!(clock.frame_index, 0);
+        assert!(clock.completed);
+
+        clock.restart();
+        assert_eq!(clock.frame_index, 0);
+        assert!(!clock.completed);
+    }
+}
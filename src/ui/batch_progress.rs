@@ -0,0 +1,82 @@
+//! Progress dialog for a batch move or centralise, dismissed as soon as the
+//! background task finishes.
+
+use std::sync::mpsc;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+};
+
+use crate::tasks::{TaskId, TaskProgress};
+use crate::transfer::BatchOutcome;
+use crate::ui::theme::Theme;
+
+/// A batch file operation the browser is waiting for
+pub struct BatchProgressDialog {
+    pub task_id: TaskId,
+    /// Verb for the message, e.g. "Moving"
+    pub verb: &'static str,
+    pub total: usize,
+    /// Receives the outcome just before the task reports completion
+    pub outcome: mpsc::Receiver<BatchOutcome>,
+}
+
+pub fn render(frame: &mut Frame, dialog: &BatchProgressDialog, progress: Option<&TaskProgress>, area: Rect, theme: &Theme) {
+    let dialog_width = 60.min(area.width.saturating_sub(4));
+    let dialog_height = 10.min(area.height);
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .title(format!(" {} ", dialog.verb));
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Message
+            Constraint::Length(1), // Gauge
+            Constraint::Length(1), // Current file
+            Constraint::Length(1), // Bytes transferred
+            Constraint::Min(0),
+            Constraint::Length(1), // Button
+        ])
+        .margin(1)
+        .split(dialog_area);
+
+    let current = progress.map(|p| p.current).unwrap_or(0);
+    let message = Paragraph::new(format!("{} {}/{} files…", dialog.verb, current, dialog.total))
+        .alignment(Alignment::Center);
+    frame.render_widget(message, chunks[0]);
+
+    let ratio = if dialog.total > 0 { current as f64 / dialog.total as f64 } else { 0.0 };
+    let gauge = Gauge::default()
+        .ratio(ratio.min(1.0))
+        .gauge_style(Style::default().fg(theme.success).bg(theme.muted));
+    frame.render_widget(gauge, chunks[1]);
+
+    if let Some(item) = progress.and_then(|p| p.current_item.as_deref()) {
+        let item = Paragraph::new(item).alignment(Alignment::Center);
+        frame.render_widget(item, chunks[2]);
+    }
+    if let Some(bytes) = progress.and_then(|p| p.message.as_deref()) {
+        let bytes = Paragraph::new(bytes)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(bytes, chunks[3]);
+    }
+
+    let button = Line::from(vec![
+        Span::styled("[Esc] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+        Span::raw("Cancel"),
+    ]);
+    frame.render_widget(Paragraph::new(button).alignment(Alignment::Center), chunks[5]);
+}
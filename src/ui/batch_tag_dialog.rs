@@ -0,0 +1,251 @@
+//! Dialog for adding and removing tags across several selected photos.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::db::UserTag;
+use crate::ui::theme::Theme;
+
+/// What confirming the dialog does with a tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchTagChange {
+    /// Leave every photo as it is
+    Keep,
+    /// Add the tag to the photos that lack it
+    AddToAll,
+    /// Remove the tag from the photos that have it
+    RemoveFromAll,
+}
+
+/// One tag carried by at least one selected photo
+#[derive(Debug, Clone)]
+pub struct BatchTagEntry {
+    pub tag: UserTag,
+    /// Number of selected photos carrying the tag
+    pub photo_count: usize,
+    pub change: BatchTagChange,
+}
+
+/// State for the batch tag dialog
+pub struct BatchTagDialog {
+    /// Database IDs of the selected photos
+    pub photo_ids: Vec<i64>,
+    /// Union of the selected photos' tags, sorted by name
+    pub entries: Vec<BatchTagEntry>,
+    pub selected_index: usize,
+}
+
+impl BatchTagDialog {
+    /// Build the dialog from each selected photo's ID and tags.
+    pub fn new(photos: Vec<(i64, Vec<UserTag>)>) -> Self {
+        let mut entries: Vec<BatchTagEntry> = Vec::new();
+        for (_, tags) in &photos {
+            for tag in tags {
+                match entries.iter_mut().find(|e| e.tag.id == tag.id) {
+                    Some(entry) => entry.photo_count += 1,
+                    None => entries.push(BatchTagEntry {
+                        tag: tag.clone(),
+                        photo_count: 1,
+                        change: BatchTagChange::Keep,
+                    }),
+                }
+            }
+        }
+        entries.sort_by_key(|e| e.tag.name.to_lowercase());
+
+        Self {
+            photo_ids: photos.into_iter().map(|(id, _)| id).collect(),
+            entries,
+            selected_index: 0,
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected_index + 1 < self.entries.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    /// Whether the tag is on every selected photo
+    fn on_all(&self, entry: &BatchTagEntry) -> bool {
+        entry.photo_count == self.photo_ids.len()
+    }
+
+    /// Toggle the checkbox of the selected tag. A tag on only some photos
+    /// cycles through add to all, remove from all, and back to unchanged.
+    pub fn toggle_selected(&mut self) {
+        let total = self.photo_ids.len();
+        if let Some(entry) = self.entries.get_mut(self.selected_index) {
+            entry.change = match entry.change {
+                BatchTagChange::Keep if entry.photo_count == total => BatchTagChange::RemoveFromAll,
+                BatchTagChange::Keep => BatchTagChange::AddToAll,
+                BatchTagChange::AddToAll => BatchTagChange::RemoveFromAll,
+                BatchTagChange::RemoveFromAll => BatchTagChange::Keep,
+            };
+        }
+    }
+
+    /// Checkbox for an entry: checked, unchecked, or partial when the tag
+    /// stays on only some photos.
+    pub fn checkbox(&self, entry: &BatchTagEntry) -> &'static str {
+        match entry.change {
+            BatchTagChange::AddToAll => "[x]",
+            BatchTagChange::RemoveFromAll => "[ ]",
+            BatchTagChange::Keep if self.on_all(entry) => "[x]",
+            BatchTagChange::Keep => "[~]",
+        }
+    }
+
+    /// `+` will add, `-` will remove, `=` already on all photos
+    pub fn indicator(&self, entry: &BatchTagEntry) -> char {
+        match entry.change {
+            BatchTagChange::AddToAll => '+',
+            BatchTagChange::RemoveFromAll => '-',
+            BatchTagChange::Keep if self.on_all(entry) => '=',
+            BatchTagChange::Keep => ' ',
+        }
+    }
+
+    /// Tags that confirming would change
+    pub fn pending_changes(&self) -> Vec<(UserTag, BatchTagChange)> {
+        self.entries
+            .iter()
+            .filter(|e| e.change != BatchTagChange::Keep)
+            .map(|e| (e.tag.clone(), e.change))
+            .collect()
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &BatchTagDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 50.min(area.width.saturating_sub(4));
+    let dialog_height = 20.min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let title = format!(" Batch Edit Tags: {} photos ", dialog.photo_ids.len());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(title);
+    frame.render_widget(block, dialog_area);
+
+    let inner = Rect::new(
+        dialog_area.x + 1,
+        dialog_area.y + 1,
+        dialog_area.width.saturating_sub(2),
+        dialog_area.height.saturating_sub(2),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(8),    // Tag list
+            Constraint::Length(2), // Help
+        ])
+        .split(inner);
+
+    if dialog.entries.is_empty() {
+        let empty = Paragraph::new("Selected photos have no tags")
+            .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC))
+            .block(Block::default().borders(Borders::ALL).title(" Tags "));
+        frame.render_widget(empty, chunks[0]);
+    } else {
+        let total = dialog.photo_ids.len();
+        let items: Vec<ListItem> = dialog
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let style = match (i == dialog.selected_index, entry.change) {
+                    (true, _) => Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                    (false, BatchTagChange::AddToAll) => Style::default().fg(theme.success),
+                    (false, BatchTagChange::RemoveFromAll) => Style::default().fg(theme.error),
+                    (false, BatchTagChange::Keep) => Style::default().fg(theme.fg),
+                };
+                ListItem::new(format!(
+                    " {} {} {} ({}/{})",
+                    dialog.indicator(entry),
+                    dialog.checkbox(entry),
+                    entry.tag.name,
+                    entry.photo_count,
+                    total
+                ))
+                .style(style)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Tags "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let mut state = ListState::default();
+        state.select(Some(dialog.selected_index));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+    }
+
+    let help = Paragraph::new("j/k:navigate | Space:toggle | Enter:apply | Esc:cancel")
+        .style(Style::default().fg(theme.muted))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(id: i64, name: &str) -> UserTag {
+        UserTag { id, name: name.to_string(), color: "#808080".to_string() }
+    }
+
+    fn dialog() -> BatchTagDialog {
+        BatchTagDialog::new(vec![
+            (1, vec![tag(10, "beach"), tag(20, "family")]),
+            (2, vec![tag(10, "beach")]),
+        ])
+    }
+
+    #[test]
+    fn test_lists_the_union_of_tags() {
+        let dialog = dialog();
+        assert_eq!(dialog.photo_ids, vec![1, 2]);
+        let summary: Vec<(&str, usize, char)> = dialog
+            .entries
+            .iter()
+            .map(|e| (e.tag.name.as_str(), e.photo_count, dialog.indicator(e)))
+            .collect();
+        assert_eq!(summary, vec![("beach", 2, '='), ("family", 1, ' ')]);
+        assert!(dialog.pending_changes().is_empty());
+    }
+
+    #[test]
+    fn test_toggling_cycles_changes() {
+        let mut dialog = dialog();
+
+        // On every photo: unchecking removes, checking again keeps
+        dialog.toggle_selected();
+        assert_eq!(dialog.indicator(&dialog.entries[0]), '-');
+        dialog.toggle_selected();
+        assert_eq!(dialog.indicator(&dialog.entries[0]), '=');
+
+        // On some photos: add to all, remove from all, unchanged
+        dialog.move_down();
+        dialog.toggle_selected();
+        assert_eq!(dialog.indicator(&dialog.entries[1]), '+');
+        dialog.toggle_selected();
+        assert_eq!(dialog.indicator(&dialog.entries[1]), '-');
+        assert_eq!(dialog.pending_changes()[0].1, BatchTagChange::RemoveFromAll);
+        dialog.toggle_selected();
+        assert_eq!(dialog.checkbox(&dialog.entries[1]), "[~]");
+    }
+}
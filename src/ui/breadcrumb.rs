@@ -0,0 +1,164 @@
+//! Breadcrumb bar showing the current directory's ancestors.
+
+use ratatui::{prelude::*, widgets::Paragraph};
+use std::path::{Component, Path, PathBuf};
+
+use crate::app::App;
+
+const SEPARATOR: &str = " > ";
+/// Shown in place of leading segments that do not fit.
+const ELLIPSIS: &str = "… > ";
+
+/// One clickable path segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crumb {
+    pub label: String,
+    pub path: PathBuf,
+}
+
+/// Split `path` into segments, collapsing the home directory to "Home".
+pub fn crumbs(path: &Path, home: Option<&Path>) -> Vec<Crumb> {
+    let mut crumbs = Vec::new();
+    let mut current = PathBuf::new();
+
+    let rest = match home.and_then(|h| path.strip_prefix(h).ok().map(|rest| (h, rest))) {
+        Some((home, rest)) => {
+            current = home.to_path_buf();
+            crumbs.push(Crumb {
+                label: "Home".to_string(),
+                path: current.clone(),
+            });
+            rest
+        }
+        None => path,
+    };
+
+    for component in rest.components() {
+        current.push(component);
+        let label = match component {
+            Component::RootDir => "/".to_string(),
+            Component::Prefix(p) => p.as_os_str().to_string_lossy().to_string(),
+            other => other.as_os_str().to_string_lossy().to_string(),
+        };
+        crumbs.push(Crumb {
+            label,
+            path: current.clone(),
+        });
+    }
+
+    crumbs
+}
+
+/// Horizontal position of each visible crumb as (index, x offset, width).
+/// When the path is too wide, leading segments are dropped so the current
+/// directory stays visible.
+pub fn layout(crumbs: &[Crumb], width: u16) -> Vec<(usize, u16, u16)> {
+    let label_width = |c: &Crumb| c.label.chars().count() as u16;
+    let total = |from: usize| -> u16 {
+        crumbs[from..].iter().map(label_width).sum::<u16>()
+            + SEPARATOR.len() as u16 * (crumbs.len() - from).saturating_sub(1) as u16
+    };
+
+    let prefix = |from: usize| if from > 0 { ELLIPSIS.chars().count() as u16 } else { 0 };
+
+    let mut first = 0;
+    while first + 1 < crumbs.len() && prefix(first) + total(first) > width {
+        first += 1;
+    }
+
+    let mut x = prefix(first);
+    let mut positions = Vec::new();
+    for (index, crumb) in crumbs.iter().enumerate().skip(first) {
+        let w = label_width(crumb);
+        positions.push((index, x, w));
+        x += w + SEPARATOR.len() as u16;
+    }
+    positions
+}
+
+/// Index of the crumb under column `x` (relative to the bar's left edge).
+pub fn hit_test(crumbs: &[Crumb], width: u16, x: u16) -> Option<usize> {
+    layout(crumbs, width)
+        .into_iter()
+        .find(|&(_, start, w)| x >= start && x < start + w)
+        .map(|(index, _, _)| index)
+}
+
+/// Crumbs for the app's current directory.
+pub fn current_crumbs(app: &App) -> Vec<Crumb> {
+    crumbs(&app.current_dir, dirs::home_dir().as_deref())
+}
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let crumbs = current_crumbs(app);
+    let positions = layout(&crumbs, area.width);
+    let last = crumbs.len().saturating_sub(1);
+
+    let separator_style = Style::default().fg(theme.muted);
+    let mut spans = Vec::new();
+    if positions.first().is_some_and(|&(index, _, _)| index > 0) {
+        spans.push(Span::styled(ELLIPSIS, separator_style));
+    }
+
+    for (n, &(index, _, _)) in positions.iter().enumerate() {
+        if n > 0 {
+            spans.push(Span::styled(SEPARATOR, separator_style));
+        }
+        let style = if app.breadcrumb_cursor == Some(index) {
+            Style::default().fg(theme.selected_fg).bg(theme.selected_bg)
+        } else if app.breadcrumb_hover == Some(index) {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::UNDERLINED)
+        } else if index == last {
+            Style::default().fg(theme.directory_color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.directory_color)
+        };
+        spans.push(Span::styled(crumbs[index].label.clone(), style));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(crumbs: &[Crumb]) -> Vec<&str> {
+        crumbs.iter().map(|c| c.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_crumbs_under_home() {
+        let crumbs = crumbs(
+            Path::new("/home/ana/photos/2024/March"),
+            Some(Path::new("/home/ana")),
+        );
+        assert_eq!(labels(&crumbs), ["Home", "photos", "2024", "March"]);
+        assert_eq!(crumbs[0].path, PathBuf::from("/home/ana"));
+        assert_eq!(crumbs[2].path, PathBuf::from("/home/ana/photos/2024"));
+    }
+
+    #[test]
+    fn test_crumbs_outside_home() {
+        let crumbs = crumbs(Path::new("/mnt/nas"), Some(Path::new("/home/ana")));
+        assert_eq!(labels(&crumbs), ["/", "mnt", "nas"]);
+        assert_eq!(crumbs[0].path, PathBuf::from("/"));
+    }
+
+    #[test]
+    fn test_layout_and_hit_test() {
+        let crumbs = crumbs(Path::new("/home/ana/photos/2024"), Some(Path::new("/home/ana")));
+        // "Home > photos > 2024"
+        assert_eq!(layout(&crumbs, 80), [(0, 0, 4), (1, 7, 6), (2, 16, 4)]);
+        assert_eq!(hit_test(&crumbs, 80, 2), Some(0));
+        assert_eq!(hit_test(&crumbs, 80, 5), None);
+        assert_eq!(hit_test(&crumbs, 80, 18), Some(2));
+
+        // Too narrow: leading segments are replaced by an ellipsis
+        assert_eq!(layout(&crumbs, 17), [(1, 4, 6), (2, 13, 4)]);
+        assert_eq!(layout(&crumbs, 10), [(2, 4, 4)]);
+    }
+}
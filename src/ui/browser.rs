@@ -1,11 +1,14 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 
-use crate::app::{App, AppMode, DirEntry};
+use crate::app::{App, AppMode, DirEntry, DragState};
+use crate::ui::theme::Theme;
+use crate::watch::WatchChange;
 
 pub fn render_parent(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let title = app
         .current_dir
         .parent()
@@ -16,17 +19,17 @@ pub fn render_parent(frame: &mut Frame, app: &App, area: Rect) {
     let items: Vec<ListItem> = app
         .parent_entries
         .iter()
-        .map(|entry| entry_to_list_item(entry, false, false))
+        .map(|entry| entry_to_list_item(entry, false, false, dir_size(app, entry), None, &[], theme))
         .collect();
 
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.muted))
                 .title(title),
         )
-        .highlight_style(Style::default().bg(Color::DarkGray));
+        .highlight_style(Style::default().bg(theme.marked_bg));
 
     let mut state = ListState::default();
     state.select(Some(app.parent_selected_index));
@@ -35,6 +38,7 @@ pub fn render_parent(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 pub fn render_current(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let title = app
         .current_dir
         .file_name()
@@ -53,28 +57,40 @@ pub fn render_current(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .map(|entry| {
             let is_selected = app.is_selected(&entry.path);
-            entry_to_list_item(entry, true, is_selected)
+            let change = app.watch_changes.get(&entry.path).copied();
+            let matched = app.quick_filter.as_ref().map(|f| f.matched_indices(entry)).unwrap_or(&[]);
+            entry_to_list_item(entry, true, is_selected, dir_size(app, entry), change, matched, theme)
         })
         .collect();
 
-    // Visual mode has a different border color
+    // Visual and watch mode have a different border color
     let border_color = if app.mode == AppMode::Visual {
-        Color::Magenta
+        theme.emphasis
+    } else if app.mode == AppMode::Watching {
+        theme.accent
     } else {
-        Color::Blue
+        theme.border_active
     };
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(title);
+    // The quick filter's query is typed into the bottom border
+    if let Some(ref filter) = app.quick_filter {
+        block = block.title_bottom(Line::from(vec![
+            Span::styled(" /", Style::default().fg(theme.accent)),
+            Span::raw(filter.query.clone()),
+            Span::styled("█ ", Style::default().fg(theme.accent)),
+        ]));
+    }
+
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color))
-                .title(title),
-        )
+        .block(block)
         .highlight_style(
             Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
+                .bg(theme.selected_bg)
+                .fg(theme.selected_fg)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -84,32 +100,106 @@ pub fn render_current(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
-fn entry_to_list_item(entry: &DirEntry, show_size: bool, is_selected: bool) -> ListItem<'static> {
+/// Floating label next to the mouse cursor while files are dragged.
+pub fn render_drag(frame: &mut Frame, drag: &DragState, area: Rect, theme: &Theme) {
+    let count = drag.files.len();
+    let files = if count == 1 { "file" } else { "files" };
+    let text = match drag.target {
+        Some(ref target) => format!(
+            " Move {} {} to {} ",
+            count,
+            files,
+            target.file_name().map(|n| n.to_string_lossy()).unwrap_or_else(|| target.to_string_lossy())
+        ),
+        None => format!(" Dragging {} {} ", count, files),
+    };
+
+    let width = (text.chars().count() as u16).min(area.width);
+    let (x, y) = drag.position;
+    // Just right of the cursor, kept on screen
+    let x = (x + 2).min(area.right().saturating_sub(width));
+    let y = y.min(area.bottom().saturating_sub(1));
+    let label_area = Rect::new(x, y, width, 1);
+
+    let style = if drag.target.is_some() {
+        Style::default().fg(theme.selected_fg).bg(theme.selected_bg).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.fg).bg(theme.marked_bg)
+    };
+    frame.render_widget(Clear, label_area);
+    frame.render_widget(Paragraph::new(text).style(style), label_area);
+}
+
+/// Recursive size of a directory entry, once computed.
+fn dir_size(app: &App, entry: &DirEntry) -> Option<u64> {
+    if entry.is_dir {
+        app.dir_size_cache.get(&entry.path).copied()
+    } else {
+        None
+    }
+}
+
+fn entry_to_list_item(
+    entry: &DirEntry,
+    show_size: bool,
+    is_selected: bool,
+    dir_size: Option<u64>,
+    change: Option<WatchChange>,
+    matched: &[usize],
+    theme: &Theme,
+) -> ListItem<'static> {
     // Selection indicator
     let select_marker = if is_selected { "* " } else { "  " };
-    let icon = if entry.is_dir { "/" } else { " " };
-    let name = entry.name.clone();
-
-    let text = if show_size && !entry.is_dir {
-        format!("{}{}{} {}", select_marker, icon, name, format_size(entry.size))
+    let icon = if entry.is_dir {
+        "/"
+    } else if change == Some(WatchChange::Modified) {
+        "~"
     } else {
-        format!("{}{}{}", select_marker, icon, name)
+        " "
+    };
+    let suffix = if let Some(size) = dir_size {
+        format!(" ({})", format_size(size))
+    } else if show_size && !entry.is_dir {
+        format!(" {}", format_size(entry.size))
+    } else {
+        String::new()
     };
 
     let mut style = if entry.is_dir {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme.directory_color)
     } else if is_image(&entry.name) {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.image_color)
     } else {
         Style::default()
     };
 
     // Selected files get a different background
     if is_selected {
-        style = style.bg(Color::DarkGray);
+        style = style.bg(theme.marked_bg);
+    }
+
+    // Files deleted while watching stay listed, struck through
+    if change == Some(WatchChange::Removed) {
+        style = style.fg(theme.muted).add_modifier(Modifier::CROSSED_OUT);
+    }
+
+    let mut spans = vec![Span::raw(format!("{}{}", select_marker, icon))];
+    if matched.is_empty() {
+        spans.push(Span::raw(entry.name.clone()));
+    } else {
+        // Letters matched by the quick filter stand out
+        let highlight = Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        for (i, c) in entry.name.chars().enumerate() {
+            if matched.contains(&i) {
+                spans.push(Span::styled(c.to_string(), highlight));
+            } else {
+                spans.push(Span::raw(c.to_string()));
+            }
+        }
     }
+    spans.push(Span::raw(suffix));
 
-    ListItem::new(text).style(style)
+    ListItem::new(Line::from(spans)).style(style)
 }
 
 fn format_size(size: u64) -> String {
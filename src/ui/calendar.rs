@@ -0,0 +1,213 @@
+//! Calendar heat-map: one cell per day of a year, shaded by photos taken.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::theme::Theme;
+
+const DAY_LABELS: [&str; 7] = ["Mon", "", "Wed", "", "Fri", "", "Sun"];
+/// Width of the weekday labels to the left of the grid
+const LABEL_WIDTH: u16 = 4;
+/// Each week takes a cell and a gap
+const CELL_WIDTH: u16 = 2;
+
+/// State for the calendar heat-map.
+pub struct CalendarView {
+    pub year: i32,
+    /// Day under the cursor, always within `year`
+    pub selected: NaiveDate,
+    /// Photos taken per day of `year`
+    pub counts: HashMap<NaiveDate, usize>,
+}
+
+impl CalendarView {
+    pub fn new(selected: NaiveDate, counts: HashMap<NaiveDate, usize>) -> Self {
+        Self {
+            year: selected.year(),
+            selected,
+            counts,
+        }
+    }
+
+    /// Switch to another year, keeping the cursor on the same month and day.
+    pub fn set_year(&mut self, year: i32, counts: HashMap<NaiveDate, usize>) {
+        self.selected = NaiveDate::from_ymd_opt(year, self.selected.month(), self.selected.day())
+            .or_else(|| NaiveDate::from_ymd_opt(year, self.selected.month(), 28))
+            .unwrap_or(self.selected);
+        self.year = year;
+        self.counts = counts;
+    }
+
+    /// Move the cursor by `days`, stopping at the ends of the year.
+    pub fn move_days(&mut self, days: i64) {
+        let target = self.selected + Duration::days(days);
+        let (first, last) = year_bounds(self.year);
+        self.selected = target.clamp(first, last);
+    }
+
+    pub fn count(&self, date: NaiveDate) -> usize {
+        self.counts.get(&date).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// Block character for a day with `count` photos.
+pub fn shade(count: usize) -> char {
+    match count {
+        0 => ' ',
+        1..=5 => '░',
+        6..=20 => '▒',
+        21..=100 => '▓',
+        _ => '█',
+    }
+}
+
+/// Grid column of a date: weeks run Monday to Sunday, and the week holding
+/// 1 January is column 0.
+pub fn week_column(date: NaiveDate) -> usize {
+    let (first, _) = year_bounds(date.year());
+    (date.ordinal0() + first.weekday().num_days_from_monday()) as usize / 7
+}
+
+fn year_bounds(year: i32) -> (NaiveDate, NaiveDate) {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+    let last = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or_default();
+    (first, last)
+}
+
+pub fn render(frame: &mut Frame, view: &CalendarView, area: Rect, theme: &Theme) {
+    let (first, last) = year_bounds(view.year);
+    let columns = week_column(last) + 1;
+
+    let dialog_width = (LABEL_WIDTH + columns as u16 * CELL_WIDTH + 2).min(area.width.saturating_sub(4));
+    let dialog_height = 14.min(area.height);
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Calendar {} ({} photos) ", view.year, view.total()));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(7),
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ])
+        .split(inner);
+
+    // Month names above the week their first day falls in
+    let mut header = vec![' '; (LABEL_WIDTH + columns as u16 * CELL_WIDTH) as usize];
+    let mut next_free = 0;
+    for month in 1..=12 {
+        let Some(start) = NaiveDate::from_ymd_opt(view.year, month, 1) else { continue };
+        let pos = (LABEL_WIDTH as usize) + week_column(start) * CELL_WIDTH as usize;
+        if pos < next_free {
+            continue;
+        }
+        for (i, c) in start.format("%b").to_string().chars().enumerate() {
+            if let Some(slot) = header.get_mut(pos + i) {
+                *slot = c;
+            }
+        }
+        next_free = pos + 4;
+    }
+    frame.render_widget(
+        Paragraph::new(header.into_iter().collect::<String>()).style(Style::default().fg(theme.muted)),
+        chunks[0],
+    );
+
+    let offset = first.weekday().num_days_from_monday() as i64;
+    let cell_style = Style::default().fg(theme.success);
+    let selected_style = Style::default()
+        .bg(theme.selected_bg)
+        .fg(theme.selected_fg)
+        .add_modifier(Modifier::BOLD);
+    let rows: Vec<Line> = DAY_LABELS
+        .iter()
+        .enumerate()
+        .map(|(weekday, label)| {
+            let mut spans = vec![Span::styled(
+                format!("{:<width$}", label, width = LABEL_WIDTH as usize),
+                Style::default().fg(theme.muted),
+            )];
+            for column in 0..columns {
+                let date = first + Duration::days((column * 7 + weekday) as i64 - offset);
+                if date < first || date > last {
+                    spans.push(Span::raw("  "));
+                    continue;
+                }
+                let style = if date == view.selected { selected_style } else { cell_style };
+                spans.push(Span::styled(shade(view.count(date)).to_string(), style));
+                spans.push(Span::raw(" "));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(rows), chunks[1]);
+
+    let count = view.count(view.selected);
+    let summary = format!(
+        " {}: {} photo{}",
+        view.selected.format("%a %-d %b %Y"),
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    let legend = Line::from(vec![
+        Span::styled(" Less ", Style::default().fg(theme.muted)),
+        Span::styled("░ ▒ ▓ █", cell_style),
+        Span::styled(" More (1-5, 6-20, 21-100, 100+)", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(summary).style(Style::default().fg(theme.header_fg)), legend]),
+        chunks[2],
+    );
+
+    let help = Paragraph::new(" h/l=week  j/k=day  ←/→=year  Enter=open day in gallery  Esc=close")
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, chunks[4]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weeks_and_shades() {
+        // 2024 starts on a Monday and is a leap year
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        assert_eq!(week_column(date(1, 1)), 0);
+        assert_eq!(week_column(date(1, 7)), 0);
+        assert_eq!(week_column(date(1, 8)), 1);
+        assert_eq!(week_column(date(12, 31)), 52);
+
+        assert_eq!([0, 1, 5, 6, 20, 21, 100, 101].map(shade), [' ', '░', '░', '▒', '▒', '▓', '▓', '█']);
+
+        // The cursor stays in the year and survives a leap day
+        let mut view = CalendarView::new(date(2, 29), HashMap::new());
+        view.set_year(2023, HashMap::new());
+        assert_eq!(view.selected, NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+        view.move_days(-400);
+        assert_eq!(view.selected, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    }
+}
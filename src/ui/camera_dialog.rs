@@ -0,0 +1,101 @@
+//! Camera picker for filtering the browser by camera make and model.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::ui::theme::Theme;
+
+/// State for the camera picker dialog.
+pub struct CameraDialog {
+    /// Camera labels ("SONY ILCE-7M4") with their photo counts
+    pub cameras: Vec<(String, i64)>,
+    /// Selected row; row 0 is "All cameras"
+    pub selected_index: usize,
+}
+
+impl CameraDialog {
+    pub fn new(cameras: Vec<(String, i64)>, current: Option<&str>) -> Self {
+        let selected_index = current
+            .and_then(|c| cameras.iter().position(|(label, _)| label == c))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        Self {
+            cameras,
+            selected_index,
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected_index < self.cameras.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    /// The chosen camera, or None for "All cameras".
+    pub fn selected_camera(&self) -> Option<String> {
+        self.selected_index
+            .checked_sub(1)
+            .and_then(|i| self.cameras.get(i))
+            .map(|(label, _)| label.clone())
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &CameraDialog, area: Rect, theme: &Theme) {
+    let longest = dialog
+        .cameras
+        .iter()
+        .map(|(label, _)| label.chars().count() as u16 + 12)
+        .max()
+        .unwrap_or(0);
+    let dialog_width = longest.max(36).min(area.width.saturating_sub(4));
+    let dialog_height = (dialog.cameras.len() as u16 + 5).min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Filter by Camera ");
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+
+    let mut items = vec![ListItem::new(" All cameras")];
+    items.extend(dialog.cameras.iter().map(|(label, count)| {
+        ListItem::new(Line::from(vec![
+            Span::raw(format!(" {}", label)),
+            Span::styled(format!(" ({})", count), Style::default().fg(theme.muted)),
+        ]))
+    }));
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(theme.selected_bg)
+            .fg(theme.selected_fg)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(dialog.selected_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let help = Paragraph::new(" j/k=move  Enter=apply  Esc=cancel")
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, chunks[1]);
+}
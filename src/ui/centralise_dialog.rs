@@ -8,6 +8,7 @@ use std::path::PathBuf;
 
 use crate::centralise::{CentralisePreview, CentraliseResult, PlannedOperation};
 use crate::config::CentraliseOperation;
+use crate::ui::theme::Theme;
 
 /// Dialog state for file centralisation
 pub struct CentraliseDialog {
@@ -29,6 +30,8 @@ pub struct CentraliseDialog {
     pub source_files: Vec<PathBuf>,
     /// Error message if any
     pub error: Option<String>,
+    /// Executing only reports what would happen
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,6 +58,7 @@ impl CentraliseDialog {
             mode: CentraliseDialogMode::Configure,
             source_files,
             error: None,
+            dry_run: false,
         }
     }
 
@@ -104,7 +108,7 @@ impl CentraliseDialog {
 }
 
 /// Render the centralise dialog
-pub fn render(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect, theme: &Theme) {
     let dialog_width = 80.min(area.width.saturating_sub(4));
     let dialog_height = 30.min(area.height.saturating_sub(4));
 
@@ -116,17 +120,17 @@ pub fn render(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
     frame.render_widget(Clear, dialog_area);
 
     match dialog.mode {
-        CentraliseDialogMode::Configure => render_configure(frame, dialog, dialog_area),
-        CentraliseDialogMode::Preview => render_preview(frame, dialog, dialog_area),
-        CentraliseDialogMode::Executing => render_executing(frame, dialog, dialog_area),
-        CentraliseDialogMode::Results => render_results(frame, dialog, dialog_area),
+        CentraliseDialogMode::Configure => render_configure(frame, dialog, dialog_area, theme),
+        CentraliseDialogMode::Preview => render_preview(frame, dialog, dialog_area, theme),
+        CentraliseDialogMode::Executing => render_executing(frame, dialog, dialog_area, theme),
+        CentraliseDialogMode::Results => render_results(frame, dialog, dialog_area, theme),
     }
 }
 
-fn render_configure(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
+fn render_configure(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect, theme: &Theme) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(" Centralise Files ");
     frame.render_widget(block, area);
 
@@ -152,14 +156,14 @@ fn render_configure(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
 
     // Title
     let title = Paragraph::new("Organize photos into a managed library")
-        .style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
     frame.render_widget(title, chunks[0]);
 
     // Library path
     let lib_text = format!("Library: {}", dialog.library_path.display());
     let lib_para = Paragraph::new(lib_text)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(theme.highlight));
     frame.render_widget(lib_para, chunks[1]);
 
     // Operation mode
@@ -168,33 +172,33 @@ fn render_configure(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
         CentraliseOperation::Move => "[C] Operation: MOVE (removes originals)",
     };
     let op_para = Paragraph::new(op_text)
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(theme.accent));
     frame.render_widget(op_para, chunks[2]);
 
     // File count
     let count_text = format!("Files to process: {}", dialog.source_files.len());
     let count_para = Paragraph::new(count_text)
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(theme.fg));
     frame.render_widget(count_para, chunks[3]);
 
     // Error message
     if let Some(ref err) = dialog.error {
         let err_para = Paragraph::new(format!("Error: {}", err))
-            .style(Style::default().fg(Color::Red));
+            .style(Style::default().fg(theme.error));
         frame.render_widget(err_para, chunks[5]);
     }
 
     // Help text
     let help = Paragraph::new("Enter: Preview | c: Toggle Copy/Move | Esc: Cancel")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[6]);
 }
 
-fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
+fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect, theme: &Theme) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green))
+        .border_style(Style::default().fg(theme.success))
         .title(" Preview - Dry Run ");
     frame.render_widget(block, area);
 
@@ -229,7 +233,7 @@ fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
             preview.skipped.len()
         );
         let summary_para = Paragraph::new(summary)
-            .style(Style::default().fg(Color::Yellow));
+            .style(Style::default().fg(theme.highlight));
         frame.render_widget(summary_para, chunks[0]);
 
         // File list
@@ -241,9 +245,9 @@ fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
         // Operations
         for (i, op) in preview.operations.iter().enumerate() {
             let style = if i == dialog.selected_index {
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::Green)
+                Style::default().fg(theme.success)
             };
 
             let src_name = op.source.file_name()
@@ -261,9 +265,9 @@ fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
         for (i, (path, reason)) in preview.skipped.iter().enumerate() {
             let idx = preview.operations.len() + i;
             let style = if idx == dialog.selected_index {
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
 
             let name = path.file_name()
@@ -303,7 +307,7 @@ fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
                 op.destination.display()
             );
             let detail_para = Paragraph::new(detail)
-                .style(Style::default().fg(Color::White))
+                .style(Style::default().fg(theme.fg))
                 .block(Block::default().borders(Borders::TOP));
             frame.render_widget(detail_para, chunks[2]);
         }
@@ -311,15 +315,15 @@ fn render_preview(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
 
     // Help text
     let help = Paragraph::new("Enter: Execute | j/k: Navigate | Esc: Back")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[3]);
 }
 
-fn render_executing(frame: &mut Frame, _dialog: &CentraliseDialog, area: Rect) {
+fn render_executing(frame: &mut Frame, _dialog: &CentraliseDialog, area: Rect, theme: &Theme) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
+        .border_style(Style::default().fg(theme.highlight))
         .title(" Executing... ");
     frame.render_widget(block, area);
 
@@ -331,16 +335,16 @@ fn render_executing(frame: &mut Frame, _dialog: &CentraliseDialog, area: Rect) {
     );
 
     let text = Paragraph::new("Processing files...")
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(theme.highlight))
         .alignment(Alignment::Center);
     frame.render_widget(text, inner);
 }
 
-fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
+fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect, theme: &Theme) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(" Results ");
+        .border_style(Style::default().fg(theme.accent))
+        .title(if dialog.dry_run { " Results (dry run) " } else { " Results " });
     frame.render_widget(block, area);
 
     let inner = Rect::new(
@@ -361,16 +365,20 @@ fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
 
     if let Some(ref result) = dialog.result {
         // Summary
-        let summary = format!(
+        let mut summary = format!(
             "Succeeded: {} | Failed: {} | Skipped: {}",
             result.succeeded.len(),
             result.failed.len(),
             result.skipped.len()
         );
+        if let Some(conflicts) = result.conflict_summary() {
+            summary.push('\n');
+            summary.push_str(&conflicts);
+        }
         let color = if result.failed.is_empty() {
-            Color::Green
+            theme.success
         } else {
-            Color::Yellow
+            theme.highlight
         };
         let summary_para = Paragraph::new(summary)
             .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
@@ -383,7 +391,7 @@ fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
         if !result.failed.is_empty() {
             lines.push(Line::from(Span::styled(
                 "Failed:",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
             )));
             for (path, err) in &result.failed {
                 let name = path.file_name()
@@ -391,7 +399,7 @@ fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
                     .unwrap_or_default();
                 lines.push(Line::from(Span::styled(
                     format!("  {} - {}", name, err),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.error),
                 )));
             }
             lines.push(Line::from(""));
@@ -400,7 +408,7 @@ fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
         if !result.succeeded.is_empty() && result.succeeded.len() <= 10 {
             lines.push(Line::from(Span::styled(
                 "Succeeded:",
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.success),
             )));
             for op in &result.succeeded {
                 let name = op.destination.file_name()
@@ -408,7 +416,7 @@ fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
                     .unwrap_or_default();
                 lines.push(Line::from(Span::styled(
                     format!("  {}", name),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.success),
                 )));
             }
         }
@@ -420,7 +428,7 @@ fn render_results(frame: &mut Frame, dialog: &CentraliseDialog, area: Rect) {
 
     // Help text
     let help = Paragraph::new("Enter/Esc: Close")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[2]);
 }
@@ -7,7 +7,8 @@ use ratatui::{
 use std::collections::HashSet;
 use std::path::PathBuf;
 
-use crate::scanner::ChangeDetectionResult;
+use crate::scanner::{ChangeDetectionResult, FieldChange};
+use crate::ui::theme::Theme;
 
 /// Tab selection for the changes dialog.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,6 +27,15 @@ pub struct ChangesDialog {
     pub selected_index: usize,
     /// Selected files for rescanning.
     pub selected_files: HashSet<PathBuf>,
+    /// Stored vs current metadata of a modified file, shown in place of
+    /// the file list.
+    pub diff: Option<MetadataDiff>,
+}
+
+/// What a rescan would change in one modified file's record.
+pub struct MetadataDiff {
+    pub filename: String,
+    pub changes: Vec<FieldChange>,
 }
 
 impl ChangesDialog {
@@ -35,6 +45,15 @@ impl ChangesDialog {
             tab: ChangesTab::New,
             selected_index: 0,
             selected_files: HashSet::new(),
+            diff: None,
+        }
+    }
+
+    /// The highlighted file, when it is on the Modified tab.
+    pub fn selected_modified_file(&self) -> Option<&PathBuf> {
+        match self.tab {
+            ChangesTab::New => None,
+            ChangesTab::Modified => self.changes.modified_files.get(self.selected_index),
         }
     }
 
@@ -120,7 +139,7 @@ impl ChangesDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &ChangesDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &ChangesDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 70.min(area.width.saturating_sub(4));
     let dialog_height = 20.min(area.height.saturating_sub(4));
@@ -161,16 +180,46 @@ pub fn render(frame: &mut Frame, dialog: &ChangesDialog, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
+                .border_style(Style::default().fg(theme.highlight))
                 .title(" File Changes "),
         )
         .select(selected_tab)
-        .style(Style::default().fg(Color::White))
-        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        .style(Style::default().fg(theme.fg))
+        .highlight_style(Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD));
 
     frame.render_widget(tabs, chunks[0]);
 
-    // File list
+    if let Some(ref diff) = dialog.diff {
+        render_diff(frame, diff, chunks[1], theme);
+    } else {
+        render_files(frame, dialog, chunks[1], theme);
+    }
+
+    // Help text
+    let sel_count = dialog.selection_count();
+    let sel_text = if sel_count > 0 {
+        format!(" ({} selected)", sel_count)
+    } else {
+        String::new()
+    };
+
+    let help_text = if dialog.diff.is_some() {
+        format!(" Space=toggle{}  Esc=back", sel_text)
+    } else {
+        format!(
+            " Tab=switch  j/k=nav  Space=toggle  a=all  d=diff  Enter=rescan{}  q=close",
+            sel_text
+        )
+    };
+
+    let help = Paragraph::new(help_text)
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::TOP));
+
+    frame.render_widget(help, chunks[2]);
+}
+
+fn render_files(frame: &mut Frame, dialog: &ChangesDialog, area: Rect, theme: &Theme) {
     let files = match dialog.tab {
         ChangesTab::New => &dialog.changes.new_files,
         ChangesTab::Modified => &dialog.changes.modified_files,
@@ -178,9 +227,9 @@ pub fn render(frame: &mut Frame, dialog: &ChangesDialog, area: Rect) {
 
     if files.is_empty() {
         let empty_msg = Paragraph::new("  No files in this category")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(Block::default().borders(Borders::ALL));
-        frame.render_widget(empty_msg, chunks[1]);
+        frame.render_widget(empty_msg, area);
     } else {
         let items: Vec<ListItem> = files
             .iter()
@@ -195,9 +244,9 @@ pub fn render(frame: &mut Frame, dialog: &ChangesDialog, area: Rect) {
                 let marker = if selected { "[x]" } else { "[ ]" };
 
                 let style = if i == dialog.selected_index {
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                 } else if selected {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(theme.success)
                 } else {
                     Style::default()
                 };
@@ -217,25 +266,38 @@ pub fn render(frame: &mut Frame, dialog: &ChangesDialog, area: Rect) {
 
         let mut state = ListState::default();
         state.select(Some(dialog.selected_index));
-        frame.render_stateful_widget(list, chunks[1], &mut state);
+        frame.render_stateful_widget(list, area, &mut state);
     }
+}
 
-    // Help text
-    let sel_count = dialog.selection_count();
-    let sel_text = if sel_count > 0 {
-        format!(" ({} selected)", sel_count)
+/// Changed fields as a diff: the stored value in red, the file's in green.
+fn render_diff(frame: &mut Frame, diff: &MetadataDiff, area: Rect, theme: &Theme) {
+    let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "(none)".to_string());
+
+    let lines: Vec<Line> = if diff.changes.is_empty() {
+        vec![Line::styled("  No field differs from the database", Style::default().fg(theme.muted))]
     } else {
-        String::new()
+        diff.changes
+            .iter()
+            .flat_map(|change| {
+                [
+                    Line::styled(
+                        format!(" - {}: {}", change.field, value(&change.stored)),
+                        Style::default().fg(theme.error),
+                    ),
+                    Line::styled(
+                        format!(" + {}: {}", change.field, value(&change.current)),
+                        Style::default().fg(theme.success),
+                    ),
+                ]
+            })
+            .collect()
     };
 
-    let help_text = format!(
-        " Tab=switch  j/k=nav  Space=toggle  a=all  Enter=rescan{}  q=close",
-        sel_text
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", diff.filename)),
     );
-
-    let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
-        .block(Block::default().borders(Borders::TOP));
-
-    frame.render_widget(help, chunks[2]);
+    frame.render_widget(paragraph, area);
 }
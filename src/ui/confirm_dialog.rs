@@ -6,6 +6,7 @@ use ratatui::{
 };
 
 use crate::config::Action;
+use crate::ui::theme::Theme;
 
 /// Focus area within the confirm dialog
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,17 +31,22 @@ pub struct ConfirmDialog {
     pub focus: ConfirmFocus,
     /// The original prompt text (to detect modifications)
     pub original_prompt: String,
+    /// Follow a scan with LLM descriptions and face detection
+    pub pipeline: bool,
 }
 
 impl ConfirmDialog {
     pub fn new(action: Action, initial_prompt: Option<String>) -> Self {
         let message = match action {
             Action::Scan => "Scan directory for photos? This will index all images in the current directory.".to_string(),
+            Action::ScanAll => "Scan every directory in scanner.auto_scan_dirs? They are scanned one after another.".to_string(),
             Action::DescribeWithLlm => "Generate AI description for this photo? This will send the image to your configured LLM.".to_string(),
             Action::BatchLlm => "Process all photos with AI? This will send all undescribed photos to your configured LLM.".to_string(),
             Action::DetectFaces => "Detect faces in photos? This will analyze images for face detection.".to_string(),
             Action::ClusterFaces => "Cluster similar faces? This will group detected faces by similarity.".to_string(),
+            Action::RegenerateFaceEmbeddings => "Regenerate face embeddings? This will re-embed faces that are missing one or were embedded by an older model.".to_string(),
             Action::ClipEmbedding => "Generate CLIP embeddings? This will create semantic embeddings for images in this directory.".to_string(),
+            Action::WriteExifToFiles => "Write descriptions, tags and ratings into the files? This modifies the selected JPEG/TIFF photos (or all in this directory).".to_string(),
             _ => format!("Execute {:?}?", action),
         };
         let has_prompt_field = matches!(action, Action::DescribeWithLlm | Action::BatchLlm);
@@ -52,7 +58,18 @@ impl ConfirmDialog {
         } else {
             ConfirmFocus::Buttons
         };
-        Self { action, message, has_prompt_field, prompt_text, prompt_cursor, focus, original_prompt }
+        Self { action, message, has_prompt_field, prompt_text, prompt_cursor, focus, original_prompt, pipeline: false }
+    }
+
+    /// Only a scan can start the Scan → LLM → Faces pipeline.
+    pub fn supports_pipeline(&self) -> bool {
+        self.action == Action::Scan
+    }
+
+    pub fn toggle_pipeline(&mut self) {
+        if self.supports_pipeline() {
+            self.pipeline = !self.pipeline;
+        }
     }
 
     pub fn prompt_modified(&self) -> bool {
@@ -108,9 +125,15 @@ impl ConfirmDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect, theme: &Theme) {
     let dialog_width = 60.min(area.width.saturating_sub(4));
-    let dialog_height = if dialog.has_prompt_field { 15 } else { 9 };
+    let dialog_height = if dialog.has_prompt_field {
+        15
+    } else if dialog.supports_pipeline() {
+        11
+    } else {
+        9
+    };
 
     let x = (area.width - dialog_width) / 2;
     let y = (area.height - dialog_height) / 2;
@@ -136,7 +159,7 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
         // Outer border
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
+            .border_style(Style::default().fg(theme.highlight))
             .title(" Confirm Task ");
         frame.render_widget(block, dialog_area);
 
@@ -148,21 +171,21 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
 
         // Prompt label
         let label = Paragraph::new("LLM Prompt (per-folder):")
-            .style(Style::default().fg(Color::Cyan));
+            .style(Style::default().fg(theme.accent));
         frame.render_widget(label, chunks[1]);
 
         // Prompt input field
         let input_style = if dialog.focus == ConfirmFocus::PromptField {
-            Style::default().fg(Color::White).bg(Color::DarkGray)
+            Style::default().fg(theme.fg).bg(theme.muted)
         } else {
-            Style::default().fg(Color::Gray)
+            Style::default().fg(theme.secondary)
         };
         let input_block = Block::default()
             .borders(Borders::ALL)
             .border_style(if dialog.focus == ConfirmFocus::PromptField {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme.accent)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             });
 
         // Show the prompt text with cursor
@@ -199,7 +222,7 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
 
         // Help text
         let help = Paragraph::new("Tab: switch focus")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(help, chunks[3]);
 
@@ -207,13 +230,13 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
         let button_style = if dialog.focus == ConfirmFocus::Buttons {
             Style::default().add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(theme.muted)
         };
         let buttons = Line::from(vec![
-            Span::styled("  [Enter/y] ", button_style.fg(Color::Green)),
+            Span::styled("  [Enter/y] ", button_style.fg(theme.success)),
             Span::styled("Yes", button_style),
             Span::raw("    "),
-            Span::styled("[Esc/n] ", button_style.fg(Color::Red)),
+            Span::styled("[Esc/n] ", button_style.fg(theme.error)),
             Span::styled("No", button_style),
         ]);
         let button_widget = Paragraph::new(buttons).alignment(Alignment::Center);
@@ -224,6 +247,7 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(3),    // Message
+                Constraint::Length(if dialog.supports_pipeline() { 2 } else { 0 }), // Pipeline toggle
                 Constraint::Length(3), // Buttons
             ])
             .margin(1)
@@ -232,7 +256,7 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
         // Outer border
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
+            .border_style(Style::default().fg(theme.highlight))
             .title(" Confirm Task ");
         frame.render_widget(block, dialog_area);
 
@@ -242,15 +266,29 @@ pub fn render(frame: &mut Frame, dialog: &ConfirmDialog, area: Rect) {
             .alignment(Alignment::Center);
         frame.render_widget(message, chunks[0]);
 
+        if dialog.supports_pipeline() {
+            let (state, style) = if dialog.pipeline {
+                ("on", Style::default().fg(theme.success).add_modifier(Modifier::BOLD))
+            } else {
+                ("off", Style::default().fg(theme.muted))
+            };
+            let pipeline = Line::from(vec![
+                Span::styled("[P] ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                Span::raw("Scan → LLM → Faces pipeline: "),
+                Span::styled(state, style),
+            ]);
+            frame.render_widget(Paragraph::new(pipeline).alignment(Alignment::Center), chunks[1]);
+        }
+
         // Button hints
         let buttons = Line::from(vec![
-            Span::styled("  [Enter/y] ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled("  [Enter/y] ", Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
             Span::raw("Yes"),
             Span::raw("    "),
-            Span::styled("[Esc/n] ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled("[Esc/n] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
             Span::raw("No"),
         ]);
         let button_widget = Paragraph::new(buttons).alignment(Alignment::Center);
-        frame.render_widget(button_widget, chunks[1]);
+        frame.render_widget(button_widget, chunks[2]);
     }
 }
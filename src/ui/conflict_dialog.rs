@@ -0,0 +1,224 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::ui::theme::Theme;
+
+/// What to do with a file whose name is taken in the destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Resolution {
+    /// Leave the file where it is
+    #[default]
+    Skip,
+    /// Replace the file in the destination
+    Overwrite,
+    /// Move it under the next free numbered name, e.g. `name_001.jpg`
+    KeepBoth,
+}
+
+impl Resolution {
+    pub fn label(self) -> &'static str {
+        match self {
+            Resolution::Skip => "Skip",
+            Resolution::Overwrite => "Overwrite",
+            Resolution::KeepBoth => "Keep both",
+        }
+    }
+}
+
+/// A file that would land on a name already taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictEntry {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    /// Taken by another file of the same move rather than one on disk
+    pub within_batch: bool,
+}
+
+/// Files whose names are taken in `target_dir`, by a file already there or
+/// by an earlier file of the same move.
+pub fn find_conflicts(files: &[PathBuf], target_dir: &std::path::Path) -> Vec<ConflictEntry> {
+    let mut claimed = HashSet::new();
+    let mut conflicts = Vec::new();
+    for source in files {
+        let Some(filename) = source.file_name() else { continue };
+        let target = target_dir.join(filename);
+        if !claimed.insert(target.clone()) {
+            conflicts.push(ConflictEntry { source: source.clone(), target, within_batch: true });
+        } else if target.exists() && &target != source {
+            conflicts.push(ConflictEntry { source: source.clone(), target, within_batch: false });
+        }
+    }
+    conflicts
+}
+
+/// State for the move conflict dialog: one resolution per conflict, chosen
+/// before any file is moved
+pub struct ConflictDialog {
+    /// Every file of the move, conflicting or not
+    pub files: Vec<PathBuf>,
+    pub target_dir: PathBuf,
+    pub conflicts: Vec<ConflictEntry>,
+    /// `resolutions[i]` applies to `conflicts[i]`
+    pub resolutions: Vec<Resolution>,
+    pub selected: usize,
+}
+
+impl ConflictDialog {
+    pub fn new(files: Vec<PathBuf>, target_dir: PathBuf, conflicts: Vec<ConflictEntry>) -> Self {
+        let resolutions = vec![Resolution::default(); conflicts.len()];
+        Self {
+            files,
+            target_dir,
+            conflicts,
+            resolutions,
+            selected: 0,
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.conflicts.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Resolve the selected conflict and move on to the next one.
+    pub fn resolve_selected(&mut self, resolution: Resolution) {
+        if let Some(slot) = self.resolutions.get_mut(self.selected) {
+            *slot = resolution;
+        }
+        self.move_down();
+    }
+
+    pub fn resolve_all(&mut self, resolution: Resolution) {
+        self.resolutions.fill(resolution);
+    }
+
+    /// Resolution per source file, for the move itself
+    pub fn resolutions_by_source(&self) -> HashMap<PathBuf, Resolution> {
+        self.conflicts
+            .iter()
+            .zip(&self.resolutions)
+            .map(|(conflict, resolution)| (conflict.source.clone(), *resolution))
+            .collect()
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &ConflictDialog, area: Rect, theme: &Theme) {
+    // Center the dialog
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = 20.min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Header
+            Constraint::Min(5),    // Conflicts
+            Constraint::Length(3), // Footer
+        ])
+        .split(dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .title(" Name Conflicts ")
+        .title_style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_widget(block, dialog_area);
+
+    let header = Paragraph::new(format!(
+        "{} of {} file(s) already exist in {}",
+        dialog.conflicts.len(),
+        dialog.files.len(),
+        dialog.target_dir.display()
+    ))
+    .style(Style::default().fg(theme.highlight));
+    frame.render_widget(header, chunks[0]);
+
+    let name_width = (chunks[1].width as usize).saturating_sub(16);
+    let items: Vec<ListItem> = dialog
+        .conflicts
+        .iter()
+        .zip(&dialog.resolutions)
+        .map(|(conflict, resolution)| {
+            let mut name = conflict
+                .target
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if conflict.within_batch {
+                name.push_str(" (twice in this move)");
+            }
+            let resolution_style = match resolution {
+                Resolution::Skip => Style::default().fg(theme.muted),
+                Resolution::Overwrite => Style::default().fg(theme.error),
+                Resolution::KeepBoth => Style::default().fg(theme.success),
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<width$} ", name, width = name_width)),
+                Span::styled(format!("[{}]", resolution.label()), resolution_style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted)))
+        .highlight_style(Style::default().bg(theme.selected_bg).fg(theme.selected_fg));
+    let mut state = ListState::default();
+    state.select(Some(dialog.selected));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+
+    let footer = Paragraph::new(vec![
+        Line::from("s: skip | o: overwrite | b: keep both | S/O/B: apply to all"),
+        Line::from("j/k: navigate | Enter: move | Esc: cancel move"),
+    ])
+    .style(Style::default().fg(theme.muted));
+    frame.render_widget(footer, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("a.jpg"), "old").unwrap();
+
+        let files = vec![
+            dir.path().join("x/a.jpg"),
+            dir.path().join("x/b.jpg"),
+            dir.path().join("y/b.jpg"),
+        ];
+        let conflicts = find_conflicts(&files, &target);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].source, files[0]);
+        assert!(!conflicts[0].within_batch);
+        assert_eq!(conflicts[1].source, files[2]);
+        assert!(conflicts[1].within_batch);
+
+        let mut dialog = ConflictDialog::new(files, target, conflicts);
+        dialog.resolve_selected(Resolution::Overwrite);
+        assert_eq!(dialog.selected, 1);
+        assert_eq!(dialog.resolutions, vec![Resolution::Overwrite, Resolution::Skip]);
+        dialog.resolve_all(Resolution::KeepBoth);
+        assert_eq!(dialog.resolutions_by_source()[&dialog.files[2]], Resolution::KeepBoth);
+    }
+}
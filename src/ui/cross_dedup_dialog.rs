@@ -0,0 +1,58 @@
+//! Picks the two directories for a cross-directory duplicate search: the
+//! staging directory photos were imported into, then the library that keeps
+//! its copies.
+
+use ratatui::prelude::*;
+use std::path::PathBuf;
+
+use crate::ui::move_dialog::{self, MoveDialog};
+use crate::ui::theme::Theme;
+
+/// State for the cross-directory dedup picker.
+pub struct CrossDirDedupDialog {
+    /// Directory browser for the step in progress
+    pub picker: MoveDialog,
+    /// Chosen staging directory, once the first step is done
+    pub staging: Option<PathBuf>,
+}
+
+impl CrossDirDedupDialog {
+    pub fn new(start_dir: PathBuf) -> Self {
+        Self {
+            picker: MoveDialog::new(start_dir, Vec::new()),
+            staging: None,
+        }
+    }
+
+    /// Accept the directory being browsed. Returns `(staging, library)`
+    /// once both have been chosen.
+    pub fn choose(&mut self) -> Option<(PathBuf, PathBuf)> {
+        let chosen = self.picker.target_dir().clone();
+        match self.staging.take() {
+            None => {
+                self.staging = Some(chosen);
+                None
+            }
+            Some(staging) => Some((staging, chosen)),
+        }
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &CrossDirDedupDialog, area: Rect, theme: &Theme) {
+    let header = match dialog.staging {
+        None => "1/2: Staging directory (its copies are marked for deletion)".to_string(),
+        Some(ref staging) => format!(
+            "2/2: Library directory (its copies are kept); staging: {}",
+            staging.display()
+        ),
+    };
+    move_dialog::render_picker(
+        frame,
+        &dialog.picker,
+        area,
+        theme,
+        " Cross-Directory Duplicates ",
+        &header,
+        "j/k: navigate | Enter: open dir | /: edit path | Space: choose this dir | Esc: cancel",
+    );
+}
@@ -0,0 +1,227 @@
+//! Date range picker for filtering the browser by capture date.
+
+use chrono::{Datelike, Local, Months, NaiveDate};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::theme::Theme;
+
+/// Date component under the cursor: year, month or day of the start (0-2)
+/// or end (3-5) date.
+const FIELD_COUNT: usize = 6;
+
+/// State for the date filter dialog.
+pub struct DateFilterDialog {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Focused component, 0-5
+    pub field: usize,
+}
+
+impl DateFilterDialog {
+    /// Open on the existing filter, or the current month.
+    pub fn new(existing: Option<(NaiveDate, NaiveDate)>) -> Self {
+        let (start, end) = existing.unwrap_or_else(|| {
+            let today = Local::now().date_naive();
+            month_range(today.year(), today.month())
+        });
+        Self {
+            start,
+            end,
+            field: 0,
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = (self.field + 1) % FIELD_COUNT;
+    }
+
+    pub fn prev_field(&mut self) {
+        self.field = (self.field + FIELD_COUNT - 1) % FIELD_COUNT;
+    }
+
+    /// Jump between the start and end date, keeping the component.
+    pub fn toggle_date(&mut self) {
+        self.field = (self.field + 3) % FIELD_COUNT;
+    }
+
+    /// Step the focused component up (`delta > 0`) or down.
+    pub fn adjust(&mut self, delta: i32) {
+        let date = if self.field < 3 { &mut self.start } else { &mut self.end };
+        *date = step(*date, self.field % 3, delta);
+    }
+
+    /// The selected range, with the dates swapped if entered backwards.
+    pub fn range(&self) -> (NaiveDate, NaiveDate) {
+        if self.start <= self.end {
+            (self.start, self.end)
+        } else {
+            (self.end, self.start)
+        }
+    }
+}
+
+/// First and last day of a month.
+fn month_range(year: i32, month: u32) -> (NaiveDate, NaiveDate) {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default();
+    let last = first
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(first);
+    (first, last)
+}
+
+/// Move one component of a date, clamping the day to the month's length.
+fn step(date: NaiveDate, component: usize, delta: i32) -> NaiveDate {
+    match component {
+        0 | 1 => {
+            let months = if component == 0 { delta * 12 } else { delta };
+            let shifted = if months >= 0 {
+                date.checked_add_months(Months::new(months as u32))
+            } else {
+                date.checked_sub_months(Months::new(months.unsigned_abs()))
+            };
+            shifted.unwrap_or(date)
+        }
+        _ => date
+            .checked_add_signed(chrono::Duration::days(delta as i64))
+            .unwrap_or(date),
+    }
+}
+
+/// Short description of a range for the status bar: "Aug 2024", "2024",
+/// "2024-08-10" or "2024-08-01..2024-09-15".
+pub fn describe_range(start: NaiveDate, end: NaiveDate) -> String {
+    if start == end {
+        return start.format("%Y-%m-%d").to_string();
+    }
+    if start.year() == end.year() && start.ordinal() == 1 && end.succ_opt().is_some_and(|d| d.ordinal() == 1) {
+        return start.format("%Y").to_string();
+    }
+    if (start, end) == month_range(start.year(), start.month()) {
+        return start.format("%b %Y").to_string();
+    }
+    format!("{}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+}
+
+fn date_spans(date: NaiveDate, focused: Option<usize>, theme: &Theme) -> Vec<Span<'static>> {
+    let parts = [
+        format!("{:04}", date.year()),
+        format!("{:02}", date.month()),
+        format!("{:02}", date.day()),
+    ];
+    let mut spans = Vec::new();
+    for (i, part) in parts.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw("-"));
+        }
+        let style = if focused == Some(i) {
+            Style::default().fg(theme.selected_fg).bg(theme.selected_bg).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.fg)
+        };
+        spans.push(Span::styled(part, style));
+    }
+    spans
+}
+
+pub fn render(frame: &mut Frame, dialog: &DateFilterDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 46.min(area.width.saturating_sub(4));
+    let dialog_height = 10.min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Filter by Date Taken ");
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(inner);
+
+    let label = |text: &'static str, active: bool| {
+        let style = if active {
+            Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        Span::styled(text, style)
+    };
+
+    let (start, end) = dialog.range();
+    let mut from = vec![label("  From: ", dialog.field < 3)];
+    from.extend(date_spans(dialog.start, (dialog.field < 3).then_some(dialog.field), theme));
+    let mut to = vec![label("  To:   ", dialog.field >= 3)];
+    to.extend(date_spans(dialog.end, (dialog.field >= 3).then_some(dialog.field - 3), theme));
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(from),
+        Line::from(to),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  Showing: {}", describe_range(start, end)),
+            Style::default().fg(theme.secondary),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new(vec![
+        Line::from("←/→ field  ↑/↓ change  Tab start/end"),
+        Line::from("Enter apply  c clear filter  Esc cancel"),
+    ])
+    .style(Style::default().fg(theme.muted))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_describe_range() {
+        assert_eq!(describe_range(date(2024, 8, 1), date(2024, 8, 31)), "Aug 2024");
+        assert_eq!(describe_range(date(2024, 1, 1), date(2024, 12, 31)), "2024");
+        assert_eq!(describe_range(date(2024, 8, 10), date(2024, 8, 10)), "2024-08-10");
+        assert_eq!(
+            describe_range(date(2024, 8, 1), date(2024, 9, 15)),
+            "2024-08-01..2024-09-15"
+        );
+    }
+
+    #[test]
+    fn test_step_clamps_day() {
+        // Jan 31 + 1 month lands on the last day of February
+        assert_eq!(step(date(2024, 1, 31), 1, 1), date(2024, 2, 29));
+        assert_eq!(step(date(2024, 2, 29), 0, 1), date(2025, 2, 28));
+        assert_eq!(step(date(2024, 3, 1), 2, -1), date(2024, 2, 29));
+        assert_eq!(step(date(2024, 1, 15), 1, -1), date(2023, 12, 15));
+    }
+
+    #[test]
+    fn test_range_is_ordered() {
+        let dialog = DateFilterDialog {
+            start: date(2024, 9, 1),
+            end: date(2024, 8, 1),
+            field: 0,
+        };
+        assert_eq!(dialog.range(), (date(2024, 8, 1), date(2024, 9, 1)));
+    }
+}
@@ -3,7 +3,9 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
-pub fn render_help(frame: &mut Frame, area: Rect) {
+use crate::ui::theme::Theme;
+
+pub fn render_help(frame: &mut Frame, area: Rect, theme: &Theme) {
     // Center the help dialog
     let dialog_width = 60.min(area.width.saturating_sub(4));
     let dialog_height = 50.min(area.height.saturating_sub(4));
@@ -17,7 +19,7 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
     frame.render_widget(Clear, dialog_area);
 
     let help_text = vec![
-        Line::from(Span::styled("Navigation", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Navigation", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  j / ↓      Move down"),
         Line::from("  k / ↑      Move up"),
@@ -28,64 +30,95 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
         Line::from("  Ctrl+f     Page down"),
         Line::from("  Ctrl+b     Page up"),
         Line::from("  ~          Go to home directory"),
+        Line::from("  B          Focus breadcrumb bar"),
         Line::from(""),
-        Line::from(Span::styled("Selection", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Selection", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  Space      Toggle file selection"),
         Line::from("  v / V      Enter visual mode (range select)"),
         Line::from("  Esc        Cancel running task / clear selection"),
         Line::from(""),
-        Line::from(Span::styled("Views", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Views", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  A          Open gallery view"),
         Line::from("  S          View image (slideshow)"),
         Line::from("  b          Open tags browser"),
+        Line::from("  Alt+b      Batch edit tags on selection"),
+        Line::from("  Alt+t      Tag statistics and filter"),
+        Line::from("  Alt+d      Calendar of photos per day"),
+        Line::from("  Alt+a      Browse albums"),
+        Line::from("  Alt+l      Browse photos by location"),
+        Line::from("  Alt+m      Import from Android device (ADB)"),
+        Line::from("  Alt+s      Share selection as a web gallery"),
         Line::from("  T          View/manage running tasks"),
         Line::from("  X          View/manage trash"),
         Line::from("  c          View recent changes"),
+        Line::from("  W          Watch directory for changes"),
+        Line::from("  Ctrl+r     Reload config file"),
         Line::from("  @          Open schedule manager"),
         Line::from(""),
-        Line::from(Span::styled("Processing", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Processing", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  s          Scan current directory for photos"),
+        Line::from("  Ctrl+s     Scan all directories in scanner.auto_scan_dirs"),
+        Line::from("  Alt+r      Rescan photos missing GPS, camera, lens..."),
         Line::from("  u          Find duplicate photos"),
+        Line::from("  Alt+u      Duplicates between import and library"),
+        Line::from("  Alt+q      Auto-cull blurry/badly exposed photos"),
         Line::from("  i          Describe image with AI (LLM)"),
         Line::from("  I          Batch process all photos with AI"),
         Line::from("  F          Detect faces in photos"),
         Line::from("  C          Cluster similar faces together"),
+        Line::from("  Alt+c      Regenerate stale face embeddings"),
         Line::from("  E          Generate CLIP embeddings"),
         Line::from(""),
-        Line::from(Span::styled("File Operations", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("File Operations", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  m          Move selected/current file(s)"),
         Line::from("  r          Rename selected/current file(s)"),
         Line::from("  y / x      Cut selected file(s)"),
         Line::from("  p          Paste file(s)"),
         Line::from("  d          Move to trash"),
+        Line::from("  Alt+x      Trash red-labeled photos"),
+        Line::from("  Alt+e      Write description/tags/rating into files"),
+        Line::from("  Ctrl+z     Undo last move/rename/trash"),
+        Line::from("  Ctrl+y     Redo"),
         Line::from("  L          Centralise files to target directory"),
         Line::from("  O          Export photo database"),
         Line::from("  ]          Rotate photo clockwise"),
         Line::from("  [          Rotate photo counter-clockwise"),
         Line::from("  o          Open file in system viewer"),
         Line::from(""),
-        Line::from(Span::styled("Other", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Other", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  /          Semantic search photos"),
-        Line::from("  P          Manage people/faces"),
+        Line::from("  f          Fuzzy filter file names"),
+        Line::from("  Alt+i      Search by selected photo"),
+        Line::from("  P          Browse people"),
+        Line::from("  Alt+p      Manage directory prompts"),
         Line::from("  e          Edit photo description"),
+        Line::from("  Ctrl+g     Set GPS location"),
         Line::from("  .          Toggle hidden files/dirs"),
         Line::from("  H          Toggle show all files"),
+        Line::from("  ,          Cycle sort order"),
+        Line::from("  Ctrl+d     Filter by date taken"),
+        Line::from("  Ctrl+k     Filter by camera"),
+        Line::from("  Ctrl+t     Pick color theme"),
+        Line::from("  < / >      Shrink/grow preview pane"),
+        Line::from("  #          Toggle histogram"),
+        Line::from("  Ctrl+p     Cycle preview mode"),
+        Line::from("  M          Show all metadata fields"),
         Line::from("  ?          Show this help"),
         Line::from("  q          Quit"),
         Line::from(""),
-        Line::from(Span::styled("Press any key to close", Style::default().fg(Color::DarkGray))),
+        Line::from(Span::styled("Press any key to close", Style::default().fg(theme.muted))),
     ];
 
     let paragraph = Paragraph::new(help_text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme.accent))
                 .title(" Help ")
                 .title_style(Style::default().add_modifier(Modifier::BOLD)),
         )
@@ -1,33 +1,283 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
-use ratatui_image::{Resize, StatefulImage};
+use ratatui_image::{protocol::StatefulProtocol, Resize, StatefulImage};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::mpsc;
 
 use crate::app::App;
 use crate::db::{PhotoRecord, SimilarityGroup, calculate_quality_score};
+use crate::llm::LlmClient;
+use crate::ui::pixel_diff::{self, PixelDiff};
+use crate::ui::preview::ImagePreviewState;
+use crate::ui::theme::Theme;
+
+/// What the duplicates view shows next to the photo list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatesViewMode {
+    Normal,
+    /// Amplified per-pixel difference against the group's first photo
+    PixelDiff,
+}
+
+/// Kind of duplicate groups the view is showing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatesTab {
+    /// Identical files (SHA256)
+    Exact,
+    /// Close perceptual hashes
+    Perceptual,
+    /// Near-duplicate shots by embedding similarity
+    Similar,
+}
+
+impl DuplicatesTab {
+    pub const ALL: [DuplicatesTab; 3] = [Self::Exact, Self::Perceptual, Self::Similar];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Exact => "Exact",
+            Self::Perceptual => "Perceptual",
+            Self::Similar => "Similar",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn for_group_type(group_type: &str) -> Self {
+        match group_type {
+            "exact" => Self::Exact,
+            "similar" => Self::Similar,
+            _ => Self::Perceptual,
+        }
+    }
+}
+
+/// Pixel diff for one pair of photos
+pub enum DiffState {
+    Computing(mpsc::Receiver<Result<PixelDiff, String>>),
+    Ready {
+        protocol: StatefulProtocol,
+        rmse: f64,
+        ssim: f64,
+    },
+    Failed(String),
+}
+
+/// LLM explanation of how a pair of photos differs
+pub enum ExplainState {
+    Asking(mpsc::Receiver<Result<String, String>>),
+    Ready(String),
+    Failed(String),
+}
 
 #[allow(dead_code)]
 pub struct DuplicatesView {
+    /// Groups in the current tab
     pub groups: Vec<SimilarityGroup>,
+    pub tab: DuplicatesTab,
+    /// Groups of the other tabs, by tab index (the current tab's slot is empty)
+    other_tabs: [Vec<SimilarityGroup>; 3],
     pub current_group: usize,
     pub selected_photo: usize,
     pub group_scroll: usize,
     pub photo_scroll: usize,
+    pub mode: DuplicatesViewMode,
+    /// Diff for the photo id pair it was computed from
+    diff: Option<((i64, i64), DiffState)>,
+    /// Explanations asked for so far, by photo id pair
+    explanations: HashMap<(i64, i64), ExplainState>,
 }
 
 impl DuplicatesView {
     pub fn new(groups: Vec<SimilarityGroup>) -> Self {
+        let mut other_tabs: [Vec<SimilarityGroup>; 3] = Default::default();
+        for group in groups {
+            other_tabs[DuplicatesTab::for_group_type(&group.group_type).index()].push(group);
+        }
+        // Open on the first tab with anything in it
+        let tab = DuplicatesTab::ALL
+            .into_iter()
+            .find(|t| !other_tabs[t.index()].is_empty())
+            .unwrap_or(DuplicatesTab::Exact);
+        let groups = std::mem::take(&mut other_tabs[tab.index()]);
+
         Self {
             groups,
+            tab,
+            other_tabs,
             current_group: 0,
             selected_photo: 0,
             group_scroll: 0,
             photo_scroll: 0,
+            mode: DuplicatesViewMode::Normal,
+            diff: None,
+            explanations: HashMap::new(),
+        }
+    }
+
+    /// Number of groups in a tab.
+    pub fn tab_len(&self, tab: DuplicatesTab) -> usize {
+        if tab == self.tab {
+            self.groups.len()
+        } else {
+            self.other_tabs[tab.index()].len()
+        }
+    }
+
+    /// No groups left in any tab.
+    pub fn is_empty(&self) -> bool {
+        DuplicatesTab::ALL.into_iter().all(|t| self.tab_len(t) == 0)
+    }
+
+    pub fn next_tab(&mut self) {
+        let next = DuplicatesTab::ALL[(self.tab.index() + 1) % DuplicatesTab::ALL.len()];
+        self.switch_tab(next);
+    }
+
+    pub fn prev_tab(&mut self) {
+        let len = DuplicatesTab::ALL.len();
+        let prev = DuplicatesTab::ALL[(self.tab.index() + len - 1) % len];
+        self.switch_tab(prev);
+    }
+
+    fn switch_tab(&mut self, tab: DuplicatesTab) {
+        if tab == self.tab {
+            return;
+        }
+        // A photo can appear in several tabs; carry its deletion mark over
+        let marks: std::collections::HashMap<i64, bool> = self
+            .groups
+            .iter()
+            .flat_map(|g| g.photos.iter().map(|p| (p.id, p.marked_for_deletion)))
+            .collect();
+        for photo in self.other_tabs.iter_mut().flatten().flat_map(|g| g.photos.iter_mut()) {
+            if let Some(&marked) = marks.get(&photo.id) {
+                photo.marked_for_deletion = marked;
+            }
+        }
+
+        self.other_tabs[self.tab.index()] = std::mem::take(&mut self.groups);
+        self.groups = std::mem::take(&mut self.other_tabs[tab.index()]);
+        self.tab = tab;
+        self.current_group = 0;
+        self.selected_photo = 0;
+        self.group_scroll = 0;
+        self.photo_scroll = 0;
+    }
+
+    pub fn toggle_pixel_diff(&mut self) {
+        self.mode = match self.mode {
+            DuplicatesViewMode::Normal => DuplicatesViewMode::PixelDiff,
+            DuplicatesViewMode::PixelDiff => DuplicatesViewMode::Normal,
+        };
+    }
+
+    /// The two photos to diff: the group's first photo and the selected one,
+    /// or the first two when the first is selected.
+    pub fn diff_pair(&self) -> Option<(&PhotoRecord, &PhotoRecord)> {
+        let photos = &self.current_group()?.photos;
+        let other = if self.selected_photo == 0 { 1 } else { self.selected_photo };
+        Some((photos.first()?, photos.get(other)?))
+    }
+
+    /// Start computing the diff for the current pair if needed, and pick up
+    /// a finished computation.
+    fn update_diff(&mut self, preview: &mut ImagePreviewState, max_size: u32) {
+        let Some((a, b)) = self.diff_pair() else {
+            self.diff = None;
+            return;
+        };
+        let key = (a.id, b.id);
+
+        if self.diff.as_ref().map(|(k, _)| *k) != Some(key) {
+            let (path_a, path_b) = (PathBuf::from(&a.path), PathBuf::from(&b.path));
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let result = pixel_diff::compare_files(&path_a, &path_b, max_size)
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(result);
+            });
+            self.diff = Some((key, DiffState::Computing(rx)));
+            return;
+        }
+
+        if let Some((_, DiffState::Computing(rx))) = &self.diff {
+            let state = match rx.try_recv() {
+                Ok(Ok(diff)) => match preview.new_protocol(diff.image) {
+                    Some(protocol) => DiffState::Ready {
+                        protocol,
+                        rmse: diff.rmse,
+                        ssim: diff.ssim,
+                    },
+                    None => DiffState::Failed("Image preview unavailable".to_string()),
+                },
+                Ok(Err(e)) => DiffState::Failed(e),
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    DiffState::Failed("Diff computation failed".to_string())
+                }
+            };
+            self.diff = Some((key, state));
+        }
+    }
+
+    /// Ask the LLM how the current diff pair differs, unless it already
+    /// has. Returns false when the group has only one photo.
+    pub fn explain_pair(&mut self, client: &LlmClient) -> bool {
+        let Some((a, b)) = self.diff_pair() else {
+            return false;
+        };
+        let key = (a.id, b.id);
+        if matches!(self.explanations.get(&key), Some(ExplainState::Asking(_) | ExplainState::Ready(_))) {
+            return true;
+        }
+
+        let (path_a, path_b) = (PathBuf::from(&a.path), PathBuf::from(&b.path));
+        let client = client.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = client
+                .explain_duplicate_difference(&path_a, &path_b)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.explanations.insert(key, ExplainState::Asking(rx));
+        true
+    }
+
+    /// Pick up finished explanations.
+    fn update_explanations(&mut self) {
+        for state in self.explanations.values_mut() {
+            if let ExplainState::Asking(rx) = state {
+                *state = match rx.try_recv() {
+                    Ok(Ok(text)) => ExplainState::Ready(text),
+                    Ok(Err(e)) => ExplainState::Failed(e),
+                    Err(mpsc::TryRecvError::Empty) => continue,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        ExplainState::Failed("LLM request failed".to_string())
+                    }
+                };
+            }
         }
     }
 
+    /// Explanation for the current diff pair, if one was asked for
+    fn current_explanation(&self) -> Option<&ExplainState> {
+        let (a, b) = self.diff_pair()?;
+        self.explanations.get(&(a.id, b.id))
+    }
+
+    /// Rows below the photo list: the selected path, plus two wrapped lines
+    /// for an explanation of the pair
+    fn footer_height(&self) -> u16 {
+        if self.current_explanation().is_some() { 4 } else { 2 }
+    }
+
     pub fn current_group(&self) -> Option<&SimilarityGroup> {
         self.groups.get(self.current_group)
     }
@@ -110,13 +360,14 @@ impl DuplicatesView {
     pub fn remove_photos(&mut self, ids: &[i64]) {
         let id_set: std::collections::HashSet<i64> = ids.iter().copied().collect();
 
-        for group in &mut self.groups {
-            group.photos.retain(|p| !id_set.contains(&p.id));
+        for groups in std::iter::once(&mut self.groups).chain(self.other_tabs.iter_mut()) {
+            for group in groups.iter_mut() {
+                group.photos.retain(|p| !id_set.contains(&p.id));
+            }
+            // Drop groups that no longer represent duplicates
+            groups.retain(|g| g.photos.len() > 1);
         }
 
-        // Drop groups that no longer represent duplicates
-        self.groups.retain(|g| g.photos.len() > 1);
-
         // Clamp cursors
         if self.groups.is_empty() {
             self.current_group = 0;
@@ -194,18 +445,43 @@ impl DuplicatesView {
 }
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme;
     let view = match app.duplicates_view.as_mut() {
         Some(v) => v,
         None => return,
     };
 
+    view.update_explanations();
+
     // Clear and create overlay
     frame.render_widget(Clear, area);
 
     // Check if image preview is available
     let has_preview = app.config.preview.image_preview && app.image_preview.is_available();
 
-    if has_preview {
+    if has_preview && view.mode == DuplicatesViewMode::PixelDiff {
+        // Four-column layout: groups | photos | preview | diff
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20),  // Groups
+                Constraint::Percentage(30),  // Photos
+                Constraint::Percentage(25),  // Preview
+                Constraint::Percentage(25),  // Diff
+            ])
+            .split(area);
+
+        let group_visible_height = chunks[0].height.saturating_sub(2) as usize;
+        let photo_visible_height = chunks[1].height.saturating_sub(2 + view.footer_height()) as usize;
+
+        view.adjust_group_scroll(group_visible_height);
+        view.adjust_photo_scroll(photo_visible_height);
+
+        render_group_list(frame, view, chunks[0], theme);
+        render_photo_list(frame, view, chunks[1], theme);
+        render_preview(frame, app, chunks[2]);
+        render_diff(frame, app, chunks[3]);
+    } else if has_preview {
         // Three-column layout: groups | photos | preview
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
@@ -218,14 +494,14 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
 
         // Calculate visible heights (subtract 2 for border + title)
         let group_visible_height = chunks[0].height.saturating_sub(2) as usize;
-        let photo_visible_height = chunks[1].height.saturating_sub(4) as usize; // 2 for border/title, 2 for path area
+        let photo_visible_height = chunks[1].height.saturating_sub(2 + view.footer_height()) as usize; // 2 for border/title, rest for path area
 
         // Adjust scroll to keep selection visible
         view.adjust_group_scroll(group_visible_height);
         view.adjust_photo_scroll(photo_visible_height);
 
-        render_group_list(frame, view, chunks[0]);
-        render_photo_list(frame, view, chunks[1]);
+        render_group_list(frame, view, chunks[0], theme);
+        render_photo_list(frame, view, chunks[1], theme);
         render_preview(frame, app, chunks[2]);
     } else {
         // Two-column layout (no preview)
@@ -236,18 +512,18 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
 
         // Calculate visible heights
         let group_visible_height = chunks[0].height.saturating_sub(2) as usize;
-        let photo_visible_height = chunks[1].height.saturating_sub(4) as usize;
+        let photo_visible_height = chunks[1].height.saturating_sub(2 + view.footer_height()) as usize;
 
         // Adjust scroll to keep selection visible
         view.adjust_group_scroll(group_visible_height);
         view.adjust_photo_scroll(photo_visible_height);
 
-        render_group_list(frame, view, chunks[0]);
-        render_photo_list(frame, view, chunks[1]);
+        render_group_list(frame, view, chunks[0], theme);
+        render_photo_list(frame, view, chunks[1], theme);
     }
 }
 
-fn render_group_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
+fn render_group_list(frame: &mut Frame, view: &DuplicatesView, area: Rect, theme: &Theme) {
     // Calculate visible height (subtract 2 for borders)
     let visible_height = area.height.saturating_sub(2) as usize;
 
@@ -263,12 +539,16 @@ fn render_group_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
         .take(end - start)
         .map(|(i, group)| {
             let marker = if i == view.current_group { ">" } else { " " };
-            let type_icon = if group.group_type == "exact" { "=" } else { "~" };
+            let type_icon = match group.group_type.as_str() {
+                "exact" => "=",
+                "similar" => "≈",
+                _ => "~",
+            };
             let count = group.photos.len();
             let marked = group.photos.iter().filter(|p| p.marked_for_deletion).count();
 
             let style = if i == view.current_group {
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
@@ -281,20 +561,33 @@ fn render_group_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
         })
         .collect();
 
+    // Tab bar: the current tab highlighted, each with its group count
+    let mut tabs = vec![Span::raw(" ")];
+    for tab in DuplicatesTab::ALL {
+        let style = if tab == view.tab {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        tabs.push(Span::styled(format!("{} ({})", tab.label(), view.tab_len(tab)), style));
+        tabs.push(Span::raw(" "));
+    }
+
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Blue))
-            .title(format!(" Groups ({}) ", view.groups.len())),
+            .border_style(Style::default().fg(theme.border_active))
+            .title(Line::from(tabs)),
     );
 
     frame.render_widget(list, area);
 }
 
-fn render_photo_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
+fn render_photo_list(frame: &mut Frame, view: &DuplicatesView, area: Rect, theme: &Theme) {
+    let explanation = view.current_explanation();
     let inner_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .constraints([Constraint::Min(0), Constraint::Length(view.footer_height())])
         .split(area);
 
     // Calculate visible height (subtract 2 for borders)
@@ -325,12 +618,12 @@ fn render_photo_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
 
                 let style = if i == view.selected_photo {
                     if photo.marked_for_deletion {
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                        Style::default().fg(theme.error).add_modifier(Modifier::BOLD)
                     } else {
-                        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                        Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
                     }
                 } else if photo.marked_for_deletion {
-                    Style::default().fg(Color::Red)
+                    Style::default().fg(theme.error)
                 } else {
                     Style::default()
                 };
@@ -345,14 +638,14 @@ fn render_photo_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
 
         let title = format!(
             " {} ({}) [Space=toggle, a=auto, A=auto-identical] ",
-            if group.group_type == "exact" { "Exact" } else { "Similar" },
+            DuplicatesTab::for_group_type(&group.group_type).label(),
             group.photos.len()
         );
 
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(theme.accent))
                 .title(title),
         );
 
@@ -362,16 +655,34 @@ fn render_photo_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
         state.select(Some(display_index));
         frame.render_stateful_widget(list, inner_chunks[0], &mut state);
 
-        // Show selected photo path
+        // Show selected photo path, and how the pair differs once asked
         if let Some(photo) = view.current_photo() {
+            let block = Block::default().borders(Borders::TOP);
+            let footer = block.inner(inner_chunks[1]);
+            frame.render_widget(block, inner_chunks[1]);
+
             let path_text = Paragraph::new(photo.path.clone())
-                .style(Style::default().fg(Color::DarkGray))
-                .block(Block::default().borders(Borders::TOP));
-            frame.render_widget(path_text, inner_chunks[1]);
+                .style(Style::default().fg(theme.muted));
+            frame.render_widget(path_text, Rect { height: 1.min(footer.height), ..footer });
+
+            let subtitle = match explanation {
+                Some(ExplainState::Asking(_)) => Some(("Asking the LLM what differs...".to_string(), Style::default().fg(theme.muted))),
+                Some(ExplainState::Ready(text)) => Some((text.clone(), Style::default().fg(theme.accent).add_modifier(Modifier::ITALIC))),
+                Some(ExplainState::Failed(e)) => Some((e.clone(), Style::default().fg(theme.error))),
+                None => None,
+            };
+            if let Some((text, style)) = subtitle {
+                let subtitle_area = Rect {
+                    y: footer.y + 1,
+                    height: footer.height.saturating_sub(1),
+                    ..footer
+                };
+                frame.render_widget(Paragraph::new(text).style(style).wrap(Wrap { trim: true }), subtitle_area);
+            }
         }
     } else {
-        let msg = Paragraph::new("No duplicates found")
-            .style(Style::default().fg(Color::DarkGray))
+        let msg = Paragraph::new(format!("No {} duplicates (Tab: next tab)", view.tab.label().to_lowercase()))
+            .style(Style::default().fg(theme.muted))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -382,17 +693,18 @@ fn render_photo_list(frame: &mut Frame, view: &DuplicatesView, area: Rect) {
 }
 
 fn render_preview(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
     // Get current photo path
     let photo_path = match app.duplicates_view.as_ref() {
         Some(view) => match view.current_photo() {
             Some(photo) => PathBuf::from(&photo.path),
             None => {
-                render_no_preview(frame, area, "No photo selected");
+                render_no_preview(frame, area, "No photo selected", &theme);
                 return;
             }
         },
         None => {
-            render_no_preview(frame, area, "No duplicates");
+            render_no_preview(frame, area, "No duplicates", &theme);
             return;
         }
     };
@@ -413,7 +725,7 @@ fn render_preview(frame: &mut Frame, app: &mut App, area: Rect) {
     if let Some(protocol) = app.image_preview.load_image(&photo_path, thumbnail_size, rotation) {
         let inner = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green))
+            .border_style(Style::default().fg(theme.success))
             .title(" Preview ");
         let inner_area = inner.inner(image_area);
         frame.render_widget(inner, image_area);
@@ -421,9 +733,9 @@ fn render_preview(frame: &mut Frame, app: &mut App, area: Rect) {
         let image = StatefulImage::new(None).resize(Resize::Fit(None));
         frame.render_stateful_widget(image, inner_area, protocol);
     } else if app.image_preview.is_loading_image(&photo_path) {
-        render_no_preview(frame, image_area, "Loading...");
+        render_no_preview(frame, image_area, "Loading...", &theme);
     } else {
-        render_no_preview(frame, image_area, "Preview unavailable");
+        render_no_preview(frame, image_area, "Preview unavailable", &theme);
     }
 
     // Render photo info
@@ -435,7 +747,7 @@ fn render_preview(frame: &mut Frame, app: &mut App, area: Rect) {
             _ => "unknown".to_string(),
         };
         let status = if photo.marked_for_deletion { "DELETE" } else { "KEEP" };
-        let status_color = if photo.marked_for_deletion { Color::Red } else { Color::Green };
+        let status_color = if photo.marked_for_deletion { theme.error } else { theme.success };
 
         let info_lines = vec![
             Line::from(vec![
@@ -452,16 +764,77 @@ fn render_preview(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
-fn render_no_preview(frame: &mut Frame, area: Rect, message: &str) {
+fn render_diff(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
+    let max_size = app.config.preview.thumbnail_size;
+    let view = match app.duplicates_view.as_mut() {
+        Some(v) => v,
+        None => return,
+    };
+    view.update_diff(&mut app.image_preview, max_size);
+
+    let names = view.diff_pair().map(|(a, b)| {
+        format!("{} vs {}", a.filename, b.filename)
+    });
+    let state = match (&mut view.diff, names) {
+        (Some((_, state)), Some(names)) => (state, names),
+        _ => {
+            render_no_preview(frame, area, "Need two photos to compare", &theme);
+            return;
+        }
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(5)])
+        .split(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Pixel Diff (x8) ");
+    let inner_area = block.inner(chunks[0]);
+    frame.render_widget(block, chunks[0]);
+
+    let (scores, score_style) = match state.0 {
+        DiffState::Computing(_) => {
+            frame.render_widget(
+                Paragraph::new("Computing...")
+                    .style(Style::default().fg(theme.muted))
+                    .alignment(Alignment::Center),
+                inner_area,
+            );
+            ("RMSE: - | SSIM: -".to_string(), Style::default().fg(theme.muted))
+        }
+        DiffState::Ready { protocol, rmse, ssim } => {
+            let image = StatefulImage::new(None).resize(Resize::Fit(None));
+            frame.render_stateful_widget(image, inner_area, protocol);
+            (
+                format!("RMSE: {:.2} | SSIM: {:.4}", rmse, ssim),
+                Style::default().fg(theme.fg),
+            )
+        }
+        DiffState::Failed(e) => (e.clone(), Style::default().fg(theme.error)),
+    };
+
+    let info = Paragraph::new(vec![
+        Line::from(state.1),
+        Line::from(Span::styled(scores, score_style)),
+    ])
+    .block(Block::default().borders(Borders::ALL).title(" Comparison "));
+    frame.render_widget(info, chunks[1]);
+}
+
+fn render_no_preview(frame: &mut Frame, area: Rect, message: &str, theme: &Theme) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.muted))
         .title(" Preview ");
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
     let msg = Paragraph::new(message)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
 
     // Center vertically
@@ -472,9 +845,9 @@ fn render_no_preview(frame: &mut Frame, area: Rect, message: &str) {
     }
 }
 
-pub fn render_help(frame: &mut Frame, area: Rect) {
+pub fn render_help(frame: &mut Frame, area: Rect, theme: &Theme) {
     let dialog_width = 55.min(area.width.saturating_sub(4));
-    let dialog_height = 24.min(area.height.saturating_sub(4));
+    let dialog_height = 28.min(area.height.saturating_sub(4));
 
     let x = (area.width - dialog_width) / 2;
     let y = (area.height - dialog_height) / 2;
@@ -484,10 +857,11 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
     frame.render_widget(Clear, dialog_area);
 
     let help_text = vec![
-        Line::from(Span::styled("Duplicates View", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Duplicates View", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  j/k/Up/Down      Move between photos"),
         Line::from("  J/K/Left/Right   Move between groups"),
+        Line::from("  Tab/Shift+Tab    Exact, Perceptual and Similar tabs"),
         Line::from("  Mouse click      Select group or photo"),
         Line::from("  Mouse scroll     Scroll groups/photos list"),
         Line::from("  Right-click      Open photo in external viewer"),
@@ -495,22 +869,25 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
         Line::from("  a                Auto-select (keep best quality)"),
         Line::from("  A                Auto-mark identical only"),
         Line::from("  o                Open in external viewer"),
+        Line::from("  d                Toggle pixel diff panel"),
+        Line::from("  e                Ask the LLM how the pair differs"),
         Line::from("  x                Move marked to duplicate trash"),
         Line::from("  X                Permanently delete"),
         Line::from("  R                Rescan duplicates"),
         Line::from("  Esc              Exit (press u to return)"),
         Line::from("  ?                Toggle this help"),
         Line::from(""),
-        Line::from(Span::styled("Legend", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Legend", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from("  =        Exact duplicate (SHA256)"),
         Line::from("  ~        Perceptual similar"),
+        Line::from("  ≈        Similar shot (embeddings)"),
         Line::from("  [D]      Marked for deletion"),
     ];
 
     let paragraph = Paragraph::new(help_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.accent))
             .title(" Duplicates Help "),
     );
 
@@ -6,6 +6,8 @@ use ratatui::{
 };
 use std::path::PathBuf;
 
+use crate::ui::theme::Theme;
+
 /// Dialog state for editing a photo's description
 pub struct EditDescriptionDialog {
     /// Path of the photo being edited
@@ -113,7 +115,7 @@ impl EditDescriptionDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect, theme: &Theme) {
     let dialog_width = 70.min(area.width.saturating_sub(4));
     let dialog_height = 20.min(area.height.saturating_sub(4));
 
@@ -138,7 +140,7 @@ pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect) {
     // Outer border
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(" Edit Description ");
     frame.render_widget(block, dialog_area);
 
@@ -148,7 +150,7 @@ pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect) {
         .unwrap_or_else(|| "Unknown".to_string());
     let modified_marker = if dialog.is_modified() { " [modified]" } else { "" };
     let filename_widget = Paragraph::new(format!("{}{}", filename, modified_marker))
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(theme.highlight))
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(filename_widget, chunks[0]);
 
@@ -176,14 +178,14 @@ pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect) {
             Span::raw(before),
             Span::styled(
                 cursor_char.to_string(),
-                Style::default().bg(Color::White).fg(Color::Black),
+                Style::default().bg(theme.fg).fg(theme.bg),
             ),
             Span::raw(rest),
         ])
     } else {
         Line::from(vec![
             Span::raw(&dialog.text),
-            Span::styled(" ", Style::default().bg(Color::White)),
+            Span::styled(" ", Style::default().bg(theme.fg)),
         ])
     };
 
@@ -192,7 +194,7 @@ pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Green))
+                .border_style(Style::default().fg(theme.success))
                 .title(" Description (Ctrl+Enter to save) "),
         );
     frame.render_widget(text_widget, chunks[1]);
@@ -203,7 +205,7 @@ pub fn render(frame: &mut Frame, dialog: &EditDescriptionDialog, area: Rect) {
         Line::from("Ctrl+U=clear | Ctrl+R=revert | Arrows=move cursor"),
     ];
     let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[2]);
 }
@@ -0,0 +1,279 @@
+//! Every metadata field of the selected photo, one per row, with `Enter`
+//! showing a field in full: long lens names, descriptions and the complete
+//! EXIF dump, or a world map for GPS coordinates.
+
+use ratatui::{
+    prelude::*,
+    widgets::canvas::{Canvas, Map, MapResolution},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::db::PhotoMetadata;
+use crate::ui::theme::Theme;
+
+/// A field's full value
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Text(String),
+    Gps { lat: f64, lon: f64 },
+}
+
+impl FieldValue {
+    /// The value on one line, as the table shows it before truncation
+    fn summary(&self) -> String {
+        match self {
+            FieldValue::Text(text) => text.split_whitespace().collect::<Vec<_>>().join(" "),
+            FieldValue::Gps { lat, lon } => format!("{:.6}, {:.6}", lat, lon),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataField {
+    pub label: &'static str,
+    pub value: FieldValue,
+}
+
+pub struct ExpandedFieldDialog {
+    pub filename: String,
+    pub fields: Vec<MetadataField>,
+    pub selected_index: usize,
+    /// Scroll offset of the field shown in full, when one is
+    pub expanded: Option<u16>,
+}
+
+impl ExpandedFieldDialog {
+    pub fn new(filename: String, fields: Vec<MetadataField>) -> Self {
+        Self {
+            filename,
+            fields,
+            selected_index: 0,
+            expanded: None,
+        }
+    }
+
+    /// Rows for a scanned photo; `all_exif` is the JSON object stored at
+    /// scan time and is shown pretty-printed.
+    pub fn from_metadata(meta: &PhotoMetadata, all_exif: Option<&str>) -> Self {
+        let mut fields = Vec::new();
+        let mut text = |label: &'static str, value: Option<String>| {
+            if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+                fields.push(MetadataField { label, value: FieldValue::Text(value) });
+            }
+        };
+
+        text("Path", Some(meta.path.clone()));
+        text("Dimensions", meta.width.zip(meta.height).map(|(w, h)| format!("{}x{}", w, h)));
+        text("Format", meta.format.clone());
+        let camera: Vec<&str> = [meta.camera_make.as_deref(), meta.camera_model.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        text("Camera", (!camera.is_empty()).then(|| camera.join(" ")));
+        text("Lens", meta.lens.clone());
+        text("Aperture", meta.aperture.map(|a| format!("f/{:.1}", a)));
+        text("Shutter", meta.shutter_speed.as_ref().map(|s| format!("{}s", s)));
+        text("ISO", meta.iso.map(|iso| iso.to_string()));
+        text("Focal length", meta.focal_length.map(|f| format!("{:.0}mm", f)));
+        text("Taken", meta.taken_at.clone());
+        text("People", (!meta.people_names.is_empty()).then(|| meta.people_names.join(", ")));
+        text("Tags", meta.tags.as_deref().and_then(|t| serde_json::from_str::<Vec<String>>(t).ok()).map(|t| t.join(", ")));
+        text("Description", meta.description.clone());
+        text("SHA-256", meta.sha256_hash.clone());
+        text("Scanned", meta.scanned_at.clone());
+        text("All EXIF", all_exif.map(pretty_json));
+
+        if let (Some(lat), Some(lon)) = (meta.gps_latitude, meta.gps_longitude) {
+            let index = fields.iter().position(|f| f.label == "Taken").map_or(fields.len(), |i| i + 1);
+            fields.insert(index, MetadataField { label: "GPS", value: FieldValue::Gps { lat, lon } });
+        }
+
+        Self::new(meta.filename.clone(), fields)
+    }
+
+    pub fn selected_field(&self) -> Option<&MetadataField> {
+        self.fields.get(self.selected_index)
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected_index + 1 < self.fields.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    pub fn expand(&mut self) {
+        if self.selected_field().is_some() {
+            self.expanded = Some(0);
+        }
+    }
+
+    /// Back to the table
+    pub fn collapse(&mut self) {
+        self.expanded = None;
+    }
+
+    pub fn scroll_down(&mut self, lines: u16) {
+        if let Some(ref mut offset) = self.expanded {
+            *offset = offset.saturating_add(lines);
+        }
+    }
+
+    pub fn scroll_up(&mut self, lines: u16) {
+        if let Some(ref mut offset) = self.expanded {
+            *offset = offset.saturating_sub(lines);
+        }
+    }
+}
+
+/// EXIF JSON with one field per line and keys in order, or the text as it
+/// is when it does not parse
+fn pretty_json(json: &str) -> String {
+    serde_json::from_str::<std::collections::BTreeMap<String, serde_json::Value>>(json)
+        .ok()
+        .and_then(|fields| serde_json::to_string_pretty(&fields).ok())
+        .unwrap_or_else(|| json.to_string())
+}
+
+/// `text` cut to `width` characters, ending in `…` when cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+pub fn render(frame: &mut Frame, dialog: &ExpandedFieldDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 80.min(area.width.saturating_sub(4));
+    let dialog_height = 30.min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let title = match (dialog.expanded, dialog.selected_field()) {
+        (Some(_), Some(field)) => format!(" {} - {} ", dialog.filename, field.label),
+        _ => format!(" {} ", dialog.filename),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(title);
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+
+    let help = match (dialog.expanded, dialog.selected_field()) {
+        (Some(offset), Some(field)) => {
+            render_field(frame, field, offset, chunks[0], theme);
+            " j/k=scroll  Esc=back"
+        }
+        _ => {
+            render_table(frame, dialog, chunks[0], theme);
+            " j/k=move  Enter=show in full  Esc=close"
+        }
+    };
+
+    let help = Paragraph::new(help)
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, chunks[1]);
+}
+
+fn render_table(frame: &mut Frame, dialog: &ExpandedFieldDialog, area: Rect, theme: &Theme) {
+    let label_width = dialog.fields.iter().map(|f| f.label.len()).max().unwrap_or(0);
+    let value_width = (area.width as usize).saturating_sub(label_width + 3);
+
+    let items: Vec<ListItem> = dialog
+        .fields
+        .iter()
+        .map(|field| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {:<width$}  ", field.label, width = label_width), Style::default().fg(theme.muted)),
+                Span::raw(truncate(&field.value.summary(), value_width)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(theme.selected_bg)
+            .fg(theme.selected_fg)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(dialog.selected_index));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_field(frame: &mut Frame, field: &MetadataField, offset: u16, area: Rect, theme: &Theme) {
+    match field.value {
+        FieldValue::Text(ref text) => {
+            let paragraph = Paragraph::new(text.as_str())
+                .wrap(Wrap { trim: false })
+                .scroll((offset, 0));
+            frame.render_widget(paragraph, area);
+        }
+        FieldValue::Gps { lat, lon } => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            frame.render_widget(Paragraph::new(field.value.summary()), chunks[0]);
+
+            let (map_color, marker_color) = (theme.muted, theme.error);
+            let map = Canvas::default()
+                .marker(symbols::Marker::Braille)
+                .x_bounds([-180.0, 180.0])
+                .y_bounds([-90.0, 90.0])
+                .paint(move |ctx| {
+                    ctx.draw(&Map { resolution: MapResolution::High, color: map_color });
+                    ctx.layer();
+                    ctx.print(lon, lat, Span::styled("●", Style::default().fg(marker_color)));
+                });
+            frame.render_widget(map, chunks[1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_metadata() {
+        let meta = PhotoMetadata {
+            path: "/photos/IMG_1.jpg".to_string(),
+            filename: "IMG_1.jpg".to_string(),
+            lens: Some("FE 24-70mm F2.8 GM II".to_string()),
+            taken_at: Some("2024-05-01 10:30:00".to_string()),
+            gps_latitude: Some(51.5),
+            gps_longitude: Some(-0.12),
+            ..Default::default()
+        };
+        let dialog = ExpandedFieldDialog::from_metadata(&meta, Some(r#"{"primary:Model":"ILCE-7M4","primary:Make":"SONY"}"#));
+        let labels: Vec<&str> = dialog.fields.iter().map(|f| f.label).collect();
+        assert_eq!(labels, ["Path", "Lens", "Taken", "GPS", "All EXIF"]);
+        assert_eq!(dialog.fields[3].value, FieldValue::Gps { lat: 51.5, lon: -0.12 });
+        assert_eq!(
+            dialog.fields[4].value,
+            FieldValue::Text("{\n  \"primary:Make\": \"SONY\",\n  \"primary:Model\": \"ILCE-7M4\"\n}".to_string())
+        );
+
+        assert_eq!(truncate("FE 24-70mm F2.8 GM II", 8), "FE 24-7…");
+        assert_eq!(truncate("f/2.8", 8), "f/2.8");
+    }
+}
@@ -5,6 +5,7 @@ use ratatui::{
 use std::path::PathBuf;
 
 use crate::export::ExportFormat;
+use crate::ui::theme::Theme;
 
 /// State for the export dialog
 pub struct ExportDialog {
@@ -16,17 +17,33 @@ pub struct ExportDialog {
     formats: Vec<ExportFormat>,
     /// Selected format index
     selected_index: usize,
+    /// Write XMP sidecars next to each photo instead of into the output directory
+    pub sidecars_beside_photos: bool,
 }
 
 impl ExportDialog {
     pub fn new(default_dir: PathBuf) -> Self {
-        let formats = vec![ExportFormat::Json, ExportFormat::Csv, ExportFormat::Html];
+        let formats = vec![
+            ExportFormat::Json,
+            ExportFormat::Csv,
+            ExportFormat::Html,
+            ExportFormat::LightroomCatalog,
+            ExportFormat::PhotoPrism,
+        ];
 
         Self {
             format: ExportFormat::Json,
             output_path: default_dir.join("clepho_export.json"),
             formats,
             selected_index: 0,
+            sidecars_beside_photos: false,
+        }
+    }
+
+    /// Switch XMP sidecars between the output directory and beside each photo.
+    pub fn toggle_sidecar_location(&mut self) {
+        if self.format == ExportFormat::LightroomCatalog {
+            self.sidecars_beside_photos = !self.sidecars_beside_photos;
         }
     }
 
@@ -53,7 +70,11 @@ impl ExportDialog {
             .unwrap_or_else(|| "clepho_export".to_string());
 
         if let Some(parent) = self.output_path.parent() {
-            self.output_path = parent.join(format!("{}.{}", stem, self.format.extension()));
+            self.output_path = match self.format.extension() {
+                // Sidecars go into a directory
+                "" => parent.join(stem),
+                ext => parent.join(format!("{}.{}", stem, ext)),
+            };
         }
     }
 
@@ -66,10 +87,10 @@ impl ExportDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 60.min(area.width.saturating_sub(4));
-    let dialog_height = 15.min(area.height.saturating_sub(4));
+    let dialog_height = 17.min(area.height.saturating_sub(4));
 
     let x = (area.width - dialog_width) / 2;
     let y = (area.height - dialog_height) / 2;
@@ -85,7 +106,7 @@ pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect) {
         .margin(1)
         .constraints([
             Constraint::Length(2), // Header
-            Constraint::Length(5), // Format selection
+            Constraint::Length(7), // Format selection
             Constraint::Length(3), // Output path
             Constraint::Length(2), // Footer
         ])
@@ -94,14 +115,14 @@ pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect) {
     // Draw border
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green))
+        .border_style(Style::default().fg(theme.success))
         .title(" Export Photos ")
         .title_style(Style::default().add_modifier(Modifier::BOLD));
     frame.render_widget(block, dialog_area);
 
     // Header
     let header = Paragraph::new("Select export format:")
-        .style(Style::default().fg(Color::Green));
+        .style(Style::default().fg(theme.success));
     frame.render_widget(header, chunks[0]);
 
     // Format selection
@@ -113,6 +134,8 @@ pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect) {
                 ExportFormat::Json => "JSON - Full metadata export",
                 ExportFormat::Csv => "CSV  - Spreadsheet compatible",
                 ExportFormat::Html => "HTML - Visual gallery report",
+                ExportFormat::LightroomCatalog => "XMP  - Lightroom sidecar per photo",
+                ExportFormat::PhotoPrism => "YAML - PhotoPrism sidecar per photo",
             };
             ListItem::new(desc)
         })
@@ -122,8 +145,8 @@ pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect) {
         .block(Block::default().borders(Borders::ALL).title(" Format "))
         .highlight_style(
             Style::default()
-                .bg(Color::Green)
-                .fg(Color::Black)
+                .bg(theme.success)
+                .fg(theme.bg)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -132,13 +155,27 @@ pub fn render(frame: &mut Frame, dialog: &ExportDialog, area: Rect) {
     frame.render_stateful_widget(list, chunks[1], &mut state);
 
     // Output path
-    let output = Paragraph::new(format!("Output: {}", dialog.output_path.display()))
-        .style(Style::default().fg(Color::DarkGray))
-        .block(Block::default().borders(Borders::ALL).title(" Output File "));
+    let (output_text, output_title) = match dialog.format {
+        ExportFormat::LightroomCatalog if dialog.sidecars_beside_photos => {
+            ("Output: next to each photo".to_string(), " Output ")
+        }
+        ExportFormat::LightroomCatalog | ExportFormat::PhotoPrism => {
+            (format!("Output: {}", dialog.output_path.display()), " Output Directory ")
+        }
+        _ => (format!("Output: {}", dialog.output_path.display()), " Output File "),
+    };
+    let output = Paragraph::new(output_text)
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::ALL).title(output_title));
     frame.render_widget(output, chunks[2]);
 
     // Footer
-    let footer = Paragraph::new("j/k: select | Enter: export | Esc: cancel")
-        .style(Style::default().fg(Color::DarkGray));
+    let footer_text = if dialog.format == ExportFormat::LightroomCatalog {
+        "j/k: select | s: beside photos | Enter: export | Esc: cancel"
+    } else {
+        "j/k: select | Enter: export | Esc: cancel"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(theme.muted));
     frame.render_widget(footer, chunks[3]);
 }
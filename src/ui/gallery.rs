@@ -5,14 +5,20 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
 };
 use ratatui_image::StatefulImage;
+use std::collections::hash_map::RandomState;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::hash::BuildHasher;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use image::{DynamicImage, imageops::FilterType};
 use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
 
 use crate::app::App;
-use crate::config::ImageProtocol;
+use crate::config::{DatabaseConfig, ImageProtocol};
+use crate::db::events::parse_taken_at;
+use crate::db::Database;
+use crate::scanner::ThumbnailManager;
+use crate::ui::theme::Theme;
 
 /// Thumbnail size options for gallery view
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -70,31 +76,48 @@ impl ThumbnailSize {
 
 /// Sort options for gallery
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum SortOption {
+pub enum GallerySort {
     #[default]
     Name,
+    /// Newest `taken_at` first
     Date,
+    /// Largest first
     Size,
+    /// Highest rating first
+    Rating,
+    Random,
 }
 
-impl SortOption {
+impl GallerySort {
     pub fn cycle(&self) -> Self {
         match self {
-            SortOption::Name => SortOption::Date,
-            SortOption::Date => SortOption::Size,
-            SortOption::Size => SortOption::Name,
+            GallerySort::Name => GallerySort::Date,
+            GallerySort::Date => GallerySort::Size,
+            GallerySort::Size => GallerySort::Rating,
+            GallerySort::Rating => GallerySort::Random,
+            GallerySort::Random => GallerySort::Name,
         }
     }
 
     pub fn label(&self) -> &'static str {
         match self {
-            SortOption::Name => "Name",
-            SortOption::Date => "Date",
-            SortOption::Size => "Size",
+            GallerySort::Name => "Name",
+            GallerySort::Date => "Date",
+            GallerySort::Size => "Size",
+            GallerySort::Rating => "Rating",
+            GallerySort::Random => "Random",
         }
     }
+
+    /// Whether the sort reads `taken_at` or rating from the database
+    fn uses_metadata(&self) -> bool {
+        matches!(self, GallerySort::Date | GallerySort::Rating)
+    }
 }
 
+/// `taken_at` and rating of an image, for sorting
+type SortMetadata = (Option<String>, Option<u8>);
+
 /// Selection mode for gallery
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SelectionMode {
@@ -105,6 +128,13 @@ pub enum SelectionMode {
     Visual,
 }
 
+/// Rating and color label shown over a thumbnail
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Badges {
+    pub rating: Option<i32>,
+    pub color_label: Option<String>,
+}
+
 /// Gallery view state
 pub struct GalleryView {
     /// All image paths in the current directory
@@ -116,7 +146,11 @@ pub struct GalleryView {
     /// Current thumbnail size setting
     pub thumbnail_size: ThumbnailSize,
     /// Current sort option
-    pub sort_by: SortOption,
+    pub sort_by: GallerySort,
+    /// `taken_at` and rating per image, loaded in the background on open
+    pub metadata_cache: HashMap<PathBuf, SortMetadata>,
+    /// Receiver for the background metadata load
+    metadata_receiver: Option<mpsc::Receiver<HashMap<PathBuf, SortMetadata>>>,
     /// Image picker for protocol detection
     picker: Option<Picker>,
     /// Cache of loaded thumbnail images
@@ -129,8 +163,17 @@ pub struct GalleryView {
     sender: mpsc::Sender<(PathBuf, DynamicImage)>,
     /// Track last rendered areas to avoid unnecessary re-encoding
     last_render_areas: HashMap<PathBuf, Rect>,
+    /// Rating and color label per image, filled in as images scroll into view
+    badges: HashMap<PathBuf, Badges>,
+    /// On-disk thumbnail cache, read before decoding the full image
+    thumbnails: Option<ThumbnailManager>,
     /// Directory being viewed
     pub directory: PathBuf,
+    /// Header label for galleries that are not one directory, e.g. a day
+    pub title: Option<String>,
+    /// Manual album shown in its stored order, which `J`/`K` rearrange.
+    /// Cleared once the gallery is sorted.
+    pub album_id: Option<i64>,
     /// Set of selected indices (for multi-select)
     pub selected_indices: HashSet<usize>,
     /// Selection mode (normal or visual)
@@ -152,14 +195,20 @@ impl GalleryView {
             selected: 0,
             scroll_offset: 0,
             thumbnail_size: ThumbnailSize::default(),
-            sort_by: SortOption::default(),
+            sort_by: GallerySort::default(),
+            metadata_cache: HashMap::new(),
+            metadata_receiver: None,
             picker,
             thumbnail_cache: HashMap::new(),
             loading: HashSet::new(),
             receiver: Some(rx),
             sender: tx,
             directory,
+            title: None,
+            album_id: None,
             last_render_areas: HashMap::new(),
+            badges: HashMap::new(),
+            thumbnails: None,
             selected_indices: HashSet::new(),
             selection_mode: SelectionMode::Normal,
             visual_anchor: None,
@@ -168,6 +217,42 @@ impl GalleryView {
         }
     }
 
+    /// Read thumbnails from the on-disk cache when it has them.
+    pub fn with_thumbnails(mut self, thumbnails: ThumbnailManager) -> Self {
+        self.thumbnails = Some(thumbnails);
+        self
+    }
+
+    /// Load `taken_at` and ratings for the date and rating sorts in the
+    /// background.
+    pub fn with_sort_metadata(mut self, db_config: &DatabaseConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let db_config = db_config.clone();
+        let images = self.images.clone();
+        std::thread::spawn(move || {
+            let db = match Database::open_read_only(&db_config) {
+                Ok(db) => db,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to open database for gallery sorting");
+                    return;
+                }
+            };
+            let metadata = images
+                .into_iter()
+                .map(|path| {
+                    let entry = match db.get_photo_metadata(&path) {
+                        Ok(Some(m)) => (m.taken_at, m.rating.and_then(|r| u8::try_from(r).ok())),
+                        _ => (None, None),
+                    };
+                    (path, entry)
+                })
+                .collect();
+            let _ = tx.send(metadata);
+        });
+        self.metadata_receiver = Some(rx);
+        self
+    }
+
     /// Update cached layout values from render. Called during render to keep navigation in sync.
     pub fn update_layout_cache(&mut self, columns: usize, visible_rows: usize) {
         self.cached_columns = columns;
@@ -187,12 +272,20 @@ impl GalleryView {
     fn create_picker(protocol: ImageProtocol) -> Option<Picker> {
         match protocol {
             ImageProtocol::None => None,
-            _ => Picker::from_query_stdio().ok(),
+            _ => crate::ui::preview::picker_for(Picker::from_query_stdio().ok(), protocol),
         }
     }
 
     /// Poll for completed async thumbnail loads
     pub fn poll_async_loads(&mut self) {
+        if let Some(metadata) = self.metadata_receiver.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.metadata_receiver = None;
+            self.metadata_cache = metadata;
+            if self.sort_by.uses_metadata() {
+                self.sort_images();
+            }
+        }
+
         if let Some(ref receiver) = self.receiver {
             while let Ok((path, dyn_img)) = receiver.try_recv() {
                 self.loading.remove(&path);
@@ -222,6 +315,23 @@ impl GalleryView {
         (area_height / cell_height).max(1) as usize
     }
 
+    /// Index of the thumbnail under (x, y), using the layout from the last
+    /// render of a gallery filling `area`.
+    pub fn hit_test(&self, area: Rect, x: u16, y: u16) -> Option<usize> {
+        // Grid sits between the 1-row header and the 2-row footer
+        let grid_top = area.y + 1;
+        if x < area.x || y < grid_top || y >= area.bottom().saturating_sub(2) {
+            return None;
+        }
+        let col = ((x - area.x) / self.thumbnail_size.cell_width()) as usize;
+        let row = ((y - grid_top) / self.thumbnail_size.cell_height()) as usize;
+        if col >= self.cached_columns || row >= self.cached_visible_rows {
+            return None;
+        }
+        let index = (self.scroll_offset + row) * self.cached_columns + col;
+        (index < self.images.len()).then_some(index)
+    }
+
     /// Get total number of rows
     #[allow(dead_code)]
     pub fn total_rows(&self, columns: usize) -> usize {
@@ -366,22 +476,14 @@ impl GalleryView {
             let sender = self.sender.clone();
             let size = self.thumbnail_size.pixel_size();
             let rotation = rotation_degrees;
+            // Cached thumbnails smaller than the cells would look blurry
+            let thumbnails = self.thumbnails.clone().filter(|t| t.size() >= size);
 
             std::thread::spawn(move || {
-                if let Ok(img) = image::ImageReader::open(&path_clone)
-                    .and_then(|r| r.decode().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
-                {
-                    let resized = img.resize(size, size, FilterType::Triangle);
-                    // Apply rotation
-                    let rotated = match rotation {
-                        90 => resized.rotate90(),
-                        180 => resized.rotate180(),
-                        270 => resized.rotate270(),
-                        _ => resized,
-                    };
+                if let Some(img) = decode_thumbnail(thumbnails, &path_clone, rotation, size) {
                     // Send with rotation-aware cache key
                     let cache_key = PathBuf::from(format!("{}#{}", path_clone.display(), rotation));
-                    let _ = sender.send((cache_key, rotated));
+                    let _ = sender.send((cache_key, img));
                 }
             });
         }
@@ -389,12 +491,48 @@ impl GalleryView {
         None
     }
 
+    /// Show `path` at its new `rotation`. Its old thumbnails are dropped and
+    /// the rotated one is generated into the on-disk cache in the
+    /// background, so the cell is redrawn on the first render after that.
+    pub fn refresh_rotated(&mut self, path: &Path, rotation: i32) {
+        let prefix = format!("{}#", path.display());
+        self.thumbnail_cache.retain(|key, _| !key.to_string_lossy().starts_with(&prefix));
+        self.loading.retain(|key| !key.to_string_lossy().starts_with(&prefix));
+        self.last_render_areas.remove(path);
+
+        // Without a thumbnail cache the cell decodes the original as usual
+        let Some(thumbnails) = self.thumbnails.clone() else { return };
+        let size = self.thumbnail_size.pixel_size();
+        let cache_key = PathBuf::from(format!("{}{}", prefix, rotation));
+        self.loading.insert(cache_key.clone());
+        let sender = self.sender.clone();
+        let path = path.to_path_buf();
+        std::thread::spawn(move || {
+            if let Err(e) = thumbnails.generate(&path, rotation) {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to regenerate thumbnail");
+            }
+            let thumbnails = Some(thumbnails).filter(|t| t.size() >= size);
+            if let Some(img) = decode_thumbnail(thumbnails, &path, rotation, size) {
+                let _ = sender.send((cache_key, img));
+            }
+        });
+    }
+
     /// Check if a thumbnail is currently loading (rotation-aware)
     pub fn is_loading(&self, path: &PathBuf, rotation_degrees: i32) -> bool {
         let cache_key = PathBuf::from(format!("{}#{}", path.display(), rotation_degrees));
         self.loading.contains(&cache_key)
     }
 
+    /// Whether the rating and label of `path` still need looking up
+    pub fn needs_badges(&self, path: &PathBuf) -> bool {
+        !self.badges.contains_key(path)
+    }
+
+    pub fn set_badges(&mut self, path: PathBuf, badges: Badges) {
+        self.badges.insert(path, badges);
+    }
+
     /// Clear thumbnail cache (e.g., when changing thumbnail size)
     pub fn clear_cache(&mut self) {
         self.thumbnail_cache.clear();
@@ -421,28 +559,78 @@ impl GalleryView {
         self.sort_images();
     }
 
+    /// Sort by `sort`, e.g. an album's auto sort when it opens
+    pub fn set_sort(&mut self, sort: GallerySort) {
+        self.sort_by = sort;
+        self.sort_images();
+    }
+
+    /// Swap the image under the cursor with the one at `target`, keeping
+    /// the cursor and selection on the same images
+    pub fn swap_selected_with(&mut self, target: usize) {
+        if self.selected >= self.images.len() || target >= self.images.len() {
+            return;
+        }
+        let current = self.selected;
+        self.images.swap(current, target);
+        let (had_current, had_target) = (self.selected_indices.remove(&current), self.selected_indices.remove(&target));
+        if had_current {
+            self.selected_indices.insert(target);
+        }
+        if had_target {
+            self.selected_indices.insert(current);
+        }
+        self.selected = target;
+    }
+
+    /// Reorder `images` by `sort_by`, keeping the cursor and selection on
+    /// the same images. Images without a date or rating go last, by name.
     fn sort_images(&mut self) {
+        let current = self.images.get(self.selected).cloned();
+        let selected: HashSet<PathBuf> = self.get_selected_paths().into_iter().collect();
+        self.album_id = None;
+
+        self.images.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
         match self.sort_by {
-            SortOption::Name => {
-                self.images.sort_by(|a, b| {
-                    a.file_name().cmp(&b.file_name())
+            GallerySort::Name => {}
+            GallerySort::Date => {
+                let cache = &self.metadata_cache;
+                self.images.sort_by_cached_key(|path| {
+                    let taken = cache.get(path).and_then(|(taken_at, _)| taken_at.as_deref().and_then(parse_taken_at));
+                    (taken.is_none(), std::cmp::Reverse(taken))
                 });
             }
-            SortOption::Date => {
-                self.images.sort_by(|a, b| {
-                    let a_time = std::fs::metadata(a).and_then(|m| m.modified()).ok();
-                    let b_time = std::fs::metadata(b).and_then(|m| m.modified()).ok();
-                    b_time.cmp(&a_time) // Newest first
+            GallerySort::Size => {
+                self.images.sort_by_cached_key(|path| {
+                    std::cmp::Reverse(std::fs::metadata(path).map(|m| m.len()).unwrap_or(0))
                 });
             }
-            SortOption::Size => {
-                self.images.sort_by(|a, b| {
-                    let a_size = std::fs::metadata(a).map(|m| m.len()).unwrap_or(0);
-                    let b_size = std::fs::metadata(b).map(|m| m.len()).unwrap_or(0);
-                    b_size.cmp(&a_size) // Largest first
+            GallerySort::Rating => {
+                let cache = &self.metadata_cache;
+                self.images.sort_by_cached_key(|path| {
+                    let rating = cache.get(path).and_then(|(_, rating)| *rating);
+                    (rating.is_none(), std::cmp::Reverse(rating))
                 });
             }
+            GallerySort::Random => {
+                // A fresh RandomState hashes every path to a new random key
+                let state = RandomState::new();
+                self.images.sort_by_cached_key(|path| state.hash_one(path));
+            }
+        }
+
+        if let Some(current) = current {
+            self.selected = self.images.iter().position(|p| *p == current).unwrap_or(0);
         }
+        self.selected_indices = self
+            .images
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| selected.contains(*p))
+            .map(|(i, _)| i)
+            .collect();
+        self.visual_anchor = None;
+        self.selection_mode = SelectionMode::Normal;
     }
 
     // === Selection Methods ===
@@ -539,8 +727,29 @@ impl GalleryView {
     }
 }
 
+/// Thumbnail of `path` at `rotation` fitting `size`, from the on-disk cache
+/// when given one that has it, otherwise decoded from the original.
+fn decode_thumbnail(thumbnails: Option<ThumbnailManager>, path: &Path, rotation: i32, size: u32) -> Option<DynamicImage> {
+    // Cached thumbnails already have the rotation applied
+    let cached = thumbnails
+        .and_then(|t| t.get_cached_path(path, rotation))
+        .and_then(|p| image::open(p).ok());
+    if let Some(img) = cached {
+        return Some(img.resize(size, size, FilterType::Triangle));
+    }
+    let img = image::ImageReader::open(path).ok()?.decode().ok()?;
+    let resized = img.resize(size, size, FilterType::Triangle);
+    Some(match rotation {
+        90 => resized.rotate90(),
+        180 => resized.rotate180(),
+        270 => resized.rotate270(),
+        _ => resized,
+    })
+}
+
 /// Render the gallery view
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
     // First pass: poll async loads, compute layout, and collect visible paths
     let (columns, visible_rows, visible_paths) = {
         let gallery = match app.gallery_view.as_mut() {
@@ -576,6 +785,25 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         rotations.insert(path.clone(), rotation);
     }
 
+    // Load ratings and labels once per image, through the metadata cache
+    let show_rating = app.config.gallery.show_rating_overlay;
+    let show_label = app.config.gallery.show_label_overlay;
+    if show_rating || show_label {
+        for path in &visible_paths {
+            let needed = app.gallery_view.as_ref().is_some_and(|g| g.needs_badges(path));
+            if !needed {
+                continue;
+            }
+            let badges = app
+                .get_photo_metadata(path)
+                .map(|m| Badges { rating: m.rating, color_label: m.color_label })
+                .unwrap_or_default();
+            if let Some(gallery) = app.gallery_view.as_mut() {
+                gallery.set_badges(path.clone(), badges);
+            }
+        }
+    }
+
     // Second pass: render with pre-computed rotations
     let gallery = app.gallery_view.as_mut().unwrap();
 
@@ -590,19 +818,21 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         .split(area);
 
     // Render header
-    render_header(frame, gallery, chunks[0]);
+    render_header(frame, gallery, chunks[0], &theme);
 
     // Render thumbnail grid with pre-computed rotations
-    render_grid(frame, gallery, &rotations, chunks[1], columns, visible_rows);
+    render_grid(frame, gallery, &rotations, chunks[1], columns, visible_rows, (show_rating, show_label), &theme);
 
     // Render footer with controls
-    render_footer(frame, gallery, chunks[2]);
+    render_footer(frame, gallery, chunks[2], &theme);
 }
 
-fn render_header(frame: &mut Frame, gallery: &GalleryView, area: Rect) {
-    let dir_name = gallery.directory.file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| gallery.directory.to_string_lossy().to_string());
+fn render_header(frame: &mut Frame, gallery: &GalleryView, area: Rect, theme: &Theme) {
+    let dir_name = gallery.title.clone().unwrap_or_else(|| {
+        gallery.directory.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| gallery.directory.to_string_lossy().to_string())
+    });
 
     let header = format!(
         " Gallery: {} | {} images | Sort: {} | Size: {:?}",
@@ -613,11 +843,12 @@ fn render_header(frame: &mut Frame, gallery: &GalleryView, area: Rect) {
     );
 
     let paragraph = Paragraph::new(header)
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        .style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD));
     frame.render_widget(paragraph, area);
 }
 
-fn render_grid(frame: &mut Frame, gallery: &mut GalleryView, rotations: &std::collections::HashMap<std::path::PathBuf, i32>, area: Rect, columns: usize, visible_rows: usize) {
+#[allow(clippy::too_many_arguments)]
+fn render_grid(frame: &mut Frame, gallery: &mut GalleryView, rotations: &std::collections::HashMap<std::path::PathBuf, i32>, area: Rect, columns: usize, visible_rows: usize, overlays: (bool, bool), theme: &Theme) {
     let cell_width = gallery.thumbnail_size.cell_width();
     let cell_height = gallery.thumbnail_size.cell_height();
 
@@ -654,12 +885,13 @@ fn render_grid(frame: &mut Frame, gallery: &mut GalleryView, rotations: &std::co
                 let path = gallery.images[image_idx].clone();
                 // Use pre-computed rotation (cached to avoid per-frame DB queries)
                 let rotation = rotations.get(&path).copied().unwrap_or(0);
-                render_thumbnail_cell(frame, gallery, &path, *cell_area, is_cursor, is_selected, rotation);
+                render_thumbnail_cell(frame, gallery, &path, *cell_area, is_cursor, is_selected, rotation, overlays, theme);
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_thumbnail_cell(
     frame: &mut Frame,
     gallery: &mut GalleryView,
@@ -668,14 +900,16 @@ fn render_thumbnail_cell(
     is_cursor: bool,
     is_selected: bool,
     rotation_degrees: i32,
+    (show_rating, show_label): (bool, bool),
+    theme: &Theme,
 ) {
     // Create block with selection highlighting
     // Cursor = current position (cyan), Selected = in selection set (green)
     let (border_color, border_type) = match (is_cursor, is_selected) {
-        (true, true) => (Color::Yellow, Borders::ALL),    // Cursor + Selected
-        (true, false) => (Color::Cyan, Borders::ALL),     // Cursor only
-        (false, true) => (Color::Green, Borders::ALL),    // Selected only
-        (false, false) => (Color::DarkGray, Borders::ALL), // Neither
+        (true, true) => (theme.highlight, Borders::ALL),    // Cursor + Selected
+        (true, false) => (theme.accent, Borders::ALL),     // Cursor only
+        (false, true) => (theme.success, Borders::ALL),    // Selected only
+        (false, false) => (theme.muted, Borders::ALL), // Neither
     };
 
     let filename = path.file_name()
@@ -712,7 +946,7 @@ fn render_thumbnail_cell(
     } else if gallery.is_loading(path, rotation_degrees) {
         // Show loading indicator
         let loading = Paragraph::new("Loading...")
-            .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+            .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC))
             .alignment(Alignment::Center);
 
         // Center vertically
@@ -724,7 +958,7 @@ fn render_thumbnail_cell(
     } else {
         // Show placeholder
         let placeholder = Paragraph::new("[ ]")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
 
         if inner.height > 1 {
@@ -733,9 +967,44 @@ fn render_thumbnail_cell(
             frame.render_widget(placeholder, centered);
         }
     }
+
+    let badges = gallery.badges.get(path).cloned().unwrap_or_default();
+    if show_rating {
+        if let Some(rating) = badges.rating.filter(|r| *r > 0) {
+            let stars = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+            let row = Paragraph::new(rating_stars(rating))
+                .style(Style::default().fg(theme.highlight).bg(theme.bg))
+                .alignment(Alignment::Center);
+            frame.render_widget(row, stars);
+        }
+    }
+    if show_label {
+        if let Some(color) = badges.color_label.as_deref().and_then(label_color) {
+            let corner = Rect::new(inner.x + inner.width - 1, inner.y, 1, 1);
+            frame.render_widget(Paragraph::new("█").style(Style::default().fg(color)), corner);
+        }
+    }
+}
+
+/// Five stars, `rating` of them filled
+fn rating_stars(rating: i32) -> String {
+    let filled = rating.clamp(0, 5) as usize;
+    format!("{}{}", "★".repeat(filled), "☆".repeat(5 - filled))
 }
 
-fn render_footer(frame: &mut Frame, gallery: &GalleryView, area: Rect) {
+/// Terminal color for a stored color label
+fn label_color(label: &str) -> Option<Color> {
+    match label {
+        "red" => Some(Color::Red),
+        "yellow" => Some(Color::Yellow),
+        "green" => Some(Color::Green),
+        "blue" => Some(Color::Blue),
+        "purple" => Some(Color::Magenta),
+        _ => None,
+    }
+}
+
+fn render_footer(frame: &mut Frame, gallery: &GalleryView, area: Rect, theme: &Theme) {
     let selection_count = gallery.selection_count();
     let mode_indicator = match gallery.selection_mode {
         SelectionMode::Normal => "",
@@ -771,11 +1040,11 @@ fn render_footer(frame: &mut Frame, gallery: &GalleryView, area: Rect) {
         .split(area);
 
     let info = Paragraph::new(selected_info)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(theme.highlight));
     frame.render_widget(info, footer_chunks[0]);
 
     let help_text = Paragraph::new(help)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(theme.muted));
     frame.render_widget(help_text, footer_chunks[1]);
 }
 
@@ -796,9 +1065,9 @@ fn format_size(size: u64) -> String {
 }
 
 /// Render gallery help dialog
-pub fn render_help(frame: &mut Frame, area: Rect) {
+pub fn render_help(frame: &mut Frame, area: Rect, theme: &Theme) {
     let dialog_width = 60.min(area.width.saturating_sub(4));
-    let dialog_height = 28.min(area.height.saturating_sub(4));
+    let dialog_height = 32.min(area.height.saturating_sub(4));
 
     let x = (area.width - dialog_width) / 2;
     let y = (area.height - dialog_height) / 2;
@@ -808,7 +1077,7 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
     frame.render_widget(Clear, dialog_area);
 
     let help_text = vec![
-        Line::from(Span::styled("Gallery View", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Gallery View", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from(Span::styled("Navigation", Style::default().add_modifier(Modifier::BOLD))),
         Line::from("  h/Left           Move left"),
@@ -827,14 +1096,15 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
         Line::from("  Esc              Clear selection / Exit visual"),
         Line::from(""),
         Line::from(Span::styled("Actions", Style::default().add_modifier(Modifier::BOLD))),
-        Line::from("  ] / [            Rotate CW / CCW"),
+        Line::from("  r or ] / [       Rotate CW / CCW"),
         Line::from("  d / Delete       Move to trash"),
         Line::from("  y / x            Cut to clipboard"),
         Line::from("  p                Paste from clipboard"),
         Line::from("  S                View image (slideshow)"),
         Line::from("  Enter            Open in external viewer"),
         Line::from("  +/-              Thumbnail size"),
-        Line::from("  s                Cycle sort"),
+        Line::from("  s                Cycle sort (date, rating, …)"),
+        Line::from("  J / K            Move later / earlier in album"),
         Line::from("  q                Exit gallery"),
         Line::from("  ?                Toggle this help"),
     ];
@@ -842,9 +1112,51 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
     let paragraph = Paragraph::new(help_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.accent))
             .title(" Gallery Help "),
     );
 
     frame.render_widget(paragraph, dialog_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_rendering() {
+        assert_eq!(rating_stars(3), "★★★☆☆");
+        assert_eq!(rating_stars(9), "★★★★★");
+        assert_eq!(label_color("purple"), Some(Color::Magenta));
+        assert_eq!(label_color("orange"), None);
+    }
+
+    #[test]
+    fn test_sort_by_metadata() {
+        let paths: Vec<PathBuf> = ["a.jpg", "b.jpg", "c.jpg"].iter().map(PathBuf::from).collect();
+        let mut gallery = GalleryView::new(PathBuf::from("/p"), paths.clone(), ImageProtocol::None);
+        gallery.metadata_cache = HashMap::from([
+            (paths[0].clone(), (Some("2023:01:01 10:00:00".to_string()), Some(2))),
+            (paths[1].clone(), (None, Some(5))),
+            (paths[2].clone(), (Some("2024-06-01 08:00:00".to_string()), None)),
+        ]);
+        gallery.selected = 2;
+        gallery.selected_indices.insert(0);
+
+        gallery.cycle_sort();
+        assert_eq!(gallery.sort_by, GallerySort::Date);
+        assert_eq!(gallery.images, [paths[2].clone(), paths[0].clone(), paths[1].clone()]);
+        assert_eq!(gallery.selected, 0);
+        assert_eq!(gallery.get_selected_paths(), [paths[0].clone()]);
+
+        gallery.sort_by = GallerySort::Size;
+        gallery.cycle_sort();
+        assert_eq!(gallery.sort_by, GallerySort::Rating);
+        assert_eq!(gallery.images, [paths[1].clone(), paths[0].clone(), paths[2].clone()]);
+
+        gallery.cycle_sort();
+        assert_eq!(gallery.images.len(), 3);
+        gallery.cycle_sort();
+        assert_eq!(gallery.images, paths);
+    }
+}
@@ -0,0 +1,385 @@
+//! Dialog for entering GPS coordinates by hand.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use crate::ui::theme::Theme;
+
+/// Which coordinate field has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpsField {
+    Latitude,
+    Longitude,
+}
+
+/// Dialog state for editing a photo's coordinates
+pub struct GpsDialog {
+    pub photo_path: PathBuf,
+    pub photo_id: i64,
+    /// Coordinates stored before editing, with their source ("exif"/"manual")
+    pub existing: Option<(f64, f64, Option<String>)>,
+    pub latitude: String,
+    pub longitude: String,
+    pub active: GpsField,
+    /// Cursor position within the active field
+    pub cursor: usize,
+    pub error: Option<String>,
+    /// Place name from the last geocode lookup
+    pub suggestion: Option<String>,
+    geocode_rx: Option<mpsc::Receiver<Result<String, String>>>,
+}
+
+impl GpsDialog {
+    pub fn new(
+        photo_path: PathBuf,
+        photo_id: i64,
+        existing: Option<(f64, f64)>,
+        source: Option<String>,
+    ) -> Self {
+        let (latitude, longitude) = existing
+            .map(|(lat, lon)| (format!("{:.6}", lat), format!("{:.6}", lon)))
+            .unwrap_or_default();
+        let cursor = latitude.len();
+        Self {
+            photo_path,
+            photo_id,
+            existing: existing.map(|(lat, lon)| (lat, lon, source)),
+            latitude,
+            longitude,
+            active: GpsField::Latitude,
+            cursor,
+            error: None,
+            suggestion: None,
+            geocode_rx: None,
+        }
+    }
+
+    fn active_text(&mut self) -> &mut String {
+        match self.active {
+            GpsField::Latitude => &mut self.latitude,
+            GpsField::Longitude => &mut self.longitude,
+        }
+    }
+
+    /// Switch focus between latitude and longitude
+    pub fn next_field(&mut self) {
+        self.active = match self.active {
+            GpsField::Latitude => GpsField::Longitude,
+            GpsField::Longitude => GpsField::Latitude,
+        };
+        self.cursor = self.active_text().len();
+    }
+
+    pub fn handle_char(&mut self, c: char) {
+        // Degree, minute and second marks are accepted as separators
+        let c = if matches!(c, '°' | '\'' | '"') { ' ' } else { c };
+        if !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | ' ') || "NSEWnsew".contains(c)) {
+            return;
+        }
+        let cursor = self.cursor;
+        self.active_text().insert(cursor, c);
+        self.cursor += 1;
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let cursor = self.cursor;
+            self.active_text().remove(cursor);
+        }
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        let len = self.active_text().len();
+        if self.cursor < len {
+            self.cursor += 1;
+        }
+    }
+
+    /// Parse both fields, or describe what is wrong.
+    pub fn coordinates(&self) -> Result<(f64, f64), String> {
+        let lat = parse_coordinate(&self.latitude, 90.0, ('N', 'S'))
+            .ok_or_else(|| "Invalid latitude (use -33.8688 or 33 52 7.7 S)".to_string())?;
+        let lon = parse_coordinate(&self.longitude, 180.0, ('E', 'W'))
+            .ok_or_else(|| "Invalid longitude (use 151.2093 or 151 12 33.5 E)".to_string())?;
+        Ok((lat, lon))
+    }
+
+    /// Start a background lookup of the place at the entered coordinates.
+    pub fn start_geocode(&mut self) {
+        let (lat, lon) = match self.coordinates() {
+            Ok(coords) => coords,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = crate::geocode::reverse_geocode(lat, lon).map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.geocode_rx = Some(rx);
+        self.suggestion = None;
+        self.error = None;
+    }
+
+    pub fn is_geocoding(&self) -> bool {
+        self.geocode_rx.is_some()
+    }
+
+    /// Pick up a finished geocode lookup.
+    pub fn poll_geocode(&mut self) {
+        let Some(rx) = self.geocode_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(place)) => {
+                self.suggestion = Some(place);
+                self.geocode_rx = None;
+            }
+            Ok(Err(e)) => {
+                self.error = Some(e);
+                self.geocode_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.geocode_rx = None,
+        }
+    }
+}
+
+/// Parse a coordinate in decimal degrees (`-33.8688`) or degrees, minutes
+/// and seconds (`33 52 7.7 S`). `hemispheres` holds the positive and
+/// negative direction letters; values outside `±max` are rejected.
+pub fn parse_coordinate(input: &str, max: f64, hemispheres: (char, char)) -> Option<f64> {
+    let mut text = input.trim().to_uppercase();
+    let mut negative = false;
+
+    // Hemisphere letter at either end
+    for letter in [hemispheres.0, hemispheres.1] {
+        if let Some(rest) = text.strip_suffix(letter).or_else(|| text.strip_prefix(letter)) {
+            negative = letter == hemispheres.1;
+            text = rest.trim().to_string();
+            break;
+        }
+    }
+
+    if let Some(rest) = text.strip_prefix('-') {
+        negative = !negative;
+        text = rest.to_string();
+    } else if let Some(rest) = text.strip_prefix('+') {
+        text = rest.to_string();
+    }
+
+    let parts: Vec<f64> = text
+        .split_whitespace()
+        .map(|p| p.parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0))
+        .collect::<Option<_>>()?;
+
+    let value = match parts[..] {
+        [deg] => deg,
+        [deg, min] if min < 60.0 => deg + min / 60.0,
+        [deg, min, sec] if min < 60.0 && sec < 60.0 => deg + min / 60.0 + sec / 3600.0,
+        _ => return None,
+    };
+
+    if value > max {
+        return None;
+    }
+    Some(if negative { -value } else { value })
+}
+
+fn compass_rose(lat: Option<f64>, lon: Option<f64>, theme: &Theme) -> Vec<Line<'static>> {
+    let on = Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD);
+    let off = Style::default().fg(theme.muted);
+    let pick = |active: bool| if active { on } else { off };
+
+    let north = pick(lat.is_some_and(|v| v > 0.0));
+    let south = pick(lat.is_some_and(|v| v < 0.0));
+    let east = pick(lon.is_some_and(|v| v > 0.0));
+    let west = pick(lon.is_some_and(|v| v < 0.0));
+    let line = Style::default().fg(theme.secondary);
+
+    vec![
+        Line::from(Span::styled("      N", north)),
+        Line::from(Span::styled("      │", line)),
+        Line::from(vec![
+            Span::styled("  W ", west),
+            Span::styled("──┼──", line),
+            Span::styled(" E", east),
+        ]),
+        Line::from(Span::styled("      │", line)),
+        Line::from(Span::styled("      S", south)),
+    ]
+}
+
+fn field_line<'a>(
+    label: &'a str,
+    text: &'a str,
+    cursor: Option<usize>,
+    theme: &Theme,
+) -> Line<'a> {
+    let label_style = if cursor.is_some() {
+        Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.muted)
+    };
+    let mut spans = vec![Span::styled(label, label_style)];
+
+    match cursor {
+        Some(cursor) if cursor < text.len() => {
+            let (before, after) = text.split_at(cursor);
+            spans.push(Span::raw(before));
+            spans.push(Span::styled(&after[..1], Style::default().bg(theme.fg).fg(theme.bg)));
+            spans.push(Span::raw(&after[1..]));
+        }
+        Some(_) => {
+            spans.push(Span::raw(text));
+            spans.push(Span::styled(" ", Style::default().bg(theme.fg)));
+        }
+        None => spans.push(Span::raw(text)),
+    }
+    Line::from(spans)
+}
+
+pub fn render(frame: &mut Frame, dialog: &GpsDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 64.min(area.width.saturating_sub(4));
+    let dialog_height = 16.min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let filename = dialog
+        .photo_path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" GPS Location: {} ", filename));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(7), Constraint::Length(3)])
+        .split(inner);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(30), Constraint::Length(14)])
+        .split(rows[0]);
+
+    let cursor_for = |field: GpsField| (dialog.active == field).then_some(dialog.cursor);
+
+    let current = match &dialog.existing {
+        Some((lat, lon, source)) => format!(
+            "{:.6}, {:.6} ({})",
+            lat,
+            lon,
+            source.as_deref().unwrap_or("exif")
+        ),
+        None => "none".to_string(),
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Current:   ", Style::default().fg(theme.muted)),
+            Span::raw(current),
+        ]),
+        Line::from(""),
+        field_line("Latitude:  ", &dialog.latitude, cursor_for(GpsField::Latitude), theme),
+        field_line("Longitude: ", &dialog.longitude, cursor_for(GpsField::Longitude), theme),
+        Line::from(""),
+    ];
+
+    if dialog.is_geocoding() {
+        lines.push(Line::from(Span::styled(
+            "Looking up place...",
+            Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+        )));
+    } else if let Some(ref place) = dialog.suggestion {
+        lines.push(Line::from(vec![
+            Span::styled("Place:     ", Style::default().fg(theme.muted)),
+            Span::styled(place.as_str(), Style::default().fg(theme.success)),
+        ]));
+    }
+    if let Some(ref error) = dialog.error {
+        lines.push(Line::from(Span::styled(error.as_str(), Style::default().fg(theme.error))));
+    }
+
+    frame.render_widget(Paragraph::new(lines), cols[0]);
+
+    let parsed = dialog.coordinates().ok();
+    let rose = Paragraph::new(compass_rose(
+        parsed.map(|(lat, _)| lat),
+        parsed.map(|(_, lon)| lon),
+        theme,
+    ));
+    frame.render_widget(rose, cols[1]);
+
+    let help = Paragraph::new(vec![
+        Line::from("Decimal (-33.8688) or DMS (33 52 7.7 S)"),
+        Line::from("Tab=next field | Ctrl+L=look up place | Enter=save | Esc=cancel"),
+    ])
+    .style(Style::default().fg(theme.muted))
+    .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, rows[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAT: (char, char) = ('N', 'S');
+    const LON: (char, char) = ('E', 'W');
+
+    fn approx(a: Option<f64>, b: f64) -> bool {
+        a.is_some_and(|a| (a - b).abs() < 1e-6)
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert!(approx(parse_coordinate("-33.8688", 90.0, LAT), -33.8688));
+        assert!(approx(parse_coordinate(" +151.2093 ", 180.0, LON), 151.2093));
+        assert!(approx(parse_coordinate("33.8688 S", 90.0, LAT), -33.8688));
+        assert_eq!(parse_coordinate("91", 90.0, LAT), None);
+        assert_eq!(parse_coordinate("", 90.0, LAT), None);
+    }
+
+    #[test]
+    fn test_parse_dms() {
+        let expected = 33.0 + 52.0 / 60.0 + 7.7 / 3600.0;
+        assert!(approx(parse_coordinate("33 52 7.7 S", 90.0, LAT), -expected));
+        assert!(approx(parse_coordinate("N 33 52 7.7", 90.0, LAT), expected));
+        assert!(approx(parse_coordinate("151 12.5 w", 180.0, LON), -(151.0 + 12.5 / 60.0)));
+        // Minutes and seconds must be below 60; E is not a latitude direction
+        assert_eq!(parse_coordinate("33 60 0", 90.0, LAT), None);
+        assert_eq!(parse_coordinate("33 52 7.7 E", 90.0, LAT), None);
+    }
+
+    #[test]
+    fn test_input_filtering() {
+        let mut dialog = GpsDialog::new(PathBuf::from("a.jpg"), 1, None, None);
+        for c in "33°52'7.7\"Sx".chars() {
+            dialog.handle_char(c);
+        }
+        assert_eq!(dialog.latitude, "33 52 7.7 S");
+        dialog.next_field();
+        dialog.handle_char('1');
+        assert_eq!(dialog.longitude, "1");
+    }
+}
@@ -0,0 +1,111 @@
+//! Luminance histogram drawn with braille characters.
+
+use image::DynamicImage;
+
+/// Character rows of the histogram chart.
+pub const CHART_ROWS: usize = 20;
+
+/// Character columns of the histogram chart.
+pub const CHART_COLUMNS: usize = 32;
+
+/// Braille dot bits for the left and right column, top row first.
+const LEFT_DOTS: [u32; 4] = [0x01, 0x02, 0x04, 0x40];
+const RIGHT_DOTS: [u32; 4] = [0x08, 0x10, 0x20, 0x80];
+
+/// Count pixels per luminance value (Rec. 601 luma, as `to_luma8` computes).
+pub fn luma_histogram(image: &DynamicImage) -> [u32; 256] {
+    let mut buckets = [0u32; 256];
+    for pixel in image.to_luma8().pixels() {
+        buckets[pixel[0] as usize] += 1;
+    }
+    buckets
+}
+
+/// Render a histogram as `rows` lines of `columns` braille characters.
+/// Each character holds two bars of four dots, so buckets are merged into
+/// `columns * 2` bars scaled to the tallest one.
+pub fn braille_chart(buckets: &[u32; 256], columns: usize, rows: usize) -> Vec<String> {
+    let bars = (columns * 2).max(1);
+    let per_bar = 256usize.div_ceil(bars);
+    let sums: Vec<u64> = buckets
+        .chunks(per_bar)
+        .map(|chunk| chunk.iter().map(|&c| c as u64).sum())
+        .chain(std::iter::repeat(0))
+        .take(bars)
+        .collect();
+
+    let dots_high = rows * 4;
+    let max = sums.iter().copied().max().unwrap_or(0).max(1);
+    // Any non-empty bar gets at least one dot so it stays visible
+    let heights: Vec<usize> = sums
+        .iter()
+        .map(|&s| {
+            let h = (s * dots_high as u64).div_ceil(max) as usize;
+            if s > 0 { h.max(1) } else { 0 }
+        })
+        .collect();
+
+    (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|col| {
+                    let mut bits = 0;
+                    for (dot, (&left, &right)) in LEFT_DOTS.iter().zip(&RIGHT_DOTS).enumerate() {
+                        // Dot rows counted from the bottom of the chart
+                        let from_bottom = dots_high - (row * 4 + dot);
+                        if heights[col * 2] >= from_bottom {
+                            bits |= left;
+                        }
+                        if heights[col * 2 + 1] >= from_bottom {
+                            bits |= right;
+                        }
+                    }
+                    char::from_u32(0x2800 + bits).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn test_luma_histogram_counts_pixels() {
+        let image = DynamicImage::ImageLuma8(GrayImage::from_fn(10, 4, |x, _| {
+            Luma([if x < 5 { 0 } else { 200 }])
+        }));
+        let buckets = luma_histogram(&image);
+        assert_eq!(buckets[0], 20);
+        assert_eq!(buckets[200], 20);
+        assert_eq!(buckets.iter().sum::<u32>(), 40);
+    }
+
+    #[test]
+    fn test_braille_chart_full_and_empty_bars() {
+        let mut buckets = [0u32; 256];
+        // First bar (left dots of the first character) at full height
+        buckets[0] = 10;
+        let chart = braille_chart(&buckets, 2, 1);
+        assert_eq!(chart, vec!["\u{2847}\u{2800}".to_string()]);
+    }
+
+    #[test]
+    fn test_braille_chart_scales_to_tallest() {
+        let mut buckets = [0u32; 256];
+        buckets[0] = 100; // left bar: full height
+        buckets[200] = 50; // right bar: half height
+        let chart = braille_chart(&buckets, 1, 2);
+        // Top row: only the left bar reaches it; bottom row: both bars
+        assert_eq!(chart, vec!["\u{2847}".to_string(), "\u{28ff}".to_string()]);
+
+        // A tiny bucket still shows one dot at the bottom
+        let mut buckets = [0u32; 256];
+        buckets[0] = 1000;
+        buckets[255] = 1;
+        let chart = braille_chart(&buckets, 2, 2);
+        assert_eq!(chart[1].chars().last(), Some('\u{2880}'));
+    }
+}
@@ -0,0 +1,292 @@
+//! Location browser: photos grouped by where they were taken, one card per
+//! grid cell with a sample thumbnail.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+use ratatui_image::StatefulImage;
+
+use crate::app::App;
+use crate::config::ImageProtocol;
+use crate::db::locations::{self, GpsCluster};
+use crate::ui::gallery::GalleryView;
+use crate::ui::theme::Theme;
+
+/// A place name lookup running in the background
+struct PendingGeocode {
+    latitude: f64,
+    longitude: f64,
+    rx: mpsc::Receiver<Result<String, String>>,
+}
+
+/// State for the location browser.
+pub struct LocationsView {
+    pub clusters: Vec<GpsCluster>,
+    /// Grid cell size in degrees
+    pub precision: f32,
+    /// Sample thumbnails and cursor: `grid.images[i]` is the first photo of
+    /// `clusters[i]`
+    pub grid: GalleryView,
+    geocode: Option<PendingGeocode>,
+}
+
+impl LocationsView {
+    pub fn new(directory: PathBuf, clusters: Vec<GpsCluster>, precision: f32, protocol: ImageProtocol) -> Self {
+        let samples = Self::samples(&clusters);
+        Self {
+            clusters,
+            precision,
+            grid: GalleryView::new(directory, samples, protocol),
+            geocode: None,
+        }
+    }
+
+    /// Replace the clusters after the precision changes, cursor at the start.
+    pub fn set_clusters(&mut self, clusters: Vec<GpsCluster>, precision: f32) {
+        self.grid.images = Self::samples(&clusters);
+        self.grid.selected = 0;
+        self.grid.scroll_offset = 0;
+        self.clusters = clusters;
+        self.precision = precision;
+    }
+
+    pub fn selected(&self) -> Option<&GpsCluster> {
+        self.clusters.get(self.grid.selected)
+    }
+
+    /// Look up the selected cluster's place name in a background thread.
+    pub fn start_geocode(&mut self) {
+        let Some(cluster) = self.selected() else { return };
+        let (latitude, longitude) = (cluster.latitude, cluster.longitude);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = crate::geocode::reverse_geocode(latitude, longitude).map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.geocode = Some(PendingGeocode { latitude, longitude, rx });
+    }
+
+    pub fn is_geocoding(&self) -> bool {
+        self.geocode.is_some()
+    }
+
+    /// Pick up a finished lookup as `(latitude, longitude, result)`. A found
+    /// name is shown on every cluster around the same spot.
+    pub fn poll_geocode(&mut self) -> Option<(f64, f64, Result<String, String>)> {
+        let pending = self.geocode.as_ref()?;
+        let result = match pending.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return None,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Lookup failed".to_string()),
+        };
+        let (latitude, longitude) = (pending.latitude, pending.longitude);
+        self.geocode = None;
+
+        if let Ok(ref name) = result {
+            let key = locations::place_key(latitude, longitude);
+            for cluster in &mut self.clusters {
+                if locations::place_key(cluster.latitude, cluster.longitude) == key {
+                    cluster.place = Some(name.clone());
+                }
+            }
+        }
+        Some((latitude, longitude, result))
+    }
+
+    fn samples(clusters: &[GpsCluster]) -> Vec<PathBuf> {
+        clusters
+            .iter()
+            .map(|c| c.paths.first().map(PathBuf::from).unwrap_or_default())
+            .collect()
+    }
+}
+
+/// Card title for a cluster: its place name, or its coordinates
+pub fn cluster_label(cluster: &GpsCluster) -> String {
+    match cluster.place {
+        Some(ref place) => place.clone(),
+        None => format!("{:.4}, {:.4}", cluster.latitude, cluster.longitude),
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
+    let (columns, visible_rows, visible_samples) = {
+        let view = match app.locations_view.as_mut() {
+            Some(v) => v,
+            None => return,
+        };
+        view.grid.poll_async_loads();
+
+        let columns = view.grid.columns(area.width);
+        let visible_rows = view.grid.visible_rows(area.height.saturating_sub(3));
+        view.grid.update_layout_cache(columns, visible_rows);
+        view.grid.ensure_visible(columns, visible_rows);
+
+        let start = (view.grid.scroll_offset * columns).min(view.clusters.len());
+        let end = (start + visible_rows * columns).min(view.clusters.len());
+        (columns, visible_rows, view.grid.images[start..end].to_vec())
+    };
+
+    let rotations: HashMap<PathBuf, i32> = visible_samples
+        .iter()
+        .map(|p| (p.clone(), app.get_photo_rotation(p)))
+        .collect();
+
+    let view = match app.locations_view.as_mut() {
+        Some(v) => v,
+        None => return,
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Grid
+            Constraint::Length(2),  // Footer
+        ])
+        .split(area);
+
+    let photos: usize = view.clusters.iter().map(|c| c.paths.len()).sum();
+    let header = format!(
+        " Locations: {} places, {} photos · {}° cells ({})",
+        view.clusters.len(),
+        photos,
+        view.precision,
+        locations::precision_label(view.precision)
+    );
+    frame.render_widget(
+        Paragraph::new(header).style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        chunks[0],
+    );
+
+    if view.clusters.is_empty() {
+        let empty = Paragraph::new("No photos with GPS coordinates. Scan a directory to read them.")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, Rect::new(chunks[1].x, chunks[1].y + chunks[1].height / 2, chunks[1].width, 1));
+    }
+
+    let cell_width = view.grid.thumbnail_size.cell_width();
+    let cell_height = view.grid.thumbnail_size.cell_height();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints((0..visible_rows).map(|_| Constraint::Length(cell_height)).collect::<Vec<_>>())
+        .split(chunks[1]);
+    for (row_idx, row_area) in rows.iter().enumerate() {
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints((0..columns).map(|_| Constraint::Length(cell_width)).collect::<Vec<_>>())
+            .split(*row_area);
+        for (col_idx, cell_area) in cols.iter().enumerate() {
+            let idx = (view.grid.scroll_offset + row_idx) * columns + col_idx;
+            if idx < view.clusters.len() {
+                let rotation = rotations.get(&view.grid.images[idx]).copied().unwrap_or(0);
+                render_cluster_cell(frame, view, idx, *cell_area, rotation, &theme);
+            }
+        }
+    }
+
+    render_footer(frame, view, chunks[2], &theme);
+}
+
+fn render_cluster_cell(frame: &mut Frame, view: &mut LocationsView, idx: usize, area: Rect, rotation: i32, theme: &Theme) {
+    let cluster = &view.clusters[idx];
+    let border_color = if idx == view.grid.selected { theme.accent } else { theme.muted };
+
+    let label = cluster_label(cluster);
+    let max_name_len = (area.width as usize).saturating_sub(4);
+    let name = if label.chars().count() > max_name_len {
+        let kept: String = label.chars().take(max_name_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    } else {
+        label
+    };
+    let count = cluster.paths.len();
+    let info = format!("{} photo{}", count, if count == 1 { "" } else { "s" });
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(name);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width < 2 || inner.height < 3 {
+        return;
+    }
+
+    let info_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+    frame.render_widget(
+        Paragraph::new(info).style(Style::default().fg(theme.secondary)).alignment(Alignment::Center),
+        info_area,
+    );
+
+    let image_area = Rect::new(inner.x, inner.y, inner.width, inner.height - 1);
+    let sample = view.grid.images[idx].clone();
+    if let Some(protocol) = view.grid.load_thumbnail(&sample, rotation) {
+        frame.render_stateful_widget(StatefulImage::new(None), image_area, protocol);
+    } else {
+        let text = if view.grid.is_loading(&sample, rotation) { "Loading..." } else { "[ ]" };
+        let placeholder = Paragraph::new(text)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        let middle = Rect::new(image_area.x, image_area.y + image_area.height / 2, image_area.width, 1);
+        frame.render_widget(placeholder, middle);
+    }
+}
+
+fn render_footer(frame: &mut Frame, view: &LocationsView, area: Rect, theme: &Theme) {
+    let (text, style) = if view.is_geocoding() {
+        (" Looking up place name...".to_string(), theme.highlight)
+    } else {
+        (
+            " hjkl/arrows:navigate  Enter:filter browser  +/-:finer/coarser  n:look up name  c:clear filter  q/Esc:close"
+                .to_string(),
+            theme.muted,
+        )
+    };
+
+    let footer = Paragraph::new(text)
+        .style(Style::default().fg(style))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(footer, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cluster(latitude: f64, longitude: f64, paths: &[&str]) -> GpsCluster {
+        GpsCluster {
+            latitude,
+            longitude,
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            place: None,
+        }
+    }
+
+    #[test]
+    fn test_geocode_names_matching_clusters() {
+        let clusters = vec![
+            cluster(51.5072, -0.1276, &["/a.jpg", "/b.jpg"]),
+            cluster(48.8566, 2.3522, &["/paris.jpg"]),
+        ];
+        let mut view = LocationsView::new(PathBuf::from("/"), clusters, 0.01, ImageProtocol::None);
+        assert_eq!(view.grid.images, vec![PathBuf::from("/a.jpg"), PathBuf::from("/paris.jpg")]);
+        assert_eq!(cluster_label(&view.clusters[0]), "51.5072, -0.1276");
+
+        let (tx, rx) = mpsc::channel();
+        view.geocode = Some(PendingGeocode { latitude: 51.5071, longitude: -0.1275, rx });
+        assert!(view.poll_geocode().is_none());
+        tx.send(Ok("London".to_string())).unwrap();
+        assert!(view.poll_geocode().unwrap().2.is_ok());
+        assert!(!view.is_geocoding());
+        assert_eq!(cluster_label(&view.clusters[0]), "London");
+        assert_eq!(view.clusters[1].place, None);
+    }
+}
@@ -1,30 +1,105 @@
+mod breadcrumb;
 mod browser;
+pub mod adb_dialog;
+pub mod albums;
+pub mod animation;
+pub mod batch_progress;
+pub mod batch_tag_dialog;
+pub mod calendar;
+pub mod camera_dialog;
 pub mod centralise_dialog;
 pub mod changes_dialog;
 pub mod confirm_dialog;
+pub mod conflict_dialog;
+pub mod cross_dedup_dialog;
+pub mod date_filter_dialog;
 mod dialogs;
 pub mod duplicates;
 pub mod edit_dialog;
+pub mod expanded_field_dialog;
 pub mod export_dialog;
 pub mod gallery;
+pub mod gps_dialog;
+pub mod histogram;
+pub mod locations;
 pub mod move_dialog;
+pub mod tag_browser;
 pub mod tag_dialog;
+pub mod text_preview;
 pub mod slideshow;
+pub mod theme;
+pub mod theme_dialog;
 pub mod overdue_dialog;
+pub mod people;
 pub mod people_dialog;
+pub mod pixel_diff;
 pub mod preview;
+pub mod prompt_manager;
+pub mod protocol;
+pub mod quick_filter;
 pub mod rename_dialog;
+pub mod rescan_dialog;
 pub mod schedule_dialog;
 pub mod search_dialog;
 pub mod settings_dialog;
+pub mod share_dialog;
 mod status_bar;
 mod task_list_dialog;
 pub mod trash_dialog;
+pub mod warmup_dialog;
+pub mod zoom;
 
 use ratatui::prelude::*;
 use ratatui::widgets::Clear;
 
 use crate::app::{App, AppMode};
+use crate::config::UiConfig;
+
+/// Split the screen into the browser panes, breadcrumb bar and status bar.
+pub fn main_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+        .split(area)
+}
+
+/// Below this width the parent pane is hidden.
+const TWO_PANE_WIDTH: u16 = 100;
+/// Below this width only the current directory is shown.
+const ONE_PANE_WIDTH: u16 = 60;
+
+/// Split the browser area into parent, current and preview panes using
+/// the configured width percentages. Narrow terminals drop the parent
+/// pane, then the preview, and the preview also goes when it would be
+/// narrower than `min_preview_width`; hidden panes get zero width.
+pub fn browser_layout(area: Rect, ui: &UiConfig) -> [Rect; 3] {
+    let [parent, current, preview] = ui.pane_ratios;
+    let show_parent = area.width >= TWO_PANE_WIDTH;
+    let show_preview = area.width >= ONE_PANE_WIDTH;
+
+    let split = |show_parent: bool, show_preview: bool| -> [Rect; 3] {
+        let pane = |ratio: u16, shown: bool| {
+            if shown {
+                Constraint::Fill(ratio)
+            } else {
+                Constraint::Length(0)
+            }
+        };
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([pane(parent, show_parent), pane(current, true), pane(preview, show_preview)])
+            .areas(area)
+    };
+
+    let panes = split(show_parent, show_preview);
+    if show_preview && panes[2].width < ui.min_preview_width {
+        split(show_parent, false)
+    } else {
+        panes
+    }
+}
+
+pub use breadcrumb::{current_crumbs, hit_test as breadcrumb_hit_test};
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
@@ -42,7 +117,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     if app.mode == AppMode::Duplicates || app.mode == AppMode::DuplicatesHelp {
         duplicates::render(frame, app, area);
         if app.mode == AppMode::DuplicatesHelp {
-            duplicates::render_help(frame, area);
+            duplicates::render_help(frame, area, &app.theme);
         }
         return;
     }
@@ -51,74 +126,104 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     if app.mode == AppMode::Gallery || app.mode == AppMode::GalleryHelp {
         gallery::render(frame, app, area);
         if app.mode == AppMode::GalleryHelp {
-            gallery::render_help(frame, area);
+            gallery::render_help(frame, area, &app.theme);
         }
         return;
     }
 
+    if app.mode == AppMode::Albums {
+        albums::render(frame, app, area);
+        return;
+    }
+
+    if app.mode == AppMode::People {
+        people::render(frame, app, area);
+        return;
+    }
+
+    if app.mode == AppMode::Locations {
+        locations::render(frame, app, area);
+        return;
+    }
+
     // Handle slideshow mode
     if app.mode == AppMode::Slideshow || app.mode == AppMode::SlideshowHelp {
         slideshow::render(frame, app, area);
         if app.mode == AppMode::SlideshowHelp {
-            slideshow::render_help(frame, area);
+            slideshow::render_help(frame, area, &app.theme);
         }
         return;
     }
 
-    // Main layout: content area + status bar
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(1)])
-        .split(area);
-
-    // Three-column layout for the browser
-    let browser_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(20), // Parent directory
-            Constraint::Percentage(40), // Current directory
-            Constraint::Percentage(40), // Preview
-        ])
-        .split(main_chunks[0]);
-
-    // Render the three columns
-    browser::render_parent(frame, app, browser_chunks[0]);
+    // Main layout: content area + breadcrumb + status bar
+    let main_chunks = main_layout(area);
+
+    // Up to three columns for the browser, fewer on narrow terminals
+    let browser_chunks = browser_layout(main_chunks[0], &app.config.ui);
+    app.browser_rows = browser_chunks[1].height.saturating_sub(2) as usize;
+
+    if browser_chunks[0].width > 0 {
+        browser::render_parent(frame, app, browser_chunks[0]);
+    }
     browser::render_current(frame, app, browser_chunks[1]);
-    preview::render(frame, app, browser_chunks[2]);
+    if browser_chunks[2].width > 0 {
+        preview::render(frame, app, browser_chunks[2]);
+    }
 
-    // Render status bar
-    status_bar::render(frame, app, main_chunks[1]);
+    // Render breadcrumb and status bar
+    breadcrumb::render(frame, app, main_chunks[1]);
+    status_bar::render(frame, app, main_chunks[2]);
+
+    // Render the drag label on top of the browser
+    if let Some(ref drag) = app.drag {
+        if drag.active {
+            browser::render_drag(frame, drag, area, &app.theme);
+        }
+    }
 
     // Render help overlay if in help mode
     if app.mode == AppMode::Help {
-        dialogs::render_help(frame, area);
+        dialogs::render_help(frame, area, &app.theme);
     }
 
     // Render move dialog if in move mode
     if app.mode == AppMode::Moving {
         if let Some(ref dialog) = app.move_dialog {
-            move_dialog::render(frame, dialog, area);
+            move_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    // Render move conflict dialog if resolving conflicts
+    if app.mode == AppMode::ConflictResolving {
+        if let Some(ref dialog) = app.conflict_dialog {
+            conflict_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    if app.mode == AppMode::CrossDirDedup {
+        if let Some(ref dialog) = app.cross_dedup_dialog {
+            cross_dedup_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render rename dialog if in rename mode
     if app.mode == AppMode::Renaming {
         if let Some(ref dialog) = app.rename_dialog {
-            rename_dialog::render(frame, dialog, area);
+            rename_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render export dialog if in export mode
     if app.mode == AppMode::Exporting {
         if let Some(ref dialog) = app.export_dialog {
-            export_dialog::render(frame, dialog, area);
+            export_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render search dialog if in search mode
     if app.mode == AppMode::Searching {
-        if let Some(ref dialog) = app.search_dialog {
-            search_dialog::render(frame, dialog, area);
+        if let Some(ref mut dialog) = app.search_dialog {
+            search_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
@@ -137,63 +242,195 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Render trash dialog if in trash viewing mode
     if app.mode == AppMode::TrashViewing {
         if let Some(ref dialog) = app.trash_dialog {
-            trash_dialog::render(frame, dialog, area);
+            trash_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render edit description dialog if in edit mode
     if app.mode == AppMode::EditingDescription {
         if let Some(ref dialog) = app.edit_dialog {
-            edit_dialog::render(frame, dialog, area);
+            edit_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    // Render date filter dialog
+    if app.mode == AppMode::DateFilter {
+        if let Some(ref dialog) = app.date_filter_dialog {
+            date_filter_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    // Render camera filter dialog
+    if app.mode == AppMode::RescanMissing {
+        if let Some(ref dialog) = app.rescan_dialog {
+            rescan_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    if app.mode == AppMode::ExpandedField {
+        if let Some(ref dialog) = app.expanded_field_dialog {
+            expanded_field_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    if app.mode == AppMode::CameraFilter {
+        if let Some(ref dialog) = app.camera_dialog {
+            camera_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    if app.mode == AppMode::TagBrowser {
+        if let Some(ref dialog) = app.tag_browser {
+            tag_browser::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    if app.mode == AppMode::Calendar {
+        if let Some(ref view) = app.calendar {
+            calendar::render(frame, view, area, &app.theme);
+        }
+    }
+
+    if app.mode == AppMode::PromptManager {
+        if let Some(ref dialog) = app.prompt_manager {
+            prompt_manager::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    // Render GPS dialog if in GPS editing mode
+    if app.mode == AppMode::EditingGPS {
+        if let Some(ref dialog) = app.gps_dialog {
+            gps_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render changes dialog if in changes viewing mode
     if app.mode == AppMode::ChangesViewing {
         if let Some(ref dialog) = app.changes_dialog {
-            changes_dialog::render(frame, dialog, area);
+            changes_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render schedule dialog if in scheduling mode
     if app.mode == AppMode::Scheduling {
         if let Some(ref dialog) = app.schedule_dialog {
-            schedule_dialog::render(frame, dialog, area);
+            schedule_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render overdue dialog if in overdue dialog mode
     if app.mode == AppMode::OverdueDialog {
         if let Some(ref dialog) = app.overdue_dialog {
-            overdue_dialog::render(frame, dialog, area);
+            overdue_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render tag dialog if in tagging mode
     if app.mode == AppMode::Tagging {
         if let Some(ref dialog) = app.tag_dialog {
-            tag_dialog::render(frame, dialog, area);
+            tag_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    // Render batch tag dialog if batch tagging
+    if app.mode == AppMode::BatchTagging {
+        if let Some(ref dialog) = app.batch_tag_dialog {
+            batch_tag_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render centralise dialog if in centralising mode
     if app.mode == AppMode::Centralising {
         if let Some(ref dialog) = app.centralise_dialog {
-            centralise_dialog::render(frame, dialog, area);
+            centralise_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render confirm dialog if in confirming mode
     if app.mode == AppMode::Confirming {
         if let Some(ref dialog) = app.confirm_dialog {
-            confirm_dialog::render(frame, dialog, area);
+            confirm_dialog::render(frame, dialog, area, &app.theme);
         }
     }
 
     // Render settings dialog if in settings mode
     if app.mode == AppMode::Settings {
         if let Some(ref dialog) = app.settings_dialog {
-            settings_dialog::render(frame, dialog, area);
+            settings_dialog::render(frame, dialog, area, &app.theme);
         }
     }
+
+    // Render theme picker if in theme picking mode
+    if app.mode == AppMode::ThemePicking {
+        if let Some(ref dialog) = app.theme_dialog {
+            theme_dialog::render(frame, dialog, area, &app.theme);
+        }
+    }
+
+    // Render thumbnail warm-up progress while the gallery waits for it
+    if app.mode == AppMode::ThumbnailWarmup {
+        if let Some(ref dialog) = app.thumbnail_warmup {
+            let running = app.task_manager.running_tasks();
+            let progress = running
+                .iter()
+                .find(|t| t.id == dialog.task_id)
+                .and_then(|t| t.progress.as_ref());
+            warmup_dialog::render(frame, dialog, progress, area, &app.theme);
+        }
+    }
+
+    // Render the Android import wizard
+    if app.mode == AppMode::AdbImport {
+        let theme = app.theme.clone();
+        if let Some(ref mut dialog) = app.adb_import {
+            adb_dialog::render(frame, dialog, area, &theme);
+        }
+    }
+
+    // Render move/centralise progress until the task finishes
+    if app.mode == AppMode::BatchProgress {
+        if let Some(ref dialog) = app.batch_progress {
+            let running = app.task_manager.running_tasks();
+            let progress = running
+                .iter()
+                .find(|t| t.id == dialog.task_id)
+                .and_then(|t| t.progress.as_ref());
+            batch_progress::render(frame, dialog, progress, area, &app.theme);
+        }
+    }
+
+    // Render the web gallery address while it is being shared
+    if app.mode == AppMode::Sharing {
+        if let Some(ref dialog) = app.share_dialog {
+            let running = app.task_manager.running_tasks();
+            let progress = running
+                .iter()
+                .find(|t| t.id == dialog.task_id)
+                .and_then(|t| t.progress.as_ref());
+            share_dialog::render(frame, dialog, progress, area, &app.theme);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browser_layout_breakpoints() {
+        let widths = |ui: &UiConfig, width: u16| browser_layout(Rect::new(0, 0, width, 30), ui).map(|pane| pane.width);
+
+        let ui = UiConfig::default();
+        assert_eq!(widths(&ui, 150), [30, 60, 60]);
+        assert_eq!(widths(&ui, 90), [0, 45, 45]);
+        assert_eq!(widths(&ui, 59), [0, 59, 0]);
+
+        // Two panes keep the current:preview ratio
+        let ui = UiConfig { pane_ratios: [30, 40, 30], ..UiConfig::default() };
+        assert_eq!(widths(&ui, 99), [0, 57, 42]);
+
+        // A preview under the minimum gives its width to the other panes
+        let ui = UiConfig { min_preview_width: 50, ..ui };
+        assert_eq!(widths(&ui, 120), [51, 69, 0]);
+    }
 }
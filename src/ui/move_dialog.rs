@@ -5,6 +5,8 @@ use ratatui::{
 use std::fs;
 use std::path::PathBuf;
 
+use crate::ui::theme::Theme;
+
 /// State for the move file dialog
 pub struct MoveDialog {
     /// Current directory being browsed
@@ -118,7 +120,29 @@ impl MoveDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect, theme: &Theme) {
+    render_picker(
+        frame,
+        dialog,
+        area,
+        theme,
+        " Move Files ",
+        &format!("Moving {} file(s) to:", dialog.files_to_move.len()),
+        "j/k: navigate | Enter: open dir | /: edit path | m: confirm move | Esc: cancel",
+    );
+}
+
+/// Draw the directory browser with its own title, header line and key hints,
+/// for other dialogs that pick a directory.
+pub fn render_picker(
+    frame: &mut Frame,
+    dialog: &MoveDialog,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    header: &str,
+    footer: &str,
+) {
     // Center the dialog
     let dialog_width = 70.min(area.width.saturating_sub(4));
     let dialog_height = 25.min(area.height.saturating_sub(4));
@@ -146,17 +170,13 @@ pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect) {
     // Draw border
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .title(" Move Files ")
+        .border_style(Style::default().fg(theme.highlight))
+        .title(title.to_string())
         .title_style(Style::default().add_modifier(Modifier::BOLD));
     frame.render_widget(block, dialog_area);
 
-    // Header: show file count
-    let header = Paragraph::new(format!(
-        "Moving {} file(s) to:",
-        dialog.files_to_move.len()
-    ))
-    .style(Style::default().fg(Color::Yellow));
+    let header = Paragraph::new(header.to_string())
+        .style(Style::default().fg(theme.highlight));
     frame.render_widget(header, chunks[0]);
 
     // Directory listing
@@ -172,7 +192,7 @@ pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect) {
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_else(|| path.to_string_lossy().to_string())
             };
-            ListItem::new(format!("/ {}", name)).style(Style::default().fg(Color::Cyan))
+            ListItem::new(format!("/ {}", name)).style(Style::default().fg(theme.accent))
         })
         .collect();
 
@@ -180,13 +200,13 @@ pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.muted))
                 .title(format!(" {} ", dialog.current_dir.display())),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::Yellow)
-                .fg(Color::Black)
+                .bg(theme.highlight)
+                .fg(theme.bg)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -196,9 +216,9 @@ pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect) {
 
     // Path input
     let input_style = if dialog.input_mode {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.highlight)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
     let input_text = if dialog.input_mode {
         format!("> {}_", dialog.input)
@@ -211,9 +231,7 @@ pub fn render(frame: &mut Frame, dialog: &MoveDialog, area: Rect) {
     frame.render_widget(input, chunks[2]);
 
     // Footer with instructions
-    let footer = Paragraph::new(
-        "j/k: navigate | Enter: open dir | /: edit path | m: confirm move | Esc: cancel",
-    )
-    .style(Style::default().fg(Color::DarkGray));
+    let footer = Paragraph::new(footer.to_string())
+        .style(Style::default().fg(theme.muted));
     frame.render_widget(footer, chunks[3]);
 }
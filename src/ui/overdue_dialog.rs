@@ -7,6 +7,7 @@ use ratatui::{
 use std::collections::HashSet;
 
 use crate::db::ScheduledTask;
+use crate::ui::theme::Theme;
 
 /// State for the overdue schedules dialog.
 pub struct OverdueDialog {
@@ -80,7 +81,7 @@ impl OverdueDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &OverdueDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &OverdueDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 70.min(area.width.saturating_sub(4));
     let dialog_height = 18.min(area.height.saturating_sub(4));
@@ -105,11 +106,11 @@ pub fn render(frame: &mut Frame, dialog: &OverdueDialog, area: Rect) {
 
     // Header
     let header = Paragraph::new(format!(" {} overdue scheduled tasks found", dialog.tasks.len()))
-        .style(Style::default().fg(Color::Red))
+        .style(Style::default().fg(theme.error))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red))
+                .border_style(Style::default().fg(theme.error))
                 .title(" Overdue Tasks "),
         );
     frame.render_widget(header, chunks[0]);
@@ -117,7 +118,7 @@ pub fn render(frame: &mut Frame, dialog: &OverdueDialog, area: Rect) {
     // Task list
     if dialog.tasks.is_empty() {
         let empty_msg = Paragraph::new("  No overdue tasks")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(empty_msg, chunks[1]);
     } else {
@@ -130,9 +131,9 @@ pub fn render(frame: &mut Frame, dialog: &OverdueDialog, area: Rect) {
                 let marker = if selected { "[x]" } else { "[ ]" };
 
                 let style = if i == dialog.selected_index {
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                 } else if selected {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(theme.success)
                 } else {
                     Style::default()
                 };
@@ -177,7 +178,7 @@ pub fn render(frame: &mut Frame, dialog: &OverdueDialog, area: Rect) {
         " j/k=nav  Space=toggle  a=all  Enter=run{}  c=cancel all  q=dismiss",
         sel_text
     ))
-    .style(Style::default().fg(Color::DarkGray))
+    .style(Style::default().fg(theme.muted))
     .block(Block::default().borders(Borders::TOP));
 
     frame.render_widget(help, chunks[2]);
@@ -0,0 +1,407 @@
+//! People browser: a grid of cards, one per known person, each showing a
+//! few of their faces, plus a card for faces not yet assigned to anyone.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+
+use image::DynamicImage;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+use ratatui_image::{Resize, StatefulImage};
+
+use crate::app::App;
+use crate::db::Person;
+use crate::ui::people_dialog::{crop_face, FaceEntry};
+use crate::ui::theme::Theme;
+
+const CARD_WIDTH: u16 = 28;
+const CARD_HEIGHT: u16 = 14;
+/// Faces shown on each card, as a 2×2 grid
+pub const CARD_FACES: usize = 4;
+/// Face crops of cards further than this many rows from the visible ones
+/// are dropped from the cache
+const FACE_CACHE_MARGIN_ROWS: usize = 5;
+
+/// One card in the grid
+#[derive(Clone)]
+pub struct PersonCard {
+    /// None for the card collecting unassigned faces
+    pub person: Option<Person>,
+    /// Up to [`CARD_FACES`] faces to show
+    pub faces: Vec<FaceEntry>,
+    /// Photos of the person, or unassigned faces for the unassigned card
+    pub count: usize,
+}
+
+/// Line of input the people browser is waiting for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeoplePrompt {
+    /// New name for the selected person
+    Rename(String),
+    /// y/n before deleting the selected person
+    ConfirmDelete,
+    /// Picking the person that the card at `source` is merged into
+    MergeInto { source: usize },
+}
+
+/// State for the people browser.
+pub struct PeopleView {
+    pub cards: Vec<PersonCard>,
+    pub selected: usize,
+    /// First visible row of cards
+    pub scroll_offset: usize,
+    pub prompt: Option<PeoplePrompt>,
+    /// Cards per row and visible rows, updated on render
+    columns: usize,
+    visible_rows: usize,
+    /// Face crops by face ID; `None` while the crop is being loaded
+    face_images: HashMap<i64, Option<Arc<DynamicImage>>>,
+    /// Faces whose photo could not be read, so they are not retried
+    unreadable_faces: HashSet<i64>,
+    face_sender: mpsc::Sender<(i64, Option<DynamicImage>)>,
+    face_receiver: mpsc::Receiver<(i64, Option<DynamicImage>)>,
+}
+
+impl PeopleView {
+    pub fn new(cards: Vec<PersonCard>) -> Self {
+        let (face_sender, face_receiver) = mpsc::channel();
+        Self {
+            cards,
+            selected: 0,
+            scroll_offset: 0,
+            prompt: None,
+            columns: 4,
+            visible_rows: 3,
+            face_images: HashMap::new(),
+            unreadable_faces: HashSet::new(),
+            face_sender,
+            face_receiver,
+        }
+    }
+
+    /// Replace the cards after a change, keeping the cursor in range.
+    pub fn set_cards(&mut self, cards: Vec<PersonCard>) {
+        self.selected = self.selected.min(cards.len().saturating_sub(1));
+        self.cards = cards;
+    }
+
+    pub fn selected_card(&self) -> Option<&PersonCard> {
+        self.cards.get(self.selected)
+    }
+
+    /// The selected person, unless the unassigned card is selected
+    pub fn selected_person(&self) -> Option<&Person> {
+        self.selected_card().and_then(|c| c.person.as_ref())
+    }
+
+    pub fn move_left(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        if self.selected + 1 < self.cards.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.selected >= self.columns {
+            self.selected -= self.columns;
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + self.columns < self.cards.len() {
+            self.selected += self.columns;
+        } else if self.selected / self.columns < self.cards.len().saturating_sub(1) / self.columns {
+            // Partial last row: land on its last card
+            self.selected = self.cards.len() - 1;
+        }
+    }
+
+    /// Record the grid size and scroll the selected card into view.
+    pub fn set_layout(&mut self, columns: usize, visible_rows: usize) {
+        self.columns = columns.max(1);
+        self.visible_rows = visible_rows.max(1);
+        let row = self.selected / self.columns;
+        if row < self.scroll_offset {
+            self.scroll_offset = row;
+        } else if row >= self.scroll_offset + self.visible_rows {
+            self.scroll_offset = row + 1 - self.visible_rows;
+        }
+    }
+
+    /// Indices of the cards currently shown
+    pub fn visible_cards(&self) -> std::ops::Range<usize> {
+        let start = (self.scroll_offset * self.columns).min(self.cards.len());
+        start..(start + self.visible_rows * self.columns).min(self.cards.len())
+    }
+
+    /// The face's crop, if it has finished loading
+    pub fn face_image(&self, face_id: i64) -> Option<Arc<DynamicImage>> {
+        self.face_images.get(&face_id).cloned().flatten()
+    }
+
+    /// Collect finished crops, start loading faces on visible cards that are
+    /// not cached yet and drop crops of cards far out of view.
+    pub fn load_visible_faces(&mut self, thumbnail_size: u32) {
+        while let Ok((face_id, image)) = self.face_receiver.try_recv() {
+            match image {
+                Some(image) => {
+                    self.face_images.insert(face_id, Some(Arc::new(image)));
+                }
+                None => {
+                    self.face_images.remove(&face_id);
+                    self.unreadable_faces.insert(face_id);
+                }
+            }
+        }
+
+        for card in &self.cards[self.visible_cards()] {
+            for face in &card.faces {
+                if self.face_images.contains_key(&face.face_id) || self.unreadable_faces.contains(&face.face_id) {
+                    continue;
+                }
+                self.face_images.insert(face.face_id, None);
+                let sender = self.face_sender.clone();
+                let (face_id, path, bbox) = (face.face_id, face.photo_path.clone(), face.bbox.clone());
+                std::thread::spawn(move || {
+                    let _ = sender.send((face_id, crop_face(Path::new(&path), &bbox, thumbnail_size)));
+                });
+            }
+        }
+
+        let visible = self.visible_cards();
+        let margin = FACE_CACHE_MARGIN_ROWS * self.columns;
+        let keep = visible.start.saturating_sub(margin)..visible.end + margin;
+        let kept: HashSet<i64> = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| keep.contains(i))
+            .flat_map(|(_, c)| c.faces.iter().map(|f| f.face_id))
+            .collect();
+        // Crops still loading stay until they arrive and are dropped on a later pass
+        self.face_images.retain(|id, image| image.is_none() || kept.contains(id));
+    }
+}
+
+/// What one visible card needs for drawing, copied out of the view
+struct CardData {
+    title: String,
+    info: String,
+    unassigned: bool,
+    selected: bool,
+    faces: Vec<(i64, Option<Arc<DynamicImage>>)>,
+}
+
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
+    let thumbnail_size = app.config.preview.thumbnail_size;
+    let images_available = app.config.preview.image_preview && app.image_preview.is_available();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Header
+            Constraint::Min(10),    // Grid
+            Constraint::Length(2),  // Footer
+        ])
+        .split(area);
+
+    let columns = (chunks[1].width / CARD_WIDTH).max(1) as usize;
+    let visible_rows = (chunks[1].height / CARD_HEIGHT).max(1) as usize;
+
+    let (cards, people) = {
+        let Some(view) = app.people_view.as_mut() else { return };
+        view.set_layout(columns, visible_rows);
+        if images_available {
+            view.load_visible_faces(thumbnail_size);
+        }
+        let merge_source = match view.prompt {
+            Some(PeoplePrompt::MergeInto { source }) => Some(source),
+            _ => None,
+        };
+        let cards: Vec<CardData> = view
+            .visible_cards()
+            .map(|idx| {
+                let card = &view.cards[idx];
+                let (title, info) = match &card.person {
+                    Some(person) => (
+                        person.name.clone(),
+                        format!("{} photo{}", card.count, if card.count == 1 { "" } else { "s" }),
+                    ),
+                    None => (
+                        "Unassigned".to_string(),
+                        format!("{} face{} to name", card.count, if card.count == 1 { "" } else { "s" }),
+                    ),
+                };
+                let title = if merge_source == Some(idx) { format!("{} (merging)", title) } else { title };
+                CardData {
+                    title,
+                    info,
+                    unassigned: card.person.is_none(),
+                    selected: idx == view.selected,
+                    faces: card.faces.iter().map(|f| (f.face_id, view.face_image(f.face_id))).collect(),
+                }
+            })
+            .collect();
+        let people = view.cards.iter().filter(|c| c.person.is_some()).count();
+        (cards, people)
+    };
+
+    let header = format!(" People: {}", people);
+    frame.render_widget(
+        Paragraph::new(header).style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        chunks[0],
+    );
+
+    for (i, card) in cards.iter().enumerate() {
+        let row = i / columns;
+        let col = i % columns;
+        let cell = Rect::new(
+            chunks[1].x + col as u16 * CARD_WIDTH,
+            chunks[1].y + row as u16 * CARD_HEIGHT,
+            CARD_WIDTH.min(chunks[1].width),
+            CARD_HEIGHT.min(chunks[1].height),
+        );
+        render_card(frame, app, card, cell, images_available, &theme);
+    }
+
+    if let Some(view) = app.people_view.as_ref() {
+        render_footer(frame, view, chunks[2], &theme);
+    }
+}
+
+fn render_card(frame: &mut Frame, app: &mut App, card: &CardData, area: Rect, images_available: bool, theme: &Theme) {
+    let border_color = if card.selected { theme.accent } else { theme.muted };
+    let title_style = if card.unassigned { theme.emphasis } else { theme.fg };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(Span::styled(card.title.clone(), Style::default().fg(title_style).add_modifier(Modifier::BOLD)));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width < 2 || inner.height < 3 {
+        return;
+    }
+
+    let info_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+    frame.render_widget(
+        Paragraph::new(card.info.clone()).style(Style::default().fg(theme.secondary)).alignment(Alignment::Center),
+        info_area,
+    );
+
+    let faces_area = Rect::new(inner.x, inner.y, inner.width, inner.height - 1);
+    if card.faces.is_empty() {
+        let middle = Rect::new(faces_area.x, faces_area.y + faces_area.height / 2, faces_area.width, 1);
+        frame.render_widget(
+            Paragraph::new("(no faces)").style(Style::default().fg(theme.muted)).alignment(Alignment::Center),
+            middle,
+        );
+        return;
+    }
+
+    let half_width = faces_area.width / 2;
+    let half_height = faces_area.height / 2;
+    for (i, (face_id, image)) in card.faces.iter().enumerate().take(CARD_FACES) {
+        let slot = Rect::new(
+            faces_area.x + (i as u16 % 2) * half_width,
+            faces_area.y + (i as u16 / 2) * half_height,
+            half_width,
+            half_height,
+        );
+        let protocol = match (images_available, image) {
+            (true, Some(image)) => app.image_preview.face_crop_protocol(*face_id, image),
+            _ => None,
+        };
+        if let Some(protocol) = protocol {
+            frame.render_stateful_widget(StatefulImage::new(None).resize(Resize::Fit(None)), slot, protocol);
+        } else {
+            let middle = Rect::new(slot.x, slot.y + slot.height / 2, slot.width, 1);
+            let text = if images_available { "…" } else { "[face]" };
+            frame.render_widget(
+                Paragraph::new(text).style(Style::default().fg(theme.muted)).alignment(Alignment::Center),
+                middle,
+            );
+        }
+    }
+}
+
+fn render_footer(frame: &mut Frame, view: &PeopleView, area: Rect, theme: &Theme) {
+    let name = view.selected_person().map(|p| p.name.as_str()).unwrap_or_default();
+    let (text, style) = match &view.prompt {
+        Some(PeoplePrompt::Rename(input)) => (format!(" Rename to: {}_", input), theme.highlight),
+        Some(PeoplePrompt::ConfirmDelete) => (
+            format!(" Delete \"{}\"? Their faces become unassigned. (y/n)", name),
+            theme.error,
+        ),
+        Some(PeoplePrompt::MergeInto { source }) => {
+            let source_name = view
+                .cards
+                .get(*source)
+                .and_then(|c| c.person.as_ref())
+                .map(|p| p.name.as_str())
+                .unwrap_or_default();
+            (
+                format!(" Merge \"{}\" into: move to a person and press Enter (Esc cancels)", source_name),
+                theme.highlight,
+            )
+        }
+        None => (
+            " hjkl/arrows:navigate  Enter:photos (unassigned: name faces)  n:rename  m:merge  d:delete  q/Esc:close"
+                .to_string(),
+            theme.muted,
+        ),
+    };
+
+    let footer = Paragraph::new(text)
+        .style(Style::default().fg(style))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(footer, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(count: usize) -> Vec<PersonCard> {
+        (0..count)
+            .map(|i| PersonCard {
+                person: (i > 0).then(|| Person { id: i as i64, name: format!("Person {}", i), face_count: 0 }),
+                faces: Vec::new(),
+                count: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_grid_navigation() {
+        let mut view = PeopleView::new(cards(10));
+        view.set_layout(4, 2);
+        assert!(view.selected_person().is_none());
+
+        // Down from the second row lands on the last card of the short third row
+        view.move_down();
+        view.move_right();
+        view.move_right();
+        view.move_down();
+        assert_eq!(view.selected, 9);
+        view.set_layout(4, 2);
+        assert_eq!(view.scroll_offset, 1);
+        assert_eq!(view.visible_cards(), 4..10);
+
+        view.move_up();
+        view.move_up();
+        assert_eq!(view.selected, 1);
+        assert_eq!(view.selected_person().unwrap().name, "Person 1");
+
+        // Deleting people keeps the cursor on a card
+        view.selected = 9;
+        view.set_cards(cards(3));
+        assert_eq!(view.selected, 2);
+    }
+}
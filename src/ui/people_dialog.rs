@@ -1,11 +1,21 @@
+use image::{imageops::FilterType, DynamicImage};
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 use ratatui_image::{Resize, StatefulImage};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
 
 use crate::app::App;
+use crate::db::face_crops::crop_with_padding;
 use crate::db::{BoundingBox, FaceWithPhoto, Person};
+use crate::ui::theme::Theme;
+
+/// Face crops further than this many rows from the visible ones are
+/// dropped from the cache
+const FACE_CACHE_MARGIN: usize = 20;
 
 /// A simplified face entry for display
 #[derive(Clone)]
@@ -75,11 +85,22 @@ pub struct PeopleDialog {
     pub cursor: usize,
     /// Status message
     pub status: Option<String>,
+    /// First face shown in the face list
+    pub scroll_offset: usize,
+    /// Face rows that fit in the list, updated on render
+    visible_rows: usize,
+    /// Face crops by face ID; `None` while the crop is being loaded
+    face_images: HashMap<i64, Option<Arc<DynamicImage>>>,
+    /// Faces whose photo could not be read, so they are not retried
+    unreadable_faces: HashSet<i64>,
+    face_sender: mpsc::Sender<(i64, Option<DynamicImage>)>,
+    face_receiver: mpsc::Receiver<(i64, Option<DynamicImage>)>,
 }
 
 impl PeopleDialog {
     pub fn new(people: Vec<Person>, faces: Vec<FaceWithPhoto>) -> Self {
         let face_entries: Vec<FaceEntry> = faces.into_iter().map(|f| f.into()).collect();
+        let (face_sender, face_receiver) = mpsc::channel();
         Self {
             view_mode: if people.is_empty() && !face_entries.is_empty() {
                 PeopleViewMode::Faces
@@ -94,6 +115,12 @@ impl PeopleDialog {
             name_input: String::new(),
             cursor: 0,
             status: None,
+            scroll_offset: 0,
+            visible_rows: 10,
+            face_images: HashMap::new(),
+            unreadable_faces: HashSet::new(),
+            face_sender,
+            face_receiver,
         }
     }
 
@@ -103,6 +130,7 @@ impl PeopleDialog {
             PeopleViewMode::Faces => PeopleViewMode::People,
         };
         self.selected_index = 0;
+        self.scroll_offset = 0;
         self.active_pane = PeopleActivePane::List;
     }
 
@@ -222,6 +250,79 @@ impl PeopleDialog {
         if self.selected_index > max_index {
             self.selected_index = max_index;
         }
+        self.ensure_visible();
+    }
+
+    /// Record how many face rows the list shows and scroll so the
+    /// selection stays among them.
+    pub fn set_visible_rows(&mut self, rows: usize) {
+        self.visible_rows = rows.max(1);
+        self.ensure_visible();
+    }
+
+    fn ensure_visible(&mut self) {
+        if self.selected_index < self.scroll_offset {
+            self.scroll_offset = self.selected_index;
+        } else if self.selected_index >= self.scroll_offset + self.visible_rows {
+            self.scroll_offset = self.selected_index + 1 - self.visible_rows;
+        }
+        let last_page = self.faces.len().saturating_sub(self.visible_rows);
+        self.scroll_offset = self.scroll_offset.min(last_page);
+    }
+
+    /// Indices of the faces currently shown in the list
+    pub fn visible_faces(&self) -> std::ops::Range<usize> {
+        let start = self.scroll_offset.min(self.faces.len());
+        start..(start + self.visible_rows).min(self.faces.len())
+    }
+
+    /// The face's crop, if it has finished loading
+    pub fn face_image(&self, face_id: i64) -> Option<Arc<DynamicImage>> {
+        self.face_images.get(&face_id).cloned().flatten()
+    }
+
+    pub fn is_face_unreadable(&self, face_id: i64) -> bool {
+        self.unreadable_faces.contains(&face_id)
+    }
+
+    /// Collect finished crops, start loading the visible faces that are not
+    /// cached yet and drop crops that have scrolled far out of view.
+    pub fn load_visible_faces(&mut self, thumbnail_size: u32) {
+        while let Ok((face_id, image)) = self.face_receiver.try_recv() {
+            match image {
+                Some(image) => {
+                    self.face_images.insert(face_id, Some(Arc::new(image)));
+                }
+                None => {
+                    self.face_images.remove(&face_id);
+                    self.unreadable_faces.insert(face_id);
+                }
+            }
+        }
+
+        for face in &self.faces[self.visible_faces()] {
+            if self.face_images.contains_key(&face.face_id) || self.unreadable_faces.contains(&face.face_id) {
+                continue;
+            }
+            self.face_images.insert(face.face_id, None);
+            let sender = self.face_sender.clone();
+            let (face_id, path, bbox) = (face.face_id, face.photo_path.clone(), face.bbox.clone());
+            std::thread::spawn(move || {
+                let _ = sender.send((face_id, crop_face(Path::new(&path), &bbox, thumbnail_size)));
+            });
+        }
+
+        let visible = self.visible_faces();
+        let keep = visible.start.saturating_sub(FACE_CACHE_MARGIN)..visible.end + FACE_CACHE_MARGIN;
+        let kept: HashSet<i64> = self
+            .faces
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| keep.contains(i))
+            .map(|(_, f)| f.face_id)
+            .collect();
+        // Crops still loading stay until they arrive and are dropped on a later pass
+        self.face_images.retain(|id, image| image.is_none() || kept.contains(id));
     }
 
     /// Check if the current list is empty
@@ -242,7 +343,21 @@ impl PeopleDialog {
     }
 }
 
+/// Crop a face out of its photo the way the detector does, downscaled to
+/// at most `thumbnail_size` (small crops are not upscaled, which would blur
+/// them)
+pub fn crop_face(path: &Path, bbox: &BoundingBox, thumbnail_size: u32) -> Option<DynamicImage> {
+    let dyn_img = image::ImageReader::open(path).ok()?.decode().ok()?;
+    let cropped = crop_with_padding(&dyn_img, bbox);
+    if cropped.width() > thumbnail_size || cropped.height() > thumbnail_size {
+        Some(cropped.resize(thumbnail_size, thumbnail_size, FilterType::Lanczos3))
+    } else {
+        Some(cropped)
+    }
+}
+
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme.clone();
     // Extract all needed data from dialog first to avoid borrow conflicts
     let (view_mode, input_mode, people_len, faces_len, name_input, cursor, status, _selected_index) = {
         let dialog = match app.people_dialog.as_ref() {
@@ -278,7 +393,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     let title = " People & Faces ";
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta))
+        .border_style(Style::default().fg(theme.emphasis))
         .title(title)
         .title_style(Style::default().add_modifier(Modifier::BOLD));
 
@@ -300,14 +415,14 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Tab bar
     let people_style = if view_mode == PeopleViewMode::People {
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD).add_modifier(Modifier::UNDERLINED)
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD).add_modifier(Modifier::UNDERLINED)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
     let faces_style = if view_mode == PeopleViewMode::Faces {
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD).add_modifier(Modifier::UNDERLINED)
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD).add_modifier(Modifier::UNDERLINED)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
 
     let tab_text = Line::from(vec![
@@ -325,7 +440,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         PeopleViewMode::People => {
             // Re-borrow dialog for people list (immutable is fine here)
             if let Some(ref dialog) = app.people_dialog {
-                render_people_list(frame, dialog, chunks[1]);
+                render_people_list(frame, dialog, chunks[1], &theme);
             }
         }
         PeopleViewMode::Faces => {
@@ -341,19 +456,19 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
             &name_input[cursor..]
         );
         let input = Paragraph::new(input_text)
-            .style(Style::default().fg(Color::Yellow))
+            .style(Style::default().fg(theme.highlight))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" Enter name ")
-                    .border_style(Style::default().fg(Color::Yellow)),
+                    .border_style(Style::default().fg(theme.highlight)),
             );
         frame.render_widget(input, chunks[2]);
     }
 
     // Status
     let status_text = status.as_deref().unwrap_or("");
-    let status_widget = Paragraph::new(status_text).style(Style::default().fg(Color::DarkGray));
+    let status_widget = Paragraph::new(status_text).style(Style::default().fg(theme.muted));
     frame.render_widget(status_widget, chunks[3]);
 
     // Footer
@@ -362,20 +477,20 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     } else {
         "↑↓: navigate | Tab: switch view | n: name | Enter: view photos | Esc: close"
     };
-    let footer = Paragraph::new(footer_text).style(Style::default().fg(Color::DarkGray));
+    let footer = Paragraph::new(footer_text).style(Style::default().fg(theme.muted));
     frame.render_widget(footer, chunks[4]);
 }
 
-fn render_people_list(frame: &mut Frame, dialog: &PeopleDialog, area: Rect) {
+fn render_people_list(frame: &mut Frame, dialog: &PeopleDialog, area: Rect, theme: &Theme) {
     if dialog.people.is_empty() {
         let empty = Paragraph::new("No named people yet.\nSwitch to Faces view (Tab) to name detected faces.")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" People ")
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(theme.muted)),
             );
         frame.render_widget(empty, area);
         return;
@@ -387,11 +502,11 @@ fn render_people_list(frame: &mut Frame, dialog: &PeopleDialog, area: Rect) {
         .map(|person| {
             ListItem::new(vec![
                 Line::from(vec![
-                    Span::styled(&person.name, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                    Span::styled(&person.name, Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)),
                 ]),
                 Line::from(Span::styled(
                     format!("  {} photos", person.face_count),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )),
             ])
         })
@@ -402,12 +517,12 @@ fn render_people_list(frame: &mut Frame, dialog: &PeopleDialog, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(" People ")
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.muted)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::Magenta)
-                .fg(Color::White)
+                .bg(theme.emphasis)
+                .fg(theme.fg)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -419,25 +534,45 @@ fn render_people_list(frame: &mut Frame, dialog: &PeopleDialog, area: Rect) {
 }
 
 fn render_faces_with_preview(frame: &mut Frame, app: &mut App, area: Rect) {
-    let (faces_empty, active_pane, selected_index, faces_data) = match app.people_dialog.as_ref() {
-        Some(d) => (
-            d.faces.is_empty(),
-            d.active_pane,
-            d.selected_index,
-            d.faces.iter().map(|f| (f.photo_filename.clone(), f.face_id)).collect::<Vec<_>>(),
-        ),
-        None => return,
+    let thumbnail_size = app.config.preview.thumbnail_size;
+    let images_available = app.config.preview.image_preview && app.image_preview.is_available();
+    let theme = &app.theme;
+    let Some(dialog) = app.people_dialog.as_mut() else {
+        return;
     };
 
+    // Each face takes two rows inside the list border
+    dialog.set_visible_rows((area.height.saturating_sub(2) / 2) as usize);
+    if images_available {
+        dialog.load_visible_faces(thumbnail_size);
+    }
+
+    let visible = dialog.visible_faces();
+    let (faces_empty, active_pane, selected_index, scroll_offset) =
+        (dialog.faces.is_empty(), dialog.active_pane, dialog.selected_index, dialog.scroll_offset);
+    let faces_data: Vec<(String, i64, &'static str)> = dialog.faces[visible]
+        .iter()
+        .map(|f| {
+            let state = if !images_available || dialog.face_image(f.face_id).is_some() {
+                ""
+            } else if dialog.is_face_unreadable(f.face_id) {
+                "  [unreadable]"
+            } else {
+                "  [loading…]"
+            };
+            (f.photo_filename.clone(), f.face_id, state)
+        })
+        .collect();
+
     if faces_empty {
         let empty = Paragraph::new("No unassigned faces.\nRun face detection first (F key in browser).")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" Unassigned Faces ")
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(theme.muted)),
             );
         frame.render_widget(empty, area);
         return;
@@ -454,27 +589,27 @@ fn render_faces_with_preview(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Determine border colors based on active pane
     let list_border_color = if active_pane == PeopleActivePane::List {
-        Color::Yellow
+        theme.highlight
     } else {
-        Color::DarkGray
+        theme.muted
     };
     let preview_border_color = if active_pane == PeopleActivePane::Preview {
-        Color::Yellow
+        theme.highlight
     } else {
-        Color::DarkGray
+        theme.muted
     };
 
-    // Render face list
+    // Render only the visible faces; the rest are never built or loaded
     let items: Vec<ListItem> = faces_data
         .iter()
-        .map(|(filename, face_id)| {
+        .map(|(filename, face_id, state)| {
             ListItem::new(vec![
                 Line::from(vec![
-                    Span::styled(filename, Style::default().fg(Color::Yellow)),
+                    Span::styled(filename, Style::default().fg(theme.highlight)),
                 ]),
                 Line::from(Span::styled(
-                    format!("  Face #{}", face_id),
-                    Style::default().fg(Color::DarkGray),
+                    format!("  Face #{}{}", face_id, state),
+                    Style::default().fg(theme.muted),
                 )),
             ])
         })
@@ -489,13 +624,13 @@ fn render_faces_with_preview(frame: &mut Frame, app: &mut App, area: Rect) {
         )
         .highlight_style(
             Style::default()
-                .bg(Color::Yellow)
-                .fg(Color::Black)
+                .bg(theme.highlight)
+                .fg(theme.bg)
                 .add_modifier(Modifier::BOLD),
         );
 
     let mut state = ListState::default();
-    state.select(Some(selected_index));
+    state.select(Some(selected_index - scroll_offset));
     frame.render_stateful_widget(list, chunks[0], &mut state);
 
     // Render face preview
@@ -503,6 +638,7 @@ fn render_faces_with_preview(frame: &mut Frame, app: &mut App, area: Rect) {
 }
 
 fn render_face_preview(frame: &mut Frame, app: &mut App, area: Rect, border_color: Color) {
+    let theme = &app.theme;
     let preview_block = Block::default()
         .borders(Borders::ALL)
         .title(" Face Preview ")
@@ -510,20 +646,14 @@ fn render_face_preview(frame: &mut Frame, app: &mut App, area: Rect, border_colo
 
     // Get selected face info before borrowing app mutably
     let face_info = app.people_dialog.as_ref().and_then(|d| {
-        d.selected_face().map(|f| {
-            (
-                std::path::PathBuf::from(&f.photo_path),
-                f.bbox.clone(),
-                f.face_id,
-            )
-        })
+        d.selected_face().map(|f| (f.bbox.clone(), f.face_id))
     });
 
-    let (path, bbox, face_id) = match face_info {
+    let (bbox, face_id) = match face_info {
         Some(info) => info,
         None => {
             let empty = Paragraph::new("No face selected")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(theme.muted))
                 .alignment(Alignment::Center)
                 .block(preview_block);
             frame.render_widget(empty, area);
@@ -540,7 +670,7 @@ fn render_face_preview(frame: &mut Frame, app: &mut App, area: Rect, border_colo
             "Face #{}\n\nPosition: {}x{}\nSize: {}x{}\n\n(Image preview not available)",
             face_id, bbox.x, bbox.y, bbox.width, bbox.height
         ))
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
         frame.render_widget(info, inner_area);
         return;
@@ -555,36 +685,67 @@ fn render_face_preview(frame: &mut Frame, app: &mut App, area: Rect, border_colo
         ])
         .split(inner_area);
 
-    // Load and render the face crop
-    let thumbnail_size = app.config.preview.thumbnail_size;
-
-    // Create a unique cache key for this face crop
-    let face_cache_key = std::path::PathBuf::from(format!(
-        "{}#face_{}",
-        path.display(),
-        face_id
-    ));
-
-    // Try to load the face crop (or start async loading)
-    if let Some(protocol) = app.image_preview.load_face_crop(&path, &bbox, face_id, thumbnail_size) {
+    // Render the face crop once it has loaded
+    let face_image = app.people_dialog.as_ref().and_then(|d| d.face_image(face_id));
+    let unreadable = app.people_dialog.as_ref().is_some_and(|d| d.is_face_unreadable(face_id));
+    let protocol = face_image.and_then(|image| app.image_preview.face_crop_protocol(face_id, &image));
+    if let Some(protocol) = protocol {
         let image = StatefulImage::new(None).resize(Resize::Fit(None));
         frame.render_stateful_widget(image, preview_chunks[0], protocol);
-    } else if app.image_preview.is_loading_face(&face_cache_key) {
-        let loading = Paragraph::new("Loading face...")
-            .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
-            .alignment(Alignment::Center);
-        frame.render_widget(loading, preview_chunks[0]);
     } else {
-        let loading = Paragraph::new("Preparing preview...")
-            .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
-            .alignment(Alignment::Center);
-        frame.render_widget(loading, preview_chunks[0]);
+        let placeholder = Paragraph::new(if unreadable { "[could not read photo]" } else { "[loading…]" })
+            .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted)));
+        frame.render_widget(placeholder, preview_chunks[0]);
     }
 
     // Face info
     let info_text = format!("Face #{} | {}x{} px", face_id, bbox.width, bbox.height);
     let info = Paragraph::new(info_text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(info, preview_chunks[1]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Face;
+
+    fn faces(count: i64) -> Vec<FaceWithPhoto> {
+        (0..count)
+            .map(|id| FaceWithPhoto {
+                face: Face {
+                    id,
+                    photo_id: id,
+                    bbox: BoundingBox { x: 0, y: 0, width: 10, height: 10 },
+                    embedding: None,
+                    person_id: None,
+                    confidence: None,
+                },
+                photo_path: format!("/photos/{}.jpg", id),
+                photo_filename: format!("{}.jpg", id),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_only_visible_faces_are_in_range() {
+        let mut dialog = PeopleDialog::new(Vec::new(), faces(500));
+        dialog.set_visible_rows(10);
+        assert_eq!(dialog.visible_faces(), 0..10);
+
+        for _ in 0..12 {
+            dialog.move_down();
+        }
+        dialog.set_visible_rows(10);
+        assert_eq!(dialog.visible_faces(), 3..13);
+
+        // Fewer faces after naming some: the window shrinks to fit
+        dialog.update_data(Vec::new(), faces(5));
+        assert_eq!(dialog.selected_index, 4);
+        assert_eq!(dialog.visible_faces(), 0..5);
+        assert!(dialog.face_image(0).is_none());
+    }
+}
@@ -0,0 +1,155 @@
+//! Per-pixel comparison of two near-identical photos for the duplicates view.
+
+use anyhow::{Context, Result};
+use image::{imageops::FilterType, DynamicImage, GrayImage, RgbImage};
+use std::path::Path;
+
+/// Differences are multiplied by this so small changes become visible.
+const DIFF_GAIN: u16 = 8;
+
+/// SSIM is computed over non-overlapping windows of this size.
+const SSIM_WINDOW: u32 = 8;
+
+/// Stabilising constants from the SSIM paper, for 8-bit values.
+const SSIM_C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+const SSIM_C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+/// Result of comparing two images.
+pub struct PixelDiff {
+    /// Amplified absolute difference, the size of the first image
+    pub image: DynamicImage,
+    /// Root mean square error over all RGB channels (0-255 scale)
+    pub rmse: f64,
+    /// Mean structural similarity of the luma channel (1.0 = identical)
+    pub ssim: f64,
+}
+
+/// Load two photos, scaled to fit `max_size`, and compare them.
+pub fn compare_files(a: &Path, b: &Path, max_size: u32) -> Result<PixelDiff> {
+    let load = |path: &Path| -> Result<DynamicImage> {
+        let img = image::open(path)
+            .with_context(|| format!("Cannot decode {}", path.display()))?;
+        Ok(if img.width() > max_size || img.height() > max_size {
+            img.resize(max_size, max_size, FilterType::Triangle)
+        } else {
+            img
+        })
+    };
+    Ok(compare(&load(a)?, &load(b)?))
+}
+
+/// Compare two images. The second is resized to the first's dimensions so
+/// that recompressed or downscaled copies can still be compared.
+pub fn compare(a: &DynamicImage, b: &DynamicImage) -> PixelDiff {
+    let a = a.to_rgb8();
+    let b = if b.width() == a.width() && b.height() == a.height() {
+        b.to_rgb8()
+    } else {
+        b.resize_exact(a.width(), a.height(), FilterType::Triangle).to_rgb8()
+    };
+
+    let mut diff = RgbImage::new(a.width(), a.height());
+    let mut sum_sq = 0.0f64;
+    for ((pa, pb), pd) in a.pixels().zip(b.pixels()).zip(diff.pixels_mut()) {
+        for c in 0..3 {
+            let d = (pa[c] as i16 - pb[c] as i16).unsigned_abs();
+            sum_sq += (d as f64) * (d as f64);
+            pd[c] = (d * DIFF_GAIN).min(255) as u8;
+        }
+    }
+
+    let samples = (a.width() as f64 * a.height() as f64 * 3.0).max(1.0);
+    let rmse = (sum_sq / samples).sqrt();
+
+    let ssim = ssim(
+        &DynamicImage::ImageRgb8(a).to_luma8(),
+        &DynamicImage::ImageRgb8(b).to_luma8(),
+    );
+
+    PixelDiff {
+        image: DynamicImage::ImageRgb8(diff),
+        rmse,
+        ssim,
+    }
+}
+
+/// Mean SSIM over non-overlapping windows of two equally sized images.
+fn ssim(a: &GrayImage, b: &GrayImage) -> f64 {
+    let window = SSIM_WINDOW.min(a.width()).min(a.height());
+    if window == 0 {
+        return 1.0;
+    }
+
+    let mut total = 0.0;
+    let mut count = 0u32;
+    for y0 in (0..=a.height() - window).step_by(window as usize) {
+        for x0 in (0..=a.width() - window).step_by(window as usize) {
+            let n = (window * window) as f64;
+            let (mut sa, mut sb, mut saa, mut sbb, mut sab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for y in y0..y0 + window {
+                for x in x0..x0 + window {
+                    let va = a.get_pixel(x, y)[0] as f64;
+                    let vb = b.get_pixel(x, y)[0] as f64;
+                    sa += va;
+                    sb += vb;
+                    saa += va * va;
+                    sbb += vb * vb;
+                    sab += va * vb;
+                }
+            }
+            let (mean_a, mean_b) = (sa / n, sb / n);
+            let var_a = saa / n - mean_a * mean_a;
+            let var_b = sbb / n - mean_b * mean_b;
+            let cov = sab / n - mean_a * mean_b;
+
+            total += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * cov + SSIM_C2))
+                / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1) * (var_a + var_b + SSIM_C2));
+            count += 1;
+        }
+    }
+
+    total / count as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    fn gradient(width: u32, height: u32, offset: u8) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let v = ((x * 7 + y * 3) % 200) as u8;
+            Rgb([v.saturating_add(offset), v, 255 - v])
+        }))
+    }
+
+    #[test]
+    fn test_identical_images() {
+        let img = gradient(32, 24, 0);
+        let diff = compare(&img, &img);
+        assert_eq!(diff.rmse, 0.0);
+        assert!((diff.ssim - 1.0).abs() < 1e-9);
+        assert!(diff.image.to_rgb8().pixels().all(|p| p.0 == [0, 0, 0]));
+    }
+
+    #[test]
+    fn test_difference_is_amplified() {
+        let diff = compare(&gradient(16, 16, 0), &gradient(16, 16, 2));
+        // Only the red channel differs, by 2 -> 16 after gain
+        let px = diff.image.to_rgb8().get_pixel(0, 0).0;
+        assert_eq!(px, [16, 0, 0]);
+        assert!(diff.rmse > 0.0 && diff.rmse < 2.0);
+        assert!(diff.ssim > 0.9);
+    }
+
+    #[test]
+    fn test_different_sizes_are_compared() {
+        let a = gradient(40, 30, 0);
+        let inverted = DynamicImage::ImageRgb8(RgbImage::from_fn(20, 15, |x, y| {
+            Rgb([(x * 12) as u8, 255 - (y * 16) as u8, 128])
+        }));
+        let diff = compare(&a, &inverted);
+        assert_eq!((diff.image.width(), diff.image.height()), (40, 30));
+        assert!(diff.ssim < 0.9);
+    }
+}
@@ -3,20 +3,37 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
 use image::{DynamicImage, imageops::FilterType};
-use ratatui_image::{picker::Picker, protocol::StatefulProtocol, Resize, StatefulImage};
+use ratatui_image::{picker::{Picker, ProtocolType}, protocol::StatefulProtocol, Resize, StatefulImage};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 
-use crate::app::App;
-use crate::config::{ImageProtocol, ThumbnailConfig};
-use crate::db::{BoundingBox, PhotoMetadata};
-use crate::scanner::ThumbnailManager;
+use crate::app::{App, DirEntry, DirStats};
+use crate::config::{ImageProtocol, PreviewConfig, PreviewMode, ThumbnailConfig};
+use crate::db::PhotoMetadata;
+use crate::scanner::{heif, pdf, video, ThumbnailManager};
+use crate::ui::animation::{self, Animation, DecodedAnimation, FrameClock};
+use crate::ui::histogram::{self, CHART_COLUMNS, CHART_ROWS};
+use crate::ui::protocol;
+use crate::ui::text_preview;
+use crate::ui::theme::Theme;
+use crate::ui::zoom::Zoom;
+
+/// Histograms are cached per image and rotation
+type HistogramKey = (PathBuf, i32);
 
 /// Manages image preview state and caching
 pub struct ImagePreviewState {
     picker: Option<Picker>,
+    /// What the terminal query found at startup, before any protocol is
+    /// forced
+    detected: Option<Picker>,
+    /// Protocol `picker` was made for
+    protocol: ImageProtocol,
+    /// What `ImageProtocol::Auto` stands for in this terminal, detected at
+    /// startup
+    auto_protocol: ImageProtocol,
     /// Cache of loaded images keyed by path (ready to display)
     image_cache: HashMap<PathBuf, StatefulProtocol>,
     /// Cache of photo metadata from database keyed by path
@@ -29,46 +46,76 @@ pub struct ImagePreviewState {
     image_receiver: Option<mpsc::Receiver<(PathBuf, DynamicImage)>>,
     /// Sender for async image loading
     image_sender: mpsc::Sender<(PathBuf, DynamicImage)>,
+    /// The selected image's animation (GIF/WebP) keyed by path; frames take
+    /// far more memory than stills, so no other animation is kept
+    animations: HashMap<PathBuf, Animation>,
+    /// Receiver for async animation decoding
+    animation_receiver: Option<mpsc::Receiver<(PathBuf, DecodedAnimation)>>,
+    /// Sender for async animation decoding
+    animation_sender: mpsc::Sender<(PathBuf, DecodedAnimation)>,
+    /// Whether the current image is an animation being played
+    pub is_animated: bool,
     /// Current image being displayed
     current_path: Option<PathBuf>,
     /// Scroll offset for preview text (metadata + description)
     pub scroll_offset: u16,
     /// Thumbnail size for image loading
     thumbnail_size: u32,
-    /// Cache of face crops keyed by "path#face_id"
-    face_cache: HashMap<PathBuf, StatefulProtocol>,
-    /// Face crops currently being loaded
-    loading_faces: HashSet<PathBuf>,
-    /// Receiver for async face crop loading
-    face_receiver: Option<mpsc::Receiver<(PathBuf, DynamicImage)>>,
-    /// Sender for async face crop loading
-    face_sender: mpsc::Sender<(PathBuf, DynamicImage)>,
+    /// Face crops ready to display, keyed by face ID
+    face_cache: HashMap<i64, StatefulProtocol>,
     /// Thumbnail manager for accessing pre-generated thumbnails
     thumbnail_manager: ThumbnailManager,
+    /// Zoom into the current image
+    pub zoom: Zoom,
+    /// Whether the luminance histogram is shown under the image
+    pub show_histogram: bool,
+    /// Cache of luminance histograms
+    histograms: HashMap<HistogramKey, [u32; 256]>,
+    /// Histograms currently being computed
+    loading_histograms: HashSet<HistogramKey>,
+    /// Receiver for async histogram computation
+    histogram_receiver: Option<mpsc::Receiver<(HistogramKey, [u32; 256])>>,
+    /// Sender for async histogram computation
+    histogram_sender: mpsc::Sender<(HistogramKey, [u32; 256])>,
+    /// Text or hex preview of the last non-image file shown
+    text_preview: Option<(PathBuf, Result<Vec<Line<'static>>, String>)>,
 }
 
 impl ImagePreviewState {
-    pub fn new(protocol: ImageProtocol, thumbnail_config: &ThumbnailConfig) -> Self {
-        let picker = Self::create_picker(protocol);
+    pub fn new(preview: &PreviewConfig, thumbnail_config: &ThumbnailConfig) -> Self {
+        let detected = if preview.any_images() { Picker::from_query_stdio().ok() } else { None };
+        let auto_protocol = if preview.any_images() { protocol::best_protocol(detected) } else { ImageProtocol::None };
         let (img_tx, img_rx) = mpsc::channel();
-        let (face_tx, face_rx) = mpsc::channel();
+        let (anim_tx, anim_rx) = mpsc::channel();
+        let (hist_tx, hist_rx) = mpsc::channel();
         let thumbnail_manager = ThumbnailManager::new(thumbnail_config);
         Self {
-            picker,
+            picker: picker_for(detected, resolve_protocol(preview.protocol, auto_protocol)),
+            detected,
+            protocol: preview.protocol,
+            auto_protocol,
             image_cache: HashMap::new(),
             metadata_cache: HashMap::new(),
             rotation_cache: HashMap::new(),
             loading_images: HashSet::new(),
             image_receiver: Some(img_rx),
             image_sender: img_tx,
+            animations: HashMap::new(),
+            animation_receiver: Some(anim_rx),
+            animation_sender: anim_tx,
+            is_animated: false,
             current_path: None,
             scroll_offset: 0,
             thumbnail_size: 1024,
             face_cache: HashMap::new(),
-            loading_faces: HashSet::new(),
-            face_receiver: Some(face_rx),
-            face_sender: face_tx,
             thumbnail_manager,
+            zoom: Zoom::default(),
+            show_histogram: false,
+            histograms: HashMap::new(),
+            loading_histograms: HashSet::new(),
+            histogram_receiver: Some(hist_rx),
+            histogram_sender: hist_tx,
+            text_preview: None,
         }
     }
 
@@ -87,12 +134,23 @@ impl ImagePreviewState {
         self.scroll_offset = 0;
     }
 
+    /// Zoom into the centre of the current image
+    pub fn zoom_in(&mut self) {
+        self.zoom.zoom_in();
+    }
+
+    /// Zoom back out, down to the whole image
+    pub fn zoom_out(&mut self) {
+        self.zoom.zoom_out();
+    }
+
     /// Check for completed async image loads
     pub fn poll_async_loads(&mut self) {
         // Poll for completed images
         if let Some(ref receiver) = self.image_receiver {
             while let Ok((path, dyn_img)) = receiver.try_recv() {
                 self.loading_images.remove(&path);
+                self.zoom.offer_source(&path.to_string_lossy(), &dyn_img);
                 // Convert to protocol on main thread (fast)
                 if let Some(ref mut picker) = self.picker {
                     let protocol = picker.new_resize_protocol(dyn_img);
@@ -101,17 +159,62 @@ impl ImagePreviewState {
             }
         }
 
-        // Poll for completed face crops
-        if let Some(ref receiver) = self.face_receiver {
-            while let Ok((cache_key, dyn_img)) = receiver.try_recv() {
-                self.loading_faces.remove(&cache_key);
-                // Convert to protocol on main thread (fast)
+        // Poll for decoded animations
+        if let Some(ref receiver) = self.animation_receiver {
+            while let Ok((path, decoded)) = receiver.try_recv() {
+                self.loading_images.remove(&path);
+                // Dropped if the selection moved on while it was decoding
+                if self.current_path.as_ref() != Some(&path) {
+                    continue;
+                }
                 if let Some(ref mut picker) = self.picker {
-                    let protocol = picker.new_resize_protocol(dyn_img);
-                    self.face_cache.insert(cache_key, protocol);
+                    let (images, delays): (Vec<_>, Vec<_>) = decoded.frames.into_iter().unzip();
+                    let frames = images
+                        .into_iter()
+                        .map(|img| picker.new_resize_protocol(img))
+                        .collect();
+                    self.animations.insert(
+                        path,
+                        Animation {
+                            label: decoded.label,
+                            frames,
+                            clock: FrameClock::new(delays),
+                        },
+                    );
                 }
             }
         }
+
+        // Poll for computed histograms
+        if let Some(ref receiver) = self.histogram_receiver {
+            while let Ok((key, buckets)) = receiver.try_recv() {
+                self.loading_histograms.remove(&key);
+                self.histograms.insert(key, buckets);
+            }
+        }
+    }
+
+    /// Convert an already decoded image to a terminal protocol.
+    pub fn new_protocol(&mut self, img: DynamicImage) -> Option<StatefulProtocol> {
+        self.picker.as_mut().map(|picker| picker.new_resize_protocol(img))
+    }
+
+    /// Advance the current animation, called on every event loop tick.
+    /// Returns true when a new frame should be drawn.
+    pub fn tick_animation(&mut self) -> bool {
+        if !self.is_animated {
+            return false;
+        }
+        self.current_path
+            .as_ref()
+            .and_then(|path| self.animations.get_mut(path))
+            .map(|anim| anim.clock.tick())
+            .unwrap_or(false)
+    }
+
+    /// Title indicator for an animated image, e.g. `[GIF 12 frames]`.
+    pub fn animation_indicator(&self, path: &PathBuf) -> Option<String> {
+        self.animations.get(path).map(|anim| anim.indicator())
     }
 
     /// Get cached metadata for a path. Returns None if not in cache.
@@ -152,6 +255,7 @@ impl ImagePreviewState {
     pub fn invalidate_cache(&mut self) {
         if let Some(ref path) = self.current_path.clone() {
             self.image_cache.remove(path);
+            self.animations.remove(path);
             self.metadata_cache.remove(path);
             self.rotation_cache.remove(path);
             // Also invalidate on-disk thumbnail cache for all rotations
@@ -162,30 +266,61 @@ impl ImagePreviewState {
     /// Invalidate thumbnail for a specific path (used by gallery rotation)
     pub fn invalidate_thumbnail(&mut self, path: &PathBuf) {
         self.image_cache.remove(path);
+        self.animations.remove(path);
         self.metadata_cache.remove(path);
         self.rotation_cache.remove(path);
         self.thumbnail_manager.invalidate(path);
     }
 
-    fn create_picker(protocol: ImageProtocol) -> Option<Picker> {
-        match protocol {
-            ImageProtocol::None => None,
-            // For all other cases, try to auto-detect terminal capabilities
-            // ratatui-image v3.0 handles protocol selection automatically
-            _ => Picker::from_query_stdio().ok(),
+    /// `protocol` with `Auto` replaced by the protocol detected at startup
+    pub fn resolve_protocol(&self, protocol: ImageProtocol) -> ImageProtocol {
+        resolve_protocol(protocol, self.auto_protocol)
+    }
+
+    /// Switch to `protocol`, e.g. on entering a directory with a protocol
+    /// override. Images encoded for the old protocol are dropped.
+    pub fn set_protocol(&mut self, protocol: ImageProtocol) {
+        if protocol == self.protocol {
+            return;
         }
+        self.protocol = protocol;
+        self.picker = picker_for(self.detected, self.resolve_protocol(protocol));
+        self.image_cache.clear();
+        self.animations.clear();
+        self.face_cache.clear();
+        self.zoom = Zoom::default();
     }
 
     /// Load an image for the given path asynchronously, returns cached if available
     /// rotation_degrees: 0, 90, 180, or 270 degrees clockwise
     pub fn load_image(&mut self, path: &PathBuf, thumbnail_size: u32, rotation_degrees: i32) -> Option<&mut StatefulProtocol> {
-        // Poll for any completed loads first
-        self.poll_async_loads();
-
-        // Update current path and thumbnail size
+        // Update current path and thumbnail size; leaving an animation
+        // drops it, so it plays from the first frame when selected again
+        if self.current_path.as_ref() != Some(path) {
+            self.animations.clear();
+        }
         self.current_path = Some(path.clone());
         self.thumbnail_size = thumbnail_size;
 
+        // Poll for any completed loads
+        self.poll_async_loads();
+        self.zoom.follow(&path.to_string_lossy());
+
+        // Animated images show the frame picked by the animation clock
+        self.is_animated = self.animations.contains_key(path);
+        if self.is_animated {
+            return self.animations.get_mut(path).and_then(|anim| anim.current_frame());
+        }
+
+        // Zooming crops the decoded image, so reload it if it was dropped
+        if self.zoom.needs_source() {
+            self.image_cache.remove(path);
+        }
+        if self.zoom.is_zoomed() && !self.zoom.needs_source() {
+            let picker = self.picker.as_mut()?;
+            return self.zoom.view(picker);
+        }
+
         // Check cache first - return immediately if available
         if self.image_cache.contains_key(path) {
             return self.image_cache.get_mut(path);
@@ -196,6 +331,7 @@ impl ImagePreviewState {
             self.loading_images.insert(path.clone());
             let path_clone = path.clone();
             let sender = self.image_sender.clone();
+            let anim_sender = self.animation_sender.clone();
             let size = thumbnail_size;
             let rotation = rotation_degrees;
 
@@ -203,11 +339,29 @@ impl ImagePreviewState {
             let cached_thumb = self.thumbnail_manager.get_cached_path(path, rotation);
 
             std::thread::spawn(move || {
+                if let Some(decoded) = animation::decode(&path_clone, size, rotation) {
+                    let _ = anim_sender.send((path_clone, decoded));
+                    return;
+                }
+
                 // Always load from original for preview - we need higher resolution than cached thumbnails
                 // Cached thumbnails are 256px, but preview needs 1024px for quality
                 // Rotation is applied here since we're loading from original
-                let load_result = image::ImageReader::open(&path_clone)
-                    .and_then(|r| r.decode().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+                let decoded = if pdf::is_pdf(&path_clone) {
+                    pdf::render_first_page(&path_clone, size)
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+                } else if video::is_video(&path_clone) {
+                    video::first_frame(&path_clone, size)
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+                } else {
+                    image::ImageReader::open(&path_clone)
+                        .and_then(|r| r.decode().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+                        .or_else(|e| {
+                            // Not decodable (e.g. HEIF): show the embedded EXIF preview instead
+                            heif::embedded_thumbnail(&path_clone).ok_or(e)
+                        })
+                };
+                let load_result = decoded
                     .map(|img| {
                         let resized = img.resize(size, size, FilterType::Triangle);
                         // Apply rotation
@@ -233,82 +387,40 @@ impl ImagePreviewState {
         None
     }
 
-    /// Check if an image is currently loading
-    pub fn is_loading_image(&self, path: &PathBuf) -> bool {
-        self.loading_images.contains(path)
-    }
-
-    /// Load a face crop for the given path and bounding box
-    pub fn load_face_crop(
-        &mut self,
-        path: &PathBuf,
-        bbox: &BoundingBox,
-        face_id: i64,
-        thumbnail_size: u32,
-    ) -> Option<&mut StatefulProtocol> {
-        // Poll for any completed loads first
-        self.poll_async_loads();
-
-        // Create unique cache key for this face
-        let cache_key = PathBuf::from(format!("{}#face_{}", path.display(), face_id));
-
-        // Check cache first
-        if self.face_cache.contains_key(&cache_key) {
-            return self.face_cache.get_mut(&cache_key);
-        }
-
-        // Start async load if not already loading
-        if !self.loading_faces.contains(&cache_key) && self.picker.is_some() {
-            self.loading_faces.insert(cache_key.clone());
-            let path_clone = path.clone();
-            let sender = self.face_sender.clone();
-            let bbox_x = bbox.x;
-            let bbox_y = bbox.y;
-            let bbox_w = bbox.width;
-            let bbox_h = bbox.height;
-
+    /// Luminance histogram for an image, computed in the background on
+    /// first request. Returns None until it is ready.
+    pub fn load_histogram(&mut self, path: &Path, rotation_degrees: i32) -> Option<&[u32; 256]> {
+        let key = (path.to_path_buf(), rotation_degrees);
+        if !self.histograms.contains_key(&key) && !self.loading_histograms.contains(&key) {
+            self.loading_histograms.insert(key.clone());
+            let sender = self.histogram_sender.clone();
+            let thumbnails = self.thumbnail_manager.clone();
+            let job = key.clone();
             std::thread::spawn(move || {
-                if let Ok(dyn_img) = image::ImageReader::open(&path_clone)
-                    .and_then(|r| r.decode().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
-                {
-                    // Calculate crop region with padding (20% extra on each side)
-                    let img_width = dyn_img.width() as i32;
-                    let img_height = dyn_img.height() as i32;
-
-                    let padding_x = (bbox_w as f32 * 0.3) as i32;
-                    let padding_y = (bbox_h as f32 * 0.3) as i32;
-
-                    let crop_x = (bbox_x - padding_x).max(0) as u32;
-                    let crop_y = (bbox_y - padding_y).max(0) as u32;
-                    let crop_w = ((bbox_w + padding_x * 2) as i32)
-                        .min(img_width - crop_x as i32)
-                        .max(1) as u32;
-                    let crop_h = ((bbox_h + padding_y * 2) as i32)
-                        .min(img_height - crop_y as i32)
-                        .max(1) as u32;
-
-                    // Crop to face region
-                    let cropped = dyn_img.crop_imm(crop_x, crop_y, crop_w, crop_h);
-
-                    // Only downscale if crop is larger than target, never upscale
-                    // (upscaling small face crops makes them blurry)
-                    let final_image = if cropped.width() > thumbnail_size || cropped.height() > thumbnail_size {
-                        cropped.resize(thumbnail_size, thumbnail_size, FilterType::Lanczos3)
-                    } else {
-                        cropped
-                    };
-                    let _ = sender.send((cache_key, final_image));
-                }
+                // An empty histogram marks the image as undecodable
+                let buckets = compute_histogram(&job.0, job.1, &thumbnails).unwrap_or_else(|e| {
+                    tracing::debug!(path = %job.0.display(), error = %e, "Histogram failed");
+                    [0; 256]
+                });
+                let _ = sender.send((job, buckets));
             });
         }
+        self.histograms.get(&key)
+    }
 
-        // Return None while loading
-        None
+    /// Check if an image is currently loading
+    pub fn is_loading_image(&self, path: &PathBuf) -> bool {
+        self.loading_images.contains(path)
     }
 
-    /// Check if a face crop is currently loading
-    pub fn is_loading_face(&self, cache_key: &PathBuf) -> bool {
-        self.loading_faces.contains(cache_key)
+    /// Display protocol for a decoded face crop, created on first use
+    pub fn face_crop_protocol(&mut self, face_id: i64, image: &DynamicImage) -> Option<&mut StatefulProtocol> {
+        let picker = self.picker.as_mut()?;
+        Some(
+            self.face_cache
+                .entry(face_id)
+                .or_insert_with(|| picker.new_resize_protocol(image.clone())),
+        )
     }
 
     /// Check if image preview is available
@@ -319,14 +431,41 @@ impl ImagePreviewState {
 
 impl Default for ImagePreviewState {
     fn default() -> Self {
-        Self::new(ImageProtocol::Auto, &ThumbnailConfig::default())
+        Self::new(&PreviewConfig::default(), &ThumbnailConfig::default())
     }
 }
 
+fn resolve_protocol(protocol: ImageProtocol, auto_protocol: ImageProtocol) -> ImageProtocol {
+    match protocol {
+        ImageProtocol::Auto => auto_protocol,
+        protocol => protocol,
+    }
+}
+
+/// The detected picker forced to `protocol`; `Auto` runs protocol
+/// detection
+pub fn picker_for(detected: Option<Picker>, protocol: ImageProtocol) -> Option<Picker> {
+    let mut picker = detected?;
+    let protocol_type = match protocol {
+        ImageProtocol::None => return None,
+        ImageProtocol::Auto => return picker_for(detected, protocol::best_protocol(detected)),
+        ImageProtocol::Sixel => ProtocolType::Sixel,
+        ImageProtocol::Kitty => ProtocolType::Kitty,
+        ImageProtocol::ITerm2 => ProtocolType::Iterm2,
+        ImageProtocol::Halfblocks => ProtocolType::Halfblocks,
+    };
+    picker.set_protocol_type(protocol_type);
+    Some(picker)
+}
+
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let protocol = app.config.preview.protocol_for(&app.current_dir);
+    app.image_preview.set_protocol(protocol);
+
+    let theme = &app.theme;
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.muted))
         .title("Preview");
 
     // Clone entry to avoid borrow conflicts
@@ -334,7 +473,11 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
 
     match selected {
         Some(ref entry) if entry.is_dir => {
-            render_directory_preview(frame, &entry.path, block, area);
+            let stats = app.dir_stats.get(&entry.path);
+            render_directory_preview(frame, &entry.path, stats, block, area, theme);
+        }
+        Some(ref entry) if app.preview_mode() == PreviewMode::TextPreview => {
+            TextPreviewRenderer.render(frame, app, entry, block, area);
         }
         Some(ref entry) if is_image(&entry.name) => {
             // Get metadata from database (cached)
@@ -342,18 +485,147 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
             render_image_preview(frame, app, entry, metadata.as_ref(), block, area);
         }
         Some(ref entry) => {
-            render_file_preview(frame, entry, block, area);
+            renderer_for(&entry.path).render(frame, app, entry, block, area);
         }
         None => {
             let paragraph = Paragraph::new("No selection")
                 .block(block)
-                .style(Style::default().fg(Color::DarkGray));
+                .style(Style::default().fg(theme.muted));
             frame.render_widget(paragraph, area);
         }
     }
 }
 
-fn render_directory_preview(frame: &mut Frame, path: &std::path::Path, block: Block, area: Rect) {
+/// Preview for files other than photos, shown with `show_all_files`
+pub trait PreviewRenderer {
+    fn can_render(&self, path: &Path) -> bool;
+    /// Draw the file into `area`. Scrollable text uses the shared
+    /// `ImagePreviewState::scroll_offset`.
+    fn render(&self, frame: &mut Frame, app: &mut App, entry: &DirEntry, block: Block, area: Rect);
+}
+
+/// First page of a PDF, with its scanned metadata
+pub struct PdfPreviewRenderer;
+
+impl PreviewRenderer for PdfPreviewRenderer {
+    fn can_render(&self, path: &Path) -> bool {
+        pdf::is_pdf(path)
+    }
+
+    fn render(&self, frame: &mut Frame, app: &mut App, entry: &DirEntry, block: Block, area: Rect) {
+        let metadata = app.get_photo_metadata(&entry.path);
+        render_image_preview(frame, app, entry, metadata.as_ref(), block, area);
+    }
+}
+
+/// First frame of a video, extracted with ffmpeg
+pub struct VideoThumbnailRenderer;
+
+impl PreviewRenderer for VideoThumbnailRenderer {
+    fn can_render(&self, path: &Path) -> bool {
+        video::is_video(path)
+    }
+
+    fn render(&self, frame: &mut Frame, app: &mut App, entry: &DirEntry, block: Block, area: Rect) {
+        let theme = app.theme.clone();
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(inner_area);
+
+        if app.preview_mode().shows_image() {
+            let thumbnail_size = app.config.preview.thumbnail_size;
+            if let Some(protocol) = app.image_preview.load_image(&entry.path, thumbnail_size, 0) {
+                let image = StatefulImage::new(None).resize(Resize::Fit(None));
+                frame.render_stateful_widget(image, chunks[0], protocol);
+            } else if app.image_preview.is_loading_image(&entry.path) {
+                let loading = Paragraph::new("Extracting first frame...")
+                    .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC))
+                    .alignment(Alignment::Center);
+                frame.render_widget(loading, chunks[0]);
+            }
+        }
+
+        let info_lines = vec![
+            Line::from(vec![
+                Span::styled("File: ", Style::default().fg(theme.muted)),
+                Span::raw(&entry.name),
+            ]),
+            Line::from(vec![
+                Span::styled("Size: ", Style::default().fg(theme.muted)),
+                Span::raw(format_size(entry.size)),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(info_lines).scroll((app.image_preview.scroll_offset, 0)), chunks[1]);
+    }
+}
+
+/// Highlighted text, or a hex dump for binary files. Renders anything, so
+/// it comes last.
+pub struct TextPreviewRenderer;
+
+impl PreviewRenderer for TextPreviewRenderer {
+    fn can_render(&self, _path: &Path) -> bool {
+        true
+    }
+
+    fn render(&self, frame: &mut Frame, app: &mut App, entry: &DirEntry, block: Block, area: Rect) {
+        render_file_preview(frame, app, entry, block, area);
+    }
+}
+
+/// Non-photo renderers, most specific first
+const RENDERERS: &[&dyn PreviewRenderer] = &[&PdfPreviewRenderer, &VideoThumbnailRenderer, &TextPreviewRenderer];
+
+/// The first renderer that can show the file
+fn renderer_for(path: &Path) -> &'static dyn PreviewRenderer {
+    RENDERERS
+        .iter()
+        .copied()
+        .find(|r| r.can_render(path))
+        .unwrap_or(&TextPreviewRenderer)
+}
+
+fn render_directory_preview(
+    frame: &mut Frame,
+    path: &std::path::Path,
+    stats: Option<&DirStats>,
+    block: Block,
+    area: Rect,
+    theme: &Theme,
+) {
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(inner);
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    let summary = match stats {
+        Some(stats) => Line::from(format_dir_stats(stats)),
+        None => Line::from(Span::styled(
+            "Counting...",
+            Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+        )),
+    };
+    let header = Paragraph::new(vec![
+        Line::from(Span::styled(
+            format!("📁 {}/", name),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        )),
+        summary,
+    ])
+    .wrap(Wrap { trim: true });
+    frame.render_widget(header, chunks[0]);
+
     let entries: Vec<ListItem> = match fs::read_dir(path) {
         Ok(dir) => dir
             .filter_map(|e| e.ok())
@@ -363,39 +635,125 @@ fn render_directory_preview(frame: &mut Frame, path: &std::path::Path, block: Bl
                 let name = entry.file_name().to_string_lossy().to_string();
                 let icon = if is_dir { "/ " } else { "  " };
                 let style = if is_dir {
-                    Style::default().fg(Color::Cyan)
+                    Style::default().fg(theme.accent)
                 } else {
                     Style::default()
                 };
                 ListItem::new(format!("{}{}", icon, name)).style(style)
             })
             .collect(),
-        Err(_) => vec![ListItem::new("Cannot read directory").style(Style::default().fg(Color::Red))],
+        Err(_) => vec![ListItem::new("Cannot read directory").style(Style::default().fg(theme.error))],
+    };
+
+    let list = List::new(entries);
+    frame.render_widget(list, chunks[1]);
+}
+
+/// "3 subdirectories · 412 images · 4.2 GB · 2024-08-10"
+fn format_dir_stats(stats: &DirStats) -> String {
+    let mut parts = vec![match stats.subdirectories {
+        1 => "1 subdirectory".to_string(),
+        n => format!("{} subdirectories", n),
+    }];
+
+    if stats.photos.photo_count == 0 {
+        parts.push("no scanned images".to_string());
+    } else {
+        let count = stats.photos.photo_count;
+        parts.push(format!("{} image{}", count, if count == 1 { "" } else { "s" }));
+        parts.push(format_size(stats.photos.total_bytes.max(0) as u64));
+        if let Some(ref taken) = stats.photos.latest_taken_at {
+            // EXIF dates are "YYYY-MM-DD HH:MM:SS" or "YYYY:MM:DD HH:MM:SS"
+            parts.push(taken.chars().take(10).collect::<String>().replace(':', "-"));
+        }
+    }
+
+    parts.join(" · ")
+}
+
+/// Luminance histogram of a photo. The cached scan thumbnail for the
+/// rotation is used when there is one (fast); otherwise the original is
+/// decoded and shrunk. Rotation does not change the counts, it only picks
+/// the thumbnail.
+pub fn compute_histogram(path: &Path, rotation: i32, thumbnails: &ThumbnailManager) -> anyhow::Result<[u32; 256]> {
+    let image = match thumbnails.get_cached_path(path, rotation) {
+        Some(thumb) => image::open(thumb)?,
+        None if pdf::is_pdf(path) => pdf::render_first_page(path, 256)?,
+        None => match image::open(path) {
+            Ok(img) => img.thumbnail(256, 256),
+            Err(e) => heif::embedded_thumbnail(path).ok_or(e)?,
+        },
     };
+    Ok(histogram::luma_histogram(&image))
+}
 
-    let list = List::new(entries).block(block);
-    frame.render_widget(list, area);
+fn render_histogram(frame: &mut Frame, app: &mut App, path: &Path, rotation: i32, area: Rect, theme: &Theme) {
+    let lines: Vec<Line> = match app.image_preview.load_histogram(path, rotation) {
+        Some(buckets) if buckets.iter().all(|&c| c == 0) => vec![Line::from(Span::styled(
+            "No histogram available",
+            Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+        ))],
+        Some(buckets) => {
+            let columns = CHART_COLUMNS.min(area.width as usize);
+            histogram::braille_chart(buckets, columns, area.height as usize)
+                .into_iter()
+                .map(|row| Line::from(Span::styled(row, Style::default().fg(theme.secondary))))
+                .collect()
+        }
+        None => vec![Line::from(Span::styled(
+            "Computing histogram...",
+            Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+        ))],
+    };
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
 }
 
 fn render_image_preview(
     frame: &mut Frame,
     app: &mut App,
-    entry: &crate::app::DirEntry,
+    entry: &DirEntry,
     metadata: Option<&PhotoMetadata>,
     block: Block,
     area: Rect,
 ) {
+    let theme = app.theme.clone();
+    let block = match app.image_preview.animation_indicator(&entry.path) {
+        Some(indicator) => block.title(indicator),
+        None => block,
+    };
+    app.image_preview.zoom.follow(&entry.path.to_string_lossy());
+    let block = match app.image_preview.zoom.label() {
+        Some(label) => block.title(format!(" {} ", label)),
+        None => block,
+    };
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
 
-    // Check if image preview is enabled and available
-    let show_image = app.config.preview.image_preview && app.image_preview.is_available();
+    // Histogram along the bottom, up to a third of the pane
+    let inner_area = if app.image_preview.show_histogram {
+        let rows = CHART_ROWS.min(inner_area.height as usize / 3) as u16;
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(rows)])
+            .split(inner_area);
+        let rotation = app.get_photo_rotation(&entry.path);
+        render_histogram(frame, app, &entry.path, rotation, parts[1], &theme);
+        parts[0]
+    } else {
+        inner_area
+    };
+
+    let mode = app.preview_mode();
     let scroll_offset = app.image_preview.scroll_offset;
 
-    if show_image {
+    if mode.shows_image() {
         // Adaptive split: smaller image when we have description content
         let has_description = metadata.as_ref().map(|m| m.description.is_some()).unwrap_or(false);
-        let image_percent = if has_description { 45 } else { 60 };
+        let image_percent = match mode {
+            PreviewMode::ImageOnly => 100,
+            _ if has_description => 45,
+            _ => 60,
+        };
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -415,33 +773,36 @@ fn render_image_preview(
         } else if app.image_preview.is_loading_image(&entry.path) {
             // Show loading indicator while image loads
             let loading = Paragraph::new("Loading image...")
-                .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+                .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC))
                 .alignment(Alignment::Center);
             frame.render_widget(loading, chunks[0]);
         }
 
         // Render metadata below
-        render_image_metadata(frame, entry, metadata, chunks[1], scroll_offset);
+        if mode != PreviewMode::ImageOnly {
+            render_image_metadata(frame, entry, metadata, chunks[1], scroll_offset, &theme);
+        }
     } else {
-        // Just show metadata (fallback mode)
-        render_image_metadata(frame, entry, metadata, inner_area, scroll_offset);
+        // Just show metadata (metadata-only mode, or images unavailable)
+        render_image_metadata(frame, entry, metadata, inner_area, scroll_offset, &theme);
     }
 }
 
 fn render_image_metadata(
     frame: &mut Frame,
-    entry: &crate::app::DirEntry,
+    entry: &DirEntry,
     metadata: Option<&PhotoMetadata>,
     area: Rect,
     scroll_offset: u16,
+    theme: &Theme,
 ) {
     let mut info_lines = vec![
         Line::from(vec![
-            Span::styled("File: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("File: ", Style::default().fg(theme.muted)),
             Span::raw(&entry.name),
         ]),
         Line::from(vec![
-            Span::styled("Size: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Size: ", Style::default().fg(theme.muted)),
             Span::raw(format_size(entry.size)),
         ]),
     ];
@@ -450,7 +811,7 @@ fn render_image_metadata(
         // Dimensions
         if let (Some(w), Some(h)) = (meta.width, meta.height) {
             info_lines.push(Line::from(vec![
-                Span::styled("Dimensions: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Dimensions: ", Style::default().fg(theme.muted)),
                 Span::raw(format!("{}x{}", w, h)),
             ]));
         }
@@ -458,7 +819,7 @@ fn render_image_metadata(
         // Format
         if let Some(ref format) = meta.format {
             info_lines.push(Line::from(vec![
-                Span::styled("Format: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Format: ", Style::default().fg(theme.muted)),
                 Span::raw(format),
             ]));
         }
@@ -473,7 +834,7 @@ fn render_image_metadata(
         .collect();
         if !camera_info.is_empty() {
             info_lines.push(Line::from(vec![
-                Span::styled("Camera: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Camera: ", Style::default().fg(theme.muted)),
                 Span::raw(camera_info.join(" ")),
             ]));
         }
@@ -481,7 +842,7 @@ fn render_image_metadata(
         // Lens
         if let Some(ref lens) = meta.lens {
             info_lines.push(Line::from(vec![
-                Span::styled("Lens: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Lens: ", Style::default().fg(theme.muted)),
                 Span::raw(lens),
             ]));
         }
@@ -502,7 +863,7 @@ fn render_image_metadata(
         }
         if !exposure_parts.is_empty() {
             info_lines.push(Line::from(vec![
-                Span::styled("Exposure: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Exposure: ", Style::default().fg(theme.muted)),
                 Span::raw(exposure_parts.join(" | ")),
             ]));
         }
@@ -510,7 +871,7 @@ fn render_image_metadata(
         // Date taken
         if let Some(ref taken) = meta.taken_at {
             info_lines.push(Line::from(vec![
-                Span::styled("Taken: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Taken: ", Style::default().fg(theme.muted)),
                 Span::raw(taken),
             ]));
         }
@@ -518,8 +879,12 @@ fn render_image_metadata(
         // GPS coordinates
         if let (Some(lat), Some(lon)) = (meta.gps_latitude, meta.gps_longitude) {
             info_lines.push(Line::from(vec![
-                Span::styled("GPS: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("GPS: ", Style::default().fg(theme.muted)),
                 Span::raw(format!("{:.6}, {:.6}", lat, lon)),
+                Span::styled(
+                    if meta.gps_source.as_deref() == Some("manual") { " (manual)" } else { "" },
+                    Style::default().fg(theme.muted),
+                ),
             ]));
         }
 
@@ -531,7 +896,7 @@ fn render_image_metadata(
                 format!("{} ({})", meta.face_count, meta.people_names.join(", "))
             };
             info_lines.push(Line::from(vec![
-                Span::styled("Faces: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Faces: ", Style::default().fg(theme.muted)),
                 Span::raw(face_text),
             ]));
         }
@@ -539,17 +904,34 @@ fn render_image_metadata(
         // Scanned timestamp
         if let Some(ref scanned) = meta.scanned_at {
             info_lines.push(Line::from(vec![
-                Span::styled("Scanned: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Scanned: ", Style::default().fg(theme.muted)),
                 Span::raw(scanned),
             ]));
         }
 
+        // Tags (LLM-generated, stored as a JSON array)
+        let tags: Vec<String> = meta
+            .tags
+            .as_deref()
+            .and_then(|t| serde_json::from_str(t).ok())
+            .unwrap_or_default();
+        if !tags.is_empty() {
+            let mut spans = vec![Span::styled("Tags: ", Style::default().fg(theme.muted))];
+            for (i, tag) in tags.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::styled(tag.clone(), Style::default().fg(theme.tag_color(tag))));
+            }
+            info_lines.push(Line::from(spans));
+        }
+
         // AI Description
         if let Some(ref description) = meta.description {
             info_lines.push(Line::from(""));
             info_lines.push(Line::from(Span::styled(
                 "AI Description:",
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.header_fg).add_modifier(Modifier::BOLD),
             )));
             for line in description.lines() {
                 info_lines.push(Line::from(line.to_string()));
@@ -559,7 +941,7 @@ fn render_image_metadata(
         // Not in database
         info_lines.push(Line::from(Span::styled(
             "Not scanned yet",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::ITALIC),
+            Style::default().fg(theme.highlight).add_modifier(Modifier::ITALIC),
         )));
     }
 
@@ -570,7 +952,7 @@ fn render_image_metadata(
     } else {
         "[i] describe with AI | [s] scan"
     };
-    info_lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+    info_lines.push(Line::from(Span::styled(hint, Style::default().fg(theme.muted))));
 
     let text = Text::from(info_lines);
     let paragraph = Paragraph::new(text)
@@ -579,20 +961,67 @@ fn render_image_metadata(
     frame.render_widget(paragraph, area);
 }
 
-fn render_file_preview(frame: &mut Frame, entry: &crate::app::DirEntry, block: Block, area: Rect) {
+fn render_file_preview(frame: &mut Frame, app: &mut App, entry: &DirEntry, block: Block, area: Rect) {
+    let theme = app.theme.clone();
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(inner_area);
+
     let info_lines = vec![
         Line::from(vec![
-            Span::styled("File: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("File: ", Style::default().fg(theme.muted)),
             Span::raw(&entry.name),
         ]),
         Line::from(vec![
-            Span::styled("Size: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Size: ", Style::default().fg(theme.muted)),
             Span::raw(format_size(entry.size)),
         ]),
     ];
+    frame.render_widget(Paragraph::new(info_lines), chunks[0]);
 
-    let text = Text::from(info_lines);
-    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    let scroll = app.image_preview.scroll_offset;
+    render_text_file(frame, &mut app.image_preview, &entry.path, scroll, chunks[1], &theme);
+}
+
+/// Show the start of a non-image file: syntax highlighted text, or a hex
+/// dump for binary files. The content is read once per file and cached.
+pub fn render_text_file(
+    frame: &mut Frame,
+    preview: &mut ImagePreviewState,
+    path: &Path,
+    scroll: u16,
+    area: Rect,
+    theme: &Theme,
+) {
+    if preview.text_preview.as_ref().map(|(p, _)| p.as_path()) != Some(path) {
+        let lines = text_preview::load(path).map_err(|e| e.to_string());
+        preview.text_preview = Some((path.to_path_buf(), lines));
+    }
+
+    let lines = match preview.text_preview.as_ref().map(|(_, lines)| lines) {
+        Some(Ok(lines)) => lines,
+        Some(Err(e)) => {
+            let error = Paragraph::new(format!("Cannot read file: {}", e))
+                .style(Style::default().fg(theme.error))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(error, area);
+            return;
+        }
+        None => return,
+    };
+
+    // Only clone the lines that fit on screen
+    let visible: Vec<Line> = lines
+        .iter()
+        .skip(scroll as usize)
+        .take(area.height as usize)
+        .cloned()
+        .collect();
+    let paragraph = Paragraph::new(visible).style(Style::default().fg(theme.fg));
     frame.render_widget(paragraph, area);
 }
 
@@ -0,0 +1,230 @@
+//! Prompt manager: every directory's custom LLM prompt in one place.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::ui::theme::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptManagerMode {
+    /// Moving through the list
+    Browse,
+    /// Editing the selected directory's prompt
+    Edit,
+    /// Waiting for y/n before deleting the selected prompt
+    ConfirmDelete,
+}
+
+/// A directory and its custom prompt, if it has one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptEntry {
+    pub directory: String,
+    /// `None` when the directory uses the global default prompt
+    pub prompt: Option<String>,
+}
+
+/// State for the prompt manager dialog.
+pub struct PromptManager {
+    /// Directories sorted by path, always including the current directory
+    pub entries: Vec<PromptEntry>,
+    pub selected_index: usize,
+    pub mode: PromptManagerMode,
+    /// Prompt text while editing
+    pub input: String,
+    /// Directory the browser was in when the dialog opened
+    pub current_dir: String,
+}
+
+impl PromptManager {
+    pub fn new(prompts: Vec<(String, String)>, current_dir: String) -> Self {
+        let mut manager = Self {
+            entries: Vec::new(),
+            selected_index: 0,
+            mode: PromptManagerMode::Browse,
+            input: String::new(),
+            current_dir,
+        };
+        manager.set_prompts(prompts);
+        let current_dir = manager.current_dir.clone();
+        manager.select_directory(&current_dir);
+        manager
+    }
+
+    /// Replace the list after a change, keeping the cursor in range.
+    pub fn set_prompts(&mut self, prompts: Vec<(String, String)>) {
+        let mut entries: Vec<PromptEntry> = prompts
+            .into_iter()
+            .map(|(directory, prompt)| PromptEntry { directory, prompt: Some(prompt) })
+            .collect();
+        if !entries.iter().any(|e| e.directory == self.current_dir) {
+            entries.push(PromptEntry { directory: self.current_dir.clone(), prompt: None });
+        }
+        entries.sort_by(|a, b| a.directory.cmp(&b.directory));
+
+        self.entries = entries;
+        self.selected_index = self.selected_index.min(self.entries.len().saturating_sub(1));
+        self.mode = PromptManagerMode::Browse;
+        self.input.clear();
+    }
+
+    pub fn select_directory(&mut self, directory: &str) {
+        if let Some(index) = self.entries.iter().position(|e| e.directory == directory) {
+            self.selected_index = index;
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected_index + 1 < self.entries.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    pub fn selected(&self) -> Option<&PromptEntry> {
+        self.entries.get(self.selected_index)
+    }
+
+    pub fn start_edit(&mut self) {
+        if let Some(entry) = self.selected() {
+            self.input = entry.prompt.clone().unwrap_or_default();
+            self.mode = PromptManagerMode::Edit;
+        }
+    }
+
+    /// Edit the current directory's prompt, creating it if needed.
+    pub fn start_new(&mut self) {
+        let current_dir = self.current_dir.clone();
+        self.select_directory(&current_dir);
+        self.start_edit();
+    }
+
+    pub fn start_delete(&mut self) {
+        if self.selected().is_some_and(|e| e.prompt.is_some()) {
+            self.mode = PromptManagerMode::ConfirmDelete;
+        }
+    }
+
+    /// Leave editing or delete confirmation without changing anything.
+    pub fn cancel(&mut self) {
+        self.mode = PromptManagerMode::Browse;
+        self.input.clear();
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &PromptManager, area: Rect, theme: &Theme) {
+    let dialog_width = 90.min(area.width.saturating_sub(4));
+    let edit_height = if dialog.mode == PromptManagerMode::Edit { 6 } else { 0 };
+    let dialog_height = (dialog.entries.len() as u16 + 6 + edit_height).clamp(10, area.height.saturating_sub(4).max(10));
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height.min(area.height));
+
+    frame.render_widget(Clear, dialog_area);
+
+    let custom = dialog.entries.iter().filter(|e| e.prompt.is_some()).count();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Directory Prompts ({}) ", custom));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(edit_height), Constraint::Length(2)])
+        .split(inner);
+
+    let rows = dialog.entries.iter().map(|entry| {
+        let directory = if entry.directory == dialog.current_dir {
+            format!(" {} (current)", entry.directory)
+        } else {
+            format!(" {}", entry.directory)
+        };
+        let prompt = match entry.prompt {
+            Some(ref prompt) => Cell::from(prompt.lines().next().unwrap_or_default().to_string()),
+            None => Cell::from("Uses global default")
+                .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)),
+        };
+        Row::new(vec![Cell::from(directory), prompt])
+    });
+
+    let table = Table::new(rows, [Constraint::Percentage(45), Constraint::Percentage(55)])
+        .header(
+            Row::new(vec![Cell::from(" Directory"), Cell::from("Prompt")])
+                .style(Style::default().fg(theme.header_fg).add_modifier(Modifier::BOLD)),
+        )
+        .column_spacing(1)
+        .row_highlight_style(
+            Style::default()
+                .bg(theme.selected_bg)
+                .fg(theme.selected_fg)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut state = TableState::default();
+    state.select(Some(dialog.selected_index));
+    frame.render_stateful_widget(table, chunks[0], &mut state);
+
+    if dialog.mode == PromptManagerMode::Edit {
+        let editor = Paragraph::new(format!("{}_", dialog.input))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.success))
+                    .title(" Prompt "),
+            );
+        frame.render_widget(editor, chunks[1]);
+    }
+
+    let help = match (dialog.mode, dialog.selected()) {
+        (PromptManagerMode::ConfirmDelete, Some(entry)) => Paragraph::new(format!(
+            " Delete the prompt for {}? y/n",
+            entry.directory
+        ))
+        .style(Style::default().fg(theme.error)),
+        (PromptManagerMode::Edit, _) => Paragraph::new(" Enter=save (empty uses global default)  Esc=cancel")
+            .style(Style::default().fg(theme.muted)),
+        _ => Paragraph::new(" j/k=move  e=edit  n=new for current dir  d=delete  Esc=close")
+            .style(Style::default().fg(theme.muted)),
+    };
+    frame.render_widget(help.block(Block::default().borders(Borders::TOP)), chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_directory_is_listed_with_global_default() {
+        let prompts = vec![
+            ("/photos/b".to_string(), "Birthday party".to_string()),
+            ("/photos/z".to_string(), "Zoo trip".to_string()),
+        ];
+        let mut manager = PromptManager::new(prompts, "/photos/m".to_string());
+
+        let dirs: Vec<&str> = manager.entries.iter().map(|e| e.directory.as_str()).collect();
+        assert_eq!(dirs, vec!["/photos/b", "/photos/m", "/photos/z"]);
+        assert_eq!(manager.selected().unwrap().prompt, None);
+
+        // Only directories with a prompt can be deleted
+        manager.start_delete();
+        assert_eq!(manager.mode, PromptManagerMode::Browse);
+        manager.move_up();
+        manager.start_delete();
+        assert_eq!(manager.mode, PromptManagerMode::ConfirmDelete);
+
+        // Once the current directory has a prompt it is not listed twice
+        manager.set_prompts(vec![("/photos/m".to_string(), "Museum".to_string())]);
+        assert_eq!(manager.entries.len(), 1);
+        manager.start_new();
+        assert_eq!(manager.input, "Museum");
+    }
+}
@@ -0,0 +1,160 @@
+//! Choosing a graphics protocol for `preview.protocol = "auto"`.
+//!
+//! Terminals that announce themselves through the environment are
+//! trusted first. Inside tmux, the outer terminal's name comes from tmux
+//! itself, and images are only attempted when tmux is new enough to pass
+//! graphics through. Otherwise the terminal is asked directly, with the
+//! Kitty graphics query and device attributes, and anything that answers
+//! neither gets half blocks.
+
+use ratatui_image::picker::{Picker, ProtocolType};
+use std::process::{Command, Stdio};
+
+use crate::config::ImageProtocol;
+
+/// The environment variables detection looks at
+#[derive(Debug, Clone, Default)]
+struct TermEnv {
+    term: String,
+    term_program: String,
+    lc_terminal: String,
+    kitty_window_id: bool,
+}
+
+impl TermEnv {
+    fn from_env() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        Self {
+            term: var("TERM"),
+            term_program: var("TERM_PROGRAM"),
+            lc_terminal: var("LC_TERMINAL"),
+            kitty_window_id: !var("KITTY_WINDOW_ID").is_empty(),
+        }
+    }
+}
+
+/// Query the terminal and pick the best protocol it supports
+pub fn detect_best_protocol() -> ImageProtocol {
+    best_protocol(Picker::from_query_stdio().ok())
+}
+
+/// `detect_best_protocol` for a terminal that has already been queried;
+/// `queried` is None when it did not answer.
+pub fn best_protocol(queried: Option<Picker>) -> ImageProtocol {
+    let mut env = TermEnv::from_env();
+    if !std::env::var("TMUX").unwrap_or_default().is_empty() {
+        if !tmux_passthrough() {
+            return ImageProtocol::Halfblocks;
+        }
+        // tmux keeps the environment of the terminal its server started
+        // in, so KITTY_WINDOW_ID may belong to another terminal
+        if let Some(outer) = tmux_client_term() {
+            env.term = outer;
+            env.kitty_window_id = false;
+        }
+    }
+
+    protocol_from_env(&env)
+        .or_else(|| queried.map(|picker| from_protocol_type(picker.protocol_type())))
+        .unwrap_or(ImageProtocol::Halfblocks)
+}
+
+/// Protocols of terminals recognisable from their environment
+fn protocol_from_env(env: &TermEnv) -> Option<ImageProtocol> {
+    let term = env.term.as_str();
+    let program = env.term_program.as_str();
+    if env.kitty_window_id || term.contains("kitty") || term == "xterm-ghostty" || program == "ghostty" {
+        Some(ImageProtocol::Kitty)
+    } else if ["iTerm", "WezTerm", "mintty", "vscode"].iter().any(|p| program.contains(p))
+        || env.lc_terminal.contains("iTerm")
+    {
+        Some(ImageProtocol::ITerm2)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") {
+        Some(ImageProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+fn from_protocol_type(protocol: ProtocolType) -> ImageProtocol {
+    match protocol {
+        ProtocolType::Kitty => ImageProtocol::Kitty,
+        ProtocolType::Sixel => ImageProtocol::Sixel,
+        ProtocolType::Iterm2 => ImageProtocol::ITerm2,
+        ProtocolType::Halfblocks => ImageProtocol::Halfblocks,
+    }
+}
+
+/// Whether this tmux passes graphics through to the outer terminal.
+/// `allow-passthrough` only exists from tmux 3.3, so failing to read it
+/// means images cannot get out; it is also off unless the user turned it on.
+fn tmux_passthrough() -> bool {
+    tmux_output(&["show-options", "-gv", "allow-passthrough"]).is_some_and(|value| passthrough_enabled(&value))
+}
+
+/// Whether an `allow-passthrough` value lets escapes through
+fn passthrough_enabled(value: &str) -> bool {
+    matches!(value, "on" | "all")
+}
+
+/// `$TERM` of the terminal the tmux client is running in
+fn tmux_client_term() -> Option<String> {
+    tmux_output(&["display-message", "-p", "#{client_termname}"]).filter(|term| !term.is_empty())
+}
+
+fn tmux_output(args: &[&str]) -> Option<String> {
+    let output = Command::new("tmux")
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The value to write as `preview.protocol` for `protocol`
+pub fn config_name(protocol: ImageProtocol) -> &'static str {
+    match protocol {
+        ImageProtocol::Auto => "auto",
+        ImageProtocol::Sixel => "sixel",
+        ImageProtocol::Kitty => "kitty",
+        ImageProtocol::ITerm2 => "iterm2",
+        ImageProtocol::Halfblocks => "halfblocks",
+        ImageProtocol::None => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(term: &str, term_program: &str) -> TermEnv {
+        TermEnv { term: term.to_string(), term_program: term_program.to_string(), ..TermEnv::default() }
+    }
+
+    #[test]
+    fn test_protocol_from_env() {
+        assert_eq!(protocol_from_env(&env("xterm-kitty", "")), Some(ImageProtocol::Kitty));
+        assert_eq!(protocol_from_env(&env("xterm-ghostty", "ghostty")), Some(ImageProtocol::Kitty));
+        assert_eq!(protocol_from_env(&env("xterm-256color", "iTerm.app")), Some(ImageProtocol::ITerm2));
+        assert_eq!(protocol_from_env(&env("xterm-256color", "WezTerm")), Some(ImageProtocol::ITerm2));
+        assert_eq!(protocol_from_env(&env("foot", "")), Some(ImageProtocol::Sixel));
+        assert_eq!(protocol_from_env(&env("xterm-256color", "")), None);
+        assert_eq!(
+            protocol_from_env(&TermEnv { kitty_window_id: true, ..env("tmux-256color", "tmux") }),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(config_name(ImageProtocol::ITerm2), "iterm2");
+    }
+
+    #[test]
+    fn test_passthrough_enabled() {
+        assert!(passthrough_enabled("on"));
+        assert!(passthrough_enabled("all"));
+        assert!(!passthrough_enabled("off"));
+        assert!(!passthrough_enabled(""));
+    }
+}
@@ -0,0 +1,83 @@
+//! Quick filter for the file browser: narrows the current directory's
+//! entries to fuzzy matches of a typed query, best match first.
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::app::DirEntry;
+
+pub struct QuickFilter {
+    pub query: String,
+    /// The directory's entries before filtering, restored on `Esc`
+    pub all_entries: Vec<DirEntry>,
+    /// Character positions of the matched letters in each shown name
+    pub matches: HashMap<PathBuf, Vec<usize>>,
+}
+
+impl QuickFilter {
+    pub fn new(all_entries: Vec<DirEntry>) -> Self {
+        Self {
+            query: String::new(),
+            all_entries,
+            matches: HashMap::new(),
+        }
+    }
+
+    /// Entries matching the query, best first; all of them in directory
+    /// order when the query is empty.
+    pub fn apply(&mut self) -> Vec<DirEntry> {
+        self.matches.clear();
+        if self.query.is_empty() {
+            return self.all_entries.clone();
+        }
+
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<(i64, &DirEntry)> = Vec::new();
+        for entry in &self.all_entries {
+            if let Some((score, indices)) = matcher.fuzzy_indices(&entry.name, &self.query) {
+                self.matches.insert(entry.path.clone(), indices);
+                scored.push((score, entry));
+            }
+        }
+        // Stable, so equal scores keep the directory's sort order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry.clone()).collect()
+    }
+
+    pub fn matched_indices(&self, entry: &DirEntry) -> &[usize] {
+        self.matches.get(&entry.path).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str) -> DirEntry {
+        DirEntry {
+            name: name.to_string(),
+            path: PathBuf::from("/photos").join(name),
+            is_dir: false,
+            size: 0,
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut filter = QuickFilter::new(vec![entry("beach_2023.jpg"), entry("IMG_0001.jpg"), entry("bike.png")]);
+        assert_eq!(filter.apply().len(), 3);
+
+        filter.query = "bch".to_string();
+        let names: Vec<String> = filter.apply().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, ["beach_2023.jpg"]);
+        assert_eq!(filter.matched_indices(&entry("beach_2023.jpg")), [0, 3, 4]);
+
+        filter.query = "png".to_string();
+        let names: Vec<String> = filter.apply().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, ["bike.png"]);
+        assert!(filter.matched_indices(&entry("beach_2023.jpg")).is_empty());
+    }
+}
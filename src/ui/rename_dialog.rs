@@ -2,7 +2,65 @@ use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::ui::theme::Theme;
+
+/// What to do when a new name is already taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenameConflictStrategy {
+    /// Leave the file under its old name
+    #[default]
+    Skip,
+    /// Replace the file that has the name
+    Overwrite,
+    /// Append the next free number, e.g. `name_003.jpg`
+    AutoNumber,
+}
+
+impl RenameConflictStrategy {
+    pub const ALL: [RenameConflictStrategy; 3] = [
+        RenameConflictStrategy::Skip,
+        RenameConflictStrategy::Overwrite,
+        RenameConflictStrategy::AutoNumber,
+    ];
+
+    pub fn next(self) -> Self {
+        match self {
+            RenameConflictStrategy::Skip => RenameConflictStrategy::Overwrite,
+            RenameConflictStrategy::Overwrite => RenameConflictStrategy::AutoNumber,
+            RenameConflictStrategy::AutoNumber => RenameConflictStrategy::Skip,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RenameConflictStrategy::Skip => "Skip",
+            RenameConflictStrategy::Overwrite => "Overwrite",
+            RenameConflictStrategy::AutoNumber => "Auto-number",
+        }
+    }
+}
+
+/// Where one file goes once conflicts are resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameTarget {
+    /// Rename to `name`; `overwrites` when that replaces an existing file
+    Rename { name: String, overwrites: bool },
+    /// Left alone because its new name is taken
+    Skip,
+}
+
+/// Files renamed, skipped over a conflict, and failed
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RenameSummary {
+    pub renamed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// `(old, new)` path of each file actually renamed, in rename order
+    pub moves: Vec<(PathBuf, PathBuf)>,
+}
 
 /// State for the batch rename dialog
 pub struct RenameDialog {
@@ -18,6 +76,10 @@ pub struct RenameDialog {
     pub error: Option<String>,
     /// Counter start value
     pub counter_start: u32,
+    /// How taken names are handled, cycled with Tab
+    pub conflict_strategy: RenameConflictStrategy,
+    /// Waiting for y/n before a single file replaces another
+    pub confirming_overwrite: bool,
 }
 
 impl RenameDialog {
@@ -29,6 +91,8 @@ impl RenameDialog {
             preview: Vec::new(),
             error: None,
             counter_start: 1,
+            conflict_strategy: RenameConflictStrategy::default(),
+            confirming_overwrite: false,
         };
         dialog.update_preview();
         dialog
@@ -55,6 +119,11 @@ impl RenameDialog {
         }
     }
 
+    pub fn cycle_conflict_strategy(&mut self) {
+        self.conflict_strategy = self.conflict_strategy.next();
+        self.update_preview();
+    }
+
     pub fn move_cursor_left(&mut self) {
         if self.cursor > 0 {
             self.cursor -= 1;
@@ -166,77 +235,181 @@ impl RenameDialog {
     }
 
     fn check_conflicts(&mut self) {
-        let new_names: Vec<&String> = self.preview.iter().map(|(_, new)| new).collect();
-
-        // Check for duplicates in new names
-        for (i, name) in new_names.iter().enumerate() {
-            for (j, other) in new_names.iter().enumerate() {
-                if i != j && name == other {
-                    self.error = Some(format!("Conflict: multiple files would have name '{}'", name));
-                    return;
-                }
+        // Skip and Auto-number resolve files sharing a new name; overwriting
+        // would lose all but the last of them
+        if self.conflict_strategy != RenameConflictStrategy::Overwrite {
+            return;
+        }
+        let mut seen = HashSet::new();
+        for (file_path, (_, new_name)) in self.files.iter().zip(self.preview.iter()) {
+            if !seen.insert(file_path.with_file_name(new_name)) {
+                self.error = Some(format!("Conflict: multiple files would have name '{}'", new_name));
+                return;
             }
         }
+    }
+
+    /// Resolve every file's new name against the files on disk and the names
+    /// given to earlier files in the batch, in the order they are renamed.
+    /// With Overwrite every file in the batch is renamed, so the current
+    /// names of the ones changing are free to reuse rather than replaced.
+    pub fn plan(&self) -> Vec<RenameTarget> {
+        let mut claimed: HashSet<PathBuf> = HashSet::new();
+        let mut vacated: HashSet<PathBuf> = HashSet::new();
+        if self.conflict_strategy == RenameConflictStrategy::Overwrite {
+            vacated.extend(
+                self.files
+                    .iter()
+                    .zip(self.preview.iter())
+                    .filter(|(file_path, (_, new_name))| file_path.with_file_name(new_name) != **file_path)
+                    .map(|(file_path, _)| file_path.clone()),
+            );
+        }
+        let mut targets = Vec::with_capacity(self.files.len());
 
-        // Check for conflicts with existing files (that aren't being renamed)
-        for (old_name, new_name) in &self.preview {
-            if old_name != new_name {
-                for file_path in &self.files {
-                    if let Some(parent) = file_path.parent() {
-                        let new_path = parent.join(new_name);
-                        if new_path.exists() {
-                            // Check if it's one of the files we're renaming
-                            let is_being_renamed = self.preview.iter().any(|(old, _)| {
-                                file_path.file_name().map(|n| n.to_string_lossy().to_string())
-                                    == Some(old.clone())
-                            });
-                            if !is_being_renamed {
-                                self.error = Some(format!("File '{}' already exists", new_name));
-                                return;
-                            }
-                        }
-                    }
+        for (file_path, (_, new_name)) in self.files.iter().zip(self.preview.iter()) {
+            let new_path = file_path.with_file_name(new_name);
+            if &new_path == file_path {
+                claimed.insert(new_path);
+                targets.push(RenameTarget::Rename { name: new_name.clone(), overwrites: false });
+                continue;
+            }
+
+            let taken = claimed.contains(&new_path) || (new_path.exists() && !vacated.contains(&new_path));
+            let target = match (taken, self.conflict_strategy) {
+                (false, _) => RenameTarget::Rename { name: new_name.clone(), overwrites: false },
+                (true, RenameConflictStrategy::Skip) => RenameTarget::Skip,
+                (true, RenameConflictStrategy::Overwrite) => {
+                    RenameTarget::Rename { name: new_name.clone(), overwrites: true }
+                }
+                (true, RenameConflictStrategy::AutoNumber) => {
+                    let parent = file_path.parent().unwrap_or(Path::new(""));
+                    let claimed_here: HashSet<String> = claimed
+                        .iter()
+                        .filter(|p| p.parent() == Some(parent))
+                        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                        .collect();
+                    RenameTarget::Rename { name: next_numbered_name(parent, new_name, &claimed_here), overwrites: false }
                 }
+            };
+            if let RenameTarget::Rename { ref name, .. } = target {
+                claimed.insert(file_path.with_file_name(name));
+                vacated.insert(file_path.clone());
             }
+            targets.push(target);
         }
+        targets
     }
 
-    /// Execute the rename operation
-    pub fn execute(&self) -> Result<(usize, usize), String> {
-        if self.error.is_some() {
-            return Err(self.error.clone().unwrap());
-        }
+    /// Whether Enter should first ask before replacing a file: only when
+    /// renaming a single file, as batches overwrite without asking
+    pub fn needs_overwrite_confirmation(&self) -> bool {
+        self.files.len() == 1
+            && self.conflict_strategy == RenameConflictStrategy::Overwrite
+            && self.plan().iter().any(|t| matches!(t, RenameTarget::Rename { overwrites: true, .. }))
+    }
 
-        let mut success = 0;
-        let mut failed = 0;
+    /// Execute the rename operation. Files are first moved to temporary
+    /// names and then to their new ones, so a file taking the name of
+    /// another in the batch never replaces it before it has moved.
+    pub fn execute(&self) -> Result<RenameSummary, String> {
+        if let Some(ref error) = self.error {
+            return Err(error.clone());
+        }
 
-        for (file_path, (_, new_name)) in self.files.iter().zip(self.preview.iter()) {
-            if let Some(parent) = file_path.parent() {
-                let new_path = parent.join(new_name);
+        let mut summary = RenameSummary::default();
+        let mut staged = Vec::new();
+        // Files that could not be moved aside, and so still hold their name
+        let mut stuck: HashSet<PathBuf> = HashSet::new();
+        for (index, (file_path, target)) in self.files.iter().zip(self.plan()).enumerate() {
+            let RenameTarget::Rename { name, .. } = target else {
+                summary.skipped += 1;
+                continue;
+            };
+            let new_path = file_path.with_file_name(&name);
+            if file_path == &new_path {
+                // No change needed
+                summary.renamed += 1;
+                continue;
+            }
 
-                if file_path == &new_path {
-                    // No change needed
-                    success += 1;
-                    continue;
+            let temp_path = staging_path(file_path, index);
+            match std::fs::rename(file_path, &temp_path) {
+                Ok(_) => staged.push((file_path, temp_path, new_path)),
+                Err(_) => {
+                    summary.failed += 1;
+                    stuck.insert(file_path.clone());
                 }
+            }
+        }
 
-                match std::fs::rename(file_path, &new_path) {
-                    Ok(_) => success += 1,
-                    Err(_) => failed += 1,
-                }
-            } else {
-                failed += 1;
+        for (file_path, temp_path, new_path) in staged {
+            let renamed = !stuck.contains(&new_path) && std::fs::rename(&temp_path, &new_path).is_ok();
+            if renamed {
+                summary.renamed += 1;
+                summary.moves.push((file_path.clone(), new_path));
+                continue;
+            }
+            summary.failed += 1;
+            // Put the file back, unless another file has taken its name
+            if file_path.exists() || std::fs::rename(&temp_path, file_path).is_err() {
+                tracing::warn!(path = %temp_path.display(), "Rename failed, file left under its temporary name");
             }
         }
 
-        Ok((success, failed))
+        summary.moves = replayable_order(summary.moves);
+        Ok(summary)
+    }
+}
+
+/// `moves` ordered so that each can be made, one at a time, without
+/// replacing a file a later move takes away: undo and redo replay them
+/// one by one. Moves in a cycle keep their order.
+fn replayable_order(mut moves: Vec<(PathBuf, PathBuf)>) -> Vec<(PathBuf, PathBuf)> {
+    let mut ordered = Vec::with_capacity(moves.len());
+    while !moves.is_empty() {
+        let sources: HashSet<&PathBuf> = moves.iter().map(|(from, _)| from).collect();
+        let ready = moves.iter().position(|(_, to)| !sources.contains(to)).unwrap_or(0);
+        ordered.push(moves.remove(ready));
     }
+    ordered
 }
 
-pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect) {
+/// A hidden name beside `file_path` to park it under while a batch is
+/// renamed
+fn staging_path(file_path: &Path, index: usize) -> PathBuf {
+    let name = file_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    file_path.with_file_name(format!(".clepho-rename-{}-{}-{}", std::process::id(), index, name))
+}
+
+/// `name` with the next free number appended, as `stem_NNN.ext`. Numbers
+/// already used by files in `dir` or by `claimed` names are skipped over.
+pub fn next_numbered_name(dir: &Path, name: &str, claimed: &HashSet<String>) -> String {
+    let path = Path::new(name);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+
+    let mut names: HashSet<String> = claimed.clone();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        names.extend(entries.flatten().map(|e| e.file_name().to_string_lossy().to_string()));
+    }
+
+    let prefix = format!("{}_", stem);
+    let highest = names
+        .iter()
+        .filter_map(|n| n.strip_prefix(&prefix)?.strip_suffix(ext.as_str())?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    (highest + 1..)
+        .map(|n| format!("{}_{:03}{}", stem, n, ext))
+        .find(|candidate| !names.contains(candidate))
+        .expect("some numbered name is free")
+}
+
+pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 80.min(area.width.saturating_sub(4));
-    let dialog_height = 28.min(area.height.saturating_sub(4));
+    let dialog_height = 30.min(area.height.saturating_sub(4));
 
     let x = (area.width - dialog_width) / 2;
     let y = (area.height - dialog_height) / 2;
@@ -254,6 +427,7 @@ pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect) {
             Constraint::Length(3),  // Header
             Constraint::Length(3),  // Pattern input
             Constraint::Length(3),  // Variables help
+            Constraint::Length(2),  // Conflict strategy
             Constraint::Min(10),    // Preview
             Constraint::Length(2),  // Error/status
             Constraint::Length(2),  // Footer
@@ -263,14 +437,14 @@ pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect) {
     // Draw border
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta))
+        .border_style(Style::default().fg(theme.emphasis))
         .title(" Batch Rename ")
         .title_style(Style::default().add_modifier(Modifier::BOLD));
     frame.render_widget(block, dialog_area);
 
     // Header
     let header = Paragraph::new(format!("Renaming {} file(s)", dialog.files.len()))
-        .style(Style::default().fg(Color::Magenta));
+        .style(Style::default().fg(theme.emphasis));
     frame.render_widget(header, chunks[0]);
 
     // Pattern input with cursor
@@ -278,14 +452,14 @@ pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect) {
     let pattern_after = &dialog.pattern[dialog.cursor..];
     let input = Paragraph::new(Line::from(vec![
         Span::raw(&pattern_display[..pattern_display.len() - 1]),
-        Span::styled("|", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::styled("|", Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD)),
         Span::raw(pattern_after),
     ]))
     .block(
         Block::default()
             .borders(Borders::ALL)
             .title(" Pattern ")
-            .border_style(Style::default().fg(Color::Yellow)),
+            .border_style(Style::default().fg(theme.highlight)),
     );
     frame.render_widget(input, chunks[1]);
 
@@ -293,25 +467,48 @@ pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect) {
     let help = Paragraph::new(
         "Variables: {name} {ext} {date} {time} {counter} {c}",
     )
-    .style(Style::default().fg(Color::DarkGray))
+    .style(Style::default().fg(theme.muted))
     .wrap(Wrap { trim: true });
     frame.render_widget(help, chunks[2]);
 
-    // Preview list
+    // Conflict strategy, cycled with Tab
+    let mut strategy_spans = vec![Span::styled("If a name is taken: ", Style::default().fg(theme.muted))];
+    for strategy in RenameConflictStrategy::ALL {
+        let style = if strategy == dialog.conflict_strategy {
+            Style::default().fg(theme.selected_fg).bg(theme.selected_bg).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        strategy_spans.push(Span::styled(format!(" {} ", strategy.label()), style));
+        strategy_spans.push(Span::raw(" "));
+    }
+    strategy_spans.push(Span::styled("(Tab)", Style::default().fg(theme.muted)));
+    frame.render_widget(Paragraph::new(Line::from(strategy_spans)), chunks[3]);
+
+    // Preview list, with conflicts resolved
+    let plan = if dialog.error.is_none() { dialog.plan() } else { Vec::new() };
     let preview_items: Vec<ListItem> = dialog
         .preview
         .iter()
+        .enumerate()
         .take(10) // Limit preview to first 10 files
-        .map(|(old, new)| {
-            let style = if old == new {
-                Style::default().fg(Color::DarkGray)
-            } else {
-                Style::default().fg(Color::Green)
+        .map(|(i, (old, new))| {
+            let (name, note, style) = match plan.get(i) {
+                Some(RenameTarget::Skip) => (new.clone(), " (skipped, name taken)", Style::default().fg(theme.muted)),
+                Some(RenameTarget::Rename { name, overwrites: true }) => {
+                    (name.clone(), " (overwrites)", Style::default().fg(theme.highlight))
+                }
+                Some(RenameTarget::Rename { name, .. }) if name == old => {
+                    (name.clone(), "", Style::default().fg(theme.muted))
+                }
+                Some(RenameTarget::Rename { name, .. }) => (name.clone(), "", Style::default().fg(theme.success)),
+                None => (new.clone(), "", Style::default().fg(theme.muted)),
             };
             ListItem::new(Line::from(vec![
-                Span::styled(old, Style::default().fg(Color::Red)),
+                Span::styled(old, Style::default().fg(theme.error)),
                 Span::raw(" -> "),
-                Span::styled(new, style),
+                Span::styled(name, style),
+                Span::styled(note, style),
             ]))
         })
         .collect();
@@ -326,20 +523,106 @@ pub fn render(frame: &mut Frame, dialog: &RenameDialog, area: Rect) {
         Block::default()
             .borders(Borders::ALL)
             .title(more_text)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.muted)),
     );
-    frame.render_widget(preview_list, chunks[3]);
+    frame.render_widget(preview_list, chunks[4]);
 
     // Error or status
     let status = if let Some(ref error) = dialog.error {
-        Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red))
+        Paragraph::new(error.as_str()).style(Style::default().fg(theme.error))
+    } else if dialog.confirming_overwrite {
+        let name = dialog.preview.first().map(|(_, new)| new.as_str()).unwrap_or_default();
+        Paragraph::new(format!("'{}' already exists. Overwrite it? (y/n)", name))
+            .style(Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD))
     } else {
-        Paragraph::new("Ready to rename").style(Style::default().fg(Color::Green))
+        Paragraph::new("Ready to rename").style(Style::default().fg(theme.success))
     };
-    frame.render_widget(status, chunks[4]);
+    frame.render_widget(status, chunks[5]);
 
     // Footer
-    let footer = Paragraph::new("Enter: confirm | Esc: cancel | Arrows: move cursor")
-        .style(Style::default().fg(Color::DarkGray));
-    frame.render_widget(footer, chunks[5]);
+    let footer = Paragraph::new("Enter: confirm | Esc: cancel | Tab: conflict handling | Arrows: move cursor")
+        .style(Style::default().fg(theme.muted));
+    frame.render_widget(footer, chunks[6]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_strategies() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.jpg", "b.jpg", "taken.jpg", "taken_004.jpg"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        let files = vec![dir.path().join("a.jpg"), dir.path().join("b.jpg")];
+        let mut dialog = RenameDialog::new(files);
+        dialog.pattern = "taken.jpg".to_string();
+        dialog.update_preview();
+        assert_eq!(dialog.plan(), vec![RenameTarget::Skip, RenameTarget::Skip]);
+
+        // Both files can't replace the same one
+        dialog.cycle_conflict_strategy();
+        assert_eq!(dialog.conflict_strategy, RenameConflictStrategy::Overwrite);
+        assert!(dialog.error.is_some());
+
+        // Numbering continues after the highest existing number
+        dialog.cycle_conflict_strategy();
+        let renamed = |name: &str| RenameTarget::Rename { name: name.to_string(), overwrites: false };
+        assert_eq!(dialog.plan(), vec![renamed("taken_005.jpg"), renamed("taken_006.jpg")]);
+        let summary = dialog.execute().unwrap();
+        assert_eq!((summary.renamed, summary.skipped, summary.failed), (2, 0, 0));
+        assert_eq!(summary.moves[1], (dir.path().join("b.jpg"), dir.path().join("taken_006.jpg")));
+        assert_eq!(std::fs::read_to_string(dir.path().join("taken_006.jpg")).unwrap(), "b.jpg");
+        assert_eq!(std::fs::read_to_string(dir.path().join("taken.jpg")).unwrap(), "taken.jpg");
+    }
+
+    #[test]
+    fn test_single_overwrite_asks_first() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.jpg"), "a").unwrap();
+        std::fs::write(dir.path().join("b.jpg"), "b").unwrap();
+        let mut dialog = RenameDialog::new(vec![dir.path().join("a.jpg")]);
+        dialog.pattern = "b.jpg".to_string();
+        dialog.conflict_strategy = RenameConflictStrategy::Overwrite;
+        dialog.update_preview();
+
+        assert!(dialog.needs_overwrite_confirmation());
+        dialog.execute().unwrap();
+        assert_eq!(std::fs::read_to_string(dir.path().join("b.jpg")).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_shifting_numbers_keeps_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = ["photo_001.jpg", "photo_002.jpg"]
+            .iter()
+            .map(|name| {
+                std::fs::write(dir.path().join(name), name).unwrap();
+                dir.path().join(name)
+            })
+            .collect();
+        let mut dialog = RenameDialog::new(files);
+        dialog.pattern = "photo_{counter}.{ext}".to_string();
+        dialog.counter_start = 2;
+        dialog.conflict_strategy = RenameConflictStrategy::Overwrite;
+        dialog.update_preview();
+
+        let renamed = |name: &str| RenameTarget::Rename { name: name.to_string(), overwrites: false };
+        assert_eq!(dialog.plan(), vec![renamed("photo_002.jpg"), renamed("photo_003.jpg")]);
+        assert!(!dialog.needs_overwrite_confirmation());
+
+        let summary = dialog.execute().unwrap();
+        assert_eq!((summary.renamed, summary.failed), (2, 0));
+        assert_eq!(std::fs::read_to_string(dir.path().join("photo_002.jpg")).unwrap(), "photo_001.jpg");
+        assert_eq!(std::fs::read_to_string(dir.path().join("photo_003.jpg")).unwrap(), "photo_002.jpg");
+        assert!(!dir.path().join("photo_001.jpg").exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+        // Undo replays these backwards: 002 back to 001 before 003 back to 002
+        let path = |name: &str| dir.path().join(name);
+        assert_eq!(
+            summary.moves,
+            vec![(path("photo_002.jpg"), path("photo_003.jpg")), (path("photo_001.jpg"), path("photo_002.jpg"))]
+        );
+    }
 }
@@ -0,0 +1,116 @@
+//! Field picker for rescanning photos that lack metadata, such as GPS
+//! locations from before the scanner read them.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::db::missing_fields::MISSING_FIELDS;
+use crate::ui::theme::Theme;
+
+pub struct RescanDialog {
+    /// Whether each of `MISSING_FIELDS` is picked
+    pub checked: Vec<bool>,
+    pub selected_index: usize,
+}
+
+impl RescanDialog {
+    pub fn new() -> Self {
+        Self {
+            checked: vec![false; MISSING_FIELDS.len()],
+            selected_index: 0,
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected_index + 1 < self.checked.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(checked) = self.checked.get_mut(self.selected_index) {
+            *checked = !*checked;
+        }
+    }
+
+    /// Names of the picked fields, for `get_photos_with_missing_fields`
+    pub fn selected_fields(&self) -> Vec<&'static str> {
+        MISSING_FIELDS
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(field, _)| field.name)
+            .collect()
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &RescanDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 50.min(area.width.saturating_sub(4));
+    let dialog_height = (MISSING_FIELDS.len() as u16 + 6).min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Rescan Photos Missing ");
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+
+    let items: Vec<ListItem> = MISSING_FIELDS
+        .iter()
+        .zip(&dialog.checked)
+        .map(|(field, checked)| {
+            let mark = if *checked { "[x]" } else { "[ ]" };
+            ListItem::new(format!(" {} {}", mark, field.label))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(theme.selected_bg)
+            .fg(theme.selected_fg)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(dialog.selected_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let help = Paragraph::new(" j/k=move  Space=toggle  Enter=rescan  Esc=cancel")
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_fields() {
+        let mut dialog = RescanDialog::new();
+        assert!(dialog.selected_fields().is_empty());
+        dialog.toggle();
+        dialog.move_down();
+        dialog.move_down();
+        dialog.toggle();
+        assert_eq!(dialog.selected_fields(), ["gps", "lens"]);
+    }
+}
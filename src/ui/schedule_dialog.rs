@@ -8,6 +8,7 @@ use ratatui::{
 use std::path::PathBuf;
 
 use crate::db::ScheduledTaskType;
+use crate::ui::theme::Theme;
 
 /// Which field is currently being edited.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -177,7 +178,7 @@ impl ScheduleDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &ScheduleDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &ScheduleDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 60.min(area.width.saturating_sub(4));
     let dialog_height = 16.min(area.height.saturating_sub(4));
@@ -208,11 +209,11 @@ pub fn render(frame: &mut Frame, dialog: &ScheduleDialog, area: Rect) {
     };
 
     let header = Paragraph::new(format!(" Schedule {} for: {}", dialog.task_type.display_name(), file_count))
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(theme.highlight))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
+                .border_style(Style::default().fg(theme.highlight))
                 .title(" Schedule Task "),
         );
     frame.render_widget(header, chunks[0]);
@@ -220,7 +221,7 @@ pub fn render(frame: &mut Frame, dialog: &ScheduleDialog, area: Rect) {
     // Content - field list
     let field_style = |f: ScheduleField| {
         if dialog.field == f {
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         }
@@ -284,7 +285,7 @@ pub fn render(frame: &mut Frame, dialog: &ScheduleDialog, area: Rect) {
 
     // Help text
     let help = Paragraph::new(" Tab/j/k=nav  +/-=change  Enter=schedule  n=run now  q=cancel")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .block(Block::default().borders(Borders::TOP));
 
     frame.render_widget(help, chunks[2]);
@@ -1,9 +1,36 @@
+use std::path::PathBuf;
+
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 
-use crate::db::SearchResult;
+use crate::db::{SavedSearch, SearchFilter, SearchResult, SearchScope};
+use crate::scanner::color::BASIC_COLORS;
+use crate::ui::theme::Theme;
+
+/// Results fetched by a search, and added by each "Load more"
+pub const SEARCH_PAGE_SIZE: usize = 20;
+
+/// Photos fetched when listing by face count alone
+pub const FACE_LISTING_LIMIT: usize = 200;
+
+/// Results skipped by PageUp/PageDown
+const PAGE_JUMP: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchDialogMode {
+    /// Typing a query and browsing results
+    Query,
+    /// Typing a name to save the current query under
+    NamingSave,
+    /// Choosing a saved search to run
+    PickingSaved,
+    /// Typing face count limits
+    EditingFaces,
+    /// Choosing a color to search by
+    PickingColor,
+}
 
 /// State for the semantic search dialog
 pub struct SearchDialog {
@@ -13,12 +40,42 @@ pub struct SearchDialog {
     pub cursor: usize,
     /// Search results
     pub results: Vec<SearchResult>,
-    /// Selected result index
+    /// Selected result index; `results.len()` is the "Load more" entry
     pub selected_index: usize,
+    /// First result shown in the list
+    pub scroll_offset: usize,
+    /// Results that fit in the list, updated on render
+    visible_rows: usize,
+    /// Limit the current results were fetched with
+    pub result_limit: usize,
+    /// The last fetch filled its limit, so more results may exist
+    pub has_more: bool,
     /// Status message
     pub status: Option<String>,
     /// Is currently searching
     pub searching: bool,
+    /// Scopes Tab cycles through, with their labels
+    pub scopes: Vec<(SearchScope, String)>,
+    /// Index of the active scope
+    pub scope_index: usize,
+    pub mode: SearchDialogMode,
+    /// Name being typed when saving
+    pub save_name: String,
+    /// Saved searches shown in the picker
+    pub saved: Vec<SavedSearch>,
+    pub saved_index: usize,
+    /// Only photos with at least this many faces
+    pub min_faces: Option<usize>,
+    /// Only photos with at most this many faces
+    pub max_faces: Option<usize>,
+    /// Face count limits being typed, e.g. "5+"
+    pub faces_input: String,
+    /// Photo whose embedding is the query instead of the typed text
+    pub image_query: Option<PathBuf>,
+    /// Index into `BASIC_COLORS` of the color being searched by
+    pub color_query: Option<usize>,
+    /// Color under the picker's cursor
+    pub color_index: usize,
 }
 
 impl SearchDialog {
@@ -28,20 +85,236 @@ impl SearchDialog {
             cursor: 0,
             results: Vec::new(),
             selected_index: 0,
+            scroll_offset: 0,
+            visible_rows: PAGE_JUMP,
+            result_limit: SEARCH_PAGE_SIZE,
+            has_more: false,
             status: None,
             searching: false,
+            scopes: vec![(SearchScope::All, "All photos".to_string())],
+            scope_index: 0,
+            mode: SearchDialogMode::Query,
+            save_name: String::new(),
+            saved: Vec::new(),
+            saved_index: 0,
+            min_faces: None,
+            max_faces: None,
+            faces_input: String::new(),
+            image_query: None,
+            color_query: None,
+            color_index: 0,
+        }
+    }
+
+    /// Search for photos similar to this one instead of a text query.
+    pub fn set_image_query(&mut self, path: PathBuf) {
+        self.query.clear();
+        self.cursor = 0;
+        self.color_query = None;
+        self.image_query = Some(path);
+    }
+
+    pub fn start_color_pick(&mut self) {
+        self.color_index = self.color_query.unwrap_or(self.color_index);
+        self.mode = SearchDialogMode::PickingColor;
+    }
+
+    pub fn move_color(&mut self, forward: bool) {
+        let len = BASIC_COLORS.len();
+        self.color_index = if forward { (self.color_index + 1) % len } else { (self.color_index + len - 1) % len };
+    }
+
+    /// Search by the color under the picker's cursor instead of a query.
+    pub fn pick_color(&mut self) {
+        self.query.clear();
+        self.cursor = 0;
+        self.image_query = None;
+        self.color_query = Some(self.color_index);
+        self.mode = SearchDialogMode::Query;
+    }
+
+    /// Name and RGB value of the color being searched by.
+    pub fn query_color(&self) -> Option<(&'static str, [u8; 3])> {
+        self.color_query.map(|i| BASIC_COLORS[i])
+    }
+
+    /// Filename of the query photo.
+    pub fn image_query_name(&self) -> Option<String> {
+        self.image_query
+            .as_ref()
+            .map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+    }
+
+    /// Whether Enter has something to search for.
+    pub fn has_query(&self) -> bool {
+        !self.query.is_empty() || self.image_query.is_some() || self.color_query.is_some() || self.has_face_limits()
+    }
+
+    /// Offer these scopes (after "All photos"), starting on `current`.
+    pub fn with_scopes(mut self, scopes: Vec<(SearchScope, String)>, current: &SearchScope) -> Self {
+        self.scopes.extend(scopes);
+        self.scope_index = self.scopes.iter().position(|(s, _)| s == current).unwrap_or(0);
+        self
+    }
+
+    pub fn scope(&self) -> SearchScope {
+        self.scopes[self.scope_index].0.clone()
+    }
+
+    pub fn scope_label(&self) -> &str {
+        &self.scopes[self.scope_index].1
+    }
+
+    /// Scope and face count limits to search within.
+    pub fn filter(&self) -> SearchFilter {
+        SearchFilter {
+            scope: self.scope(),
+            min_faces: self.min_faces,
+            max_faces: self.max_faces,
+        }
+    }
+
+    pub fn has_face_limits(&self) -> bool {
+        self.min_faces.is_some() || self.max_faces.is_some()
+    }
+
+    /// Face count limits for display: "5+", "3", "2-4" or "≤3".
+    pub fn faces_label(&self) -> Option<String> {
+        match (self.min_faces, self.max_faces) {
+            (None, None) => None,
+            (Some(min), None) => Some(format!("{}+", min)),
+            (None, Some(max)) => Some(format!("≤{}", max)),
+            (Some(min), Some(max)) if min == max => Some(min.to_string()),
+            (Some(min), Some(max)) => Some(format!("{}-{}", min, max)),
+        }
+    }
+
+    pub fn start_faces_edit(&mut self) {
+        self.faces_input = match (self.min_faces, self.max_faces) {
+            (None, Some(max)) => format!("-{}", max),
+            _ => self.faces_label().unwrap_or_default(),
+        };
+        self.mode = SearchDialogMode::EditingFaces;
+    }
+
+    /// Apply the typed face limits; false if they could not be parsed.
+    pub fn apply_faces_input(&mut self) -> bool {
+        match parse_face_range(&self.faces_input) {
+            Some((min, max)) => {
+                self.min_faces = min;
+                self.max_faces = max;
+                self.mode = SearchDialogMode::Query;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Label of a scope, falling back to its stored form if not offered.
+    pub fn label_for(&self, scope: &SearchScope) -> String {
+        self.scopes
+            .iter()
+            .find(|(s, _)| s == scope)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_else(|| scope.to_db_string())
+    }
+
+    pub fn next_scope(&mut self) {
+        self.scope_index = (self.scope_index + 1) % self.scopes.len();
+        self.scope_changed();
+    }
+
+    pub fn prev_scope(&mut self) {
+        self.scope_index = (self.scope_index + self.scopes.len() - 1) % self.scopes.len();
+        self.scope_changed();
+    }
+
+    /// Switch to a scope, adding it when it is a directory not offered yet.
+    /// Returns false if the album, tag or person no longer exists.
+    pub fn set_scope(&mut self, scope: &SearchScope) -> bool {
+        if let Some(i) = self.scopes.iter().position(|(s, _)| s == scope) {
+            self.scope_index = i;
+            return true;
+        }
+        if let SearchScope::Directory(dir) = scope {
+            let name = std::path::Path::new(dir)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| dir.clone());
+            self.scopes.push((scope.clone(), format!("Directory: {}", name)));
+            self.scope_index = self.scopes.len() - 1;
+            return true;
+        }
+        false
+    }
+
+    pub fn start_save(&mut self) {
+        self.save_name = self.query.clone();
+        self.mode = SearchDialogMode::NamingSave;
+    }
+
+    pub fn open_saved(&mut self, saved: Vec<SavedSearch>) {
+        self.saved = saved;
+        self.saved_index = 0;
+        self.mode = SearchDialogMode::PickingSaved;
+    }
+
+    pub fn move_saved_down(&mut self) {
+        if self.saved_index + 1 < self.saved.len() {
+            self.saved_index += 1;
+        }
+    }
+
+    pub fn move_saved_up(&mut self) {
+        self.saved_index = self.saved_index.saturating_sub(1);
+    }
+
+    pub fn selected_saved(&self) -> Option<&SavedSearch> {
+        self.saved.get(self.saved_index)
+    }
+
+    /// Put a saved search's query and scope into the dialog.
+    pub fn load_saved(&mut self, search: &SavedSearch) {
+        self.query = search.query.clone();
+        self.cursor = self.query.len();
+        if !self.set_scope(&search.scope) {
+            self.scope_index = 0;
+        }
+        self.mode = SearchDialogMode::Query;
+    }
+
+    fn scope_changed(&mut self) {
+        if !self.results.is_empty() {
+            self.status = Some("Scope changed, press Enter to search again".to_string());
         }
     }
 
     pub fn handle_char(&mut self, c: char) {
+        // Typing switches back to a text query
+        self.image_query = None;
+        self.color_query = None;
         self.query.insert(self.cursor, c);
         self.cursor += 1;
+        self.query_edited();
     }
 
     pub fn backspace(&mut self) {
+        if self.image_query.take().is_some() || self.color_query.take().is_some() {
+            self.query_edited();
+            return;
+        }
         if self.cursor > 0 {
             self.cursor -= 1;
             self.query.remove(self.cursor);
+            self.query_edited();
+        }
+    }
+
+    /// A changed query cannot load more of the old one
+    fn query_edited(&mut self) {
+        if self.is_load_more_selected() {
+            self.selected_index = 0;
+            self.scroll_offset = 0;
         }
     }
 
@@ -58,27 +331,89 @@ impl SearchDialog {
     }
 
     pub fn clear(&mut self) {
+        self.image_query = None;
+        self.color_query = None;
         self.query.clear();
         self.cursor = 0;
         self.results.clear();
         self.selected_index = 0;
+        self.scroll_offset = 0;
+        self.has_more = false;
+    }
+
+    /// Results plus the "Load more" entry when there may be more
+    pub fn entry_count(&self) -> usize {
+        self.results.len() + usize::from(self.has_more)
+    }
+
+    pub fn is_load_more_selected(&self) -> bool {
+        self.has_more && self.selected_index == self.results.len()
     }
 
     pub fn move_selection_up(&mut self) {
         if self.selected_index > 0 {
             self.selected_index -= 1;
         }
+        self.ensure_visible();
     }
 
     pub fn move_selection_down(&mut self) {
-        if !self.results.is_empty() && self.selected_index < self.results.len() - 1 {
+        if self.selected_index + 1 < self.entry_count() {
             self.selected_index += 1;
         }
+        self.ensure_visible();
+    }
+
+    pub fn page_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(PAGE_JUMP);
+        self.ensure_visible();
+    }
+
+    pub fn page_down(&mut self) {
+        self.selected_index = (self.selected_index + PAGE_JUMP).min(self.entry_count().saturating_sub(1));
+        self.ensure_visible();
+    }
+
+    /// Record how many results the list can show and keep the selection in view
+    pub fn set_visible_rows(&mut self, rows: usize) {
+        self.visible_rows = rows.max(1);
+        self.ensure_visible();
     }
 
-    pub fn set_results(&mut self, results: Vec<SearchResult>) {
+    fn ensure_visible(&mut self) {
+        if self.selected_index < self.scroll_offset {
+            self.scroll_offset = self.selected_index;
+        } else if self.selected_index >= self.scroll_offset + self.visible_rows {
+            self.scroll_offset = self.selected_index + 1 - self.visible_rows;
+        }
+    }
+
+    /// First and last result on screen (1-based), when not all of them fit
+    pub fn visible_range(&self) -> Option<(usize, usize)> {
+        if self.entry_count() <= self.visible_rows || self.results.is_empty() {
+            return None;
+        }
+        let first = self.scroll_offset.min(self.results.len() - 1);
+        let last = (self.scroll_offset + self.visible_rows).min(self.results.len());
+        Some((first + 1, last))
+    }
+
+    /// Show fetched results, appended to the list when loading more
+    pub fn show_results(&mut self, results: Vec<SearchResult>, limit: usize, append: bool) {
+        if append {
+            self.append_results(results, limit);
+        } else {
+            self.set_results(results, limit);
+        }
+    }
+
+    /// Show the results of a new search fetched with `limit`
+    fn set_results(&mut self, results: Vec<SearchResult>, limit: usize) {
+        self.has_more = results.len() >= limit;
+        self.result_limit = limit;
         self.results = results;
         self.selected_index = 0;
+        self.scroll_offset = 0;
         self.searching = false;
         if self.results.is_empty() {
             self.status = Some("No results found".to_string());
@@ -87,21 +422,71 @@ impl SearchDialog {
         }
     }
 
+    /// Append the results of a larger fetch that are not listed yet and
+    /// select the first of them.
+    fn append_results(&mut self, results: Vec<SearchResult>, limit: usize) {
+        let fetched = results.len();
+        let before = self.results.len();
+        for result in results {
+            if !self.results.iter().any(|r| r.photo_id == result.photo_id) {
+                self.results.push(result);
+            }
+        }
+        let added = self.results.len() - before;
+
+        self.has_more = added > 0 && fetched >= limit;
+        self.result_limit = limit;
+        self.searching = false;
+        self.selected_index = before.min(self.entry_count().saturating_sub(1));
+        self.ensure_visible();
+        self.status = Some(if added == 0 {
+            format!("No more results ({} total)", self.results.len())
+        } else {
+            format!("Loaded {} more results ({} total)", added, self.results.len())
+        });
+    }
+
     pub fn selected_result(&self) -> Option<&SearchResult> {
         self.results.get(self.selected_index)
     }
 }
 
+/// Parse face count limits: "5+" (at least 5), "3" (exactly 3), "2-4",
+/// "-3" (at most 3). Empty input clears the limits.
+pub fn parse_face_range(input: &str) -> Option<(Option<usize>, Option<usize>)> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Some((None, None));
+    }
+    if let Some(min) = input.strip_suffix('+') {
+        return Some((Some(min.trim().parse().ok()?), None));
+    }
+    if let Some((min, max)) = input.split_once('-') {
+        let bound = |s: &str| -> Option<Option<usize>> {
+            let s = s.trim();
+            if s.is_empty() { Some(None) } else { s.parse().ok().map(Some) }
+        };
+        let (min, max) = (bound(min)?, bound(max)?);
+        return match (min, max) {
+            (Some(a), Some(b)) if a > b => None,
+            (None, None) => None,
+            range => Some(range),
+        };
+    }
+    let exact = input.parse().ok()?;
+    Some((Some(exact), Some(exact)))
+}
+
 impl Default for SearchDialog {
     fn default() -> Self {
         Self::new()
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &SearchDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &mut SearchDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 80.min(area.width.saturating_sub(4));
-    let dialog_height = 25.min(area.height.saturating_sub(4));
+    let dialog_height = 26.min(area.height.saturating_sub(4));
 
     let x = (area.width - dialog_width) / 2;
     let y = (area.height - dialog_height) / 2;
@@ -117,9 +502,10 @@ pub fn render(frame: &mut Frame, dialog: &SearchDialog, area: Rect) {
         .margin(1)
         .constraints([
             Constraint::Length(3), // Search input
+            Constraint::Length(1), // Scope
             Constraint::Min(10),   // Results list
             Constraint::Length(2), // Status
-            Constraint::Length(2), // Footer
+            Constraint::Length(3), // Footer
         ])
         .split(dialog_area);
 
@@ -132,29 +518,60 @@ pub fn render(frame: &mut Frame, dialog: &SearchDialog, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(title)
         .title_style(Style::default().add_modifier(Modifier::BOLD));
     frame.render_widget(block, dialog_area);
 
-    // Search input
-    let input_text = format!(
-        "{}|{}",
-        &dialog.query[..dialog.cursor],
-        &dialog.query[dialog.cursor..]
-    );
+    // Search input, or the photo or color being searched by
+    let input_text = match (dialog.image_query_name(), dialog.query_color()) {
+        (Some(name), _) => Line::from(format!("🖼 {}", name)),
+        (None, Some((name, [r, g, b]))) => Line::from(vec![
+            Span::styled("██ ", Style::default().fg(Color::Rgb(r, g, b))),
+            Span::raw(name),
+        ]),
+        (None, None) => Line::from(format!(
+            "{}|{}",
+            &dialog.query[..dialog.cursor],
+            &dialog.query[dialog.cursor..]
+        )),
+    };
     let input = Paragraph::new(input_text)
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(theme.highlight))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Query ")
-                .border_style(Style::default().fg(Color::Yellow)),
+                .border_style(Style::default().fg(theme.highlight)),
         );
     frame.render_widget(input, chunks[0]);
 
-    // Results list
-    let items: Vec<ListItem> = dialog
+    // Scope selector and face count limits
+    let mut scope_spans = vec![
+        Span::styled(" Scope: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            format!("◂ {} ▸", dialog.scope_label()),
+            Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  ({}/{})", dialog.scope_index + 1, dialog.scopes.len()),
+            Style::default().fg(theme.muted),
+        ),
+    ];
+    if let Some(faces) = dialog.faces_label() {
+        scope_spans.push(Span::styled("  Faces: ", Style::default().fg(theme.muted)));
+        scope_spans.push(Span::styled(
+            faces,
+            Style::default().fg(theme.highlight).add_modifier(Modifier::BOLD),
+        ));
+    }
+    let scope = Paragraph::new(Line::from(scope_spans));
+    frame.render_widget(scope, chunks[1]);
+
+    // Results list; each result takes two lines inside the border
+    dialog.set_visible_rows(usize::from(chunks[2].height.saturating_sub(2)) / 2);
+
+    let mut items: Vec<ListItem> = dialog
         .results
         .iter()
         .map(|result| {
@@ -171,26 +588,39 @@ pub fn render(frame: &mut Frame, dialog: &SearchDialog, area: Rect) {
                 })
                 .unwrap_or_else(|| "(no description)".to_string());
 
+            // Face count listings without a query have no similarity
+            let score = if dialog.query.trim().is_empty() && dialog.image_query.is_none() && dialog.color_query.is_none() {
+                String::new()
+            } else {
+                format!("[{}%] ", similarity_pct)
+            };
+
             ListItem::new(vec![
                 Line::from(vec![
-                    Span::styled(
-                        format!("[{}%] ", similarity_pct),
-                        Style::default().fg(Color::Green),
-                    ),
-                    Span::styled(&result.filename, Style::default().fg(Color::White)),
+                    Span::styled(score, Style::default().fg(theme.success)),
+                    Span::styled(&result.filename, Style::default().fg(theme.fg)),
                 ]),
                 Line::from(Span::styled(
                     format!("  {}", desc),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 )),
             ])
         })
         .collect();
+    if dialog.has_more {
+        items.push(ListItem::new(vec![
+            Line::from(Span::styled("▼ Load more", Style::default().fg(theme.highlight))),
+            Line::from(Span::styled(
+                format!("  Enter fetches up to {} more results", SEARCH_PAGE_SIZE),
+                Style::default().fg(theme.muted),
+            )),
+        ]));
+    }
 
-    let results_title = if dialog.results.is_empty() {
-        " Results ".to_string()
-    } else {
-        format!(" Results ({}) ", dialog.results.len())
+    let results_title = match dialog.visible_range() {
+        _ if dialog.results.is_empty() => " Results ".to_string(),
+        Some((first, last)) => format!(" Results [{}-{} of {}] ", first, last, dialog.results.len()),
+        None => format!(" Results ({}) ", dialog.results.len()),
     };
 
     let list = List::new(items)
@@ -198,30 +628,189 @@ pub fn render(frame: &mut Frame, dialog: &SearchDialog, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(results_title)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.muted)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::Cyan)
-                .fg(Color::Black)
+                .bg(theme.accent)
+                .fg(theme.bg)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut state = ListState::default().with_offset(dialog.scroll_offset);
+    if dialog.mode == SearchDialogMode::PickingSaved {
+        render_saved(frame, dialog, chunks[2], theme);
+    } else {
+        if !dialog.results.is_empty() {
+            state.select(Some(dialog.selected_index));
+        }
+        frame.render_stateful_widget(list, chunks[2], &mut state);
+    }
+
+    // Status, or the prompt being typed
+    let prompt = match dialog.mode {
+        SearchDialogMode::NamingSave => Some(("Save as: ", &dialog.save_name)),
+        SearchDialogMode::EditingFaces => Some(("Faces (5+, 3, 2-4, -3): ", &dialog.faces_input)),
+        _ => None,
+    };
+    let status = match prompt {
+        Some((label, input)) => Paragraph::new(Line::from(vec![
+            Span::styled(label, Style::default().fg(theme.muted)),
+            Span::styled(format!("{}_", input), Style::default().fg(theme.highlight)),
+        ])),
+        None if dialog.mode == SearchDialogMode::PickingColor => color_picker(dialog.color_index, theme),
+        None => {
+            let status_text = dialog.status.as_deref().unwrap_or("");
+            Paragraph::new(status_text).style(Style::default().fg(theme.muted))
+        }
+    };
+    frame.render_widget(status, chunks[3]);
+
+    // Footer: keys, and the query syntax guide while typing a query
+    let footer_lines = match dialog.mode {
+        SearchDialogMode::Query => vec![
+            Line::from("Enter:search/load more Tab:scope ↑↓ PgUp/PgDn:select Esc:close"),
+            Line::from("^O:open ^S:save ^L:saved ^F:faces ^P:by color"),
+            Line::from("Syntax: AND OR NOT ( )  e.g. beach AND (sunset OR dusk) NOT dog"),
+        ],
+        SearchDialogMode::NamingSave => vec![Line::from("Enter: save | Esc: cancel")],
+        SearchDialogMode::EditingFaces => {
+            vec![Line::from("Enter: apply (empty clears) | Esc: cancel")]
+        }
+        SearchDialogMode::PickingSaved => {
+            vec![Line::from("Enter: run | ↑↓: select | Delete: remove | Esc: back")]
+        }
+        SearchDialogMode::PickingColor => {
+            vec![Line::from("Enter: find photos with this color | ←→: choose | Esc: cancel")]
+        }
+    };
+    let footer = Paragraph::new(footer_lines).style(Style::default().fg(theme.muted));
+    frame.render_widget(footer, chunks[4]);
+}
+
+/// A swatch per basic color, the one under the cursor bracketed, and its
+/// name underneath
+fn color_picker(selected: usize, theme: &Theme) -> Paragraph<'static> {
+    let mut swatches = Vec::new();
+    for (i, (_, [r, g, b])) in BASIC_COLORS.iter().enumerate() {
+        let (open, close) = if i == selected { ("[", "]") } else { (" ", " ") };
+        swatches.push(Span::styled(open, Style::default().fg(theme.highlight)));
+        swatches.push(Span::styled("██", Style::default().fg(Color::Rgb(*r, *g, *b))));
+        swatches.push(Span::styled(close, Style::default().fg(theme.highlight)));
+    }
+    Paragraph::new(vec![
+        Line::from(swatches),
+        Line::from(Span::styled(BASIC_COLORS[selected].0, Style::default().fg(theme.highlight))),
+    ])
+}
+
+fn render_saved(frame: &mut Frame, dialog: &SearchDialog, area: Rect, theme: &Theme) {
+    let items: Vec<ListItem> = dialog
+        .saved
+        .iter()
+        .map(|search| {
+            let scope = match search.scope {
+                SearchScope::All => String::new(),
+                ref scope => format!("  [{}]", dialog.label_for(scope)),
+            };
+            ListItem::new(vec![
+                Line::from(Span::styled(&search.name, Style::default().fg(theme.fg))),
+                Line::from(Span::styled(
+                    format!("  {}{}", search.query, scope),
+                    Style::default().fg(theme.muted),
+                )),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Saved Searches ({}) ", dialog.saved.len()))
+                .border_style(Style::default().fg(theme.muted)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(theme.accent)
+                .fg(theme.bg)
                 .add_modifier(Modifier::BOLD),
         );
 
     let mut state = ListState::default();
-    if !dialog.results.is_empty() {
-        state.select(Some(dialog.selected_index));
-    }
-    frame.render_stateful_widget(list, chunks[1], &mut state);
-
-    // Status
-    let status_text = dialog.status.as_deref().unwrap_or("");
-    let status = Paragraph::new(status_text).style(Style::default().fg(Color::DarkGray));
-    frame.render_widget(status, chunks[2]);
-
-    // Footer
-    let footer = Paragraph::new(
-        "Enter: search | ↑↓: select | Ctrl+O: open | Esc: close",
-    )
-    .style(Style::default().fg(Color::DarkGray));
-    frame.render_widget(footer, chunks[3]);
+    if !dialog.saved.is_empty() {
+        state.select(Some(dialog.saved_index));
+    }
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_face_range() {
+        assert_eq!(parse_face_range("5+"), Some((Some(5), None)));
+        assert_eq!(parse_face_range(" 3 "), Some((Some(3), Some(3))));
+        assert_eq!(parse_face_range("2-4"), Some((Some(2), Some(4))));
+        assert_eq!(parse_face_range("-3"), Some((None, Some(3))));
+        assert_eq!(parse_face_range("2-"), Some((Some(2), None)));
+        assert_eq!(parse_face_range(""), Some((None, None)));
+        assert_eq!(parse_face_range("4-2"), None);
+        assert_eq!(parse_face_range("-"), None);
+        assert_eq!(parse_face_range("many"), None);
+    }
+
+    #[test]
+    fn test_faces_label_round_trips() {
+        let mut dialog = SearchDialog::new();
+        for input in ["5+", "3", "2-4", "-3"] {
+            dialog.faces_input = input.to_string();
+            assert!(dialog.apply_faces_input());
+            dialog.start_faces_edit();
+            assert_eq!(dialog.faces_input, input);
+        }
+    }
+
+    fn results(ids: std::ops::Range<i64>) -> Vec<SearchResult> {
+        ids.map(|id| SearchResult {
+            photo_id: id,
+            path: format!("/photos/{}.jpg", id),
+            filename: format!("{}.jpg", id),
+            similarity: 0.5,
+            description: None,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_pages_through_results() {
+        let mut dialog = SearchDialog::new();
+        dialog.set_visible_rows(10);
+        dialog.show_results(results(0..20), SEARCH_PAGE_SIZE, false);
+        assert!(dialog.has_more);
+        assert_eq!(dialog.visible_range(), Some((1, 10)));
+
+        dialog.page_down();
+        dialog.page_down();
+        assert_eq!(dialog.selected_index, 20);
+        assert!(dialog.is_load_more_selected());
+        assert_eq!(dialog.visible_range(), Some((12, 20)));
+
+        dialog.page_up();
+        assert_eq!(dialog.selected_index, 10);
+        assert_eq!(dialog.scroll_offset, 10);
+    }
+
+    #[test]
+    fn test_load_more_appends_new_results() {
+        let mut dialog = SearchDialog::new();
+        dialog.show_results(results(0..20), SEARCH_PAGE_SIZE, false);
+
+        dialog.show_results(results(0..25), 40, true);
+        assert_eq!(dialog.results.len(), 25);
+        assert_eq!(dialog.selected_index, 20);
+        assert!(!dialog.has_more);
+        assert_eq!(dialog.entry_count(), 25);
+    }
 }
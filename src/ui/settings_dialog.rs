@@ -6,6 +6,7 @@ use ratatui::{
 };
 
 use crate::config::{Config, LlmProviderType};
+use crate::ui::theme::Theme;
 
 /// Active section in the settings dialog
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -193,7 +194,7 @@ impl SettingsDialog {
             }
             EditingField::BatchConcurrency => {
                 if let Ok(val) = self.edit_buffer.parse::<usize>() {
-                    self.batch_concurrency = val.max(1).min(32);
+                    self.batch_concurrency = val.clamp(1, crate::config::MAX_LLM_CONCURRENCY);
                     self.modified = true;
                 }
             }
@@ -291,7 +292,7 @@ impl SettingsDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &SettingsDialog, area: Rect, theme: &Theme) {
     let dialog_width = 80.min(area.width.saturating_sub(4));
     let dialog_height = 30.min(area.height.saturating_sub(4));
 
@@ -317,24 +318,24 @@ pub fn render(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
     let modified_marker = if dialog.modified { " [modified]" } else { "" };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(format!(" Settings{} ", modified_marker));
     frame.render_widget(block, dialog_area);
 
     // Tabs
-    render_tabs(frame, dialog, chunks[0]);
+    render_tabs(frame, dialog, chunks[0], theme);
 
     // Content based on section
     match dialog.section {
-        SettingsSection::LlmSettings => render_llm_settings(frame, dialog, chunks[1]),
-        SettingsSection::Prompts => render_prompts(frame, dialog, chunks[1]),
+        SettingsSection::LlmSettings => render_llm_settings(frame, dialog, chunks[1], theme),
+        SettingsSection::Prompts => render_prompts(frame, dialog, chunks[1], theme),
     }
 
     // Help
-    render_help(frame, dialog, chunks[2]);
+    render_help(frame, dialog, chunks[2], theme);
 }
 
-fn render_tabs(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
+fn render_tabs(frame: &mut Frame, dialog: &SettingsDialog, area: Rect, theme: &Theme) {
     let tabs = vec![
         ("LLM Settings", SettingsSection::LlmSettings),
         ("Prompts", SettingsSection::Prompts),
@@ -345,9 +346,9 @@ fn render_tabs(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
         .enumerate()
         .flat_map(|(i, (name, section))| {
             let style = if *section == dialog.section {
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
             let mut spans = vec![Span::styled(format!(" {} ", name), style)];
             if i < tabs.len() - 1 {
@@ -363,7 +364,7 @@ fn render_tabs(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
     frame.render_widget(tabs_widget, area);
 }
 
-fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
+fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect, theme: &Theme) {
     let items = vec![
         format_setting_item(
             "Provider",
@@ -372,6 +373,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
             dialog.editing == EditingField::Provider,
             &dialog.edit_buffer,
             dialog.cursor,
+            theme,
         ),
         format_setting_item(
             "Endpoint",
@@ -380,6 +382,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
             dialog.editing == EditingField::Endpoint,
             &dialog.edit_buffer,
             dialog.cursor,
+            theme,
         ),
         format_setting_item(
             "Model",
@@ -388,6 +391,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
             dialog.editing == EditingField::Model,
             &dialog.edit_buffer,
             dialog.cursor,
+            theme,
         ),
         format_setting_item(
             "API Key",
@@ -396,6 +400,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
             dialog.editing == EditingField::ApiKey,
             &dialog.edit_buffer,
             dialog.cursor,
+            theme,
         ),
         format_setting_item(
             "Batch Concurrency",
@@ -404,6 +409,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
             dialog.editing == EditingField::BatchConcurrency,
             &dialog.edit_buffer,
             dialog.cursor,
+            theme,
         ),
     ];
 
@@ -412,7 +418,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
     let list = List::new(list_items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Blue))
+            .border_style(Style::default().fg(theme.border_active))
             .title(" LLM Configuration "),
     );
 
@@ -421,7 +427,7 @@ fn render_llm_settings(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
     frame.render_stateful_widget(list, area, &mut state);
 }
 
-fn render_prompts(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
+fn render_prompts(frame: &mut Frame, dialog: &SettingsDialog, area: Rect, theme: &Theme) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -429,9 +435,9 @@ fn render_prompts(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
 
     // Custom prompt
     let custom_style = if dialog.selected == 0 {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::Blue)
+        Style::default().fg(theme.border_active)
     };
     let custom_text = if dialog.editing == EditingField::CustomPrompt {
         format_edit_text(&dialog.edit_buffer, dialog.cursor)
@@ -450,9 +456,9 @@ fn render_prompts(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
 
     // Base prompt
     let base_style = if dialog.selected == 1 {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::Blue)
+        Style::default().fg(theme.border_active)
     };
     let base_text = if dialog.editing == EditingField::BasePrompt {
         format_edit_text(&dialog.edit_buffer, dialog.cursor)
@@ -470,7 +476,7 @@ fn render_prompts(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
     frame.render_widget(base_widget, chunks[1]);
 }
 
-fn render_help(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
+fn render_help(frame: &mut Frame, dialog: &SettingsDialog, area: Rect, theme: &Theme) {
     let help_text = if dialog.editing != EditingField::None {
         vec![
             Line::from("Enter=save | Esc=cancel | Ctrl+U=clear"),
@@ -484,7 +490,7 @@ fn render_help(frame: &mut Frame, dialog: &SettingsDialog, area: Rect) {
     };
 
     let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, area);
 }
@@ -496,10 +502,11 @@ fn format_setting_item(
     editing: bool,
     edit_buffer: &str,
     cursor: usize,
+    theme: &Theme,
 ) -> Line<'static> {
     let marker = if selected { "> " } else { "  " };
     let label_style = if selected {
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
@@ -511,11 +518,11 @@ fn format_setting_item(
     };
 
     let value_style = if editing {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.highlight)
     } else if selected {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.success)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
 
     Line::from(vec![
@@ -0,0 +1,102 @@
+//! Dialog showing where the shared web gallery can be opened, with the URL
+//! as a QR code for phones. Closing it stops the server.
+
+use std::sync::mpsc;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::share::qr_lines;
+use crate::tasks::{TaskId, TaskProgress};
+use crate::ui::theme::Theme;
+
+pub struct ShareDialog {
+    pub task_id: TaskId,
+    pub photo_count: usize,
+    /// URL once the server listens, or why it could not start
+    pub ready: mpsc::Receiver<Result<String, String>>,
+    pub url: Option<String>,
+    pub qr: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl ShareDialog {
+    pub fn new(task_id: TaskId, photo_count: usize, ready: mpsc::Receiver<Result<String, String>>) -> Self {
+        Self {
+            task_id,
+            photo_count,
+            ready,
+            url: None,
+            qr: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Pick up the server's URL or start-up error.
+    pub fn poll(&mut self) {
+        match self.ready.try_recv() {
+            Ok(Ok(url)) => {
+                self.qr = qr_lines(&url);
+                self.url = Some(url);
+            }
+            Ok(Err(error)) => self.error = Some(error),
+            Err(_) => {}
+        }
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &ShareDialog, progress: Option<&TaskProgress>, area: Rect, theme: &Theme) {
+    let qr_width = dialog.qr.first().map(|l| l.chars().count()).unwrap_or(0) as u16;
+    let dialog_width = (qr_width + 4).max(60).min(area.width.saturating_sub(4));
+    let dialog_height = (dialog.qr.len() as u16 + 8).max(9).min(area.height);
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .title(format!(" Sharing {} Photos ", dialog.photo_count))
+        .title_style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // URL or status
+            Constraint::Min(0),    // QR code
+            Constraint::Length(1), // Button
+        ])
+        .margin(1)
+        .split(dialog_area);
+
+    let status = match (&dialog.url, &dialog.error) {
+        (_, Some(error)) => Paragraph::new(format!("Sharing failed: {}", error)).style(Style::default().fg(theme.error)),
+        (Some(url), None) => Paragraph::new(vec![
+            Line::from(Span::styled(url.as_str(), Style::default().fg(theme.success).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled("Open on any device on this network", Style::default().fg(theme.muted))),
+        ]),
+        (None, None) => {
+            let current = progress.map(|p| p.current).unwrap_or(0);
+            Paragraph::new(format!("Preparing gallery {}/{}…", current, dialog.photo_count))
+        }
+    };
+    frame.render_widget(status.alignment(Alignment::Center), chunks[0]);
+
+    if !dialog.qr.is_empty() {
+        let qr: Vec<Line> = dialog.qr.iter().map(|l| Line::from(l.as_str())).collect();
+        frame.render_widget(Paragraph::new(qr).alignment(Alignment::Center), chunks[1]);
+    }
+
+    let button = Line::from(vec![
+        Span::styled("[Esc] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+        Span::raw("Stop sharing"),
+    ]);
+    frame.render_widget(Paragraph::new(button).alignment(Alignment::Center), chunks[2]);
+}
@@ -2,7 +2,7 @@
 
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Wrap},
 };
 use ratatui_image::{Resize, StatefulImage};
 use std::collections::HashMap;
@@ -15,6 +15,23 @@ use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
 use crate::app::App;
 use crate::config::ImageProtocol;
 use crate::db::Database;
+use crate::ui::animation::{self, Animation, DecodedAnimation, FrameClock};
+use crate::ui::theme::Theme;
+use crate::ui::zoom::Zoom;
+
+/// Seconds added or removed by `+` / `-`, and the shortest interval
+const INTERVAL_STEP: f64 = 0.5;
+
+/// Longest interval
+const MAX_INTERVAL: f64 = 30.0;
+
+/// Interval used when no usable one is saved or configured
+const DEFAULT_INTERVAL: f64 = 3.0;
+
+/// `secs` on the `INTERVAL_STEP` grid, so a configured 3.2s steps to 3.5s
+fn round_interval(secs: f64) -> f64 {
+    (secs / INTERVAL_STEP).round() * INTERVAL_STEP
+}
 
 /// Slideshow display mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -22,7 +39,7 @@ pub enum SlideshowDisplayMode {
     /// Show only the current image fullscreen
     #[default]
     Fullscreen,
-    /// Show presenter view with prev/current/next
+    /// Show the photo beside presenter notes, prev/next and progress
     Presenter,
 }
 
@@ -35,9 +52,11 @@ pub struct SlideshowView {
     /// Whether slideshow is playing (auto-advance)
     pub playing: bool,
     /// Auto-advance interval in seconds
-    pub interval: u64,
+    pub interval: f64,
     /// Last advance timestamp
     pub last_advance: Instant,
+    /// When the slideshow was opened, for the presenter's elapsed time
+    pub started: Instant,
     /// Display mode
     pub display_mode: SlideshowDisplayMode,
     /// Image picker for protocol detection
@@ -50,34 +69,53 @@ pub struct SlideshowView {
     receiver: Option<mpsc::Receiver<(String, DynamicImage)>>,
     /// Sender for async image loading
     sender: mpsc::Sender<(String, DynamicImage)>,
-    /// Source directory (stored for potential future use)
-    pub _directory: PathBuf,
+    /// The current image's animation (keyed by "path#rotation"); frames
+    /// take far more memory than stills, so no other animation is kept
+    animations: HashMap<String, Animation>,
+    /// Animated images held in `image_cache` as their first frame, since
+    /// they were only shown in the presenter strip
+    animated_stills: std::collections::HashSet<String>,
+    /// Receiver for async animation decoding
+    animation_receiver: Option<mpsc::Receiver<(String, DecodedAnimation)>>,
+    /// Sender for async animation decoding
+    animation_sender: mpsc::Sender<(String, DecodedAnimation)>,
+    /// Zoom into the current image
+    pub zoom: Zoom,
+    /// Source directory, whose saved interval this slideshow uses
+    pub directory: PathBuf,
 }
 
 impl SlideshowView {
     pub fn new(directory: PathBuf, images: Vec<PathBuf>, protocol: ImageProtocol) -> Self {
         let picker = Self::create_picker(protocol);
         let (tx, rx) = mpsc::channel();
+        let (anim_tx, anim_rx) = mpsc::channel();
         Self {
             images,
             current: 0,
             playing: false,
-            interval: 5,
+            interval: DEFAULT_INTERVAL,
             last_advance: Instant::now(),
+            started: Instant::now(),
             display_mode: SlideshowDisplayMode::default(),
             picker,
             image_cache: HashMap::new(),
             loading: std::collections::HashSet::new(),
             receiver: Some(rx),
             sender: tx,
-            _directory: directory,
+            animations: HashMap::new(),
+            animated_stills: std::collections::HashSet::new(),
+            animation_receiver: Some(anim_rx),
+            animation_sender: anim_tx,
+            zoom: Zoom::default(),
+            directory,
         }
     }
 
     fn create_picker(protocol: ImageProtocol) -> Option<Picker> {
         match protocol {
             ImageProtocol::None => None,
-            _ => Picker::from_query_stdio().ok(),
+            _ => crate::ui::preview::picker_for(Picker::from_query_stdio().ok(), protocol),
         }
     }
 
@@ -86,12 +124,67 @@ impl SlideshowView {
         if let Some(ref receiver) = self.receiver {
             while let Ok((cache_key, dyn_img)) = receiver.try_recv() {
                 self.loading.remove(&cache_key);
+                self.zoom.offer_source(&cache_key, &dyn_img);
                 if let Some(ref mut picker) = self.picker {
                     let protocol = picker.new_resize_protocol(dyn_img);
                     self.image_cache.insert(cache_key, protocol);
                 }
             }
         }
+
+        if let Some(ref receiver) = self.animation_receiver {
+            while let Ok((cache_key, decoded)) = receiver.try_recv() {
+                self.loading.remove(&cache_key);
+                let current_prefix = self.current_image().map(|path| format!("{}#", path.display()));
+                let is_current = current_prefix.as_ref().is_some_and(|prefix| cache_key.starts_with(prefix));
+                if let Some(ref mut picker) = self.picker {
+                    if !is_current {
+                        if let Some((first, _)) = decoded.frames.into_iter().next() {
+                            self.image_cache.insert(cache_key.clone(), picker.new_resize_protocol(first));
+                            self.animated_stills.insert(cache_key);
+                        }
+                        continue;
+                    }
+                    self.animations.clear();
+                    let (images, delays): (Vec<_>, Vec<_>) = decoded.frames.into_iter().unzip();
+                    let frames = images
+                        .into_iter()
+                        .map(|img| picker.new_resize_protocol(img))
+                        .collect();
+                    self.animations.insert(
+                        cache_key,
+                        Animation {
+                            label: decoded.label,
+                            frames,
+                            clock: FrameClock::new(delays),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Animation of the current image, if it is animated and loaded
+    fn current_animation_mut(&mut self) -> Option<&mut Animation> {
+        let prefix = format!("{}#", self.current_image()?.display());
+        self.animations
+            .iter_mut()
+            .find(|(key, _)| key.starts_with(&prefix))
+            .map(|(_, anim)| anim)
+    }
+
+    /// Advance the current image's animation, if any
+    pub fn tick_animation(&mut self) {
+        if let Some(anim) = self.current_animation_mut() {
+            anim.clock.tick();
+        }
+    }
+
+    /// Play the current image's animation from its first frame
+    fn restart_animation(&mut self) {
+        if let Some(anim) = self.current_animation_mut() {
+            anim.clock.restart();
+        }
     }
 
     /// Check if image preview is available
@@ -124,6 +217,7 @@ impl SlideshowView {
         if self.current < self.images.len().saturating_sub(1) {
             self.current += 1;
             self.last_advance = Instant::now();
+            self.restart_animation();
         }
     }
 
@@ -132,6 +226,7 @@ impl SlideshowView {
         if self.current > 0 {
             self.current -= 1;
             self.last_advance = Instant::now();
+            self.restart_animation();
         }
     }
 
@@ -139,12 +234,14 @@ impl SlideshowView {
     pub fn first(&mut self) {
         self.current = 0;
         self.last_advance = Instant::now();
+        self.restart_animation();
     }
 
     /// Go to last image
     pub fn last(&mut self) {
         self.current = self.images.len().saturating_sub(1);
         self.last_advance = Instant::now();
+        self.restart_animation();
     }
 
     /// Toggle play/pause
@@ -161,19 +258,32 @@ impl SlideshowView {
         };
     }
 
+    /// Use a saved or configured interval, clamped to
+    /// `INTERVAL_STEP..=MAX_INTERVAL`; infinite or not-a-number values fall
+    /// back to the default
+    pub fn set_interval(&mut self, secs: f64) {
+        self.interval = if secs.is_finite() { secs.clamp(INTERVAL_STEP, MAX_INTERVAL) } else { DEFAULT_INTERVAL };
+    }
+
     /// Increase interval
     pub fn increase_interval(&mut self) {
-        self.interval = (self.interval + 1).min(30);
+        self.interval = round_interval(self.interval + INTERVAL_STEP).min(MAX_INTERVAL);
     }
 
     /// Decrease interval
     pub fn decrease_interval(&mut self) {
-        self.interval = self.interval.saturating_sub(1).max(1);
+        self.interval = round_interval(self.interval - INTERVAL_STEP).max(INTERVAL_STEP);
     }
 
-    /// Check if should auto-advance
-    pub fn should_advance(&self) -> bool {
-        self.playing && self.last_advance.elapsed() >= Duration::from_secs(self.interval)
+    /// Check if should auto-advance. Animated images hold the slide
+    /// until they have played through once.
+    pub fn should_advance(&mut self) -> bool {
+        self.playing
+            && self.last_advance.elapsed() >= Duration::from_secs_f64(self.interval)
+            && self
+                .current_animation_mut()
+                .map(|anim| anim.clock.completed)
+                .unwrap_or(true)
     }
 
     /// Perform auto-advance if needed
@@ -181,6 +291,7 @@ impl SlideshowView {
         if self.should_advance() {
             if self.current < self.images.len().saturating_sub(1) {
                 self.current += 1;
+                self.restart_animation();
             } else {
                 // Stop at end
                 self.playing = false;
@@ -201,6 +312,28 @@ impl SlideshowView {
 
         let cache_key = Self::cache_key(path, rotation_degrees);
 
+        // Animated images show the frame picked by the animation clock
+        if self.animations.contains_key(&cache_key) {
+            return self.animations.get_mut(&cache_key).and_then(|anim| anim.current_frame());
+        }
+
+        // Only the current image is zoomed, not the presenter strip
+        if self.current_image() == Some(path) {
+            // Load it again to play it, now that it is the one shown
+            if self.animated_stills.remove(&cache_key) {
+                self.image_cache.remove(&cache_key);
+            }
+            self.zoom.follow(&cache_key);
+            // Zooming crops the decoded image, so reload it if it was dropped
+            if self.zoom.needs_source() {
+                self.image_cache.remove(&cache_key);
+            }
+            if self.zoom.is_zoomed() && !self.zoom.needs_source() {
+                let picker = self.picker.as_mut()?;
+                return self.zoom.view(picker);
+            }
+        }
+
         // Check cache first
         if self.image_cache.contains_key(&cache_key) {
             return self.image_cache.get_mut(&cache_key);
@@ -211,9 +344,15 @@ impl SlideshowView {
             self.loading.insert(cache_key.clone());
             let path_clone = path.clone();
             let sender = self.sender.clone();
+            let anim_sender = self.animation_sender.clone();
             let rotation = rotation_degrees;
 
             std::thread::spawn(move || {
+                if let Some(decoded) = animation::decode(&path_clone, max_size, rotation) {
+                    let _ = anim_sender.send((cache_key, decoded));
+                    return;
+                }
+
                 if let Ok(img) = image::ImageReader::open(&path_clone)
                     .and_then(|r| r.decode().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
                 {
@@ -243,6 +382,7 @@ impl SlideshowView {
 
 /// Render the slideshow view
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme;
     // Borrow db separately to avoid borrow conflicts with slideshow_view
     let db = &app.db;
     let slideshow = match app.slideshow_view.as_mut() {
@@ -251,18 +391,19 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     };
 
     // Auto-advance if playing
+    slideshow.tick_animation();
     slideshow.auto_advance();
 
     // Clear background
     frame.render_widget(Clear, area);
 
     match slideshow.display_mode {
-        SlideshowDisplayMode::Fullscreen => render_fullscreen(frame, slideshow, db, area),
-        SlideshowDisplayMode::Presenter => render_presenter(frame, slideshow, db, area),
+        SlideshowDisplayMode::Fullscreen => render_fullscreen(frame, slideshow, db, area, theme),
+        SlideshowDisplayMode::Presenter => render_presenter(frame, slideshow, db, area, theme),
     }
 }
 
-fn render_fullscreen(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Database, area: Rect) {
+fn render_fullscreen(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Database, area: Rect, theme: &Theme) {
     // Main layout: image + status bar
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -273,7 +414,7 @@ fn render_fullscreen(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Data
     if let Some(path) = slideshow.current_image().cloned() {
         let block = Block::default()
             .borders(Borders::NONE)
-            .style(Style::default().bg(Color::Black));
+            .style(Style::default().bg(theme.bg));
         frame.render_widget(block, chunks[0]);
 
         // Get rotation from database (combines EXIF + user rotation)
@@ -283,120 +424,147 @@ fn render_fullscreen(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Data
             frame.render_stateful_widget(image, chunks[0], protocol);
         } else if slideshow.is_loading(&path) {
             let loading = Paragraph::new("Loading...")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(theme.muted))
                 .alignment(Alignment::Center);
             frame.render_widget(loading, centered_rect(chunks[0], 20, 1));
         }
     }
 
     // Status bar
-    render_status_bar(frame, slideshow, chunks[1]);
+    render_status_bar(frame, slideshow, chunks[1], theme);
 }
 
-fn render_presenter(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Database, area: Rect) {
-    // Layout: preview strip at top + main image + status bar
-    let chunks = Layout::default()
+fn render_presenter(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Database, area: Rect, theme: &Theme) {
+    // Layout: audience image | presenter panel, status bar underneath
+    let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(12), // Preview strip
-            Constraint::Min(10),    // Main image
-            Constraint::Length(2),  // Status bar
-        ])
+        .constraints([Constraint::Min(10), Constraint::Length(2)])
         .split(area);
-
-    // Render preview strip (prev | current | next)
-    render_preview_strip(frame, slideshow, db, chunks[0]);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+        .split(rows[0]);
 
     // Render current image
     if let Some(path) = slideshow.current_image().cloned() {
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.accent))
             .title(" Current (Audience View) ");
-        let inner = block.inner(chunks[1]);
-        frame.render_widget(block, chunks[1]);
+        let inner = block.inner(cols[0]);
+        frame.render_widget(block, cols[0]);
 
         // Get rotation from database (combines EXIF + user rotation)
         let rotation = db.get_photo_rotation(&path).unwrap_or(0);
-        if let Some(protocol) = slideshow.load_image(&path, 1024, rotation) {
+        if let Some(protocol) = slideshow.load_image(&path, 2048, rotation) {
             let image = StatefulImage::new(None).resize(Resize::Fit(None));
             frame.render_stateful_widget(image, inner, protocol);
         } else if slideshow.is_loading(&path) {
             let loading = Paragraph::new("Loading...")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(theme.muted))
                 .alignment(Alignment::Center);
             frame.render_widget(loading, centered_rect(inner, 20, 1));
         }
     }
 
+    render_presenter_panel(frame, slideshow, db, cols[1], theme);
+
     // Status bar
-    render_status_bar(frame, slideshow, chunks[2]);
+    render_status_bar(frame, slideshow, rows[1], theme);
 }
 
-fn render_preview_strip(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Database, area: Rect) {
-    // Three-column layout for prev/current/next
-    let cols = Layout::default()
-        .direction(Direction::Horizontal)
+/// Presenter notes, previous/next thumbnails, progress and elapsed time
+fn render_presenter_panel(frame: &mut Frame, slideshow: &mut SlideshowView, db: &Database, area: Rect, theme: &Theme) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
+            Constraint::Min(5),     // Notes
+            Constraint::Length(10), // Previous / next
+            Constraint::Length(3),  // Progress
+            Constraint::Length(1),  // Elapsed time
         ])
         .split(area);
 
-    // Previous
-    let prev_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
-        .title(" Previous ");
-    let prev_inner = prev_block.inner(cols[0]);
-    frame.render_widget(prev_block, cols[0]);
+    // The LLM description serves as presenter notes
+    let notes = slideshow
+        .current_image()
+        .and_then(|path| db.get_description(path).ok().flatten());
+    let notes_widget = match notes {
+        Some(text) => Paragraph::new(text).style(Style::default().fg(theme.fg)),
+        None => Paragraph::new("No description for this photo.")
+            .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)),
+    };
+    frame.render_widget(
+        notes_widget.wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight))
+                .title(" Presenter Notes "),
+        ),
+        chunks[0],
+    );
 
-    if let Some(path) = slideshow.prev_image().cloned() {
-        let rotation = db.get_photo_rotation(&path).unwrap_or(0);
-        if let Some(protocol) = slideshow.load_image(&path, 256, rotation) {
-            let image = StatefulImage::new(None).resize(Resize::Fit(None));
-            frame.render_stateful_widget(image, prev_inner, protocol);
-        }
-    }
+    let thumbs = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    let prev = slideshow.prev_image().cloned();
+    let next = slideshow.next_image().cloned();
+    render_thumbnail(frame, slideshow, db, prev, thumbs[0], " Previous ", theme.muted);
+    render_thumbnail(frame, slideshow, db, next, thumbs[1], " Next ", theme.highlight);
+
+    let total = slideshow.images.len().max(1);
+    let progress = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted)))
+        .gauge_style(Style::default().fg(theme.success).bg(theme.bg))
+        .ratio((slideshow.current + 1) as f64 / total as f64)
+        .label(format!("Image {} of {}", slideshow.current + 1, slideshow.images.len()));
+    frame.render_widget(progress, chunks[2]);
+
+    let elapsed = Paragraph::new(format!(" Elapsed {}", format_elapsed(slideshow.started.elapsed())))
+        .style(Style::default().fg(theme.accent));
+    frame.render_widget(elapsed, chunks[3]);
+}
 
-    // Current (highlighted)
-    let curr_block = Block::default()
+fn render_thumbnail(
+    frame: &mut Frame,
+    slideshow: &mut SlideshowView,
+    db: &Database,
+    path: Option<PathBuf>,
+    area: Rect,
+    title: &str,
+    border_color: Color,
+) {
+    let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Green))
-        .title(" Current ");
-    let curr_inner = curr_block.inner(cols[1]);
-    frame.render_widget(curr_block, cols[1]);
+        .border_style(Style::default().fg(border_color))
+        .title(title.to_string());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
-    if let Some(path) = slideshow.current_image().cloned() {
+    if let Some(path) = path {
         let rotation = db.get_photo_rotation(&path).unwrap_or(0);
         if let Some(protocol) = slideshow.load_image(&path, 256, rotation) {
             let image = StatefulImage::new(None).resize(Resize::Fit(None));
-            frame.render_stateful_widget(image, curr_inner, protocol);
+            frame.render_stateful_widget(image, inner, protocol);
         }
     }
+}
 
-    // Next (preview)
-    let next_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .title(" Next (Preview) ");
-    let next_inner = next_block.inner(cols[2]);
-    frame.render_widget(next_block, cols[2]);
-
-    if let Some(path) = slideshow.next_image().cloned() {
-        let rotation = db.get_photo_rotation(&path).unwrap_or(0);
-        if let Some(protocol) = slideshow.load_image(&path, 256, rotation) {
-            let image = StatefulImage::new(None).resize(Resize::Fit(None));
-            frame.render_stateful_widget(image, next_inner, protocol);
-        }
+/// Elapsed presentation time as m:ss, or h:mm:ss after the first hour
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
     }
 }
 
-fn render_status_bar(frame: &mut Frame, slideshow: &SlideshowView, area: Rect) {
+fn render_status_bar(frame: &mut Frame, slideshow: &SlideshowView, area: Rect, theme: &Theme) {
     let play_status = if slideshow.playing { "▶ Playing" } else { "⏸ Paused" };
     let progress = format!("{}/{}", slideshow.current + 1, slideshow.images.len());
-    let interval = format!("{}s", slideshow.interval);
+    let interval = format!("[{:.1}s]", slideshow.interval);
     let mode = match slideshow.display_mode {
         SlideshowDisplayMode::Fullscreen => "Fullscreen",
         SlideshowDisplayMode::Presenter => "Presenter",
@@ -407,12 +575,15 @@ fn render_status_bar(frame: &mut Frame, slideshow: &SlideshowView, area: Rect) {
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_default();
 
-    let status_line = format!(
-        " {} | {} | Interval: {} | Mode: {} | {} ",
+    let mut status_line = format!(
+        " {} | {} | {} | Mode: {} | {} ",
         play_status, progress, interval, mode, filename
     );
+    if let Some(zoom) = slideshow.zoom.label() {
+        status_line.push_str(&format!("| Zoom: {} ", zoom));
+    }
 
-    let help = "Space:play/pause | h/l:prev/next | v:mode | +/-:speed | q:quit";
+    let help = "Space:play/pause | h/l:prev/next | P:presenter | +/-:speed | q:quit";
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -420,11 +591,11 @@ fn render_status_bar(frame: &mut Frame, slideshow: &SlideshowView, area: Rect) {
         .split(area);
 
     let status = Paragraph::new(status_line)
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(theme.accent));
     frame.render_widget(status, chunks[0]);
 
     let help_text = Paragraph::new(help)
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(theme.muted));
     frame.render_widget(help_text, chunks[1]);
 }
 
@@ -435,7 +606,7 @@ fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
 }
 
 /// Render slideshow help dialog
-pub fn render_help(frame: &mut Frame, area: Rect) {
+pub fn render_help(frame: &mut Frame, area: Rect, theme: &Theme) {
     let dialog_width = 50.min(area.width.saturating_sub(4));
     let dialog_height = 16.min(area.height.saturating_sub(4));
 
@@ -447,14 +618,14 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
     frame.render_widget(Clear, dialog_area);
 
     let help_text = vec![
-        Line::from(Span::styled("Slideshow Controls", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))),
+        Line::from(Span::styled("Slideshow Controls", Style::default().add_modifier(Modifier::BOLD).fg(theme.header_fg))),
         Line::from(""),
         Line::from("  Space          Play/Pause"),
         Line::from("  h/Left         Previous image"),
         Line::from("  l/Right        Next image"),
         Line::from("  g              First image"),
         Line::from("  G              Last image"),
-        Line::from("  v              Toggle view mode"),
+        Line::from("  P/v            Toggle presenter mode"),
         Line::from("  +/=            Slower (more seconds)"),
         Line::from("  -              Faster (fewer seconds)"),
         Line::from("  Esc/q          Exit slideshow"),
@@ -464,9 +635,45 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
     let paragraph = Paragraph::new(help_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.accent))
             .title(" Slideshow Help "),
     );
 
     frame.render_widget(paragraph, dialog_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(7)), "0:07");
+        assert_eq!(format_elapsed(Duration::from_secs(754)), "12:34");
+        assert_eq!(format_elapsed(Duration::from_secs(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn test_interval_steps() {
+        let mut slideshow = SlideshowView::new(PathBuf::from("/photos"), Vec::new(), ImageProtocol::None);
+        slideshow.set_interval(3.2);
+        slideshow.increase_interval();
+        assert_eq!(slideshow.interval, 3.5);
+
+        slideshow.set_interval(0.0);
+        assert_eq!(slideshow.interval, INTERVAL_STEP);
+        slideshow.decrease_interval();
+        assert_eq!(slideshow.interval, INTERVAL_STEP);
+
+        slideshow.set_interval(MAX_INTERVAL);
+        slideshow.increase_interval();
+        assert_eq!(slideshow.interval, MAX_INTERVAL);
+
+        slideshow.set_interval(1e300);
+        assert_eq!(slideshow.interval, MAX_INTERVAL);
+        for bad in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            slideshow.set_interval(bad);
+            assert_eq!(slideshow.interval, DEFAULT_INTERVAL);
+        }
+    }
+}
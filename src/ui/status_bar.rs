@@ -4,14 +4,18 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::config::SortOrder;
+use crate::ui::date_filter_dialog::describe_range;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+
     // If there's a status message, show it prominently
     if let Some(ref message) = app.status_message {
         let line = Line::from(vec![
             Span::styled(
                 format!(" {} ", message),
-                Style::default().fg(Color::Yellow).bg(Color::DarkGray),
+                Style::default().fg(theme.highlight).bg(theme.status_bar_bg),
             ),
         ]);
         let paragraph = Paragraph::new(line);
@@ -56,13 +60,13 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     // Left: path
     spans.push(Span::styled(
         format!(" {} ", path),
-        Style::default().fg(Color::White).bg(Color::DarkGray),
+        Style::default().fg(theme.status_bar_fg).bg(theme.status_bar_bg),
     ));
 
     // Middle: dir/file count
     spans.push(Span::styled(
         format!(" {} dirs, {} files ", dir_count, file_count),
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme.secondary),
     ));
 
     // Filter indicators
@@ -73,10 +77,47 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     if app.show_all_files {
         filter_parts.push("all");
     }
+    if app.sort_order != SortOrder::Name {
+        filter_parts.push(app.sort_order.label());
+    }
+    if app.dir_watcher.is_some() {
+        filter_parts.push("watch");
+    }
+    if app.dry_run {
+        filter_parts.push("dry-run");
+    }
     if !filter_parts.is_empty() {
         spans.push(Span::styled(
             format!(" [{}] ", filter_parts.join(",")),
-            Style::default().fg(Color::Magenta),
+            Style::default().fg(theme.emphasis),
+        ));
+    }
+
+    if let Some((start, end)) = app.date_filter {
+        spans.push(Span::styled(
+            format!(" [Date: {}] ", describe_range(start, end)),
+            Style::default().fg(theme.emphasis),
+        ));
+    }
+
+    if let Some(ref camera) = app.camera_filter {
+        spans.push(Span::styled(
+            format!(" [Camera: {}] ", camera),
+            Style::default().fg(theme.emphasis),
+        ));
+    }
+
+    if let Some(ref tag) = app.tag_filter {
+        spans.push(Span::styled(
+            format!(" [Tag: {}] ", tag.name),
+            Style::default().fg(theme.emphasis),
+        ));
+    }
+
+    if let Some((ref label, _)) = app.location_filter {
+        spans.push(Span::styled(
+            format!(" [Location: {}] ", label),
+            Style::default().fg(theme.emphasis),
         ));
     }
 
@@ -84,7 +125,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     if !task_indicators.is_empty() {
         spans.push(Span::styled(
             format!(" {} ", task_indicators),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         ));
     }
 
@@ -94,7 +135,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         if change_count > 0 {
             spans.push(Span::styled(
                 format!(" [!{} changes] ", change_count),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme.error),
             ));
         }
     }
@@ -122,7 +163,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     // Right: help hints
     spans.push(Span::styled(
         help_text,
-        Style::default().fg(Color::White).bg(Color::DarkGray),
+        Style::default().fg(theme.status_bar_fg).bg(theme.status_bar_bg),
     ));
 
     let line = Line::from(spans);
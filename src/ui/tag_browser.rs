@@ -0,0 +1,345 @@
+//! Tag browser: every tag with its photo count, for filtering and cleanup.
+//! Nested tags are listed under their parent.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState},
+};
+use std::collections::{HashMap, HashSet};
+
+use crate::db::{TagStat, UserTag};
+use crate::ui::theme::Theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagBrowserMode {
+    /// Moving through the list
+    Browse,
+    /// Editing the selected tag's name
+    Rename,
+    /// Waiting for y/n before deleting the selected tag
+    ConfirmDelete,
+    /// Choosing the tag to nest the selected tag under
+    PickParent,
+}
+
+/// A tag the selected tag can be moved under, or the top level
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentChoice {
+    /// None for the top level
+    pub id: Option<i64>,
+    pub label: String,
+}
+
+/// State for the tag browser dialog.
+pub struct TagBrowser {
+    /// Tags in tree order: each followed by the tags nested in it, and
+    /// tags with the same parent sorted by photo count, most used first
+    pub stats: Vec<TagStat>,
+    /// Nesting depth of each tag in `stats`, 0 for top level
+    pub depths: Vec<usize>,
+    pub selected_index: usize,
+    pub mode: TagBrowserMode,
+    /// New name while renaming
+    pub input: String,
+    /// Where the selected tag can be moved, while picking a parent
+    pub parent_choices: Vec<ParentChoice>,
+    pub parent_index: usize,
+}
+
+impl TagBrowser {
+    pub fn new(stats: Vec<TagStat>) -> Self {
+        let (stats, depths) = tree_order(stats);
+        Self {
+            stats,
+            depths,
+            selected_index: 0,
+            mode: TagBrowserMode::Browse,
+            input: String::new(),
+            parent_choices: Vec::new(),
+            parent_index: 0,
+        }
+    }
+
+    /// Replace the list after a change, keeping the cursor in range.
+    pub fn set_stats(&mut self, stats: Vec<TagStat>) {
+        (self.stats, self.depths) = tree_order(stats);
+        self.selected_index = self.selected_index.min(self.stats.len().saturating_sub(1));
+        self.cancel();
+    }
+
+    /// Put the cursor on the tag with `tag_id`, if it is listed
+    pub fn select_tag(&mut self, tag_id: i64) {
+        if let Some(index) = self.stats.iter().position(|s| s.tag.id == tag_id) {
+            self.selected_index = index;
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected_index + 1 < self.stats.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    pub fn selected(&self) -> Option<&TagStat> {
+        self.stats.get(self.selected_index)
+    }
+
+    pub fn selected_tag(&self) -> Option<UserTag> {
+        self.selected().map(|s| s.tag.clone())
+    }
+
+    pub fn start_rename(&mut self) {
+        if let Some(name) = self.selected().map(|s| s.tag.name.clone()) {
+            self.input = name;
+            self.mode = TagBrowserMode::Rename;
+        }
+    }
+
+    pub fn start_delete(&mut self) {
+        if self.selected().is_some() {
+            self.mode = TagBrowserMode::ConfirmDelete;
+        }
+    }
+
+    /// List the tags the selected tag can be nested under: all but itself
+    /// and the tags already nested in it, after a `[root]` entry for the
+    /// top level.
+    pub fn start_pick_parent(&mut self) {
+        let Some(selected) = self.selected() else {
+            return;
+        };
+        let current_parent = selected.parent_id;
+        let depth = self.depths[self.selected_index];
+        let subtree_end = (self.selected_index + 1..self.stats.len())
+            .find(|&i| self.depths[i] <= depth)
+            .unwrap_or(self.stats.len());
+
+        self.parent_choices = vec![ParentChoice { id: None, label: "[root]".to_string() }];
+        for (i, stat) in self.stats.iter().enumerate() {
+            if !(self.selected_index..subtree_end).contains(&i) {
+                self.parent_choices.push(ParentChoice { id: Some(stat.tag.id), label: self.tree_label(i) });
+            }
+        }
+        self.parent_index = self
+            .parent_choices
+            .iter()
+            .position(|c| c.id == current_parent)
+            .unwrap_or(0);
+        self.mode = TagBrowserMode::PickParent;
+    }
+
+    pub fn move_parent_down(&mut self) {
+        if self.parent_index + 1 < self.parent_choices.len() {
+            self.parent_index += 1;
+        }
+    }
+
+    pub fn move_parent_up(&mut self) {
+        self.parent_index = self.parent_index.saturating_sub(1);
+    }
+
+    pub fn selected_parent(&self) -> Option<&ParentChoice> {
+        self.parent_choices.get(self.parent_index)
+    }
+
+    /// Leave rename, delete confirmation or the parent picker without
+    /// changing anything.
+    pub fn cancel(&mut self) {
+        self.mode = TagBrowserMode::Browse;
+        self.input.clear();
+        self.parent_choices.clear();
+    }
+
+    /// The tag's name indented under its parent, e.g. `   └─ puppies`
+    fn tree_label(&self, index: usize) -> String {
+        indented(&self.stats[index].tag.name, self.depths[index])
+    }
+}
+
+fn indented(name: &str, depth: usize) -> String {
+    match depth {
+        0 => name.to_string(),
+        depth => format!("{}└─ {}", "   ".repeat(depth - 1), name),
+    }
+}
+
+/// `stats` with each tag followed by the tags nested in it, keeping their
+/// order among siblings, and the depth of each. Tags whose parent is
+/// missing are listed at the top level.
+fn tree_order(stats: Vec<TagStat>) -> (Vec<TagStat>, Vec<usize>) {
+    let ids: HashSet<i64> = stats.iter().map(|s| s.tag.id).collect();
+    let mut children: HashMap<Option<i64>, Vec<TagStat>> = HashMap::new();
+    let mut parents = Vec::new();
+    for stat in stats {
+        let parent = stat.parent_id.filter(|p| ids.contains(p));
+        if !children.contains_key(&parent) {
+            parents.push(parent);
+        }
+        children.entry(parent).or_default().push(stat);
+    }
+
+    let mut ordered = Vec::new();
+    let mut depths = Vec::new();
+    let mut stack: Vec<(TagStat, usize)> = children
+        .remove(&None)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .map(|stat| (stat, 0))
+        .collect();
+    while let Some((stat, depth)) = stack.pop() {
+        let nested = children.remove(&Some(stat.tag.id)).unwrap_or_default();
+        stack.extend(nested.into_iter().rev().map(|child| (child, depth + 1)));
+        ordered.push(stat);
+        depths.push(depth);
+    }
+
+    // Tags caught in a parent loop are never reached from the top level
+    for parent in parents {
+        for stat in children.remove(&parent).unwrap_or_default() {
+            ordered.push(stat);
+            depths.push(0);
+        }
+    }
+    (ordered, depths)
+}
+
+pub fn render(frame: &mut Frame, dialog: &TagBrowser, area: Rect, theme: &Theme) {
+    let longest = (0..dialog.stats.len())
+        .map(|i| dialog.tree_label(i).chars().count() as u16)
+        .max()
+        .unwrap_or(0);
+    let dialog_width = (longest + 20).max(44).min(area.width.saturating_sub(4));
+    let dialog_height = (dialog.stats.len() as u16 + 6).clamp(8, area.height.saturating_sub(4).max(8));
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height.min(area.height));
+
+    frame.render_widget(Clear, dialog_area);
+
+    let title = match (dialog.mode, dialog.selected()) {
+        (TagBrowserMode::PickParent, Some(stat)) => format!(" Move \"{}\" under ", stat.tag.name),
+        _ => format!(" Tags ({}) ", dialog.stats.len()),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(title);
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+
+    if dialog.mode == TagBrowserMode::PickParent {
+        let items: Vec<ListItem> = dialog
+            .parent_choices
+            .iter()
+            .map(|choice| ListItem::new(format!(" {}", choice.label)))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.selected_bg)
+                .fg(theme.selected_fg)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default();
+        state.select(Some(dialog.parent_index));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+    } else if dialog.stats.is_empty() {
+        let empty = Paragraph::new(" No tags yet. Press t on a photo to add one.")
+            .style(Style::default().fg(theme.muted));
+        frame.render_widget(empty, chunks[0]);
+    } else {
+        let rows = dialog.stats.iter().enumerate().map(|(i, stat)| {
+            let name = if i == dialog.selected_index && dialog.mode == TagBrowserMode::Rename {
+                indented(&format!("> {}_", dialog.input), dialog.depths[i])
+            } else {
+                dialog.tree_label(i)
+            };
+            Row::new(vec![
+                Cell::from(format!(" {}", name)),
+                Cell::from(Line::from(stat.photo_count.to_string()).alignment(Alignment::Right)),
+            ])
+        });
+
+        let table = Table::new(rows, [Constraint::Min(10), Constraint::Length(8)])
+            .header(
+                Row::new(vec![
+                    Cell::from(" Tag"),
+                    Cell::from(Line::from("Photos").alignment(Alignment::Right)),
+                ])
+                .style(Style::default().fg(theme.header_fg).add_modifier(Modifier::BOLD)),
+            )
+            .column_spacing(1)
+            .row_highlight_style(
+                Style::default()
+                    .bg(theme.selected_bg)
+                    .fg(theme.selected_fg)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let mut state = TableState::default();
+        state.select(Some(dialog.selected_index));
+        frame.render_stateful_widget(table, chunks[0], &mut state);
+    }
+
+    let help = match (dialog.mode, dialog.selected()) {
+        (TagBrowserMode::ConfirmDelete, Some(stat)) => Paragraph::new(format!(
+            " Delete \"{}\" from {} photos? y/n",
+            stat.tag.name, stat.photo_count
+        ))
+        .style(Style::default().fg(theme.error)),
+        (TagBrowserMode::Rename, _) => Paragraph::new(" Enter=save  Esc=cancel")
+            .style(Style::default().fg(theme.muted)),
+        (TagBrowserMode::PickParent, _) => Paragraph::new(" j/k=move  Enter=move here  Esc=cancel")
+            .style(Style::default().fg(theme.muted)),
+        _ => Paragraph::new(" j/k=move  Enter=filter  m=nest  r=rename  d=delete  Esc=close")
+            .style(Style::default().fg(theme.muted)),
+    };
+    frame.render_widget(help.block(Block::default().borders(Borders::TOP)), chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(id: i64, name: &str, photo_count: i64, parent_id: Option<i64>) -> TagStat {
+        TagStat {
+            tag: UserTag { id, name: name.to_string(), color: "#808080".to_string() },
+            photo_count,
+            parent_id,
+        }
+    }
+
+    #[test]
+    fn test_tree_and_parent_choices() {
+        let mut browser = TagBrowser::new(vec![
+            stat(3, "puppies", 9, Some(2)),
+            stat(4, "beach", 5, None),
+            stat(2, "dogs", 4, Some(1)),
+            stat(1, "animals", 1, None),
+        ]);
+        let labels: Vec<String> = (0..browser.stats.len()).map(|i| browser.tree_label(i)).collect();
+        assert_eq!(labels, ["beach", "animals", "└─ dogs", "   └─ puppies"]);
+
+        // dogs cannot go under itself or puppies
+        browser.select_tag(2);
+        browser.start_pick_parent();
+        let choices: Vec<Option<i64>> = browser.parent_choices.iter().map(|c| c.id).collect();
+        assert_eq!(choices, [None, Some(4), Some(1)]);
+        assert_eq!(browser.selected_parent().and_then(|c| c.id), Some(1));
+
+        browser.cancel();
+        assert_eq!(browser.mode, TagBrowserMode::Browse);
+        assert!(browser.parent_choices.is_empty());
+    }
+}
@@ -7,6 +7,10 @@ use ratatui::{
 use std::path::PathBuf;
 
 use crate::db::UserTag;
+use crate::ui::theme::Theme;
+
+/// Color assigned to tags created without an explicit color.
+const DEFAULT_TAG_COLOR: &str = "#808080";
 
 /// Dialog state for tagging a photo
 pub struct TagDialog {
@@ -127,7 +131,7 @@ impl TagDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &TagDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &TagDialog, area: Rect, theme: &Theme) {
     let dialog_width = 50.min(area.width.saturating_sub(4));
     let dialog_height = 20.min(area.height.saturating_sub(4));
 
@@ -144,7 +148,7 @@ pub fn render(frame: &mut Frame, dialog: &TagDialog, area: Rect) {
         .unwrap_or_default());
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(title);
     frame.render_widget(block, dialog_area);
 
@@ -166,21 +170,21 @@ pub fn render(frame: &mut Frame, dialog: &TagDialog, area: Rect) {
         .split(inner);
 
     match dialog.mode {
-        TagDialogMode::ViewTags => render_view_mode(frame, dialog, chunks),
-        TagDialogMode::AddTag => render_add_mode(frame, dialog, chunks),
+        TagDialogMode::ViewTags => render_view_mode(frame, dialog, chunks, theme),
+        TagDialogMode::AddTag => render_add_mode(frame, dialog, chunks, theme),
     }
 }
 
-fn render_view_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[Rect]>) {
+fn render_view_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[Rect]>, theme: &Theme) {
     // Mode indicator
     let mode_text = Paragraph::new("Current tags (a=add, d=delete, Esc=close)")
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(theme.highlight));
     frame.render_widget(mode_text, chunks[0]);
 
     // Current tags list
     if dialog.current_tags.is_empty() {
         let empty = Paragraph::new("No tags assigned")
-            .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+            .style(Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC))
             .block(Block::default().borders(Borders::ALL).title(" Tags "));
         frame.render_widget(empty, chunks[1]);
     } else {
@@ -189,9 +193,9 @@ fn render_view_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[
             .enumerate()
             .map(|(i, tag)| {
                 let style = if i == dialog.selected_index {
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default()
+                    Style::default().fg(tag_color(tag, theme))
                 };
                 ListItem::new(format!("  {} ", tag.name)).style(style)
             })
@@ -208,12 +212,12 @@ fn render_view_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[
 
     // Help text
     let help = Paragraph::new("j/k:navigate | a:add tag | d:remove tag | Esc:close")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[2]);
 }
 
-fn render_add_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[Rect]>) {
+fn render_add_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[Rect]>, theme: &Theme) {
     // Input field (placeholder text computed but using dialog.input directly below)
     let _input_text = if dialog.input.is_empty() {
         "Type tag name (Enter=select/create, Esc=cancel)"
@@ -221,9 +225,9 @@ fn render_add_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[R
         &dialog.input
     };
     let input_style = if dialog.input.is_empty() {
-        Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)
+        Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.fg)
     };
     let input = Paragraph::new(format!("> {}_", if dialog.input.is_empty() { "" } else { &dialog.input }))
         .style(input_style)
@@ -234,7 +238,7 @@ fn render_add_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[R
     if dialog.suggestions.is_empty() && !dialog.input.is_empty() {
         let create_msg = format!("Press Enter to create tag: \"{}\"", dialog.input);
         let msg = Paragraph::new(create_msg)
-            .style(Style::default().fg(Color::Yellow))
+            .style(Style::default().fg(theme.highlight))
             .block(Block::default().borders(Borders::ALL).title(" Suggestions "));
         frame.render_widget(msg, chunks[1]);
     } else {
@@ -243,9 +247,9 @@ fn render_add_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[R
             .enumerate()
             .map(|(i, tag)| {
                 let style = if i == dialog.selected_index {
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default()
+                    Style::default().fg(tag_color(tag, theme))
                 };
                 ListItem::new(format!("  {} ", tag.name)).style(style)
             })
@@ -262,7 +266,17 @@ fn render_add_mode(frame: &mut Frame, dialog: &TagDialog, chunks: std::rc::Rc<[R
 
     // Help text
     let help = Paragraph::new("j/k:select | Enter:add | Esc:cancel")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[2]);
 }
+
+/// Color for a tag: its own color if one was set, otherwise the theme palette.
+fn tag_color(tag: &UserTag, theme: &Theme) -> Color {
+    if tag.color != DEFAULT_TAG_COLOR {
+        if let Ok(color) = tag.color.parse::<Color>() {
+            return color;
+        }
+    }
+    theme.tag_color(&tag.name)
+}
@@ -5,9 +5,11 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph, Gauge};
 
 use crate::app::App;
 use crate::tasks::BackgroundTask;
+use crate::ui::theme::Theme;
 
 /// Render the task list dialog.
 pub fn render(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let area = frame.area();
 
     // Calculate dialog size - centered, not too wide
@@ -26,7 +28,7 @@ pub fn render(frame: &mut Frame, app: &App) {
     let block = Block::default()
         .title(" Running Tasks ")
         .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.bg));
     frame.render_widget(block, dialog_area);
 
     // Get running tasks
@@ -38,10 +40,11 @@ pub fn render(frame: &mut Frame, app: &App) {
         dialog_area.height.saturating_sub(2),
     );
 
-    if running_tasks.is_empty() {
+    let queued = app.task_manager.queued_tasks();
+    if running_tasks.is_empty() && queued.is_empty() {
         // Show message when no tasks running
         let text = Paragraph::new("No tasks running\n\nPress Esc or T to close")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(text, inner);
     } else {
@@ -56,7 +59,19 @@ pub fn render(frame: &mut Frame, app: &App) {
                 inner.width,
                 task_height as u16,
             );
-            render_task(frame, task, idx, task_area);
+            render_task(frame, task, idx, task_area, theme);
+        }
+
+        // Tasks waiting on another one, one line each
+        let waiting_y = inner.y + (running_tasks.len().min(max_tasks) * task_height) as u16;
+        for (idx, task) in queued.iter().enumerate() {
+            let y = waiting_y + idx as u16;
+            if y + 2 >= inner.y + inner.height {
+                break;
+            }
+            let line = Paragraph::new(format!("    Waiting: {}", task.task_type.display_name()))
+                .style(Style::default().fg(theme.muted));
+            frame.render_widget(line, Rect::new(inner.x, y, inner.width, 1));
         }
 
         // Render help at the bottom
@@ -64,7 +79,7 @@ pub fn render(frame: &mut Frame, app: &App) {
         if help_y < area.height {
             let help_area = Rect::new(dialog_area.x + 1, help_y, dialog_area.width - 2, 1);
             let help_text = Paragraph::new("1-9:cancel task  c:cancel all  Esc:close")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(theme.muted))
                 .alignment(Alignment::Center);
             frame.render_widget(help_text, help_area);
         }
@@ -72,7 +87,7 @@ pub fn render(frame: &mut Frame, app: &App) {
 }
 
 /// Render a single task row with progress bar.
-fn render_task(frame: &mut Frame, task: &BackgroundTask, index: usize, area: Rect) {
+fn render_task(frame: &mut Frame, task: &BackgroundTask, index: usize, area: Rect, theme: &Theme) {
     if area.height < 2 {
         return;
     }
@@ -88,7 +103,7 @@ fn render_task(frame: &mut Frame, task: &BackgroundTask, index: usize, area: Rec
         elapsed_str
     );
     let header_text = Paragraph::new(header)
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(theme.accent));
     let header_area = Rect::new(area.x, area.y, area.width, 1);
     frame.render_widget(header_text, header_area);
 
@@ -98,12 +113,17 @@ fn render_task(frame: &mut Frame, task: &BackgroundTask, index: usize, area: Rec
 
         if let Some(ref progress) = task.progress {
             let label = if let Some(ref item) = progress.current_item {
+                // Transfers add the bytes copied so far
+                let item = match progress.message {
+                    Some(ref message) => format!("{} ({})", item, message),
+                    None => item.clone(),
+                };
                 // Truncate item name if too long
                 let max_len = (area.width as usize).saturating_sub(12);
                 if item.len() > max_len {
                     format!("{:.width$}...", item, width = max_len.saturating_sub(3))
                 } else {
-                    item.clone()
+                    item
                 }
             } else {
                 format!("{}/{}", progress.current, progress.total)
@@ -118,11 +138,11 @@ fn render_task(frame: &mut Frame, task: &BackgroundTask, index: usize, area: Rec
             let gauge = Gauge::default()
                 .ratio(ratio.min(1.0))
                 .label(label)
-                .gauge_style(Style::default().fg(Color::Green).bg(Color::DarkGray));
+                .gauge_style(Style::default().fg(theme.success).bg(theme.muted));
             frame.render_widget(gauge, progress_area);
         } else {
             let status = Paragraph::new("Starting...")
-                .style(Style::default().fg(Color::Yellow));
+                .style(Style::default().fg(theme.highlight));
             frame.render_widget(status, progress_area);
         }
     }
@@ -0,0 +1,156 @@
+//! Preview content for non-image files: highlighted text or a hex dump.
+
+use ratatui::prelude::*;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme as SyntaxTheme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Text files longer than this are cut off in the preview.
+pub const MAX_LINES: usize = 1000;
+
+/// Bytes read from the start of a file; enough for 1000 typical lines.
+const MAX_BYTES: u64 = 256 * 1024;
+
+/// Bytes per row of the hex dump.
+const HEX_ROW: usize = 16;
+
+/// Highlighting theme; only foreground colours are used so the app theme's
+/// background shows through.
+const SYNTAX_THEME: &str = "base16-ocean.dark";
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn syntax_theme() -> &'static SyntaxTheme {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    &THEMES.get_or_init(ThemeSet::load_defaults).themes[SYNTAX_THEME]
+}
+
+/// Read the start of a file and turn it into preview lines.
+pub fn load(path: &Path) -> std::io::Result<Vec<Line<'static>>> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)?
+        .take(MAX_BYTES)
+        .read_to_end(&mut bytes)?;
+
+    if is_binary(&bytes) {
+        Ok(hex_dump(&bytes[..bytes.len().min(MAX_LINES * HEX_ROW)]))
+    } else {
+        Ok(highlight(path, &String::from_utf8_lossy(&bytes)))
+    }
+}
+
+/// A file is treated as binary if it contains NUL bytes or invalid UTF-8.
+/// A multi-byte character cut off at the end of the read is not invalid.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.contains(&0)
+        || std::str::from_utf8(bytes).is_err_and(|e| e.error_len().is_some())
+}
+
+/// Syntax highlight text, picking the syntax from the file name or its
+/// first line (shebangs), and plain text otherwise.
+fn highlight(path: &Path, text: &str) -> Vec<Line<'static>> {
+    let syntaxes = syntax_set();
+    let syntax = syntaxes
+        .find_syntax_for_file(path)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, syntax_theme());
+
+    LinesWithEndings::from(text)
+        .take(MAX_LINES)
+        .map(|line| {
+            let spans = match highlighter.highlight_line(line, syntaxes) {
+                Ok(ranges) => ranges
+                    .into_iter()
+                    .map(|(style, piece)| {
+                        let fg = style.foreground;
+                        Span::styled(clean(piece), Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)))
+                    })
+                    .collect(),
+                Err(_) => vec![Span::raw(clean(line))],
+            };
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Drop line endings and expand tabs, which the terminal would not align.
+fn clean(text: &str) -> String {
+    text.trim_end_matches(['\n', '\r']).replace('\t', "    ")
+}
+
+/// Format bytes as "offset  hex bytes  |ascii|" rows of 16.
+pub fn hex_dump(bytes: &[u8]) -> Vec<Line<'static>> {
+    bytes
+        .chunks(HEX_ROW)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let mut hex = String::with_capacity(HEX_ROW * 3 + 1);
+            for i in 0..HEX_ROW {
+                if i == HEX_ROW / 2 {
+                    hex.push(' ');
+                }
+                match chunk.get(i) {
+                    Some(b) => hex.push_str(&format!("{:02x} ", b)),
+                    None => hex.push_str("   "),
+                }
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect();
+            Line::from(vec![
+                Span::styled(format!("{:08x}  ", row * HEX_ROW), Style::default().add_modifier(Modifier::DIM)),
+                Span::raw(hex),
+                Span::styled(format!(" |{}|", ascii), Style::default().add_modifier(Modifier::DIM)),
+            ])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"hello\nworld\n"));
+        assert!(!is_binary("caf\u{e9}".as_bytes()));
+        // A character cut off by the read limit is still text
+        assert!(!is_binary(&"caf\u{e9}".as_bytes()[..4]));
+        assert!(is_binary(b"\x89PNG\r\n\x1a\n\x00\x00"));
+        assert!(is_binary(&[0xff, 0xfe, b'a']));
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let lines = hex_dump(b"Hello, world!\n\x00\x01AB");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            plain(&lines[0]),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|"
+        );
+        assert!(plain(&lines[1]).starts_with("00000010  41 42 "));
+        assert!(plain(&lines[1]).ends_with(" |AB|"));
+    }
+
+    #[test]
+    fn test_highlight_limits_and_cleans_lines() {
+        let text = "a\tb\n".repeat(MAX_LINES + 5);
+        let lines = highlight(Path::new("notes.txt"), &text);
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(plain(&lines[0]), "a    b");
+    }
+}
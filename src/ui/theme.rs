@@ -0,0 +1,306 @@
+//! Color themes for the TUI.
+//!
+//! All render functions take their colors from a [`Theme`] instead of
+//! hardcoding them. A theme is built from one of the built-in palettes
+//! selected by `[theme] name` and then patched with any per-color
+//! overrides from the config.
+
+use std::str::FromStr;
+
+use ratatui::style::Color;
+
+use crate::config::ThemeConfig;
+
+/// Names of the built-in themes, in picker order.
+pub const BUILTIN_THEMES: &[&str] = &["dark", "light", "solarized"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// Primary text
+    pub fg: Color,
+    /// Background fills and text drawn on top of bright highlights
+    pub bg: Color,
+    /// Inactive borders, labels and hints
+    pub muted: Color,
+    /// Secondary text that should stay readable
+    pub secondary: Color,
+    /// Titles, key hints and informational text
+    pub accent: Color,
+    /// Focused dialogs, warnings and highlighted items
+    pub highlight: Color,
+    /// Alternate accent (visual mode, rename, people)
+    pub emphasis: Color,
+    pub success: Color,
+    pub error: Color,
+    /// Border of the focused pane
+    pub border_active: Color,
+    /// Cursor row in the file browser
+    pub selected_fg: Color,
+    pub selected_bg: Color,
+    /// Background of multi-selected entries
+    pub marked_bg: Color,
+    pub status_bar_fg: Color,
+    pub status_bar_bg: Color,
+    /// Section headings inside panes and dialogs
+    pub header_fg: Color,
+    pub directory_color: Color,
+    pub image_color: Color,
+    /// Palette cycled through when coloring tags
+    pub tag_colors: Vec<Color>,
+}
+
+impl Theme {
+    /// The original clepho colors.
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            fg: Color::White,
+            bg: Color::Black,
+            muted: Color::DarkGray,
+            secondary: Color::Gray,
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            emphasis: Color::Magenta,
+            success: Color::Green,
+            error: Color::Red,
+            border_active: Color::Blue,
+            selected_fg: Color::White,
+            selected_bg: Color::Blue,
+            marked_bg: Color::DarkGray,
+            status_bar_fg: Color::White,
+            status_bar_bg: Color::DarkGray,
+            header_fg: Color::Cyan,
+            directory_color: Color::Cyan,
+            image_color: Color::Green,
+            tag_colors: vec![
+                Color::Cyan,
+                Color::Green,
+                Color::Yellow,
+                Color::Magenta,
+                Color::Blue,
+                Color::LightRed,
+            ],
+        }
+    }
+
+    /// For terminals with a light background.
+    pub fn light() -> Self {
+        let dark_green = Color::Rgb(0x00, 0x80, 0x00);
+        let dark_orange = Color::Rgb(0xb0, 0x5a, 0x00);
+        let light_gray = Color::Rgb(0xd8, 0xd8, 0xd8);
+        Self {
+            name: "light".to_string(),
+            fg: Color::Black,
+            bg: Color::White,
+            muted: Color::DarkGray,
+            secondary: Color::DarkGray,
+            accent: Color::Blue,
+            highlight: dark_orange,
+            emphasis: Color::Magenta,
+            success: dark_green,
+            error: Color::Red,
+            border_active: Color::Blue,
+            selected_fg: Color::White,
+            selected_bg: Color::Blue,
+            marked_bg: light_gray,
+            status_bar_fg: Color::Black,
+            status_bar_bg: light_gray,
+            header_fg: Color::Blue,
+            directory_color: Color::Blue,
+            image_color: dark_green,
+            tag_colors: vec![
+                Color::Blue,
+                dark_green,
+                dark_orange,
+                Color::Magenta,
+                Color::Red,
+                Color::Rgb(0x00, 0x80, 0x80),
+            ],
+        }
+    }
+
+    /// Solarized dark palette.
+    pub fn solarized() -> Self {
+        let base03 = Color::Rgb(0x00, 0x2b, 0x36);
+        let base02 = Color::Rgb(0x07, 0x36, 0x42);
+        let base01 = Color::Rgb(0x58, 0x6e, 0x75);
+        let base0 = Color::Rgb(0x83, 0x94, 0x96);
+        let base1 = Color::Rgb(0x93, 0xa1, 0xa1);
+        let base3 = Color::Rgb(0xfd, 0xf6, 0xe3);
+        let yellow = Color::Rgb(0xb5, 0x89, 0x00);
+        let orange = Color::Rgb(0xcb, 0x4b, 0x16);
+        let red = Color::Rgb(0xdc, 0x32, 0x2f);
+        let magenta = Color::Rgb(0xd3, 0x36, 0x82);
+        let violet = Color::Rgb(0x6c, 0x71, 0xc4);
+        let blue = Color::Rgb(0x26, 0x8b, 0xd2);
+        let cyan = Color::Rgb(0x2a, 0xa1, 0x98);
+        let green = Color::Rgb(0x85, 0x99, 0x00);
+        Self {
+            name: "solarized".to_string(),
+            fg: base0,
+            bg: base03,
+            muted: base01,
+            secondary: base1,
+            accent: cyan,
+            highlight: yellow,
+            emphasis: magenta,
+            success: green,
+            error: red,
+            border_active: blue,
+            selected_fg: base3,
+            selected_bg: blue,
+            marked_bg: base02,
+            status_bar_fg: base1,
+            status_bar_bg: base02,
+            header_fg: blue,
+            directory_color: blue,
+            image_color: green,
+            tag_colors: vec![cyan, green, yellow, magenta, violet, orange],
+        }
+    }
+
+    /// Look up a built-in theme by name (case-insensitive).
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            _ => None,
+        }
+    }
+
+    /// Build the theme described by the config: the named built-in theme
+    /// with any color overrides applied. Unknown names fall back to `dark`.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = Self::builtin(&config.name).unwrap_or_else(|| {
+            tracing::warn!(name = %config.name, "Unknown theme, using dark");
+            Self::dark()
+        });
+
+        macro_rules! apply_overrides {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if let Some(color) = config.$field.as_deref().and_then(parse_color) {
+                        theme.$field = color;
+                    }
+                )*
+            };
+        }
+
+        apply_overrides!(
+            fg,
+            bg,
+            muted,
+            secondary,
+            accent,
+            highlight,
+            emphasis,
+            success,
+            error,
+            border_active,
+            selected_fg,
+            selected_bg,
+            marked_bg,
+            status_bar_fg,
+            status_bar_bg,
+            header_fg,
+            directory_color,
+            image_color,
+        );
+
+        let tag_colors: Vec<Color> = config
+            .tag_colors
+            .iter()
+            .filter_map(|s| parse_color(s))
+            .collect();
+        if !tag_colors.is_empty() {
+            theme.tag_colors = tag_colors;
+        }
+
+        theme
+    }
+
+    /// Stable color for a tag, so the same tag always looks the same.
+    pub fn tag_color(&self, tag: &str) -> Color {
+        if self.tag_colors.is_empty() {
+            return self.accent;
+        }
+        // FNV-1a: cheap and stable across runs, unlike the std hasher
+        let hash = tag
+            .to_lowercase()
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x100000001b3)
+            });
+        self.tag_colors[(hash % self.tag_colors.len() as u64) as usize]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    match Color::from_str(value) {
+        Ok(color) => Some(color),
+        Err(_) => {
+            tracing::warn!(value, "Invalid theme color");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes() {
+        for name in BUILTIN_THEMES {
+            let theme = Theme::builtin(name).unwrap();
+            assert_eq!(theme.name, *name);
+        }
+        assert_eq!(Theme::builtin("Solarized").unwrap().name, "solarized");
+        assert!(Theme::builtin("neon").is_none());
+    }
+
+    #[test]
+    fn test_from_config_overrides() {
+        let config = ThemeConfig {
+            name: "light".to_string(),
+            selected_bg: Some("#ff8800".to_string()),
+            header_fg: Some("magenta".to_string()),
+            image_color: Some("not-a-color".to_string()),
+            tag_colors: vec!["red".to_string(), "green".to_string()],
+            ..Default::default()
+        };
+
+        let theme = Theme::from_config(&config);
+        assert_eq!(theme.name, "light");
+        assert_eq!(theme.selected_bg, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.header_fg, Color::Magenta);
+        // Invalid colors keep the theme's value
+        assert_eq!(theme.image_color, Theme::light().image_color);
+        assert_eq!(theme.tag_colors, vec![Color::Red, Color::Green]);
+    }
+
+    #[test]
+    fn test_unknown_name_falls_back_to_dark() {
+        let config = ThemeConfig {
+            name: "neon".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(Theme::from_config(&config), Theme::dark());
+    }
+
+    #[test]
+    fn test_tag_color_is_stable() {
+        let theme = Theme::dark();
+        assert_eq!(theme.tag_color("beach"), theme.tag_color("beach"));
+        assert_eq!(theme.tag_color("Beach"), theme.tag_color("beach"));
+        assert!(theme.tag_colors.contains(&theme.tag_color("sunset")));
+    }
+}
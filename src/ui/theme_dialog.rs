@@ -0,0 +1,96 @@
+//! Theme picker dialog.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::ui::theme::{Theme, BUILTIN_THEMES};
+
+/// State for the theme picker dialog.
+pub struct ThemeDialog {
+    /// Selected index into `BUILTIN_THEMES`.
+    pub selected_index: usize,
+    /// Theme name active when the dialog was opened (restored on cancel).
+    pub original: String,
+}
+
+impl ThemeDialog {
+    pub fn new(current: &str) -> Self {
+        let selected_index = BUILTIN_THEMES
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(current))
+            .unwrap_or(0);
+        Self {
+            selected_index,
+            original: current.to_string(),
+        }
+    }
+
+    /// Move selection down.
+    pub fn move_down(&mut self) {
+        if self.selected_index < BUILTIN_THEMES.len() - 1 {
+            self.selected_index += 1;
+        }
+    }
+
+    /// Move selection up.
+    pub fn move_up(&mut self) {
+        if self.selected_index > 0 {
+            self.selected_index -= 1;
+        }
+    }
+
+    /// Name of the highlighted theme.
+    pub fn selected_name(&self) -> &'static str {
+        BUILTIN_THEMES[self.selected_index]
+    }
+}
+
+pub fn render(frame: &mut Frame, dialog: &ThemeDialog, area: Rect, theme: &Theme) {
+    let dialog_width = 30.min(area.width.saturating_sub(4));
+    let dialog_height = (BUILTIN_THEMES.len() as u16 + 5).min(area.height.saturating_sub(4));
+
+    let x = (area.width - dialog_width) / 2;
+    let y = (area.height - dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .title(" Theme ");
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+
+    let items: Vec<ListItem> = BUILTIN_THEMES
+        .iter()
+        .map(|name| {
+            let marker = if *name == dialog.original { "*" } else { " " };
+            ListItem::new(format!(" {} {}", marker, name))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(theme.selected_bg)
+            .fg(theme.selected_fg)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(dialog.selected_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let help = Paragraph::new(" j/k=preview  Enter=apply  Esc=cancel")
+        .style(Style::default().fg(theme.muted))
+        .block(Block::default().borders(Borders::TOP));
+    frame.render_widget(help, chunks[1]);
+}
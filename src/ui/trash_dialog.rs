@@ -4,6 +4,7 @@ use ratatui::{
 };
 
 use crate::db::trash::TrashedPhoto;
+use crate::ui::theme::Theme;
 
 /// State for the trash viewing dialog
 pub struct TrashDialog {
@@ -53,7 +54,7 @@ impl TrashDialog {
     }
 }
 
-pub fn render(frame: &mut Frame, dialog: &TrashDialog, area: Rect) {
+pub fn render(frame: &mut Frame, dialog: &TrashDialog, area: Rect, theme: &Theme) {
     // Center the dialog
     let dialog_width = 80.min(area.width.saturating_sub(4));
     let dialog_height = 24.min(area.height.saturating_sub(4));
@@ -94,11 +95,11 @@ pub fn render(frame: &mut Frame, dialog: &TrashDialog, area: Rect) {
     );
 
     let header = Paragraph::new(header_text)
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(theme.highlight))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
+                .border_style(Style::default().fg(theme.highlight))
                 .title(" Trash "),
         );
     frame.render_widget(header, chunks[0]);
@@ -106,7 +107,7 @@ pub fn render(frame: &mut Frame, dialog: &TrashDialog, area: Rect) {
     // File list
     if dialog.entries.is_empty() {
         let empty_msg = Paragraph::new("  Trash is empty")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(empty_msg, chunks[1]);
     } else {
@@ -120,7 +121,7 @@ pub fn render(frame: &mut Frame, dialog: &TrashDialog, area: Rect) {
                 let date = format_date(&entry.trashed_at);
 
                 let style = if i == dialog.selected_index {
-                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
                 };
@@ -148,13 +149,13 @@ pub fn render(frame: &mut Frame, dialog: &TrashDialog, area: Rect) {
     let help_text = vec![
         Line::from(Span::styled(
             "  j/k=Navigate  Enter/r=Restore  d=Delete permanently  c=Cleanup old  q=Close",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
         Line::from(""),
         if let Some(entry) = dialog.selected_entry() {
             Line::from(Span::styled(
                 format!("  Original: {}", entry.original_path),
-                Style::default().fg(Color::Blue),
+                Style::default().fg(theme.border_active),
             ))
         } else {
             Line::from("")
@@ -0,0 +1,75 @@
+//! Progress dialog shown while a directory's missing thumbnails are
+//! generated, before the gallery opens.
+
+use std::path::PathBuf;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+};
+
+use crate::tasks::{TaskId, TaskProgress};
+use crate::ui::theme::Theme;
+
+/// A thumbnail warm-up the gallery is waiting for
+pub struct WarmupDialog {
+    pub task_id: TaskId,
+    /// Directory the gallery opens on once the warm-up finishes
+    pub directory: PathBuf,
+    /// Thumbnails that were missing when the warm-up started
+    pub total: usize,
+}
+
+pub fn render(frame: &mut Frame, dialog: &WarmupDialog, progress: Option<&TaskProgress>, area: Rect, theme: &Theme) {
+    let dialog_width = 60.min(area.width.saturating_sub(4));
+    let dialog_height = 9.min(area.height);
+
+    let x = area.width.saturating_sub(dialog_width) / 2;
+    let y = area.height.saturating_sub(dialog_height) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .title(" Gallery ");
+    frame.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Message
+            Constraint::Length(1), // Gauge
+            Constraint::Length(1), // Current file
+            Constraint::Min(0),
+            Constraint::Length(1), // Button
+        ])
+        .margin(1)
+        .split(dialog_area);
+
+    let current = progress.map(|p| p.current).unwrap_or(0);
+    let message = Paragraph::new(format!("Generating thumbnails {}/{}…", current, dialog.total))
+        .alignment(Alignment::Center);
+    frame.render_widget(message, chunks[0]);
+
+    let ratio = if dialog.total > 0 { current as f64 / dialog.total as f64 } else { 0.0 };
+    let gauge = Gauge::default()
+        .ratio(ratio.min(1.0))
+        .gauge_style(Style::default().fg(theme.success).bg(theme.muted));
+    frame.render_widget(gauge, chunks[1]);
+
+    if let Some(item) = progress.and_then(|p| p.current_item.as_deref()) {
+        let item = Paragraph::new(item)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(item, chunks[2]);
+    }
+
+    let button = Line::from(vec![
+        Span::styled("[Esc] ", Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+        Span::raw("Cancel"),
+    ]);
+    frame.render_widget(Paragraph::new(button).alignment(Alignment::Center), chunks[4]);
+}
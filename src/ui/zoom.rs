@@ -0,0 +1,166 @@
+//! Zooming into the centre of the displayed image.
+
+use image::DynamicImage;
+use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
+
+/// Largest zoom factor; beyond this the preview is mostly interpolation.
+pub const MAX_ZOOM: f32 = 8.0;
+
+/// Factor applied per zoom step (one scroll notch).
+const ZOOM_STEP: f32 = 1.25;
+
+/// Zoom level of the image currently shown, and its zoomed rendering.
+pub struct Zoom {
+    /// Image the zoom applies to; switching images resets the zoom
+    key: Option<String>,
+    /// Zoom factor, 1.0 shows the whole image
+    level: f32,
+    /// Decoded image the zoomed view is cropped from
+    source: Option<DynamicImage>,
+    /// Encoded zoomed view and the level it was made for
+    view: Option<(f32, StatefulProtocol)>,
+}
+
+impl Default for Zoom {
+    fn default() -> Self {
+        Self {
+            key: None,
+            level: 1.0,
+            source: None,
+            view: None,
+        }
+    }
+}
+
+impl Zoom {
+    /// Current zoom factor.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    pub fn is_zoomed(&self) -> bool {
+        self.level() > 1.0
+    }
+
+    /// Track the image being displayed, resetting the zoom when it changes.
+    pub fn follow(&mut self, key: &str) {
+        if self.key.as_deref() != Some(key) {
+            *self = Self {
+                key: Some(key.to_string()),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Keep a decoded image if it is the one being displayed.
+    pub fn offer_source(&mut self, key: &str, image: &DynamicImage) {
+        if self.key.as_deref() == Some(key) {
+            self.source = Some(image.clone());
+            self.view = None;
+        }
+    }
+
+    /// Zoomed in, but the decoded image has to be loaded again to crop it.
+    pub fn needs_source(&self) -> bool {
+        self.is_zoomed() && self.source.is_none()
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.level = step(self.level, ZOOM_STEP);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.level = step(self.level, 1.0 / ZOOM_STEP);
+    }
+
+    /// Protocol for the zoomed view, re-encoded when the level changed.
+    /// None when not zoomed or the source is not available yet.
+    pub fn view(&mut self, picker: &mut Picker) -> Option<&mut StatefulProtocol> {
+        let level = self.level();
+        if level <= 1.0 {
+            return None;
+        }
+        let source = self.source.as_ref()?;
+        if self.view.as_ref().map(|(l, _)| *l) != Some(level) {
+            let protocol = picker.new_resize_protocol(crop_center(source, level));
+            self.view = Some((level, protocol));
+        }
+        self.view.as_mut().map(|(_, protocol)| protocol)
+    }
+
+    /// Label for a title or status bar, e.g. "2.0×".
+    pub fn label(&self) -> Option<String> {
+        self.is_zoomed().then(|| format!("{:.1}×", self.level()))
+    }
+}
+
+/// Multiply a zoom level, snapping back to exactly 1.0 near the bottom so
+/// repeated steps in and out land on the unzoomed image.
+fn step(level: f32, factor: f32) -> f32 {
+    let next = (level * factor).clamp(1.0, MAX_ZOOM);
+    if (next - 1.0).abs() < 0.01 {
+        1.0
+    } else {
+        next
+    }
+}
+
+/// The centre `1/level` of an image in each dimension.
+pub fn crop_center(image: &DynamicImage, level: f32) -> DynamicImage {
+    let level = level.max(1.0);
+    let width = ((image.width() as f32 / level).round() as u32).max(1);
+    let height = ((image.height() as f32 / level).round() as u32).max(1);
+    let x = (image.width() - width) / 2;
+    let y = (image.height() - height) / 2;
+    image.crop_imm(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_step_clamps_and_snaps() {
+        let mut level = 1.0;
+        for _ in 0..20 {
+            level = step(level, ZOOM_STEP);
+        }
+        assert_eq!(level, MAX_ZOOM);
+
+        let mut level = step(1.0, ZOOM_STEP);
+        assert!(level > 1.0);
+        level = step(level, 1.0 / ZOOM_STEP);
+        assert_eq!(level, 1.0);
+        assert_eq!(step(1.0, 1.0 / ZOOM_STEP), 1.0);
+    }
+
+    #[test]
+    fn test_crop_center() {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_fn(100, 60, |x, y| {
+            Rgb([x as u8, y as u8, 0])
+        }));
+        let cropped = crop_center(&image, 2.0);
+        assert_eq!((cropped.width(), cropped.height()), (50, 30));
+        // Top-left of the crop is a quarter of the way in
+        assert_eq!(cropped.to_rgb8().get_pixel(0, 0).0, [25, 15, 0]);
+
+        let same = crop_center(&image, 1.0);
+        assert_eq!((same.width(), same.height()), (100, 60));
+    }
+
+    #[test]
+    fn test_follow_resets_zoom() {
+        let mut zoom = Zoom::default();
+        zoom.follow("a.jpg");
+        zoom.zoom_in();
+        assert!(zoom.is_zoomed());
+        assert!(zoom.needs_source());
+
+        zoom.follow("a.jpg");
+        assert!(zoom.is_zoomed());
+        zoom.follow("b.jpg");
+        assert!(!zoom.is_zoomed());
+        assert_eq!(zoom.label(), None);
+    }
+}
@@ -0,0 +1,149 @@
+//! Watching the browsed directory for files that appear, change or disappear.
+
+use anyhow::{Context, Result};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// How long the directory must stay quiet before a batch of changes is applied.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// What happened to a file since watching started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchChange {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// Combine an earlier change to a file with a later one. `None` means the
+/// file is back to how it was before watching started.
+pub fn merge_change(previous: Option<WatchChange>, next: WatchChange) -> Option<WatchChange> {
+    match (previous, next) {
+        // A file created and deleted while watching never existed as far as we care
+        (Some(WatchChange::Created), WatchChange::Removed) => None,
+        // Writing to a new file keeps it new
+        (Some(WatchChange::Created), WatchChange::Modified) => Some(WatchChange::Created),
+        // A file replaced under the same name has changed
+        (Some(WatchChange::Removed), WatchChange::Created) => Some(WatchChange::Modified),
+        (_, next) => Some(next),
+    }
+}
+
+/// Non-recursive watcher on a single directory with debounced change batches.
+pub struct DirWatcher {
+    // Dropping the watcher stops the notifications
+    _watcher: RecommendedWatcher,
+    rx: Receiver<notify::Result<Event>>,
+    pub dir: PathBuf,
+    pending: HashMap<PathBuf, WatchChange>,
+    last_event: Option<Instant>,
+}
+
+impl DirWatcher {
+    pub fn new(dir: &Path) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx).context("Failed to create file watcher")?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+            dir: dir.to_path_buf(),
+            pending: HashMap::new(),
+            last_event: None,
+        })
+    }
+
+    /// Collect pending notifications and return them as one batch once the
+    /// directory has been quiet for the debounce interval.
+    pub fn poll(&mut self) -> Option<HashMap<PathBuf, WatchChange>> {
+        while let Ok(event) = self.rx.try_recv() {
+            let Ok(event) = event else { continue };
+            for (path, change) in classify(&event) {
+                let previous = self.pending.get(&path).copied();
+                match merge_change(previous, change) {
+                    Some(change) => self.pending.insert(path, change),
+                    None => self.pending.remove(&path),
+                };
+            }
+            self.last_event = Some(Instant::now());
+        }
+
+        let quiet = self.last_event.is_some_and(|t| t.elapsed() >= WATCH_DEBOUNCE);
+        if !quiet {
+            return None;
+        }
+        self.last_event = None;
+        if self.pending.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.pending))
+        }
+    }
+}
+
+/// Changes described by one notification
+fn classify(event: &Event) -> Vec<(PathBuf, WatchChange)> {
+    let all = |change: WatchChange| event.paths.iter().map(|p| (p.clone(), change)).collect();
+
+    match event.kind {
+        EventKind::Create(_) => all(WatchChange::Created),
+        EventKind::Remove(_) => all(WatchChange::Removed),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => all(WatchChange::Removed),
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => all(WatchChange::Created),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => vec![
+            (event.paths[0].clone(), WatchChange::Removed),
+            (event.paths[1].clone(), WatchChange::Created),
+        ],
+        // The backend could not tell which side of the rename this is
+        EventKind::Modify(ModifyKind::Name(_)) => event
+            .paths
+            .iter()
+            .map(|p| {
+                let change = if p.exists() { WatchChange::Created } else { WatchChange::Removed };
+                (p.clone(), change)
+            })
+            .collect(),
+        // Permission and timestamp updates do not change the photo
+        EventKind::Modify(ModifyKind::Metadata(_)) => Vec::new(),
+        EventKind::Modify(_) => all(WatchChange::Modified),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merges_successive_changes() {
+        use WatchChange::*;
+
+        assert_eq!(merge_change(None, Created), Some(Created));
+        assert_eq!(merge_change(Some(Created), Modified), Some(Created));
+        assert_eq!(merge_change(Some(Created), Removed), None);
+        assert_eq!(merge_change(Some(Removed), Created), Some(Modified));
+        assert_eq!(merge_change(Some(Modified), Removed), Some(Removed));
+    }
+
+    #[test]
+    fn test_renames_split_into_remove_and_create() {
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/photos/a.jpg"))
+            .add_path(PathBuf::from("/photos/b.jpg"));
+
+        assert_eq!(
+            classify(&event),
+            vec![
+                (PathBuf::from("/photos/a.jpg"), WatchChange::Removed),
+                (PathBuf::from("/photos/b.jpg"), WatchChange::Created),
+            ]
+        );
+    }
+}